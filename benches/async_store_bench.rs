@@ -0,0 +1,159 @@
+// Benchmark: memory store throughput under concurrent load
+// Compares the blocking `Arc<Mutex<MemoryStore>>` pattern the servers used
+// with `AsyncMemoryStore` over a sync and an async file backend. Each task
+// runs one add and four searches per round on a multi-threaded runtime.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hipcortex::async_memory_store::AsyncMemoryStore;
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::{AsyncFileBackend, FileBackend, MemoryBackend};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const SEEDED: usize = 2_000;
+const SEARCHES_PER_ADD: usize = 4;
+
+fn record(i: usize) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        format!("agent_{}", i % 16),
+        format!("action_{}", i % 32),
+        format!("observed event {} near zone {}", i, i % 50),
+        json!({ "idx": i }),
+    )
+}
+
+fn seeded_store(dir: &std::path::Path) -> MemoryStore<FileBackend> {
+    let mut store = MemoryStore::new(dir.join("mutex.jsonl")).unwrap();
+    for i in 0..SEEDED {
+        store.add(record(i)).unwrap();
+    }
+    store.flush().unwrap();
+    store
+}
+
+async fn seeded_async<B: MemoryBackend + Send + 'static>(store: &AsyncMemoryStore<B>) {
+    store
+        .add_batch((0..SEEDED).map(record).collect())
+        .await
+        .unwrap();
+}
+
+async fn run_mutex(store: Arc<Mutex<MemoryStore<FileBackend>>>, tasks: usize) {
+    let handles: Vec<_> = (0..tasks)
+        .map(|t| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store.lock().unwrap().add(record(t)).unwrap();
+                for _ in 0..SEARCHES_PER_ADD {
+                    let ms = store.lock().unwrap();
+                    std::hint::black_box(ms.search_semantic(None, "event zone", 10, false).len());
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.await.unwrap();
+    }
+}
+
+async fn run_async<B: MemoryBackend + Send + 'static>(store: AsyncMemoryStore<B>, tasks: usize) {
+    let handles: Vec<_> = (0..tasks)
+        .map(|t| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store.add(record(t)).await.unwrap();
+                for _ in 0..SEARCHES_PER_ADD {
+                    std::hint::black_box(store.search("event zone", 10).await.unwrap().len());
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.await.unwrap();
+    }
+}
+
+fn bench_concurrent_throughput(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let mutex_store = Arc::new(Mutex::new(seeded_store(dir.path())));
+    let sync_backed = rt.block_on(async {
+        let store = AsyncMemoryStore::from(Arc::new(ConcurrentMemoryStore::new(
+            MemoryStore::new(dir.path().join("sync.jsonl")).unwrap(),
+        )));
+        seeded_async(&store).await;
+        store
+    });
+    let async_backed = rt.block_on(async {
+        let backend = AsyncFileBackend::new(dir.path().join("async.jsonl"), false)
+            .await
+            .unwrap();
+        let store = AsyncMemoryStore::new(backend, &dir.path().join("async.audit"), 8)
+            .await
+            .unwrap();
+        seeded_async(&store).await;
+        store
+    });
+
+    let mut group = c.benchmark_group("concurrent_store_throughput");
+    group.sample_size(10);
+    for tasks in [8usize, 64] {
+        group.throughput(Throughput::Elements(
+            (tasks * (1 + SEARCHES_PER_ADD)) as u64,
+        ));
+        group.bench_with_input(BenchmarkId::new("mutex_store", tasks), &tasks, |b, &n| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let started = Instant::now();
+                    rt.block_on(run_mutex(mutex_store.clone(), n));
+                    total += started.elapsed();
+                }
+                total
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("async_file_backend", tasks),
+            &tasks,
+            |b, &n| {
+                b.iter_custom(|iters| {
+                    let mut total = Duration::ZERO;
+                    for _ in 0..iters {
+                        let started = Instant::now();
+                        rt.block_on(run_async(async_backed.clone(), n));
+                        total += started.elapsed();
+                    }
+                    total
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("async_sync_backend", tasks),
+            &tasks,
+            |b, &n| {
+                b.iter_custom(|iters| {
+                    let mut total = Duration::ZERO;
+                    for _ in 0..iters {
+                        let started = Instant::now();
+                        rt.block_on(run_async(sync_backed.clone(), n));
+                        total += started.elapsed();
+                    }
+                    total
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_throughput);
+criterion_main!(benches);
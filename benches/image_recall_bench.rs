@@ -0,0 +1,143 @@
+// Benchmark: image retrieval recall and encode throughput
+// Compares the built-in grid-RGB encoder with a learned ONNX image model.
+// A gallery of synthetic scenes is queried with augmented copies (crop,
+// flip, brightness); recall@k counts queries whose source scene ranks in the
+// top k. The ONNX run needs `--features onnx` and HIPCORTEX_ONNX_IMAGE_MODEL
+// pointing at an image tower taking [N, 3, 224, 224] pixels, e.g.
+//   HIPCORTEX_ONNX_IMAGE_MODEL=clip-vit-b32-visual.onnx \
+//     cargo bench --features onnx --bench image_recall_bench
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hipcortex::vision_encoder::VisionEncoder;
+use image::{DynamicImage, Rgb, RgbImage};
+
+const SCENES: usize = 64;
+const SIDE: u32 = 96;
+
+/// Small deterministic generator so runs are comparable.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as u32
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        self.next() % n.max(1)
+    }
+}
+
+fn scene(rng: &mut Lcg) -> DynamicImage {
+    let bg = Rgb([
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+    ]);
+    let mut img = RgbImage::from_pixel(SIDE, SIDE, bg);
+    for _ in 0..4 {
+        let color = Rgb([
+            rng.below(256) as u8,
+            rng.below(256) as u8,
+            rng.below(256) as u8,
+        ]);
+        let (x0, y0) = (rng.below(SIDE - 16), rng.below(SIDE - 16));
+        let (w, h) = (8 + rng.below(SIDE / 2), 8 + rng.below(SIDE / 2));
+        for y in y0..(y0 + h).min(SIDE) {
+            for x in x0..(x0 + w).min(SIDE) {
+                img.put_pixel(x, y, color);
+            }
+        }
+    }
+    DynamicImage::ImageRgb8(img)
+}
+
+fn augment(img: &DynamicImage, rng: &mut Lcg) -> DynamicImage {
+    let crop = SIDE * 3 / 4 + rng.below(SIDE / 4);
+    let (x, y) = (rng.below(SIDE - crop + 1), rng.below(SIDE - crop + 1));
+    let mut out = img.crop_imm(x, y, crop, crop).resize_exact(
+        SIDE,
+        SIDE,
+        image::imageops::FilterType::Triangle,
+    );
+    if rng.below(2) == 1 {
+        out = out.fliph();
+    }
+    out.brighten(rng.below(41) as i32 - 20)
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let na = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let nb = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if na == 0.0 || nb == 0.0 {
+        0.0
+    } else {
+        dot / (na * nb)
+    }
+}
+
+/// (recall@1, recall@5) of query i retrieving gallery item i.
+fn recall(gallery: &[Vec<f32>], queries: &[Vec<f32>]) -> (f64, f64) {
+    let mut hits = (0usize, 0usize);
+    for (i, q) in queries.iter().enumerate() {
+        let target = cosine(q, &gallery[i]);
+        let rank = gallery
+            .iter()
+            .enumerate()
+            .filter(|(j, g)| *j != i && cosine(q, g) > target)
+            .count();
+        hits.0 += (rank < 1) as usize;
+        hits.1 += (rank < 5) as usize;
+    }
+    let n = queries.len() as f64;
+    (hits.0 as f64 / n, hits.1 as f64 / n)
+}
+
+fn dataset() -> (Vec<DynamicImage>, Vec<DynamicImage>) {
+    let mut rng = Lcg(0x5eed);
+    let gallery: Vec<DynamicImage> = (0..SCENES).map(|_| scene(&mut rng)).collect();
+    let queries = gallery.iter().map(|g| augment(g, &mut rng)).collect();
+    (gallery, queries)
+}
+
+fn grid(img: &DynamicImage) -> Vec<f32> {
+    VisionEncoder::encode_image_grid(img, 4)
+}
+
+fn bench_image_recall(c: &mut Criterion) {
+    let (gallery, queries) = dataset();
+    let (r1, r5) = recall(
+        &gallery.iter().map(grid).collect::<Vec<_>>(),
+        &queries.iter().map(grid).collect::<Vec<_>>(),
+    );
+    eprintln!("[image recall] grid-rgb  recall@1 {r1:.3}  recall@5 {r5:.3}");
+
+    let mut group = c.benchmark_group("image_encode");
+    group.bench_function("grid_rgb_64", |b| {
+        b.iter(|| gallery.iter().map(grid).collect::<Vec<_>>())
+    });
+
+    #[cfg(feature = "onnx")]
+    if let Ok(path) = std::env::var("HIPCORTEX_ONNX_IMAGE_MODEL") {
+        use hipcortex::vision_encoder::{ImagePreprocess, OnnxImageEncoder};
+        let encoder = OnnxImageEncoder::load(&path, ImagePreprocess::clip(), "bench")
+            .expect("load HIPCORTEX_ONNX_IMAGE_MODEL");
+        let (r1, r5) = recall(
+            &encoder.encode_images(&gallery).unwrap(),
+            &encoder.encode_images(&queries).unwrap(),
+        );
+        eprintln!("[image recall] onnx      recall@1 {r1:.3}  recall@5 {r5:.3}");
+        group.sample_size(10);
+        group.bench_function("onnx_64", |b| {
+            b.iter(|| encoder.encode_images(&gallery).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_image_recall);
+criterion_main!(benches);
@@ -18,6 +18,7 @@ fn main() {
         intervention_var: "treatment".to_string(),
         intervention_value: 1.0,
         conditioned_on: std::collections::HashMap::new(),
+        intervention_label: None,
         outcome: "outcome".to_string(),
     };
     if let Ok(dist) = wm.causal_intervention(query) {
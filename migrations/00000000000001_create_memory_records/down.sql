@@ -0,0 +1 @@
+DROP TABLE memory_records;
@@ -0,0 +1,19 @@
+-- Full MemoryRecords for the SQLite MemoryBackend. `body` is the record as
+-- JSON; the other columns duplicate the fields queries filter on.
+CREATE TABLE IF NOT EXISTS memory_records (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    id TEXT NOT NULL UNIQUE,
+    record_type TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    target TEXT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    body TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_memory_records_actor ON memory_records(actor);
+CREATE INDEX IF NOT EXISTS idx_memory_records_action ON memory_records(action);
+CREATE INDEX IF NOT EXISTS idx_memory_records_target ON memory_records(target);
+CREATE INDEX IF NOT EXISTS idx_memory_records_type ON memory_records(record_type);
+CREATE INDEX IF NOT EXISTS idx_memory_records_ts ON memory_records(timestamp);
+CREATE INDEX IF NOT EXISTS idx_memory_records_status ON memory_records(status);
@@ -1,64 +1,351 @@
-#[cfg(feature = "async-store")]
+//! AsyncMemoryStore — future-returning API over the shared memory store.
+//!
+//! Chain-of-thought: the axum handlers ran `MemoryStore` writes, and the
+//! fsyncs behind them, directly on runtime workers, so one slow flush stalled
+//! every request scheduled on that worker. A second store implementation
+//! would duplicate indexing, search, audit and journal semantics, so instead
+//! the async API drives the existing `MemoryStore` through
+//! `ConcurrentMemoryStore`: reads query the published snapshot, writes run on
+//! tokio's blocking pool, and every write future resolves once the change is
+//! flushed. `AsyncMemoryBackend`s plug in underneath through `AsyncBridge`, a
+//! `MemoryBackend` that forwards each backend call to a dedicated I/O thread
+//! running the async backend, so one store persists to either kind.
+
 use crate::audit_log::AuditLog;
-#[cfg(feature = "async-store")]
+use crate::concurrent_memory_store::{ConcurrentMemoryStore, MemorySnapshot};
+use crate::embedding_provider::AsyncEmbeddingProvider;
 use crate::memory_record::MemoryRecord;
-#[cfg(feature = "async-store")]
-use crate::persistence::AsyncMemoryBackend;
-#[cfg(feature = "async-store")]
-use anyhow::Result;
-#[cfg(feature = "async-store")]
-use std::collections::VecDeque;
-#[cfg(feature = "async-store")]
+use crate::memory_store::MemoryStore;
+pub use crate::memory_store::RecordPatch;
+use crate::persistence::{AsyncMemoryBackend, MemoryBackend};
+use crate::safety_guardrail::ScreenOutcome;
+use anyhow::{anyhow, Result};
 use std::path::Path;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use uuid::Uuid;
 
-#[cfg(feature = "async-store")]
-pub struct AsyncMemoryStore<B: AsyncMemoryBackend> {
-    backend: B,
-    records: Vec<MemoryRecord>,
-    audit: AuditLog,
-    buffer: VecDeque<MemoryRecord>,
-    batch_size: usize,
+enum BridgeOp {
+    Load(SyncSender<Result<Vec<MemoryRecord>>>),
+    Append(Box<MemoryRecord>),
+    Clear,
+    Flush(SyncSender<Result<()>>),
+    Update(Box<MemoryRecord>, SyncSender<Result<bool>>),
+    Delete(Vec<Uuid>, SyncSender<Result<bool>>),
 }
 
-#[cfg(feature = "async-store")]
-impl<B: AsyncMemoryBackend> AsyncMemoryStore<B> {
-    pub async fn new(mut backend: B, audit_path: &Path, batch_size: usize) -> Result<Self> {
-        let mut store = Self {
-            backend,
-            records: Vec::new(),
-            audit: AuditLog::new(audit_path)?,
-            buffer: VecDeque::new(),
-            batch_size,
-        };
-        store.load().await?;
-        Ok(store)
+/// `MemoryBackend` that runs an `AsyncMemoryBackend` on its own I/O thread.
+///
+/// Appends and clears are queued in order; `load`, `flush`, `update` and
+/// `delete` block until the thread answers, and `flush` also reports the
+/// first queued write that failed since the previous flush. Dropping the
+/// bridge drains the queue, flushes and joins the thread, so the drop blocks
+/// until pending writes are on disk. Inside a multi-threaded tokio runtime the
+/// join runs under `block_in_place`, handing the worker's other tasks off;
+/// on a current-thread runtime drop the store from `spawn_blocking` instead.
+pub struct AsyncBridge {
+    ops: Option<UnboundedSender<BridgeOp>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AsyncBridge {
+    pub fn spawn<A: AsyncMemoryBackend + Send + 'static>(backend: A) -> Result<Self> {
+        let (tx, rx) = unbounded_channel();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let worker = std::thread::Builder::new()
+            .name("hipcortex-async-backend".into())
+            .spawn(move || runtime.block_on(drive(backend, rx)))?;
+        Ok(Self {
+            ops: Some(tx),
+            worker: Some(worker),
+        })
     }
 
-    async fn load(&mut self) -> Result<()> {
-        self.records = self.backend.load().await?;
-        Ok(())
+    fn send(&self, op: BridgeOp) -> Result<()> {
+        self.ops
+            .as_ref()
+            .and_then(|tx| tx.send(op).ok())
+            .ok_or_else(|| anyhow!("async backend thread has stopped"))
     }
 
-    pub fn all(&self) -> &[MemoryRecord] {
-        &self.records
+    fn ask<T>(&self, op: impl FnOnce(SyncSender<Result<T>>) -> BridgeOp) -> Result<T> {
+        let (reply, answer) = sync_channel(1);
+        self.send(op(reply))?;
+        answer
+            .recv()
+            .map_err(|_| anyhow!("async backend thread has stopped"))?
     }
+}
 
-    pub async fn add(&mut self, record: MemoryRecord) -> Result<()> {
-        self.records.push(record.clone());
-        self.buffer.push_back(record.clone());
-        self.audit
-            .append(&record.actor, &record.action, &record.target)?;
-        if self.buffer.len() >= self.batch_size {
-            self.flush().await?;
+async fn drive<A: AsyncMemoryBackend + Send>(mut backend: A, mut ops: UnboundedReceiver<BridgeOp>) {
+    let mut failed: Option<anyhow::Error> = None;
+    while let Some(op) = ops.recv().await {
+        match op {
+            BridgeOp::Load(reply) => {
+                let _ = reply.send(backend.load().await);
+            }
+            BridgeOp::Append(record) => {
+                if let Err(e) = backend.append(&record).await {
+                    failed.get_or_insert(e);
+                }
+            }
+            BridgeOp::Clear => {
+                if let Err(e) = backend.clear().await {
+                    failed.get_or_insert(e);
+                }
+            }
+            BridgeOp::Flush(reply) => {
+                let flushed = backend.flush().await;
+                let _ = reply.send(match failed.take() {
+                    Some(e) => Err(e),
+                    None => flushed,
+                });
+            }
+            BridgeOp::Update(record, reply) => {
+                let _ = reply.send(backend.update(&record).await);
+            }
+            BridgeOp::Delete(ids, reply) => {
+                let _ = reply.send(backend.delete(&ids).await);
+            }
         }
-        Ok(())
+    }
+    let _ = backend.flush().await;
+}
+
+impl MemoryBackend for AsyncBridge {
+    fn load(&mut self) -> Result<Vec<MemoryRecord>> {
+        self.ask(BridgeOp::Load)
+    }
+
+    fn append(&mut self, record: &MemoryRecord) -> Result<()> {
+        self.send(BridgeOp::Append(Box::new(record.clone())))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.ask(BridgeOp::Flush)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.send(BridgeOp::Clear)
+    }
+
+    fn update(&mut self, record: &MemoryRecord) -> Result<bool> {
+        let record = Box::new(record.clone());
+        self.ask(|reply| BridgeOp::Update(record, reply))
     }
 
-    pub async fn flush(&mut self) -> Result<()> {
-        while let Some(rec) = self.buffer.pop_front() {
-            self.backend.append(&rec).await?;
+    fn delete(&mut self, ids: &[Uuid]) -> Result<bool> {
+        let ids = ids.to_vec();
+        self.ask(|reply| BridgeOp::Delete(ids, reply))
+    }
+}
+
+impl Drop for AsyncBridge {
+    fn drop(&mut self) {
+        self.ops.take();
+        if let Some(worker) = self.worker.take() {
+            let on_worker = tokio::runtime::Handle::try_current()
+                .is_ok_and(|h| h.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread);
+            if on_worker {
+                let _ = tokio::task::block_in_place(|| worker.join());
+            } else {
+                let _ = worker.join();
+            }
         }
-        self.backend.flush().await?;
-        Ok(())
+    }
+}
+
+/// Cloneable async handle to a `ConcurrentMemoryStore`.
+///
+/// Write futures resolve after the store has been flushed, so a returned
+/// `Ok` means the change reached the backend. Reads never wait for writers.
+pub struct AsyncMemoryStore<B: MemoryBackend> {
+    shared: Arc<ConcurrentMemoryStore<B>>,
+    embedder: Option<Arc<dyn AsyncEmbeddingProvider>>,
+}
+
+impl<B: MemoryBackend> Clone for AsyncMemoryStore<B> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            embedder: self.embedder.clone(),
+        }
+    }
+}
+
+impl<B: MemoryBackend> From<Arc<ConcurrentMemoryStore<B>>> for AsyncMemoryStore<B> {
+    fn from(shared: Arc<ConcurrentMemoryStore<B>>) -> Self {
+        Self {
+            shared,
+            embedder: None,
+        }
+    }
+}
+
+impl AsyncMemoryStore<AsyncBridge> {
+    /// Open a store persisted by `backend`, writing its audit trail to
+    /// `audit_path`. `batch_size` applies to writes made through `shared()`.
+    pub async fn new<A: AsyncMemoryBackend + Send + 'static>(
+        backend: A,
+        audit_path: &Path,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let bridge = AsyncBridge::spawn(backend)?;
+        let audit = AuditLog::new(audit_path)?;
+        let store = tokio::task::spawn_blocking(move || {
+            MemoryStore::with_backend(bridge, audit, batch_size)
+        })
+        .await??;
+        Ok(Self::from(Arc::new(ConcurrentMemoryStore::new(store))))
+    }
+}
+
+impl<B: MemoryBackend + Send + 'static> AsyncMemoryStore<B> {
+    /// Embed each added record's `target` (into `metadata.embedding`) and
+    /// each search query with `embedder`.
+    pub fn with_embedder(mut self, embedder: Arc<dyn AsyncEmbeddingProvider>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// The underlying store, for synchronous callers.
+    pub fn shared(&self) -> &Arc<ConcurrentMemoryStore<B>> {
+        &self.shared
+    }
+
+    pub fn snapshot(&self) -> Arc<MemorySnapshot> {
+        self.shared.snapshot()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<MemoryRecord> {
+        self.snapshot().find_by_id(id).cloned()
+    }
+
+    /// Run `f` under one write guard on the blocking pool, then flush. Its
+    /// changes are published as one snapshot; an error part-way through does
+    /// not roll back what `f` already changed.
+    pub async fn transact<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut MemoryStore<B>) -> Result<T> + Send + 'static,
+    {
+        let shared = self.shared.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut store = shared
+                .lock()
+                .map_err(|e| anyhow!("memory store lock poisoned: {e}"))?;
+            let out = f(&mut store)?;
+            store.flush()?;
+            Ok(out)
+        })
+        .await?
+    }
+
+    pub async fn add(&self, record: MemoryRecord) -> Result<Uuid> {
+        let ids = self.add_batch(vec![record]).await?;
+        Ok(ids[0])
+    }
+
+    /// Add all `records` under one guard; stops at the first failure.
+    /// The store's write screen runs first, with no lock held, and before
+    /// embedding, so the embedder only sees redacted text.
+    pub async fn add_batch(&self, records: Vec<MemoryRecord>) -> Result<Vec<Uuid>> {
+        let mut records = self.screen_all(records).await?;
+        if let Some(embedder) = &self.embedder {
+            for record in records.iter_mut() {
+                if record.metadata.get("embedding").is_none() {
+                    let embedding = embedder.embed(&record.target).await?;
+                    record.set_metadata_field("embedding", embedding).ok();
+                }
+            }
+        }
+        self.transact(move |store| {
+            let mut ids = Vec::with_capacity(records.len());
+            for record in records {
+                ids.push(record.id);
+                store.add_prescreened(record)?;
+            }
+            Ok(ids)
+        })
+        .await
+    }
+
+    /// Run the store's write screen on `record` on the blocking pool against
+    /// the current snapshot, so classification holds no lock. Add the result
+    /// with `add_prescreened` inside `transact`.
+    pub async fn screen(
+        &self,
+        mut record: MemoryRecord,
+    ) -> Result<(MemoryRecord, Option<ScreenOutcome>)> {
+        let snapshot = self.snapshot();
+        tokio::task::spawn_blocking(move || {
+            let outcome = snapshot.screen(&mut record)?;
+            Ok((record, outcome))
+        })
+        .await?
+    }
+
+    async fn screen_all(&self, mut records: Vec<MemoryRecord>) -> Result<Vec<MemoryRecord>> {
+        let snapshot = self.snapshot();
+        tokio::task::spawn_blocking(move || {
+            for record in records.iter_mut() {
+                snapshot.screen(record)?;
+            }
+            Ok(records)
+        })
+        .await?
+    }
+
+    /// `search_tiered` over the current snapshot, excluding quarantined
+    /// records. Scoring runs on the blocking pool.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<(MemoryRecord, f64)>> {
+        let embedding: Option<Vec<f64>> = match &self.embedder {
+            Some(embedder) => Some(
+                embedder
+                    .embed(query)
+                    .await?
+                    .into_iter()
+                    .map(f64::from)
+                    .collect(),
+            ),
+            None => None,
+        };
+        let snapshot = self.snapshot();
+        let query = query.to_string();
+        let hits = tokio::task::spawn_blocking(move || {
+            snapshot.search_tiered(embedding.as_deref(), &query, limit, false)
+        })
+        .await?;
+        Ok(hits)
+    }
+
+    /// Apply `patch` through `ConcurrentMemoryStore::update_memory`, which
+    /// screens the updated record like an add, and return it; `Ok(None)`
+    /// when no record has this id.
+    pub async fn update(&self, id: Uuid, patch: RecordPatch) -> Result<Option<MemoryRecord>> {
+        let shared = self.shared.clone();
+        tokio::task::spawn_blocking(move || {
+            let updated = shared.update_memory(id, &patch)?;
+            shared
+                .lock()
+                .map_err(|e| anyhow!("memory store lock poisoned: {e}"))?
+                .flush()?;
+            Ok(updated)
+        })
+        .await?
+    }
+
+    /// Remove one record; `Ok(false)` when no record has this id.
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        self.transact(move |store| store.delete_durable(id)).await
+    }
+
+    /// Flush writes made through `shared()` that are still buffered.
+    pub async fn flush(&self) -> Result<()> {
+        self.transact(|_| Ok(())).await
     }
 }
@@ -0,0 +1,94 @@
+//! hipcortex-mcp — Model Context Protocol server over stdio (default) or
+//! streamable HTTP (`--http ADDR`, needs the `web-server` feature).
+//!
+//! Reads and writes `memory.jsonl` and `worldmodel.json` in `--data-dir`
+//! (default `$DATA_DIR`, `$HIPCORTEX_STORAGE` or `.`), the same layout the
+//! REST server uses. In stdio mode stdout carries only protocol messages;
+//! diagnostics go to stderr. `HIPCORTEX_SAFETY_POLICY`,
+//! `HIPCORTEX_VAULT_KEY` and `HIPCORTEX_HOT_RECORDS` apply as for the REST
+//! server; the vault defaults to `memory-vault.jsonl` in the data dir,
+//! shared with the REST server and the CLI.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::mcp::{serve_stdio, McpService};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use std::sync::Arc;
+
+const USAGE: &str =
+    "usage: hipcortex-mcp [--data-dir DIR] [--actor NAME] [--http ADDR] [--allow-origin ORIGIN]...";
+
+fn main() -> anyhow::Result<()> {
+    let mut data_dir = std::env::var("DATA_DIR")
+        .or_else(|_| std::env::var("HIPCORTEX_STORAGE"))
+        .unwrap_or_else(|_| ".".to_string());
+    let mut actor = std::env::var("HIPCORTEX_ACTOR").unwrap_or_else(|_| "mcp-session".into());
+    let mut http: Option<String> = None;
+    let mut origins: Vec<String> = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow::anyhow!("{arg} needs a value\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--data-dir" => data_dir = value()?,
+            "--actor" => actor = value()?,
+            "--http" => http = Some(value()?),
+            "--allow-origin" => origins.push(value()?),
+            "-h" | "--help" => {
+                eprintln!("{USAGE}");
+                return Ok(());
+            }
+            other => anyhow::bail!("unknown argument `{other}`\n{USAGE}"),
+        }
+    }
+
+    // Same safety policy and redaction vault settings as the REST server and CLI.
+    hipcortex::safety_guardrail::SAFETY_GUARDRAIL
+        .lock()
+        .map_err(|e| anyhow::anyhow!("safety guardrail lock: {e}"))?
+        .configure_from_env(Some(&std::path::Path::new(&data_dir).join("memory-vault.jsonl")))
+        .map_err(anyhow::Error::msg)?;
+
+    let mut memory = MemoryStore::new(format!("{data_dir}/memory.jsonl"))?;
+    if let Some(hot) = hipcortex::memory_store::hot_records_from_env() {
+        memory = memory.with_paging(format!("{data_dir}/pages"), hot)?;
+    }
+    let store = Arc::new(ConcurrentMemoryStore::new(memory));
+    let wm_path = format!("{data_dir}/worldmodel.json");
+    let world_model =
+        WorldModelEnhanced::load(&wm_path).unwrap_or_else(|_| WorldModelEnhanced::new());
+    let service = McpService::new(store.clone())
+        .with_world_model(Arc::new(world_model))
+        .with_actor(&actor);
+
+    match http {
+        None => {
+            eprintln!("hipcortex-mcp: serving stdio, data in {data_dir}");
+            let stdin = std::io::stdin();
+            serve_stdio(&service, stdin.lock(), std::io::stdout())?;
+        }
+        #[cfg(feature = "web-server")]
+        Some(addr) => {
+            let addr: std::net::SocketAddr = addr.parse()?;
+            let transport = origins
+                .iter()
+                .fold(hipcortex::mcp::http::HttpTransport::new(service), |t, o| {
+                    t.with_allowed_origin(o)
+                });
+            eprintln!("hipcortex-mcp: serving http://{addr}/mcp, data in {data_dir}");
+            tokio::runtime::Runtime::new()?.block_on(transport.serve(addr))?;
+        }
+        #[cfg(not(feature = "web-server"))]
+        Some(_) => {
+            let _ = origins;
+            anyhow::bail!("--http needs hipcortex built with the `web-server` feature");
+        }
+    }
+    store
+        .lock()
+        .map_err(|e| anyhow::anyhow!("memory store lock: {e}"))?
+        .flush()?;
+    Ok(())
+}
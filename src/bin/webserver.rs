@@ -1,6 +1,7 @@
 use hipcortex::archive_store::ArchiveStore;
 use hipcortex::aureus_bridge::AureusBridge;
 use hipcortex::coherence::CoherenceChecker;
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::memory_store::MemoryStore;
 use hipcortex::self_model::calibration::CalibrationTracker;
 use hipcortex::self_model::{CapabilityDescriptor, SelfModel};
@@ -32,9 +33,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .or_else(|_| std::env::var("HIPCORTEX_STORAGE"))
         .unwrap_or_else(|_| ".".to_string());
 
+    // ── Tracing: OTLP export when OTEL_EXPORTER_OTLP_ENDPOINT is set ─────────
+    if hipcortex::telemetry::init_from_env("hipcortex").is_some() {
+        println!("Tracing: exporting spans via OTLP");
+    }
+
     // ── Memory store ─────────────────────────────────────────────────────────
     let store_path = format!("{}/memory.jsonl", data_dir);
-    let memory_store = Arc::new(Mutex::new(MemoryStore::new(&store_path)?));
+    let cold_dir = format!("{}/cold", data_dir);
+    let mut memory = MemoryStore::new(&store_path)?.with_cold_tier(&cold_dir)?;
+    if let Some(hot) = hipcortex::memory_store::hot_records_from_env() {
+        memory = memory.with_paging(format!("{}/pages", data_dir), hot)?;
+        println!("Memory: paging out beyond {} hot records", hot);
+    }
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(memory));
 
     // ── WorldModelEnhanced: load from disk or start fresh ────────────────────
     let wm_path = format!("{}/worldmodel.json", data_dir);
@@ -101,7 +113,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Arc::clone(&coherence),
         Arc::clone(&calibration),
         Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
-    ));
+    )
+    .with_tiering(hipcortex::experience_store::TieringPolicy::default()));
     let state = AppState {
         memory_store: memory_store.clone(),
         symbolic_store: Arc::new(Mutex::new(SymbolicStore::<InMemoryGraph>::new())),
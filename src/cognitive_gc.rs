@@ -54,7 +54,10 @@ impl CognitiveGC {
 
     /// Bulk-register references from stored records' evidence fields.
     /// Call on startup to rebuild the reference map.
-    pub fn rebuild_from_records(&mut self, records: &[crate::memory_record::MemoryRecord]) {
+    pub fn rebuild_from_records<'a>(
+        &mut self,
+        records: impl IntoIterator<Item = &'a crate::memory_record::MemoryRecord>,
+    ) {
         self.references.clear();
         for r in records {
             for &evidence_id in &r.evidence {
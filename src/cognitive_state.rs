@@ -11,8 +11,8 @@ use uuid::Uuid;
 
 use crate::archive_store::ArchiveStore;
 use crate::cognitive_gc::{CognitiveGC, GcAction};
-use crate::memory_record::{MemoryRecord, MemoryType};
-use crate::memory_store::MemoryStore;
+use crate::memory_record::{MemoryRecord, MemoryType, RecordStatus};
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
 use crate::coherence::CoherenceChecker;
 use crate::self_model::calibration::CalibrationTracker;
 use crate::self_model::SelfModel;
@@ -66,6 +66,9 @@ pub enum CognitiveDelta {
     RegisterSkill(SkillPayload),
     // Phase 4 stubs — return CognitiveError::NotImplemented
     Consolidate { source_ids: Vec<Uuid>, summary: MemoryRecord },
+    /// Like `Consolidate`, but the summary is generated by the handle's
+    /// `Summarizer` (extractive when none is configured).
+    Summarize { source_ids: Vec<Uuid> },
     /// Reshaped from ForgetActor(String) to satisfy serde internal tagging.
     ForgetActor { actor: String },
     /// Reshaped from ArchiveRecord(Uuid) to satisfy serde internal tagging.
@@ -94,6 +97,7 @@ impl CognitiveDelta {
             Self::AdvanceGoal { .. } => "AdvanceGoal",
             Self::RegisterSkill(_) => "RegisterSkill",
             Self::Consolidate { .. } => "Consolidate",
+            Self::Summarize { .. } => "Summarize",
             Self::ForgetActor { .. } => "ForgetActor",
             Self::ArchiveRecord { .. } => "ArchiveRecord",
             Self::RetractBelief { .. } => "RetractBelief",
@@ -183,9 +187,12 @@ pub struct CognitiveSnapshot {
 
 // ─── CognitiveHandle ─────────────────────────────────────────────────────────
 
+/// Lease lifetime when the holder does not renew.
+pub const DEFAULT_LEASE_TTL_SECS: u64 = 300;
+
 #[allow(dead_code)]
 pub struct CognitiveHandle<B: MemoryBackend + Send + Sync + 'static> {
-    pub memory: Arc<Mutex<MemoryStore<B>>>,
+    pub memory: Arc<ConcurrentMemoryStore<B>>,
     pub(crate) world: Arc<std::sync::RwLock<WorldModelEnhanced>>,
     pub(crate) self_model: Arc<SelfModel>,
     pub(crate) tx_log: Option<Arc<TxLog>>,
@@ -196,6 +203,14 @@ pub struct CognitiveHandle<B: MemoryBackend + Send + Sync + 'static> {
     pub(crate) archive_store: Option<Arc<Mutex<ArchiveStore>>>,
     /// Phase 4: multi-agent workspace registry.
     pub workspace_registry: Arc<Mutex<WorkspaceRegistry>>,
+    /// Leases on symbolic-store regions; expired ones are released by the
+    /// `lease_cleanup` maintenance job.
+    pub lease_manager: Arc<Mutex<crate::lease_manager::LeaseManager>>,
+    /// Hot/cold tiering applied after an AddMemory whose actor reaches the Raw
+    /// cap. None = tiering disabled.
+    pub(crate) tiering: Option<crate::experience_store::TieringPolicy>,
+    /// Summarizer for `Summarize` and `AutoConsolidate`. None = extractive summaries.
+    pub(crate) summarizer: Option<Arc<crate::summarizer::Summarizer>>,
 }
 
 /// Mean binary entropy over a slice of confidence values in [0,1].
@@ -216,7 +231,7 @@ pub fn compute_epistemic_entropy(confs: &[f32]) -> f32 {
 
 impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
     pub fn new(
-        memory: Arc<Mutex<MemoryStore<B>>>,
+        memory: Arc<ConcurrentMemoryStore<B>>,
         world: Arc<std::sync::RwLock<WorldModelEnhanced>>,
         self_model: Arc<SelfModel>,
         tx_log: Option<Arc<TxLog>>,
@@ -224,10 +239,26 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         calibration: Arc<CalibrationTracker>,
         gc: Arc<CognitiveGC>,
     ) -> Self {
+        // With a tx log attached every mutation's post-image rides along on its
+        // entry, which is what point-in-time recovery replays. Switching the
+        // journal on checkpoints the store so earlier writes have a base.
+        if let Some(log) = &tx_log {
+            if let Ok(mut ms) = memory.lock() {
+                if let Err(e) = crate::pitr::start_journal(&mut ms, log) {
+                    tracing::warn!(error = %e, "pitr: journal start checkpoint failed");
+                    ms.enable_change_journal();
+                }
+            }
+        }
         Self {
             memory, world, self_model, tx_log, coherence, calibration, gc,
             archive_store: None,
             workspace_registry: Arc::new(Mutex::new(WorkspaceRegistry::new())),
+            lease_manager: Arc::new(Mutex::new(crate::lease_manager::LeaseManager::new(
+                DEFAULT_LEASE_TTL_SECS,
+            ))),
+            tiering: None,
+            summarizer: None,
         }
     }
 
@@ -236,6 +267,19 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         self
     }
 
+    /// Enable automatic hot/cold tiering. Requires the MemoryStore to have a
+    /// cold tier attached (`MemoryStore::with_cold_tier`); otherwise a no-op.
+    pub fn with_tiering(mut self, policy: crate::experience_store::TieringPolicy) -> Self {
+        self.tiering = Some(policy);
+        self
+    }
+
+    /// Generate consolidation summaries with `summarizer` (e.g. one backed by an `LLMClient`).
+    pub fn with_summarizer(mut self, summarizer: crate::summarizer::Summarizer) -> Self {
+        self.summarizer = Some(Arc::new(summarizer));
+        self
+    }
+
     /// Apply a CognitiveDelta; returns tx_cursor. Thin wrapper over `transact_ex`.
     pub fn transact(&self, delta: CognitiveDelta, actor: &str) -> Result<u64, CognitiveError> {
         Ok(self.transact_ex(delta, actor)?.tx_cursor)
@@ -244,15 +288,49 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
     /// Full transact returning `TransactResult` (includes `records_deleted` for ForgetActor).
     ///
     /// Pipeline: safety gate → structural check → apply → tx log → calibration ping.
+    /// Runs inside a `cognitive.transact` span; latency lands in `TRANSACT_SECONDS`.
     pub fn transact_ex(
         &self,
         delta: CognitiveDelta,
         actor: &str,
+    ) -> Result<TransactResult, CognitiveError> {
+        let label = delta.label();
+        let span = tracing::info_span!(
+            "cognitive.transact",
+            delta = label,
+            actor = actor,
+            tx_cursor = tracing::field::Empty,
+            outcome = tracing::field::Empty,
+        );
+        let _guard = span.enter();
+        let started = std::time::Instant::now();
+        let result = self.transact_pipeline(delta, actor);
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        if let Ok(r) = &result {
+            span.record("tx_cursor", r.tx_cursor);
+        }
+        span.record("outcome", outcome);
+        crate::telemetry::METRICS.observe_duration(
+            crate::telemetry::TRANSACT_SECONDS,
+            &[("delta", label), ("outcome", outcome)],
+            started.elapsed(),
+        );
+        result
+    }
+
+    fn transact_pipeline(
+        &self,
+        delta: CognitiveDelta,
+        actor: &str,
     ) -> Result<TransactResult, CognitiveError> {
         // Step 1: Safety gate
-        self.coherence
-            .gate_write(delta.label())
-            .map_err(|r| CognitiveError::CoherenceRejection(r.reason))?;
+        let gate = self.coherence.gate_write(delta.label());
+        crate::telemetry::METRICS.inc(
+            crate::telemetry::COHERENCE_GATE_TOTAL,
+            &[("decision", if gate.is_ok() { "accept" } else { "reject" })],
+            1.0,
+        );
+        gate.map_err(|r| CognitiveError::CoherenceRejection(r.reason))?;
 
         // Step 2: Structural coherence
         self.coherence
@@ -265,6 +343,11 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
                 let tx_cursor = self.consolidate_memory(source_ids, summary.clone(), actor)?;
                 return Ok(TransactResult { tx_cursor, records_deleted: None });
             }
+            CognitiveDelta::Summarize { source_ids } => {
+                let summary = self.generate_summary(source_ids, actor)?;
+                let tx_cursor = self.consolidate_memory(source_ids, summary, actor)?;
+                return Ok(TransactResult { tx_cursor, records_deleted: None });
+            }
             CognitiveDelta::ForgetActor { actor: target_actor } => {
                 let (tx_cursor, deleted) = self.forget_actor(target_actor, actor)?;
                 return Ok(TransactResult { tx_cursor, records_deleted: Some(deleted) });
@@ -308,12 +391,17 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
                 CognitiveDelta::RegisterSkill(_) => TxKind::MemoryAdd,
                 _ => unreachable!(),
             };
-            tx.append(kind, affected_ids, actor)
+            self.log_tx(tx, kind, affected_ids, actor)
         } else {
             0
         };
 
-        // Step 6: Calibration — update pressure, entropy, EWMA
+        // Step 6: Tiering — demote Raw overflow to the cold tier
+        if let CognitiveDelta::AddMemory(record) = &delta {
+            self.enforce_tiering(&record.actor, actor)?;
+        }
+
+        // Step 7: Calibration — update pressure, entropy, EWMA
         self.calibrate_after_tx(tx_cursor);
 
         Ok(TransactResult { tx_cursor, records_deleted: None })
@@ -339,12 +427,17 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         let capped: Vec<Uuid> = source_ids.to_vec();
 
         let summary_id = summary.id;
+        crate::telemetry::METRICS.inc(
+            crate::telemetry::CONSOLIDATION_RUNS_TOTAL,
+            &[("kind", "summary")],
+            1.0,
+        );
         {
             let mut ms = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
 
             // Archive or delete each source record
             for &id in &capped {
-                if let Some(rec) = ms.find_by_id(id).cloned() {
+                if let Some(rec) = ms.find_by_id_tiered(id).cloned() {
                     // Move to cold store if available
                     if let Some(arc) = &self.archive_store {
                         let _ = arc.lock().map(|mut as_| as_.append(rec));
@@ -358,7 +451,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         }
 
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::Consolidate, vec![summary_id], actor)
+            self.log_tx(tx, TxKind::Consolidate, vec![summary_id], actor)
         } else {
             0
         };
@@ -366,6 +459,18 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         Ok(tx_cursor)
     }
 
+    /// Build a summary record for `source_ids` with the configured summarizer.
+    fn generate_summary(&self, source_ids: &[Uuid], actor: &str) -> Result<MemoryRecord, CognitiveError> {
+        let ms = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
+        let sources: Vec<&MemoryRecord> = source_ids.iter().filter_map(|id| ms.find_by_id(*id)).collect();
+        if sources.is_empty() {
+            return Err(CognitiveError::DeltaInvalid("no source records found".into()));
+        }
+        let fallback = crate::summarizer::Summarizer::extractive();
+        let summarizer = self.summarizer.as_deref().unwrap_or(&fallback);
+        Ok(summarizer.summary_record(actor, &sources))
+    }
+
     /// GDPR hard-delete: remove all records for `target_actor` from Hot store.
     /// Returns (tx_cursor, records_deleted).
     fn forget_actor(
@@ -381,7 +486,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         };
 
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::ForgetActor, vec![], tx_actor)
+            self.log_tx(tx, TxKind::ForgetActor, vec![], tx_actor)
         } else {
             0
         };
@@ -395,7 +500,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         let mut ms = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
         match action {
             GcAction::Archive => {
-                if let Some(rec) = ms.find_by_id(id).cloned() {
+                if let Some(rec) = ms.find_by_id_tiered(id).cloned() {
                     if let Some(arc) = &self.archive_store {
                         let _ = arc.lock().map(|mut as_| as_.append(rec));
                     }
@@ -409,7 +514,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         drop(ms);
 
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::ArchiveRecord, vec![id], actor)
+            self.log_tx(tx, TxKind::ArchiveRecord, vec![id], actor)
         } else {
             0
         };
@@ -425,9 +530,9 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             // Primary retraction already logged inside propagate_retraction;
             // append a summary entry for the root retraction.
             if cascaded.is_empty() {
-                tx.append(TxKind::BeliefRetract, vec![id], actor)
+                self.log_tx(tx, TxKind::BeliefRetract, vec![id], actor)
             } else {
-                tx.append(TxKind::BeliefRetract, cascaded, actor)
+                self.log_tx(tx, TxKind::BeliefRetract, cascaded, actor)
             }
         } else {
             0
@@ -448,7 +553,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             .map_err(|e| CognitiveError::StoreError(e))?;
         drop(ms);
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::BeliefAssert, vec![belief_id], actor)
+            self.log_tx(tx, TxKind::BeliefAssert, vec![belief_id], actor)
         } else {
             0
         };
@@ -460,11 +565,12 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         let freq = if min_frequency == 0 { 3 } else { min_frequency };
         let report = {
             let mut ms = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
-            crate::consolidation::mine_and_consolidate(
+            crate::consolidation::mine_and_consolidate_with_summarizer(
                 &mut *ms,
                 self.tx_log.as_deref(),
                 freq,
                 actor,
+                self.summarizer.as_deref(),
             )
             .map_err(|e| CognitiveError::StoreError(e))?
         };
@@ -481,7 +587,8 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             }
         }
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(
+            self.log_tx(
+                tx,
                 TxKind::Consolidate,
                 report.source_ids_archived.clone(),
                 actor,
@@ -507,7 +614,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         drop(store);
         drop(reg);
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::WorkspaceOp, vec![], actor)
+            self.log_tx(tx, TxKind::WorkspaceOp, vec![], actor)
         } else {
             0
         };
@@ -525,7 +632,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         reg.merge(&from, &into).map_err(|e| CognitiveError::StoreError(e))?;
         drop(reg);
         let tx_cursor = if let Some(tx) = &self.tx_log {
-            tx.append(TxKind::WorkspaceOp, vec![], actor)
+            self.log_tx(tx, TxKind::WorkspaceOp, vec![], actor)
         } else {
             0
         };
@@ -533,6 +640,40 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         Ok(tx_cursor)
     }
 
+    /// Run the tiering policy if configured and `record_actor` (the writer of
+    /// the record just added) is at the Raw cap; the full pass scans the store,
+    /// so it is not run on every add. Logs a tx when records moved.
+    fn enforce_tiering(&self, record_actor: &str, actor: &str) -> Result<(), CognitiveError> {
+        let Some(policy) = &self.tiering else { return Ok(()) };
+        let report = {
+            let mut ms = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
+            if !crate::experience_store::over_raw_cap(&ms, record_actor, policy) {
+                return Ok(());
+            }
+            crate::experience_store::enforce_tiering(&mut ms, self.tx_log.as_deref(), policy)
+                .map_err(CognitiveError::StoreError)?
+        };
+        if report.records_demoted > 0 {
+            if let Some(tx) = &self.tx_log {
+                self.log_tx(tx, TxKind::MemoryArchive, vec![], actor);
+            }
+        }
+        Ok(())
+    }
+
+    /// Append a tx carrying the store's pending record changes. Must be called
+    /// with the memory lock released; it is held across the append so tx order
+    /// matches the order the changes were made in.
+    fn log_tx(&self, tx: &TxLog, kind: TxKind, ids: Vec<Uuid>, actor: &str) -> u64 {
+        match self.memory.lock() {
+            Ok(mut ms) => {
+                let changes = ms.take_changes();
+                tx.append_with_changes(kind, ids, actor, changes)
+            }
+            Err(_) => tx.append(kind, ids, actor),
+        }
+    }
+
     /// Re-acquire store (briefly) to compute real pressure + entropy after any transact.
     /// Best-effort: if lock is poisoned, EWMA ping still fires.
     fn calibrate_after_tx(&self, tx_cursor: u64) {
@@ -551,9 +692,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             CognitiveDelta::AddMemory(record) => {
                 let id = record.id;
                 self.memory
-                    .lock()
-                    .map_err(|_| CognitiveError::LockError)?
-                    .add(record.clone())
+                    .add_memory(record.clone())
                     .map_err(|e| CognitiveError::StoreError(e.to_string()))?;
                 Ok(vec![id])
             }
@@ -636,7 +775,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
     /// Materialise a complete CognitiveSnapshot for the given actor.
     /// actor = "" → include all actors.
     pub fn snapshot(&self, actor: &str) -> Result<CognitiveSnapshot, CognitiveError> {
-        let mem = self.memory.lock().map_err(|_| CognitiveError::LockError)?;
+        let mem = self.memory.snapshot();
 
         // Temporal view
         let temporal_recs: Vec<_> = mem
@@ -644,7 +783,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Temporal
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .collect();
@@ -668,7 +807,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Goal
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .filter_map(|r| {
@@ -698,7 +837,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Belief
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .filter_map(|r| {
@@ -769,7 +908,10 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
 
     pub fn fork(&self) -> Result<SimulationFork<B>, CognitiveError> {
         let base_tx = self.tx_log.as_ref().map(|t| t.current_tx()).unwrap_or(0);
-        SimulationFork::from_handle(self, base_tx)
+        let _span = tracing::info_span!("cognitive.fork", base_tx = base_tx).entered();
+        let fork = SimulationFork::from_handle(self, base_tx)?;
+        crate::telemetry::METRICS.inc(crate::telemetry::FORKS_TOTAL, &[], 1.0);
+        Ok(fork)
     }
 
     /// Create a (SimulationFork, ContinuousDynamics) pair for assembling a DigitalTwin.
@@ -806,7 +948,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
 
     /// Materialize ExperienceStore view for an actor.
     pub fn experience_tiers(&self, actor: &str) -> crate::experience_store::ExperienceStore {
-        let store = self.memory.lock().unwrap();
+        let store = self.memory.snapshot();
         crate::experience_store::ExperienceStore::from_store(&*store, actor)
     }
 
@@ -816,7 +958,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> CognitiveHandle<B> {
         actor: &str,
         query: &str,
     ) -> Vec<crate::memory_record::MemoryRecord> {
-        let store = self.memory.lock().unwrap();
+        let store = self.memory.snapshot();
         let es = crate::experience_store::ExperienceStore::from_store(&*store, actor);
         es.search_compressed(&*store, query)
     }
@@ -0,0 +1,309 @@
+//! ColdTier — zstd-compressed segment archive for demoted Raw records.
+//!
+//! Chain-of-thought: ArchiveStore is an append-only plain JSONL that nothing
+//! reads back. The cold tier is the other half of tiered storage: records
+//! demoted from the hot `MemoryStore` are written as immutable zstd segments
+//! (`seg-000001.jsonl.zst`, …), an id → segment index stays in memory, and a
+//! faulted record is tombstoned in the segment it came from so it is not
+//! resurrected on restart; a later demotion of the same id writes a new
+//! segment the tombstone does not cover. `compact()` rewrites segments
+//! without tombstoned records.
+//!
+//! Searches must not decompress the archive, so next to the index sits a
+//! search stub per live record: the record with its metadata cut down to the
+//! keys ranking reads. Only the few cold hits a search returns are read from
+//! their segments, and recently decoded segments are kept in a small LRU.
+
+use crate::memory_record::MemoryRecord;
+use anyhow::Result;
+use indexmap::IndexMap;
+use lru::LruCache;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use uuid::Uuid;
+
+const SEGMENT_PREFIX: &str = "seg-";
+const SEGMENT_SUFFIX: &str = ".jsonl.zst";
+const TOMBSTONE_FILE: &str = "faulted.ids";
+const ZSTD_LEVEL: i32 = 3;
+/// Tombstone line without a segment number (older files): hides the id in every segment.
+const ALL_SEGMENTS: u32 = u32::MAX;
+/// Metadata keys search ranking reads (`weighted_score`, `compute_decay`);
+/// the only metadata a search stub keeps.
+const SEARCH_METADATA: [&str; 3] = ["embedding", "decay_factor", "decay_half_life_secs"];
+/// Decoded segments kept for `get`.
+const DECODED_SEGMENTS: usize = 8;
+
+pub struct ColdTier {
+    dir: PathBuf,
+    /// Record id → segment number holding it.
+    index: HashMap<Uuid, u32>,
+    /// (id, segment) pairs faulted back to the hot store; still physically
+    /// present in that segment.
+    tombstones: HashSet<(Uuid, u32)>,
+    /// Search stub per live record, in demotion order.
+    stubs: IndexMap<Uuid, MemoryRecord>,
+    /// Recently decoded segments. Segments are immutable once written.
+    decoded: Mutex<LruCache<u32, Arc<Vec<MemoryRecord>>>>,
+    next_segment: u32,
+}
+
+/// `rec` with its metadata cut down to `SEARCH_METADATA`.
+fn search_stub(rec: &MemoryRecord) -> MemoryRecord {
+    let metadata: serde_json::Map<String, serde_json::Value> = SEARCH_METADATA
+        .iter()
+        .filter_map(|&k| Some((k.to_string(), rec.metadata.get(k)?.clone())))
+        .collect();
+    MemoryRecord {
+        metadata: serde_json::Value::Object(metadata),
+        ..rec.clone()
+    }
+}
+
+impl ColdTier {
+    /// Open (or create) a cold tier rooted at `dir`, rebuilding the index from segments.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut tombstones = HashSet::new();
+        let tomb_path = dir.join(TOMBSTONE_FILE);
+        if tomb_path.exists() {
+            for line in BufReader::new(File::open(&tomb_path)?).lines() {
+                let line = line?;
+                let mut parts = line.split_whitespace();
+                let Some(Ok(id)) = parts.next().map(Uuid::parse_str) else {
+                    continue;
+                };
+                let seg = match parts.next() {
+                    Some(seg) => match seg.parse() {
+                        Ok(seg) => seg,
+                        Err(_) => continue,
+                    },
+                    None => ALL_SEGMENTS,
+                };
+                tombstones.insert((id, seg));
+            }
+        }
+
+        let mut tier = Self {
+            dir,
+            index: HashMap::new(),
+            tombstones,
+            stubs: IndexMap::new(),
+            decoded: Mutex::new(LruCache::new(NonZeroUsize::new(DECODED_SEGMENTS).unwrap())),
+            next_segment: 1,
+        };
+        for seg in tier.segment_numbers()? {
+            for rec in tier.read_segment(seg)? {
+                if !tier.is_tombstoned(rec.id, seg) {
+                    tier.index.insert(rec.id, seg);
+                    tier.stubs.insert(rec.id, search_stub(&rec));
+                }
+            }
+            tier.next_segment = tier.next_segment.max(seg + 1);
+        }
+        Ok(tier)
+    }
+
+    fn is_tombstoned(&self, id: Uuid, seg: u32) -> bool {
+        self.tombstones.contains(&(id, seg)) || self.tombstones.contains(&(id, ALL_SEGMENTS))
+    }
+
+    /// Atomically rewrite the tombstone file from the in-memory set.
+    fn write_tombstones(&self) -> Result<()> {
+        let path = self.dir.join(TOMBSTONE_FILE);
+        let tmp = path.with_extension("ids.tmp");
+        let mut entries: Vec<&(Uuid, u32)> = self.tombstones.iter().collect();
+        entries.sort();
+        let mut f = File::create(&tmp)?;
+        for (id, seg) in entries {
+            writeln!(f, "{id} {seg}")?;
+        }
+        f.sync_all()?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn segment_path(&self, seg: u32) -> PathBuf {
+        self.dir.join(format!("{SEGMENT_PREFIX}{seg:06}{SEGMENT_SUFFIX}"))
+    }
+
+    fn segment_numbers(&self) -> Result<Vec<u32>> {
+        let mut segs: Vec<u32> = std::fs::read_dir(&self.dir)?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.strip_prefix(SEGMENT_PREFIX)?
+                    .strip_suffix(SEGMENT_SUFFIX)?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        segs.sort_unstable();
+        Ok(segs)
+    }
+
+    fn read_segment(&self, seg: u32) -> Result<Vec<MemoryRecord>> {
+        let mut raw = String::new();
+        zstd::stream::read::Decoder::new(File::open(self.segment_path(seg))?)?
+            .read_to_string(&mut raw)?;
+        Ok(raw
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect())
+    }
+
+    /// `read_segment` through the decoded-segment cache.
+    fn segment(&self, seg: u32) -> Result<Arc<Vec<MemoryRecord>>> {
+        let cached = self
+            .decoded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&seg)
+            .cloned();
+        if let Some(records) = cached {
+            return Ok(records);
+        }
+        let records = Arc::new(self.read_segment(seg)?);
+        self.decoded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .put(seg, records.clone());
+        Ok(records)
+    }
+
+    /// Write `records` as one new compressed segment. Returns the number written.
+    pub fn demote(&mut self, records: &[MemoryRecord]) -> Result<usize> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        let seg = self.next_segment;
+        let mut buf = Vec::new();
+        for rec in records {
+            serde_json::to_writer(&mut buf, rec)?;
+            buf.push(b'\n');
+        }
+        let compressed = zstd::stream::encode_all(&buf[..], ZSTD_LEVEL)?;
+        std::fs::write(self.segment_path(seg), compressed)?;
+        // Segment tombstones never cover the new segment, but an old-format
+        // line would hide the id everywhere; drop those once the data is safe.
+        let mut legacy = false;
+        for rec in records {
+            legacy |= self.tombstones.remove(&(rec.id, ALL_SEGMENTS));
+            self.index.insert(rec.id, seg);
+            self.stubs.insert(rec.id, search_stub(rec));
+        }
+        if legacy {
+            self.write_tombstones()?;
+        }
+        self.next_segment += 1;
+        Ok(records.len())
+    }
+
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    /// Read a cold record without removing it.
+    pub fn get(&self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        match self.index.get(&id) {
+            Some(&seg) => Ok(self.segment(seg)?.iter().find(|r| r.id == id).cloned()),
+            None => Ok(None),
+        }
+    }
+
+    /// Search stubs of every live cold record, from memory: full records
+    /// except that metadata holds only the keys search ranking reads.
+    pub fn search_stubs(&self) -> impl Iterator<Item = &MemoryRecord> + '_ {
+        self.stubs.values()
+    }
+
+    /// Remove a record from the cold tier (tombstone) and return it for re-insertion hot.
+    pub fn fault(&mut self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        let rec = self.get(id)?;
+        if rec.is_some() {
+            self.tombstone(id)?;
+        }
+        Ok(rec)
+    }
+
+    /// Tombstone `id` without reading it back. Returns false if it was not cold.
+    pub fn tombstone(&mut self, id: Uuid) -> Result<bool> {
+        let Some(seg) = self.index.remove(&id) else {
+            return Ok(false);
+        };
+        self.stubs.swap_remove(&id);
+        self.tombstones.insert((id, seg));
+        let mut f = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(TOMBSTONE_FILE))?;
+        writeln!(f, "{id} {seg}")?;
+        Ok(true)
+    }
+
+    /// Ids of every live cold record, from the in-memory index.
+    pub fn ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.index.keys().copied()
+    }
+
+    /// All live cold records (decompresses every segment).
+    pub fn scan(&self) -> Result<Vec<MemoryRecord>> {
+        let mut out = Vec::with_capacity(self.index.len());
+        for seg in self.segment_numbers()? {
+            out.extend(
+                self.read_segment(seg)?
+                    .into_iter()
+                    .filter(|r| self.index.get(&r.id) == Some(&seg)),
+            );
+        }
+        Ok(out)
+    }
+
+    /// Number of live cold records.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn segment_count(&self) -> usize {
+        self.segment_numbers().map(|s| s.len()).unwrap_or(0)
+    }
+
+    /// Compressed bytes on disk across all segments.
+    pub fn bytes_on_disk(&self) -> u64 {
+        self.segment_numbers()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|s| std::fs::metadata(self.segment_path(s)).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+
+    /// Rewrite all segments into one, dropping tombstoned records.
+    /// Returns the number of live records retained.
+    pub fn compact(&mut self) -> Result<usize> {
+        let live = self.scan()?;
+        let old = self.segment_numbers()?;
+        // Write the merged segment before deleting old ones so a crash never loses data.
+        self.index.clear();
+        self.stubs.clear();
+        self.demote(&live)?;
+        for seg in old {
+            std::fs::remove_file(self.segment_path(seg))?;
+        }
+        self.decoded.lock().unwrap_or_else(PoisonError::into_inner).clear();
+        self.tombstones.clear();
+        let _ = std::fs::remove_file(self.dir.join(TOMBSTONE_FILE));
+        Ok(live.len())
+    }
+}
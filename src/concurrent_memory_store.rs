@@ -0,0 +1,593 @@
+//! ConcurrentMemoryStore — one `MemoryStore` shared by many threads.
+//!
+//! Chain-of-thought: wrapping `MemoryStore<B>` in `Arc<Mutex<…>>` makes a
+//! semantic search wait for an ingest and vice versa. Here writers still
+//! serialize on a mutex around the store, but releasing a guard that mutated
+//! it publishes an immutable snapshot (a detached read view plus stats)
+//! behind an `Arc`. Readers clone the current `Arc` and query it with no lock
+//! held, so they never wait for a writer and always see the last committed
+//! version. Records live in a persistent `RecordSet`, so publishing shares
+//! them with the writer instead of copying: O(1), plus O(log n) for each
+//! record the next write touches. Stats are computed on first use per
+//! snapshot. Reads don't mutate: access counts queue up on the side, one
+//! counter per record so read-only load can't grow the queue past the
+//! store's size, and the next writer applies them, which is what pruning
+//! relies on.
+
+use crate::memory_record::{MemoryRecord, MemoryType, Priority};
+use crate::memory_store::{MemoryStore, RecordPatch};
+use crate::record_set::RecordSet;
+use crate::persistence::{InMemoryBackend, MemoryBackend};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+    Arc, LockResult, Mutex, MutexGuard, OnceLock, PoisonError, RwLock, TryLockError,
+    TryLockResult,
+};
+use uuid::Uuid;
+
+/// Structured query over a snapshot. Unset fields match everything; the
+/// timestamp range is inclusive at both ends. `offset`/`limit` trim the
+/// sorted match list before it is paginated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryQuery {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+    pub record_type: Option<MemoryType>,
+    pub from_timestamp: Option<DateTime<Utc>>,
+    pub to_timestamp: Option<DateTime<Utc>>,
+    pub min_relevance: Option<f64>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub sort_by: Option<SortCriteria>,
+}
+
+impl MemoryQuery {
+    pub fn matches(&self, r: &MemoryRecord) -> bool {
+        self.actor.as_ref().is_none_or(|a| &r.actor == a)
+            && self.action.as_ref().is_none_or(|a| &r.action == a)
+            && self.target.as_ref().is_none_or(|t| &r.target == t)
+            && self
+                .record_type
+                .as_ref()
+                .is_none_or(|t| &r.record_type == t)
+            && self.from_timestamp.is_none_or(|from| r.timestamp >= from)
+            && self.to_timestamp.is_none_or(|to| r.timestamp <= to)
+            && self
+                .min_relevance
+                .is_none_or(|min| r.relevance_score >= min)
+    }
+}
+
+/// Result ordering; every criterion sorts descending. Without one, results
+/// come back in insertion order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SortCriteria {
+    Timestamp,
+    Relevance,
+    AccessCount,
+    LastAccessed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedResult {
+    pub records: Vec<MemoryRecord>,
+    pub total_count: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub total_pages: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchOperationResult {
+    pub successful: Vec<Uuid>,
+    pub failed: Vec<(MemoryRecord, String)>,
+    pub total_processed: usize,
+    pub processing_time_ms: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStoreStats {
+    pub total_records: usize,
+    pub records_by_type: HashMap<MemoryType, usize>,
+    pub avg_access_count: f64,
+    pub avg_relevance_score: f64,
+    pub oldest_record: Option<DateTime<Utc>>,
+    pub newest_record: Option<DateTime<Utc>>,
+    pub cache_hit_rate: f64,
+}
+
+impl MemoryStoreStats {
+    fn of(records: &RecordSet) -> Self {
+        let mut stats = Self {
+            total_records: records.len(),
+            ..Self::default()
+        };
+        if records.is_empty() {
+            return stats;
+        }
+        let mut total_access = 0u64;
+        let mut total_relevance = 0.0;
+        for r in records {
+            *stats
+                .records_by_type
+                .entry(r.record_type.clone())
+                .or_insert(0) += 1;
+            total_access += r.access_count as u64;
+            total_relevance += r.relevance_score;
+        }
+        stats.avg_access_count = total_access as f64 / records.len() as f64;
+        stats.avg_relevance_score = total_relevance / records.len() as f64;
+        stats.oldest_record = records.iter().map(|r| r.timestamp).min();
+        stats.newest_record = records.iter().map(|r| r.timestamp).max();
+        stats
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryStoreConfig {
+    pub max_cache_size: usize,
+    pub cache_ttl_seconds: i64,
+    pub auto_prune_enabled: bool,
+    pub prune_threshold_days: i64,
+    pub min_access_threshold: u32,
+    pub batch_size: usize,
+}
+
+impl Default for MemoryStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_cache_size: 1000,
+            cache_ttl_seconds: 300, // 5 minutes
+            auto_prune_enabled: true,
+            prune_threshold_days: 30,
+            min_access_threshold: 2,
+            batch_size: 100,
+        }
+    }
+}
+
+/// One committed version of the store. Derefs to a read-only `MemoryStore`,
+/// so every `&self` lookup (`find_by_*`, `search_semantic`, …) works on it.
+pub struct MemorySnapshot {
+    version: u64,
+    view: MemoryStore<InMemoryBackend>,
+    stats: OnceLock<MemoryStoreStats>,
+}
+
+impl MemorySnapshot {
+    fn of<B: MemoryBackend>(version: u64, store: &MemoryStore<B>) -> Self {
+        Self {
+            version,
+            view: store.read_view(),
+            stats: OnceLock::new(),
+        }
+    }
+
+    /// Bumped by every write guard that mutated the store.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn stats(&self) -> &MemoryStoreStats {
+        self.stats.get_or_init(|| MemoryStoreStats::of(self.view.all()))
+    }
+
+    /// All records matching `query`, sorted and trimmed by its
+    /// `offset`/`limit`. Uses the actor/action/target indices when set.
+    pub fn matching(&self, query: &MemoryQuery) -> Vec<&MemoryRecord> {
+        let candidates = if let Some(actor) = &query.actor {
+            self.view.find_by_actor(actor)
+        } else if let Some(action) = &query.action {
+            self.view.find_by_action(action)
+        } else if let Some(target) = &query.target {
+            self.view.find_by_target(target)
+        } else {
+            self.view.all().iter().collect()
+        };
+        let mut hits: Vec<&MemoryRecord> = candidates
+            .into_iter()
+            .filter(|r| query.matches(r))
+            .collect();
+        if let Some(criteria) = &query.sort_by {
+            sort_records(&mut hits, criteria);
+        }
+        hits.into_iter()
+            .skip(query.offset.unwrap_or(0))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Page `page` (zero-based) of `matching(query)`.
+    pub fn query_paginated(
+        &self,
+        query: &MemoryQuery,
+        page: usize,
+        page_size: usize,
+    ) -> PaginatedResult {
+        let page_size = page_size.max(1);
+        let hits = self.matching(query);
+        let total_count = hits.len();
+        let start = page.saturating_mul(page_size).min(total_count);
+        let end = (start + page_size).min(total_count);
+        PaginatedResult {
+            records: hits[start..end].iter().map(|r| (*r).clone()).collect(),
+            total_count,
+            page,
+            page_size,
+            total_pages: total_count.div_ceil(page_size),
+        }
+    }
+
+    /// Records scoring at least `threshold` on `MemoryRecord::similarity_score`
+    /// against `record` (excluding itself), best first.
+    pub fn find_similar(
+        &self,
+        record: &MemoryRecord,
+        threshold: f64,
+        limit: usize,
+    ) -> Vec<(&MemoryRecord, f64)> {
+        let mut similar: Vec<(&MemoryRecord, f64)> = self
+            .view
+            .all()
+            .iter()
+            .filter(|r| r.id != record.id)
+            .map(|r| (r, record.similarity_score(r)))
+            .filter(|(_, score)| *score >= threshold)
+            .collect();
+        similar.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        similar.truncate(limit);
+        similar
+    }
+}
+
+impl Deref for MemorySnapshot {
+    type Target = MemoryStore<InMemoryBackend>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+fn sort_records(records: &mut [&MemoryRecord], criteria: &SortCriteria) {
+    match criteria {
+        SortCriteria::Timestamp => records.sort_by_key(|r| Reverse(r.timestamp)),
+        SortCriteria::Relevance => records.sort_by(|a, b| {
+            b.relevance_score
+                .partial_cmp(&a.relevance_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        SortCriteria::AccessCount => records.sort_by_key(|r| Reverse(r.access_count)),
+        SortCriteria::LastAccessed => records.sort_by_key(|r| Reverse(r.last_accessed)),
+    }
+}
+
+/// Paginated results for one snapshot version; a new version empties it.
+struct QueryCache {
+    version: u64,
+    entries: HashMap<String, (PaginatedResult, DateTime<Utc>)>,
+    max_size: usize,
+    ttl_seconds: i64,
+}
+
+impl QueryCache {
+    fn get(&mut self, version: u64, key: &str) -> Option<PaginatedResult> {
+        if version != self.version {
+            self.entries.clear();
+            self.version = version;
+            return None;
+        }
+        let (result, at) = self.entries.get(key)?;
+        ((Utc::now() - *at).num_seconds() < self.ttl_seconds).then(|| result.clone())
+    }
+
+    fn set(&mut self, version: u64, key: String, result: PaginatedResult) {
+        if version != self.version || self.max_size == 0 {
+            return;
+        }
+        if self.entries.len() >= self.max_size {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, at))| *at)
+                .map(|(k, _)| k.clone());
+            if let Some(k) = oldest {
+                self.entries.remove(&k);
+            }
+        }
+        self.entries.insert(key, (result, Utc::now()));
+    }
+}
+
+/// A `MemoryStore<B>` that any number of threads can read while one writes.
+///
+/// Reads go through [`snapshot`](Self::snapshot); writes through
+/// [`lock`](Self::lock), whose guard derefs to the store and publishes a new
+/// snapshot on drop if it was mutably borrowed. The paginated query, pruning
+/// and similarity API of the former `OptimizedMemoryStore` sits on top.
+pub struct ConcurrentMemoryStore<B: MemoryBackend> {
+    writer: Mutex<MemoryStore<B>>,
+    published: RwLock<Arc<MemorySnapshot>>,
+    /// Accesses per id returned by paginated queries, applied by the next
+    /// writer.
+    accessed: Mutex<HashMap<Uuid, u32>>,
+    cache: Mutex<QueryCache>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    config: MemoryStoreConfig,
+}
+
+/// Exclusive write access; see [`ConcurrentMemoryStore::lock`].
+pub struct MemoryStoreGuard<'a, B: MemoryBackend> {
+    owner: &'a ConcurrentMemoryStore<B>,
+    store: MutexGuard<'a, MemoryStore<B>>,
+    dirty: bool,
+}
+
+impl<B: MemoryBackend> Deref for MemoryStoreGuard<'_, B> {
+    type Target = MemoryStore<B>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.store
+    }
+}
+
+impl<B: MemoryBackend> DerefMut for MemoryStoreGuard<'_, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dirty = true;
+        &mut self.store
+    }
+}
+
+impl<B: MemoryBackend> Drop for MemoryStoreGuard<'_, B> {
+    fn drop(&mut self) {
+        if self.dirty {
+            self.owner.publish(&self.store);
+        }
+    }
+}
+
+impl<B: MemoryBackend> ConcurrentMemoryStore<B> {
+    pub fn new(store: MemoryStore<B>) -> Self {
+        Self::with_config(store, MemoryStoreConfig::default())
+    }
+
+    pub fn with_config(store: MemoryStore<B>, config: MemoryStoreConfig) -> Self {
+        let snapshot = MemorySnapshot::of(0, &store);
+        Self {
+            writer: Mutex::new(store),
+            published: RwLock::new(Arc::new(snapshot)),
+            accessed: Mutex::new(HashMap::new()),
+            cache: Mutex::new(QueryCache {
+                version: 0,
+                entries: HashMap::new(),
+                max_size: config.max_cache_size,
+                ttl_seconds: config.cache_ttl_seconds,
+            }),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &MemoryStoreConfig {
+        &self.config
+    }
+
+    /// The latest committed version. Never waits for a writer.
+    pub fn snapshot(&self) -> Arc<MemorySnapshot> {
+        Arc::clone(
+            &self
+                .published
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Exclusive write access, with `Mutex::lock` semantics: a panic while a
+    /// guard is held poisons the store, and the error still carries the guard.
+    pub fn lock(&self) -> LockResult<MemoryStoreGuard<'_, B>> {
+        match self.writer.lock() {
+            Ok(store) => Ok(self.guard(store)),
+            Err(poisoned) => Err(PoisonError::new(self.guard(poisoned.into_inner()))),
+        }
+    }
+
+    pub fn try_lock(&self) -> TryLockResult<MemoryStoreGuard<'_, B>> {
+        match self.writer.try_lock() {
+            Ok(store) => Ok(self.guard(store)),
+            Err(TryLockError::WouldBlock) => Err(TryLockError::WouldBlock),
+            Err(TryLockError::Poisoned(p)) => Err(TryLockError::Poisoned(PoisonError::new(
+                self.guard(p.into_inner()),
+            ))),
+        }
+    }
+
+    pub fn into_inner(self) -> MemoryStore<B> {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn guard<'a>(&'a self, mut store: MutexGuard<'a, MemoryStore<B>>) -> MemoryStoreGuard<'a, B> {
+        let accessed =
+            std::mem::take(&mut *self.accessed.lock().unwrap_or_else(PoisonError::into_inner));
+        if !accessed.is_empty() {
+            store.touch(&accessed);
+        }
+        MemoryStoreGuard {
+            owner: self,
+            store,
+            dirty: !accessed.is_empty(),
+        }
+    }
+
+    fn write(&self) -> MemoryStoreGuard<'_, B> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Called with the writer lock held, so versions are published in order.
+    fn publish(&self, store: &MemoryStore<B>) {
+        let version = self.snapshot().version + 1;
+        let snapshot = Arc::new(MemorySnapshot::of(version, store));
+        *self
+            .published
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = snapshot;
+    }
+
+    /// Add `record`. The write screen runs on the latest snapshot before the
+    /// writer lock is taken, so a slow classifier holds up no other writer.
+    pub fn add_memory(&self, mut record: MemoryRecord) -> Result<Uuid> {
+        let id = record.id;
+        self.snapshot().screen(&mut record)?;
+        self.write().add_prescreened(record)?;
+        Ok(id)
+    }
+
+    /// Add `records` under one guard, so the batch publishes one snapshot.
+    /// Records are screened before the guard is taken.
+    pub fn add_memories_batch(&self, records: Vec<MemoryRecord>) -> BatchOperationResult {
+        let started = std::time::Instant::now();
+        let total_processed = records.len();
+        let mut successful = Vec::new();
+        let mut failed = Vec::new();
+        let snapshot = self.snapshot();
+        let mut screened = Vec::with_capacity(records.len());
+        for mut record in records {
+            match snapshot.screen(&mut record) {
+                Ok(_) => screened.push(record),
+                Err(e) => failed.push((record, e.to_string())),
+            }
+        }
+        let mut store = self.write();
+        for record in screened {
+            let id = record.id;
+            match store.add_prescreened(record.clone()) {
+                Ok(()) => successful.push(id),
+                Err(e) => failed.push((record, e.to_string())),
+            }
+        }
+        BatchOperationResult {
+            successful,
+            failed,
+            total_processed,
+            processing_time_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Page `page` of `query` against the latest snapshot, cached per
+    /// snapshot version. Returned records count as accessed.
+    pub fn query_memories_paginated(
+        &self,
+        query: &MemoryQuery,
+        page: usize,
+        page_size: usize,
+    ) -> PaginatedResult {
+        let snapshot = self.snapshot();
+        let key = format!("{:?}:{}:{}", query, page, page_size);
+        let cached = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(snapshot.version, &key);
+        let result = match cached {
+            Some(hit) => {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                hit
+            }
+            None => {
+                self.cache_misses.fetch_add(1, Ordering::Relaxed);
+                let result = snapshot.query_paginated(query, page, page_size);
+                self.cache
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .set(snapshot.version, key, result.clone());
+                result
+            }
+        };
+        let mut accessed = self.accessed.lock().unwrap_or_else(PoisonError::into_inner);
+        for r in &result.records {
+            let count = accessed.entry(r.id).or_default();
+            *count = count.saturating_add(1);
+        }
+        drop(accessed);
+        result
+    }
+
+    pub fn get_stats(&self) -> MemoryStoreStats {
+        let mut stats = self.snapshot().stats().clone();
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let total = hits + self.cache_misses.load(Ordering::Relaxed);
+        if total > 0 {
+            stats.cache_hit_rate = hits as f64 / total as f64;
+        }
+        stats
+    }
+
+    /// `MemoryStore::update_with`, screening the updated record on the latest
+    /// snapshot before the writer lock is taken. If the record changed in
+    /// between, the patch is applied and screened again under the lock.
+    /// `Ok(None)` when no record has this id.
+    pub fn update_memory(&self, id: Uuid, patch: &RecordPatch) -> Result<Option<MemoryRecord>> {
+        let snapshot = self.snapshot();
+        let Some(base) = snapshot.find_by_id(id).map(MemoryRecord::compute_hash) else {
+            return Ok(None);
+        };
+        let Some(mut next) = snapshot.patched(id, patch) else {
+            return Ok(None);
+        };
+        snapshot.screen(&mut next)?;
+        let mut store = self.write();
+        match store.find_by_id(id).map(MemoryRecord::compute_hash) {
+            None => Ok(None),
+            Some(current) if current == base => store.update_prescreened(next).map(Some),
+            Some(_) => store.update_with(id, patch).map(Some),
+        }
+    }
+
+    /// Delete stale, rarely accessed or irrelevant records
+    /// (`MemoryRecord::should_prune`). Pinned records are never pruned.
+    pub fn prune_memories(&self) -> usize {
+        if !self.config.auto_prune_enabled {
+            return 0;
+        }
+        let mut store = self.write();
+        let ids: Vec<Uuid> = store
+            .all()
+            .iter()
+            .filter(|r| {
+                r.priority != Priority::Pinned
+                    && r.should_prune(
+                        self.config.min_access_threshold,
+                        self.config.prune_threshold_days,
+                    )
+            })
+            .map(|r| r.id)
+            .collect();
+        ids.into_iter().filter(|id| store.delete_by_id(*id)).count()
+    }
+
+    pub fn remove_memory(&self, id: Uuid) -> bool {
+        self.write().delete_by_id(id)
+    }
+
+    pub fn find_similar_memories(
+        &self,
+        record: &MemoryRecord,
+        threshold: f64,
+        limit: usize,
+    ) -> Vec<(MemoryRecord, f64)> {
+        self.snapshot()
+            .find_similar(record, threshold, limit)
+            .into_iter()
+            .map(|(r, score)| (r.clone(), score))
+            .collect()
+    }
+}
@@ -12,10 +12,11 @@ use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::archive_store::ArchiveStore;
-use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_record::{MemoryRecord, MemoryType, RecordStatus};
 use crate::memory_store::MemoryStore;
 use crate::payloads::{BeliefPayload, EpistemicStatus, SkillPayload};
 use crate::persistence::MemoryBackend;
+use crate::summarizer::Summarizer;
 use crate::symbolic_store::{GraphDatabase, SymbolicStore};
 use crate::tx_log::{TxKind, TxLog};
 
@@ -75,6 +76,29 @@ pub fn consolidate<B: MemoryBackend, S: GraphDatabase>(
     log: &TxLog,
     config: &ConsolidationConfig,
 ) -> Result<ConsolidationReport, String> {
+    consolidate_with_summarizer(store, archive, graph, log, config, None)
+}
+
+/// `consolidate` with an optional `Summarizer`.
+///
+/// With a summarizer, each group's summary record carries the generated text
+/// (`metadata.summary`), its method and faithfulness score. Without one the
+/// record is the legacy `summary:<tags>` marker. Either way `evidence` lists
+/// the archived source ids.
+pub fn consolidate_with_summarizer<B: MemoryBackend, S: GraphDatabase>(
+    store: &mut MemoryStore<B>,
+    archive: &mut ArchiveStore,
+    graph: &mut SymbolicStore<S>,
+    log: &TxLog,
+    config: &ConsolidationConfig,
+    summarizer: Option<&Summarizer>,
+) -> Result<ConsolidationReport, String> {
+    let _span = tracing::info_span!("consolidation.consolidate").entered();
+    crate::telemetry::METRICS.inc(
+        crate::telemetry::CONSOLIDATION_RUNS_TOTAL,
+        &[("kind", "grouped")],
+        1.0,
+    );
     // Group active Temporal records by (actor, sorted tags)
     let groups = group_temporal_records(store, config.min_group_size);
 
@@ -84,6 +108,13 @@ pub fn consolidate<B: MemoryBackend, S: GraphDatabase>(
     let mut archived_ids: Vec<Uuid> = Vec::new();
 
     for (key, ids) in groups {
+        // Summarize before the sources leave the hot store
+        let generated = summarizer.map(|s| {
+            let sources: Vec<&MemoryRecord> =
+                ids.iter().filter_map(|id| store.find_by_id(*id)).collect();
+            s.summarize(&sources)
+        });
+
         // Archive originals
         for id in &ids {
             if let Some(rec) = store.find_by_id(*id).cloned() {
@@ -97,16 +128,28 @@ pub fn consolidate<B: MemoryBackend, S: GraphDatabase>(
         }
 
         // Insert summary record
-        let summary = MemoryRecord::new(
+        let mut meta = serde_json::json!({ "source_count": ids.len(), "tags": key.1 });
+        if let Some(g) = &generated {
+            meta["summary"] = serde_json::json!(g.text);
+            meta["summary_method"] = serde_json::json!(g.method);
+            meta["faithfulness"] = serde_json::json!(g.faithfulness);
+        }
+        let mut summary = MemoryRecord::new(
             MemoryType::Temporal,
             key.0.clone(),
             "consolidated".into(),
             format!("summary:{}", key.1),
-            serde_json::json!({ "source_count": ids.len(), "tags": key.1 }),
+            meta,
         );
+        summary.evidence = ids.clone();
         let summary_id = summary.id;
         store.add(summary).map_err(|e| format!("store error: {e}"))?;
-        log.append(TxKind::Consolidate, vec![summary_id], &key.0);
+        log.append_with_changes(
+            TxKind::Consolidate,
+            vec![summary_id],
+            &key.0,
+            store.take_changes(),
+        );
 
         // Add graph node for summary
         let mut props = HashMap::new();
@@ -134,7 +177,7 @@ fn group_temporal_records<B: MemoryBackend>(
 ) -> HashMap<(String, String), Vec<Uuid>> {
     let mut groups: HashMap<(String, String), Vec<Uuid>> = HashMap::new();
     for rec in store.all() {
-        if rec.record_type != MemoryType::Temporal || rec.status != "active" {
+        if rec.record_type != MemoryType::Temporal || rec.status != RecordStatus::Active {
             continue;
         }
         let mut sorted_tags = rec.tags.clone();
@@ -282,7 +325,7 @@ pub fn mine_causal_motifs<B: MemoryBackend>(
     let mut groups: HashMap<String, (usize, Vec<Uuid>)> = HashMap::new();
 
     for rec in &all {
-        if rec.record_type != MemoryType::Temporal || rec.status != "active" {
+        if rec.record_type != MemoryType::Temporal || rec.status != RecordStatus::Active {
             continue;
         }
         if rec.derived_from.is_none() {
@@ -373,6 +416,16 @@ pub fn induce_belief_record(motif: &CausalMotif, skill_id: Uuid, actor: &str) ->
     rec
 }
 
+/// Write a summary into a Belief record's `justification` (payload + metadata).
+fn attach_justification(belief: &mut MemoryRecord, summary: &crate::summarizer::Summary) {
+    if let Ok(mut payload) = serde_json::from_value::<BeliefPayload>(belief.metadata.clone()) {
+        payload.justification = summary.text.clone();
+        belief.metadata = serde_json::to_value(&payload).unwrap_or_default();
+    }
+    belief.metadata["summary_method"] = serde_json::json!(summary.method);
+    belief.metadata["faithfulness"] = serde_json::json!(summary.faithfulness);
+}
+
 /// Mine motifs, induce Skill+Belief records, archive source episodes.
 ///
 /// Returns source IDs that were archived (to be committed to cold store by the caller).
@@ -382,6 +435,25 @@ pub fn mine_and_consolidate<B: MemoryBackend>(
     min_frequency: usize,
     actor: &str,
 ) -> Result<MiningReport, String> {
+    mine_and_consolidate_with_summarizer(store, log, min_frequency, actor, None)
+}
+
+/// `mine_and_consolidate` with an optional `Summarizer`: each induced Belief's
+/// `justification` is a summary of the motif's source episodes, and its
+/// metadata records the summary method and faithfulness.
+pub fn mine_and_consolidate_with_summarizer<B: MemoryBackend>(
+    store: &mut MemoryStore<B>,
+    log: Option<&TxLog>,
+    min_frequency: usize,
+    actor: &str,
+    summarizer: Option<&Summarizer>,
+) -> Result<MiningReport, String> {
+    let _span = tracing::info_span!("consolidation.mine", min_frequency = min_frequency).entered();
+    crate::telemetry::METRICS.inc(
+        crate::telemetry::CONSOLIDATION_RUNS_TOTAL,
+        &[("kind", "mined")],
+        1.0,
+    );
     let motifs = mine_causal_motifs(store, min_frequency, 2, 5);
 
     if motifs.is_empty() {
@@ -397,11 +469,24 @@ pub fn mine_and_consolidate<B: MemoryBackend>(
         let skill_id = skill.id;
         store.add(skill).map_err(|e| format!("skill add: {e}"))?;
         if let Some(tx) = log {
-            tx.append(TxKind::Consolidate, vec![skill_id], actor);
+            tx.append_with_changes(
+                TxKind::Consolidate,
+                vec![skill_id],
+                actor,
+                store.take_changes(),
+            );
         }
         skills_induced += 1;
 
-        let belief = induce_belief_record(motif, skill_id, actor);
+        let mut belief = induce_belief_record(motif, skill_id, actor);
+        if let Some(s) = summarizer {
+            let sources: Vec<&MemoryRecord> = motif
+                .member_ids
+                .iter()
+                .filter_map(|id| store.find_by_id(*id))
+                .collect();
+            attach_justification(&mut belief, &s.summarize(&sources));
+        }
         store.add(belief).map_err(|e| format!("belief add: {e}"))?;
         beliefs_induced += 1;
 
@@ -1,14 +1,42 @@
+//! Conversation memory — chat turns, in memory or threaded through a `MemoryStore`.
+//!
+//! Chain-of-thought: `ConversationMemory` is a plain `Vec` that forgets everything
+//! on restart and knows nothing about sessions. `ConversationThreads` stores each
+//! turn as a record (tags `conversation` + `session:<id>`, metadata carrying the
+//! session id, turn index, role and reply-to), so threads survive restarts and go
+//! through the same audit, tx log and retrieval paths as every other memory. When
+//! a thread's unsummarized text exceeds `summary_budget_chars`, the older turns
+//! are folded into a rolling summary record; the turns themselves are kept, and a
+//! summary covers a contiguous turn range so the next one picks up after it.
+//! User turns are scanned for stated facts and preferences, which become `Belief`
+//! records whose `evidence` points at the turn they came from.
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_store::MemoryStore;
+use crate::payloads::{BeliefPayload, EpistemicStatus};
+use crate::persistence::MemoryBackend;
+use crate::summarizer::Summarizer;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationMessage {
     pub id: Uuid,
+    /// Speaker role, e.g. "user" or "assistant".
     pub sender: String,
     pub text: String,
     pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// 0-based position in the thread.
+    #[serde(default)]
+    pub turn: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +62,9 @@ impl ConversationMemory {
             sender: sender.to_string(),
             text: text.to_string(),
             timestamp: Utc::now(),
+            session_id: None,
+            turn: self.messages.len(),
+            reply_to: self.messages.last().map(|m| m.id),
         };
         self.messages.push(msg.clone());
         msg.id
@@ -56,3 +87,435 @@ impl ConversationMemory {
         self.messages.clear();
     }
 }
+
+// ─── Persistent threads ──────────────────────────────────────────────────────
+
+/// `action` of a stored conversation turn.
+pub const TURN_ACTION: &str = "conversation_turn";
+/// `action` of a rolling thread summary.
+pub const SUMMARY_ACTION: &str = "conversation_summary";
+pub const CONVERSATION_TAG: &str = "conversation";
+
+pub fn session_tag(session_id: &str) -> String {
+    format!("session:{session_id}")
+}
+
+#[derive(Debug, Clone)]
+pub struct ThreadConfig {
+    /// Unsummarized turn text (chars) above which older turns get summarized.
+    pub summary_budget_chars: usize,
+    /// Most recent turns never folded into a rolling summary.
+    pub keep_recent: usize,
+    /// Extract facts/preferences from user turns into Belief records.
+    pub extract_beliefs: bool,
+}
+
+impl Default for ThreadConfig {
+    fn default() -> Self {
+        Self {
+            summary_budget_chars: 4000,
+            keep_recent: 4,
+            extract_beliefs: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementKind {
+    Fact,
+    Preference,
+}
+
+/// A fact or preference stated in a turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedStatement {
+    pub kind: StatementKind,
+    pub proposition: String,
+}
+
+/// Result of appending a turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppendOutcome {
+    pub message: ConversationMessage,
+    /// Belief records created from this turn.
+    pub beliefs: Vec<Uuid>,
+    /// Rolling summary written because the thread went over budget.
+    pub summary: Option<Uuid>,
+}
+
+/// One page of a thread, oldest turn first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadPage {
+    pub session_id: String,
+    pub total: usize,
+    pub offset: usize,
+    pub turns: Vec<ConversationMessage>,
+    /// Text of the newest rolling summary, if any.
+    pub summary: Option<String>,
+}
+
+/// Session-threaded conversations stored as `MemoryStore` records.
+#[derive(Default)]
+pub struct ConversationThreads {
+    pub config: ThreadConfig,
+    summarizer: Option<Arc<Summarizer>>,
+}
+
+impl ConversationThreads {
+    pub fn new(config: ThreadConfig) -> Self {
+        Self {
+            config,
+            summarizer: None,
+        }
+    }
+
+    /// Summarize with `summarizer` instead of the default extractive one.
+    pub fn with_summarizer(mut self, summarizer: Arc<Summarizer>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Append a turn to `session_id`. `reply_to` defaults to the previous turn.
+    /// May also write Belief records and a rolling summary (see `AppendOutcome`).
+    pub fn append<B: MemoryBackend>(
+        &self,
+        store: &mut MemoryStore<B>,
+        session_id: &str,
+        role: &str,
+        text: &str,
+        reply_to: Option<Uuid>,
+    ) -> Result<AppendOutcome> {
+        let previous = self.turns(store, session_id);
+        if let Some(parent) = reply_to {
+            if !previous.iter().any(|t| t.id == parent) {
+                anyhow::bail!("reply_to {parent} is not a turn in session {session_id}");
+            }
+        }
+        // Next index after the highest stored turn: earlier turns may have been
+        // archived by consolidation, so the count is not a safe index.
+        let turn = previous.last().map(|t| t.turn + 1).unwrap_or(0);
+        let reply_to = reply_to.or_else(|| previous.last().map(|t| t.id));
+
+        let rec = turn_record(session_id, turn, role, text, reply_to);
+        let message = turn_from_record(&rec).expect("freshly built turn record");
+        store.add(rec)?;
+
+        let mut beliefs = Vec::new();
+        if self.config.extract_beliefs && role == "user" {
+            for stmt in extract_statements(text) {
+                let belief = belief_record(session_id, &message, &stmt);
+                beliefs.push(belief.id);
+                store.add(belief)?;
+            }
+        }
+
+        let summary = if self.over_budget(store, session_id) {
+            self.summarize(store, session_id, false)?
+        } else {
+            None
+        };
+        Ok(AppendOutcome {
+            message,
+            beliefs,
+            summary,
+        })
+    }
+
+    /// Every turn of `session_id`, ordered by turn index.
+    pub fn turns<B: MemoryBackend>(
+        &self,
+        store: &MemoryStore<B>,
+        session_id: &str,
+    ) -> Vec<ConversationMessage> {
+        let tag = session_tag(session_id);
+        let mut turns: Vec<ConversationMessage> = store
+            .find_by_tags(&[tag.as_str()])
+            .into_iter()
+            .filter(|r| r.action == TURN_ACTION)
+            .filter_map(turn_from_record)
+            .collect();
+        turns.sort_by_key(|t| t.turn);
+        turns
+    }
+
+    /// `limit` turns starting at `offset`, plus the newest summary.
+    pub fn page<B: MemoryBackend>(
+        &self,
+        store: &MemoryStore<B>,
+        session_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> ThreadPage {
+        let turns = self.turns(store, session_id);
+        ThreadPage {
+            session_id: session_id.to_string(),
+            total: turns.len(),
+            offset,
+            turns: turns.into_iter().skip(offset).take(limit).collect(),
+            summary: self.latest_summary(store, session_id).map(|r| r.target.clone()),
+        }
+    }
+
+    /// Rolling summary records of `session_id`, oldest first.
+    pub fn summaries<'a, B: MemoryBackend>(
+        &self,
+        store: &'a MemoryStore<B>,
+        session_id: &str,
+    ) -> Vec<&'a MemoryRecord> {
+        let tag = session_tag(session_id);
+        let mut out: Vec<&MemoryRecord> = store
+            .find_by_tags(&[tag.as_str()])
+            .into_iter()
+            .filter(|r| r.action == SUMMARY_ACTION)
+            .collect();
+        out.sort_by_key(|r| r.metadata["to_turn"].as_u64().unwrap_or(0));
+        out
+    }
+
+    pub fn latest_summary<'a, B: MemoryBackend>(
+        &self,
+        store: &'a MemoryStore<B>,
+        session_id: &str,
+    ) -> Option<&'a MemoryRecord> {
+        self.summaries(store, session_id).pop()
+    }
+
+    /// Session ids that have at least one stored turn.
+    pub fn sessions<B: MemoryBackend>(&self, store: &MemoryStore<B>) -> Vec<String> {
+        let mut ids: Vec<String> = store
+            .find_by_tags(&[CONVERSATION_TAG])
+            .into_iter()
+            .filter(|r| r.action == TURN_ACTION)
+            .filter_map(|r| r.metadata["session_id"].as_str().map(str::to_string))
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// Turns not yet covered by a rolling summary.
+    fn unsummarized<B: MemoryBackend>(
+        &self,
+        store: &MemoryStore<B>,
+        session_id: &str,
+    ) -> Vec<ConversationMessage> {
+        let covered = self
+            .latest_summary(store, session_id)
+            .and_then(|r| r.metadata["to_turn"].as_u64())
+            .map(|t| t as usize + 1)
+            .unwrap_or(0);
+        self.turns(store, session_id)
+            .into_iter()
+            .filter(|t| t.turn >= covered)
+            .collect()
+    }
+
+    fn over_budget<B: MemoryBackend>(&self, store: &MemoryStore<B>, session_id: &str) -> bool {
+        let pending = self.unsummarized(store, session_id);
+        pending.len() > self.config.keep_recent
+            && pending.iter().map(|t| t.text.len()).sum::<usize>() > self.config.summary_budget_chars
+    }
+
+    /// Fold unsummarized turns into a summary record. Unless `all`, the newest
+    /// `keep_recent` turns are left out. Returns `None` if there was nothing to fold.
+    pub fn summarize<B: MemoryBackend>(
+        &self,
+        store: &mut MemoryStore<B>,
+        session_id: &str,
+        all: bool,
+    ) -> Result<Option<Uuid>> {
+        let mut pending = self.unsummarized(store, session_id);
+        if !all {
+            let keep = self.config.keep_recent.min(pending.len());
+            pending.truncate(pending.len() - keep);
+        }
+        let (Some(first), Some(last)) = (pending.first(), pending.last()) else {
+            return Ok(None);
+        };
+        let (from_turn, to_turn) = (first.turn, last.turn);
+        let ids: Vec<Uuid> = pending.iter().map(|t| t.id).collect();
+
+        let fallback = Summarizer::extractive();
+        let summarizer = self.summarizer.as_deref().unwrap_or(&fallback);
+        let sources: Vec<&MemoryRecord> = ids.iter().filter_map(|id| store.find_by_id(*id)).collect();
+        let summary = summarizer.summarize(&sources);
+
+        let mut rec = MemoryRecord::new(
+            MemoryType::Temporal,
+            "conversation".into(),
+            SUMMARY_ACTION.into(),
+            summary.text.clone(),
+            serde_json::json!({
+                "session_id": session_id,
+                "from_turn": from_turn,
+                "to_turn": to_turn,
+                "summary_method": summary.method,
+                "faithfulness": summary.faithfulness,
+            }),
+        );
+        rec.tags = vec![CONVERSATION_TAG.into(), session_tag(session_id)];
+        rec.evidence = summary.evidence;
+        seal(&mut rec);
+        let id = rec.id;
+        store.add(rec)?;
+        Ok(Some(id))
+    }
+}
+
+fn seal(rec: &mut MemoryRecord) {
+    let hash = rec.compute_hash();
+    rec.integrity = Some(hash.clone());
+    rec.content_hash = Some(hash);
+}
+
+/// Build the stored form of a turn. `ConversationThreads::append` and the
+/// chat importers both go through this so imported transcripts page like live ones.
+pub fn turn_record(
+    session_id: &str,
+    turn: usize,
+    role: &str,
+    text: &str,
+    reply_to: Option<Uuid>,
+) -> MemoryRecord {
+    let mut rec = MemoryRecord::new(
+        MemoryType::Temporal,
+        role.to_string(),
+        TURN_ACTION.into(),
+        text.to_string(),
+        serde_json::json!({
+            "session_id": session_id,
+            "turn": turn,
+            "role": role,
+            "reply_to": reply_to,
+        }),
+    );
+    rec.tags = vec![CONVERSATION_TAG.into(), session_tag(session_id)];
+    seal(&mut rec);
+    rec
+}
+
+/// Rebuild a `ConversationMessage` from a stored turn record.
+pub fn turn_from_record(r: &MemoryRecord) -> Option<ConversationMessage> {
+    if r.action != TURN_ACTION {
+        return None;
+    }
+    Some(ConversationMessage {
+        id: r.id,
+        sender: r.metadata["role"].as_str().unwrap_or(&r.actor).to_string(),
+        text: r.target.clone(),
+        timestamp: r.timestamp,
+        session_id: r.metadata["session_id"].as_str().map(str::to_string),
+        turn: r.metadata["turn"].as_u64()? as usize,
+        reply_to: r.metadata["reply_to"].as_str().and_then(|s| Uuid::parse_str(s).ok()),
+    })
+}
+
+fn belief_record(session_id: &str, turn: &ConversationMessage, stmt: &ExtractedStatement) -> MemoryRecord {
+    let kind = match stmt.kind {
+        StatementKind::Fact => "fact",
+        StatementKind::Preference => "preference",
+    };
+    let payload = BeliefPayload {
+        proposition: stmt.proposition.clone(),
+        justification: format!("stated by {} in session {} turn {}", turn.sender, session_id, turn.turn),
+        confidence: 0.8,
+        epistemic_status: EpistemicStatus::Observed,
+        causal_source_ids: vec![turn.id],
+        ..Default::default()
+    };
+    let mut meta = serde_json::to_value(&payload).unwrap_or_default();
+    meta["session_id"] = serde_json::json!(session_id);
+    meta["statement_kind"] = serde_json::json!(kind);
+    let mut rec = MemoryRecord::new(
+        MemoryType::Belief,
+        turn.sender.clone(),
+        format!("extracted_{kind}"),
+        stmt.proposition.clone(),
+        meta,
+    );
+    rec.confidence = payload.confidence;
+    rec.tags = vec![CONVERSATION_TAG.into(), session_tag(session_id), kind.into()];
+    rec.evidence = vec![turn.id];
+    rec.derived_from = Some(turn.id);
+    seal(&mut rec);
+    rec
+}
+
+/// Lead-in phrase → (kind, proposition template). `{}` receives the rest of the clause.
+const PATTERNS: &[(&str, StatementKind, &str)] = &[
+    ("i prefer ", StatementKind::Preference, "user prefers {}"),
+    ("i really like ", StatementKind::Preference, "user likes {}"),
+    ("i like ", StatementKind::Preference, "user likes {}"),
+    ("i love ", StatementKind::Preference, "user likes {}"),
+    ("i don't like ", StatementKind::Preference, "user dislikes {}"),
+    ("i do not like ", StatementKind::Preference, "user dislikes {}"),
+    ("i dislike ", StatementKind::Preference, "user dislikes {}"),
+    ("i hate ", StatementKind::Preference, "user dislikes {}"),
+    ("my favorite ", StatementKind::Preference, "user's favorite {}"),
+    ("my favourite ", StatementKind::Preference, "user's favorite {}"),
+    ("my name is ", StatementKind::Fact, "user's name is {}"),
+    ("i live in ", StatementKind::Fact, "user lives in {}"),
+    ("i work at ", StatementKind::Fact, "user works at {}"),
+    ("i work for ", StatementKind::Fact, "user works for {}"),
+    ("i am allergic to ", StatementKind::Fact, "user is allergic to {}"),
+    ("i'm allergic to ", StatementKind::Fact, "user is allergic to {}"),
+    ("remember that ", StatementKind::Fact, "{}"),
+];
+
+/// Rule-based extraction of first-person facts and preferences, one per clause.
+/// Matches at the start of a sentence or of an "and I …"/"but my …" clause,
+/// case-insensitively.
+pub fn extract_statements(text: &str) -> Vec<ExtractedStatement> {
+    let mut out: Vec<ExtractedStatement> = Vec::new();
+    for sentence in text.split(['.', '!', '?', ';', '\n']) {
+        for clause in split_clauses(sentence) {
+            // ASCII lowercasing keeps byte offsets aligned with `clause`.
+            let lower = clause.to_ascii_lowercase();
+            let Some((lead, kind, template)) =
+                PATTERNS.iter().find(|(lead, _, _)| lower.starts_with(lead))
+            else {
+                continue;
+            };
+            let rest = clause[lead.len()..].trim().trim_end_matches([',', ':']);
+            if rest.is_empty() {
+                continue;
+            }
+            let stmt = ExtractedStatement {
+                kind: *kind,
+                proposition: template.replace("{}", rest),
+            };
+            if !out.contains(&stmt) {
+                out.push(stmt);
+            }
+        }
+    }
+    out
+}
+
+/// Split before " and "/" but " when the next word starts a first-person clause.
+fn split_clauses(sentence: &str) -> Vec<&str> {
+    let lower = sentence.to_ascii_lowercase();
+    let mut cuts: Vec<(usize, usize)> = [" and ", " but "]
+        .iter()
+        .flat_map(|conj| {
+            lower.match_indices(conj).map(move |(i, _)| (i, i + conj.len()))
+        })
+        .filter(|&(_, end)| {
+            let next = &lower[end..];
+            next.starts_with("i ") || next.starts_with("i'm ") || next.starts_with("my ")
+        })
+        .collect();
+    cuts.sort_unstable();
+    let mut clauses = Vec::new();
+    let mut pos = 0;
+    for (start, end) in cuts {
+        if start >= pos {
+            clauses.push(sentence[pos..start].trim().trim_end_matches(','));
+            pos = end;
+        }
+    }
+    clauses.push(sentence[pos..].trim());
+    clauses.retain(|c| !c.is_empty());
+    clauses
+}
@@ -1,5 +1,5 @@
 #[cfg(feature = "web-server")]
-use crate::memory_store::MemoryStore;
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
 #[cfg(feature = "web-server")]
 use crate::persistence::MemoryBackend;
 #[cfg(feature = "web-server")]
@@ -7,12 +7,12 @@ use axum::{routing::get, Json, Router};
 
 #[cfg(feature = "web-server")]
 pub fn routes<B: MemoryBackend + Send + 'static>(
-    store: std::sync::Arc<std::sync::Mutex<MemoryStore<B>>>,
+    store: std::sync::Arc<ConcurrentMemoryStore<B>>,
 ) -> Router {
     Router::new().route(
         "/nodes",
         get(move || {
-            let store = store.lock().unwrap();
+            let store = store.snapshot();
             let data: Vec<_> = store.all().iter().map(|r| &r.target).cloned().collect();
             async move { Json(data) }
         }),
@@ -30,7 +30,7 @@ mod tests {
         let path = "dash.jsonl";
         let _ = std::fs::remove_file(path);
         let store = MemoryStore::new(path).unwrap();
-        let arc = std::sync::Arc::new(std::sync::Mutex::new(store));
+        let arc = std::sync::Arc::new(ConcurrentMemoryStore::new(store));
         let _app = routes(arc);
         let _ = std::fs::remove_file(path);
     }
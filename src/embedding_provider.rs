@@ -120,6 +120,339 @@ impl EmbeddingProvider for NoopEmbeddingProvider {
     }
 }
 
+// ─── CLIP text tokenizer ────────────────────────────────────────────────────
+
+use std::collections::HashMap;
+
+const CLIP_BOS: &str = "<|startoftext|>";
+const CLIP_EOS: &str = "<|endoftext|>";
+
+/// Byte-level BPE tokenizer of the CLIP text tower, so learned text
+/// embeddings land in the same space as `OnnxImageEncoder` image embeddings.
+///
+/// Loads the Hugging Face `vocab.json` + `merges.txt` pair shipped with
+/// every CLIP checkpoint. Output is padded (with the end token, as CLIP does)
+/// or truncated to `context_len`, bracketed by start/end tokens.
+pub struct ClipTokenizer {
+    vocab: HashMap<String, i64>,
+    ranks: HashMap<(String, String), usize>,
+    byte_chars: [char; 256],
+    pattern: regex::Regex,
+    bos: i64,
+    eos: i64,
+    pub context_len: usize,
+}
+
+impl ClipTokenizer {
+    pub fn from_files<P: AsRef<std::path::Path>>(
+        vocab_json: P,
+        merges_txt: P,
+    ) -> anyhow::Result<Self> {
+        let vocab: HashMap<String, i64> =
+            serde_json::from_str(&std::fs::read_to_string(vocab_json)?)?;
+        let merges = std::fs::read_to_string(merges_txt)?;
+        let merges = merges
+            .lines()
+            .filter(|l| !l.starts_with("#version"))
+            .filter_map(|l| l.split_once(' '))
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        Self::from_parts(vocab, merges)
+    }
+
+    /// Build from an in-memory vocabulary and merge list (highest priority
+    /// first). The vocabulary must contain the start and end tokens.
+    pub fn from_parts(
+        vocab: HashMap<String, i64>,
+        merges: Vec<(String, String)>,
+    ) -> anyhow::Result<Self> {
+        let bos = *vocab
+            .get(CLIP_BOS)
+            .ok_or_else(|| anyhow::anyhow!("vocabulary has no {CLIP_BOS}"))?;
+        let eos = *vocab
+            .get(CLIP_EOS)
+            .ok_or_else(|| anyhow::anyhow!("vocabulary has no {CLIP_EOS}"))?;
+        let ranks = merges
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| (m, i))
+            .collect();
+        let pattern =
+            regex::Regex::new(r"(?i)'s|'t|'re|'ve|'m|'ll|'d|\p{L}+|\p{N}|[^\s\p{L}\p{N}]+")?;
+        Ok(Self {
+            vocab,
+            ranks,
+            byte_chars: byte_chars(),
+            pattern,
+            bos,
+            eos,
+            context_len: 77,
+        })
+    }
+
+    /// Token ids, `context_len` long. Pieces missing from the vocabulary are
+    /// dropped.
+    pub fn encode(&self, text: &str) -> Vec<i64> {
+        let clean = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let mut ids = vec![self.bos];
+        for word in self.pattern.find_iter(&clean) {
+            let chars: String = word
+                .as_str()
+                .bytes()
+                .map(|b| self.byte_chars[b as usize])
+                .collect();
+            ids.extend(self.bpe(&chars).iter().filter_map(|t| self.vocab.get(t)));
+        }
+        ids.truncate(self.context_len.saturating_sub(1));
+        ids.push(self.eos);
+        ids.resize(self.context_len, self.eos);
+        ids
+    }
+
+    /// Number of real tokens (start and end included) before padding.
+    pub fn token_count(&self, ids: &[i64]) -> usize {
+        ids.iter()
+            .position(|&t| t == self.eos)
+            .map_or(ids.len(), |p| p + 1)
+    }
+
+    fn bpe(&self, word: &str) -> Vec<String> {
+        let mut parts: Vec<String> = word.chars().map(String::from).collect();
+        if let Some(last) = parts.last_mut() {
+            last.push_str("</w>");
+        }
+        while parts.len() > 1 {
+            let best = parts
+                .windows(2)
+                .enumerate()
+                .filter_map(|(i, w)| {
+                    self.ranks
+                        .get(&(w[0].clone(), w[1].clone()))
+                        .map(|r| (*r, i))
+                })
+                .min();
+            let Some((_, i)) = best else { break };
+            let (left, right) = (parts[i].clone(), parts[i + 1].clone());
+            // Merge every occurrence of the pair, left to right.
+            let mut merged = Vec::with_capacity(parts.len());
+            let mut j = 0;
+            while j < parts.len() {
+                if j + 1 < parts.len() && parts[j] == left && parts[j + 1] == right {
+                    merged.push(format!("{left}{right}"));
+                    j += 2;
+                } else {
+                    merged.push(parts[j].clone());
+                    j += 1;
+                }
+            }
+            parts = merged;
+        }
+        parts
+    }
+}
+
+/// GPT-2 byte-to-unicode table: printable bytes map to themselves, the rest
+/// to code points from U+0100 up, so every byte is a visible vocab symbol.
+fn byte_chars() -> [char; 256] {
+    let printable =
+        |b: u32| (33..=126).contains(&b) || (161..=172).contains(&b) || (174..=255).contains(&b);
+    let mut table = ['\0'; 256];
+    let mut next = 256;
+    for b in 0..256u32 {
+        table[b as usize] = if printable(b) {
+            char::from_u32(b).unwrap()
+        } else {
+            next += 1;
+            char::from_u32(next - 1).unwrap()
+        };
+    }
+    table
+}
+
+// ─── ONNX text tower ────────────────────────────────────────────────────────
+
+/// Learned text embeddings from an ONNX text tower (e.g. the text half of a
+/// CLIP export) run on the CPU with tract.
+///
+/// The model takes `[1, context_len]` int64 token ids, plus an attention
+/// mask when it declares a second input, and its first output is the
+/// `[1, D]` embedding. `name()` returns the embedding space so a
+/// `PerceptionAdapter` pairing it with an image encoder of the same space
+/// can search images by text.
+#[cfg(feature = "onnx")]
+pub struct OnnxTextEncoder {
+    plan: tract_onnx::prelude::TypedRunnableModel<tract_onnx::prelude::TypedModel>,
+    tokenizer: ClipTokenizer,
+    space: String,
+    dim: usize,
+    with_mask: bool,
+}
+
+#[cfg(feature = "onnx")]
+impl OnnxTextEncoder {
+    pub fn load<P: AsRef<std::path::Path>>(
+        path: P,
+        tokenizer: ClipTokenizer,
+        space: &str,
+    ) -> anyhow::Result<Self> {
+        use tract_onnx::prelude::*;
+        let mut model = tract_onnx::onnx().model_for_path(path)?;
+        let with_mask = model.inputs.len() > 1;
+        let fact = InferenceFact::dt_shape(i64::datum_type(), tvec!(1, tokenizer.context_len));
+        model = model.with_input_fact(0, fact.clone())?;
+        if with_mask {
+            model = model.with_input_fact(1, fact)?;
+        }
+        let mut encoder = Self {
+            plan: model.into_optimized()?.into_runnable()?,
+            tokenizer,
+            space: space.to_string(),
+            dim: 0,
+            with_mask,
+        };
+        encoder.dim = encoder.try_embed("")?.len();
+        Ok(encoder)
+    }
+
+    pub fn try_embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        use tract_onnx::prelude::*;
+        let ids = self.tokenizer.encode(text);
+        let shape = [1, ids.len()];
+        let mut inputs: TVec<TValue> = tvec!(Tensor::from_shape(&shape, &ids)?.into());
+        if self.with_mask {
+            let count = self.tokenizer.token_count(&ids);
+            let mask: Vec<i64> = (0..ids.len()).map(|i| (i < count) as i64).collect();
+            inputs.push(Tensor::from_shape(&shape, &mask)?.into());
+        }
+        let out = self.plan.run(inputs)?;
+        let mut embedding = out[0].as_slice::<f32>()?.to_vec();
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            embedding.iter_mut().for_each(|x| *x /= norm);
+        }
+        Ok(embedding)
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl EmbeddingProvider for OnnxTextEncoder {
+    /// Zero vector if the model fails; use `try_embed` to see the error.
+    fn embed(&self, text: &str) -> Vec<f32> {
+        self.try_embed(text).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "onnx text embedding failed");
+            vec![0.0; self.dim]
+        })
+    }
+
+    fn dimension(&self) -> usize {
+        self.dim
+    }
+
+    fn name(&self) -> &str {
+        &self.space
+    }
+}
+
+/// Async counterpart of `EmbeddingProvider` for providers that call out over
+/// the network, where a request can fail or take seconds.
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+pub trait AsyncEmbeddingProvider: Send + Sync {
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>>;
+
+    fn name(&self) -> &str;
+}
+
+/// Embeddings from an Ollama or OpenAI embeddings endpoint.
+#[cfg(feature = "async-store")]
+pub struct RemoteEmbeddingProvider {
+    client: reqwest::Client,
+    remote: Remote,
+    model: String,
+    label: String,
+}
+
+#[cfg(feature = "async-store")]
+enum Remote {
+    Ollama { base_url: String },
+    OpenAI { api_key: String },
+}
+
+#[cfg(feature = "async-store")]
+impl RemoteEmbeddingProvider {
+    /// Parse `"ollama/<model>"` (endpoint from `OLLAMA_URL`, default
+    /// `http://localhost:11434`) or `"openai/<model>"` (key from
+    /// `OPENAI_API_KEY`).
+    pub fn from_model(spec: &str) -> anyhow::Result<Self> {
+        let remote = if spec.starts_with("ollama/") {
+            Remote::Ollama {
+                base_url: std::env::var("OLLAMA_URL")
+                    .unwrap_or_else(|_| "http://localhost:11434".to_string()),
+            }
+        } else if spec.starts_with("openai/") {
+            Remote::OpenAI {
+                api_key: std::env::var("OPENAI_API_KEY").unwrap_or_default(),
+            }
+        } else {
+            anyhow::bail!(
+                "embedding_model must start with 'ollama/' or 'openai/', got: {}",
+                spec
+            );
+        };
+        let (_, model) = spec.split_once('/').unwrap_or_default();
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+        Ok(Self {
+            client,
+            remote,
+            model: model.to_string(),
+            label: spec.to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl AsyncEmbeddingProvider for RemoteEmbeddingProvider {
+    /// An empty vector means the endpoint answered without an embedding.
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let (request, pointer) = match &self.remote {
+            Remote::Ollama { base_url } => (
+                self.client
+                    .post(format!("{}/api/embeddings", base_url))
+                    .json(&serde_json::json!({ "model": self.model, "prompt": text })),
+                "/embedding",
+            ),
+            Remote::OpenAI { api_key } => (
+                self.client
+                    .post("https://api.openai.com/v1/embeddings")
+                    .bearer_auth(api_key)
+                    .json(&serde_json::json!({ "model": self.model, "input": text })),
+                "/data/0/embedding",
+            ),
+        };
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("{} request failed: {}", self.label, e))?;
+        let data: serde_json::Value = resp.json().await.unwrap_or_default();
+        Ok(data
+            .pointer(pointer)
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+            .unwrap_or_default())
+    }
+
+    fn name(&self) -> &str {
+        &self.label
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +509,35 @@ mod tests {
         assert!(v.iter().all(|x| *x == 0.0));
     }
 
+    #[test]
+    fn test_clip_tokenizer_bpe_and_padding() {
+        let vocab: HashMap<String, i64> = [
+            ("a", 0),
+            ("b", 1),
+            ("b</w>", 2),
+            ("ab", 3),
+            ("abc</w>", 4),
+            ("!</w>", 5),
+            ("<|startoftext|>", 6),
+            ("<|endoftext|>", 7),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let merges = vec![
+            ("a".to_string(), "b".to_string()),
+            ("ab".to_string(), "c</w>".to_string()),
+        ];
+        let mut tok = ClipTokenizer::from_parts(vocab, merges).unwrap();
+        tok.context_len = 8;
+        // "abc" merges twice; a word-final "b" is "b</w>", so "ab" stays split.
+        let ids = tok.encode("ABC  ab!");
+        assert_eq!(ids, vec![6, 4, 0, 2, 5, 7, 7, 7]);
+        assert_eq!(tok.token_count(&ids), 6);
+        tok.context_len = 3;
+        assert_eq!(tok.encode("ab ab ab"), vec![6, 0, 7]);
+    }
+
     fn cosine(a: &[f32], b: &[f32]) -> f32 {
         let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
         let na: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
@@ -7,11 +7,15 @@
 //!              Lossy compression, but evidence links preserved.
 //!
 //! This is a read view over MemoryStore — it does not own records. Call from_store()
-//! to materialize tier counts. Consolidation is driven by mine_and_consolidate externally.
+//! to materialize tier counts. `enforce_tiering` is the write side: when Raw pressure
+//! is exceeded it consolidates, then demotes the oldest Raw records to the store's
+//! zstd cold tier (Episode/Abstract stay hot). `MemoryStore::find_by_id` and
+//! `search_tiered` read cold records in place; writes to one fault it back hot.
 
 use crate::memory_record::{MemoryRecord, MemoryType};
 use crate::memory_store::MemoryStore;
 use crate::persistence::MemoryBackend;
+use crate::tx_log::TxLog;
 use uuid::Uuid;
 
 pub const RAW_CAP: usize = 1000;
@@ -113,3 +117,147 @@ fn classify(r: &MemoryRecord) -> Tier {
     }
     Tier::Raw
 }
+
+/// When and how far to demote Raw records to the cold tier.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TieringPolicy {
+    /// Raw count (per actor) at which tiering kicks in. Default `RAW_CAP`.
+    pub raw_cap: usize,
+    /// Raw count to keep hot after demotion (newest first). Default `RAW_CAP / 2`.
+    pub raw_target: usize,
+    /// Motif threshold for the consolidation pass run before demotion. 0 = skip.
+    pub consolidate_min_frequency: usize,
+}
+
+impl Default for TieringPolicy {
+    fn default() -> Self {
+        Self {
+            raw_cap: RAW_CAP,
+            raw_target: RAW_CAP / 2,
+            consolidate_min_frequency: 3,
+        }
+    }
+}
+
+/// Outcome of one `enforce_tiering` pass.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TieringReport {
+    pub actors_over_pressure: usize,
+    pub skills_induced: usize,
+    pub beliefs_induced: usize,
+    pub records_demoted: usize,
+}
+
+/// Per-tier record counts across all actors, plus cold tier size.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TierSizes {
+    pub raw: usize,
+    pub episode: usize,
+    pub abstract_count: usize,
+    pub cold: usize,
+    pub cold_segments: usize,
+    pub cold_bytes: u64,
+}
+
+/// Count hot records per tier (all actors) and read cold tier sizes.
+pub fn tier_sizes<B: MemoryBackend + Send + Sync>(store: &MemoryStore<B>) -> TierSizes {
+    let mut sizes = TierSizes::default();
+    for r in store.all() {
+        match classify(r) {
+            Tier::Raw => sizes.raw += 1,
+            Tier::Episode => sizes.episode += 1,
+            Tier::Abstract => sizes.abstract_count += 1,
+        }
+    }
+    if let Some(cold) = store.cold_tier() {
+        sizes.cold = cold.len();
+        sizes.cold_segments = cold.segment_count();
+        sizes.cold_bytes = cold.bytes_on_disk();
+    }
+    sizes
+}
+
+/// True when `actor` has at least `policy.raw_cap` hot Raw records and a cold
+/// tier is attached, i.e. when `enforce_tiering` has work for it. Reads the
+/// actor index rather than scanning the store, so it is cheap per write.
+pub fn over_raw_cap<B: MemoryBackend + Send + Sync>(
+    store: &MemoryStore<B>,
+    actor: &str,
+    policy: &TieringPolicy,
+) -> bool {
+    store.cold_tier().is_some()
+        && store
+            .all()
+            .by_actor(actor)
+            .into_iter()
+            .filter(|r| matches!(classify(r), Tier::Raw))
+            .count()
+            >= policy.raw_cap
+}
+
+/// Apply `policy` to every actor whose Raw tier is at or above `raw_cap`:
+/// run `mine_and_consolidate`, then demote the oldest Raw records until
+/// `raw_target` remain hot. No-op without a cold tier attached.
+pub fn enforce_tiering<B: MemoryBackend + Send + Sync>(
+    store: &mut MemoryStore<B>,
+    log: Option<&TxLog>,
+    policy: &TieringPolicy,
+) -> Result<TieringReport, String> {
+    let mut report = TieringReport::default();
+    if store.cold_tier().is_none() {
+        return Ok(report);
+    }
+    let mut raw_by_actor: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    for r in store.all() {
+        if matches!(classify(r), Tier::Raw) {
+            *raw_by_actor.entry(r.actor.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut hot_actors: Vec<String> = raw_by_actor
+        .into_iter()
+        .filter(|(_, n)| *n >= policy.raw_cap)
+        .map(|(a, _)| a)
+        .collect();
+    hot_actors.sort();
+
+    if hot_actors.is_empty() {
+        return Ok(report);
+    }
+    report.actors_over_pressure = hot_actors.len();
+
+    // Consolidate first. mine_and_consolidate deletes motif sources from the hot
+    // store, so capture them beforehand and keep them in the cold tier instead.
+    if policy.consolidate_min_frequency > 0 {
+        let before = store.all().clone();
+        let mined = crate::consolidation::mine_and_consolidate(
+            store,
+            log,
+            policy.consolidate_min_frequency,
+            "tiering",
+        )?;
+        report.skills_induced = mined.skills_induced;
+        report.beliefs_induced = mined.beliefs_induced;
+        let sources: Vec<MemoryRecord> = mined
+            .source_ids_archived
+            .iter()
+            .filter_map(|id| before.by_id(*id).cloned())
+            .collect();
+        if let Some(mut cold) = store.cold_tier_mut() {
+            report.records_demoted += cold.demote(&sources).map_err(|e| e.to_string())?;
+        }
+    }
+
+    for actor in hot_actors {
+        let mut raw: Vec<&MemoryRecord> = store
+            .all()
+            .iter()
+            .filter(|r| r.actor == actor && matches!(classify(r), Tier::Raw))
+            .collect();
+        raw.sort_by_key(|r| r.timestamp);
+        let excess = raw.len().saturating_sub(policy.raw_target);
+        let ids: Vec<Uuid> = raw.iter().take(excess).map(|r| r.id).collect();
+        report.records_demoted += store.demote_to_cold(&ids).map_err(|e| e.to_string())?;
+    }
+    Ok(report)
+}
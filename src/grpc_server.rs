@@ -4,9 +4,11 @@ pub mod grpc {
 }
 
 #[cfg(feature = "grpc-server")]
-use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::async_memory_store::AsyncMemoryStore;
+#[cfg(feature = "grpc-server")]
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
 #[cfg(feature = "grpc-server")]
-use crate::memory_store::MemoryStore;
+use crate::memory_record::{MemoryRecord, MemoryType};
 #[cfg(feature = "grpc-server")]
 use crate::persistence::MemoryBackend;
 #[cfg(feature = "grpc-server")]
@@ -18,12 +20,12 @@ use grpc::{AddRecordRequest, AddRecordResponse, ListRecordsRequest, ListRecordsR
 #[cfg(feature = "grpc-server")]
 use std::net::SocketAddr;
 #[cfg(feature = "grpc-server")]
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 #[cfg(feature = "grpc-server")]
 #[derive(Clone)]
 struct MemoryServiceImpl<B: MemoryBackend + Send + 'static> {
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
 }
 
 #[cfg(feature = "grpc-server")]
@@ -64,12 +66,10 @@ impl<B: MemoryBackend + Send + 'static> MemoryService for MemoryServiceImpl<B> {
         };
         let hash = record.compute_hash();
         record.integrity = Some(hash);
-        {
-            let mut store = self.store.lock().unwrap();
-            store
-                .add(record)
-                .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        }
+        self.store
+            .add(record)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(AddRecordResponse { ok: true }))
     }
 
@@ -77,7 +77,7 @@ impl<B: MemoryBackend + Send + 'static> MemoryService for MemoryServiceImpl<B> {
         &self,
         _req: tonic::Request<ListRecordsRequest>,
     ) -> Result<tonic::Response<ListRecordsResponse>, tonic::Status> {
-        let store = self.store.lock().unwrap();
+        let store = self.store.snapshot();
         let records = store
             .all()
             .iter()
@@ -98,9 +98,11 @@ impl<B: MemoryBackend + Send + 'static> MemoryService for MemoryServiceImpl<B> {
 #[cfg(feature = "grpc-server")]
 pub async fn serve<B: MemoryBackend + Send + 'static>(
     addr: SocketAddr,
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let svc = MemoryServiceImpl { store };
+    let svc = MemoryServiceImpl {
+        store: AsyncMemoryStore::from(store),
+    };
     tonic::transport::Server::builder()
         .add_service(MemoryServiceServer::new(svc))
         .serve(addr)
@@ -96,6 +96,7 @@ fn send_perception(state: State<GuiState>, text: String) -> String {
         text: Some(text.clone()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec![],
     };
     PerceptionAdapter::adapt(input);
@@ -0,0 +1,1296 @@
+//! Importers — format adapters for agent traces, chat exports and notes.
+//!
+//! Chain-of-thought: `scripts/import_trace.rs` used to write raw JSONL next to
+//! the store, so imported records never hit the audit log, never got embedded
+//! and were duplicated on every re-run. Here each format has an adapter that
+//! only turns its input into `MemoryRecord`s; the `Importer` then pushes every
+//! candidate through `MemoryStore::embed_and_add`. Dedup uses a content hash
+//! over type/actor/action/target plus the item's identity in the source (run
+//! id, span id, session + turn, note path), so re-importing the same export is
+//! a no-op while two identical "ok" turns in different chats are both kept.
+//! Per-item problems never abort the import; they land in `ImportReport::errors`
+//! with a location such as `runs.json[3]` or `notes.csv:line 7`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::conversation_memory::turn_record;
+use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_store::MemoryStore;
+use crate::persistence::MemoryBackend;
+use crate::store_format::FormatHeader;
+
+/// Longest `target` an adapter produces from structured payloads.
+const MAX_TARGET_CHARS: usize = 4000;
+
+// ─── Formats ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// Flat `[{actor, action, target}]` array (the old `import_trace` input).
+    Trace,
+    /// OpenAI chat messages or a ChatGPT `conversations.json` export.
+    #[value(alias = "chatgpt")]
+    Openai,
+    /// Anthropic Messages API transcripts or a Claude.ai export.
+    #[value(alias = "claude")]
+    Anthropic,
+    /// LangChain / LangGraph / LangSmith run trees.
+    #[value(alias = "langgraph", alias = "langsmith")]
+    Langchain,
+    /// OpenTelemetry spans following the GenAI semantic conventions (OTLP JSON).
+    #[value(alias = "otlp")]
+    Otel,
+    /// CSV with a header row; needs a `target` (or `text`/`content`) column.
+    Csv,
+    /// Markdown notes, one record per file; a directory is walked recursively.
+    #[value(alias = "md")]
+    Markdown,
+}
+
+impl ImportFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            ImportFormat::Trace => "trace",
+            ImportFormat::Openai => "openai",
+            ImportFormat::Anthropic => "anthropic",
+            ImportFormat::Langchain => "langchain",
+            ImportFormat::Otel => "otel",
+            ImportFormat::Csv => "csv",
+            ImportFormat::Markdown => "markdown",
+        }
+    }
+
+    /// Parse a format name or alias, case-insensitively.
+    pub fn parse(s: &str) -> Result<Self> {
+        <Self as ValueEnum>::from_str(s, true).map_err(|_| {
+            let names: Vec<&str> = Self::value_variants().iter().map(|f| f.name()).collect();
+            anyhow::anyhow!(
+                "unknown import format {s:?}; expected one of {}",
+                names.join(", ")
+            )
+        })
+    }
+
+    /// Guess the format from the file extension, then from the content shape.
+    pub fn detect(path: Option<&Path>, content: &str) -> Option<Self> {
+        let ext = path
+            .and_then(|p| p.extension())
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match ext.as_deref() {
+            Some("csv") => return Some(ImportFormat::Csv),
+            Some("md") | Some("markdown") => return Some(ImportFormat::Markdown),
+            _ => {}
+        }
+        let trimmed = content.trim_start();
+        if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+            return None;
+        }
+        let mut issues = Vec::new();
+        load_json(content, "", &mut issues)
+            .ok()
+            .and_then(|v| detect_json(&v))
+    }
+}
+
+fn detect_json(v: &Value) -> Option<ImportFormat> {
+    let first = match v {
+        Value::Array(items) => items.first()?,
+        other => other,
+    };
+    let has = |key: &str| first.get(key).is_some();
+    if has("resourceSpans") || has("spanId") || has("span_id") || has("scopeSpans") {
+        return Some(ImportFormat::Otel);
+    }
+    if has("run_type") || has("child_runs") || has("runs") {
+        return Some(ImportFormat::Langchain);
+    }
+    if has("chat_messages")
+        || first
+            .get("model")
+            .and_then(Value::as_str)
+            .is_some_and(|m| m.starts_with("claude"))
+    {
+        return Some(ImportFormat::Anthropic);
+    }
+    if has("mapping") || has("messages") || has("role") {
+        return Some(ImportFormat::Openai);
+    }
+    if has("actor") || has("target") || has("text") {
+        return Some(ImportFormat::Trace);
+    }
+    None
+}
+
+// ─── Options and report ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportOptions {
+    /// `None` detects the format from the extension and content.
+    pub format: Option<ImportFormat>,
+    /// Parse, validate and dedup without writing anything.
+    pub dry_run: bool,
+    /// Stored as `source` on every record; defaults to `import:<format>:<origin>`.
+    pub source: Option<String>,
+    /// Session id for chat transcripts that carry none of their own.
+    pub session: Option<String>,
+}
+
+/// A problem with one input item. The rest of the import carries on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportIssue {
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub format: ImportFormat,
+    pub dry_run: bool,
+    /// Candidate records produced by the adapter.
+    pub scanned: usize,
+    /// Records added (or, on a dry run, that would be added).
+    pub imported: usize,
+    /// Candidates whose content hash is already in the store or earlier in the input.
+    pub duplicates: usize,
+    /// Items that were understood but carry nothing to store, e.g. non-GenAI spans.
+    pub skipped: usize,
+    pub errors: Vec<ImportIssue>,
+    /// Ids of the added records; empty on a dry run.
+    pub record_ids: Vec<Uuid>,
+}
+
+impl ImportReport {
+    fn new(format: ImportFormat, dry_run: bool) -> Self {
+        Self {
+            format,
+            dry_run,
+            scanned: 0,
+            imported: 0,
+            duplicates: 0,
+            skipped: 0,
+            errors: Vec::new(),
+            record_ids: Vec::new(),
+        }
+    }
+}
+
+/// Passed to the progress callback after every candidate.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ImportProgress {
+    pub done: usize,
+    pub total: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+}
+
+/// Adapter output for one document.
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    /// `(location, record)` in input order.
+    pub records: Vec<(String, MemoryRecord)>,
+    pub skipped: usize,
+    pub issues: Vec<ImportIssue>,
+}
+
+impl ParsedImport {
+    fn issue(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ImportIssue {
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+}
+
+// ─── Importer ────────────────────────────────────────────────────────────────
+
+type ProgressFn = Box<dyn FnMut(&ImportProgress) + Send>;
+
+pub struct Importer {
+    pub options: ImportOptions,
+    progress: Option<ProgressFn>,
+}
+
+impl Importer {
+    pub fn new(options: ImportOptions) -> Self {
+        Self {
+            options,
+            progress: None,
+        }
+    }
+
+    /// Call `f` after each candidate is stored, skipped as duplicate or failed.
+    pub fn with_progress(mut self, f: impl FnMut(&ImportProgress) + Send + 'static) -> Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Import one document held in memory. `origin` names it in locations and
+    /// in the default `source` (a file name, or e.g. "request" over HTTP).
+    pub fn import_str<B: MemoryBackend>(
+        &mut self,
+        store: &mut MemoryStore<B>,
+        content: &str,
+        origin: &str,
+    ) -> Result<ImportReport> {
+        let format = match self.options.format {
+            Some(f) => f,
+            None => ImportFormat::detect(Some(Path::new(origin)), content).with_context(|| {
+                format!("cannot detect the import format of {origin}; pass one explicitly")
+            })?,
+        };
+        let parsed = parse(format, content, origin, self.options.session.as_deref())?;
+        Ok(self.store_parsed(store, format, origin, parsed))
+    }
+
+    /// Import a file, or every Markdown file under a directory.
+    pub fn import_path<B: MemoryBackend>(
+        &mut self,
+        store: &mut MemoryStore<B>,
+        path: &Path,
+    ) -> Result<ImportReport> {
+        if !path.is_dir() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            // The file name keeps the extension, so detection still sees it.
+            let origin = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("input")
+                .to_string();
+            return self.import_str(store, &content, &origin);
+        }
+
+        let format = self.options.format.unwrap_or(ImportFormat::Markdown);
+        if format != ImportFormat::Markdown {
+            anyhow::bail!(
+                "directory import only supports markdown notes, not {}",
+                format.name()
+            );
+        }
+        let mut files = Vec::new();
+        collect_markdown(path, &mut files)?;
+        files.sort();
+        let mut parsed = ParsedImport::default();
+        for file in files {
+            let rel = file
+                .strip_prefix(path)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            match std::fs::read_to_string(&file) {
+                Ok(text) => match parse_markdown_note(&text, &rel) {
+                    Some(rec) => parsed.records.push((rel, rec)),
+                    None => parsed.skipped += 1,
+                },
+                Err(e) => parsed.issue(rel, e.to_string()),
+            }
+        }
+        let origin = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("notes")
+            .to_string();
+        Ok(self.store_parsed(store, format, &origin, parsed))
+    }
+
+    fn store_parsed<B: MemoryBackend>(
+        &mut self,
+        store: &mut MemoryStore<B>,
+        format: ImportFormat,
+        origin: &str,
+        parsed: ParsedImport,
+    ) -> ImportReport {
+        let mut report = ImportReport::new(format, self.options.dry_run);
+        report.scanned = parsed.records.len();
+        report.skipped = parsed.skipped;
+        report.errors = parsed.issues;
+
+        let source = self
+            .options
+            .source
+            .clone()
+            .unwrap_or_else(|| format!("import:{}:{origin}", format.name()));
+        let mut seen: HashMap<String, Uuid> =
+            store.all().iter().map(|r| (dedup_hash(r), r.id)).collect();
+        // Candidate id -> id actually in the store, for duplicates that later
+        // candidates point at through `reply_to`/`derived_from`/`evidence`.
+        let mut redirect: HashMap<Uuid, Uuid> = HashMap::new();
+        let total = parsed.records.len();
+
+        for (done, (location, mut rec)) in parsed.records.into_iter().enumerate() {
+            relink(&mut rec, &redirect);
+            let hash = dedup_hash(&rec);
+            if let Some(existing) = seen.get(&hash) {
+                redirect.insert(rec.id, *existing);
+                report.duplicates += 1;
+            } else {
+                rec.source = Some(source.clone());
+                if let Value::Object(map) = &mut rec.metadata {
+                    map.insert(
+                        "import".into(),
+                        serde_json::json!({"format": format.name(), "origin": origin, "location": location}),
+                    );
+                }
+                seal(&mut rec);
+                seen.insert(hash, rec.id);
+                if self.options.dry_run {
+                    report.imported += 1;
+                } else {
+                    let id = rec.id;
+                    let text = rec.target.clone();
+                    match store.embed_and_add(rec, &text) {
+                        Ok(()) => {
+                            report.imported += 1;
+                            report.record_ids.push(id);
+                        }
+                        Err(e) => report.errors.push(ImportIssue {
+                            location,
+                            message: e.to_string(),
+                        }),
+                    }
+                }
+            }
+            if let Some(progress) = self.progress.as_mut() {
+                progress(&ImportProgress {
+                    done: done + 1,
+                    total,
+                    imported: report.imported,
+                    duplicates: report.duplicates,
+                });
+            }
+        }
+        if !self.options.dry_run && report.imported > 0 {
+            if let Err(e) = store.flush() {
+                report.errors.push(ImportIssue {
+                    location: origin.to_string(),
+                    message: format!("flush failed: {e}"),
+                });
+            }
+        }
+        report
+    }
+}
+
+/// Run the adapter for `format` over one document.
+pub fn parse(
+    format: ImportFormat,
+    content: &str,
+    origin: &str,
+    session: Option<&str>,
+) -> Result<ParsedImport> {
+    let mut out = ParsedImport::default();
+    match format {
+        ImportFormat::Csv => parse_csv(content, origin, &mut out)?,
+        ImportFormat::Markdown => match parse_markdown_note(content, origin) {
+            Some(rec) => out.records.push((origin.to_string(), rec)),
+            None => out.skipped += 1,
+        },
+        json_format => {
+            let value = load_json(content, origin, &mut out.issues)?;
+            match json_format {
+                ImportFormat::Trace => parse_trace(&value, origin, &mut out),
+                ImportFormat::Openai | ImportFormat::Anthropic => {
+                    parse_chat(&value, origin, session, json_format, &mut out)
+                }
+                ImportFormat::Langchain => parse_langchain(&value, origin, &mut out),
+                ImportFormat::Otel => parse_otel(&value, origin, &mut out),
+                ImportFormat::Csv | ImportFormat::Markdown => unreachable!("handled above"),
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Content hash used for dedup: type, actor, action, target and the item's
+/// identity in its source (`metadata.import_key`), if any.
+pub fn dedup_hash(rec: &MemoryRecord) -> String {
+    use sha2::{Digest, Sha256};
+    let key = rec
+        .metadata
+        .get("import_key")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let data = serde_json::json!([rec.record_type, rec.actor, rec.action, rec.target, key]);
+    hex::encode(Sha256::digest(data.to_string().as_bytes()))
+}
+
+fn seal(rec: &mut MemoryRecord) {
+    let hash = rec.compute_hash();
+    rec.integrity = Some(hash.clone());
+    rec.content_hash = Some(hash);
+}
+
+fn relink(rec: &mut MemoryRecord, redirect: &HashMap<Uuid, Uuid>) {
+    if redirect.is_empty() {
+        return;
+    }
+    let map = |id: &mut Uuid| {
+        if let Some(to) = redirect.get(id) {
+            *id = *to;
+        }
+    };
+    if let Some(parent) = rec.derived_from.as_mut() {
+        map(parent);
+    }
+    rec.evidence.iter_mut().for_each(map);
+    let reply_to = rec
+        .metadata
+        .get("reply_to")
+        .and_then(Value::as_str)
+        .and_then(|s| Uuid::parse_str(s).ok());
+    if let Some(to) = reply_to.and_then(|id| redirect.get(&id)) {
+        rec.metadata["reply_to"] = serde_json::json!(to);
+    }
+}
+
+fn collect_markdown(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_markdown(&path, out)?;
+        } else if matches!(
+            path.extension()
+                .and_then(|e| e.to_str())
+                .map(str::to_ascii_lowercase)
+                .as_deref(),
+            Some("md") | Some("markdown")
+        ) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+// ─── Shared helpers ──────────────────────────────────────────────────────────
+
+/// Parse a JSON document, falling back to JSON Lines (bad lines become issues).
+fn load_json(content: &str, origin: &str, issues: &mut Vec<ImportIssue>) -> Result<Value> {
+    if let Ok(v) = serde_json::from_str::<Value>(content) {
+        return Ok(v);
+    }
+    let mut items = Vec::new();
+    for (n, line) in content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && FormatHeader::parse(l).is_none())
+    {
+        match serde_json::from_str::<Value>(line) {
+            Ok(v) => items.push(v),
+            Err(e) => issues.push(ImportIssue {
+                location: format!("{origin}:line {}", n + 1),
+                message: e.to_string(),
+            }),
+        }
+    }
+    if items.is_empty() {
+        anyhow::bail!("{origin} is neither JSON nor JSON Lines");
+    }
+    Ok(Value::Array(items))
+}
+
+/// Items of a top-level array, or the value itself.
+fn as_items(v: &Value) -> Vec<&Value> {
+    match v {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    }
+}
+
+fn str_field<'a>(v: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .filter_map(|k| v.get(*k).and_then(Value::as_str))
+        .find(|s| !s.trim().is_empty())
+}
+
+/// RFC 3339, naive ISO (taken as UTC), or epoch seconds/ms/ns as number or string.
+fn parse_time(v: &Value) -> Option<DateTime<Utc>> {
+    let epoch = |n: f64| -> Option<DateTime<Utc>> {
+        let nanos = if n > 1e17 {
+            n
+        } else if n > 1e14 {
+            n * 1e3
+        } else if n > 1e11 {
+            n * 1e6
+        } else {
+            n * 1e9
+        };
+        Some(Utc.timestamp_nanos(nanos as i64))
+    };
+    match v {
+        Value::Number(n) => epoch(n.as_f64()?),
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .map(|t| t.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                    .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f"))
+                    .ok()
+                    .map(|t| t.and_utc())
+            })
+            .or_else(|| s.parse::<f64>().ok().and_then(epoch)),
+        _ => None,
+    }
+}
+
+/// Best human-readable text in a structured payload.
+fn text_of(v: &Value) -> String {
+    let text = match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .map(text_of)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(map) => {
+            let preferred = [
+                "text",
+                "content",
+                "output",
+                "answer",
+                "result",
+                "message",
+                "generations",
+                "messages",
+                "parts",
+            ];
+            match preferred.iter().find_map(|k| map.get(*k)) {
+                Some(inner) => text_of(inner),
+                // A typed content block with no text (image, tool call) adds nothing.
+                None if map.is_empty() || map.contains_key("type") => String::new(),
+                None => v.to_string(),
+            }
+        }
+        other => other.to_string(),
+    };
+    truncate(text.trim())
+}
+
+fn truncate(s: &str) -> String {
+    match s.char_indices().nth(MAX_TARGET_CHARS) {
+        Some((cut, _)) => format!("{}…", &s[..cut]),
+        None => s.to_string(),
+    }
+}
+
+fn record(
+    record_type: MemoryType,
+    actor: &str,
+    action: &str,
+    target: String,
+    metadata: Value,
+) -> MemoryRecord {
+    MemoryRecord::new(
+        record_type,
+        actor.to_string(),
+        action.to_string(),
+        target,
+        metadata,
+    )
+}
+
+// ─── Trace ───────────────────────────────────────────────────────────────────
+
+fn parse_trace(v: &Value, origin: &str, out: &mut ParsedImport) {
+    for (i, item) in as_items(v).into_iter().enumerate() {
+        let location = format!("{origin}[{i}]");
+        let Some(target) = str_field(item, &["target", "text"]) else {
+            out.issue(location, "missing target/text");
+            continue;
+        };
+        let record_type = str_field(item, &["record_type", "type"])
+            .and_then(|t| <MemoryType as ValueEnum>::from_str(t, true).ok())
+            .unwrap_or(MemoryType::Temporal);
+        let mut rec = record(
+            record_type,
+            str_field(item, &["actor"]).unwrap_or("import"),
+            str_field(item, &["action"]).unwrap_or("observed"),
+            target.to_string(),
+            item.get("metadata")
+                .cloned()
+                .filter(Value::is_object)
+                .unwrap_or_else(|| serde_json::json!({})),
+        );
+        if let Some(ts) = item.get("timestamp").and_then(parse_time) {
+            rec.timestamp = ts;
+        }
+        out.records.push((location, rec));
+    }
+}
+
+// ─── Chat transcripts ────────────────────────────────────────────────────────
+
+struct ChatMessage {
+    role: String,
+    text: String,
+    time: Option<DateTime<Utc>>,
+}
+
+fn parse_chat(
+    v: &Value,
+    origin: &str,
+    session: Option<&str>,
+    format: ImportFormat,
+    out: &mut ParsedImport,
+) {
+    let is_message_list =
+        matches!(v, Value::Array(items) if items.first().is_some_and(|m| m.get("role").is_some()));
+    let conversations: Vec<&Value> = if is_message_list {
+        vec![v]
+    } else {
+        as_items(v)
+    };
+    let multiple = conversations.len() > 1;
+
+    for (ci, conv) in conversations.into_iter().enumerate() {
+        let location = if multiple {
+            format!("{origin}[{ci}]")
+        } else {
+            origin.to_string()
+        };
+        let messages = match chat_messages(conv) {
+            Some(m) => m,
+            None => {
+                out.issue(location, "no messages, chat_messages or mapping found");
+                continue;
+            }
+        };
+        let session_id = str_field(conv, &["conversation_id", "uuid", "id"])
+            .map(str::to_string)
+            .or_else(|| {
+                session.map(|s| {
+                    if multiple {
+                        format!("{s}-{ci}")
+                    } else {
+                        s.to_string()
+                    }
+                })
+            })
+            .unwrap_or_else(|| {
+                let stem = Path::new(origin)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(origin);
+                if multiple {
+                    format!("{stem}-{ci}")
+                } else {
+                    stem.to_string()
+                }
+            });
+        let title = str_field(conv, &["title", "name"]);
+
+        let mut previous: Option<Uuid> = None;
+        let mut turn = 0;
+        for (mi, msg) in messages.into_iter().enumerate() {
+            if msg.text.trim().is_empty() {
+                out.skipped += 1;
+                continue;
+            }
+            let mut rec = turn_record(&session_id, turn, &msg.role, &truncate(&msg.text), previous);
+            rec.metadata["import_key"] =
+                serde_json::json!(format!("{}:{session_id}:{turn}", format.name()));
+            if let Some(title) = title {
+                rec.metadata["title"] = serde_json::json!(title);
+            }
+            if let Some(ts) = msg.time {
+                rec.timestamp = ts;
+            }
+            previous = Some(rec.id);
+            turn += 1;
+            out.records
+                .push((format!("{location}.messages[{mi}]"), rec));
+        }
+    }
+}
+
+/// Messages of one conversation in any of the supported shapes, in order.
+fn chat_messages(conv: &Value) -> Option<Vec<ChatMessage>> {
+    let time = |m: &Value| {
+        ["create_time", "created_at", "timestamp"]
+            .iter()
+            .find_map(|k| m.get(*k).and_then(parse_time))
+    };
+    let normalize_role = |r: &str| match r {
+        "human" => "user".to_string(),
+        "ai" | "bot" => "assistant".to_string(),
+        other => other.to_string(),
+    };
+
+    // Bare message list, OpenAI/Anthropic `messages`, or Claude.ai `chat_messages`.
+    let list = match conv {
+        Value::Array(items) => Some(items),
+        _ => conv
+            .get("messages")
+            .or_else(|| conv.get("chat_messages"))
+            .and_then(Value::as_array),
+    };
+    if let Some(list) = list {
+        let mut out = Vec::new();
+        // Anthropic puts the system prompt beside the messages.
+        if let Some(system) = conv.get("system").filter(|s| !s.is_null()) {
+            out.push(ChatMessage {
+                role: "system".into(),
+                text: text_of(system),
+                time: None,
+            });
+        }
+        for m in list {
+            let role = str_field(m, &["role", "sender"]).unwrap_or("user");
+            let body = m
+                .get("content")
+                .filter(|c| !c.is_null() && c.as_str() != Some(""));
+            let text = match body {
+                Some(c) => text_of(c),
+                None => text_of(m.get("text").unwrap_or(&Value::Null)),
+            };
+            out.push(ChatMessage {
+                role: normalize_role(role),
+                text,
+                time: time(m),
+            });
+        }
+        return Some(out);
+    }
+
+    // ChatGPT export: a node tree under `mapping`; order by creation time.
+    let mapping = conv.get("mapping")?.as_object()?;
+    let mut nodes: Vec<(Option<DateTime<Utc>>, ChatMessage)> = mapping
+        .values()
+        .filter_map(|node| node.get("message").filter(|m| !m.is_null()))
+        .map(|m| {
+            let role = m
+                .pointer("/author/role")
+                .and_then(Value::as_str)
+                .unwrap_or("user");
+            let text = text_of(
+                m.pointer("/content/parts")
+                    .or_else(|| m.get("content"))
+                    .unwrap_or(&Value::Null),
+            );
+            let t = time(m);
+            (
+                t,
+                ChatMessage {
+                    role: normalize_role(role),
+                    text,
+                    time: t,
+                },
+            )
+        })
+        .collect();
+    nodes.sort_by_key(|(t, _)| *t);
+    Some(nodes.into_iter().map(|(_, m)| m).collect())
+}
+
+// ─── LangChain / LangGraph runs ──────────────────────────────────────────────
+
+fn parse_langchain(v: &Value, origin: &str, out: &mut ParsedImport) {
+    let roots: Vec<&Value> = match v.get("runs").and_then(Value::as_array) {
+        Some(runs) => runs.iter().collect(),
+        None => as_items(v),
+    };
+    let mut by_run_id: HashMap<String, Uuid> = HashMap::new();
+    for (i, run) in roots.into_iter().enumerate() {
+        langchain_run(run, &format!("{origin}[{i}]"), None, &mut by_run_id, out);
+    }
+}
+
+fn langchain_run(
+    run: &Value,
+    location: &str,
+    parent: Option<Uuid>,
+    by_run_id: &mut HashMap<String, Uuid>,
+    out: &mut ParsedImport,
+) {
+    if !run.is_object() {
+        out.issue(location, "run is not an object");
+        return;
+    }
+    let run_id = str_field(run, &["id", "run_id"]).map(str::to_string);
+    let parent = parent
+        .or_else(|| str_field(run, &["parent_run_id"]).and_then(|p| by_run_id.get(p).copied()));
+    let outputs = run.get("outputs").unwrap_or(&Value::Null);
+    let error = str_field(run, &["error"]);
+    let target = match (text_of(outputs), error) {
+        (t, _) if !t.is_empty() => t,
+        (_, Some(e)) => format!("error: {e}"),
+        _ => text_of(run.get("inputs").unwrap_or(&Value::Null)),
+    };
+
+    let mut this = None;
+    if target.is_empty() {
+        out.skipped += 1;
+    } else {
+        let node = run
+            .pointer("/extra/metadata/langgraph_node")
+            .or_else(|| run.pointer("/metadata/langgraph_node"))
+            .and_then(Value::as_str);
+        let name = str_field(run, &["name"]).unwrap_or("langchain");
+        let run_type = str_field(run, &["run_type"]).unwrap_or("run");
+        let mut rec = record(
+            MemoryType::Temporal,
+            node.unwrap_or(name),
+            run_type,
+            target,
+            serde_json::json!({
+                "run_id": run_id,
+                "parent_run_id": run.get("parent_run_id"),
+                "trace_id": run.get("trace_id"),
+                "name": name,
+                "run_type": run_type,
+                "inputs": run.get("inputs"),
+                "outputs": outputs,
+                "error": error,
+                "start_time": run.get("start_time"),
+                "end_time": run.get("end_time"),
+                "import_key": run_id.as_ref().map(|id| format!("langchain:{id}")),
+            }),
+        );
+        if let Some(tags) = run.get("tags").and_then(Value::as_array) {
+            rec.tags = tags
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(ts) = run.get("start_time").and_then(parse_time) {
+            rec.timestamp = ts;
+        }
+        rec.derived_from = parent;
+        if let Some(id) = &run_id {
+            by_run_id.insert(id.clone(), rec.id);
+        }
+        this = Some(rec.id);
+        out.records.push((location.to_string(), rec));
+    }
+
+    if let Some(children) = run.get("child_runs").and_then(Value::as_array) {
+        for (i, child) in children.iter().enumerate() {
+            langchain_run(
+                child,
+                &format!("{location}.child_runs[{i}]"),
+                this.or(parent),
+                by_run_id,
+                out,
+            );
+        }
+    }
+}
+
+// ─── OpenTelemetry GenAI spans ───────────────────────────────────────────────
+
+struct Span<'a> {
+    location: String,
+    raw: &'a Value,
+    attrs: Map<String, Value>,
+    resource: Map<String, Value>,
+    start: Option<DateTime<Utc>>,
+}
+
+/// OTLP `[{key, value: {stringValue|intValue|…}}]` or a plain object.
+fn otel_attributes(v: Option<&Value>) -> Map<String, Value> {
+    match v {
+        Some(Value::Object(map)) => map.clone(),
+        Some(Value::Array(kvs)) => kvs
+            .iter()
+            .filter_map(|kv| {
+                Some((
+                    kv.get("key")?.as_str()?.to_string(),
+                    otel_value(kv.get("value")?),
+                ))
+            })
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
+fn otel_value(v: &Value) -> Value {
+    let Some(obj) = v.as_object() else {
+        return v.clone();
+    };
+    if let Some(s) = obj.get("stringValue") {
+        return s.clone();
+    }
+    if let Some(i) = obj.get("intValue") {
+        // OTLP JSON encodes 64-bit ints as strings.
+        return i
+            .as_str()
+            .and_then(|s| s.parse::<i64>().ok())
+            .map(Value::from)
+            .unwrap_or_else(|| i.clone());
+    }
+    if let Some(x) = obj.get("doubleValue").or_else(|| obj.get("boolValue")) {
+        return x.clone();
+    }
+    if let Some(values) = v.pointer("/arrayValue/values").and_then(Value::as_array) {
+        return Value::Array(values.iter().map(otel_value).collect());
+    }
+    if let Some(kvs) = v.pointer("/kvlistValue/values") {
+        return Value::Object(otel_attributes(Some(kvs)));
+    }
+    v.clone()
+}
+
+fn collect_spans<'a>(v: &'a Value, origin: &str) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    for (i, doc) in as_items(v).into_iter().enumerate() {
+        let resource_spans = doc.get("resourceSpans").and_then(Value::as_array);
+        let Some(resource_spans) = resource_spans else {
+            // A flat span, or a list of them.
+            spans.push(Span {
+                location: format!("{origin}[{i}]"),
+                raw: doc,
+                attrs: otel_attributes(doc.get("attributes")),
+                resource: otel_attributes(doc.pointer("/resource/attributes")),
+                start: ["startTimeUnixNano", "start_time", "startTime"]
+                    .iter()
+                    .find_map(|k| doc.get(*k).and_then(parse_time)),
+            });
+            continue;
+        };
+        for (ri, rs) in resource_spans.iter().enumerate() {
+            let resource = otel_attributes(rs.pointer("/resource/attributes"));
+            let scopes = rs
+                .get("scopeSpans")
+                .or_else(|| rs.get("instrumentationLibrarySpans"));
+            for (si, scope) in scopes
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                for (pi, span) in scope
+                    .get("spans")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                {
+                    spans.push(Span {
+                        location: format!(
+                            "{origin}.resourceSpans[{ri}].scopeSpans[{si}].spans[{pi}]"
+                        ),
+                        raw: span,
+                        attrs: otel_attributes(span.get("attributes")),
+                        resource: resource.clone(),
+                        start: span.get("startTimeUnixNano").and_then(parse_time),
+                    });
+                }
+            }
+        }
+    }
+    spans
+}
+
+/// Text of the newest GenAI event (`gen_ai.choice`, `gen_ai.user.message`, …).
+fn otel_event_text(span: &Value, names: &[&str]) -> Option<String> {
+    span.get("events")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter(|e| {
+            e.get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|n| names.contains(&n))
+        })
+        .filter_map(|e| {
+            let attrs = otel_attributes(e.get("attributes"));
+            let body = attrs
+                .get("message")
+                .or_else(|| attrs.get("content"))
+                .or_else(|| e.get("body"))?;
+            Some(text_of(body))
+        })
+        .find(|t| !t.is_empty())
+}
+
+fn parse_otel(v: &Value, origin: &str, out: &mut ParsedImport) {
+    let mut spans = collect_spans(v, origin);
+    spans.sort_by_key(|s| s.start);
+    let mut by_span_id: HashMap<String, Uuid> = HashMap::new();
+
+    for span in spans {
+        let is_genai = span.attrs.keys().any(|k| k.starts_with("gen_ai."))
+            || otel_event_text(
+                span.raw,
+                &[
+                    "gen_ai.choice",
+                    "gen_ai.user.message",
+                    "gen_ai.assistant.message",
+                ],
+            )
+            .is_some();
+        if !is_genai {
+            out.skipped += 1;
+            continue;
+        }
+        let attr = |k: &str| {
+            span.attrs
+                .get(k)
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+        };
+        let name = str_field(span.raw, &["name"]).unwrap_or("span");
+        let completion = otel_event_text(span.raw, &["gen_ai.choice", "gen_ai.assistant.message"])
+            .or_else(|| {
+                attr("gen_ai.completion.0.content")
+                    .or(attr("gen_ai.completion"))
+                    .map(str::to_string)
+            });
+        let prompt = || {
+            otel_event_text(span.raw, &["gen_ai.user.message"]).or_else(|| {
+                attr("gen_ai.prompt.0.content")
+                    .or(attr("gen_ai.prompt"))
+                    .map(str::to_string)
+            })
+        };
+        let target = completion
+            .or_else(prompt)
+            .unwrap_or_else(|| name.to_string());
+
+        let actor = attr("gen_ai.agent.name")
+            .or(attr("gen_ai.system"))
+            .or(attr("gen_ai.provider.name"))
+            .or(span.resource.get("service.name").and_then(Value::as_str))
+            .unwrap_or("otel");
+        let action = attr("gen_ai.operation.name").unwrap_or(name);
+        let trace_id = str_field(span.raw, &["traceId", "trace_id"]);
+        let span_id = str_field(span.raw, &["spanId", "span_id"]);
+        let parent_id = str_field(span.raw, &["parentSpanId", "parent_span_id"]);
+
+        let mut rec = record(
+            MemoryType::Temporal,
+            actor,
+            action,
+            truncate(&target),
+            serde_json::json!({
+                "trace_id": trace_id,
+                "span_id": span_id,
+                "parent_span_id": parent_id,
+                "span_name": name,
+                "model": attr("gen_ai.response.model").or(attr("gen_ai.request.model")),
+                "input_tokens": span.attrs.get("gen_ai.usage.input_tokens"),
+                "output_tokens": span.attrs.get("gen_ai.usage.output_tokens"),
+                "attributes": span.attrs,
+                "import_key": span_id.map(|s| format!("otel:{}:{s}", trace_id.unwrap_or(""))),
+            }),
+        );
+        if let Some(ts) = span.start {
+            rec.timestamp = ts;
+        }
+        rec.derived_from = parent_id.and_then(|p| by_span_id.get(p).copied());
+        if let Some(id) = span_id {
+            by_span_id.insert(id.to_string(), rec.id);
+        }
+        out.records.push((span.location, rec));
+    }
+}
+
+// ─── CSV ─────────────────────────────────────────────────────────────────────
+
+fn parse_csv(content: &str, origin: &str, out: &mut ParsedImport) -> Result<()> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .with_context(|| format!("{origin}: reading CSV header"))?
+        .iter()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let col = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+    let target_col = col(&["target", "text", "content", "note"])
+        .with_context(|| format!("{origin}: CSV needs a target, text, content or note column"))?;
+    let (actor_col, action_col) = (col(&["actor"]), col(&["action"]));
+    let type_col = col(&["record_type", "type"]);
+    let time_col = col(&["timestamp", "time", "date"]);
+    let tags_col = col(&["tags"]);
+    let conf_col = col(&["confidence"]);
+    let known: HashSet<usize> = [
+        Some(target_col),
+        actor_col,
+        action_col,
+        type_col,
+        time_col,
+        tags_col,
+        conf_col,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    for row in reader.records() {
+        let row = match row {
+            Ok(r) => r,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                out.issue(format!("{origin}:line {line}"), e.to_string());
+                continue;
+            }
+        };
+        let location = format!(
+            "{origin}:line {}",
+            row.position().map(|p| p.line()).unwrap_or(0)
+        );
+        let cell = |c: Option<usize>| c.and_then(|c| row.get(c)).filter(|s| !s.is_empty());
+        let Some(target) = cell(Some(target_col)) else {
+            out.issue(location, "empty target");
+            continue;
+        };
+        let record_type = match cell(type_col) {
+            Some(t) => match <MemoryType as ValueEnum>::from_str(t, true) {
+                Ok(t) => t,
+                Err(_) => {
+                    out.issue(location, format!("unknown record type {t:?}"));
+                    continue;
+                }
+            },
+            None => MemoryType::Temporal,
+        };
+        let metadata: Map<String, Value> = headers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !known.contains(i))
+            .filter_map(|(i, h)| {
+                Some((
+                    h.clone(),
+                    Value::from(row.get(i).filter(|s| !s.is_empty())?),
+                ))
+            })
+            .collect();
+        let mut rec = record(
+            record_type,
+            cell(actor_col).unwrap_or("import"),
+            cell(action_col).unwrap_or("observed"),
+            truncate(target),
+            Value::Object(metadata),
+        );
+        if let Some(ts) = cell(time_col) {
+            match parse_time(&Value::from(ts)) {
+                Some(ts) => rec.timestamp = ts,
+                None => {
+                    out.issue(location, format!("unparseable timestamp {ts:?}"));
+                    continue;
+                }
+            }
+        }
+        if let Some(tags) = cell(tags_col) {
+            rec.tags = tags
+                .split([';', ','])
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(c) = cell(conf_col).and_then(|c| c.parse::<f32>().ok()) {
+            rec.confidence = c.clamp(0.0, 1.0);
+        }
+        out.records.push((location, rec));
+    }
+    Ok(())
+}
+
+// ─── Markdown notes ──────────────────────────────────────────────────────────
+
+/// One note → one `Symbolic` record. Front matter `title`/`author`/`tags` are
+/// honoured; inline `#tags` are collected too. `None` for an empty note.
+fn parse_markdown_note(text: &str, path: &str) -> Option<MemoryRecord> {
+    let text = text.trim_start_matches('\u{feff}');
+    let (front, body) = split_front_matter(text);
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    let title = front
+        .get("title")
+        .cloned()
+        .or_else(|| {
+            body.lines()
+                .find_map(|l| l.strip_prefix("# "))
+                .map(|t| t.trim().to_string())
+        })
+        .unwrap_or_else(|| {
+            Path::new(path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or(path)
+                .to_string()
+        });
+
+    let mut tags = vec!["note".to_string()];
+    if let Some(list) = front.get("tags") {
+        tags.extend(
+            list.trim_matches(['[', ']'])
+                .split(',')
+                .map(|t| t.trim().trim_matches(['"', '\'']).to_string())
+                .filter(|t| !t.is_empty()),
+        );
+    }
+    let inline = regex::Regex::new(r"(?:^|\s)#([A-Za-z][\w/-]*)").expect("valid tag regex");
+    for cap in inline.captures_iter(body) {
+        let tag = cap[1].to_string();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let mut rec = record(
+        MemoryType::Symbolic,
+        front.get("author").map(String::as_str).unwrap_or("notes"),
+        "note",
+        truncate(body),
+        serde_json::json!({
+            "title": title,
+            "path": path,
+            "front_matter": front,
+            "import_key": format!("markdown:{path}"),
+        }),
+    );
+    rec.tags = tags;
+    if let Some(ts) = front.get("date").and_then(|d| {
+        parse_time(&Value::from(d.as_str())).or_else(|| {
+            chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .ok()
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        })
+    }) {
+        rec.timestamp = ts;
+    }
+    Some(rec)
+}
+
+/// `---`-delimited `key: value` front matter (flat YAML subset) and the rest.
+fn split_front_matter(text: &str) -> (std::collections::BTreeMap<String, String>, &str) {
+    let mut front = std::collections::BTreeMap::new();
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (front, text);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (front, text);
+    };
+    for line in rest[..end].lines() {
+        if let Some((k, v)) = line.split_once(':') {
+            front.insert(
+                k.trim().to_ascii_lowercase(),
+                v.trim().trim_matches(['"', '\'']).to_string(),
+            );
+        }
+    }
+    let body = rest[end + 4..].trim_start_matches(['-', '\r', '\n']);
+    (front, body)
+}
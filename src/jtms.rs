@@ -126,7 +126,12 @@ pub fn propagate_retraction<B: MemoryBackend>(
         payload.jtms_label = JtmsLabel::Out;
         if save_belief(store, id, &payload).is_ok() {
             if let Some(tx) = tx_log {
-                tx.append(TxKind::BeliefRetract, vec![id], actor);
+                tx.append_with_changes(
+                    TxKind::BeliefRetract,
+                    vec![id],
+                    actor,
+                    store.take_changes(),
+                );
             }
             cascaded.push(id);
         }
@@ -9,6 +9,8 @@ pub mod audit_log;
 #[path = "modules/aureus_bridge.rs"]
 pub mod aureus_bridge;
 pub mod cognitive_gc;
+pub mod cold_tier;
+pub mod concurrent_memory_store;
 pub mod cognitive_state;
 pub mod continuous_dynamics;
 pub mod digital_twin;
@@ -33,11 +35,16 @@ pub mod gui;
 pub mod hypotheses_graph;
 #[path = "modules/hypothesis_manager.rs"]
 pub mod hypothesis_manager;
+pub mod importers;
+pub mod injection_defense;
 #[path = "modules/integration_layer.rs"]
 pub mod integration_layer;
 pub mod knowledge_export;
 pub mod llm_clients;
+pub mod maintenance;
 pub mod markov;
+#[path = "modules/mcp/mod.rs"]
+pub mod mcp;
 #[path = "modules/mcp_bridge.rs"]
 pub mod mcp_bridge;
 pub mod memory;
@@ -50,18 +57,24 @@ pub mod memory_store;
 pub mod monitoring;
 #[path = "modules/openmanus_bridge.rs"]
 pub mod openmanus_bridge;
-pub mod optimized_memory_store;
+pub mod page_tier;
 pub mod payloads;
 #[path = "modules/perception_adapter.rs"]
 pub mod perception_adapter;
 pub mod persistence;
+pub mod pitr;
 pub mod plugin_host;
 pub mod poisson;
 #[path = "modules/procedural_cache.rs"]
 pub mod procedural_cache;
+pub mod redaction_vault;
 pub mod safety_classifier;
 pub mod safety_guardrail;
 pub mod state_diff;
+pub mod state_bundle;
+pub mod store_format;
+pub mod summarizer;
+pub mod telemetry;
 pub mod tx_log;
 pub use procedural_cache::skill_compiler;
 #[path = "modules/latent_map.rs"]
@@ -71,6 +84,7 @@ pub mod latent_map_evaluator;
 #[path = "modules/puzzle.rs"]
 pub mod puzzle;
 pub mod rag_adapter;
+pub mod record_set;
 #[path = "modules/reflexion_hooks.rs"]
 pub mod reflexion_hooks;
 #[path = "modules/representation_auditor.rs"]
@@ -78,6 +92,8 @@ pub mod representation_auditor;
 pub mod retrieval_pipeline;
 #[cfg(feature = "rocksdb-backend")]
 pub mod rocksdb_backend;
+#[cfg(feature = "sqlite_backend")]
+pub mod sqlite_backend;
 pub mod sandbox;
 pub mod schema;
 pub mod segmented_buffer;
@@ -123,10 +139,14 @@ pub mod broker_policy;
 pub mod continuation_checkpoint;
 #[path = "modules/executive/scheduler.rs"]
 pub mod executive_scheduler;
+#[path = "modules/executive/htn.rs"]
+pub mod htn;
 #[path = "modules/lease_manager.rs"]
 pub mod lease_manager;
 #[path = "modules/world_model_enhanced/simulator.rs"]
 pub mod mcts_simulator;
+#[path = "modules/executive/pddl.rs"]
+pub mod pddl;
 #[path = "modules/session_context.rs"]
 pub mod session_context;
 #[path = "modules/executive/task_graph.rs"]
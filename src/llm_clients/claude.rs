@@ -1,6 +1,9 @@
 use super::LLMClient;
 use reqwest::blocking::Client;
-use serde_json::json;
+use serde_json::{json, Value};
+
+const URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
 
 pub struct ClaudeClient {
     pub api_key: String,
@@ -14,27 +17,54 @@ impl ClaudeClient {
             model: model.into(),
         }
     }
+
+    fn body(&self, prompt: &str) -> Value {
+        json!({
+            "model": self.model,
+            "messages": [{"role": "user", "content": prompt}],
+        })
+    }
+
+    fn reply(val: &Value) -> String {
+        val["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or("")
+            .to_string()
+    }
 }
 
 impl LLMClient for ClaudeClient {
     fn generate_response(&self, prompt: &str) -> String {
-        let client = Client::new();
-        let body = json!({
-            "model": self.model,
-            "messages": [{"role": "user", "content": prompt}],
-        });
-        let resp = client
-            .post("https://api.anthropic.com/v1/messages")
+        let resp = Client::new()
+            .post(URL)
             .bearer_auth(&self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .json(&body)
+            .header("anthropic-version", API_VERSION)
+            .json(&self.body(prompt))
             .send();
         match resp {
-            Ok(r) => match r.json::<serde_json::Value>() {
-                Ok(val) => val["choices"][0]["message"]["content"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string(),
+            Ok(r) => match r.json::<Value>() {
+                Ok(val) => Self::reply(&val),
+                Err(_) => "".into(),
+            },
+            Err(_) => "".into(),
+        }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl super::AsyncLLMClient for ClaudeClient {
+    async fn generate_response(&self, prompt: &str) -> String {
+        let resp = reqwest::Client::new()
+            .post(URL)
+            .bearer_auth(&self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .json(&self.body(prompt))
+            .send()
+            .await;
+        match resp {
+            Ok(r) => match r.json::<Value>().await {
+                Ok(val) => Self::reply(&val),
                 Err(_) => "".into(),
             },
             Err(_) => "".into(),
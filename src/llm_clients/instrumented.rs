@@ -0,0 +1,38 @@
+use super::LLMClient;
+use std::time::Instant;
+
+/// Wraps any `LLMClient` and records call latency and token usage into
+/// `telemetry::METRICS` under `provider`. Tokens are estimated (≈4 chars/token)
+/// because `LLMClient` returns plain text without provider usage data.
+pub struct InstrumentedLLMClient<C: LLMClient> {
+    inner: C,
+    provider: String,
+}
+
+impl<C: LLMClient> InstrumentedLLMClient<C> {
+    pub fn new(inner: C, provider: impl Into<String>) -> Self {
+        Self {
+            inner,
+            provider: provider.into(),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C: LLMClient> LLMClient for InstrumentedLLMClient<C> {
+    fn generate_response(&self, prompt: &str) -> String {
+        let _span = tracing::info_span!("llm.generate", provider = self.provider.as_str()).entered();
+        let started = Instant::now();
+        let out = self.inner.generate_response(prompt);
+        crate::telemetry::record_llm_call(
+            &self.provider,
+            started.elapsed(),
+            crate::telemetry::estimate_tokens(prompt),
+            crate::telemetry::estimate_tokens(&out),
+        );
+        out
+    }
+}
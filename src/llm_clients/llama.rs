@@ -1,6 +1,6 @@
 use super::LLMClient;
 use reqwest::blocking::Client;
-use serde_json::json;
+use serde_json::{json, Value};
 
 /// Simple connector for llama.cpp style HTTP endpoints.
 pub struct LlamaClient {
@@ -13,16 +13,38 @@ impl LlamaClient {
             base_url: base_url.into(),
         }
     }
+
+    fn url(&self) -> String {
+        format!("{}/v1/generate", self.base_url.trim_end_matches('/'))
+    }
 }
 
 impl LLMClient for LlamaClient {
     fn generate_response(&self, prompt: &str) -> String {
-        let client = Client::new();
-        let url = format!("{}/v1/generate", self.base_url.trim_end_matches('/'));
         let body = json!({"prompt": prompt});
-        let resp = client.post(&url).json(&body).send();
+        let resp = Client::new().post(self.url()).json(&body).send();
+        match resp {
+            Ok(r) => match r.json::<Value>() {
+                Ok(val) => val["response"].as_str().unwrap_or("").to_string(),
+                Err(_) => "".into(),
+            },
+            Err(_) => "".into(),
+        }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl super::AsyncLLMClient for LlamaClient {
+    async fn generate_response(&self, prompt: &str) -> String {
+        let body = json!({"prompt": prompt});
+        let resp = reqwest::Client::new()
+            .post(self.url())
+            .json(&body)
+            .send()
+            .await;
         match resp {
-            Ok(r) => match r.json::<serde_json::Value>() {
+            Ok(r) => match r.json::<Value>().await {
                 Ok(val) => val["response"].as_str().unwrap_or("").to_string(),
                 Err(_) => "".into(),
             },
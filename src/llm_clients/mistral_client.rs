@@ -71,3 +71,47 @@ impl LLMClient for MistralClient {
         self.generate(prompt)
     }
 }
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl super::AsyncLLMClient for MistralClient {
+    async fn generate_response(&self, prompt: &str) -> String {
+        let url = format!("{}/generate", self.base_url.trim_end_matches('/'));
+        let resp = reqwest::Client::new()
+            .post(&url)
+            .json(&json!({"prompt": prompt}))
+            .send()
+            .await;
+        match resp {
+            Ok(r) => r
+                .json::<serde_json::Value>()
+                .await
+                .map(|v| v["text"].as_str().unwrap_or("").to_string())
+                .unwrap_or_default(),
+            Err(_) => "".into(),
+        }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl crate::embedding_provider::AsyncEmbeddingProvider for MistralClient {
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let url = format!("{}/embed", self.base_url.trim_end_matches('/'));
+        let v: serde_json::Value = reqwest::Client::new()
+            .post(&url)
+            .json(&json!({"text": text}))
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok(v["embedding"]
+            .as_array()
+            .map(|arr| arr.iter().filter_map(|x| x.as_f64().map(|f| f as f32)).collect())
+            .unwrap_or_default())
+    }
+
+    fn name(&self) -> &str {
+        "mistral"
+    }
+}
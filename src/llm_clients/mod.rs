@@ -9,9 +9,45 @@ pub trait LanguageModelClient: Send + Sync {
     fn chat(&self, history: Vec<String>, new_input: &str) -> String;
 }
 
+/// Non-blocking counterpart of `LLMClient` for callers on a tokio runtime.
+/// The HTTP clients implement it natively; wrap anything else in
+/// `BlockingLLMClient`.
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+pub trait AsyncLLMClient: Send + Sync {
+    async fn generate_response(&self, prompt: &str) -> String;
+}
+
+/// Runs a blocking `LLMClient` (a local command, a mock) on tokio's
+/// blocking pool.
+#[cfg(feature = "async-store")]
+pub struct BlockingLLMClient<C: LLMClient + ?Sized> {
+    inner: std::sync::Arc<C>,
+}
+
+#[cfg(feature = "async-store")]
+impl<C: LLMClient + ?Sized> BlockingLLMClient<C> {
+    pub fn new(inner: std::sync::Arc<C>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl<C: LLMClient + ?Sized + 'static> AsyncLLMClient for BlockingLLMClient<C> {
+    async fn generate_response(&self, prompt: &str) -> String {
+        let inner = self.inner.clone();
+        let prompt = prompt.to_string();
+        tokio::task::spawn_blocking(move || inner.generate_response(&prompt))
+            .await
+            .unwrap_or_default()
+    }
+}
+
 pub mod claude;
 pub mod deepseek_client;
 pub mod falcon_client;
+pub mod instrumented;
 pub mod llama;
 pub mod local_llm_client;
 pub mod mistral_client;
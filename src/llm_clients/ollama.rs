@@ -1,6 +1,6 @@
 use super::LLMClient;
 use reqwest::blocking::Client;
-use serde_json::json;
+use serde_json::{json, Value};
 
 pub struct OllamaClient {
     pub base_url: String,
@@ -14,16 +14,40 @@ impl OllamaClient {
             model: model.into(),
         }
     }
+
+    fn url(&self) -> String {
+        format!("{}/api/generate", self.base_url.trim_end_matches('/'))
+    }
+
+    fn body(&self, prompt: &str) -> Value {
+        json!({"model": self.model, "prompt": prompt})
+    }
 }
 
 impl LLMClient for OllamaClient {
     fn generate_response(&self, prompt: &str) -> String {
-        let client = Client::new();
-        let url = format!("{}/api/generate", self.base_url.trim_end_matches('/'));
-        let body = json!({"model": self.model, "prompt": prompt});
-        let resp = client.post(&url).json(&body).send();
+        let resp = Client::new().post(self.url()).json(&self.body(prompt)).send();
+        match resp {
+            Ok(r) => match r.json::<Value>() {
+                Ok(val) => val["response"].as_str().unwrap_or("").to_string(),
+                Err(_) => "".into(),
+            },
+            Err(_) => "".into(),
+        }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl super::AsyncLLMClient for OllamaClient {
+    async fn generate_response(&self, prompt: &str) -> String {
+        let resp = reqwest::Client::new()
+            .post(self.url())
+            .json(&self.body(prompt))
+            .send()
+            .await;
         match resp {
-            Ok(r) => match r.json::<serde_json::Value>() {
+            Ok(r) => match r.json::<Value>().await {
                 Ok(val) => val["response"].as_str().unwrap_or("").to_string(),
                 Err(_) => "".into(),
             },
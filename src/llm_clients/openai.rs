@@ -1,6 +1,8 @@
 use super::LLMClient;
 use reqwest::blocking::Client;
-use serde_json::json;
+use serde_json::{json, Value};
+
+const URL: &str = "https://api.openai.com/v1/chat/completions";
 
 pub struct OpenAIClient {
     pub api_key: String,
@@ -14,34 +16,74 @@ impl OpenAIClient {
             model: model.into(),
         }
     }
-}
 
-impl LLMClient for OpenAIClient {
-    fn generate_response(&self, prompt: &str) -> String {
-        if crate::safety_guardrail::SAFETY_GUARDRAIL
+    fn permitted(prompt: &str) -> bool {
+        crate::safety_guardrail::SAFETY_GUARDRAIL
             .lock()
             .unwrap()
             .check_precondition(prompt)
-            .is_err()
-        {
-            return String::new();
-        }
-        let client = Client::new();
-        let body = json!({
+            .is_ok()
+    }
+
+    fn body(&self, prompt: &str) -> Value {
+        json!({
             "model": self.model,
             "messages": [{"role": "user", "content": prompt}],
-        });
-        let resp = client
-            .post("https://api.openai.com/v1/chat/completions")
+        })
+    }
+
+    fn reply(val: &Value, started: std::time::Instant) -> String {
+        crate::telemetry::record_llm_call(
+            "openai",
+            started.elapsed(),
+            val["usage"]["prompt_tokens"].as_u64().unwrap_or(0),
+            val["usage"]["completion_tokens"].as_u64().unwrap_or(0),
+        );
+        val["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or("")
+            .to_string()
+    }
+}
+
+impl LLMClient for OpenAIClient {
+    fn generate_response(&self, prompt: &str) -> String {
+        if !Self::permitted(prompt) {
+            return String::new();
+        }
+        let started = std::time::Instant::now();
+        let resp = Client::new()
+            .post(URL)
             .bearer_auth(&self.api_key)
-            .json(&body)
+            .json(&self.body(prompt))
             .send();
         match resp {
-            Ok(r) => match r.json::<serde_json::Value>() {
-                Ok(val) => val["choices"][0]["message"]["content"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string(),
+            Ok(r) => match r.json::<Value>() {
+                Ok(val) => Self::reply(&val, started),
+                Err(_) => "".into(),
+            },
+            Err(_) => "".into(),
+        }
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl super::AsyncLLMClient for OpenAIClient {
+    async fn generate_response(&self, prompt: &str) -> String {
+        if !Self::permitted(prompt) {
+            return String::new();
+        }
+        let started = std::time::Instant::now();
+        let resp = reqwest::Client::new()
+            .post(URL)
+            .bearer_auth(&self.api_key)
+            .json(&self.body(prompt))
+            .send()
+            .await;
+        match resp {
+            Ok(r) => match r.json::<Value>().await {
+                Ok(val) => Self::reply(&val, started),
                 Err(_) => "".into(),
             },
             Err(_) => "".into(),
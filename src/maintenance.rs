@@ -0,0 +1,827 @@
+//! Maintenance — cron-like scheduler for background upkeep jobs.
+//!
+//! Chain-of-thought: decay, `purge_expired`, GC, source-trust decay, the
+//! scheduled coherence check, workspace/lease expiry and consolidation all
+//! exist, but each only runs when a request happens to call it. The
+//! `MaintenanceScheduler` owns a list of named jobs, each with a `Schedule`
+//! (`@every 5m`, `@hourly`, or a 5-field cron expression) and optional jitter.
+//! Jobs are claimed under the scheduler lock and executed outside it, so a slow
+//! consolidation pass never blocks the `/maintenance` endpoints. Every run lands
+//! in a bounded history ring, failures are counted per job and exported through
+//! `telemetry::METRICS`. The injection sweep quarantines the high-risk
+//! records that retrieval only withholds.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use rand::Rng;
+
+use crate::archive_store::ArchiveStore;
+use crate::cognitive_gc::{CognitiveGC, GcAction};
+use crate::coherence::CoherenceChecker;
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
+use crate::lease_manager::LeaseManager;
+use crate::memory_record::{Priority, RecordStatus};
+use crate::persistence::MemoryBackend;
+use crate::symbolic_store::{InMemoryGraph, SymbolicStore};
+use crate::tx_log::{TxKind, TxLog};
+use crate::workspace::WorkspaceRegistry;
+
+/// Number of runs kept in the history ring.
+pub const DEFAULT_HISTORY_CAP: usize = 500;
+
+// ─── Schedule ────────────────────────────────────────────────────────────────
+
+/// When a job fires.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    /// Fixed interval from the previous run.
+    Every(Duration),
+    /// Cron expression: minute hour day-of-month month day-of-week.
+    Cron(CronExpr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpr {
+    source: String,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl Schedule {
+    /// Parse `@every <n>(s|m|h|d)`, `@hourly`, `@daily`, `@weekly`, or a
+    /// 5-field cron expression supporting `*`, `*/n`, `a-b`, `a-b/n` and lists.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        match spec {
+            "@hourly" => return Self::parse("0 * * * *"),
+            "@daily" => return Self::parse("0 0 * * *"),
+            "@weekly" => return Self::parse("0 0 * * 0"),
+            _ => {}
+        }
+        if let Some(rest) = spec.strip_prefix("@every") {
+            return parse_duration(rest.trim()).map(Schedule::Every);
+        }
+        let fields: Vec<&str> = spec.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("cron expression needs 5 fields, got {}: {spec:?}", fields.len()));
+        }
+        Ok(Schedule::Cron(CronExpr {
+            source: spec.to_string(),
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days_of_month: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            // 7 is an alias for Sunday
+            days_of_week: {
+                let mut dow = parse_field(fields[4], 0, 7)?;
+                if dow[7] {
+                    dow[0] = true;
+                }
+                dow.truncate(7);
+                dow
+            },
+            dom_restricted: fields[2] != "*",
+            dow_restricted: fields[4] != "*",
+        }))
+    }
+
+    /// First fire time strictly after `after`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(d) => {
+                after + chrono::Duration::from_std(*d).unwrap_or_else(|_| chrono::Duration::minutes(1))
+            }
+            Schedule::Cron(c) => c.next_after(after),
+        }
+    }
+}
+
+impl std::fmt::Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Schedule::Every(d) => write!(f, "@every {}s", d.as_secs()),
+            Schedule::Cron(c) => f.write_str(&c.source),
+        }
+    }
+}
+
+impl CronExpr {
+    fn matches(&self, t: DateTime<Utc>) -> bool {
+        let dom = self.days_of_month[t.day() as usize];
+        let dow = self.days_of_week[t.weekday().num_days_from_sunday() as usize];
+        // Vixie-cron rule: when both day fields are restricted, either may match.
+        let day_ok = if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        };
+        self.minutes[t.minute() as usize]
+            && self.hours[t.hour() as usize]
+            && self.months[t.month() as usize]
+            && day_ok
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        let mut t = after
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(after)
+            + chrono::Duration::minutes(1);
+        // Any satisfiable expression fires within four years (Feb 29).
+        for _ in 0..(4 * 366 * 24 * 60) {
+            if self.matches(t) {
+                return t;
+            }
+            t += chrono::Duration::minutes(1);
+        }
+        t
+    }
+}
+
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (num, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let n: u64 = num.parse().map_err(|_| format!("invalid interval {s:?}"))?;
+    let secs = match unit {
+        "s" | "" => n,
+        "m" => n * 60,
+        "h" => n * 3600,
+        "d" => n * 86_400,
+        _ => return Err(format!("invalid interval unit in {s:?}")),
+    };
+    if secs == 0 {
+        return Err("interval must be > 0".into());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<bool>, String> {
+    let mut set = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().map_err(|_| format!("invalid step in {part:?}"))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(format!("step must be > 0 in {part:?}"));
+        }
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_num(a, min, max)?, parse_num(b, min, max)?)
+        } else {
+            let v = parse_num(range, min, max)?;
+            (v, if step > 1 { max } else { v })
+        };
+        if lo > hi {
+            return Err(format!("empty range {part:?}"));
+        }
+        for v in (lo..=hi).step_by(step as usize) {
+            set[v as usize] = true;
+        }
+    }
+    Ok(set)
+}
+
+fn parse_num(s: &str, min: u32, max: u32) -> Result<u32, String> {
+    let v: u32 = s.parse().map_err(|_| format!("invalid cron value {s:?}"))?;
+    if v < min || v > max {
+        return Err(format!("cron value {v} outside {min}-{max}"));
+    }
+    Ok(v)
+}
+
+// ─── Jobs and runs ───────────────────────────────────────────────────────────
+
+/// Job body: `Ok(detail)` on success, `Err(reason)` on failure.
+pub type JobFn = Arc<dyn Fn() -> Result<String, String> + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    Scheduled,
+    Manual,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobRun {
+    pub job: String,
+    pub trigger: Trigger,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub ok: bool,
+    /// Job output on success, error message on failure.
+    pub detail: String,
+}
+
+/// Serializable view of one job for listings.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub schedule: String,
+    pub jitter_secs: u64,
+    pub paused: bool,
+    pub running: bool,
+    pub next_run: Option<DateTime<Utc>>,
+    pub runs: u64,
+    pub failures: u64,
+    pub consecutive_failures: u64,
+    pub last_run: Option<JobRun>,
+    pub last_error: Option<String>,
+}
+
+struct Job {
+    name: String,
+    schedule: Schedule,
+    jitter: Duration,
+    run: JobFn,
+    paused: bool,
+    running: bool,
+    next_run: DateTime<Utc>,
+    runs: u64,
+    failures: u64,
+    consecutive_failures: u64,
+    last_run: Option<JobRun>,
+    last_error: Option<String>,
+}
+
+impl Job {
+    fn schedule_next(&mut self, after: DateTime<Utc>) {
+        let jitter_ms = self.jitter.as_millis() as i64;
+        let offset = if jitter_ms > 0 {
+            rand::thread_rng().gen_range(0..=jitter_ms)
+        } else {
+            0
+        };
+        self.next_run = self.schedule.next_after(after) + chrono::Duration::milliseconds(offset);
+    }
+
+    fn status(&self) -> JobStatus {
+        JobStatus {
+            name: self.name.clone(),
+            schedule: self.schedule.to_string(),
+            jitter_secs: self.jitter.as_secs(),
+            paused: self.paused,
+            running: self.running,
+            next_run: (!self.paused).then_some(self.next_run),
+            runs: self.runs,
+            failures: self.failures,
+            consecutive_failures: self.consecutive_failures,
+            last_run: self.last_run.clone(),
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+/// A job taken out of the scheduler for execution outside its lock.
+pub struct ClaimedJob {
+    pub name: String,
+    pub trigger: Trigger,
+    run: JobFn,
+}
+
+impl ClaimedJob {
+    /// Run the job body, catching panics as failures.
+    pub fn execute(self) -> JobRun {
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| (self.run)()))
+            .unwrap_or_else(|_| Err("job panicked".to_string()));
+        let elapsed = started.elapsed();
+        crate::telemetry::METRICS.observe_duration(
+            crate::telemetry::MAINTENANCE_JOB_SECONDS,
+            &[("job", &self.name)],
+            elapsed,
+        );
+        let (ok, detail) = match result {
+            Ok(d) => (true, d),
+            Err(e) => (false, e),
+        };
+        JobRun {
+            job: self.name,
+            trigger: self.trigger,
+            started_at,
+            duration_ms: elapsed.as_millis() as u64,
+            ok,
+            detail,
+        }
+    }
+}
+
+// ─── Scheduler ───────────────────────────────────────────────────────────────
+
+pub struct MaintenanceScheduler {
+    jobs: Vec<Job>,
+    history: VecDeque<JobRun>,
+    history_cap: usize,
+}
+
+impl Default for MaintenanceScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaintenanceScheduler {
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            history: VecDeque::new(),
+            history_cap: DEFAULT_HISTORY_CAP,
+        }
+    }
+
+    pub fn with_history_cap(mut self, cap: usize) -> Self {
+        self.history_cap = cap.max(1);
+        self
+    }
+
+    /// Register a job. The first run is scheduled from now. Names must be unique.
+    pub fn register(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        jitter: Duration,
+        run: JobFn,
+    ) -> Result<(), String> {
+        if self.jobs.iter().any(|j| j.name == name) {
+            return Err(format!("job {name:?} already registered"));
+        }
+        let mut job = Job {
+            name: name.to_string(),
+            schedule,
+            jitter,
+            run,
+            paused: false,
+            running: false,
+            next_run: Utc::now(),
+            runs: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            last_run: None,
+            last_error: None,
+        };
+        job.schedule_next(Utc::now());
+        self.jobs.push(job);
+        Ok(())
+    }
+
+    fn job_mut(&mut self, name: &str) -> Result<&mut Job, String> {
+        self.jobs
+            .iter_mut()
+            .find(|j| j.name == name)
+            .ok_or_else(|| format!("unknown job {name:?}"))
+    }
+
+    pub fn job_names(&self) -> Vec<String> {
+        self.jobs.iter().map(|j| j.name.clone()).collect()
+    }
+
+    pub fn jobs(&self) -> Vec<JobStatus> {
+        self.jobs.iter().map(Job::status).collect()
+    }
+
+    pub fn job(&self, name: &str) -> Option<JobStatus> {
+        self.jobs.iter().find(|j| j.name == name).map(Job::status)
+    }
+
+    pub fn pause(&mut self, name: &str) -> Result<(), String> {
+        self.job_mut(name)?.paused = true;
+        Ok(())
+    }
+
+    /// Resume a paused job; its next run is rescheduled from now.
+    pub fn resume(&mut self, name: &str) -> Result<(), String> {
+        let job = self.job_mut(name)?;
+        job.paused = false;
+        job.schedule_next(Utc::now());
+        Ok(())
+    }
+
+    /// Replace a job's schedule; the next run is recomputed from now.
+    pub fn reschedule(&mut self, name: &str, schedule: Schedule) -> Result<(), String> {
+        let job = self.job_mut(name)?;
+        job.schedule = schedule;
+        job.schedule_next(Utc::now());
+        Ok(())
+    }
+
+    /// Claim every unpaused, idle job whose `next_run <= now` and advance its
+    /// schedule. The caller executes the claims and hands results to `complete`.
+    pub fn claim_due(&mut self, now: DateTime<Utc>) -> Vec<ClaimedJob> {
+        let mut claimed = Vec::new();
+        for job in self.jobs.iter_mut() {
+            if job.paused || job.running || job.next_run > now {
+                continue;
+            }
+            job.running = true;
+            job.schedule_next(now);
+            claimed.push(ClaimedJob {
+                name: job.name.clone(),
+                trigger: Trigger::Scheduled,
+                run: job.run.clone(),
+            });
+        }
+        claimed
+    }
+
+    /// Claim one job for an immediate manual run (allowed while paused).
+    pub fn claim(&mut self, name: &str) -> Result<ClaimedJob, String> {
+        let job = self.job_mut(name)?;
+        if job.running {
+            return Err(format!("job {name:?} is already running"));
+        }
+        job.running = true;
+        Ok(ClaimedJob {
+            name: job.name.clone(),
+            trigger: Trigger::Manual,
+            run: job.run.clone(),
+        })
+    }
+
+    /// Record a finished run: update job counters, history and metrics.
+    pub fn complete(&mut self, run: JobRun) {
+        crate::telemetry::METRICS.inc(
+            crate::telemetry::MAINTENANCE_RUNS_TOTAL,
+            &[("job", &run.job), ("outcome", if run.ok { "ok" } else { "error" })],
+            1.0,
+        );
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.name == run.job) {
+            job.running = false;
+            job.runs += 1;
+            if run.ok {
+                job.consecutive_failures = 0;
+            } else {
+                job.failures += 1;
+                job.consecutive_failures += 1;
+                job.last_error = Some(run.detail.clone());
+                eprintln!("[maintenance] job {} failed: {}", run.job, run.detail);
+            }
+            job.last_run = Some(run.clone());
+        }
+        self.history.push_back(run);
+        while self.history.len() > self.history_cap {
+            self.history.pop_front();
+        }
+    }
+
+    /// Claim, execute and complete all due jobs in the calling thread. The
+    /// lock is held only to claim and to complete, never while a job runs.
+    pub fn run_due(scheduler: &Mutex<Self>, now: DateTime<Utc>) -> Vec<JobRun> {
+        let claimed = match scheduler.lock() {
+            Ok(mut s) => s.claim_due(now),
+            Err(_) => return Vec::new(),
+        };
+        let runs: Vec<JobRun> = claimed.into_iter().map(ClaimedJob::execute).collect();
+        if let Ok(mut s) = scheduler.lock() {
+            for run in &runs {
+                s.complete(run.clone());
+            }
+        }
+        runs
+    }
+
+    /// Run one job immediately in the calling thread, outside the lock.
+    pub fn trigger(scheduler: &Mutex<Self>, name: &str) -> Result<JobRun, String> {
+        let claimed = scheduler.lock().map_err(lock_err("scheduler"))?.claim(name)?;
+        let run = claimed.execute();
+        scheduler.lock().map_err(lock_err("scheduler"))?.complete(run.clone());
+        Ok(run)
+    }
+
+    /// Most recent runs first, optionally filtered to one job.
+    pub fn history(&self, job: Option<&str>, limit: usize) -> Vec<JobRun> {
+        self.history
+            .iter()
+            .rev()
+            .filter(|r| job.is_none_or(|j| r.job == j))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent failed runs first.
+    pub fn failures(&self, limit: usize) -> Vec<JobRun> {
+        self.history.iter().rev().filter(|r| !r.ok).take(limit).cloned().collect()
+    }
+
+    /// Earliest `next_run` among unpaused jobs.
+    pub fn next_wakeup(&self) -> Option<DateTime<Utc>> {
+        self.jobs.iter().filter(|j| !j.paused).map(|j| j.next_run).min()
+    }
+}
+
+/// Drive `scheduler` on a background thread, polling every `poll`.
+/// Jobs execute outside the scheduler lock.
+pub fn spawn_daemon(
+    scheduler: Arc<Mutex<MaintenanceScheduler>>,
+    poll: Duration,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(poll);
+        if scheduler.is_poisoned() {
+            return;
+        }
+        MaintenanceScheduler::run_due(&scheduler, Utc::now());
+    })
+}
+
+// ─── Standard jobs ───────────────────────────────────────────────────────────
+
+pub const JOB_PURGE_EXPIRED: &str = "purge_expired";
+pub const JOB_DECAY: &str = "decay";
+pub const JOB_GC: &str = "cognitive_gc";
+pub const JOB_SOURCE_TRUST: &str = "source_trust_decay";
+pub const JOB_COHERENCE: &str = "coherence_check";
+pub const JOB_WORKSPACES: &str = "workspace_evict";
+pub const JOB_LEASES: &str = "lease_cleanup";
+pub const JOB_CONSOLIDATION: &str = "consolidation";
+pub const JOB_CHECKPOINT: &str = "pitr_checkpoint";
+pub const JOB_INJECTIONS: &str = "injection_sweep";
+
+/// Schedules and thresholds for the standard jobs.
+#[derive(Debug, Clone)]
+pub struct MaintenanceConfig {
+    /// Job name → schedule spec (see `Schedule::parse`).
+    pub schedules: HashMap<String, String>,
+    pub jitter: Duration,
+    /// Records with `relevance_score` at or below this are collected by GC.
+    pub gc_threshold: f64,
+    /// Let GC hard-delete unreferenced records. Off by default: collected
+    /// records are moved to the archive store, or marked `Archived` in place
+    /// when there is none.
+    pub gc_hard_delete: bool,
+    /// Sources idle longer than this many days lose trust.
+    pub source_stale_days: i64,
+    /// Point-in-time-recovery checkpoints kept on disk; older ones are pruned.
+    pub checkpoint_keep: usize,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        let schedules = [
+            (JOB_PURGE_EXPIRED, "@every 1m"),
+            (JOB_DECAY, "@every 15m"),
+            (JOB_GC, "@hourly"),
+            (JOB_SOURCE_TRUST, "0 3 * * *"),
+            (JOB_COHERENCE, "@every 1m"),
+            (JOB_WORKSPACES, "@every 1m"),
+            (JOB_LEASES, "@every 30s"),
+            (JOB_CONSOLIDATION, "*/10 * * * *"),
+            (JOB_CHECKPOINT, "@hourly"),
+            (JOB_INJECTIONS, "@every 5m"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        Self {
+            schedules,
+            jitter: Duration::from_secs(5),
+            gc_threshold: 0.01,
+            gc_hard_delete: false,
+            source_stale_days: 30,
+            checkpoint_keep: 24,
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    /// Defaults overridden by `HIPCORTEX_MAINTENANCE`, a `;`-separated list of
+    /// `job=schedule` pairs (e.g. `decay=@every 5m;consolidation=0 * * * *`).
+    /// `HIPCORTEX_GC_HARD_DELETE=1` opts GC into hard deletes.
+    pub fn from_env() -> Result<Self, String> {
+        let mut cfg = Self::default();
+        if let Ok(spec) = std::env::var("HIPCORTEX_MAINTENANCE") {
+            cfg.apply_overrides(&spec)?;
+        }
+        cfg.gc_hard_delete = std::env::var("HIPCORTEX_GC_HARD_DELETE")
+            .is_ok_and(|v| matches!(v.as_str(), "1" | "true" | "yes"));
+        Ok(cfg)
+    }
+
+    pub fn apply_overrides(&mut self, spec: &str) -> Result<(), String> {
+        for pair in spec.split(';').filter(|p| !p.trim().is_empty()) {
+            let (job, sched) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected job=schedule, got {pair:?}"))?;
+            Schedule::parse(sched)?;
+            self.schedules.insert(job.trim().to_string(), sched.trim().to_string());
+        }
+        Ok(())
+    }
+
+    fn schedule(&self, job: &str) -> Result<Schedule, String> {
+        let spec = self
+            .schedules
+            .get(job)
+            .ok_or_else(|| format!("no schedule configured for {job:?}"))?;
+        Schedule::parse(spec)
+    }
+}
+
+/// Components the standard jobs operate on. Jobs whose component is `None`
+/// are not registered.
+pub struct MaintenanceTargets<B: MemoryBackend + Send + Sync + 'static> {
+    pub memory: Arc<ConcurrentMemoryStore<B>>,
+    pub archive: Option<Arc<Mutex<ArchiveStore>>>,
+    pub symbolic: Option<Arc<Mutex<SymbolicStore<InMemoryGraph>>>>,
+    pub tx_log: Option<Arc<TxLog>>,
+    pub coherence: Option<Arc<CoherenceChecker>>,
+    pub workspaces: Option<Arc<Mutex<WorkspaceRegistry>>>,
+    pub leases: Option<Arc<Mutex<LeaseManager>>>,
+}
+
+impl<B: MemoryBackend + Send + Sync + 'static> MaintenanceTargets<B> {
+    pub fn new(memory: Arc<ConcurrentMemoryStore<B>>) -> Self {
+        Self {
+            memory,
+            archive: None,
+            symbolic: None,
+            tx_log: None,
+            coherence: None,
+            workspaces: None,
+            leases: None,
+        }
+    }
+}
+
+fn lock_err<T>(what: &str) -> impl FnOnce(T) -> String + '_ {
+    move |_| format!("{what} lock poisoned")
+}
+
+/// Build a scheduler with the standard jobs for the components in `targets`.
+pub fn standard_scheduler<B: MemoryBackend + Send + Sync + 'static>(
+    targets: MaintenanceTargets<B>,
+    config: &MaintenanceConfig,
+) -> Result<MaintenanceScheduler, String> {
+    let mut s = MaintenanceScheduler::new();
+    let jitter = config.jitter;
+
+    let mem = targets.memory.clone();
+    let log = targets.tx_log.clone();
+    s.register(JOB_PURGE_EXPIRED, config.schedule(JOB_PURGE_EXPIRED)?, jitter, Arc::new(move || {
+        let mut ms = mem.lock().map_err(lock_err("memory"))?;
+        let n = ms.purge_expired();
+        if n > 0 {
+            ms.compact_backend().map_err(|e| e.to_string())?;
+            if let Some(log) = &log {
+                crate::pitr::log_pending(&mut ms, log, TxKind::MemoryDelete, "maintenance");
+            }
+        }
+        Ok(format!("purged {n} expired records"))
+    }))?;
+
+    let mem = targets.memory.clone();
+    s.register(JOB_DECAY, config.schedule(JOB_DECAY)?, jitter, Arc::new(move || {
+        let n = mem.lock().map_err(lock_err("memory"))?.apply_decay();
+        Ok(format!("decayed {n} records"))
+    }))?;
+
+    let mem = targets.memory.clone();
+    let archive = targets.archive.clone();
+    let log = targets.tx_log.clone();
+    let threshold = config.gc_threshold;
+    let hard_delete = config.gc_hard_delete;
+    s.register(JOB_GC, config.schedule(JOB_GC)?, jitter, Arc::new(move || {
+        let mut ms = mem.lock().map_err(lock_err("memory"))?;
+        let mut gc = CognitiveGC::new();
+        gc.rebuild_from_records(ms.all());
+        let doomed: Vec<_> = ms
+            .all()
+            .iter()
+            .filter(|r| {
+                r.priority != Priority::Pinned
+                    && r.status != RecordStatus::Archived
+                    && r.relevance_score <= threshold
+            })
+            .map(|r| (r.id, gc.gc_action(r.id)))
+            .collect();
+        let (mut archived, mut deleted) = (0usize, 0usize);
+        for (id, action) in doomed {
+            if hard_delete && action != GcAction::Archive {
+                ms.delete_by_id(id);
+                deleted += 1;
+                continue;
+            }
+            match (&archive, ms.find_by_id(id).cloned()) {
+                (Some(arc), Some(rec)) => {
+                    arc.lock()
+                        .map_err(lock_err("archive"))?
+                        .append(rec)
+                        .map_err(|e| format!("archive error: {e}"))?;
+                    ms.delete_by_id(id);
+                }
+                // Nowhere to move it: soft-archive in place.
+                _ => ms
+                    .set_status(id, RecordStatus::Archived)
+                    .map_err(|e| e.to_string())?,
+            }
+            archived += 1;
+        }
+        if archived + deleted > 0 {
+            ms.compact_backend().map_err(|e| e.to_string())?;
+            if let Some(log) = &log {
+                crate::pitr::log_pending(&mut ms, log, TxKind::ArchiveRecord, "maintenance");
+            }
+        }
+        Ok(format!("archived {archived}, deleted {deleted}"))
+    }))?;
+
+    let mem = targets.memory.clone();
+    let stale_days = config.source_stale_days;
+    s.register(JOB_SOURCE_TRUST, config.schedule(JOB_SOURCE_TRUST)?, jitter, Arc::new(move || {
+        let mut ms = mem.lock().map_err(lock_err("memory"))?;
+        ms.source_trust.decay_all_stale(stale_days);
+        Ok(format!("decayed sources idle > {stale_days} days"))
+    }))?;
+
+    // Retrieval only withholds high-risk hits; this sweep quarantines them.
+    // Scoring runs on a snapshot, the writer lock only covers the updates.
+    let mem = targets.memory.clone();
+    let log = targets.tx_log.clone();
+    s.register(JOB_INJECTIONS, config.schedule(JOB_INJECTIONS)?, jitter, Arc::new(move || {
+        let cfg = crate::injection_defense::InjectionConfig::default();
+        let ids = crate::injection_defense::high_risk(&mem.snapshot(), &cfg);
+        if ids.is_empty() {
+            return Ok("quarantined 0 records".into());
+        }
+        let mut ms = mem.lock().map_err(lock_err("memory"))?;
+        let n = crate::injection_defense::quarantine(&mut ms, &ids)
+            .map_err(|e| e.to_string())?
+            .len();
+        if let Some(log) = &log {
+            crate::pitr::log_pending(&mut ms, log, TxKind::MemoryUpdate, "maintenance");
+        }
+        Ok(format!("quarantined {n} records"))
+    }))?;
+
+    if let Some(coherence) = targets.coherence.clone() {
+        s.register(JOB_COHERENCE, config.schedule(JOB_COHERENCE)?, jitter, Arc::new(move || {
+            if !coherence.should_run_scheduled_check()? {
+                return Ok("skipped: checked recently".into());
+            }
+            let found = coherence.check_consistency()?;
+            Ok(format!("{} inconsistencies", found.len()))
+        }))?;
+    }
+
+    if let Some(ws) = targets.workspaces.clone() {
+        s.register(JOB_WORKSPACES, config.schedule(JOB_WORKSPACES)?, jitter, Arc::new(move || {
+            let n = ws.lock().map_err(lock_err("workspace registry"))?.evict_expired();
+            Ok(format!("evicted {n} workspaces"))
+        }))?;
+    }
+
+    if let Some(leases) = targets.leases.clone() {
+        s.register(JOB_LEASES, config.schedule(JOB_LEASES)?, jitter, Arc::new(move || {
+            let n = leases.lock().map_err(lock_err("lease manager"))?.cleanup_expired();
+            Ok(format!("released {n} expired leases"))
+        }))?;
+    }
+
+    if let (Some(archive), Some(symbolic), Some(log)) =
+        (targets.archive.clone(), targets.symbolic.clone(), targets.tx_log.clone())
+    {
+        let mem = targets.memory.clone();
+        s.register(JOB_CONSOLIDATION, config.schedule(JOB_CONSOLIDATION)?, jitter, Arc::new(move || {
+            let cfg = crate::consolidation::ConsolidationConfig::default();
+            let mut ms = mem.lock().map_err(lock_err("memory"))?;
+            let pressure = crate::consolidation::compute_pressure(&ms, &cfg);
+            if pressure <= cfg.pressure_threshold {
+                return Ok(format!("skipped: pressure {pressure:.2} below threshold"));
+            }
+            let mut arc = archive.lock().map_err(lock_err("archive"))?;
+            let mut sym = symbolic.lock().map_err(lock_err("symbolic store"))?;
+            let report = crate::consolidation::consolidate(&mut ms, &mut arc, &mut sym, &log, &cfg)?;
+            if report.records_archived > 0 {
+                ms.compact_backend().map_err(|e| e.to_string())?;
+            }
+            Ok(format!(
+                "{} groups, {} records archived",
+                report.groups_consolidated, report.records_archived
+            ))
+        }))?;
+    }
+
+    if let Some(log) = targets.tx_log.clone() {
+        let mem = targets.memory.clone();
+        let keep = config.checkpoint_keep;
+        s.register(JOB_CHECKPOINT, config.schedule(JOB_CHECKPOINT)?, jitter, Arc::new(move || {
+            let tx = {
+                let mut ms = mem.lock().map_err(lock_err("memory"))?;
+                crate::pitr::checkpoint(&mut ms, &log)?
+            };
+            let pruned = crate::pitr::prune_checkpoints(&log, keep)?;
+            Ok(format!("checkpoint at tx {tx}, pruned {pruned}"))
+        }))?;
+    }
+
+    Ok(s)
+}
@@ -2,6 +2,7 @@
 use crate::{
     aureus_bridge::AureusBridge,
     coherence::CoherenceChecker,
+    concurrent_memory_store::ConcurrentMemoryStore,
     grpc_server,
     integration_layer::IntegrationLayer,
     memory_store::MemoryStore,
@@ -25,7 +26,7 @@ use uuid::Uuid;
 #[cfg(all(feature = "web-server", feature = "grpc-server"))]
 /// Combined MCP server struct holding the core modules and stores.
 pub struct McpServer<B: MemoryBackend + Send + 'static> {
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     symbolic: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
     indexer: Arc<Mutex<TemporalIndexer<Uuid>>>,
     fsm: Arc<Mutex<ProceduralCache>>,
@@ -61,7 +62,7 @@ impl<B: MemoryBackend + Send + 'static> McpServer<B> {
             PerceptionSession::new()
         };
         Self {
-            store: Arc::new(Mutex::new(store)),
+            store: Arc::new(ConcurrentMemoryStore::new(store)),
             symbolic: Arc::new(Mutex::new(SymbolicStore::new())),
             indexer: Arc::new(Mutex::new(TemporalIndexer::new(256, 60))),
             fsm: Arc::new(Mutex::new(ProceduralCache::new())),
@@ -75,7 +76,7 @@ impl<B: MemoryBackend + Send + 'static> McpServer<B> {
     }
 
     /// Access the underlying memory store.
-    pub fn store(&self) -> Arc<Mutex<MemoryStore<B>>> {
+    pub fn store(&self) -> Arc<ConcurrentMemoryStore<B>> {
         self.store.clone()
     }
 
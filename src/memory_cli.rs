@@ -1,12 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
 use crate::llm_clients::{LLMClient, LanguageModelClient};
 use crate::memory_processor::MemoryProcessor;
 use crate::memory_query::MemoryQuery;
-use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_record::{MemoryRecord, MemoryType, RecordStatus};
 use crate::memory_store::MemoryStore;
 use crate::snapshot_manager::SnapshotManager;
 use uuid::Uuid;
@@ -17,6 +19,10 @@ struct Cli {
     /// Path to memory store file
     #[arg(long, default_value = "memory.jsonl")]
     store: String,
+    /// Keep at most this many records in memory and page the rest out to
+    /// `<stem>-pages/` next to the store
+    #[arg(long)]
+    hot_records: Option<usize>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -85,25 +91,132 @@ enum Commands {
     RunWorkflow,
     /// Show recent safety audit snapshots
     SafetyAudit,
+    /// Run maintenance jobs (expiry purge, decay, GC, source-trust decay, consolidation)
+    Maintain {
+        /// Run only the named job (repeatable); default is every job
+        #[arg(long = "job")]
+        jobs: Vec<String>,
+        /// List jobs and schedules without running anything
+        #[arg(long)]
+        list: bool,
+        /// Keep running jobs on their schedules instead of one pass
+        #[arg(long)]
+        daemon: bool,
+        /// Schedule overrides, e.g. "decay=@every 5m;consolidation=0 * * * *"
+        #[arg(long)]
+        schedule: Option<String>,
+    },
+    /// Import chat transcripts, agent traces, OTel spans, CSV or Markdown notes
+    Import {
+        /// File to import, or a directory of Markdown notes
+        path: PathBuf,
+        /// Input format; detected from the extension and content when omitted
+        #[arg(long, value_enum)]
+        format: Option<crate::importers::ImportFormat>,
+        /// Report what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Session id for chat transcripts that carry none
+        #[arg(long)]
+        session: Option<String>,
+        /// `source` recorded on every imported record
+        #[arg(long)]
+        source: Option<String>,
+        /// Write the full JSON report (including every error) to this file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Write a point-in-time-recovery checkpoint next to the store's tx log
+    Checkpoint,
+    /// Rewind the store to a past tx id or timestamp by replaying the tx log
+    Recover {
+        #[arg(long, conflicts_with = "at", required_unless_present = "at")]
+        tx: Option<u64>,
+        /// RFC 3339 timestamp, e.g. 2026-10-01T12:00:00Z
+        #[arg(long)]
+        at: Option<DateTime<Utc>>,
+        /// Print what the store would contain without changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Upgrade the store file to the current on-disk format (keeps a backup)
+    Migrate {
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not keep `<store>.v<N>.bak`
+        #[arg(long)]
+        no_backup: bool,
+        /// Write the migrated store to this path and leave the original as is
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Write every record (hot and cold) to a portable state bundle
+    ExportBundle {
+        /// Bundle file; gzip-compressed when it ends in `.gz`
+        out: PathBuf,
+        /// Export only records tagged `ns:<namespace>`
+        #[arg(long)]
+        namespace: Option<String>,
+    },
+    /// Load a state bundle (or a legacy record export) into the store
+    ImportBundle {
+        path: PathBuf,
+        /// Make the store match the bundle instead of merging into it
+        #[arg(long)]
+        replace: bool,
+        /// Rename a namespace on the way in, e.g. `--ns acme=acme-staging`
+        #[arg(long = "ns", value_name = "FROM=TO")]
+        namespace_map: Vec<String>,
+    },
+    /// Replace redaction tokens in TEXT with the originals from the vault
+    Rehydrate { text: String },
 }
 
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
+    // Runs before the store is opened: loading a file from a newer build fails.
+    if let Commands::Migrate {
+        dry_run,
+        no_backup,
+        out,
+    } = cli.command
+    {
+        return migrate(&cli.store, dry_run, no_backup, out);
+    }
+    // Safety policy and redaction vault (`<stem>-vault.jsonl`) from the environment.
+    crate::safety_guardrail::SAFETY_GUARDRAIL
+        .lock()
+        .unwrap()
+        .configure_from_env(Some(&sibling_path(&cli.store, "vault.jsonl")))
+        .map_err(anyhow::Error::msg)?;
     let mut store = MemoryStore::new(&cli.store)?;
+    if let Some(hot) = cli.hot_records {
+        store = store.with_paging(sibling_path(&cli.store, "pages"), hot)?;
+    }
+    store.set_write_screening(true);
     match cli.command {
         Commands::Add {
             actor,
             action,
             target,
         } => {
-            let record = MemoryRecord::new(
+            let mut record = MemoryRecord::new(
                 MemoryType::Temporal,
                 actor,
                 action,
                 target,
                 serde_json::json!({}),
             );
-            store.add(record)?;
+            if let Some(outcome) = store.screen(&mut record)? {
+                if outcome.redacted > 0 {
+                    println!("redacted {} span(s): {}", outcome.redacted, record.target);
+                }
+                if record.status == RecordStatus::Quarantine {
+                    println!("quarantined: {:?} risk={:.2}", outcome.category, outcome.risk_score);
+                }
+            }
+            store.add_prescreened(record)?;
             // Keep on-disk store deduplicated when adding records
             let mut all = store.all().to_vec();
             MemoryProcessor::deduplicate(&mut all);
@@ -267,6 +380,283 @@ pub fn run() -> Result<()> {
             };
             println!("{}", serde_json::to_string_pretty(&snaps)?);
         }
+        Commands::Maintain {
+            jobs,
+            list,
+            daemon,
+            schedule,
+        } => {
+            maintain(store, &cli.store, &jobs, list, daemon, schedule.as_deref())?;
+        }
+        Commands::Import {
+            path,
+            format,
+            dry_run,
+            session,
+            source,
+            report,
+        } => {
+            use crate::importers::{ImportOptions, Importer};
+            let options = ImportOptions {
+                format,
+                dry_run,
+                source,
+                session,
+            };
+            let mut importer = Importer::new(options).with_progress(|p| {
+                if p.done % 500 == 0 || p.done == p.total {
+                    eprint!("\r{}/{} processed", p.done, p.total);
+                    if p.done == p.total {
+                        eprintln!();
+                    }
+                }
+            });
+            let result = importer.import_path(&mut store, &path)?;
+            let verb = if result.dry_run { "would import" } else { "imported" };
+            println!(
+                "{} ({}): {verb} {} of {} records, {} duplicates, {} skipped, {} errors",
+                path.display(),
+                result.format.name(),
+                result.imported,
+                result.scanned,
+                result.duplicates,
+                result.skipped,
+                result.errors.len()
+            );
+            for issue in result.errors.iter().take(20) {
+                println!("  {}: {}", issue.location, issue.message);
+            }
+            if result.errors.len() > 20 {
+                println!("  … {} more", result.errors.len() - 20);
+            }
+            if let Some(out) = report {
+                std::fs::write(&out, serde_json::to_string_pretty(&result)?)?;
+            }
+        }
+        Commands::Checkpoint => {
+            let log = crate::tx_log::TxLog::open(sibling_path(&cli.store, "tx.jsonl"))
+                .map_err(anyhow::Error::msg)?;
+            let tx = crate::pitr::checkpoint(&mut store, &log).map_err(anyhow::Error::msg)?;
+            println!(
+                "checkpoint at tx {tx} in {}",
+                crate::pitr::checkpoint_dir(&log).display()
+            );
+        }
+        Commands::Recover { tx, at, dry_run } => {
+            use crate::pitr::AsOf;
+            let log = crate::tx_log::TxLog::open(sibling_path(&cli.store, "tx.jsonl"))
+                .map_err(anyhow::Error::msg)?;
+            let point = match (tx, at) {
+                (Some(tx), _) => AsOf::Tx(tx),
+                (None, Some(at)) => AsOf::TimestampMs(at.timestamp_millis().max(0) as u64),
+                (None, None) => anyhow::bail!("one of --tx or --at is required"),
+            };
+            if dry_run {
+                let target = crate::pitr::resolve_tx(&log, point).map_err(anyhow::Error::msg)?;
+                let records = crate::pitr::state_as_of(&log, point).map_err(anyhow::Error::msg)?;
+                println!("as of tx {target}: {} records", records.len());
+                for rec in &records {
+                    println!("{}  {} {} {}", rec.id, rec.actor, rec.action, rec.target);
+                }
+            } else {
+                let report =
+                    crate::pitr::restore(&mut store, &log, point).map_err(anyhow::Error::msg)?;
+                println!(
+                    "restored to tx {} ({} -> {} records), logged as tx {}",
+                    report.target_tx, report.records_before, report.records_after, report.restore_tx
+                );
+            }
+        }
+        Commands::Migrate { .. } => unreachable!("handled before the store is opened"),
+        Commands::ExportBundle { out, namespace } => {
+            use crate::state_bundle::{BundleExportOptions, BundleTargets};
+            let mut targets = BundleTargets::new(Arc::new(ConcurrentMemoryStore::new(store)));
+            targets.tx_log = existing_tx_log(&cli.store)?;
+            let bundle = crate::state_bundle::export(&targets, &BundleExportOptions { namespace })?;
+            crate::state_bundle::write_bundle(&out, &bundle)?;
+            println!("exported {} records to {}", bundle.records.len(), out.display());
+        }
+        Commands::ImportBundle {
+            path,
+            replace,
+            namespace_map,
+        } => {
+            use crate::state_bundle::{BundleImportOptions, BundleMode, BundleTargets};
+            let mut options = BundleImportOptions {
+                mode: if replace { BundleMode::Replace } else { BundleMode::Merge },
+                actor: Some("cli".into()),
+                ..Default::default()
+            };
+            for pair in &namespace_map {
+                let (from, to) = pair
+                    .split_once('=')
+                    .with_context(|| format!("--ns expects FROM=TO, got {pair:?}"))?;
+                options.namespace_map.insert(from.to_string(), to.to_string());
+            }
+            let mut targets = BundleTargets::new(Arc::new(ConcurrentMemoryStore::new(store)));
+            targets.tx_log = existing_tx_log(&cli.store)?;
+            let doc = crate::state_bundle::read_bundle(&path)?;
+            let report = crate::state_bundle::import(&targets, doc, &options)?;
+            for step in &report.migrations {
+                println!("migrated {step}");
+            }
+            println!(
+                "{}: {} records added, {} already present, {} removed, {} remapped",
+                path.display(),
+                report.records_added,
+                report.records_existing,
+                report.records_removed,
+                report.records_remapped
+            );
+            if !report.sections_ignored.is_empty() {
+                println!("ignored sections: {}", report.sections_ignored.join(", "));
+            }
+        }
+        Commands::Rehydrate { text } => {
+            let vault = crate::safety_guardrail::SAFETY_GUARDRAIL
+                .lock()
+                .unwrap()
+                .vault()
+                .context("no redaction vault; set HIPCORTEX_VAULT_KEY")?;
+            println!("{}", vault.rehydrate(&text)?);
+        }
+    }
+    Ok(())
+}
+
+/// `hipcortex migrate`: upgrade the store file in place via `store_format`.
+fn migrate(store_path: &str, dry_run: bool, no_backup: bool, out: Option<PathBuf>) -> Result<()> {
+    use crate::store_format::{migrate_file, MigrateOptions, FORMAT_VERSION};
+    let path = Path::new(store_path);
+    if !path.exists() {
+        anyhow::bail!("{store_path} does not exist");
+    }
+    let options = MigrateOptions {
+        dry_run,
+        no_backup,
+        out,
+    };
+    let report = migrate_file(path, &options)?;
+    if report.steps.is_empty() && report.dropped_lines.is_empty() {
+        println!(
+            "{store_path} is already at format {FORMAT_VERSION} ({} records)",
+            report.records
+        );
+        if report.written.is_none() {
+            return Ok(());
+        }
+    }
+    for step in &report.steps {
+        println!("{step}");
+    }
+    let verb = if dry_run { "would update" } else { "updated" };
+    println!(
+        "format {} -> {}: {verb} {} of {} records ({} resealed), dropped {} invalid lines",
+        report.from_version,
+        report.to_version,
+        report.changed,
+        report.records,
+        report.resealed,
+        report.dropped_lines.len()
+    );
+    if !report.dropped_lines.is_empty() {
+        println!("  dropped lines: {:?}", report.dropped_lines);
+    }
+    if let Some(backup) = &report.backup {
+        println!("backup: {}", backup.display());
+    }
+    if let Some(written) = &report.written {
+        println!("wrote {}", written.display());
+    }
+    Ok(())
+}
+
+/// The store's tx log, if it has one; bundle commands do not create it.
+fn existing_tx_log(store_path: &str) -> Result<Option<Arc<crate::tx_log::TxLog>>> {
+    let path = sibling_path(store_path, "tx.jsonl");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let log = crate::tx_log::TxLog::open(path).map_err(anyhow::Error::msg)?;
+    Ok(Some(Arc::new(log)))
+}
+
+/// `<dir>/<stem>-<suffix>` next to the store file, e.g. `memory-tx.jsonl`.
+fn sibling_path(store_path: &str, suffix: &str) -> PathBuf {
+    let path = PathBuf::from(store_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("memory");
+    path.with_file_name(format!("{stem}-{suffix}"))
+}
+
+/// `hipcortex maintain`: build the standard maintenance jobs around the store
+/// file (archive and tx log live next to it) and run them once or on schedule.
+fn maintain(
+    store: MemoryStore<crate::persistence::FileBackend>,
+    store_path: &str,
+    only: &[String],
+    list: bool,
+    daemon: bool,
+    schedule: Option<&str>,
+) -> Result<()> {
+    use crate::maintenance::{
+        standard_scheduler, MaintenanceConfig, MaintenanceScheduler, MaintenanceTargets,
+    };
+
+    let sibling = |suffix: &str| sibling_path(store_path, suffix);
+
+    let mut config = MaintenanceConfig::from_env().map_err(anyhow::Error::msg)?;
+    if let Some(spec) = schedule {
+        config.apply_overrides(spec).map_err(anyhow::Error::msg)?;
+    }
+    let memory = Arc::new(ConcurrentMemoryStore::new(store));
+    let mut targets = MaintenanceTargets::new(memory.clone());
+    targets.archive = Some(Arc::new(Mutex::new(crate::archive_store::ArchiveStore::new(
+        sibling("archive.jsonl"),
+    ))));
+    targets.symbolic = Some(Arc::new(Mutex::new(crate::symbolic_store::SymbolicStore::new())));
+    targets.tx_log = Some(Arc::new(
+        crate::tx_log::TxLog::open(sibling("tx.jsonl")).map_err(anyhow::Error::msg)?,
+    ));
+    let mut scheduler = standard_scheduler(targets, &config).map_err(anyhow::Error::msg)?;
+
+    let names = scheduler.job_names();
+    if let Some(unknown) = only.iter().find(|j| !names.contains(j)) {
+        anyhow::bail!("unknown job {unknown:?}; available: {}", names.join(", "));
+    }
+    // Restrict to the selected jobs by pausing the rest.
+    for name in names.iter().filter(|n| !only.is_empty() && !only.contains(n)) {
+        scheduler.pause(name).map_err(anyhow::Error::msg)?;
+    }
+
+    if list {
+        for job in scheduler.jobs() {
+            let next = job.next_run.map(|t| t.to_rfc3339()).unwrap_or_else(|| "paused".into());
+            println!("{:<20} {:<16} next: {}", job.name, job.schedule, next);
+        }
+        return Ok(());
+    }
+
+    if daemon {
+        let scheduler = Arc::new(Mutex::new(scheduler));
+        crate::maintenance::spawn_daemon(scheduler, std::time::Duration::from_secs(1))
+            .join()
+            .map_err(|_| anyhow::anyhow!("maintenance daemon panicked"))?;
+        return Ok(());
+    }
+
+    let scheduler = Mutex::new(scheduler);
+    let mut failed = 0;
+    for name in names.iter().filter(|n| only.is_empty() || only.contains(n)) {
+        let run = MaintenanceScheduler::trigger(&scheduler, name).map_err(anyhow::Error::msg)?;
+        let outcome = if run.ok { "ok" } else { "FAILED" };
+        println!("{:<20} {:<6} {:>6}ms  {}", run.job, outcome, run.duration_ms, run.detail);
+        if !run.ok {
+            failed += 1;
+        }
+    }
+    memory.lock().map_err(|_| anyhow::anyhow!("memory lock poisoned"))?.flush()?;
+    if failed > 0 {
+        anyhow::bail!("{failed} maintenance job(s) failed");
     }
     Ok(())
 }
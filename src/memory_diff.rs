@@ -1,4 +1,4 @@
-use crate::memory_record::MemoryRecord;
+use crate::memory_record::{MemoryRecord, RecordStatus};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -37,7 +37,7 @@ pub struct StateDiff {
     pub to_version: u32,
     pub field_changes: Vec<FieldChange>,
     pub confidence_delta: f32,
-    pub status_change: Option<(String, String)>,
+    pub status_change: Option<(RecordStatus, RecordStatus)>,
     pub react_iterations_delta: u32,
 }
 
@@ -73,7 +73,7 @@ pub fn compute_diff(from: &MemoryRecord, to: &MemoryRecord) -> StateDiff {
         field_changes: changes,
         confidence_delta: to.confidence - from.confidence,
         status_change: if from.status != to.status {
-            Some((from.status.clone(), to.status.clone()))
+            Some((from.status, to.status))
         } else {
             None
         },
@@ -54,14 +54,12 @@ pub struct MemoryRecord {
     /// Tags for categorization and RAG filtering (e.g. ["bug", "architecture", "decision"])
     #[serde(default)]
     pub tags: Vec<String>,
-    /// Memory priority: "pinned" bypasses decay and always appears in search.
-    /// Values: "pinned" | "high" | "normal" | "low". Default "normal".
-    #[serde(default = "default_priority")]
-    pub priority: String,
-    /// Memory status: "active" | "quarantine" | "archived". Default "active".
+    /// Memory priority: `Pinned` bypasses decay and always appears in search.
+    #[serde(default)]
+    pub priority: Priority,
     /// Quarantined records are excluded from search/query unless include_quarantined=true.
-    #[serde(default = "default_status")]
-    pub status: String,
+    #[serde(default)]
+    pub status: RecordStatus,
     /// IDs of records that support or evidence this record.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub evidence: Vec<Uuid>,
@@ -73,10 +71,6 @@ pub struct MemoryRecord {
     pub react_iteration: Option<u32>,
 }
 
-fn default_status() -> String {
-    "active".to_string()
-}
-
 fn default_relevance() -> f64 {
     1.0
 }
@@ -85,8 +79,123 @@ fn default_confidence() -> f32 {
     1.0
 }
 
-fn default_priority() -> String {
-    "normal".to_string()
+/// Retention priority of a record. Serialized lowercase; see `parse_lenient`
+/// for the spellings older files used.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Pinned,
+}
+
+impl Priority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Pinned => "pinned",
+        }
+    }
+
+    /// Map any spelling seen in the wild to a priority; unknown values and the
+    /// empty string fall back to `Normal` (what the old string default meant).
+    pub fn parse_lenient(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pinned" | "pin" | "sticky" => Priority::Pinned,
+            "high" | "urgent" | "critical" | "important" | "p0" | "p1" => Priority::High,
+            "low" | "minor" | "p3" | "p4" => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
+
+    /// Whether `s` is already the canonical spelling.
+    pub fn is_canonical(s: &str) -> bool {
+        s == Self::parse_lenient(s).as_str()
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s: Option<String> = Option::deserialize(d)?;
+        Ok(s.map_or_else(Priority::default, |s| Priority::parse_lenient(&s)))
+    }
+}
+
+/// Lifecycle status of a record. Serialized lowercase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordStatus {
+    #[default]
+    Active,
+    Quarantine,
+    Archived,
+}
+
+impl RecordStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecordStatus::Active => "active",
+            RecordStatus::Quarantine => "quarantine",
+            RecordStatus::Archived => "archived",
+        }
+    }
+
+    /// Lenient counterpart of `Priority::parse_lenient`; unknown values are
+    /// `Active`. Only for loading stored records; API input goes through
+    /// `FromStr`, which rejects unknown values.
+    pub fn parse_lenient(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "quarantine" | "quarantined" | "flagged" => RecordStatus::Quarantine,
+            "archived" | "archive" | "inactive" | "deleted" => RecordStatus::Archived,
+            _ => RecordStatus::Active,
+        }
+    }
+
+    pub fn is_canonical(s: &str) -> bool {
+        s == Self::parse_lenient(s).as_str()
+    }
+}
+
+impl std::fmt::Display for RecordStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Strict parse: the spellings `parse_lenient` recognises, but an unknown
+/// value is an error rather than `Active`.
+impl std::str::FromStr for RecordStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(RecordStatus::Active),
+            "quarantine" | "quarantined" | "flagged" => Ok(RecordStatus::Quarantine),
+            "archived" | "archive" | "inactive" | "deleted" => Ok(RecordStatus::Archived),
+            _ => Err(format!(
+                "unknown record status '{s}' (expected active, quarantine or archived)"
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RecordStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s: Option<String> = Option::deserialize(d)?;
+        Ok(s.map_or_else(RecordStatus::default, |s| RecordStatus::parse_lenient(&s)))
+    }
 }
 
 impl MemoryRecord {
@@ -116,8 +225,8 @@ impl MemoryRecord {
             source: None,
             version: 0,
             tags: Vec::new(),
-            priority: "normal".to_string(),
-            status: "active".to_string(),
+            priority: Priority::Normal,
+            status: RecordStatus::Active,
             evidence: Vec::new(),
             derived_from: None,
             react_iteration: None,
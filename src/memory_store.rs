@@ -1,27 +1,33 @@
-use indexmap::IndexMap;
+use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::io::{BufRead, Write};
 use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::audit_log::AuditLog;
+use crate::cold_tier::ColdTier;
 use crate::embedding_provider::EmbeddingProvider;
-use crate::memory_record::MemoryRecord;
-use crate::persistence::{FileBackend, InMemoryBackend, MemoryBackend};
+use crate::memory_record::{MemoryRecord, Priority, RecordStatus};
+use crate::page_tier::PageTier;
+use crate::persistence::{FileBackend, InMemoryBackend, MemoryBackend, RecordFilter};
+use crate::record_set::RecordSet;
 #[cfg(feature = "rocksdb-backend")]
 use crate::rocksdb_backend::RocksDbBackend;
+#[cfg(feature = "sqlite_backend")]
+use crate::sqlite_backend::SqliteBackend;
 use crate::source_trust::SourceTrustRegistry;
+use crate::store_format::FormatHeader;
+use crate::tx_log::RecordChange;
 use anyhow::Result;
+use elsa::sync::FrozenMap;
 
 pub struct MemoryStore<B: MemoryBackend> {
     backend: B,
-    records: Vec<MemoryRecord>,
+    /// Resident records with their actor/action/target indices.
+    records: RecordSet,
     audit: AuditLog,
     buffer: VecDeque<MemoryRecord>,
     batch_size: usize,
-    index_actor: IndexMap<String, Vec<usize>>,
-
-    index_action: IndexMap<String, Vec<usize>>,
-    index_target: IndexMap<String, Vec<usize>>,
     /// Source trust registry for credibility-weighted memory operations.
     pub source_trust: SourceTrustRegistry,
     /// Optional embedding provider for zero-config auto-embedding on ingest.
@@ -29,6 +35,47 @@ pub struct MemoryStore<B: MemoryBackend> {
     /// Active namespace for multi-tenant isolation. When set, all operations
     /// are scoped to this namespace. Records are tagged with `ns:<namespace>`.
     pub namespace: Option<String>,
+    /// Optional zstd cold tier holding demoted Raw records, shared with read
+    /// views. Read in place by lookups and search; faulted back hot on writes.
+    cold: Option<Arc<RwLock<ColdTier>>>,
+    /// Paged or cold records read since the last write, pinned so lookups
+    /// can lend references. Cleared whenever either tier changes.
+    disk_reads: FrozenMap<uuid::Uuid, Box<MemoryRecord>>,
+    /// Page tier and hot-record cap when opened `with_paging`.
+    paging: Option<Paging>,
+    /// Pending record changes for the tx log; `None` until `enable_change_journal`.
+    journal: Option<Vec<RecordChange>>,
+    /// Run every added record through the global safety guardrail first
+    /// (see `set_write_screening`).
+    screen_writes: bool,
+}
+
+/// `HIPCORTEX_HOT_RECORDS`: the hot-record cap the servers pass to
+/// `MemoryStore::with_paging`, or `None` (paging off) when unset or invalid.
+pub fn hot_records_from_env() -> Option<usize> {
+    std::env::var("HIPCORTEX_HOT_RECORDS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+}
+
+/// Page tier shared with read views, plus the resident-record cap.
+#[derive(Clone)]
+struct Paging {
+    tier: Arc<RwLock<PageTier>>,
+    hot_records: usize,
+}
+
+/// A `search_tiered` candidate: a resident or paged record, or the search
+/// stub of a cold one (see `ColdTier::search_stubs`).
+struct Candidate<'a> {
+    record: Cow<'a, MemoryRecord>,
+    cold_stub: bool,
+}
+
+impl std::borrow::Borrow<MemoryRecord> for Candidate<'_> {
+    fn borrow(&self) -> &MemoryRecord {
+        &self.record
+    }
 }
 
 impl MemoryStore<FileBackend> {
@@ -45,17 +92,18 @@ impl MemoryStore<FileBackend> {
         let audit_path = path.as_ref().with_extension("audit.log");
         let mut store = Self {
             backend,
-            records: Vec::new(),
+            records: RecordSet::new(),
             audit: AuditLog::new(&audit_path)?,
             buffer: VecDeque::new(),
             batch_size: batch,
-            index_actor: IndexMap::new(),
-
-            index_action: IndexMap::new(),
-            index_target: IndexMap::new(),
             source_trust: SourceTrustRegistry::new(),
             embedding_provider: None,
             namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
         };
         store.load()?;
         Ok(store)
@@ -66,17 +114,18 @@ impl MemoryStore<FileBackend> {
         let audit_path = path.as_ref().with_extension("audit.log");
         let mut store = Self {
             backend,
-            records: Vec::new(),
+            records: RecordSet::new(),
             audit: AuditLog::new(&audit_path)?,
             buffer: VecDeque::new(),
             batch_size: 8,
-            index_actor: IndexMap::new(),
-
-            index_action: IndexMap::new(),
-            index_target: IndexMap::new(),
             source_trust: SourceTrustRegistry::new(),
             embedding_provider: None,
             namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
         };
         store.load()?;
         Ok(store)
@@ -91,23 +140,34 @@ impl MemoryStore<FileBackend> {
         let audit_path = path.as_ref().with_extension("audit.log");
         let mut store = Self {
             backend,
-            records: Vec::new(),
+            records: RecordSet::new(),
             audit: AuditLog::new(&audit_path)?,
             buffer: VecDeque::new(),
             batch_size: 8,
-            index_actor: IndexMap::new(),
-
-            index_action: IndexMap::new(),
-            index_target: IndexMap::new(),
             source_trust: SourceTrustRegistry::new(),
             embedding_provider: None,
             namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
         };
         store.load()?;
         Ok(store)
     }
 }
 
+/// Partial update for `MemoryStore::update_with`; unset fields are kept.
+#[derive(Debug, Clone, Default)]
+pub struct RecordPatch {
+    pub target: Option<String>,
+    pub action: Option<String>,
+    pub confidence: Option<f32>,
+    pub source: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
 /// Per-record error detail returned by bulk add operations.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BulkAddError {
@@ -122,18 +182,28 @@ impl MemoryStore<InMemoryBackend> {
         let backend = InMemoryBackend::new();
         Self {
             backend,
-            records: Vec::new(),
+            records: RecordSet::new(),
             audit: AuditLog::new_sink(),
             buffer: VecDeque::new(),
             batch_size: 1,
-            index_actor: IndexMap::new(),
-            index_action: IndexMap::new(),
-            index_target: IndexMap::new(),
             source_trust: SourceTrustRegistry::new(),
             embedding_provider: None,
             namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
         }
     }
+
+    /// An in-memory store holding `records` as they are: no hashing, screening,
+    /// audit or metrics. For rebuilt states (`pitr`), not for new writes.
+    pub(crate) fn from_records(records: impl IntoIterator<Item = MemoryRecord>) -> Self {
+        let mut store = Self::new_in_memory();
+        store.records = records.into_iter().collect();
+        store
+    }
 }
 
 #[cfg(feature = "rocksdb-backend")]
@@ -143,16 +213,43 @@ impl MemoryStore<RocksDbBackend> {
         let audit_path = path.as_ref().with_extension("audit.log");
         let mut store = Self {
             backend,
-            records: Vec::new(),
+            records: RecordSet::new(),
             audit: AuditLog::new(&audit_path)?,
             buffer: VecDeque::new(),
             batch_size: batch,
-            index_actor: IndexMap::new(),
-            index_action: IndexMap::new(),
-            index_target: IndexMap::new(),
             source_trust: SourceTrustRegistry::new(),
             embedding_provider: None,
             namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
+        };
+        store.load()?;
+        Ok(store)
+    }
+}
+
+#[cfg(feature = "sqlite_backend")]
+impl MemoryStore<SqliteBackend> {
+    pub fn new_sqlite<P: AsRef<Path>>(path: P, batch: usize) -> Result<Self> {
+        let backend = SqliteBackend::new(&path)?;
+        let audit_path = path.as_ref().with_extension("audit.log");
+        let mut store = Self {
+            backend,
+            records: RecordSet::new(),
+            audit: AuditLog::new(&audit_path)?,
+            buffer: VecDeque::new(),
+            batch_size: batch,
+            source_trust: SourceTrustRegistry::new(),
+            embedding_provider: None,
+            namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
         };
         store.load()?;
         Ok(store)
@@ -160,85 +257,195 @@ impl MemoryStore<RocksDbBackend> {
 }
 
 impl<B: MemoryBackend> MemoryStore<B> {
-    fn load(&mut self) -> Result<()> {
-        self.records = self.backend.load()?;
-        self.index_actor.clear();
-
-        self.index_action.clear();
-        self.index_target.clear();
-
-        for (i, rec) in self.records.iter().enumerate() {
-            self.index_actor
-                .entry(rec.actor.clone())
-                .or_default()
-                .push(i);
-
-            self.index_action
-                .entry(rec.action.clone())
-                .or_default()
-                .push(i);
-            self.index_target
-                .entry(rec.target.clone())
-                .or_default()
-                .push(i);
-        }
-        Ok(())
+    /// Open a store over an already constructed backend and load what it holds.
+    pub fn with_backend(backend: B, audit: AuditLog, batch_size: usize) -> Result<Self> {
+        let mut store = Self {
+            backend,
+            records: RecordSet::new(),
+            audit,
+            buffer: VecDeque::new(),
+            batch_size: batch_size.max(1),
+            source_trust: SourceTrustRegistry::new(),
+            embedding_provider: None,
+            namespace: None,
+            cold: None,
+            disk_reads: FrozenMap::new(),
+            paging: None,
+            journal: None,
+            screen_writes: false,
+        };
+        store.load()?;
+        Ok(store)
     }
 
-    /// Rebuild all positional indices from scratch after structural Vec changes.
-    /// Called after any retain/remove operation to keep index_actor / index_action /
-    /// index_target consistent with the current Vec positions.
-    fn rebuild_indices(&mut self) {
-        self.index_actor.clear();
-        self.index_action.clear();
-        self.index_target.clear();
-        for (i, rec) in self.records.iter().enumerate() {
-            self.index_actor
-                .entry(rec.actor.clone())
-                .or_default()
-                .push(i);
-            self.index_action
-                .entry(rec.action.clone())
-                .or_default()
-                .push(i);
-            self.index_target
-                .entry(rec.target.clone())
-                .or_default()
-                .push(i);
-        }
+    fn load(&mut self) -> Result<()> {
+        self.records = self.backend.load()?.into_iter().collect();
+        Ok(())
     }
 
     /// Remove all records whose `expires_at` is in the past.
-    /// Rebuilds indices if any records were removed.
     /// Returns the number of records removed.
     pub fn purge_expired(&mut self) -> usize {
         let now = chrono::Utc::now().timestamp();
-        let before = self.records.len();
-        self.records
-            .retain(|r| r.expires_at.map_or(true, |exp| exp > now));
-        let removed = before - self.records.len();
+        let live = |r: &MemoryRecord| r.expires_at.is_none_or(|exp| exp > now);
+        let mut expired = self.records.retain(live);
+        if let Some(mut pages) = self.page_tier_mut() {
+            expired.extend(pages.retain(live).unwrap_or_default());
+        }
+        let removed = expired.len();
         if removed > 0 {
-            self.rebuild_indices();
+            self.buffer.retain(|r| !expired.contains(&r.id));
+            let _ = self.backend.delete(&expired);
+            for id in expired {
+                self.journal_push(RecordChange::Delete { id });
+            }
         }
         removed
     }
 
+    /// Recompute `relevance_score` for every resident non-pinned record from its
+    /// confidence decay (see `compute_decay`). Scores are refreshed in memory
+    /// only; they reach the backend with each record's next write or the next
+    /// `compact_backend`. Returns the number of records whose score dropped.
+    pub fn apply_decay(&mut self) -> usize {
+        let changed: Vec<(uuid::Uuid, f64, f64)> = self
+            .records
+            .iter()
+            .filter(|r| r.priority != Priority::Pinned)
+            .map(|r| (r.id, r.relevance_score, compute_decay(r).clamp(0.0, 1.0)))
+            .filter(|(_, before, after)| after != before)
+            .collect();
+        let mut dropped = 0;
+        for (id, before, after) in changed {
+            if after < before {
+                dropped += 1;
+            }
+            self.records.update(id, |r| r.relevance_score = after);
+        }
+        dropped
+    }
+
+    /// Rewrite the backend from the in-memory record set, making deletes
+    /// (`delete_by_id`, `purge_expired`) durable.
+    pub fn compact_backend(&mut self) -> Result<()> {
+        self.buffer.clear();
+        self.backend.clear()?;
+        let snap = self.records.clone();
+        for rec in &snap {
+            self.backend.append(rec)?;
+        }
+        self.backend.flush()?;
+        Ok(())
+    }
+
+    /// Write the changed record `id` through to the backend: in place when
+    /// the backend supports it, otherwise by rewriting everything.
+    fn persist_update(&mut self, id: uuid::Uuid) -> Result<()> {
+        self.flush()?;
+        let Some(rec) = self.records.by_id(id) else {
+            return Ok(());
+        };
+        if !self.backend.update(rec)? {
+            self.compact_backend()?;
+        }
+        Ok(())
+    }
+
+    /// Counterpart of `persist_update` for records already removed from memory.
+    fn persist_delete(&mut self, ids: &[uuid::Uuid]) -> Result<()> {
+        self.flush()?;
+        if !self.backend.delete(ids)? {
+            self.compact_backend()?;
+        }
+        Ok(())
+    }
+
     /// Remove the single record with the given `id`.
-    /// Rebuilds indices if a record was deleted.
     /// Returns `true` if a record was found and removed, `false` if not found.
+    /// Backends that delete in place drop it right away; others keep it
+    /// until `compact_backend`.
     pub fn delete_by_id(&mut self, id: uuid::Uuid) -> bool {
-        let before = self.records.len();
-        self.records.retain(|r| r.id != id);
-        let removed = before - self.records.len();
-        if removed > 0 {
-            self.rebuild_indices();
+        if self.records.remove(id).is_some() {
+            self.buffer.retain(|r| r.id != id);
+            self.journal_push(RecordChange::Delete { id });
+            let _ = self.backend.delete(&[id]);
+            true
+        } else if self.delete_paged(&[id]) > 0 {
+            self.journal_push(RecordChange::Delete { id });
             true
         } else {
             false
         }
     }
 
-    pub fn add(&mut self, mut record: MemoryRecord) -> Result<()> {
+    /// `delete_by_id` that is durable on return: backends that cannot delete
+    /// in place are compacted instead of keeping the record until later.
+    pub fn delete_durable(&mut self, id: uuid::Uuid) -> Result<bool> {
+        if self.records.remove(id).is_none() {
+            let paged = self.delete_paged(&[id]) > 0;
+            if paged {
+                self.journal_push(RecordChange::Delete { id });
+            }
+            return Ok(paged);
+        }
+        self.buffer.retain(|r| r.id != id);
+        self.journal_push(RecordChange::Delete { id });
+        self.persist_delete(&[id])?;
+        Ok(true)
+    }
+
+    /// Drop `ids` from the page tier (durable on return); returns how many were paged.
+    fn delete_paged(&mut self, ids: &[uuid::Uuid]) -> usize {
+        match self.page_tier_mut() {
+            Some(mut pages) => pages.remove(ids).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Screen every record added from now on under its namespace safety
+    /// policy: PII is tokenized into the vault, records are quarantined, or
+    /// the add fails with `WriteRefused`. Servers and the CLI turn this on so
+    /// every write path shares one hook; library stores default to off.
+    pub fn set_write_screening(&mut self, on: bool) {
+        self.screen_writes = on;
+    }
+
+    pub fn screens_writes(&self) -> bool {
+        self.screen_writes
+    }
+
+    /// Apply the write screen to `record` without adding it. `None` when
+    /// screening is off. Needs no write access, so shared stores run it on a
+    /// snapshot before taking the writer lock.
+    pub fn screen(
+        &self,
+        record: &mut MemoryRecord,
+    ) -> Result<Option<crate::safety_guardrail::ScreenOutcome>> {
+        if !self.screen_writes {
+            return Ok(None);
+        }
+        crate::safety_guardrail::screen_record(record)
+            .map(Some)
+            .map_err(|reason| crate::safety_guardrail::WriteRefused(reason).into())
+    }
+
+    pub fn add(&mut self, record: MemoryRecord) -> Result<()> {
+        self.add_screened(record).map(|_| ())
+    }
+
+    /// `add`, returning what the write screen did to the record.
+    pub fn add_screened(
+        &mut self,
+        mut record: MemoryRecord,
+    ) -> Result<Option<crate::safety_guardrail::ScreenOutcome>> {
+        let outcome = self.screen(&mut record)?;
+        self.add_prescreened(record)?;
+        Ok(outcome)
+    }
+
+    /// Add a record the caller already passed through `screen`.
+    pub(crate) fn add_prescreened(&mut self, mut record: MemoryRecord) -> Result<()> {
+        let started = std::time::Instant::now();
         // Auto-tag with namespace for multi-tenant isolation
         if let Some(ref ns) = self.namespace {
             let ns_tag = format!("ns:{}", ns);
@@ -247,27 +454,19 @@ impl<B: MemoryBackend> MemoryStore<B> {
             }
         }
         self.records.push(record.clone());
-        self.buffer.push_back(record.clone());
-        let idx = self.records.len() - 1;
-        self.index_actor
-            .entry(record.actor.clone())
-            .or_default()
-            .push(idx);
-
-        self.index_action
-            .entry(record.action.clone())
-            .or_default()
-            .push(idx);
-        self.index_target
-            .entry(record.target.clone())
-            .or_default()
-            .push(idx);
-
+        self.journal_upsert(record.id);
         self.audit
             .append(&record.actor, &record.action, &record.target)?;
+        self.buffer.push_back(record);
         if self.buffer.len() >= self.batch_size {
             self.flush()?;
         }
+        self.page_out_excess()?;
+        crate::telemetry::METRICS.observe_duration(
+            crate::telemetry::MEMORY_ADD_SECONDS,
+            &[],
+            started.elapsed(),
+        );
         Ok(())
     }
 
@@ -279,29 +478,106 @@ impl<B: MemoryBackend> MemoryStore<B> {
         Ok(())
     }
 
-    pub fn all(&self) -> &[MemoryRecord] {
+    /// The resident records. With paging on, records paged out to disk are
+    /// not included; the lookup, search and `find_*` methods cover them.
+    pub fn all(&self) -> &RecordSet {
         &self.records
     }
 
+    /// Detached, read-only copy of the resident records, indices, namespace
+    /// and trust registry, plus handles on the cold and page tiers for
+    /// lookups. Must not be written: writes would reach the shared tiers. The
+    /// records are shared with this store, so this is O(1).
+    pub(crate) fn read_view(&self) -> MemoryStore<InMemoryBackend> {
+        let mut view = MemoryStore::new_in_memory();
+        view.records = self.records.clone();
+        view.cold = self.cold.clone();
+        view.paging = self.paging.clone();
+        view.source_trust = self.source_trust.clone();
+        view.embedding_provider = self.embedding_provider.clone();
+        view.namespace = self.namespace.clone();
+        view.screen_writes = self.screen_writes;
+        view
+    }
+
+    /// Apply queued reads (id → number of accesses) to resident records.
+    /// Access stats are bookkeeping: they reach the backend with the
+    /// record's next write.
+    pub(crate) fn touch(&mut self, accessed: &std::collections::HashMap<uuid::Uuid, u32>) {
+        for (&id, &n) in accessed {
+            self.records.update(id, |r| {
+                r.mark_accessed();
+                r.access_count = r.access_count.saturating_add(n.saturating_sub(1));
+            });
+        }
+    }
+
+    /// Every record, paged ones first (streamed off disk, they are the
+    /// oldest) then resident ones.
+    fn every(&self) -> impl Iterator<Item = Cow<'_, MemoryRecord>> {
+        let paged = self.page_tier().map(|pages| pages.iter());
+        paged
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|r| !self.records.contains(r.id))
+            .map(Cow::Owned)
+            .chain(self.records.iter().map(Cow::Borrowed))
+    }
+
+    /// Lend a reference to a record from `every`; one read from disk is
+    /// pinned until this store is next written.
+    fn pin<'a>(&'a self, rec: Cow<'a, MemoryRecord>) -> &'a MemoryRecord {
+        match rec {
+            Cow::Borrowed(rec) => rec,
+            Cow::Owned(rec) => self.disk_reads.insert(rec.id, Box::new(rec)),
+        }
+    }
+
+    /// Records matching `keep`, in `every` order.
+    fn select(&self, keep: impl Fn(&MemoryRecord) -> bool) -> Vec<&MemoryRecord> {
+        self.every()
+            .filter(|r| keep(r))
+            .map(|r| self.pin(r))
+            .collect()
+    }
+
+    /// Index hits: paged ones (ids from `paged`) first, then `resident`.
+    fn indexed<'a>(
+        &'a self,
+        paged: impl FnOnce(&PageTier) -> Vec<uuid::Uuid>,
+        mut resident: Vec<&'a MemoryRecord>,
+    ) -> Vec<&'a MemoryRecord> {
+        let Some(ids) = self.page_tier().map(|pages| paged(&pages)) else {
+            return resident;
+        };
+        let mut hits: Vec<&MemoryRecord> = ids
+            .into_iter()
+            .filter(|id| !self.records.contains(*id))
+            .filter_map(|id| self.find_by_id(id))
+            .collect();
+        hits.append(&mut resident);
+        hits
+    }
+
     pub fn record_count(&self) -> usize {
-        self.records.iter().filter(|r| r.status == "active").count()
+        self.every()
+            .filter(|r| r.status == RecordStatus::Active)
+            .count()
     }
 
     pub fn all_by_type(&self, rt: crate::memory_record::MemoryType) -> Vec<&MemoryRecord> {
-        self.records
-            .iter()
-            .filter(|r| r.record_type == rt && r.status == "active")
-            .collect()
+        self.select(|r| r.record_type == rt && r.status == RecordStatus::Active)
     }
 
     pub fn evidence_edge_count(&self) -> usize {
-        self.records.iter().map(|r| r.evidence.len()).sum()
+        self.every().map(|r| r.evidence.len()).sum()
     }
 
     pub fn merkle_root_hex(&self) -> String {
         use sha2::{Digest, Sha256};
         let mut hasher = Sha256::new();
-        for r in &self.records {
+        for r in self.every() {
             let h = r.integrity.as_deref().unwrap_or("");
             hasher.update(h.as_bytes());
         }
@@ -309,27 +585,15 @@ impl<B: MemoryBackend> MemoryStore<B> {
     }
 
     pub fn find_by_actor(&self, actor: &str) -> Vec<&MemoryRecord> {
-        if let Some(ids) = self.index_actor.get(actor) {
-            ids.iter().filter_map(|&i| self.records.get(i)).collect()
-        } else {
-            Vec::new()
-        }
+        self.indexed(|p| p.by_actor(actor), self.records.by_actor(actor))
     }
 
     pub fn find_by_action(&self, action: &str) -> Vec<&MemoryRecord> {
-        if let Some(ids) = self.index_action.get(action) {
-            ids.iter().filter_map(|&i| self.records.get(i)).collect()
-        } else {
-            Vec::new()
-        }
+        self.indexed(|p| p.by_action(action), self.records.by_action(action))
     }
 
     pub fn find_by_target(&self, target: &str) -> Vec<&MemoryRecord> {
-        if let Some(ids) = self.index_target.get(target) {
-            ids.iter().filter_map(|&i| self.records.get(i)).collect()
-        } else {
-            Vec::new()
-        }
+        self.indexed(|p| p.by_target(target), self.records.by_target(target))
     }
 
     /// Find records that contain ANY of the given tags.
@@ -337,10 +601,7 @@ impl<B: MemoryBackend> MemoryStore<B> {
         if tags.is_empty() {
             return Vec::new();
         }
-        self.records
-            .iter()
-            .filter(|r| tags.iter().any(|t| r.tags.contains(&t.to_string())))
-            .collect()
+        self.select(|r| tags.iter().any(|t| r.tags.contains(&t.to_string())))
     }
 
     /// Find records matching any actor in `actors`. Returns empty vec for empty slice.
@@ -348,53 +609,50 @@ impl<B: MemoryBackend> MemoryStore<B> {
         if actors.is_empty() {
             return Vec::new();
         }
-        self.records
-            .iter()
-            .filter(|r| actors.contains(&r.actor.as_str()))
-            .collect()
+        self.select(|r| actors.contains(&r.actor.as_str()))
     }
 
-    /// Set `status` on a record by UUID. Returns error if not found.
-    pub fn set_status(&mut self, id: uuid::Uuid, status: &str) -> Result<()> {
-        let idx = self
-            .records
-            .iter()
-            .position(|r| r.id == id)
-            .ok_or_else(|| anyhow::anyhow!("record not found: {}", id))?;
-        self.records[idx].status = status.to_string();
-        self.records[idx].integrity = Some(self.records[idx].compute_hash());
-        // Rewrite backend
-        self.backend.clear()?;
-        let snap = self.records.clone();
-        for rec in &snap {
-            self.backend.append(rec)?;
+    /// Records matching `filter`, oldest first. Answered by the backend's
+    /// indexes when it has them (pending writes are flushed first), else by a
+    /// scan of the loaded records. Namespaced stores and stores with paged
+    /// records always scan.
+    pub fn query(&mut self, filter: &RecordFilter) -> Result<Vec<MemoryRecord>> {
+        if self.namespace.is_none() && self.page_tier().is_none_or(|p| p.is_empty()) {
+            self.flush()?;
+            if let Some(hits) = self.backend.query(filter)? {
+                return Ok(hits);
+            }
         }
-        self.backend.flush()?;
-        Ok(())
+        let ns_tag = self.namespace.as_ref().map(|ns| format!("ns:{}", ns));
+        Ok(self
+            .every()
+            .filter(|r| filter.matches(r))
+            .filter(|r| ns_tag.as_ref().is_none_or(|t| r.tags.contains(t)))
+            .take(filter.limit.unwrap_or(usize::MAX))
+            .map(Cow::into_owned)
+            .collect())
+    }
+
+    /// Set `status` on a record by UUID. Returns error if not found.
+    pub fn set_status(&mut self, id: uuid::Uuid, status: RecordStatus) -> Result<()> {
+        self.modify_tiered(id, |r| r.status = status)?;
+        self.persist_update(id)
     }
 
     /// Boost confidence by 0.10 (clamped to 1.0). Returns (before, after).
     /// Also records corroboration in the source trust registry for this record's source.
     pub fn corroborate(&mut self, id: uuid::Uuid) -> Result<(f32, f32)> {
-        let idx = self
-            .records
-            .iter()
-            .position(|r| r.id == id)
-            .ok_or_else(|| anyhow::anyhow!("record not found: {}", id))?;
-        let before = self.records[idx].confidence;
-        let after = (before + 0.10).min(1.0);
-        self.records[idx].confidence = after;
-        self.records[idx].integrity = Some(self.records[idx].compute_hash());
+        let mut before = 0.0;
+        let rec = self.modify_tiered(id, |r| {
+            before = r.confidence;
+            r.confidence = (before + 0.10).min(1.0);
+        })?;
+        let after = rec.confidence;
         // Track source trust
-        if let Some(ref source) = self.records[idx].source {
-            self.source_trust.record_corroboration(source);
-        }
-        self.backend.clear()?;
-        let snap = self.records.clone();
-        for rec in &snap {
-            self.backend.append(rec)?;
+        if let Some(source) = rec.source {
+            self.source_trust.record_corroboration(&source);
         }
-        self.backend.flush()?;
+        self.persist_update(id)?;
         Ok((before, after))
     }
 
@@ -402,80 +660,24 @@ impl<B: MemoryBackend> MemoryStore<B> {
     /// Also records contradiction in the source trust registry for this record's source.
     /// Returns (before, after, was_quarantined).
     pub fn contradict(&mut self, id: uuid::Uuid) -> Result<(f32, f32, bool)> {
-        let idx = self
-            .records
-            .iter()
-            .position(|r| r.id == id)
-            .ok_or_else(|| anyhow::anyhow!("record not found: {}", id))?;
-        let before = self.records[idx].confidence;
-        let after = (before - 0.15).max(0.0);
-        self.records[idx].confidence = after;
+        let mut before = 0.0;
+        let rec = self.modify_tiered(id, |r| {
+            before = r.confidence;
+            r.confidence = (before - 0.15).max(0.0);
+            if r.confidence < 0.30 {
+                r.status = RecordStatus::Quarantine;
+            }
+        })?;
+        let after = rec.confidence;
         let quarantined = after < 0.30;
-        if quarantined {
-            self.records[idx].status = "quarantine".to_string();
-        }
-        self.records[idx].integrity = Some(self.records[idx].compute_hash());
         // Track source trust
-        if let Some(ref source) = self.records[idx].source {
-            self.source_trust.record_contradiction(source);
-        }
-        self.backend.clear()?;
-        let snap = self.records.clone();
-        for rec in &snap {
-            self.backend.append(rec)?;
+        if let Some(source) = rec.source {
+            self.source_trust.record_contradiction(&source);
         }
-        self.backend.flush()?;
+        self.persist_update(id)?;
         Ok((before, after, quarantined))
     }
 
-    /// Compute time-based confidence decay for a memory record.
-    ///
-    /// Formula: `confidence × exp(−λ × elapsed_seconds / t½)`
-    ///
-    /// Parameters read from `rec.metadata`:
-    ///   - `"decay_factor"` (λ): rate multiplier, default 1.0; 0.0 = no decay
-    ///   - `"decay_half_life_secs"` (t½): seconds for confidence to halve, default 2,592,000 (30 days)
-    ///
-    /// Returns `rec.confidence as f64` unchanged when λ=0 or elapsed < 1s.
-    ///
-    /// # Invariant
-    ///
-    /// The return value is always in `[0.0, rec.confidence]`. This function is
-    /// **purely suppressive** — it can reduce or preserve a record's contribution
-    /// to the final weighted score, but never amplify it beyond what confidence allows.
-    ///
-    /// `rec.confidence` at ingestion time acts as a **permanent score ceiling**:
-    /// a record with `confidence=0.5` can contribute at most `0.5 × base_score × trust`
-    /// to the final weighted score, regardless of query relevance or time elapsed.
-    ///
-    /// This is intentional: low-confidence memories should not dominate retrieval
-    /// even when they are semantically relevant. To allow a memory to rank higher,
-    /// ingest it with a higher `confidence` value.
-    fn compute_decay(rec: &MemoryRecord) -> f64 {
-        let elapsed_secs =
-            (chrono::Utc::now().timestamp() - rec.timestamp.timestamp()).max(0) as f64;
-        let conf = rec.confidence as f64;
-        if elapsed_secs < 1.0 {
-            return conf;
-        }
-        let lambda = rec
-            .metadata
-            .get("decay_factor")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(1.0)
-            .clamp(0.0, 10.0);
-        if lambda < f64::EPSILON {
-            return conf; // λ=0 → no decay
-        }
-        let half_life = rec
-            .metadata
-            .get("decay_half_life_secs")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(2_592_000.0) // 30 days
-            .max(1.0);
-        conf * (-lambda * elapsed_secs / half_life).exp()
-    }
-
     /// Semantic search: rank records by cosine similarity against `query_embedding`
     /// if they carry a `metadata.embedding` float array, otherwise fall back to
     /// keyword matching against actor + action + target.
@@ -489,71 +691,65 @@ impl<B: MemoryBackend> MemoryStore<B> {
         limit: usize,
         include_quarantined: bool,
     ) -> Vec<(&MemoryRecord, f64)> {
-        let now_ts = chrono::Utc::now().timestamp();
-        let mut scored: Vec<(&MemoryRecord, f64)> = self
-            .records
-            .iter()
-            .filter(|r| {
-                r.priority != "pinned"
-                    && (include_quarantined || r.status != "quarantine")
-                    && r.status != "archived"
-                    && r.expires_at.map_or(true, |exp| exp > now_ts)
-            })
-            .map(|rec| {
-                let base_score = if let Some(qe) = query_embedding {
-                    let doc_vec: Option<Vec<f64>> = rec
-                        .metadata
-                        .get("embedding")
-                        .and_then(|v| serde_json::from_value(v.clone()).ok());
-                    if let Some(dv) = doc_vec {
-                        cosine_similarity(qe, &dv)
-                    } else {
-                        keyword_score(query_text, rec)
-                    }
-                } else {
-                    keyword_score(query_text, rec)
-                };
-                // Trust-weighted score: multiply by source credibility
-                let trust = rec
-                    .source
-                    .as_deref()
-                    .map(|s| self.source_trust.get_trust(s) as f64)
-                    .unwrap_or(0.5);
-                // Priority multiplier: high=1.5×, low=0.5×, normal/pinned=1.0×
-                // Note: pinned records take a separate code path (score 2.0 override).
-                let priority_mult: f64 = match rec.priority.as_str() {
-                    "high" => 1.5,
-                    "low" => 0.5,
-                    _ => 1.0,
-                };
-                let weighted =
-                    base_score * (0.5 + 0.5 * trust) * priority_mult * Self::compute_decay(rec);
-                (rec, weighted)
-            })
-            .filter(|(_, s)| *s > 0.0)
-            .collect();
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        self.rank(
+            self.every(),
+            query_embedding,
+            query_text,
+            limit,
+            include_quarantined,
+        )
+        .into_iter()
+        .map(|(r, score)| (self.pin(r), score))
+        .collect()
+    }
 
-        // Pinned records always surface at score 2.0 — they are excluded from the main
-        // scored pipeline above so decay and priority_mult do not apply.
-        // Non-expired, non-quarantined pinned records always appear first.
-        let mut pinned: Vec<(&MemoryRecord, f64)> = self
-            .records
-            .iter()
-            .filter(|r| {
-                r.priority == "pinned"
-                    && (include_quarantined || r.status != "quarantine")
-                    && r.status != "archived"
-                    && r.expires_at.map_or(true, |exp| exp > now_ts)
-            })
-            .map(|r| (r, 2.0f64))
-            .collect();
-        pinned.sort_by(|a, b| b.0.timestamp.cmp(&a.0.timestamp));
-        let scored_ids: std::collections::HashSet<uuid::Uuid> =
-            scored.iter().map(|(r, _)| r.id).collect();
-        pinned.retain(|(r, _)| !scored_ids.contains(&r.id));
+    /// Scoring behind `search_semantic` and `search_tiered`, in one pass so
+    /// records can be streamed off disk; the scored list is cut back to
+    /// `limit` as it grows.
+    fn rank<R: std::borrow::Borrow<MemoryRecord>>(
+        &self,
+        records: impl Iterator<Item = R>,
+        query_embedding: Option<&[f64]>,
+        query_text: &str,
+        limit: usize,
+        include_quarantined: bool,
+    ) -> Vec<(R, f64)> {
+        let started = std::time::Instant::now();
+        let now_ts = chrono::Utc::now().timestamp();
+        let by_score =
+            |a: &(R, f64), b: &(R, f64)| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal);
+        let keep = limit.saturating_mul(2).max(1024);
+        // Pinned records always surface at score 2.0 — they bypass scoring so
+        // decay and priority_mult do not apply, and come first, newest first.
+        let mut pinned: Vec<(R, f64)> = Vec::new();
+        let mut scored: Vec<(R, f64)> = Vec::new();
+        for rec in records {
+            let r = rec.borrow();
+            if !searchable(r, now_ts, include_quarantined) {
+                continue;
+            }
+            if r.priority == Priority::Pinned {
+                pinned.push((rec, 2.0));
+                continue;
+            }
+            let weighted = weighted_score(r, query_embedding, query_text, &self.source_trust);
+            if weighted > 0.0 {
+                scored.push((rec, weighted));
+                if scored.len() >= keep {
+                    scored.sort_by(by_score);
+                    scored.truncate(limit);
+                }
+            }
+        }
+        scored.sort_by(by_score);
+        pinned.sort_by_key(|(r, _)| std::cmp::Reverse(r.borrow().timestamp));
         pinned.extend(scored);
         pinned.truncate(limit);
+        crate::telemetry::METRICS.observe_duration(
+            crate::telemetry::MEMORY_SEARCH_SECONDS,
+            &[],
+            started.elapsed(),
+        );
         pinned
     }
 
@@ -563,12 +759,9 @@ impl<B: MemoryBackend> MemoryStore<B> {
         match self.namespace {
             Some(ref ns) => {
                 let tag = format!("ns:{}", ns);
-                self.records
-                    .iter()
-                    .filter(|r| r.tags.contains(&tag))
-                    .collect()
+                self.select(|r| r.tags.contains(&tag))
             }
-            None => self.records.iter().collect(),
+            None => self.select(|_| true),
         }
     }
 
@@ -590,10 +783,13 @@ impl<B: MemoryBackend> MemoryStore<B> {
     /// List all unique namespaces from record tags.
     pub fn list_namespaces(&self) -> Vec<String> {
         let mut namespaces: Vec<String> = self
-            .records
-            .iter()
-            .filter_map(|r| r.tags.iter().find(|t| t.starts_with("ns:")))
-            .map(|t| t[3..].to_string())
+            .every()
+            .filter_map(|r| {
+                r.tags
+                    .iter()
+                    .find(|t| t.starts_with("ns:"))
+                    .map(|t| t[3..].to_string())
+            })
             .collect();
         namespaces.sort();
         namespaces.dedup();
@@ -603,7 +799,7 @@ impl<B: MemoryBackend> MemoryStore<B> {
     /// Count records per namespace.
     pub fn namespace_counts(&self) -> std::collections::HashMap<String, usize> {
         let mut counts = std::collections::HashMap::new();
-        for rec in &self.records {
+        for rec in self.every() {
             for tag in &rec.tags {
                 if let Some(ns) = tag.strip_prefix("ns:") {
                     *counts.entry(ns.to_string()).or_insert(0) += 1;
@@ -635,6 +831,209 @@ impl<B: MemoryBackend> MemoryStore<B> {
         self
     }
 
+    /// Attach a zstd cold tier rooted at `dir` for demoted Raw records.
+    pub fn with_cold_tier<P: AsRef<Path>>(mut self, dir: P) -> Result<Self> {
+        self.cold = Some(Arc::new(RwLock::new(ColdTier::open(dir)?)));
+        self.disk_reads = FrozenMap::new();
+        Ok(self)
+    }
+
+    pub fn cold_tier(&self) -> Option<RwLockReadGuard<'_, ColdTier>> {
+        self.cold
+            .as_ref()
+            .map(|c| c.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub fn cold_tier_mut(&mut self) -> Option<RwLockWriteGuard<'_, ColdTier>> {
+        self.disk_reads = FrozenMap::new();
+        self.cold
+            .as_ref()
+            .map(|c| c.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Keep at most `hot_records` records decoded in memory and page the
+    /// rest out to a sled tier in `dir` (see `page_tier`), oldest write
+    /// first. Lookups, search, `query` and the `find_*` methods cover paged
+    /// records; `all()` and `apply_decay` see resident ones only. Writes to a
+    /// paged record fault it back in first. This is a mode of the store
+    /// rather than a backend, so the REST server, CLI, MCP server and
+    /// `CognitiveHandle` get it from whatever store they are handed.
+    pub fn with_paging<P: AsRef<Path>>(mut self, dir: P, hot_records: usize) -> Result<Self> {
+        self.paging = Some(Paging {
+            tier: Arc::new(RwLock::new(PageTier::open(dir)?)),
+            hot_records: hot_records.max(1),
+        });
+        self.disk_reads = FrozenMap::new();
+        self.page_out_excess()?;
+        Ok(self)
+    }
+
+    pub fn page_tier(&self) -> Option<RwLockReadGuard<'_, PageTier>> {
+        self.paging
+            .as_ref()
+            .map(|p| p.tier.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn page_tier_mut(&mut self) -> Option<RwLockWriteGuard<'_, PageTier>> {
+        self.disk_reads = FrozenMap::new();
+        self.paging
+            .as_ref()
+            .map(|p| p.tier.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Once more than `hot_records` records are resident, page the oldest
+    /// out until a quarter of the cap is free again, so a backend that cannot
+    /// delete in place is rewritten once per batch rather than once per add.
+    /// Records reach the page tier before they leave the backend.
+    fn page_out_excess(&mut self) -> Result<usize> {
+        let Some(cap) = self.paging.as_ref().map(|p| p.hot_records) else {
+            return Ok(0);
+        };
+        if self.records.len() <= cap {
+            return Ok(0);
+        }
+        let excess = self.records.len() - (cap - cap / 4);
+        let out: Vec<MemoryRecord> = self.records.iter().take(excess).cloned().collect();
+        self.flush()?;
+        if let Some(mut pages) = self.page_tier_mut() {
+            pages.page_out(&out)?;
+        }
+        let ids: Vec<uuid::Uuid> = out.iter().map(|r| r.id).collect();
+        for &id in &ids {
+            self.records.remove(id);
+        }
+        self.persist_delete(&ids)?;
+        Ok(ids.len())
+    }
+
+    /// Move the given hot records into the cold tier as one compressed segment and
+    /// rewrite the backend without them. Pinned records are never demoted.
+    /// Returns the number of records demoted (0 when no cold tier is attached).
+    pub fn demote_to_cold(&mut self, ids: &[uuid::Uuid]) -> Result<usize> {
+        if self.cold.is_none() || ids.is_empty() {
+            return Ok(0);
+        }
+        let wanted: std::collections::HashSet<uuid::Uuid> = ids.iter().copied().collect();
+        let demoted: Vec<MemoryRecord> = self
+            .records
+            .iter()
+            .filter(|r| wanted.contains(&r.id) && r.priority != Priority::Pinned)
+            .cloned()
+            .collect();
+        if demoted.is_empty() {
+            return Ok(0);
+        }
+        self.flush()?;
+        let n = match self.cold_tier_mut() {
+            Some(mut cold) => cold.demote(&demoted)?,
+            None => 0,
+        };
+        for rec in &demoted {
+            self.records.remove(rec.id);
+        }
+        self.backend.clear()?;
+        let snap = self.records.clone();
+        for rec in &snap {
+            self.backend.append(rec)?;
+        }
+        self.backend.flush()?;
+        self.audit
+            .append("system", "demote_cold", &format!("demoted {} records", n))?;
+        Ok(n)
+    }
+
+    /// Fault a cold or paged record back into the hot store. Returns `true`
+    /// if it was in either tier. A paged record leaves its page only once
+    /// the backend holds it.
+    pub fn fault_in(&mut self, id: uuid::Uuid) -> Result<bool> {
+        let rec = match self.cold_tier_mut() {
+            Some(mut cold) => cold.fault(id)?,
+            None => None,
+        };
+        let paged = match rec {
+            Some(_) => None,
+            None => self.page_tier().map(|p| p.get(id)).transpose()?.flatten(),
+        };
+        let Some(rec) = rec.or(paged) else {
+            return Ok(false);
+        };
+        self.backend.append(&rec)?;
+        self.backend.flush()?;
+        self.delete_paged(&[id]);
+        self.audit.append(&rec.actor, "fault_in", &id.to_string())?;
+        self.records.push(rec);
+        self.page_out_excess()?;
+        Ok(true)
+    }
+
+    /// The one mutation path for existing records: fault `id` in from the
+    /// cold or page tier if needed, apply `f`, refresh the integrity hash and
+    /// journal the post-image. Returns a copy of the updated record.
+    fn modify_tiered(
+        &mut self,
+        id: uuid::Uuid,
+        f: impl FnOnce(&mut MemoryRecord),
+    ) -> Result<MemoryRecord> {
+        if !self.records.contains(id) && !self.fault_in(id)? {
+            return Err(anyhow::anyhow!("record not found: {}", id));
+        }
+        let rec = self
+            .records
+            .update(id, |r| {
+                f(r);
+                r.integrity = Some(r.compute_hash());
+            })
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("record not found: {}", id))?;
+        self.journal_upsert(id);
+        Ok(rec)
+    }
+
+    /// `find_by_id` that transparently faults the record in from the cold or page tier.
+    pub fn find_by_id_tiered(&mut self, id: uuid::Uuid) -> Option<&MemoryRecord> {
+        if !self.records.contains(id) {
+            let _ = self.fault_in(id);
+        }
+        self.find_by_id(id)
+    }
+
+    /// `search_semantic` over the hot, paged and cold records together.
+    /// Records on disk are scored where they sit rather than faulted hot, so
+    /// read-only snapshots can run it. Cold records are ranked on their
+    /// in-memory search stubs and only the returned hits are read from their
+    /// segments. Returns owned records.
+    pub fn search_tiered(
+        &self,
+        query_embedding: Option<&[f64]>,
+        query_text: &str,
+        limit: usize,
+        include_quarantined: bool,
+    ) -> Vec<(MemoryRecord, f64)> {
+        let cold = self.cold_tier();
+        let stubs = cold
+            .iter()
+            .flat_map(|c| c.search_stubs())
+            .filter(|r| !self.records.contains(r.id))
+            .map(|r| Candidate { record: Cow::Borrowed(r), cold_stub: true });
+        let stored = self.every().map(|r| Candidate { record: r, cold_stub: false });
+        self.rank(
+            stored.chain(stubs),
+            query_embedding,
+            query_text,
+            limit,
+            include_quarantined,
+        )
+        .into_iter()
+        .map(|(candidate, score)| {
+            let full = candidate
+                .cold_stub
+                .then(|| cold.as_ref()?.get(candidate.record.id).ok().flatten())
+                .flatten();
+            (full.unwrap_or_else(|| candidate.record.into_owned()), score)
+        })
+        .collect()
+    }
+
     /// Set an embedding provider for zero-config auto-embedding.
     pub fn with_embedding_provider(
         mut self,
@@ -646,19 +1045,24 @@ impl<B: MemoryBackend> MemoryStore<B> {
 
     /// Add a record and auto-embed its text content if an embedding provider is set.
     /// The embedding is stored in `metadata.embedding` for later semantic search.
-    pub fn embed_and_add(&mut self, record: MemoryRecord, text_to_embed: &str) -> Result<()> {
+    /// The write screen runs first, and the text is embedded with the same
+    /// spans tokenized, so redacted values never reach the provider.
+    pub fn embed_and_add(&mut self, mut record: MemoryRecord, text_to_embed: &str) -> Result<()> {
+        self.screen(&mut record)?;
         if let Some(ref provider) = self.embedding_provider {
+            let mut text = text_to_embed.to_string();
+            if self.screen_writes {
+                crate::safety_guardrail::redact_for(&record, &mut text)
+                    .map_err(anyhow::Error::msg)?;
+            }
             let embedding: Vec<f64> = provider
-                .embed(text_to_embed)
+                .embed(&text)
                 .into_iter()
                 .map(|v| v as f64)
                 .collect();
-            let mut rec = record;
-            rec.set_metadata_field("embedding", embedding).ok();
-            self.add(rec)
-        } else {
-            self.add(record)
+            record.set_metadata_field("embedding", embedding).ok();
         }
+        self.add_prescreened(record)
     }
 
     /// GDPR right-to-forget: remove all records for `actor`, rewrite the backend
@@ -666,9 +1070,8 @@ impl<B: MemoryBackend> MemoryStore<B> {
     /// Returns the UUIDs of deleted records.
     pub fn delete_by_actor(&mut self, actor: &str) -> Result<Vec<uuid::Uuid>> {
         let deleted_ids: Vec<uuid::Uuid> = self
-            .records
+            .find_by_actor(actor)
             .iter()
-            .filter(|r| r.actor == actor)
             .map(|r| r.id)
             .collect();
 
@@ -677,35 +1080,14 @@ impl<B: MemoryBackend> MemoryStore<B> {
         }
 
         // Remove from in-memory records and pending write buffer
-        self.records.retain(|r| r.actor != actor);
-        self.buffer.retain(|r| r.actor != actor);
-
-        // Rebuild index maps from scratch
-        self.index_actor.clear();
-        self.index_action.clear();
-        self.index_target.clear();
-        for (i, rec) in self.records.iter().enumerate() {
-            self.index_actor
-                .entry(rec.actor.clone())
-                .or_default()
-                .push(i);
-            self.index_action
-                .entry(rec.action.clone())
-                .or_default()
-                .push(i);
-            self.index_target
-                .entry(rec.target.clone())
-                .or_default()
-                .push(i);
+        for &id in &deleted_ids {
+            self.records.remove(id);
+            self.journal_push(RecordChange::Delete { id });
         }
+        self.buffer.retain(|r| r.actor != actor);
+        self.delete_paged(&deleted_ids);
 
-        // Rewrite backend: clear the file then re-append all remaining records
-        self.backend.clear()?;
-        let remaining = self.records.clone();
-        for record in &remaining {
-            self.backend.append(record)?;
-        }
-        self.backend.flush()?;
+        self.persist_delete(&deleted_ids)?;
 
         // Record the deletion in the tamper-evident audit log
         self.audit.append(
@@ -717,13 +1099,27 @@ impl<B: MemoryBackend> MemoryStore<B> {
         Ok(deleted_ids)
     }
 
-    /// Find a single record by its UUID.
+    /// Find a single record by its UUID, hot, paged or cold. A record on
+    /// disk is read in place (not faulted hot) and kept for later lookups on
+    /// this store.
     pub fn find_by_id(&self, id: uuid::Uuid) -> Option<&MemoryRecord> {
-        self.records.iter().find(|r| r.id == id)
+        if let Some(rec) = self.records.by_id(id) {
+            return Some(rec);
+        }
+        if let Some(rec) = self.disk_reads.get(&id) {
+            return Some(rec);
+        }
+        let cold = self.cold_tier().and_then(|c| c.get(id).ok().flatten());
+        let rec = match cold {
+            Some(rec) => rec,
+            None => self.page_tier()?.get(id).ok()??,
+        };
+        Some(self.disk_reads.insert(id, Box::new(rec)))
     }
 
     /// Update a record in-place: apply partial changes, increment version,
     /// rewrite the backend file, and append an update entry to the audit log.
+    /// The updated record passes the write screen first, as an add does.
     pub fn update_record(
         &mut self,
         id: uuid::Uuid,
@@ -733,50 +1129,64 @@ impl<B: MemoryBackend> MemoryStore<B> {
         new_source: Option<&str>,
         new_metadata: Option<serde_json::Value>,
     ) -> Result<uuid::Uuid> {
-        let idx = self
-            .records
-            .iter()
-            .position(|r| r.id == id)
+        let patch = RecordPatch {
+            target: new_target.map(str::to_string),
+            action: new_action.map(str::to_string),
+            confidence: new_confidence,
+            source: new_source.map(str::to_string),
+            metadata: new_metadata,
+        };
+        self.update_with(id, &patch).map(|_| id)
+    }
+
+    /// `update_record` taking a `RecordPatch`; returns the updated record.
+    pub fn update_with(&mut self, id: uuid::Uuid, patch: &RecordPatch) -> Result<MemoryRecord> {
+        let mut next = self
+            .patched(id, patch)
             .ok_or_else(|| anyhow::anyhow!("record not found: {}", id))?;
+        self.screen(&mut next)?;
+        self.update_prescreened(next)
+    }
 
-        // Apply partial updates
-        if let Some(t) = new_target {
-            self.records[idx].target = t.to_string();
+    /// The record `update_with` would write for `patch`, before screening;
+    /// `None` when no record has this id.
+    pub fn patched(&self, id: uuid::Uuid, patch: &RecordPatch) -> Option<MemoryRecord> {
+        let mut r = self.find_by_id(id)?.clone();
+        if let Some(t) = &patch.target {
+            r.target = t.clone();
         }
-        if let Some(a) = new_action {
-            self.records[idx].action = a.to_string();
+        if let Some(a) = &patch.action {
+            r.action = a.clone();
         }
-        if let Some(c) = new_confidence {
-            self.records[idx].confidence = c.clamp(0.0, 1.0);
+        if let Some(c) = patch.confidence {
+            r.confidence = c.clamp(0.0, 1.0);
         }
-        if let Some(s) = new_source {
-            self.records[idx].source = Some(s.to_string());
+        if let Some(s) = &patch.source {
+            r.source = Some(s.clone());
         }
-        if let Some(m) = new_metadata {
-            self.records[idx].metadata = m;
+        if let Some(m) = &patch.metadata {
+            r.metadata = m.clone();
         }
-        self.records[idx].version += 1;
-        // Recompute integrity hash after update
-        self.records[idx].integrity = Some(self.records[idx].compute_hash());
+        r.version += 1;
+        Some(r)
+    }
 
-        // Rewrite backend
-        self.backend.clear()?;
-        let records_clone = self.records.clone();
-        for record in &records_clone {
-            self.backend.append(record)?;
-        }
-        self.backend.flush()?;
+    /// Store `next`, a `patched` record the caller already passed through
+    /// `screen`, over the record with its id.
+    pub(crate) fn update_prescreened(&mut self, next: MemoryRecord) -> Result<MemoryRecord> {
+        let id = next.id;
+        let rec = self.modify_tiered(id, |r| *r = next)?;
+
+        self.persist_update(id)?;
 
         // Audit log
-        let actor = self.records[idx].actor.clone();
-        let version = self.records[idx].version;
         self.audit.append(
-            &actor,
+            &rec.actor,
             "update",
-            &format!("record {} updated to version {}", id, version),
+            &format!("record {} updated to version {}", id, rec.version),
         )?;
 
-        Ok(id)
+        Ok(rec)
     }
 
     /// Return the most recent record(s) per (actor, action) combination.
@@ -790,18 +1200,14 @@ impl<B: MemoryBackend> MemoryStore<B> {
     ) -> Vec<&MemoryRecord> {
         let now_ts = chrono::Utc::now().timestamp();
         // Collect non-expired candidates
-        let mut candidates: Vec<&MemoryRecord> = self
-            .records
-            .iter()
-            .filter(|r| {
-                actor.map_or(true, |a| r.actor == a)
-                    && action.map_or(true, |ac| r.action == ac)
-                    && r.expires_at.map_or(true, |exp| exp > now_ts)
-            })
-            .collect();
+        let mut candidates: Vec<&MemoryRecord> = self.select(|r| {
+            actor.is_none_or(|a| r.actor == a)
+                && action.is_none_or(|ac| r.action == ac)
+                && r.expires_at.is_none_or(|exp| exp > now_ts)
+        });
 
         // Sort descending by timestamp (newest first)
-        candidates.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        candidates.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
 
         // Deduplicate: keep only the most recent per (actor, action) pair
         let mut seen: std::collections::HashSet<(String, String)> =
@@ -829,18 +1235,100 @@ impl<B: MemoryBackend> MemoryStore<B> {
 
     pub fn clear(&mut self) {
         self.records.clear();
-        self.index_actor.clear();
-        self.index_action.clear();
-        self.index_target.clear();
+        if let Some(mut pages) = self.page_tier_mut() {
+            let _ = pages.clear();
+        }
+        self.journal_push(RecordChange::Clear);
         let _ = self.backend.clear();
     }
 
+    /// Replace the whole record set with `records` and rewrite the backend.
+    /// Every cold copy is tombstoned; records that were cold are demoted again
+    /// in their replacement form, so they stay cold without the old copy
+    /// shadowing the new one, and cold records not in `records` are dropped.
+    /// The page tier is emptied and refilled as the new set exceeds the
+    /// hot-record cap. Journaled as a `Clear` plus one `Upsert` per record.
+    pub fn replace_all(&mut self, records: Vec<MemoryRecord>) -> Result<()> {
+        self.journal_replace(&records);
+        let hot = match self.cold_tier_mut() {
+            Some(mut cold) => {
+                let cold_ids: std::collections::HashSet<uuid::Uuid> = cold.ids().collect();
+                for id in &cold_ids {
+                    cold.tombstone(*id)?;
+                }
+                let (stay_cold, hot): (Vec<_>, Vec<_>) =
+                    records.into_iter().partition(|r| cold_ids.contains(&r.id));
+                cold.demote(&stay_cold)?;
+                hot
+            }
+            None => records,
+        };
+        self.records = hot.into_iter().collect();
+        self.buffer.clear();
+        if let Some(mut pages) = self.page_tier_mut() {
+            pages.clear()?;
+        }
+        self.compact_backend()?;
+        self.audit.append(
+            "system",
+            "replace_all",
+            &format!("{} records", self.records.len()),
+        )?;
+        self.page_out_excess()?;
+        Ok(())
+    }
+
+    // ─── Change journal ───────────────────────────────────────────────────────
+
+    /// Start recording a `RecordChange` for every mutation so callers can attach
+    /// the post-images to their tx log entry (`TxLog::append_with_changes`).
+    pub fn enable_change_journal(&mut self) {
+        if self.journal.is_none() {
+            self.journal = Some(Vec::new());
+        }
+    }
+
+    pub fn change_journal_enabled(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Drain the changes recorded since the last call. Empty when disabled.
+    pub fn take_changes(&mut self) -> Vec<RecordChange> {
+        self.journal.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn journal_push(&mut self, change: RecordChange) {
+        if let Some(j) = self.journal.as_mut() {
+            j.push(change);
+        }
+    }
+
+    fn journal_upsert(&mut self, id: uuid::Uuid) {
+        if self.journal.is_some() {
+            if let Some(record) = self.records.by_id(id).cloned() {
+                self.journal_push(RecordChange::Upsert {
+                    record: Box::new(record),
+                });
+            }
+        }
+    }
+
+    fn journal_replace(&mut self, records: &[MemoryRecord]) {
+        if let Some(j) = self.journal.as_mut() {
+            j.push(RecordChange::Clear);
+            j.extend(records.iter().map(|r| RecordChange::Upsert {
+                record: Box::new(r.clone()),
+            }));
+        }
+    }
+
     pub fn snapshot<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.flush()?;
 
         let mut file = std::fs::File::create(path)?;
-        for rec in &self.records {
-            serde_json::to_writer(&mut file, rec)?;
+        writeln!(file, "{}", FormatHeader::current().to_line())?;
+        for rec in self.every() {
+            serde_json::to_writer(&mut file, &*rec)?;
             file.write_all(b"\n")?;
         }
         Ok(())
@@ -855,6 +1343,10 @@ impl<B: MemoryBackend> MemoryStore<B> {
             if line.trim().is_empty() {
                 continue;
             }
+            if let Some(header) = FormatHeader::parse(&line) {
+                header.ensure_readable()?;
+                continue;
+            }
             let rec: MemoryRecord = serde_json::from_str(&line)?;
             if let Some(hash) = &rec.integrity {
                 if *hash != rec.compute_hash() {
@@ -863,26 +1355,10 @@ impl<B: MemoryBackend> MemoryStore<B> {
             }
             records.push(rec);
         }
-        self.records = records.clone();
-        self.index_actor.clear();
-
-        self.index_action.clear();
-        self.index_target.clear();
-
-        for (i, rec) in self.records.iter().enumerate() {
-            self.index_actor
-                .entry(rec.actor.clone())
-                .or_default()
-                .push(i);
-
-            self.index_action
-                .entry(rec.action.clone())
-                .or_default()
-                .push(i);
-            self.index_target
-                .entry(rec.target.clone())
-                .or_default()
-                .push(i);
+        self.journal_replace(&records);
+        self.records = records.iter().cloned().collect();
+        if let Some(mut pages) = self.page_tier_mut() {
+            pages.clear()?;
         }
         self.backend.clear()?;
         for rec in &records {
@@ -890,6 +1366,7 @@ impl<B: MemoryBackend> MemoryStore<B> {
         }
         self.backend.flush()?;
         self.audit.append("system", "rollback", "ok")?;
+        self.page_out_excess()?;
         Ok(())
     }
 }
@@ -1008,6 +1485,100 @@ pub fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
     }
 }
 
+/// Compute time-based confidence decay for a memory record.
+///
+/// Formula: `confidence × exp(−λ × elapsed_seconds / t½)`
+///
+/// Parameters read from `rec.metadata`:
+///   - `"decay_factor"` (λ): rate multiplier, default 1.0; 0.0 = no decay
+///   - `"decay_half_life_secs"` (t½): seconds for confidence to halve, default 2,592,000 (30 days)
+///
+/// Returns `rec.confidence as f64` unchanged when λ=0 or elapsed < 1s.
+///
+/// # Invariant
+///
+/// The return value is always in `[0.0, rec.confidence]`. This function is
+/// **purely suppressive** — it can reduce or preserve a record's contribution
+/// to the final weighted score, but never amplify it beyond what confidence allows.
+///
+/// `rec.confidence` at ingestion time acts as a **permanent score ceiling**:
+/// a record with `confidence=0.5` can contribute at most `0.5 × base_score × trust`
+/// to the final weighted score, regardless of query relevance or time elapsed.
+///
+/// This is intentional: low-confidence memories should not dominate retrieval
+/// even when they are semantically relevant. To allow a memory to rank higher,
+/// ingest it with a higher `confidence` value.
+pub(crate) fn compute_decay(rec: &MemoryRecord) -> f64 {
+    let elapsed_secs =
+        (chrono::Utc::now().timestamp() - rec.timestamp.timestamp()).max(0) as f64;
+    let conf = rec.confidence as f64;
+    if elapsed_secs < 1.0 {
+        return conf;
+    }
+    let lambda = rec
+        .metadata
+        .get("decay_factor")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0)
+        .clamp(0.0, 10.0);
+    if lambda < f64::EPSILON {
+        return conf; // λ=0 → no decay
+    }
+    let half_life = rec
+        .metadata
+        .get("decay_half_life_secs")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(2_592_000.0) // 30 days
+        .max(1.0);
+    conf * (-lambda * elapsed_secs / half_life).exp()
+}
+
+/// Whether `search_semantic` may return `rec`: not archived, not expired, and
+/// not quarantined unless asked for.
+pub(crate) fn searchable(rec: &MemoryRecord, now_ts: i64, include_quarantined: bool) -> bool {
+    (include_quarantined || rec.status != RecordStatus::Quarantine)
+        && rec.status != RecordStatus::Archived
+        && rec.expires_at.is_none_or(|exp| exp > now_ts)
+}
+
+/// `search_semantic` score of a non-pinned record: cosine similarity against
+/// `metadata.embedding` when both sides have one, else keyword overlap,
+/// weighted by source trust, priority and confidence decay.
+pub(crate) fn weighted_score(
+    rec: &MemoryRecord,
+    query_embedding: Option<&[f64]>,
+    query_text: &str,
+    source_trust: &SourceTrustRegistry,
+) -> f64 {
+    let base_score = if let Some(qe) = query_embedding {
+        let doc_vec: Option<Vec<f64>> = rec
+            .metadata
+            .get("embedding")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        if let Some(dv) = doc_vec {
+            cosine_similarity(qe, &dv)
+        } else {
+            keyword_score(query_text, rec)
+        }
+    } else {
+        keyword_score(query_text, rec)
+    };
+    // Trust-weighted score: multiply by source credibility
+    let trust = rec
+        .source
+        .as_deref()
+        .map(|s| source_trust.get_trust(s))
+        .unwrap_or(0.5);
+    // Priority multiplier: high=1.5×, low=0.5×, normal/pinned=1.0×
+    // Note: pinned records take a separate code path (score 2.0 override).
+    let priority_mult: f64 = match rec.priority {
+        Priority::High => 1.5,
+        Priority::Low => 0.5,
+        Priority::Normal | Priority::Pinned => 1.0,
+    };
+    base_score * (0.5 + 0.5 * trust) * priority_mult * compute_decay(rec)
+}
+
 /// Simple keyword score: fraction of whitespace-split query tokens found in the
 /// concatenated `actor + action + target` string (case-insensitive).
 pub fn keyword_score(query: &str, rec: &crate::memory_record::MemoryRecord) -> f64 {
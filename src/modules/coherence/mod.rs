@@ -529,6 +529,7 @@ impl CoherenceChecker {
             CognitiveDelta::RetractBelief { .. }
             | CognitiveDelta::AssertJustification { .. }
             | CognitiveDelta::AutoConsolidate { .. }
+            | CognitiveDelta::Summarize { .. }
             | CognitiveDelta::WorkspaceOpen { .. }
             | CognitiveDelta::WorkspaceMerge { .. } => {}
             CognitiveDelta::Consolidate { .. }
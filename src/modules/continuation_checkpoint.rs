@@ -157,6 +157,7 @@ impl ContinuationCheckpoint {
             goal_stack_serialized,
             next_intended_step: self.next_intended_step.clone(),
             sequence_number: self.sequence_number,
+            metadata: self.metadata.clone(),
         };
 
         let metadata = serde_json::to_value(data)
@@ -177,6 +178,56 @@ impl ContinuationCheckpoint {
 
         Ok(record_id)
     }
+
+    /// `persist_to_store`, then delete the task's older checkpoint records so
+    /// the store holds one per task. Backends that cannot delete in place
+    /// keep the old lines until `compact_backend`; `latest_in_store` still
+    /// picks the newest after a reload.
+    pub fn replace_in_store<B: crate::persistence::MemoryBackend>(
+        &self,
+        store: &mut crate::memory_store::MemoryStore<B>,
+        stack: &[crate::executive_scheduler::StackFrame],
+    ) -> Result<String, String> {
+        let record_id = self.persist_to_store(store, stack)?;
+        let stale: Vec<Uuid> = Self::records_in_store(store, self.task_id)
+            .into_iter()
+            .map(|r| r.id)
+            .filter(|id| id.to_string() != record_id)
+            .collect();
+        for id in stale {
+            store.delete_by_id(id);
+        }
+        Ok(record_id)
+    }
+
+    /// Checkpoint records for `task_id`, found through the target index.
+    fn records_in_store<B: crate::persistence::MemoryBackend>(
+        store: &crate::memory_store::MemoryStore<B>,
+        task_id: Uuid,
+    ) -> Vec<&crate::memory_record::MemoryRecord> {
+        store
+            .find_by_target(&format!("task:{}", task_id))
+            .into_iter()
+            .filter(|r| {
+                r.record_type == crate::memory_record::MemoryType::Reflexion
+                    && r.action == "checkpoint_continuation"
+            })
+            .collect()
+    }
+
+    /// The checkpoint for `task_id` with the highest sequence number, as
+    /// written by `persist_to_store`.
+    pub fn latest_in_store<B: crate::persistence::MemoryBackend>(
+        store: &crate::memory_store::MemoryStore<B>,
+        task_id: Uuid,
+    ) -> Option<ContinuationCheckpointData> {
+        Self::records_in_store(store, task_id)
+            .into_iter()
+            .filter_map(|r| {
+                serde_json::from_value::<ContinuationCheckpointData>(r.metadata.clone()).ok()
+            })
+            .max_by_key(|d| d.sequence_number)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -186,6 +237,8 @@ pub struct ContinuationCheckpointData {
     pub goal_stack_serialized: Vec<serde_json::Value>,
     pub next_intended_step: String,
     pub sequence_number: u64,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
 }
 
 #[cfg(test)]
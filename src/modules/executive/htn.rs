@@ -0,0 +1,160 @@
+//! HTN — hierarchical task network planning by method decomposition.
+//!
+//! Chain-of-thought: a task list is planned left to right (total order, as
+//! in SHOP). A primitive task is a `TaskNode` applied to the beliefs when its
+//! preconditions hold; a compound task is replaced by the subtasks of the
+//! first method, in declaration order, whose preconditions hold, and the
+//! search backtracks into later methods when a branch dead-ends. Methods
+//! therefore encode the expert's preferred recipe first and the search only
+//! explores alternatives on failure. Depth and decomposition budgets stop
+//! recursive methods that never bottom out.
+
+use crate::task_graph::{Plan, State, TaskNode};
+use std::collections::HashMap;
+
+/// One way to accomplish the compound task `task`.
+#[derive(Debug, Clone)]
+pub struct Method {
+    pub name: String,
+    pub task: String,
+    pub preconditions: HashMap<String, String>,
+    /// Primitive (by `TaskNode::summary`) or compound task names, in order.
+    pub subtasks: Vec<String>,
+}
+
+impl Method {
+    pub fn new(name: &str, task: &str, subtasks: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            task: task.to_string(),
+            preconditions: HashMap::new(),
+            subtasks: subtasks.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn with_precondition(mut self, key: &str, value: &str) -> Self {
+        self.preconditions.insert(key.into(), value.into());
+        self
+    }
+}
+
+pub struct HtnDomain {
+    primitives: HashMap<String, TaskNode>,
+    methods: Vec<Method>,
+    /// Maximum nesting of compound tasks.
+    pub max_depth: usize,
+    /// Maximum method applications tried before giving up.
+    pub max_decompositions: usize,
+}
+
+/// A pending task and the compound-task depth it was produced at.
+type Agenda = Vec<(String, usize)>;
+
+impl HtnDomain {
+    pub fn new(primitives: Vec<TaskNode>) -> Self {
+        Self {
+            primitives: primitives
+                .into_iter()
+                .map(|p| (p.summary.clone(), p))
+                .collect(),
+            methods: Vec::new(),
+            max_depth: 64,
+            max_decompositions: 100_000,
+        }
+    }
+
+    pub fn add_method(&mut self, method: Method) {
+        self.methods.push(method);
+    }
+
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.add_method(method);
+        self
+    }
+
+    /// Decompose `tasks` from `initial` into a primitive plan.
+    pub fn decompose(
+        &self,
+        initial: &HashMap<String, String>,
+        tasks: &[&str],
+    ) -> Result<Plan, String> {
+        let state: State = initial.clone().into_iter().collect();
+        // Agenda kept reversed so the next task is at the end.
+        let agenda: Agenda = tasks.iter().rev().map(|t| (t.to_string(), 0)).collect();
+        let mut tried = 0;
+        let mut steps = Vec::new();
+        match self.seek(state, agenda, &mut steps, &mut tried)? {
+            true => {
+                let cost = steps.iter().map(|s: &TaskNode| s.cost).sum();
+                Ok(Plan {
+                    steps,
+                    cost,
+                    expanded: tried,
+                })
+            }
+            false => Err(format!("no decomposition of {tasks:?} applies")),
+        }
+    }
+
+    fn seek(
+        &self,
+        state: State,
+        mut agenda: Agenda,
+        steps: &mut Vec<TaskNode>,
+        tried: &mut usize,
+    ) -> Result<bool, String> {
+        let Some((task, depth)) = agenda.pop() else {
+            return Ok(true);
+        };
+        if let Some(primitive) = self.primitives.get(&task) {
+            if !primitive.is_applicable(&state) {
+                return Ok(false);
+            }
+            let mut step = primitive.clone();
+            step.id = uuid::Uuid::new_v4();
+            steps.push(step);
+            if self.seek(primitive.apply(&state), agenda, steps, tried)? {
+                return Ok(true);
+            }
+            steps.pop();
+            return Ok(false);
+        }
+
+        let mut known = false;
+        for method in self.methods.iter().filter(|m| m.task == task) {
+            known = true;
+            if !method
+                .preconditions
+                .iter()
+                .all(|(k, v)| state.get(k) == Some(v))
+            {
+                continue;
+            }
+            if depth >= self.max_depth {
+                return Err(format!(
+                    "decomposition of `{task}` exceeds depth {}",
+                    self.max_depth
+                ));
+            }
+            *tried += 1;
+            if *tried > self.max_decompositions {
+                return Err(format!(
+                    "decomposition limit ({}) exceeded",
+                    self.max_decompositions
+                ));
+            }
+            let mut next = agenda.clone();
+            next.extend(method.subtasks.iter().rev().map(|s| (s.clone(), depth + 1)));
+            let mark = steps.len();
+            if self.seek(state.clone(), next, steps, tried)? {
+                return Ok(true);
+            }
+            steps.truncate(mark);
+        }
+        if known {
+            Ok(false)
+        } else {
+            Err(format!("unknown task `{task}`"))
+        }
+    }
+}
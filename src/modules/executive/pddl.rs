@@ -0,0 +1,511 @@
+//! PDDL import — STRIPS domains and problems, grounded into `TaskNode`s.
+//!
+//! Chain-of-thought: the planner works on string beliefs, so each ground atom
+//! becomes a belief key (`"(at robot hall)"`) whose value is `"true"` or
+//! `"false"`. The initial state lists every atom an action or the goal
+//! mentions, false unless in `:init` (closed world), which makes negative
+//! preconditions and delete effects plain key/value checks. Supported:
+//! `:typing` (with a type hierarchy), `:negative-preconditions`,
+//! `:action-costs` via `(increase (total-cost) n)` where n is a number or a
+//! fluent from `:init`, and `:constants`. Static predicates (never in an
+//! effect) are checked while grounding and dropped from the ground actions.
+
+use crate::task_graph::TaskNode;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Sexp {
+    Atom(String),
+    List(Vec<Sexp>),
+}
+
+impl Sexp {
+    fn atom(&self) -> Option<&str> {
+        match self {
+            Sexp::Atom(a) => Some(a),
+            Sexp::List(_) => None,
+        }
+    }
+
+    fn list(&self) -> Result<&[Sexp], String> {
+        match self {
+            Sexp::List(items) => Ok(items),
+            Sexp::Atom(a) => Err(format!("expected a list, found `{a}`")),
+        }
+    }
+
+    /// Head keyword of a list, lowercased.
+    fn head(&self) -> Option<String> {
+        match self {
+            Sexp::List(items) => items.first()?.atom().map(str::to_lowercase),
+            Sexp::Atom(_) => None,
+        }
+    }
+}
+
+fn parse_sexp(src: &str) -> Result<Sexp, String> {
+    let mut stack: Vec<Vec<Sexp>> = vec![Vec::new()];
+    for line in src.lines() {
+        let line = line.split(';').next().unwrap_or("");
+        let spaced = line.replace('(', " ( ").replace(')', " ) ");
+        for token in spaced.split_whitespace() {
+            match token {
+                "(" => stack.push(Vec::new()),
+                ")" => {
+                    let done = stack.pop().filter(|_| !stack.is_empty());
+                    let done = done.ok_or("unbalanced `)`")?;
+                    stack.last_mut().unwrap().push(Sexp::List(done));
+                }
+                t => stack.last_mut().unwrap().push(Sexp::Atom(t.to_lowercase())),
+            }
+        }
+    }
+    if stack.len() != 1 {
+        return Err("unbalanced `(`".into());
+    }
+    let mut top = stack.pop().unwrap();
+    match top.len() {
+        1 => Ok(top.remove(0)),
+        0 => Err("empty PDDL input".into()),
+        _ => Err("expected a single (define ...) form".into()),
+    }
+}
+
+/// `(pred arg ...)` with variables (`?x`) or object names as arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Atom {
+    pub predicate: String,
+    pub args: Vec<String>,
+}
+
+impl Atom {
+    /// Belief key of a ground atom.
+    pub fn key(&self) -> String {
+        if self.args.is_empty() {
+            format!("({})", self.predicate)
+        } else {
+            format!("({} {})", self.predicate, self.args.join(" "))
+        }
+    }
+
+    fn substitute(&self, binding: &HashMap<&str, &str>) -> Atom {
+        Atom {
+            predicate: self.predicate.clone(),
+            args: self
+                .args
+                .iter()
+                .map(|a| binding.get(a.as_str()).map_or(a.clone(), |o| o.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Literal {
+    pub atom: Atom,
+    pub positive: bool,
+}
+
+/// How much an action adds to `total-cost`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CostTerm {
+    Number(f64),
+    Fluent(Atom),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionSchema {
+    pub name: String,
+    /// `(variable, type)`, variables with their leading `?`.
+    pub parameters: Vec<(String, String)>,
+    pub precondition: Vec<Literal>,
+    pub effect: Vec<Literal>,
+    /// `None` means unit cost.
+    pub cost: Option<CostTerm>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Domain {
+    pub name: String,
+    /// Type -> parent type (`object` at the root).
+    pub types: HashMap<String, String>,
+    pub constants: Vec<(String, String)>,
+    pub actions: Vec<ActionSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Problem {
+    pub name: String,
+    pub domain: String,
+    pub objects: Vec<(String, String)>,
+    pub init: BTreeSet<Atom>,
+    /// Numeric fluents set with `(= (f args) n)` in `:init`.
+    pub fluents: HashMap<Atom, f64>,
+    pub goal: Vec<Literal>,
+}
+
+/// A problem compiled for `Planner::search`.
+#[derive(Debug, Clone)]
+pub struct GroundProblem {
+    pub actions: Vec<TaskNode>,
+    pub initial: HashMap<String, String>,
+    pub goal: HashMap<String, String>,
+}
+
+fn truth(positive: bool) -> String {
+    if positive { "true" } else { "false" }.to_string()
+}
+
+/// `a b - t c` -> [(a, t), (b, t), (c, object)].
+fn typed_list(items: &[Sexp]) -> Result<Vec<(String, String)>, String> {
+    let mut out = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut iter = items.iter();
+    while let Some(item) = iter.next() {
+        let name = item.atom().ok_or("expected a name in a typed list")?;
+        if name == "-" {
+            let ty = iter
+                .next()
+                .and_then(Sexp::atom)
+                .ok_or("expected a type after `-`")?;
+            out.extend(pending.drain(..).map(|n| (n, ty.to_string())));
+        } else {
+            pending.push(name.to_string());
+        }
+    }
+    out.extend(pending.into_iter().map(|n| (n, "object".to_string())));
+    Ok(out)
+}
+
+fn parse_atom(sexp: &Sexp) -> Result<Atom, String> {
+    let items = sexp.list()?;
+    let predicate = items
+        .first()
+        .and_then(Sexp::atom)
+        .ok_or("expected a predicate name")?;
+    let args = items[1..]
+        .iter()
+        .map(|a| a.atom().map(str::to_string).ok_or("nested term in atom"))
+        .collect::<Result<_, _>>()?;
+    Ok(Atom {
+        predicate: predicate.to_string(),
+        args,
+    })
+}
+
+/// Flatten a conjunction of (possibly negated) atoms.
+fn parse_literals(sexp: &Sexp, out: &mut Vec<Literal>) -> Result<(), String> {
+    match sexp.head().as_deref() {
+        None if matches!(sexp, Sexp::List(items) if items.is_empty()) => Ok(()),
+        Some("and") => {
+            for part in &sexp.list()?[1..] {
+                parse_literals(part, out)?;
+            }
+            Ok(())
+        }
+        Some("not") => {
+            let inner = sexp.list()?.get(1).ok_or("empty (not)")?;
+            out.push(Literal {
+                atom: parse_atom(inner)?,
+                positive: false,
+            });
+            Ok(())
+        }
+        Some(op @ ("or" | "imply" | "exists" | "forall" | "when" | "=")) => {
+            Err(format!("unsupported PDDL construct `{op}`"))
+        }
+        _ => {
+            out.push(Literal {
+                atom: parse_atom(sexp)?,
+                positive: true,
+            });
+            Ok(())
+        }
+    }
+}
+
+/// Effects: literals plus at most one `(increase (total-cost) n)`.
+fn parse_effect(
+    sexp: &Sexp,
+    out: &mut Vec<Literal>,
+    cost: &mut Option<CostTerm>,
+) -> Result<(), String> {
+    match sexp.head().as_deref() {
+        Some("and") => {
+            for part in &sexp.list()?[1..] {
+                parse_effect(part, out, cost)?;
+            }
+            Ok(())
+        }
+        Some("increase") => {
+            let items = sexp.list()?;
+            if items.len() != 3 || items[1].head().as_deref() != Some("total-cost") {
+                return Err("only (increase (total-cost) n) is supported".into());
+            }
+            *cost = Some(match &items[2] {
+                Sexp::Atom(n) => {
+                    CostTerm::Number(n.parse().map_err(|_| format!("bad cost `{n}`"))?)
+                }
+                fluent => CostTerm::Fluent(parse_atom(fluent)?),
+            });
+            Ok(())
+        }
+        _ => parse_literals(sexp, out),
+    }
+}
+
+fn sections(sexp: &Sexp, kind: &str) -> Result<(String, Vec<Sexp>), String> {
+    let items = sexp.list()?;
+    if sexp.head().as_deref() != Some("define") || items.len() < 2 {
+        return Err("expected (define ...)".into());
+    }
+    let header = items[1].list()?;
+    match header {
+        [Sexp::Atom(k), Sexp::Atom(name)] if k == kind => Ok((name.clone(), items[2..].to_vec())),
+        _ => Err(format!("expected ({kind} <name>)")),
+    }
+}
+
+pub fn parse_domain(src: &str) -> Result<Domain, String> {
+    let (name, body) = sections(&parse_sexp(src)?, "domain")?;
+    let mut domain = Domain {
+        name,
+        ..Default::default()
+    };
+    for section in &body {
+        let items = section.list()?;
+        match section.head().as_deref() {
+            Some(":types") => {
+                for (ty, parent) in typed_list(&items[1..])? {
+                    domain.types.insert(ty, parent);
+                }
+            }
+            Some(":constants") => domain.constants = typed_list(&items[1..])?,
+            Some(":action") => domain.actions.push(parse_action(items)?),
+            Some(":requirements" | ":predicates" | ":functions") => {}
+            Some(other) => return Err(format!("unsupported domain section `{other}`")),
+            None => return Err("malformed domain section".into()),
+        }
+    }
+    Ok(domain)
+}
+
+fn parse_action(items: &[Sexp]) -> Result<ActionSchema, String> {
+    let name = items
+        .get(1)
+        .and_then(Sexp::atom)
+        .ok_or("action without a name")?;
+    let mut action = ActionSchema {
+        name: name.to_string(),
+        parameters: vec![],
+        precondition: vec![],
+        effect: vec![],
+        cost: None,
+    };
+    let mut rest = items[2..].iter();
+    while let (Some(key), Some(value)) = (rest.next(), rest.next()) {
+        match key.atom() {
+            Some(":parameters") => action.parameters = typed_list(value.list()?)?,
+            Some(":precondition") => parse_literals(value, &mut action.precondition)?,
+            Some(":effect") => parse_effect(value, &mut action.effect, &mut action.cost)?,
+            other => return Err(format!("unexpected `{other:?}` in action {name}")),
+        }
+    }
+    Ok(action)
+}
+
+pub fn parse_problem(src: &str) -> Result<Problem, String> {
+    let (name, body) = sections(&parse_sexp(src)?, "problem")?;
+    let mut problem = Problem {
+        name,
+        ..Default::default()
+    };
+    for section in &body {
+        let items = section.list()?;
+        match section.head().as_deref() {
+            Some(":domain") => {
+                problem.domain = items
+                    .get(1)
+                    .and_then(Sexp::atom)
+                    .unwrap_or_default()
+                    .to_string()
+            }
+            Some(":objects") => problem.objects = typed_list(&items[1..])?,
+            Some(":init") => {
+                for fact in &items[1..] {
+                    if fact.head().as_deref() == Some("=") {
+                        let parts = fact.list()?;
+                        let (Some(f), Some(Sexp::Atom(v))) = (parts.get(1), parts.get(2)) else {
+                            return Err("expected (= (fluent ...) number)".into());
+                        };
+                        let value = v.parse().map_err(|_| format!("bad number `{v}`"))?;
+                        problem.fluents.insert(parse_atom(f)?, value);
+                    } else {
+                        problem.init.insert(parse_atom(fact)?);
+                    }
+                }
+            }
+            Some(":goal") => parse_literals(&items[1], &mut problem.goal)?,
+            Some(":metric" | ":requirements") => {}
+            Some(other) => return Err(format!("unsupported problem section `{other}`")),
+            None => return Err("malformed problem section".into()),
+        }
+    }
+    Ok(problem)
+}
+
+impl Domain {
+    fn is_subtype(&self, ty: &str, of: &str) -> bool {
+        let mut current = ty;
+        // The hierarchy is a tree; the bound guards against cycles.
+        for _ in 0..=self.types.len() {
+            if current == of || of == "object" {
+                return true;
+            }
+            match self.types.get(current) {
+                Some(parent) => current = parent,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Ground every action schema over the problem's objects.
+    pub fn ground(&self, problem: &Problem) -> Result<GroundProblem, String> {
+        if !problem.domain.is_empty() && problem.domain != self.name {
+            return Err(format!(
+                "problem is for domain `{}`, not `{}`",
+                problem.domain, self.name
+            ));
+        }
+        let objects: Vec<&(String, String)> =
+            self.constants.iter().chain(&problem.objects).collect();
+        let dynamic: HashSet<&str> = self
+            .actions
+            .iter()
+            .flat_map(|a| a.effect.iter().map(|l| l.atom.predicate.as_str()))
+            .collect();
+
+        let mut actions = Vec::new();
+        for schema in &self.actions {
+            let candidates: Vec<Vec<&str>> = schema
+                .parameters
+                .iter()
+                .map(|(_, ty)| {
+                    objects
+                        .iter()
+                        .filter(|(_, oty)| self.is_subtype(oty, ty))
+                        .map(|(o, _)| o.as_str())
+                        .collect()
+                })
+                .collect();
+            for combo in cartesian(&candidates) {
+                let binding: HashMap<&str, &str> = schema
+                    .parameters
+                    .iter()
+                    .map(|(v, _)| v.as_str())
+                    .zip(combo.iter().copied())
+                    .collect();
+                if let Some(node) = self.ground_action(schema, &binding, problem, &dynamic)? {
+                    actions.push(node);
+                }
+            }
+        }
+
+        let mut initial: HashMap<String, String> = HashMap::new();
+        for action in &actions {
+            for key in action.preconditions.keys().chain(action.effects.keys()) {
+                initial.entry(key.clone()).or_insert_with(|| truth(false));
+            }
+        }
+        let goal: HashMap<String, String> = problem
+            .goal
+            .iter()
+            .map(|l| (l.atom.key(), truth(l.positive)))
+            .collect();
+        for key in goal.keys() {
+            initial.entry(key.clone()).or_insert_with(|| truth(false));
+        }
+        for atom in &problem.init {
+            if let Some(v) = initial.get_mut(&atom.key()) {
+                *v = truth(true);
+            }
+        }
+        Ok(GroundProblem {
+            actions,
+            initial,
+            goal,
+        })
+    }
+
+    /// `None` when a static precondition rules the binding out.
+    fn ground_action(
+        &self,
+        schema: &ActionSchema,
+        binding: &HashMap<&str, &str>,
+        problem: &Problem,
+        dynamic: &HashSet<&str>,
+    ) -> Result<Option<TaskNode>, String> {
+        let args: Vec<&str> = schema
+            .parameters
+            .iter()
+            .map(|(v, _)| binding[v.as_str()])
+            .collect();
+        let summary = if args.is_empty() {
+            format!("({})", schema.name)
+        } else {
+            format!("({} {})", schema.name, args.join(" "))
+        };
+        let mut preconditions = HashMap::new();
+        for lit in &schema.precondition {
+            let atom = lit.atom.substitute(binding);
+            if dynamic.contains(atom.predicate.as_str()) {
+                preconditions.insert(atom.key(), truth(lit.positive));
+            } else if problem.init.contains(&atom) != lit.positive {
+                return Ok(None);
+            }
+        }
+        let cost = match &schema.cost {
+            None => 1.0,
+            Some(CostTerm::Number(n)) => *n,
+            Some(CostTerm::Fluent(f)) => {
+                let f = f.substitute(binding);
+                *problem
+                    .fluents
+                    .get(&f)
+                    .ok_or_else(|| format!("no value for fluent {} in :init", f.key()))?
+            }
+        };
+        let mut node = TaskNode::new(&summary, cost);
+        node.preconditions = preconditions;
+        // Deletes first, then adds: an atom both deleted and added stays true.
+        for lit in schema.effect.iter().filter(|l| !l.positive) {
+            node.effects
+                .insert(lit.atom.substitute(binding).key(), truth(false));
+        }
+        for lit in schema.effect.iter().filter(|l| l.positive) {
+            node.effects
+                .insert(lit.atom.substitute(binding).key(), truth(true));
+        }
+        Ok(Some(node))
+    }
+}
+
+fn cartesian<'a>(sets: &[Vec<&'a str>]) -> Vec<Vec<&'a str>> {
+    sets.iter().fold(vec![vec![]], |acc, set| {
+        acc.iter()
+            .flat_map(|prefix| {
+                set.iter().map(move |item| {
+                    let mut next = prefix.clone();
+                    next.push(*item);
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+/// Parse and ground a domain/problem pair.
+pub fn compile(domain_src: &str, problem_src: &str) -> Result<GroundProblem, String> {
+    parse_domain(domain_src)?.ground(&parse_problem(problem_src)?)
+}
@@ -1,8 +1,33 @@
+//! Executive scheduler — drives task-graph plans to completion.
+//!
+//! Chain-of-thought: the goal stack is kept sorted by priority, so submitting
+//! a more urgent goal preempts the running one (its graph is parked and its
+//! current step kept) and a finished goal hands control back to the one it
+//! interrupted. Each tick runs the health and coherence gates, then
+//! dispatches the top frame's current step to the first registered
+//! `StepExecutor` that claims it — a compiled skill in the `ProceduralCache`,
+//! a named tool, or an LLM-backed tool. Failures are retried up to
+//! `RetryPolicy::max_attempts`, then skip the step or abort the goal.
+//! `run_tick` also writes a `ContinuationCheckpoint` with the stack, the
+//! graphs and the retry counters whenever something changed (replacing the
+//! previous one, so the store keeps one per executive), and `resume`
+//! rebuilds the scheduler from it after a restart.
+
+use crate::backends::rustfsm_backend::RustFSMBackend;
 use crate::coherence::CoherenceChecker;
+use crate::continuation_checkpoint::ContinuationCheckpoint;
+use crate::llm_clients::LLMClient;
+use crate::memory_record::{MemoryType, Priority};
+use crate::memory_store::MemoryStore;
+use crate::payloads::{GoalPayload, GoalStatus};
+use crate::persistence::MemoryBackend;
+use crate::procedural_cache::{FSMBackend, FSMState, ProceduralCache, ProceduralTrace};
 use crate::self_model::SelfModel;
-use crate::task_graph::{TaskGraph, TaskState};
+use crate::skill_compiler::{SkillCompiler, SkillTemplate};
+use crate::task_graph::{TaskGraph, TaskNode, TaskState};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,10 +37,290 @@ pub struct StackFrame {
     pub current_step: Option<Uuid>,
 }
 
+// ─── Executors ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepOutcome {
+    Completed,
+    /// Worth retrying under the scheduler's `RetryPolicy`.
+    Failed(String),
+    /// Retrying cannot help; go straight to `RetryPolicy::on_exhausted`.
+    Fatal(String),
+}
+
+/// Something that can carry out plan steps.
+pub trait StepExecutor: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Whether this executor knows how to run `step`.
+    fn handles(&self, step: &TaskNode) -> bool;
+
+    /// Run `step`; `beliefs` are the goal frame's beliefs before the step.
+    fn execute(&self, step: &TaskNode, beliefs: &HashMap<String, String>) -> StepOutcome;
+}
+
+/// First word of a step summary, used to route steps to skills and tools.
+fn verb(step: &TaskNode) -> &str {
+    step.summary.split_whitespace().next().unwrap_or("")
+}
+
+/// Runs steps named after compiled skills by driving a fresh trace through
+/// the skill's FSM in the shared `ProceduralCache`, from `Start` to `End`.
+pub struct SkillExecutor<B: FSMBackend + Send = RustFSMBackend> {
+    cache: Arc<Mutex<ProceduralCache<B>>>,
+    skills: Mutex<HashMap<String, SkillTemplate>>,
+}
+
+impl<B: FSMBackend + Send> SkillExecutor<B> {
+    pub fn new(cache: Arc<Mutex<ProceduralCache<B>>>) -> Self {
+        Self {
+            cache,
+            skills: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Compile `template` into the cache and route steps named after it here.
+    pub fn register(&self, template: SkillTemplate) {
+        if let Ok(mut cache) = self.cache.lock() {
+            SkillCompiler::compile_and_register_skill(&mut cache, &template);
+        }
+        if let Ok(mut skills) = self.skills.lock() {
+            skills.insert(template.name.clone(), template);
+        }
+    }
+}
+
+impl<B: FSMBackend + Send> StepExecutor for SkillExecutor<B> {
+    fn name(&self) -> &str {
+        "skill"
+    }
+
+    fn handles(&self, step: &TaskNode) -> bool {
+        self.skills
+            .lock()
+            .map(|s| s.contains_key(verb(step)))
+            .unwrap_or(false)
+    }
+
+    fn execute(&self, step: &TaskNode, _beliefs: &HashMap<String, String>) -> StepOutcome {
+        let Some(template) = self
+            .skills
+            .lock()
+            .ok()
+            .and_then(|s| s.get(verb(step)).cloned())
+        else {
+            return StepOutcome::Fatal(format!("unknown skill `{}`", verb(step)));
+        };
+        let Ok(mut cache) = self.cache.lock() else {
+            return StepOutcome::Failed("procedural cache lock poisoned".into());
+        };
+        let trace_id = Uuid::new_v4();
+        cache.add_trace(ProceduralTrace {
+            id: trace_id,
+            current_state: FSMState::Start,
+            memory: HashMap::new(),
+        });
+        let conditions = std::iter::once(template.name.as_str())
+            .chain(template.steps.iter().map(|s| s.action_pattern.as_str()));
+        let mut state = FSMState::Start;
+        for condition in conditions {
+            match cache.advance(trace_id, Some(condition)) {
+                Some(next) => state = next,
+                None => break,
+            }
+        }
+        cache.remove_trace(trace_id);
+        if state == FSMState::End {
+            StepOutcome::Completed
+        } else {
+            StepOutcome::Failed(format!("skill `{}` stalled at {state:?}", template.name))
+        }
+    }
+}
+
+pub type ToolFn =
+    Arc<dyn Fn(&TaskNode, &HashMap<String, String>) -> Result<(), String> + Send + Sync>;
+
+/// Named tools; a step whose summary starts with a tool's name runs it.
+#[derive(Default)]
+pub struct ToolExecutor {
+    tools: HashMap<String, ToolFn>,
+}
+
+impl ToolExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tool(mut self, name: &str, tool: ToolFn) -> Self {
+        self.tools.insert(name.to_string(), tool);
+        self
+    }
+
+    /// A tool that asks `client` to carry out the step. A reply starting
+    /// with `FAILED` fails the step, with the rest of the reply as reason.
+    pub fn with_llm_tool(self, name: &str, client: Arc<dyn LLMClient>) -> Self {
+        let tool: ToolFn = Arc::new(move |step, beliefs| {
+            let prompt = format!(
+                "Carry out this plan step and reply DONE, or FAILED: <reason>.\nStep: {}\nState: {}",
+                step.summary,
+                serde_json::to_string(beliefs).unwrap_or_default()
+            );
+            let reply = client.generate_response(&prompt);
+            match reply.trim().strip_prefix("FAILED") {
+                Some(reason) => Err(reason.trim_start_matches(':').trim().to_string()),
+                None => Ok(()),
+            }
+        });
+        self.with_tool(name, tool)
+    }
+}
+
+impl StepExecutor for ToolExecutor {
+    fn name(&self) -> &str {
+        "tool"
+    }
+
+    fn handles(&self, step: &TaskNode) -> bool {
+        self.tools.contains_key(verb(step))
+    }
+
+    fn execute(&self, step: &TaskNode, beliefs: &HashMap<String, String>) -> StepOutcome {
+        match self.tools.get(verb(step)) {
+            Some(tool) => match tool(step, beliefs) {
+                Ok(()) => StepOutcome::Completed,
+                Err(reason) => StepOutcome::Failed(reason),
+            },
+            None => StepOutcome::Fatal(format!("unknown tool `{}`", verb(step))),
+        }
+    }
+}
+
+// ─── Policies and outcomes ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureAction {
+    /// Mark the remaining steps aborted and pop the goal.
+    AbortGoal,
+    /// Mark the step aborted and continue with the next one.
+    SkipStep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Attempts per step, including the first.
+    pub max_attempts: u32,
+    pub on_exhausted: FailureAction,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            on_exhausted: FailureAction::AbortGoal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TickOutcome {
+    /// Nothing runnable on top of the stack.
+    Idle,
+    StepCompleted {
+        goal: Uuid,
+        step: Uuid,
+    },
+    StepRetrying {
+        goal: Uuid,
+        step: Uuid,
+        attempt: u32,
+        reason: String,
+    },
+    StepSkipped {
+        goal: Uuid,
+        step: Uuid,
+        reason: String,
+    },
+    GoalCompleted(Uuid),
+    GoalAborted {
+        goal: Uuid,
+        reason: String,
+    },
+}
+
+// ─── Checkpoint payload ──────────────────────────────────────────────────────
+
+/// A task graph as node list plus index edges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GraphSnapshot {
+    nodes: Vec<TaskNode>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl GraphSnapshot {
+    fn of(graph: &TaskGraph) -> Self {
+        Self {
+            nodes: graph.graph.node_weights().cloned().collect(),
+            edges: graph
+                .graph
+                .edge_indices()
+                .filter_map(|e| graph.graph.edge_endpoints(e))
+                .map(|(a, b)| (a.index(), b.index()))
+                .collect(),
+        }
+    }
+
+    fn restore(self) -> TaskGraph {
+        let mut graph = TaskGraph::new();
+        let indices: Vec<_> = self
+            .nodes
+            .into_iter()
+            .map(|node| {
+                let id = node.id;
+                let idx = graph.graph.add_node(node);
+                graph.node_index_map.insert(id, idx);
+                idx
+            })
+            .collect();
+        for (a, b) in self.edges {
+            if let (Some(&a), Some(&b)) = (indices.get(a), indices.get(b)) {
+                graph.graph.add_edge(a, b, ());
+            }
+        }
+        graph
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExecutiveSnapshot {
+    priorities: HashMap<Uuid, Priority>,
+    graphs: HashMap<Uuid, GraphSnapshot>,
+    attempts: HashMap<Uuid, u32>,
+}
+
+/// `ContinuationCheckpoint::metadata` key holding the `ExecutiveSnapshot`.
+const SNAPSHOT_KEY: &str = "executive";
+
+// ─── Scheduler ───────────────────────────────────────────────────────────────
+
 pub struct ExecutiveScheduler {
+    /// Bottom to top; the top frame runs. Kept sorted by priority.
     pub goal_stack: Vec<StackFrame>,
+    /// Graph of the running goal (or of the last one to finish).
     pub active_graph: Option<TaskGraph>,
     pub max_depth: usize,
+    pub retry_policy: RetryPolicy,
+    /// Checkpoints are stored under this id; pass it to `resume`.
+    pub executive_id: Uuid,
+    executors: Vec<Arc<dyn StepExecutor>>,
+    active_goal: Option<Uuid>,
+    /// Graphs of preempted goals.
+    parked: HashMap<Uuid, TaskGraph>,
+    priorities: HashMap<Uuid, Priority>,
+    /// Failed attempts of each step so far.
+    attempts: HashMap<Uuid, u32>,
+    session_id: Uuid,
+    sequence: u64,
 }
 
 impl ExecutiveScheduler {
@@ -24,6 +329,104 @@ impl ExecutiveScheduler {
             goal_stack: Vec::new(),
             active_graph: None,
             max_depth,
+            retry_policy: RetryPolicy::default(),
+            executive_id: Uuid::new_v4(),
+            executors: Vec::new(),
+            active_goal: None,
+            parked: HashMap::new(),
+            priorities: HashMap::new(),
+            attempts: HashMap::new(),
+            session_id: Uuid::new_v4(),
+            sequence: 0,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Executors are consulted in registration order. With none registered
+    /// steps complete as soon as they are dispatched (a dry run).
+    pub fn register_executor(&mut self, executor: Arc<dyn StepExecutor>) {
+        self.executors.push(executor);
+    }
+
+    /// Make `graph` the active plan and push a frame for `goal_id` starting
+    /// at its first step.
+    pub fn adopt_plan(&mut self, graph: TaskGraph, goal_id: Uuid) -> Result<(), String> {
+        self.submit(graph, goal_id, Priority::Normal).map(|_| ())
+    }
+
+    /// Queue a plan by priority: above every goal of lower priority, below
+    /// those of equal or higher priority so peers are not interrupted.
+    /// Returns whether it preempted the running goal.
+    pub fn submit(
+        &mut self,
+        graph: TaskGraph,
+        goal_id: Uuid,
+        priority: Priority,
+    ) -> Result<bool, String> {
+        if self.goal_stack.len() >= self.max_depth {
+            return Err(format!("goal stack full (max depth {})", self.max_depth));
+        }
+        let Some(first) = graph.graph.node_indices().next().map(|i| graph.graph[i].id) else {
+            return Ok(false);
+        };
+        let pos = self
+            .goal_stack
+            .iter()
+            .position(|f| self.priority_of(f.task_id) >= priority)
+            .unwrap_or(self.goal_stack.len());
+        let preempts = pos == self.goal_stack.len();
+        self.goal_stack.insert(
+            pos,
+            StackFrame {
+                task_id: goal_id,
+                local_beliefs: HashMap::new(),
+                current_step: Some(first),
+            },
+        );
+        self.priorities.insert(goal_id, priority);
+        self.parked.insert(goal_id, graph);
+        self.activate_top();
+        Ok(preempts)
+    }
+
+    fn priority_of(&self, goal: Uuid) -> Priority {
+        self.priorities.get(&goal).copied().unwrap_or_default()
+    }
+
+    /// Swap the top frame's graph in as `active_graph`, parking the current
+    /// one if its goal is still on the stack.
+    fn activate_top(&mut self) {
+        let Some(top) = self.goal_stack.last().map(|f| f.task_id) else {
+            return;
+        };
+        if self.active_goal == Some(top) {
+            return;
+        }
+        match self.parked.remove(&top) {
+            Some(graph) => {
+                if let Some(prev) = self.active_goal {
+                    if let Some(old) = self.active_graph.take() {
+                        self.parked.insert(prev, old);
+                    }
+                }
+                self.active_graph = Some(graph);
+                self.active_goal = Some(top);
+            }
+            // A frame pushed directly onto the stack runs `active_graph`.
+            None if self.active_goal.is_none() => self.active_goal = Some(top),
+            None => {}
+        }
+    }
+
+    fn diagnostic_frame(reason: String) -> StackFrame {
+        StackFrame {
+            task_id: Uuid::new_v4(),
+            local_beliefs: HashMap::from([("diagnostic_reason".to_string(), reason)]),
+            current_step: None,
         }
     }
 
@@ -32,69 +435,275 @@ impl ExecutiveScheduler {
         &mut self,
         self_model: &SelfModel,
         coherence: &CoherenceChecker,
-    ) -> Result<(), String> {
+    ) -> Result<TickOutcome, String> {
         // Step 1: Health & Invariant pre-check
         if !self_model.is_healthy().unwrap_or(false) {
             // Push pre-emptive Diagnostics Goal Frame
-            self.goal_stack.push(StackFrame {
-                task_id: Uuid::new_v4(),
-                local_beliefs: {
-                    let mut b = HashMap::new();
-                    b.insert(
-                        "diagnostic_reason".to_string(),
-                        "self_model_unhealthy".to_string(),
-                    );
-                    b
-                },
-                current_step: None,
-            });
+            self.goal_stack
+                .push(Self::diagnostic_frame("self_model_unhealthy".to_string()));
             return Err("System unhealthy: pushing Diagnostic interrupt frame".to_string());
         }
 
         // Step 2: Coherence Write gating check
         if let Err(e) = coherence.gate_write("executive_scheduler::tick") {
-            self.goal_stack.push(StackFrame {
-                task_id: Uuid::new_v4(),
-                local_beliefs: {
-                    let mut b = HashMap::new();
-                    b.insert(
-                        "diagnostic_reason".to_string(),
-                        format!("coherence_gate_failed: {}", e.reason),
-                    );
-                    b
-                },
-                current_step: None,
-            });
+            self.goal_stack.push(Self::diagnostic_frame(format!(
+                "coherence_gate_failed: {}",
+                e.reason
+            )));
             return Err(format!(
                 "Coherence gate rejected write operations: {}",
                 e.reason
             ));
         }
 
+        // Both gates passed: diagnostic interrupts are resolved.
+        while self.goal_stack.last().is_some_and(|f| {
+            f.current_step.is_none() && f.local_beliefs.contains_key("diagnostic_reason")
+        }) {
+            self.goal_stack.pop();
+        }
+        self.activate_top();
+
         // Step 3: Execute active task step
-        if let Some(frame) = self.goal_stack.last_mut() {
-            if let Some(ref mut task_graph) = self.active_graph {
-                if let Some(node_idx) = frame
-                    .current_step
-                    .and_then(|id| task_graph.node_index_map.get(&id))
-                {
-                    let task = &mut task_graph.graph[*node_idx];
-                    task.state = TaskState::Active;
-
-                    // Execute step
-                    task.state = TaskState::Completed;
-
-                    // Topologically retrieve next step
-                    let mut outgoing = task_graph.graph.neighbors(*node_idx);
-                    if let Some(next_idx) = outgoing.next() {
-                        frame.current_step = Some(task_graph.graph[next_idx].id);
-                    } else {
-                        // Pop completed goal frame
-                        self.goal_stack.pop();
-                    }
+        let Some(frame) = self.goal_stack.last() else {
+            return Ok(TickOutcome::Idle);
+        };
+        let (goal, beliefs) = (frame.task_id, frame.local_beliefs.clone());
+        let Some(step_id) = frame.current_step else {
+            return Ok(TickOutcome::Idle);
+        };
+        let Some(node_idx) = self
+            .active_graph
+            .as_ref()
+            .and_then(|g| g.node_index_map.get(&step_id).copied())
+        else {
+            return Ok(TickOutcome::Idle);
+        };
+        let step = {
+            let graph = self.active_graph.as_mut().unwrap();
+            graph.graph[node_idx].state = TaskState::Active;
+            graph.graph[node_idx].clone()
+        };
+
+        let outcome = match self.executors.iter().find(|e| e.handles(&step)) {
+            Some(executor) => executor.execute(&step, &beliefs),
+            None if self.executors.is_empty() => StepOutcome::Completed,
+            None => StepOutcome::Fatal(format!("no executor handles `{}`", step.summary)),
+        };
+
+        let reason = match outcome {
+            StepOutcome::Completed => {
+                self.attempts.remove(&step_id);
+                if let Some(frame) = self.goal_stack.last_mut() {
+                    frame.local_beliefs.extend(step.effects.clone());
                 }
+                return Ok(self.finish_step(goal, node_idx, TaskState::Completed));
+            }
+            StepOutcome::Failed(reason) => {
+                let attempt = self.attempts.entry(step_id).or_insert(0);
+                *attempt += 1;
+                if *attempt < self.retry_policy.max_attempts {
+                    let attempt = *attempt;
+                    self.active_graph.as_mut().unwrap().graph[node_idx].state = TaskState::Pending;
+                    return Ok(TickOutcome::StepRetrying {
+                        goal,
+                        step: step_id,
+                        attempt,
+                        reason,
+                    });
+                }
+                reason
+            }
+            StepOutcome::Fatal(reason) => reason,
+        };
+
+        self.attempts.remove(&step_id);
+        match self.retry_policy.on_exhausted {
+            FailureAction::SkipStep => match self.finish_step(goal, node_idx, TaskState::Aborted) {
+                TickOutcome::StepCompleted { .. } => Ok(TickOutcome::StepSkipped {
+                    goal,
+                    step: step_id,
+                    reason,
+                }),
+                done => Ok(done),
+            },
+            FailureAction::AbortGoal => {
+                let graph = self.active_graph.as_mut().unwrap();
+                let mut next = Some(node_idx);
+                while let Some(idx) = next {
+                    graph.graph[idx].state = TaskState::Aborted;
+                    next = graph.graph.neighbors(idx).next();
+                }
+                self.pop_goal();
+                Ok(TickOutcome::GoalAborted { goal, reason })
+            }
+        }
+    }
+
+    /// Close the step at `node_idx` with `state` and move the frame on.
+    fn finish_step(
+        &mut self,
+        goal: Uuid,
+        node_idx: petgraph::graph::NodeIndex,
+        state: TaskState,
+    ) -> TickOutcome {
+        let graph = self.active_graph.as_mut().unwrap();
+        graph.graph[node_idx].state = state;
+        let step = graph.graph[node_idx].id;
+        // Topologically retrieve next step
+        match graph.graph.neighbors(node_idx).next() {
+            Some(next_idx) => {
+                let next = graph.graph[next_idx].id;
+                if let Some(frame) = self.goal_stack.last_mut() {
+                    frame.current_step = Some(next);
+                }
+                TickOutcome::StepCompleted { goal, step }
+            }
+            None => {
+                // Pop completed goal frame
+                self.pop_goal();
+                TickOutcome::GoalCompleted(goal)
             }
         }
-        Ok(())
+    }
+
+    fn pop_goal(&mut self) {
+        if let Some(frame) = self.goal_stack.pop() {
+            self.priorities.remove(&frame.task_id);
+        }
+        self.active_goal = None;
+        self.activate_top();
+    }
+
+    // ─── Durability ──────────────────────────────────────────────────────────
+
+    /// `tick`, then checkpoint into `store` and update the goal's `Goal`
+    /// record (from `Plan::persist`) if anything happened.
+    pub fn run_tick<B: MemoryBackend>(
+        &mut self,
+        self_model: &SelfModel,
+        coherence: &CoherenceChecker,
+        store: &mut MemoryStore<B>,
+    ) -> Result<TickOutcome, String> {
+        let outcome = self.tick(self_model, coherence)?;
+        let status = match &outcome {
+            TickOutcome::Idle => return Ok(outcome),
+            TickOutcome::GoalCompleted(goal) => Some((*goal, GoalStatus::Succeeded)),
+            TickOutcome::GoalAborted { goal, .. } => Some((*goal, GoalStatus::Failed)),
+            TickOutcome::StepCompleted { goal, .. }
+            | TickOutcome::StepRetrying { goal, .. }
+            | TickOutcome::StepSkipped { goal, .. } => Some((*goal, GoalStatus::InProgress)),
+        };
+        if let Some((goal, status)) = status {
+            Self::sync_goal_record(store, goal, status)?;
+        }
+        self.checkpoint(store)?;
+        Ok(outcome)
+    }
+
+    fn sync_goal_record<B: MemoryBackend>(
+        store: &mut MemoryStore<B>,
+        goal: Uuid,
+        status: GoalStatus,
+    ) -> Result<(), String> {
+        let Some(rec) = store
+            .find_by_id(goal)
+            .filter(|r| r.record_type == MemoryType::Goal)
+        else {
+            return Ok(());
+        };
+        let mut meta = rec.metadata.clone();
+        let Ok(mut payload) = serde_json::from_value::<GoalPayload>(meta.clone()) else {
+            return Ok(());
+        };
+        if payload.status == status {
+            return Ok(());
+        }
+        payload.status = status;
+        meta["status"] = serde_json::to_value(&payload.status).map_err(|e| e.to_string())?;
+        store
+            .update_record(goal, None, None, None, None, Some(meta))
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Persist the goal stack, every goal's graph and the retry counters as
+    /// a `ContinuationCheckpoint`, replacing the previous one. Returns the
+    /// record id.
+    pub fn checkpoint<B: MemoryBackend>(
+        &mut self,
+        store: &mut MemoryStore<B>,
+    ) -> Result<String, String> {
+        let mut graphs: HashMap<Uuid, GraphSnapshot> = self
+            .parked
+            .iter()
+            .map(|(goal, graph)| (*goal, GraphSnapshot::of(graph)))
+            .collect();
+        if let (Some(goal), Some(graph)) = (self.active_goal, &self.active_graph) {
+            graphs.insert(goal, GraphSnapshot::of(graph));
+        }
+        let snapshot = ExecutiveSnapshot {
+            priorities: self.priorities.clone(),
+            graphs,
+            attempts: self.attempts.clone(),
+        };
+        self.sequence += 1;
+        let mut cp = ContinuationCheckpoint::new(
+            self.executive_id,
+            "executive goal stack".to_string(),
+            self.session_id,
+            self.sequence,
+        )
+        .with_metadata(
+            SNAPSHOT_KEY.to_string(),
+            serde_json::to_value(&snapshot).map_err(|e| e.to_string())?,
+        );
+        if let Some(step) = self.current_step() {
+            cp.set_next_step(step.summary.clone());
+        }
+        cp.replace_in_store(store, &self.goal_stack)
+    }
+
+    /// The step the top frame will run next.
+    pub fn current_step(&self) -> Option<&TaskNode> {
+        let step = self.goal_stack.last()?.current_step?;
+        let graph = self.active_graph.as_ref()?;
+        graph.node_index_map.get(&step).map(|i| &graph.graph[*i])
+    }
+
+    /// Rebuild the scheduler `executive_id` from its newest checkpoint in
+    /// `store` (an empty scheduler if it has none). Executors are not
+    /// persisted; register them again before ticking.
+    pub fn resume<B: MemoryBackend>(
+        store: &MemoryStore<B>,
+        executive_id: Uuid,
+        max_depth: usize,
+    ) -> Result<Self, String> {
+        let mut scheduler = Self::new(max_depth);
+        scheduler.executive_id = executive_id;
+        let Some(data) = ContinuationCheckpoint::latest_in_store(store, executive_id) else {
+            return Ok(scheduler);
+        };
+        scheduler.sequence = data.sequence_number;
+        scheduler.goal_stack = data
+            .goal_stack_serialized
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("corrupt goal stack in checkpoint: {e}"))?;
+        let snapshot: ExecutiveSnapshot = match data.metadata.get(SNAPSHOT_KEY) {
+            Some(v) => serde_json::from_value(v.clone())
+                .map_err(|e| format!("corrupt executive snapshot: {e}"))?,
+            None => ExecutiveSnapshot::default(),
+        };
+        scheduler.priorities = snapshot.priorities;
+        scheduler.attempts = snapshot.attempts;
+        scheduler.parked = snapshot
+            .graphs
+            .into_iter()
+            .map(|(goal, graph)| (goal, graph.restore()))
+            .collect();
+        scheduler.activate_top();
+        Ok(scheduler)
     }
 }
@@ -1,8 +1,29 @@
+//! Task graph — plans over string key/value beliefs, as chains of `TaskNode`s.
+//!
+//! Chain-of-thought: an action is a `TaskNode` whose preconditions must all
+//! hold (`beliefs[k] == v`) and whose effects overwrite beliefs. `Planner`
+//! runs A* over belief states with a binary-heap open set and a closed set;
+//! the heuristic is h_max (the cost of the dearest goal fact in the
+//! delete-relaxed problem), which never overestimates, so the first plan
+//! popped is optimal. Plans can be checked against a `MetaLawEngine` (belief
+//! keys mapped to metric indices) and persisted as one `Goal` record plus a
+//! `Skill` record per step, from which `ExecutiveScheduler` reloads the
+//! graph. PDDL input lives in `pddl`, method decomposition in `htn`.
+
+use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_store::MemoryStore;
+use crate::payloads::{GoalPayload, GoalStatus, SkillPayload};
+use crate::persistence::MemoryBackend;
+use crate::world_model_enhanced::{EntityState, MetaLawEngine};
 use petgraph::graph::{DiGraph, NodeIndex};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
 use uuid::Uuid;
 
+/// Belief state as searched; ordered so it can be hashed into the closed set.
+pub(crate) type State = BTreeMap<String, String>;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TaskState {
     Pending,
@@ -21,6 +42,43 @@ pub struct TaskNode {
     pub cost: f64,
 }
 
+impl TaskNode {
+    pub fn new(summary: &str, cost: f64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            summary: summary.to_string(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            state: TaskState::Pending,
+            cost,
+        }
+    }
+
+    pub fn with_precondition(mut self, key: &str, value: &str) -> Self {
+        self.preconditions.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_effect(mut self, key: &str, value: &str) -> Self {
+        self.effects.insert(key.into(), value.into());
+        self
+    }
+
+    pub(crate) fn is_applicable(&self, state: &State) -> bool {
+        self.preconditions
+            .iter()
+            .all(|(k, v)| state.get(k) == Some(v))
+    }
+
+    pub(crate) fn apply(&self, state: &State) -> State {
+        let mut next = state.clone();
+        for (k, v) in &self.effects {
+            next.insert(k.clone(), v.clone());
+        }
+        next
+    }
+}
+
 #[derive(Debug)]
 pub struct TaskGraph {
     pub graph: DiGraph<TaskNode, ()>,
@@ -33,14 +91,6 @@ impl Default for TaskGraph {
     }
 }
 
-#[derive(Clone)]
-struct PlanningState {
-    current_beliefs: HashMap<String, String>,
-    path_cost: f64,
-    unmet_goals_count: usize,
-    planned_actions: Vec<TaskNode>,
-}
-
 impl TaskGraph {
     pub fn new() -> Self {
         Self {
@@ -49,104 +99,437 @@ impl TaskGraph {
         }
     }
 
-    /// Solve state-space using A* search from current state to goal state.
+    /// Steps chained in execution order.
+    pub fn from_steps(steps: Vec<TaskNode>) -> Self {
+        let mut task_graph = TaskGraph::new();
+        let mut prev_idx: Option<NodeIndex> = None;
+        for task in steps {
+            let id = task.id;
+            let idx = task_graph.graph.add_node(task);
+            task_graph.node_index_map.insert(id, idx);
+            if let Some(p) = prev_idx {
+                task_graph.graph.add_edge(p, idx, ());
+            }
+            prev_idx = Some(idx);
+        }
+        task_graph
+    }
+
+    /// Optimal plan from `current_state` to `goal_state` as a chained graph.
     pub fn solve_planning_problem(
         current_state: HashMap<String, String>,
         goal_state: HashMap<String, String>,
         available_actions: &[TaskNode],
     ) -> Result<Self, String> {
-        let initial_unmet = goal_state
+        Planner::new(available_actions.to_vec())
+            .search(&current_state, &goal_state)
+            .map(Plan::into_graph)
+    }
+
+    /// Rebuild the graph of a plan stored by `Plan::persist`.
+    pub fn load_plan<B: MemoryBackend>(
+        store: &MemoryStore<B>,
+        goal_id: Uuid,
+    ) -> Result<Self, String> {
+        let goal = store
+            .find_by_id(goal_id)
+            .filter(|r| r.record_type == MemoryType::Goal)
+            .ok_or_else(|| format!("goal {goal_id} not found"))?;
+        let steps = goal
+            .evidence
             .iter()
-            .filter(|(gk, gv)| current_state.get(*gk) != Some(*gv))
-            .count();
-
-        let mut open_set = vec![PlanningState {
-            current_beliefs: current_state.clone(),
-            path_cost: 0.0,
-            unmet_goals_count: initial_unmet,
-            planned_actions: Vec::new(),
-        }];
+            .map(|id| {
+                let rec = store
+                    .find_by_id(*id)
+                    .ok_or_else(|| format!("plan step {id} not found"))?;
+                serde_json::from_value::<TaskNode>(rec.metadata["task"].clone())
+                    .map_err(|e| format!("plan step {id}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_steps(steps))
+    }
+}
+
+// ─── Plans ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub steps: Vec<TaskNode>,
+    pub cost: f64,
+    /// Search nodes expanded (A*) or decompositions tried (HTN).
+    pub expanded: usize,
+}
+
+/// Ids of the records written by `Plan::persist`.
+#[derive(Debug, Clone)]
+pub struct PersistedPlan {
+    pub goal_id: Uuid,
+    pub step_ids: Vec<Uuid>,
+}
+
+impl Plan {
+    pub fn into_graph(self) -> TaskGraph {
+        TaskGraph::from_steps(self.steps)
+    }
+
+    /// Store the plan as a pending `Goal` record whose evidence lists one
+    /// `Skill` record per step, in order. Each skill carries its `TaskNode`
+    /// under `metadata.task`.
+    pub fn persist<B: MemoryBackend>(
+        &self,
+        store: &mut MemoryStore<B>,
+        actor: &str,
+        goal: &HashMap<String, String>,
+    ) -> Result<PersistedPlan, String> {
+        let criteria = facts(goal);
+        let payload = GoalPayload {
+            target_state: criteria.join(", "),
+            acceptance_criteria: criteria,
+            success_factors: vec![],
+            max_react_iterations: self.steps.len() as u32,
+            status: GoalStatus::Pending,
+            current_iteration: 0,
+        };
+        let mut goal_meta = serde_json::to_value(&payload).map_err(|e| e.to_string())?;
+        goal_meta["plan_cost"] = serde_json::json!(self.cost);
+        let mut goal_rec = MemoryRecord::new(
+            MemoryType::Goal,
+            actor.into(),
+            "plan".into(),
+            payload.target_state.clone(),
+            goal_meta,
+        );
+
+        let mut skills = Vec::with_capacity(self.steps.len());
+        for (i, step) in self.steps.iter().enumerate() {
+            let payload = SkillPayload {
+                procedure: step.summary.clone(),
+                preconditions: facts(&step.preconditions),
+                expected_outcomes: facts(&step.effects),
+            };
+            let mut meta = serde_json::to_value(&payload).map_err(|e| e.to_string())?;
+            meta["task"] = serde_json::to_value(step).map_err(|e| e.to_string())?;
+            meta["step"] = serde_json::json!(i);
+            let mut rec = MemoryRecord::new(
+                MemoryType::Skill,
+                actor.into(),
+                "plan_step".into(),
+                step.summary.clone(),
+                meta,
+            );
+            rec.derived_from = Some(goal_rec.id);
+            skills.push(rec);
+        }
+        goal_rec.evidence = skills.iter().map(|r| r.id).collect();
+
+        let persisted = PersistedPlan {
+            goal_id: goal_rec.id,
+            step_ids: goal_rec.evidence.clone(),
+        };
+        for rec in std::iter::once(goal_rec).chain(skills) {
+            store.add(rec).map_err(|e| e.to_string())?;
+        }
+        Ok(persisted)
+    }
+}
+
+/// `k=v` strings, sorted for stable records.
+fn facts(map: &HashMap<String, String>) -> Vec<String> {
+    let mut out: Vec<String> = map.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    out.sort();
+    out
+}
+
+// ─── Planner ─────────────────────────────────────────────────────────────────
+
+/// A* planner over a fixed action set, optionally constrained by meta-laws.
+pub struct Planner {
+    pub actions: Vec<TaskNode>,
+    /// Nodes expanded before giving up.
+    pub max_expansions: usize,
+    laws: MetaLawEngine,
+    /// Belief keys read (as numbers) into `EntityState::properties`; index i
+    /// is the law `metric_index` i.
+    metric_keys: Vec<String>,
+}
+
+struct SearchNode {
+    state: State,
+    parent: Option<usize>,
+    action: Option<usize>,
+    g: f64,
+}
+
+/// Open-set entry ordered so `BinaryHeap` pops the lowest f first, breaking
+/// ties towards deeper (higher g) nodes.
+struct OpenEntry {
+    f: f64,
+    g: f64,
+    node: usize,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f).then(self.g.total_cmp(&other.g))
+    }
+}
+
+impl Planner {
+    pub fn new(actions: Vec<TaskNode>) -> Self {
+        Self {
+            actions,
+            max_expansions: 100_000,
+            laws: MetaLawEngine::new(),
+            metric_keys: Vec::new(),
+        }
+    }
+
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = max_expansions;
+        self
+    }
 
-        let mut max_iterations = 1000;
+    /// Reject transitions (in search and `validate`) that break `laws`,
+    /// reading metric i from belief `metric_keys[i]`.
+    pub fn with_laws(mut self, laws: MetaLawEngine, metric_keys: Vec<String>) -> Self {
+        self.laws = laws;
+        self.metric_keys = metric_keys;
+        self
+    }
 
-        while let Some(best_idx) = open_set
+    /// Cheapest plan reaching `goal`.
+    pub fn search(
+        &self,
+        initial: &HashMap<String, String>,
+        goal: &HashMap<String, String>,
+    ) -> Result<Plan, String> {
+        if let Some(a) = self
+            .actions
             .iter()
-            .enumerate()
-            .min_by(|(_, a), (_, b)| {
-                let score_a = a.path_cost + a.unmet_goals_count as f64;
-                let score_b = b.path_cost + b.unmet_goals_count as f64;
-                score_a
-                    .partial_cmp(&score_b)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            })
-            .map(|(i, _)| i)
+            .find(|a| a.cost.is_nan() || a.cost < 0.0)
         {
-            if max_iterations == 0 {
-                return Err("Planning iteration limit exceeded".to_string());
-            }
-            max_iterations -= 1;
+            return Err(format!(
+                "action `{}` has cost {}; costs must be non-negative",
+                a.summary, a.cost
+            ));
+        }
+        let goal: State = goal.clone().into_iter().collect();
+        let start: State = initial.clone().into_iter().collect();
+        let Some(h0) = self.h_max(&start, &goal) else {
+            return Err("No planning path found".to_string());
+        };
 
-            let current = open_set.remove(best_idx);
+        let mut nodes = vec![SearchNode {
+            state: start.clone(),
+            parent: None,
+            action: None,
+            g: 0.0,
+        }];
+        let mut best_g: HashMap<State, f64> = HashMap::from([(start, 0.0)]);
+        let mut closed: HashSet<State> = HashSet::new();
+        let mut open = BinaryHeap::from([OpenEntry {
+            f: h0,
+            g: 0.0,
+            node: 0,
+        }]);
+        let mut expanded = 0;
 
-            // Check goal condition
-            let mut is_goal = true;
-            for (gk, gv) in &goal_state {
-                if current.current_beliefs.get(gk) != Some(gv) {
-                    is_goal = false;
-                    break;
-                }
+        while let Some(OpenEntry { node, g, .. }) = open.pop() {
+            if closed.contains(&nodes[node].state) {
+                continue;
             }
+            if satisfies(&nodes[node].state, &goal) {
+                return Ok(self.extract(&nodes, node, expanded));
+            }
+            if expanded >= self.max_expansions {
+                return Err(format!(
+                    "Planning expansion limit ({}) exceeded",
+                    self.max_expansions
+                ));
+            }
+            expanded += 1;
+            let state = nodes[node].state.clone();
+            closed.insert(state.clone());
 
-            if is_goal {
-                let mut task_graph = TaskGraph::new();
-                let mut prev_idx: Option<NodeIndex> = None;
-                for task in current.planned_actions {
-                    let idx = task_graph.graph.add_node(task.clone());
-                    task_graph.node_index_map.insert(task.id, idx);
-                    if let Some(p) = prev_idx {
-                        task_graph.graph.add_edge(p, idx, ());
-                    }
-                    prev_idx = Some(idx);
+            for (ai, action) in self.actions.iter().enumerate() {
+                if !action.is_applicable(&state) {
+                    continue;
+                }
+                let next = action.apply(&state);
+                if closed.contains(&next) || self.law_violations(&state, &next).is_some() {
+                    continue;
                 }
-                return Ok(task_graph);
+                let next_g = g + action.cost;
+                if best_g.get(&next).is_some_and(|&old| old <= next_g) {
+                    continue;
+                }
+                let Some(h) = self.h_max(&next, &goal) else {
+                    continue;
+                };
+                best_g.insert(next.clone(), next_g);
+                nodes.push(SearchNode {
+                    state: next,
+                    parent: Some(node),
+                    action: Some(ai),
+                    g: next_g,
+                });
+                open.push(OpenEntry {
+                    f: next_g + h,
+                    g: next_g,
+                    node: nodes.len() - 1,
+                });
             }
+        }
 
-            // Expand states
-            for action in available_actions {
-                let mut preconditions_met = true;
-                for (pk, pv) in &action.preconditions {
-                    if current.current_beliefs.get(pk) != Some(pv) {
-                        preconditions_met = false;
-                        break;
-                    }
-                }
+        Err("No planning path found".to_string())
+    }
 
-                if preconditions_met {
-                    let mut next_beliefs = current.current_beliefs.clone();
-                    for (ek, ev) in &action.effects {
-                        next_beliefs.insert(ek.clone(), ev.clone());
-                    }
+    fn extract(&self, nodes: &[SearchNode], mut node: usize, expanded: usize) -> Plan {
+        let cost = nodes[node].g;
+        let mut steps = Vec::new();
+        while let (Some(parent), Some(action)) = (nodes[node].parent, nodes[node].action) {
+            let mut step = self.actions[action].clone();
+            // Repeated actions become distinct graph nodes.
+            step.id = Uuid::new_v4();
+            steps.push(step);
+            node = parent;
+        }
+        steps.reverse();
+        Plan {
+            steps,
+            cost,
+            expanded,
+        }
+    }
 
-                    let mut unmet = 0;
-                    for (gk, gv) in &goal_state {
-                        if next_beliefs.get(gk) != Some(gv) {
-                            unmet += 1;
-                        }
+    /// h_max: the cost of the dearest goal fact when every action's other
+    /// effects are ignored. `None` if some goal fact is unreachable.
+    fn h_max(&self, state: &State, goal: &State) -> Option<f64> {
+        let mut cost: HashMap<(&str, &str), f64> = state
+            .iter()
+            .map(|(k, v)| ((k.as_str(), v.as_str()), 0.0))
+            .collect();
+        loop {
+            let mut changed = false;
+            for action in &self.actions {
+                let pre = action
+                    .preconditions
+                    .iter()
+                    .map(|(k, v)| cost.get(&(k.as_str(), v.as_str())).copied())
+                    .try_fold(0.0f64, |acc, c| c.map(|c| acc.max(c)));
+                let Some(pre) = pre else { continue };
+                let reached = pre + action.cost;
+                for (k, v) in &action.effects {
+                    let entry = cost
+                        .entry((k.as_str(), v.as_str()))
+                        .or_insert(f64::INFINITY);
+                    if reached < *entry {
+                        *entry = reached;
+                        changed = true;
                     }
-
-                    let mut next_actions = current.planned_actions.clone();
-                    next_actions.push(action.clone());
-
-                    open_set.push(PlanningState {
-                        current_beliefs: next_beliefs,
-                        path_cost: current.path_cost + action.cost,
-                        unmet_goals_count: unmet,
-                        planned_actions: next_actions,
-                    });
                 }
             }
+            if !changed {
+                break;
+            }
         }
+        goal.iter().try_fold(0.0f64, |acc, (k, v)| {
+            cost.get(&(k.as_str(), v.as_str())).map(|c| acc.max(*c))
+        })
+    }
 
-        Err("No planning path found".to_string())
+    fn metrics(&self, state: &State) -> Result<EntityState, String> {
+        let properties = self
+            .metric_keys
+            .iter()
+            .map(|k| {
+                state
+                    .get(k)
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .ok_or_else(|| format!("metric `{k}` is not numeric"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EntityState {
+            properties,
+            covariance: vec![],
+        })
+    }
+
+    /// Laws broken by the transition `pre -> post`, if any.
+    fn law_violations(&self, pre: &State, post: &State) -> Option<Vec<String>> {
+        if self.laws.laws.is_empty() {
+            return None;
+        }
+        let violations = match (self.metrics(pre), self.metrics(post)) {
+            (Ok(a), Ok(b)) => self.laws.verify_transition(&a, &b),
+            (Err(e), _) | (_, Err(e)) => vec![e],
+        };
+        (!violations.is_empty()).then_some(violations)
     }
+
+    /// Replay `steps` from `initial`: every precondition must hold when its
+    /// step runs, no transition may break a law, and `goal` must hold at the
+    /// end. Returns every problem found.
+    pub fn validate(
+        &self,
+        initial: &HashMap<String, String>,
+        goal: &HashMap<String, String>,
+        steps: &[TaskNode],
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut state: State = initial.clone().into_iter().collect();
+        for (i, step) in steps.iter().enumerate() {
+            let mut unmet: Vec<String> = step
+                .preconditions
+                .iter()
+                .filter(|(k, v)| state.get(*k) != Some(*v))
+                .map(|(k, v)| {
+                    format!(
+                        "step {i} `{}`: precondition {k}={v} unmet (is {})",
+                        step.summary,
+                        state.get(k).map_or("unset", String::as_str)
+                    )
+                })
+                .collect();
+            unmet.sort();
+            errors.extend(unmet);
+            let next = step.apply(&state);
+            if let Some(laws) = self.law_violations(&state, &next) {
+                errors.extend(
+                    laws.into_iter()
+                        .map(|l| format!("step {i} `{}`: violates {l}", step.summary)),
+                );
+            }
+            state = next;
+        }
+        let goal: State = goal.clone().into_iter().collect();
+        for (k, v) in &goal {
+            if state.get(k) != Some(v) {
+                errors.push(format!("goal {k}={v} not reached"));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn satisfies(state: &State, goal: &State) -> bool {
+    goal.iter().all(|(k, v)| state.get(k) == Some(v))
 }
@@ -1,7 +1,7 @@
 use crate::aureus_bridge::AureusBridge;
 use crate::llm_clients::LLMClient;
 use crate::mcp_bridge;
-use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_record::{MemoryRecord, MemoryType, Priority};
 use crate::memory_store::MemoryStore;
 use crate::openmanus_bridge;
 use crate::perception_adapter::{Modality, PerceptionSession};
@@ -149,7 +149,7 @@ impl IntegrationLayer {
                                 text.clone(),
                                 serde_json::json!({"source": "agent-auto-ingest"}),
                             );
-                            rec.priority = "low".to_string();
+                            rec.priority = Priority::Low;
                             rec.source = Some("agent-auto-ingest".to_string());
                             rec.tags = p.tags.clone();
                             println!("[IntegrationLayer] auto low-pri Temporal ingest source=agent-auto-ingest actor={}", actor);
@@ -0,0 +1,153 @@
+//! MCP streamable HTTP transport: one `/mcp` endpoint taking JSON-RPC by
+//! POST. Every response is a single JSON body (the server never streams),
+//! so GET, which would open a server-to-client event stream, is refused
+//! with 405 as the spec allows. `initialize` opens a session whose id comes
+//! back in `Mcp-Session-Id` and must accompany every later request; DELETE
+//! ends it. Cross-site browser requests are refused by `Origin` check.
+
+use super::{error_response, McpService, RpcError};
+use crate::persistence::MemoryBackend;
+use axum::body::Bytes;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+pub struct HttpTransport<B: MemoryBackend> {
+    service: McpService<B>,
+    sessions: Mutex<HashSet<String>>,
+    /// Origins accepted besides localhost ones, e.g. `https://app.example`.
+    allowed_origins: Vec<String>,
+}
+
+impl<B: MemoryBackend + Send + Sync + 'static> HttpTransport<B> {
+    pub fn new(service: McpService<B>) -> Self {
+        Self {
+            service,
+            sessions: Mutex::new(HashSet::new()),
+            allowed_origins: Vec::new(),
+        }
+    }
+
+    pub fn with_allowed_origin(mut self, origin: &str) -> Self {
+        self.allowed_origins
+            .push(origin.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn router(self) -> Router {
+        let transport = Arc::new(self);
+        let (on_post, on_delete) = (transport.clone(), transport);
+        Router::new().route(
+            "/mcp",
+            post(move |headers: HeaderMap, body: Bytes| async move {
+                // Tool calls lock the store and do blocking I/O; keep them
+                // off the runtime workers.
+                tokio::task::spawn_blocking(move || on_post.handle_post(&headers, &body))
+                    .await
+                    .unwrap_or_else(|e| {
+                        let msg = format!("mcp request failed: {e}");
+                        (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+                    })
+            })
+            .get(|| async {
+                (
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    [(header::ALLOW, "POST, DELETE")],
+                )
+            })
+            .delete(move |headers: HeaderMap| async move { on_delete.handle_delete(&headers) }),
+        )
+    }
+
+    pub async fn serve(self, addr: SocketAddr) -> anyhow::Result<()> {
+        axum::Server::bind(&addr)
+            .serve(self.router().into_make_service())
+            .await?;
+        Ok(())
+    }
+
+    fn origin_allowed(&self, headers: &HeaderMap) -> bool {
+        let Some(origin) = headers.get(header::ORIGIN).and_then(|o| o.to_str().ok()) else {
+            // Not a browser; nothing to rebind.
+            return true;
+        };
+        let origin = origin.trim_end_matches('/');
+        let host = origin.split("://").nth(1).unwrap_or(origin);
+        let host = match host.strip_prefix('[') {
+            Some(v6) => v6.split(']').next().unwrap_or(v6),
+            None => host.split(':').next().unwrap_or(host),
+        };
+        matches!(host, "localhost" | "127.0.0.1" | "::1")
+            || self.allowed_origins.iter().any(|o| o == origin)
+    }
+
+    fn session<'h>(&self, headers: &'h HeaderMap) -> Option<&'h str> {
+        headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok())
+    }
+
+    fn handle_post(&self, headers: &HeaderMap, body: &[u8]) -> Response {
+        if !self.origin_allowed(headers) {
+            return (StatusCode::FORBIDDEN, "origin not allowed").into_response();
+        }
+        let message: Value = match serde_json::from_slice(body) {
+            Ok(m) => m,
+            Err(e) => {
+                let err = RpcError::new(RpcError::PARSE_ERROR, format!("parse error: {e}"));
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(error_response(Value::Null, err)),
+                )
+                    .into_response();
+            }
+        };
+        let initializing = message.get("method").and_then(Value::as_str) == Some("initialize");
+        if !initializing {
+            match self.session(headers) {
+                None => {
+                    return (StatusCode::BAD_REQUEST, "missing Mcp-Session-Id header")
+                        .into_response()
+                }
+                Some(id) if !self.sessions.lock().is_ok_and(|s| s.contains(id)) => {
+                    return (StatusCode::NOT_FOUND, "unknown or expired session").into_response()
+                }
+                Some(_) => {}
+            }
+        }
+        let Some(reply) = self.service.handle_message(message) else {
+            return StatusCode::ACCEPTED.into_response();
+        };
+        let mut response = Json(&reply).into_response();
+        if initializing && reply.get("result").is_some() {
+            let id = uuid::Uuid::new_v4().to_string();
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                response.headers_mut().insert(SESSION_HEADER, value);
+            }
+            if let Ok(mut sessions) = self.sessions.lock() {
+                sessions.insert(id);
+            }
+        }
+        response
+    }
+
+    fn handle_delete(&self, headers: &HeaderMap) -> StatusCode {
+        if !self.origin_allowed(headers) {
+            return StatusCode::FORBIDDEN;
+        }
+        let removed = match (self.session(headers), self.sessions.lock()) {
+            (Some(id), Ok(mut sessions)) => sessions.remove(id),
+            _ => false,
+        };
+        if removed {
+            StatusCode::OK
+        } else {
+            StatusCode::NOT_FOUND
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! MCP — native Model Context Protocol server (JSON-RPC 2.0).
+//!
+//! Chain-of-thought: the protocol core is transport-agnostic. `McpService`
+//! turns one JSON-RPC message (or batch) into its response and owns the
+//! stores the tools, resources and prompts read from; `serve_stdio` frames
+//! messages as newline-delimited JSON for agents that spawn the server, and
+//! `http::HttpTransport` (behind `web-server`) serves the same service as
+//! MCP streamable HTTP on a single endpoint. Tool failures caused by the
+//! caller (bad ids, refused writes) come back as `isError` tool results so
+//! the model can read and react to them; only protocol faults use JSON-RPC
+//! errors.
+
+#[cfg(feature = "web-server")]
+pub mod http;
+pub mod prompts;
+pub mod resources;
+pub mod tools;
+
+use crate::concurrent_memory_store::{ConcurrentMemoryStore, MemoryStoreGuard};
+use crate::memory_record::MemoryRecord;
+use crate::persistence::MemoryBackend;
+use crate::symbolic_store::{InMemoryGraph, SymbolicStore};
+use crate::topological_memory::CausalTopoGraph;
+use crate::world_model_enhanced::WorldModelEnhanced;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+/// Newest protocol revision spoken; offered when the client asks for one we
+/// do not know.
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+pub const SUPPORTED_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// MCP: `resources/read` of an unknown URI.
+    pub const RESOURCE_NOT_FOUND: i64 = -32002;
+
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(Self::INTERNAL_ERROR, message)
+    }
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": err.code, "message": err.message},
+    })
+}
+
+/// Memory, graph and world-model state exposed over MCP.
+pub struct McpService<B: MemoryBackend> {
+    pub(crate) store: Arc<ConcurrentMemoryStore<B>>,
+    pub(crate) symbolic: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
+    pub(crate) topo: Arc<Mutex<CausalTopoGraph>>,
+    pub(crate) world_model: Arc<WorldModelEnhanced>,
+    /// Actor the `session_recap` prompt and conversation resource default to.
+    pub(crate) actor: String,
+}
+
+impl<B: MemoryBackend> McpService<B> {
+    /// Serve `store` with empty graphs and a fresh world model. Turns on the
+    /// store's safety write screen, as the REST server does.
+    pub fn new(store: Arc<ConcurrentMemoryStore<B>>) -> Self {
+        if let Ok(mut ms) = store.lock() {
+            ms.set_write_screening(true);
+        }
+        Self {
+            store,
+            symbolic: Arc::new(Mutex::new(SymbolicStore::new())),
+            topo: Arc::new(Mutex::new(CausalTopoGraph::new())),
+            world_model: Arc::new(WorldModelEnhanced::new()),
+            actor: "mcp-session".to_string(),
+        }
+    }
+
+    pub fn with_symbolic(mut self, symbolic: Arc<Mutex<SymbolicStore<InMemoryGraph>>>) -> Self {
+        self.symbolic = symbolic;
+        self
+    }
+
+    pub fn with_topo(mut self, topo: Arc<Mutex<CausalTopoGraph>>) -> Self {
+        self.topo = topo;
+        self
+    }
+
+    pub fn with_world_model(mut self, world_model: Arc<WorldModelEnhanced>) -> Self {
+        self.world_model = world_model;
+        self
+    }
+
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
+    }
+
+    pub fn store(&self) -> Arc<ConcurrentMemoryStore<B>> {
+        self.store.clone()
+    }
+
+    pub(crate) fn lock_store(&self) -> Result<MemoryStoreGuard<'_, B>, RpcError> {
+        self.store
+            .lock()
+            .map_err(|e| RpcError::internal(format!("memory store lock: {e}")))
+    }
+
+    /// Handle one line of newline-delimited JSON. `None` when nothing is
+    /// owed back (notifications, blank lines).
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        let response = match serde_json::from_str::<Value>(line) {
+            Ok(message) => self.handle_message(message)?,
+            Err(e) => error_response(
+                Value::Null,
+                RpcError::new(RpcError::PARSE_ERROR, format!("parse error: {e}")),
+            ),
+        };
+        Some(response.to_string())
+    }
+
+    /// Handle a request, notification or batch. `None` when the message
+    /// contained no requests.
+    pub fn handle_message(&self, message: Value) -> Option<Value> {
+        match message {
+            Value::Array(batch) if batch.is_empty() => Some(error_response(
+                Value::Null,
+                RpcError::new(RpcError::INVALID_REQUEST, "empty batch"),
+            )),
+            Value::Array(batch) => {
+                let responses: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|m| self.handle_single(m))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            single => self.handle_single(single),
+        }
+    }
+
+    fn handle_single(&self, message: Value) -> Option<Value> {
+        let Some(obj) = message.as_object() else {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(RpcError::INVALID_REQUEST, "message must be an object"),
+            ));
+        };
+        let id = obj.get("id").cloned();
+        let Some(method) = obj.get("method").and_then(Value::as_str) else {
+            // A response to a server request; this server sends none.
+            return match id {
+                Some(_) if obj.contains_key("result") || obj.contains_key("error") => None,
+                id => Some(error_response(
+                    id.unwrap_or(Value::Null),
+                    RpcError::new(RpcError::INVALID_REQUEST, "missing method"),
+                )),
+            };
+        };
+        let params = obj.get("params").cloned().unwrap_or_else(|| json!({}));
+        let result = self.dispatch(method, &params);
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(err) => error_response(id, err),
+        })
+    }
+
+    fn dispatch(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(self.initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({"tools": tools::definitions()})),
+            "tools/call" => {
+                let name = params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| RpcError::invalid_params("tools/call needs `name`"))?;
+                let args = params
+                    .get("arguments")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+                self.call_tool(name, &args)
+            }
+            "resources/list" => self.list_resources(params),
+            "resources/templates/list" => Ok(json!({"resourceTemplates": resources::templates()})),
+            "resources/read" => {
+                let uri = params
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| RpcError::invalid_params("resources/read needs `uri`"))?;
+                self.read_resource(uri)
+            }
+            "prompts/list" => Ok(json!({"prompts": prompts::definitions()})),
+            "prompts/get" => {
+                let name = params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| RpcError::invalid_params("prompts/get needs `name`"))?;
+                let args = params
+                    .get("arguments")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+                self.get_prompt(name, &args)
+            }
+            m if m.starts_with("notifications/") => Ok(Value::Null),
+            other => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("unknown method `{other}`"),
+            )),
+        }
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(Value::as_str);
+        let version = requested
+            .filter(|v| SUPPORTED_VERSIONS.contains(v))
+            .unwrap_or(PROTOCOL_VERSION);
+        json!({
+            "protocolVersion": version,
+            "capabilities": {
+                "tools": {"listChanged": false},
+                "resources": {"subscribe": false, "listChanged": false},
+                "prompts": {"listChanged": false},
+            },
+            "serverInfo": {"name": "hipcortex", "version": env!("CARGO_PKG_VERSION")},
+            "instructions": "HipCortex is a persistent memory. Call search_memory or get_context \
+                before a task and add_memory to record decisions worth keeping.",
+        })
+    }
+}
+
+/// Serve newline-delimited JSON-RPC from `input` until EOF, writing one
+/// line per response.
+pub fn serve_stdio<B: MemoryBackend, R: BufRead, W: Write>(
+    service: &McpService<B>,
+    input: R,
+    mut output: W,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        if let Some(response) = service.handle_line(&line?) {
+            writeln!(output, "{response}")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// One-line rendering of a record for tool and prompt text.
+pub(crate) fn record_line(r: &MemoryRecord) -> String {
+    format!(
+        "[{}] {} (actor: {}, id: {})",
+        r.action, r.target, r.actor, r.id
+    )
+}
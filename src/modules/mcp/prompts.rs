@@ -0,0 +1,91 @@
+//! MCP prompts: context templates filled from the memory store.
+
+use super::tools::context_block;
+use super::{record_line, McpService, RpcError};
+use crate::persistence::MemoryBackend;
+use serde_json::{json, Value};
+
+/// `prompts/list` entries.
+pub fn definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "recall_context",
+            "description": "Relevant memories for a task, framed as background for the answer",
+            "arguments": [
+                {"name": "query", "description": "The task or question", "required": true},
+                {"name": "actor", "description": "Only use memories of this actor", "required": false},
+            ],
+        }),
+        json!({
+            "name": "session_recap",
+            "description": "What an actor recorded recently, to pick up where a session left off",
+            "arguments": [
+                {"name": "actor", "description": "Actor to recap (defaults to the server's)", "required": false},
+                {"name": "limit", "description": "How many recent memories (default 20)", "required": false},
+            ],
+        }),
+    ]
+}
+
+fn user_message(description: &str, text: String) -> Value {
+    json!({
+        "description": description,
+        "messages": [{"role": "user", "content": {"type": "text", "text": text}}],
+    })
+}
+
+impl<B: MemoryBackend> McpService<B> {
+    pub(crate) fn get_prompt(&self, name: &str, args: &Value) -> Result<Value, RpcError> {
+        // Prompt arguments are strings on the wire.
+        let arg = |key: &str| args.get(key).and_then(Value::as_str);
+        match name {
+            "recall_context" => {
+                let query = arg("query")
+                    .ok_or_else(|| RpcError::invalid_params("recall_context needs `query`"))?;
+                let context =
+                    context_block(&self.store.snapshot(), query, arg("actor"), 10, None);
+                Ok(user_message(
+                    "Memory context",
+                    format!(
+                        "Background from long-term memory:\n\n{context}\n\n\
+                         Use it where it helps with the following, and say so when a memory \
+                         conflicts with what you observe now.\n\n{query}"
+                    ),
+                ))
+            }
+            "session_recap" => {
+                let actor = arg("actor").unwrap_or(&self.actor);
+                let limit = arg("limit").and_then(|l| l.parse().ok()).unwrap_or(20);
+                let ms = self.store.snapshot();
+                let mut records = ms.find_by_actor(actor);
+                records.sort_by_key(|r| r.timestamp);
+                let recent = &records[records.len().saturating_sub(limit)..];
+                let history = if recent.is_empty() {
+                    "(nothing recorded yet)".to_string()
+                } else {
+                    recent
+                        .iter()
+                        .map(|r| {
+                            format!(
+                                "- {} {}",
+                                r.timestamp.format("%Y-%m-%d %H:%M"),
+                                record_line(r)
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                Ok(user_message(
+                    "Session recap",
+                    format!(
+                        "Recent memories of `{actor}`, oldest first:\n\n{history}\n\n\
+                         Summarise where this work stands and what the next step is."
+                    ),
+                ))
+            }
+            other => Err(RpcError::invalid_params(format!(
+                "unknown prompt `{other}`"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! MCP resources: memory records and store, graph and world-model snapshots
+//! under the `hipcortex://` scheme.
+
+use super::{McpService, RpcError};
+use crate::persistence::MemoryBackend;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Records listed per `resources/list` page.
+const PAGE: usize = 100;
+
+const JSON: &str = "application/json";
+
+/// Fixed resources, listed ahead of the records.
+fn snapshots() -> Vec<Value> {
+    [
+        ("hipcortex://stats", "Memory store statistics"),
+        ("hipcortex://graph/snapshot", "Symbolic knowledge graph"),
+        (
+            "hipcortex://worldmodel/snapshot",
+            "World model transitions and causal edges",
+        ),
+    ]
+    .iter()
+    .map(|(uri, name)| json!({"uri": uri, "name": name, "mimeType": JSON}))
+    .collect()
+}
+
+/// `resources/templates/list` entries.
+pub fn templates() -> Vec<Value> {
+    vec![
+        json!({
+            "uriTemplate": "hipcortex://record/{id}",
+            "name": "Memory record",
+            "description": "One memory record by UUID",
+            "mimeType": JSON,
+        }),
+        json!({
+            "uriTemplate": "hipcortex://actor/{actor}",
+            "name": "Actor memories",
+            "description": "Every memory of an actor, oldest first",
+            "mimeType": JSON,
+        }),
+    ]
+}
+
+fn contents(uri: &str, value: &Value) -> Value {
+    json!({"contents": [{
+        "uri": uri,
+        "mimeType": JSON,
+        "text": serde_json::to_string_pretty(value).unwrap_or_default(),
+    }]})
+}
+
+fn not_found(uri: &str) -> RpcError {
+    RpcError::new(
+        RpcError::RESOURCE_NOT_FOUND,
+        format!("resource not found: {uri}"),
+    )
+}
+
+impl<B: MemoryBackend> McpService<B> {
+    /// Snapshots, then one resource per record, paged by `cursor` (the
+    /// offset into the store, as returned in `nextCursor`).
+    pub(crate) fn list_resources(&self, params: &Value) -> Result<Value, RpcError> {
+        let offset = match params.get("cursor").and_then(Value::as_str) {
+            Some(c) => c
+                .parse::<usize>()
+                .map_err(|_| RpcError::invalid_params(format!("bad cursor `{c}`")))?,
+            None => 0,
+        };
+        let ms = self.store.snapshot();
+        let records = ms.all();
+        let mut resources = if offset == 0 { snapshots() } else { vec![] };
+        resources.extend(records.iter().skip(offset).take(PAGE).map(|r| {
+            json!({
+                "uri": format!("hipcortex://record/{}", r.id),
+                "name": format!("[{}] {}", r.action, r.target.chars().take(60).collect::<String>()),
+                "mimeType": JSON,
+            })
+        }));
+        let mut result = json!({"resources": resources});
+        if offset + PAGE < records.len() {
+            result["nextCursor"] = json!((offset + PAGE).to_string());
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn read_resource(&self, uri: &str) -> Result<Value, RpcError> {
+        let value = match uri {
+            "hipcortex://stats" => self.stats().map_err(RpcError::internal)?,
+            "hipcortex://graph/snapshot" => {
+                let graph = self
+                    .symbolic
+                    .lock()
+                    .map_err(|e| RpcError::internal(format!("graph lock: {e}")))?;
+                let (nodes, edges) = graph.export_graph();
+                json!({"nodes": nodes, "edges": edges})
+            }
+            "hipcortex://worldmodel/snapshot" => self
+                .world_model
+                .to_json()
+                .map_err(|e| RpcError::internal(e.to_string()))?,
+            _ => {
+                if let Some(raw) = uri.strip_prefix("hipcortex://record/") {
+                    let id = Uuid::parse_str(raw).map_err(|_| not_found(uri))?;
+                    let ms = self.store.snapshot();
+                    let record = ms.find_by_id(id).ok_or_else(|| not_found(uri))?;
+                    json!(record)
+                } else if let Some(actor) = uri.strip_prefix("hipcortex://actor/") {
+                    let ms = self.store.snapshot();
+                    json!(ms.find_by_actor(actor))
+                } else {
+                    return Err(not_found(uri));
+                }
+            }
+        };
+        Ok(contents(uri, &value))
+    }
+}
@@ -0,0 +1,542 @@
+//! MCP tools: memory add/search/context/forget, graph and world-model
+//! queries. Names and arguments follow the Python bridge in `sdk/mcp` so
+//! agents can switch servers without re-prompting.
+
+use super::{record_line, McpService, RpcError};
+use crate::concurrent_memory_store::MemoryStoreGuard;
+use crate::injection_defense::{
+    assess_batch, source_trust_of, InjectionConfig, InjectionLevel, Spotlight,
+};
+use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_store::MemoryStore;
+use crate::persistence::MemoryBackend;
+use crate::topological_memory::EdgeType;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+fn tool(name: &str, description: &str, required: &[&str], properties: Value) -> Value {
+    json!({
+        "name": name,
+        "description": description,
+        "inputSchema": {"type": "object", "required": required, "properties": properties},
+    })
+}
+
+/// `tools/list` entries.
+pub fn definitions() -> Vec<Value> {
+    let string = |d: &str| json!({"type": "string", "description": d});
+    let limit = |default: u64| json!({"type": "integer", "default": default});
+    vec![
+        tool(
+            "add_memory",
+            "Store a memory record: a decision, fix, pattern or fact worth keeping across sessions.",
+            &["actor", "action", "target"],
+            json!({
+                "actor": string("Scope the memory belongs to, e.g. a project or user id"),
+                "action": string("What happened, e.g. 'decided', 'fixed', 'noted'"),
+                "target": string("The content to remember, specific and self-contained"),
+                "record_type": {
+                    "type": "string",
+                    "enum": ["Temporal", "Symbolic", "Procedural", "Reflexion", "Perception"],
+                    "default": "Temporal",
+                },
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "confidence": {"type": "number", "minimum": 0.0, "maximum": 1.0},
+                "source": string("Who or what produced this memory"),
+                "ttl_seconds": {"type": "integer", "description": "Expire after N seconds"},
+            }),
+        ),
+        tool(
+            "search_memory",
+            "Search stored memories by relevance to a query.",
+            &["query"],
+            json!({
+                "query": string("What to search for"),
+                "actor": string("Only return memories of this actor"),
+                "limit": limit(10),
+            }),
+        ),
+        tool(
+            "get_context",
+            "Render the memories relevant to a query as a context block ready to read.",
+            &["query"],
+            json!({
+                "query": string("The task or question the context is for"),
+                "actor": string("Only use memories of this actor"),
+                "limit": limit(10),
+                "max_tokens": {"type": "integer", "description": "Truncate to about this many tokens"},
+            }),
+        ),
+        tool(
+            "forget_actor",
+            "Delete every memory of an actor (right to be forgotten).",
+            &["actor"],
+            json!({"actor": string("Actor whose memories to delete")}),
+        ),
+        tool(
+            "delete_memory",
+            "Delete one memory record by id.",
+            &["id"],
+            json!({"id": string("UUID of the record")}),
+        ),
+        tool(
+            "get_stats",
+            "Memory store statistics: total records, records by type, unique actors.",
+            &[],
+            json!({}),
+        ),
+        tool(
+            "link_memories",
+            "Add a directed edge between two memory records in the causal graph.",
+            &["source_id", "target_id"],
+            json!({
+                "source_id": string("UUID of the source record"),
+                "target_id": string("UUID of the target record"),
+                "relation": {
+                    "type": "string",
+                    "enum": ["causal", "follows", "contradicts", "related"],
+                    "default": "related",
+                },
+            }),
+        ),
+        tool(
+            "get_neighbors",
+            "Records linked to a record in the causal graph, in either direction.",
+            &["record_id"],
+            json!({"record_id": string("UUID of the seed record"), "limit": limit(10)}),
+        ),
+        tool(
+            "search_related",
+            "Records ranked by personalized PageRank from a seed record in the causal graph.",
+            &["seed_id"],
+            json!({"seed_id": string("UUID of the seed record"), "limit": limit(10)}),
+        ),
+        tool(
+            "graph_query",
+            "Query the symbolic knowledge graph by node label, by property, or for a node's neighbors.",
+            &[],
+            json!({
+                "label": string("Nodes with this label"),
+                "key": string("Property key to match (with `value`)"),
+                "value": string("Property value to match (with `key`)"),
+                "node_id": string("Return this node and its neighbors"),
+                "relation": string("With `node_id`: only follow edges with this relation"),
+            }),
+        ),
+        tool(
+            "world_model_states",
+            "States and actions the world model has observed.",
+            &[],
+            json!({}),
+        ),
+        tool(
+            "world_model_predict",
+            "Predicted next-state distribution for taking an action in a state.",
+            &["state", "action"],
+            json!({"state": string("Current state"), "action": string("Action to take")}),
+        ),
+    ]
+}
+
+fn text_result(text: impl Into<String>) -> Value {
+    json!({"content": [{"type": "text", "text": text.into()}], "isError": false})
+}
+
+fn json_result(value: &Value) -> Value {
+    text_result(serde_json::to_string_pretty(value).unwrap_or_default())
+}
+
+fn error_result(text: impl Into<String>) -> Value {
+    json!({"content": [{"type": "text", "text": text.into()}], "isError": true})
+}
+
+/// Arguments of one tool call; missing required values become tool errors.
+struct Args<'a>(&'a Value);
+
+impl<'a> Args<'a> {
+    fn str(&self, key: &str) -> Option<&'a str> {
+        self.0.get(key).and_then(Value::as_str)
+    }
+
+    fn required(&self, key: &str) -> Result<&'a str, String> {
+        self.str(key)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| format!("missing argument `{key}`"))
+    }
+
+    fn uuid(&self, key: &str) -> Result<Uuid, String> {
+        let raw = self.required(key)?;
+        Uuid::parse_str(raw).map_err(|_| format!("`{key}` is not a UUID: {raw}"))
+    }
+
+    fn limit(&self, default: usize, max: usize) -> usize {
+        self.0
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(default, |l| l as usize)
+            .clamp(1, max)
+    }
+}
+
+/// Records relevant to `query`, minus expired ones and (optionally) other
+/// actors'. High-risk injection attempts are withheld (the injection sweep
+/// quarantines them); the ids of suspicious or low-trust records are returned
+/// for spotlighting. Takes a snapshot so classification runs outside the lock.
+pub(crate) fn relevant<B: MemoryBackend>(
+    ms: &MemoryStore<B>,
+    query: &str,
+    actor: Option<&str>,
+    limit: usize,
+) -> (Vec<(MemoryRecord, f64)>, HashSet<Uuid>) {
+    let now = chrono::Utc::now().timestamp();
+    let config = InjectionConfig::default();
+    // Over-fetch so the actor filter still leaves `limit` hits.
+    let fetch = if actor.is_some() { limit * 5 } else { limit };
+    let mut hits: Vec<(MemoryRecord, f64)> = ms
+        .search_semantic(None, query, fetch, false)
+        .into_iter()
+        .filter(|(r, _)| actor.is_none_or(|a| r.actor == a))
+        .filter(|(r, _)| r.expires_at.is_none_or(|exp| exp > now))
+        .map(|(r, score)| (r.clone(), score))
+        .collect();
+    let batch: Vec<(&MemoryRecord, Option<f64>)> = hits
+        .iter()
+        .map(|(r, _)| (r, source_trust_of(&ms.source_trust, r)))
+        .collect();
+    let mut withheld = HashSet::new();
+    let mut untrusted = HashSet::new();
+    for assessment in assess_batch(&batch, &config) {
+        if assessment.level == InjectionLevel::HighRisk {
+            withheld.insert(assessment.id);
+        } else if assessment.untrusted(&config) {
+            untrusted.insert(assessment.id);
+        }
+    }
+    hits.retain(|(r, _)| !withheld.contains(&r.id));
+    hits.truncate(limit);
+    (hits, untrusted)
+}
+
+/// Markdown context block for `query`, as served by `get_context` and the
+/// `recall_context` prompt. Untrusted records are spotlighted.
+pub(crate) fn context_block<B: MemoryBackend>(
+    ms: &MemoryStore<B>,
+    query: &str,
+    actor: Option<&str>,
+    limit: usize,
+    max_tokens: Option<usize>,
+) -> String {
+    let (hits, untrusted) = relevant(ms, query, actor, limit);
+    if hits.is_empty() {
+        return "No relevant memories found.".to_string();
+    }
+    let spotlight = (!untrusted.is_empty()).then(Spotlight::new);
+    let mut out = spotlight
+        .as_ref()
+        .map(|s| format!("{}\n", s.notice()))
+        .unwrap_or_default();
+    out.push_str("Relevant memories:\n");
+    for (r, _) in &hits {
+        let body = format!("[{}] {}", r.action, r.target);
+        let body = match &spotlight {
+            Some(s) if untrusted.contains(&r.id) => s.wrap(&body),
+            _ => body,
+        };
+        out.push_str(&format!(
+            "- {} (confidence: {:.0}%, source: {})\n",
+            body,
+            r.confidence * 100.0,
+            r.source.as_deref().unwrap_or("unknown")
+        ));
+    }
+    // 1 token ≈ 4 chars; cut on a char boundary.
+    if let Some(max_chars) = max_tokens.map(|t| t * 4) {
+        if out.len() > max_chars {
+            let mut end = max_chars;
+            while !out.is_char_boundary(end) {
+                end -= 1;
+            }
+            out.truncate(end);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn record_type(name: Option<&str>) -> MemoryType {
+    match name {
+        Some("Symbolic") => MemoryType::Symbolic,
+        Some("Procedural") => MemoryType::Procedural,
+        Some("Reflexion") => MemoryType::Reflexion,
+        Some("Perception") => MemoryType::Perception,
+        _ => MemoryType::Temporal,
+    }
+}
+
+/// Node id of a memory record in the causal graph (shared with the REST API).
+fn topo_id(id: Uuid) -> String {
+    format!("mem-{id}")
+}
+
+impl<B: MemoryBackend> McpService<B> {
+    /// `tools/call`. Unknown tools are a protocol error; everything else
+    /// that goes wrong is reported in the tool result.
+    pub(crate) fn call_tool(&self, name: &str, args: &Value) -> Result<Value, RpcError> {
+        let args = Args(args);
+        let outcome = match name {
+            "add_memory" => self.add_memory(&args),
+            "search_memory" => self.search_memory(&args),
+            "get_context" => self.get_context(&args),
+            "forget_actor" => self.forget_actor(&args),
+            "delete_memory" => self.delete_memory(&args),
+            "get_stats" => self.stats().map(|s| json_result(&s)),
+            "link_memories" => self.link_memories(&args),
+            "get_neighbors" => self.get_neighbors(&args),
+            "search_related" => self.search_related(&args),
+            "graph_query" => self.graph_query(&args),
+            "world_model_states" => Ok(json_result(&json!({
+                "states": self.world_model.get_states(),
+                "actions": self.world_model.get_actions(),
+                "observation_count": self.world_model.transition_count(),
+            }))),
+            "world_model_predict" => self.world_model_predict(&args),
+            other => {
+                return Err(RpcError::invalid_params(format!("unknown tool `{other}`")));
+            }
+        };
+        Ok(outcome.unwrap_or_else(error_result))
+    }
+
+    fn store_guard(&self) -> Result<MemoryStoreGuard<'_, B>, String> {
+        self.lock_store().map_err(|e| e.message)
+    }
+
+    fn add_memory(&self, args: &Args) -> Result<Value, String> {
+        let mut record = MemoryRecord::new(
+            record_type(args.str("record_type")),
+            args.required("actor")?.to_string(),
+            args.required("action")?.to_string(),
+            args.required("target")?.to_string(),
+            json!({}),
+        );
+        if let Some(ttl) = args.0.get("ttl_seconds").and_then(Value::as_i64) {
+            record.expires_at = Some(chrono::Utc::now().timestamp() + ttl);
+        }
+        if let Some(c) = args.0.get("confidence").and_then(Value::as_f64) {
+            record.confidence = (c as f32).clamp(0.0, 1.0);
+        }
+        if let Some(source) = args.str("source") {
+            record.source = Some(source.to_string());
+        }
+        if let Some(tags) = args.0.get("tags").and_then(Value::as_array) {
+            record.tags = tags
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+        }
+        // The store's write screen applies the namespace safety policy; it
+        // runs on a snapshot so classification doesn't hold the writer lock.
+        let screened = self
+            .store
+            .snapshot()
+            .screen(&mut record)
+            .map_err(|e| e.to_string())?;
+        let line = record_line(&record);
+        let mut ms = self.store_guard()?;
+        ms.add_prescreened(record).map_err(|e| e.to_string())?;
+        ms.flush().map_err(|e| e.to_string())?;
+        let mut text = format!("Stored {line}");
+        if let Some(screened) = screened.filter(|s| s.action != crate::safety_classifier::Action::Allow) {
+            text.push_str(&format!(
+                "\nSafety: {:?} ({:?}, {} span(s) redacted)",
+                screened.action, screened.category, screened.redacted
+            ));
+        }
+        Ok(text_result(text))
+    }
+
+    fn search_memory(&self, args: &Args) -> Result<Value, String> {
+        let query = args.required("query")?;
+        let (hits, _) = relevant(&self.store.snapshot(), query, args.str("actor"), args.limit(10, 100));
+        if hits.is_empty() {
+            return Ok(text_result("No memories found."));
+        }
+        let lines: Vec<String> = hits
+            .iter()
+            .map(|(r, score)| format!("- {} score {:.2}", record_line(r), score))
+            .collect();
+        Ok(text_result(format!(
+            "Found {} memories:\n{}",
+            lines.len(),
+            lines.join("\n")
+        )))
+    }
+
+    fn get_context(&self, args: &Args) -> Result<Value, String> {
+        let query = args.required("query")?;
+        let max_tokens = args
+            .0
+            .get("max_tokens")
+            .and_then(Value::as_u64)
+            .map(|t| t as usize);
+        Ok(text_result(context_block(
+            &self.store.snapshot(),
+            query,
+            args.str("actor"),
+            args.limit(10, 50),
+            max_tokens,
+        )))
+    }
+
+    fn forget_actor(&self, args: &Args) -> Result<Value, String> {
+        let actor = args.required("actor")?;
+        let deleted = self
+            .store_guard()?
+            .delete_by_actor(actor)
+            .map_err(|e| e.to_string())?;
+        Ok(text_result(format!(
+            "Deleted {} memories of `{actor}`.",
+            deleted.len()
+        )))
+    }
+
+    fn delete_memory(&self, args: &Args) -> Result<Value, String> {
+        let id = args.uuid("id")?;
+        let mut ms = self.store_guard()?;
+        if !ms.delete_by_id(id) {
+            return Err(format!("no memory {id}"));
+        }
+        ms.compact_backend().map_err(|e| e.to_string())?;
+        Ok(text_result(format!("Deleted memory {id}.")))
+    }
+
+    /// Store totals; also served as the `hipcortex://stats` resource.
+    pub(crate) fn stats(&self) -> Result<Value, String> {
+        let ms = self.store.snapshot();
+        let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+        let mut actors = HashSet::new();
+        for r in ms.all() {
+            *by_type.entry(format!("{:?}", r.record_type)).or_default() += 1;
+            actors.insert(r.actor.as_str());
+        }
+        Ok(json!({
+            "total_records": ms.record_count(),
+            "unique_actors": actors.len(),
+            "by_type": by_type,
+        }))
+    }
+
+    fn link_memories(&self, args: &Args) -> Result<Value, String> {
+        let (from, to) = (args.uuid("source_id")?, args.uuid("target_id")?);
+        {
+            let ms = self.store.snapshot();
+            for id in [from, to] {
+                if ms.find_by_id(id).is_none() {
+                    return Err(format!("no memory {id}"));
+                }
+            }
+        }
+        let relation = args.str("relation").unwrap_or("related");
+        let edge_type = match relation {
+            "causal" | "caused_by" => EdgeType::Causal,
+            "follows" | "temporal" => EdgeType::Temporal,
+            "contradicts" | "taxonomic" => EdgeType::Taxonomic,
+            _ => EdgeType::Supports,
+        };
+        let mut topo = self.topo.lock().map_err(|e| format!("graph lock: {e}"))?;
+        for id in [from, to] {
+            // Already present is fine.
+            let _ = topo.add_node(topo_id(id), [0.0; 128], HashMap::new());
+        }
+        topo.add_edge(topo_id(from), topo_id(to), edge_type, 1.0, 1.0)?;
+        Ok(text_result(format!("Linked {from} --{relation}--> {to}.")))
+    }
+
+    fn records_for(&self, topo_ids: &[String]) -> Result<Vec<MemoryRecord>, String> {
+        let ms = self.store.snapshot();
+        Ok(topo_ids
+            .iter()
+            .filter_map(|s| Uuid::parse_str(s.trim_start_matches("mem-")).ok())
+            .filter_map(|id| ms.find_by_id(id).cloned())
+            .collect())
+    }
+
+    fn get_neighbors(&self, args: &Args) -> Result<Value, String> {
+        let node = topo_id(args.uuid("record_id")?);
+        let (outgoing, incoming) = {
+            let topo = self.topo.lock().map_err(|e| format!("graph lock: {e}"))?;
+            (topo.get_neighbors(&node), topo.get_incoming(&node))
+        };
+        let limit = args.limit(10, 100);
+        let mut lines: Vec<String> = self
+            .records_for(&outgoing)?
+            .iter()
+            .map(|r| format!("-> {}", record_line(r)))
+            .collect();
+        lines.extend(
+            self.records_for(&incoming)?
+                .iter()
+                .map(|r| format!("<- {}", record_line(r))),
+        );
+        lines.truncate(limit);
+        if lines.is_empty() {
+            return Ok(text_result("No linked memories."));
+        }
+        Ok(text_result(lines.join("\n")))
+    }
+
+    fn search_related(&self, args: &Args) -> Result<Value, String> {
+        let seed = topo_id(args.uuid("seed_id")?);
+        let ranked = {
+            let topo = self.topo.lock().map_err(|e| format!("graph lock: {e}"))?;
+            crate::topological_memory::ppr_weighted(&topo, &seed, args.limit(10, 100))
+        };
+        let ids: Vec<String> = ranked.iter().map(|(id, _)| id.clone()).collect();
+        let records = self.records_for(&ids)?;
+        if records.is_empty() {
+            return Ok(text_result("No related memories."));
+        }
+        let scores: HashMap<&str, f64> = ranked.iter().map(|(id, s)| (id.as_str(), *s)).collect();
+        let lines: Vec<String> = records
+            .iter()
+            .map(|r| {
+                let score = scores.get(topo_id(r.id).as_str()).copied().unwrap_or(0.0);
+                format!("- {} ppr {:.3}", record_line(r), score)
+            })
+            .collect();
+        Ok(text_result(lines.join("\n")))
+    }
+
+    fn graph_query(&self, args: &Args) -> Result<Value, String> {
+        let mut graph = self
+            .symbolic
+            .lock()
+            .map_err(|e| format!("graph lock: {e}"))?;
+        let result = if let Some(raw) = args.str("node_id") {
+            let id = Uuid::parse_str(raw).map_err(|_| format!("`node_id` is not a UUID: {raw}"))?;
+            let node = graph.get_node(id).ok_or_else(|| format!("no node {id}"))?;
+            json!({"node": node, "neighbors": graph.neighbors(id, args.str("relation"))})
+        } else if let (Some(key), Some(value)) = (args.str("key"), args.str("value")) {
+            json!({"nodes": graph.find_by_property(key, value)})
+        } else if let Some(label) = args.str("label") {
+            json!({"nodes": graph.find_by_label(label)})
+        } else {
+            return Err("give `node_id`, `label`, or `key` and `value`".into());
+        };
+        Ok(json_result(&result))
+    }
+
+    fn world_model_predict(&self, args: &Args) -> Result<Value, String> {
+        let prediction = self
+            .world_model
+            .predict_next_state(args.required("state")?, args.required("action")?)?;
+        Ok(json_result(&json!({
+            "from_state": prediction.from_state,
+            "action": prediction.action,
+            "probabilities": prediction.probabilities,
+            "entropy": prediction.entropy,
+            "observation_count": prediction.observation_count,
+        })))
+    }
+}
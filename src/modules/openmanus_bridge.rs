@@ -20,6 +20,7 @@ pub fn decode_request(json: &str) -> serde_json::Result<PerceptInput> {
         text: Some(req.content),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec![req.role],
     })
 }
@@ -1,15 +1,58 @@
-/// Chain-of-Thought: input -> symbol parse -> PCA -> compressed vector
-use nalgebra::DMatrix;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-
-#[derive(Debug, Clone)]
+//! Perception adapter — turns text, images, audio and concepts into embeddings.
+//!
+//! Chain-of-Thought: input -> modality encoder -> fit to the configured
+//! dimension -> L2 normalize. Each modality has a pluggable encoder: text
+//! (and agent messages, and concept tags) go through an `EmbeddingProvider`,
+//! images through an `ImageEncoder` (the grid-RGB `VisionEncoder` by default,
+//! a CLIP-style model when one is plugged in) and audio through an
+//! `AudioEncoder` (log-mel statistics by default). Every encoder names the
+//! embedding *space* it writes into. Percepts are stored as `Perception`
+//! records carrying their embedding and space, and search only compares
+//! vectors from the same space and dimension. Cross-modal search (a text
+//! query finding images) therefore works exactly when the text and image
+//! encoders share a space, as a CLIP pair does.
+//!
+//! The dimension fit is chunk pooling (`compress_embedding`), a fixed linear
+//! map, so stored vectors stay comparable across inputs.
+use crate::embedding_provider::{EmbeddingProvider, HashEmbeddingProvider};
+use crate::memory_record::{MemoryRecord, MemoryType, RecordStatus};
+use crate::memory_store::{cosine_similarity, MemoryStore};
+use crate::persistence::MemoryBackend;
+use crate::semantic_compression::compress_embedding;
+use crate::vision_encoder::VisionEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Modality {
     Text,
     ImageEmbedding,
     Image,
     SymbolicConcept,
     AgentMessage,
+    Audio,
+}
+
+impl Modality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Modality::Text => "text",
+            Modality::ImageEmbedding => "image_embedding",
+            Modality::Image => "image",
+            Modality::SymbolicConcept => "symbolic_concept",
+            Modality::AgentMessage => "agent_message",
+            Modality::Audio => "audio",
+        }
+    }
+}
+
+/// Mono PCM samples in [-1, 1].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioClip {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -18,16 +61,16 @@ pub struct PerceptInput {
     pub text: Option<String>,
     pub embedding: Option<Vec<f32>>,
     pub image_data: Option<Vec<u8>>,
+    pub audio: Option<AudioClip>,
     pub tags: Vec<String>,
 }
 
-pub struct PerceptionAdapter;
-
 #[derive(Debug)]
 pub enum AdapterError {
     RateLimited,
     InvalidInput(&'static str),
     ImageEncoding(anyhow::Error),
+    AudioEncoding(anyhow::Error),
 }
 
 impl std::fmt::Display for AdapterError {
@@ -36,13 +79,247 @@ impl std::fmt::Display for AdapterError {
             AdapterError::RateLimited => write!(f, "rate limit exceeded"),
             AdapterError::InvalidInput(s) => write!(f, "invalid input: {}", s),
             AdapterError::ImageEncoding(e) => write!(f, "image encoding error: {}", e),
+            AdapterError::AudioEncoding(e) => write!(f, "audio encoding error: {}", e),
         }
     }
 }
 
 impl std::error::Error for AdapterError {}
 
-const COMPRESS_DIM: usize = 4;
+/// Output dimension of the static `PerceptionAdapter::adapt`.
+pub const COMPRESS_DIM: usize = 4;
+
+// ─── Encoders ────────────────────────────────────────────────────────────────
+
+/// Image bytes (PNG/JPEG) to an embedding.
+pub trait ImageEncoder: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Embedding space the vectors live in; encoders that share a space
+    /// (e.g. the two towers of a CLIP model) can be searched across.
+    fn space(&self) -> &str {
+        self.name()
+    }
+
+    fn encode(&self, bytes: &[u8]) -> anyhow::Result<Vec<f32>>;
+}
+
+/// Default image encoder: mean RGB of a 4x4 grid (48 dims). The grid
+/// features are used directly rather than `encode_image`'s per-image PCA,
+/// whose basis differs between images.
+impl ImageEncoder for VisionEncoder {
+    fn name(&self) -> &str {
+        "grid-rgb"
+    }
+
+    fn encode(&self, bytes: &[u8]) -> anyhow::Result<Vec<f32>> {
+        let img = image::load_from_memory(bytes)?;
+        Ok(VisionEncoder::encode_image_grid(&img, 4))
+    }
+}
+
+pub trait AudioEncoder: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn space(&self) -> &str {
+        self.name()
+    }
+
+    fn encode(&self, clip: &AudioClip) -> anyhow::Result<Vec<f32>>;
+}
+
+/// Log-mel features: per-band mean and standard deviation of the log mel
+/// energies over Hann-windowed frames (`2 * n_mels` dims).
+#[derive(Debug, Clone)]
+pub struct LogMelEncoder {
+    pub n_mels: usize,
+    pub frame_ms: f32,
+    pub hop_ms: f32,
+}
+
+impl Default for LogMelEncoder {
+    fn default() -> Self {
+        Self {
+            n_mels: 40,
+            frame_ms: 25.0,
+            hop_ms: 10.0,
+        }
+    }
+}
+
+impl AudioEncoder for LogMelEncoder {
+    fn name(&self) -> &str {
+        "log-mel"
+    }
+
+    fn encode(&self, clip: &AudioClip) -> anyhow::Result<Vec<f32>> {
+        if clip.sample_rate == 0 {
+            anyhow::bail!("sample rate is zero");
+        }
+        if clip.samples.is_empty() {
+            anyhow::bail!("audio clip has no samples");
+        }
+        if self.n_mels == 0 {
+            anyhow::bail!("n_mels must be positive");
+        }
+        let sr = clip.sample_rate as f32;
+        let frame = ((sr * self.frame_ms / 1000.0) as usize).max(2);
+        let hop = ((sr * self.hop_ms / 1000.0) as usize).max(1);
+        let n_fft = frame.next_power_of_two();
+        let filters = mel_filterbank(self.n_mels, n_fft, sr);
+        let window: Vec<f32> = (0..frame)
+            .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / frame as f32).cos())
+            .collect();
+
+        let mut frames: Vec<Vec<f32>> = Vec::new();
+        let mut start = 0;
+        loop {
+            let mut re = vec![0.0f32; n_fft];
+            for (i, w) in window.iter().enumerate() {
+                re[i] = clip.samples.get(start + i).copied().unwrap_or(0.0) * w;
+            }
+            let power = power_spectrum(re);
+            frames.push(
+                filters
+                    .iter()
+                    .map(|f| (f.iter().zip(&power).map(|(w, p)| w * p).sum::<f32>() + 1e-10).ln())
+                    .collect(),
+            );
+            start += hop;
+            if start + frame > clip.samples.len() {
+                break;
+            }
+        }
+
+        let n = frames.len() as f32;
+        let mut out = vec![0.0f32; 2 * self.n_mels];
+        for band in 0..self.n_mels {
+            let mean = frames.iter().map(|f| f[band]).sum::<f32>() / n;
+            let var = frames.iter().map(|f| (f[band] - mean).powi(2)).sum::<f32>() / n;
+            out[band] = mean;
+            out[self.n_mels + band] = var.sqrt();
+        }
+        Ok(out)
+    }
+}
+
+fn hz_to_mel(hz: f32) -> f32 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+/// Triangular filters evenly spaced on the mel scale up to Nyquist, one
+/// weight per FFT bin (`n_fft / 2 + 1`).
+fn mel_filterbank(n_mels: usize, n_fft: usize, sample_rate: f32) -> Vec<Vec<f32>> {
+    let bins = n_fft / 2 + 1;
+    let top = hz_to_mel(sample_rate / 2.0);
+    let edges: Vec<f32> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(top * i as f32 / (n_mels + 1) as f32))
+        .collect();
+    (0..n_mels)
+        .map(|m| {
+            let (lo, mid, hi) = (edges[m], edges[m + 1], edges[m + 2]);
+            (0..bins)
+                .map(|k| {
+                    let f = k as f32 * sample_rate / n_fft as f32;
+                    if f > lo && f <= mid {
+                        (f - lo) / (mid - lo)
+                    } else if f > mid && f < hi {
+                        (hi - f) / (hi - mid)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Power spectrum of a real frame whose length is a power of two
+/// (iterative radix-2 FFT).
+fn power_spectrum(mut re: Vec<f32>) -> Vec<f32> {
+    let n = re.len();
+    let mut im = vec![0.0f32; n];
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * std::f32::consts::PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (wr, wi) = ((angle * k as f32).cos(), (angle * k as f32).sin());
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * wr - im[b] * wi;
+                let ti = re[b] * wi + im[b] * wr;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+    (0..n / 2 + 1)
+        .map(|k| re[k] * re[k] + im[k] * im[k])
+        .collect()
+}
+
+// ─── Configuration ───────────────────────────────────────────────────────────
+
+/// Output dimension per modality. Modalities without an entry use
+/// `default_dim`; when that is unset too, the encoder's native dimension.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerceptionConfig {
+    pub dims: HashMap<Modality, usize>,
+    pub default_dim: Option<usize>,
+}
+
+impl PerceptionConfig {
+    /// Every modality fitted to `dim`.
+    pub fn uniform(dim: usize) -> Self {
+        Self {
+            dims: HashMap::new(),
+            default_dim: Some(dim),
+        }
+    }
+
+    pub fn with_dim(mut self, modality: Modality, dim: usize) -> Self {
+        self.dims.insert(modality, dim);
+        self
+    }
+
+    pub fn dim_for(&self, modality: Modality) -> Option<usize> {
+        self.dims.get(&modality).copied().or(self.default_dim)
+    }
+}
+
+/// An encoded percept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Percept {
+    pub modality: Modality,
+    pub embedding: Vec<f32>,
+    /// Embedding space of `embedding`; see `ImageEncoder::space`.
+    pub space: String,
+}
+
+/// Space recorded for caller-supplied `ImageEmbedding` vectors.
+pub const EXTERNAL_SPACE: &str = "external";
+
+// ─── Adapter ─────────────────────────────────────────────────────────────────
 
 struct RateLimiter {
     capacity: u32,
@@ -75,6 +352,8 @@ impl RateLimiter {
 
 lazy_static::lazy_static! {
     static ref ADAPTER_LIMITER: RateLimiter = RateLimiter::new(10);
+    static ref DEFAULT_ADAPTER: PerceptionAdapter =
+        PerceptionAdapter::new(PerceptionConfig::uniform(COMPRESS_DIM));
 }
 
 fn l2_normalize(mut v: Vec<f32>) -> Vec<f32> {
@@ -102,119 +381,213 @@ fn shannon_entropy(v: &[f32]) -> f32 {
     ent
 }
 
-fn text_to_embedding(text: &str, dim: usize) -> Vec<f32> {
-    let mut vec = vec![0.0; dim];
-    for token in text.split_whitespace() {
-        let mut hasher = DefaultHasher::new();
-        token.hash(&mut hasher);
-        let idx = (hasher.finish() as usize) % dim;
-        vec[idx] += 1.0;
-    }
-    vec
+pub struct PerceptionAdapter {
+    config: PerceptionConfig,
+    text: Arc<dyn EmbeddingProvider>,
+    image: Arc<dyn ImageEncoder>,
+    audio: Arc<dyn AudioEncoder>,
 }
 
-fn pca_compress(data: &[f32], new_dim: usize) -> Vec<f32> {
-    if new_dim == 0 || data.is_empty() {
-        return vec![];
+impl PerceptionAdapter {
+    /// Adapter with the built-in encoders: 128-dim n-gram hashing for text,
+    /// grid RGB for images and log-mel statistics for audio.
+    pub fn new(config: PerceptionConfig) -> Self {
+        Self {
+            config,
+            text: Arc::new(HashEmbeddingProvider::default_dim()),
+            image: Arc::new(VisionEncoder),
+            audio: Arc::new(LogMelEncoder::default()),
+        }
     }
-    if data.len() < new_dim {
-        return crate::semantic_compression::compress_embedding(data, new_dim);
+
+    pub fn with_text_encoder(mut self, encoder: Arc<dyn EmbeddingProvider>) -> Self {
+        self.text = encoder;
+        self
     }
-    let rows = data.len() - new_dim + 1;
-    let mut mat_data = Vec::with_capacity(rows * new_dim);
-    for i in 0..rows {
-        for j in 0..new_dim {
-            mat_data.push(data[i + j]);
-        }
+
+    pub fn with_image_encoder(mut self, encoder: Arc<dyn ImageEncoder>) -> Self {
+        self.image = encoder;
+        self
     }
-    let mut m = DMatrix::from_row_slice(rows, new_dim, &mat_data);
-    for c in 0..new_dim {
-        let mean: f32 = (0..rows).map(|r| m[(r, c)]).sum::<f32>() / rows as f32;
-        for r in 0..rows {
-            m[(r, c)] -= mean;
-        }
+
+    pub fn with_audio_encoder(mut self, encoder: Arc<dyn AudioEncoder>) -> Self {
+        self.audio = encoder;
+        self
     }
-    let svd = m.svd(true, true);
-    let vt = svd.v_t.unwrap();
-    let mut out = vec![0.0; new_dim];
-    for i in 0..new_dim {
-        let mut val = 0.0;
-        for j in 0..new_dim {
-            val += data[j] * vt[(i, j)];
-        }
-        out[i] = val;
+
+    pub fn config(&self) -> &PerceptionConfig {
+        &self.config
     }
-    out
-}
 
-impl PerceptionAdapter {
+    /// Rate-limited adapter shared by the agent bridges: every modality is
+    /// fitted to `COMPRESS_DIM`.
     pub fn adapt(input: PerceptInput) -> Result<Vec<f32>, AdapterError> {
         if !ADAPTER_LIMITER.allow() {
             return Err(AdapterError::RateLimited);
         }
-        match input.modality {
-            Modality::Text => {
+        if matches!(input.modality, Modality::Text) {
+            let _ = crate::safety_guardrail::SAFETY_GUARDRAIL
+                .lock()
+                .unwrap()
+                .check_precondition("System:Self:perception_adapt_epoch");
+        }
+        DEFAULT_ADAPTER.perceive(&input).map(|p| p.embedding)
+    }
+
+    /// Encode `input` with its modality's encoder and fit it to the
+    /// configured dimension.
+    pub fn perceive(&self, input: &PerceptInput) -> Result<Percept, AdapterError> {
+        let (raw, space) = match input.modality {
+            Modality::Text | Modality::AgentMessage => {
                 let text = input
                     .text
+                    .as_deref()
                     .ok_or(AdapterError::InvalidInput("missing text"))?;
-                let emb = text_to_embedding(&text, COMPRESS_DIM * 4);
-                let comp = pca_compress(&emb, COMPRESS_DIM);
-                let out = l2_normalize(comp);
-                let ent = shannon_entropy(&out);
-                println!("[PerceptionAdapter] entropy {:.3}", ent);
-                let _ = crate::safety_guardrail::SAFETY_GUARDRAIL
-                    .lock()
-                    .unwrap()
-                    .check_precondition("System:Self:perception_adapt_epoch");
-                Ok(out)
-            }
-            Modality::ImageEmbedding => {
-                if let Some(embed) = input.embedding {
-                    let comp = pca_compress(&embed, COMPRESS_DIM);
-                    let out = l2_normalize(comp);
-                    println!("[PerceptionAdapter] entropy {:.3}", shannon_entropy(&out));
-                    Ok(out)
-                } else {
-                    Err(AdapterError::InvalidInput("missing embedding"))
-                }
-            }
-            Modality::Image => {
-                if let Some(bytes) = input.image_data {
-                    match crate::vision_encoder::VisionEncoder::encode_bytes(&bytes) {
-                        Ok(emb) => {
-                            let comp = pca_compress(&emb, COMPRESS_DIM);
-                            let out = l2_normalize(comp);
-                            println!("[PerceptionAdapter] entropy {:.3}", shannon_entropy(&out));
-                            Ok(out)
-                        }
-                        Err(e) => Err(AdapterError::ImageEncoding(e)),
-                    }
-                } else {
-                    Err(AdapterError::InvalidInput("no image data"))
-                }
+                (self.text.embed(text), self.text.name().to_string())
             }
             Modality::SymbolicConcept => {
                 if input.tags.is_empty() {
-                    Err(AdapterError::InvalidInput("empty tags"))
-                } else {
-                    println!(
-                        "[PerceptionAdapter] Symbolic concept tags: {:?}",
-                        input.tags
-                    );
-                    Ok(Vec::new())
+                    return Err(AdapterError::InvalidInput("empty tags"));
                 }
+                (
+                    self.text.embed(&input.tags.join(" ")),
+                    self.text.name().to_string(),
+                )
             }
-            Modality::AgentMessage => {
-                let text = input
-                    .text
-                    .ok_or(AdapterError::InvalidInput("missing text"))?;
-                let emb = text_to_embedding(&text, COMPRESS_DIM * 4);
-                let comp = pca_compress(&emb, COMPRESS_DIM);
-                let out = l2_normalize(comp);
-                println!("[PerceptionAdapter] entropy {:.3}", shannon_entropy(&out));
-                Ok(out)
+            Modality::ImageEmbedding => {
+                let embed = input
+                    .embedding
+                    .clone()
+                    .ok_or(AdapterError::InvalidInput("missing embedding"))?;
+                (embed, EXTERNAL_SPACE.to_string())
+            }
+            Modality::Image => {
+                let bytes = input
+                    .image_data
+                    .as_deref()
+                    .ok_or(AdapterError::InvalidInput("no image data"))?;
+                let embed = self
+                    .image
+                    .encode(bytes)
+                    .map_err(AdapterError::ImageEncoding)?;
+                (embed, self.image.space().to_string())
+            }
+            Modality::Audio => {
+                let clip = input
+                    .audio
+                    .as_ref()
+                    .ok_or(AdapterError::InvalidInput("no audio clip"))?;
+                let embed = self
+                    .audio
+                    .encode(clip)
+                    .map_err(AdapterError::AudioEncoding)?;
+                (embed, self.audio.space().to_string())
             }
+        };
+        if raw.is_empty() {
+            return Err(AdapterError::InvalidInput("encoder returned no features"));
         }
+        let fitted = match self.config.dim_for(input.modality) {
+            Some(dim) if dim > 0 && dim != raw.len() => compress_embedding(&raw, dim),
+            _ => raw,
+        };
+        let embedding = l2_normalize(fitted);
+        tracing::debug!(
+            modality = input.modality.as_str(),
+            dim = embedding.len(),
+            entropy = shannon_entropy(&embedding),
+            "perception adapted"
+        );
+        Ok(Percept {
+            modality: input.modality,
+            embedding,
+            space,
+        })
+    }
+
+    /// Encode `input` and store it as a `Perception` record whose metadata
+    /// carries the embedding, its space and the modality.
+    pub fn store<B: MemoryBackend>(
+        &self,
+        store: &mut MemoryStore<B>,
+        actor: &str,
+        input: &PerceptInput,
+    ) -> anyhow::Result<(uuid::Uuid, Percept)> {
+        let percept = self
+            .perceive(input)
+            .map_err(|e| anyhow::anyhow!("perception failed: {e}"))?;
+        let target = match (&input.text, input.modality) {
+            (Some(text), _) => text.clone(),
+            (None, Modality::Image) => format!(
+                "image ({} bytes)",
+                input.image_data.as_ref().map_or(0, Vec::len)
+            ),
+            (None, Modality::Audio) => {
+                let clip = input.audio.as_ref().expect("perceive checked audio");
+                format!(
+                    "audio ({:.2}s at {} Hz)",
+                    clip.samples.len() as f32 / clip.sample_rate as f32,
+                    clip.sample_rate
+                )
+            }
+            (None, _) => input.tags.join(" "),
+        };
+        let mut rec = MemoryRecord::new(
+            MemoryType::Perception,
+            actor.to_string(),
+            "perceived".to_string(),
+            target,
+            serde_json::json!({
+                "modality": percept.modality,
+                "embedding_space": percept.space,
+                "embedding": percept.embedding,
+            }),
+        );
+        rec.tags = input.tags.clone();
+        rec.tags
+            .push(format!("modality:{}", percept.modality.as_str()));
+        // Tags and metadata changed after construction.
+        let hash = rec.compute_hash();
+        rec.integrity = Some(hash.clone());
+        rec.content_hash = Some(hash);
+        let id = rec.id;
+        store.add(rec)?;
+        Ok((id, percept))
+    }
+
+    /// Rank stored percepts by cosine similarity to `query`. Only records in
+    /// the query's embedding space and dimension are compared; `modalities`
+    /// restricts which stored modalities are searched (e.g. a text query
+    /// over images only).
+    pub fn search<'a, B: MemoryBackend>(
+        &self,
+        store: &'a MemoryStore<B>,
+        query: &PerceptInput,
+        modalities: Option<&[Modality]>,
+        limit: usize,
+    ) -> Result<Vec<(&'a MemoryRecord, f64)>, AdapterError> {
+        let percept = self.perceive(query)?;
+        let q: Vec<f64> = percept.embedding.iter().map(|&x| x as f64).collect();
+        let mut hits: Vec<(&MemoryRecord, f64)> = store
+            .all()
+            .iter()
+            .filter(|r| r.record_type == MemoryType::Perception)
+            .filter(|r| r.status == RecordStatus::Active)
+            .filter(|r| r.metadata["embedding_space"] == percept.space.as_str())
+            .filter(|r| {
+                modalities.is_none_or(|ms| {
+                    serde_json::from_value::<Modality>(r.metadata["modality"].clone())
+                        .is_ok_and(|m| ms.contains(&m))
+                })
+            })
+            .filter_map(|r| {
+                let v: Vec<f64> = serde_json::from_value(r.metadata["embedding"].clone()).ok()?;
+                (v.len() == q.len()).then(|| (r, cosine_similarity(&q, &v)))
+            })
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+        hits.truncate(limit);
+        Ok(hits)
     }
 }
 
@@ -344,6 +717,7 @@ mod tests {
             text: Some("hi".to_string()),
             embedding: None,
             image_data: None,
+            audio: None,
             tags: vec![],
         };
         let out = PerceptionAdapter::adapt(input).unwrap();
@@ -359,6 +733,7 @@ mod tests {
                 text: Some("x".into()),
                 embedding: None,
                 image_data: None,
+                audio: None,
                 tags: vec![],
             }));
         }
@@ -367,40 +742,17 @@ mod tests {
     }
 
     #[test]
-    fn pca_reconstruction_error() {
-        let data: Vec<f32> = (0..8).map(|v| v as f32).collect();
-        let compressed = pca_compress(&data, 4);
-        assert_eq!(compressed.len(), 4);
-
-        // Recompute PCA basis for reconstruction
-        let rows = data.len() - 4 + 1;
-        let mut mat_data = Vec::with_capacity(rows * 4);
-        for i in 0..rows {
-            for j in 0..4 {
-                mat_data.push(data[i + j]);
-            }
-        }
-        let mut m = DMatrix::from_row_slice(rows, 4, &mat_data);
-        for c in 0..4 {
-            let mean: f32 = (0..rows).map(|r| m[(r, c)]).sum::<f32>() / rows as f32;
-            for r in 0..rows {
-                m[(r, c)] -= mean;
-            }
-        }
-        let vt = m.svd(true, true).v_t.unwrap();
-        let mut reconstruct = vec![0.0f32; 4];
-        for i in 0..4 {
-            for j in 0..4 {
-                reconstruct[j] += compressed[i] * vt[(i, j)];
-            }
-        }
-        let err = data[..4]
-            .iter()
-            .zip(reconstruct.iter())
-            .map(|(a, b)| (a - b).powi(2))
-            .sum::<f32>()
-            .sqrt();
-        assert!(err < 1e-3);
+    fn power_spectrum_peaks_at_tone_bin() {
+        // 8 cycles over 64 samples lands exactly on bin 8.
+        let frame: Vec<f32> = (0..64)
+            .map(|i| (2.0 * std::f32::consts::PI * 8.0 * i as f32 / 64.0).sin())
+            .collect();
+        let power = power_spectrum(frame);
+        assert_eq!(power.len(), 33);
+        let peak = (0..power.len())
+            .max_by(|&a, &b| power[a].total_cmp(&power[b]))
+            .unwrap();
+        assert_eq!(peak, 8);
     }
 
     #[test]
@@ -418,6 +770,7 @@ mod tests {
             text: None,
             embedding: Some(vec![1.0, 2.0, 3.0, 4.0, 0.5, 0.2, 0.1, 0.0]),
             image_data: None,
+            audio: None,
             tags: vec![],
         };
         let out = PerceptionAdapter::adapt(input).unwrap();
@@ -3,10 +3,11 @@
 // Maintains a registry of all operations the system can perform,
 // with their resource requirements and current limitations.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Describes a capability that the system can perform
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CapabilityDescriptor {
     /// Unique name of the capability (e.g., "temporal_insert", "symbolic_query")
     pub name: String,
@@ -25,7 +26,7 @@ pub struct CapabilityDescriptor {
 }
 
 /// Represents a limitation on a capability
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Limitation {
     /// Capability is temporarily disabled
     Disabled { reason: String },
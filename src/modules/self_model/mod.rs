@@ -135,6 +135,30 @@ impl SelfModel {
         caps.get(name)
     }
 
+    /// All registered capabilities, sorted by name
+    pub fn capabilities(&self) -> Result<Vec<CapabilityDescriptor>, String> {
+        let caps = self
+            .capabilities
+            .read()
+            .map_err(|e| format!("Failed to acquire capability lock: {}", e))?;
+        let mut names = caps.list_capabilities();
+        names.sort();
+        names.iter().map(|n| caps.get(n)).collect()
+    }
+
+    /// Register a capability, replacing any existing one with the same name
+    pub fn upsert_capability(&self, descriptor: CapabilityDescriptor) -> Result<(), String> {
+        let mut caps = self
+            .capabilities
+            .write()
+            .map_err(|e| format!("Failed to acquire capability lock: {}", e))?;
+        if caps.has(&descriptor.name) {
+            caps.update(descriptor)
+        } else {
+            caps.register(descriptor)
+        }
+    }
+
     /// Check if system can execute an operation
     ///
     /// This is the main decision point - evaluates:
@@ -5,6 +5,8 @@
 // - Do-calculus interventions: P(Y|do(X=x))
 // - Counterfactual reasoning: "what if X had been x?"
 // - Cycle prevention (DAG property maintenance)
+// - Latent (unobserved) nodes for hidden confounders; identifiability and
+//   adjustment-set search live in identification.rs
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
@@ -65,6 +67,9 @@ pub struct CausalGraph {
 
     /// Hybrid topo substrate (for embeddings + topo-powered queries like ppr/paths where possible)
     topo: CausalTopoGraph,
+
+    /// Unobserved nodes: never adjusted for, and confound their children
+    latent: HashSet<String>,
 }
 
 impl CausalGraph {
@@ -76,6 +81,7 @@ impl CausalGraph {
             edge_data: HashMap::new(),
             distributions: HashMap::new(),
             topo: CausalTopoGraph::new(),
+            latent: HashSet::new(),
         }
     }
 
@@ -129,6 +135,17 @@ impl CausalGraph {
 
     /// Add causal edge A → B with cycle detection
     pub fn add_edge(&mut self, from: String, to: String) -> Result<(), String> {
+        self.add_edge_with_strength(from, to, 1.0)
+    }
+
+    /// Add edge A → B with a causal strength in [0, 1] (clamped), e.g. the
+    /// confidence a discovery algorithm assigned to it
+    pub fn add_edge_with_strength(
+        &mut self,
+        from: String,
+        to: String,
+        strength: f64,
+    ) -> Result<(), String> {
         // Ensure nodes exist
         if !self.nodes.contains_key(&from) {
             self.add_node(from.clone())?;
@@ -147,7 +164,8 @@ impl CausalGraph {
 
         // Add edge
         self.edges.get_mut(&from).unwrap().insert(to.clone());
-        self.edge_data.insert((from.clone(), to.clone()), 1.0);
+        self.edge_data
+            .insert((from.clone(), to.clone()), strength.clamp(0.0, 1.0));
 
         Ok(())
     }
@@ -232,6 +250,46 @@ impl CausalGraph {
         }
     }
 
+    /// Mark a node as latent (unobserved) or observed, adding it if missing
+    pub fn set_latent(&mut self, node: &str, latent: bool) -> Result<(), String> {
+        if !self.nodes.contains_key(node) {
+            self.add_node(node.to_string())?;
+        }
+        if latent {
+            self.latent.insert(node.to_string());
+        } else {
+            self.latent.remove(node);
+        }
+        Ok(())
+    }
+
+    pub fn is_latent(&self, node: &str) -> bool {
+        self.latent.contains(node)
+    }
+
+    /// Latent nodes, sorted
+    pub fn latent_nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = self.latent.iter().cloned().collect();
+        nodes.sort();
+        nodes
+    }
+
+    /// Add a latent common cause `U(a,b)` of `a` and `b` and return its name
+    pub fn add_latent_confounder(&mut self, a: &str, b: &str) -> Result<String, String> {
+        let name = format!("U({},{})", a, b);
+        self.set_latent(&name, true)?;
+        self.add_edge(name.clone(), a.to_string())?;
+        self.add_edge(name.clone(), b.to_string())?;
+        Ok(name)
+    }
+
+    /// All node ids, sorted
+    pub fn node_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.nodes.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
     /// Get parents of a node
     pub fn get_parents(&self, node: &str) -> Vec<String> {
         self.edges
@@ -259,7 +317,10 @@ impl CausalGraph {
     /// Implements backdoor adjustment:
     /// P(Y|do(X=x)) = Σ_z P(Y|X=x,Z=z) × P(Z)
     ///
-    /// where Z is a sufficient adjustment set (blocks backdoor paths)
+    /// where Z is a sufficient adjustment set (blocks backdoor paths), found
+    /// by `find_backdoor_set`. Fails with an explanation when the effect is
+    /// not identifiable, or is identifiable only by a formula other than
+    /// backdoor adjustment.
     pub fn compute_intervention(
         &self,
         query: &InterventionQuery,
@@ -300,7 +361,7 @@ impl CausalGraph {
             outcome_values.insert("1".to_string());
         }
 
-        let adjustment_set = self.get_parents(&query.intervention_var);
+        let adjustment_set = self.backdoor_adjustment(&query.intervention_var, &query.outcome)?;
         let mut result = HashMap::new();
 
         for outcome_val in &outcome_values {
@@ -529,7 +590,8 @@ impl CausalGraph {
     }
 
     /// Exact Backdoor Adjustment: P(Y | do(X = x)) = sum_z P(Y | X = x, Z = z) * P(Z = z)
-    /// where Z is the set of parents (sufficient adjustment set) of X.
+    /// where Z is a backdoor adjustment set for X → Y (the parents of X when
+    /// they are all observed).
     pub fn compute_empirical_intervention(
         &mut self,
         intervention_var: &str,
@@ -547,7 +609,7 @@ impl CausalGraph {
             return Err(format!("Outcome variable '{}' not found", outcome_var));
         }
 
-        let adjustment_set = self.get_parents(intervention_var);
+        let adjustment_set = self.backdoor_adjustment(intervention_var, outcome_var)?;
 
         if adjustment_set.is_empty() {
             let key_x = format!("{}={}", intervention_var, intervention_value);
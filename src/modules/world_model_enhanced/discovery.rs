@@ -0,0 +1,1469 @@
+// Causal Structure Discovery
+//
+// Learns causal structure from observational data instead of hand-written
+// edges:
+// - Datasets built from MemoryRecord metadata fields or TransitionModel counts
+// - PC algorithm (order-independent "stable" skeleton phase) with Fisher-z or
+//   G² conditional independence tests, v-structure orientation and Meek rules
+// - Greedy Equivalence Search: forward edge insertions then backward
+//   deletions over CPDAGs under a decomposable BIC score
+// - Results as a CPDAG: edges whose direction the data cannot determine stay
+//   undirected, and accepting edges into a CausalGraph keeps it acyclic
+
+use super::causal::CausalGraph;
+use super::transition::TransitionModel;
+use crate::memory_record::MemoryRecord;
+use nalgebra::DMatrix;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Quantile bins used when continuous variables meet a discrete test or score.
+const DISCRETIZATION_BINS: usize = 4;
+
+/// Ridge added to covariance submatrices before inversion.
+const RIDGE: f64 = 1e-9;
+
+/// Cap on operators applied per GES phase.
+const MAX_SEARCH_STEPS: usize = 1_000;
+
+/// Largest neighbour subset (T or H) tried by one GES operator.
+const MAX_OPERATOR_SET: usize = 4;
+
+/// Smallest BIC gain that counts as an improvement.
+const SCORE_EPSILON: f64 = 1e-9;
+
+// ============================================================================
+// Data
+// ============================================================================
+
+/// One variable's observations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Continuous(Vec<f64>),
+    /// `codes[i]` indexes into `levels`
+    Discrete {
+        codes: Vec<usize>,
+        levels: Vec<String>,
+    },
+}
+
+impl Column {
+    /// Discrete column from raw labels; levels keep first-seen order
+    pub fn discrete<S: Into<String>>(labels: impl IntoIterator<Item = S>) -> Self {
+        let mut levels: Vec<String> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let codes = labels
+            .into_iter()
+            .map(|label| {
+                let label = label.into();
+                *index.entry(label.clone()).or_insert_with(|| {
+                    levels.push(label);
+                    levels.len() - 1
+                })
+            })
+            .collect();
+        Column::Discrete { codes, levels }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Continuous(values) => values.len(),
+            Column::Discrete { codes, .. } => codes.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn select(&self, rows: &[usize]) -> Column {
+        match self {
+            Column::Continuous(values) => {
+                Column::Continuous(rows.iter().map(|&r| values[r]).collect())
+            }
+            Column::Discrete { codes, levels } => Column::Discrete {
+                codes: rows.iter().map(|&r| codes[r]).collect(),
+                levels: levels.clone(),
+            },
+        }
+    }
+
+    /// Codes and level count, binning continuous values at their quantiles
+    fn to_codes(&self, bins: usize) -> (Vec<usize>, usize) {
+        match self {
+            Column::Discrete { codes, levels } => (codes.clone(), levels.len().max(1)),
+            Column::Continuous(values) => {
+                let mut sorted = values.clone();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                let mut cuts: Vec<f64> =
+                    (1..bins).map(|k| sorted[k * sorted.len() / bins]).collect();
+                cuts.dedup();
+                cuts.retain(|&c| c > sorted[0]);
+                let codes = values
+                    .iter()
+                    .map(|v| cuts.iter().filter(|&&c| *v >= c).count())
+                    .collect();
+                (codes, cuts.len() + 1)
+            }
+        }
+    }
+}
+
+/// Weighted observations of named variables, one row per sample.
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    names: Vec<String>,
+    columns: Vec<Column>,
+    weights: Vec<f64>,
+}
+
+/// Raw metadata value: numbers keep their JSON text for use as a label
+enum RawValue {
+    Number(f64, String),
+    Label(String),
+}
+
+impl Dataset {
+    /// Dataset with unit row weights. Needs at least two uniquely named
+    /// variables of equal, non-zero length.
+    pub fn new(names: Vec<String>, columns: Vec<Column>) -> Result<Self, String> {
+        if names.len() != columns.len() {
+            return Err(format!(
+                "{} variable names for {} columns",
+                names.len(),
+                columns.len()
+            ));
+        }
+        if names.len() < 2 {
+            return Err("Causal discovery needs at least two variables".to_string());
+        }
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(format!("Duplicate variable name '{}'", name));
+            }
+        }
+        let rows = columns[0].len();
+        if rows == 0 {
+            return Err("Dataset has no rows".to_string());
+        }
+        if let Some((name, column)) = names.iter().zip(&columns).find(|(_, c)| c.len() != rows) {
+            return Err(format!(
+                "Variable '{}' has {} rows, expected {}",
+                name,
+                column.len(),
+                rows
+            ));
+        }
+        Ok(Self {
+            names,
+            columns,
+            weights: vec![1.0; rows],
+        })
+    }
+
+    /// Replace the row weights (e.g. observation counts)
+    pub fn with_weights(mut self, weights: Vec<f64>) -> Result<Self, String> {
+        if weights.len() != self.rows() {
+            return Err(format!(
+                "{} weights for {} rows",
+                weights.len(),
+                self.rows()
+            ));
+        }
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err("Row weights must be finite and non-negative".to_string());
+        }
+        if weights.iter().sum::<f64>() <= 0.0 {
+            return Err("Row weights sum to zero".to_string());
+        }
+        self.weights = weights;
+        Ok(self)
+    }
+
+    /// One variable per entry of `fields`, read from each record's metadata.
+    ///
+    /// A field starting with `/` is a JSON pointer (`/sensor/temp`), anything
+    /// else a top-level key. Variables whose values are all numbers are
+    /// continuous; strings, booleans or mixed values make a discrete variable.
+    /// Records missing any field, or holding a null, array or object there,
+    /// are skipped.
+    pub fn from_records(records: &[MemoryRecord], fields: &[&str]) -> Result<Self, String> {
+        let mut values: Vec<Vec<RawValue>> = fields.iter().map(|_| Vec::new()).collect();
+        for record in records {
+            let row: Option<Vec<RawValue>> = fields
+                .iter()
+                .map(|field| {
+                    let value = if field.starts_with('/') {
+                        record.metadata.pointer(field)
+                    } else {
+                        record.metadata.get(*field)
+                    }?;
+                    match value {
+                        serde_json::Value::Number(n) => {
+                            Some(RawValue::Number(n.as_f64()?, n.to_string()))
+                        }
+                        serde_json::Value::Bool(b) => Some(RawValue::Label(b.to_string())),
+                        serde_json::Value::String(s) => Some(RawValue::Label(s.clone())),
+                        _ => None,
+                    }
+                })
+                .collect();
+            if let Some(row) = row {
+                for (column, value) in values.iter_mut().zip(row) {
+                    column.push(value);
+                }
+            }
+        }
+        if values.first().is_none_or(|c| c.is_empty()) {
+            return Err(format!(
+                "No memory record has all of the fields {:?}",
+                fields
+            ));
+        }
+        let columns = values
+            .into_iter()
+            .map(|column| {
+                if column.iter().all(|v| matches!(v, RawValue::Number(..))) {
+                    Column::Continuous(
+                        column
+                            .iter()
+                            .map(|v| match v {
+                                RawValue::Number(x, _) => *x,
+                                RawValue::Label(_) => 0.0,
+                            })
+                            .collect(),
+                    )
+                } else {
+                    Column::discrete(column.into_iter().map(|v| match v {
+                        RawValue::Number(_, text) | RawValue::Label(text) => text,
+                    }))
+                }
+            })
+            .collect();
+        Self::new(fields.iter().map(|f| f.to_string()).collect(), columns)
+    }
+
+    /// Variables `state`, `action` and `next_state` (the node names used by
+    /// `CausalGraph::auto_populate_from_transitions`), one row per observed
+    /// triple weighted by its count.
+    pub fn from_transitions(model: &TransitionModel) -> Result<Self, String> {
+        let counts: BTreeMap<_, _> = model.counts.iter().filter(|(_, &c)| c > 0).collect();
+        let weights = counts.values().map(|&&c| c as f64).collect();
+        let columns = vec![
+            Column::discrete(counts.keys().map(|(s, _, _)| s.as_str())),
+            Column::discrete(counts.keys().map(|(_, a, _)| a.as_str())),
+            Column::discrete(counts.keys().map(|(_, _, ns)| ns.as_str())),
+        ];
+        Self::new(
+            vec!["state".into(), "action".into(), "next_state".into()],
+            columns,
+        )
+        .map_err(|e| format!("Transition model: {}", e))?
+        .with_weights(weights)
+    }
+
+    pub fn variables(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn rows(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Sum of row weights, the effective sample size
+    pub fn total_weight(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+
+    fn all_continuous(&self) -> bool {
+        self.columns
+            .iter()
+            .all(|c| matches!(c, Column::Continuous(_)))
+    }
+
+    /// Weighted covariance matrix of an all-continuous dataset
+    fn covariance(&self) -> DMatrix<f64> {
+        let total = self.total_weight();
+        let values: Vec<&[f64]> = self
+            .columns
+            .iter()
+            .map(|c| match c {
+                Column::Continuous(v) => v.as_slice(),
+                Column::Discrete { .. } => &[],
+            })
+            .collect();
+        let means: Vec<f64> = values
+            .iter()
+            .map(|v| v.iter().zip(&self.weights).map(|(x, w)| x * w).sum::<f64>() / total)
+            .collect();
+        let d = values.len();
+        DMatrix::from_fn(d, d, |i, j| {
+            (0..self.rows())
+                .map(|r| self.weights[r] * (values[i][r] - means[i]) * (values[j][r] - means[j]))
+                .sum::<f64>()
+                / total
+        })
+    }
+
+    fn discrete(&self) -> DiscreteData {
+        let (codes, levels) = self
+            .columns
+            .iter()
+            .map(|c| c.to_codes(DISCRETIZATION_BINS))
+            .unzip();
+        DiscreteData {
+            codes,
+            levels,
+            weights: self.weights.clone(),
+        }
+    }
+
+    /// Bootstrap resample: as many draws as the effective sample size, rows
+    /// picked in proportion to their weight
+    fn resample(&self, rng: &mut StdRng) -> Result<Dataset, String> {
+        let picker = WeightedIndex::new(&self.weights).map_err(|e| e.to_string())?;
+        let draws = self.total_weight().round().max(1.0) as usize;
+        let mut hits = vec![0usize; self.rows()];
+        for _ in 0..draws {
+            hits[picker.sample(rng)] += 1;
+        }
+        let rows: Vec<usize> = (0..self.rows()).filter(|&r| hits[r] > 0).collect();
+        Ok(Dataset {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c.select(&rows)).collect(),
+            weights: rows.iter().map(|&r| hits[r] as f64).collect(),
+        })
+    }
+}
+
+/// Integer-coded view of a dataset used by G² and the multinomial score.
+struct DiscreteData {
+    codes: Vec<Vec<usize>>,
+    levels: Vec<usize>,
+    weights: Vec<f64>,
+}
+
+impl DiscreteData {
+    fn config(&self, vars: &[usize], row: usize) -> Vec<usize> {
+        vars.iter().map(|&v| self.codes[v][row]).collect()
+    }
+}
+
+// ============================================================================
+// Methods and results
+// ============================================================================
+
+/// Conditional independence test used by PC.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiTest {
+    /// Fisher-z on partial correlations; continuous variables only
+    FisherZ,
+    /// Likelihood-ratio G² on contingency tables stratified by the
+    /// conditioning set; continuous variables are binned at quantiles
+    GSquare,
+    /// Fisher-z when every variable is continuous, otherwise G²
+    Auto,
+}
+
+/// Structure learning algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiscoveryMethod {
+    /// Constraint-based: drop edges between variables found independent
+    /// (p > alpha) given some subset of neighbours, orient colliders, then
+    /// propagate orientations with Meek's rules
+    Pc {
+        alpha: f64,
+        max_condition_size: usize,
+        test: CiTest,
+    },
+    /// Score-based: Greedy Equivalence Search, inserting then deleting edges
+    /// while BIC improves; `penalty_discount` scales the complexity penalty
+    Ges { penalty_discount: f64 },
+}
+
+impl DiscoveryMethod {
+    /// PC with alpha 0.05, conditioning sets of up to 3 variables, automatic test
+    pub fn pc() -> Self {
+        DiscoveryMethod::Pc {
+            alpha: 0.05,
+            max_condition_size: 3,
+            test: CiTest::Auto,
+        }
+    }
+
+    /// GES with the plain BIC penalty
+    pub fn ges() -> Self {
+        DiscoveryMethod::Ges {
+            penalty_discount: 1.0,
+        }
+    }
+}
+
+/// Edge of a discovered CPDAG.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredEdge {
+    pub from: String,
+    pub to: String,
+    /// False when every DAG in the equivalence class is consistent with
+    /// either direction; `from`/`to` then only follow variable order
+    pub directed: bool,
+    /// Confidence in [0, 1]: 1 − the largest p-value seen for the pair (PC),
+    /// the logistic of the edge's BIC gain (GES), or the bootstrap frequency
+    /// of this exact mark
+    pub confidence: f64,
+}
+
+/// Completed partially directed acyclic graph: a Markov equivalence class.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cpdag {
+    pub variables: Vec<String>,
+    pub edges: Vec<DiscoveredEdge>,
+}
+
+/// Outcome of accepting discovered edges into a `CausalGraph`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AcceptReport {
+    /// Directed edges added to the graph
+    pub added: Vec<(String, String)>,
+    /// Confident edges left out because their direction is undetermined;
+    /// orient them with `Cpdag::accept_edge`
+    pub undirected: Vec<(String, String)>,
+    /// Directed edges refused by the graph, with the reason
+    pub rejected: Vec<(String, String, String)>,
+}
+
+impl Cpdag {
+    /// Edge between `a` and `b` in either direction
+    pub fn edge(&self, a: &str, b: &str) -> Option<&DiscoveredEdge> {
+        self.edges
+            .iter()
+            .find(|e| (e.from == a && e.to == b) || (e.from == b && e.to == a))
+    }
+
+    pub fn directed_edges(&self) -> impl Iterator<Item = &DiscoveredEdge> {
+        self.edges.iter().filter(|e| e.directed)
+    }
+
+    pub fn undirected_edges(&self) -> impl Iterator<Item = &DiscoveredEdge> {
+        self.edges.iter().filter(|e| !e.directed)
+    }
+
+    /// Whether this CPDAG has `edge` with the same orientation status
+    fn has_mark(&self, edge: &DiscoveredEdge) -> bool {
+        match self.edge(&edge.from, &edge.to) {
+            Some(e) if edge.directed => e.directed && e.from == edge.from,
+            Some(e) => !e.directed,
+            None => false,
+        }
+    }
+
+    /// Add every directed edge with confidence ≥ `min_confidence` to `graph`,
+    /// using the confidence as its strength. Edges that would close a cycle
+    /// with what the graph already holds are rejected, not forced.
+    pub fn accept_into(&self, graph: &mut CausalGraph, min_confidence: f64) -> AcceptReport {
+        let mut report = AcceptReport::default();
+        for edge in self.edges.iter().filter(|e| e.confidence >= min_confidence) {
+            let pair = (edge.from.clone(), edge.to.clone());
+            if !edge.directed {
+                report.undirected.push(pair);
+                continue;
+            }
+            match graph.add_edge_with_strength(pair.0.clone(), pair.1.clone(), edge.confidence) {
+                Ok(()) => report.added.push(pair),
+                Err(reason) => report.rejected.push((pair.0, pair.1, reason)),
+            }
+        }
+        report
+    }
+
+    /// Add the edge between `from` and `to` as `from → to`. Undirected edges
+    /// may be oriented either way; a directed edge must keep its direction.
+    pub fn accept_edge(&self, graph: &mut CausalGraph, from: &str, to: &str) -> Result<(), String> {
+        let edge = self
+            .edge(from, to)
+            .ok_or_else(|| format!("No discovered edge between {} and {}", from, to))?;
+        if edge.directed && edge.from != from {
+            return Err(format!(
+                "Discovered edge is oriented {} → {}",
+                edge.from, edge.to
+            ));
+        }
+        graph.add_edge_with_strength(from.to_string(), to.to_string(), edge.confidence)
+    }
+}
+
+/// Learn a CPDAG over all variables in `data`
+pub fn discover(data: &Dataset, method: &DiscoveryMethod) -> Result<Cpdag, String> {
+    let (pdag, confidence) = match *method {
+        DiscoveryMethod::Pc {
+            alpha,
+            max_condition_size,
+            test,
+        } => {
+            if !(0.0..1.0).contains(&alpha) {
+                return Err(format!("PC alpha must be in [0, 1), got {}", alpha));
+            }
+            pc(data, alpha, max_condition_size, test)?
+        }
+        DiscoveryMethod::Ges { penalty_discount } => {
+            if penalty_discount.is_nan() || penalty_discount <= 0.0 {
+                return Err(format!(
+                    "GES penalty discount must be positive, got {}",
+                    penalty_discount
+                ));
+            }
+            ges(data, penalty_discount)
+        }
+    };
+    Ok(pdag.to_cpdag(&data.names, &confidence))
+}
+
+/// `discover`, with each edge's confidence replaced by the fraction of
+/// `resamples` bootstrap datasets whose CPDAG has the same edge mark
+pub fn discover_bootstrap(
+    data: &Dataset,
+    method: &DiscoveryMethod,
+    resamples: usize,
+    seed: u64,
+) -> Result<Cpdag, String> {
+    let mut result = discover(data, method)?;
+    if resamples == 0 {
+        return Ok(result);
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut support = vec![0usize; result.edges.len()];
+    for _ in 0..resamples {
+        let sample = discover(&data.resample(&mut rng)?, method)?;
+        for (hits, edge) in support.iter_mut().zip(&result.edges) {
+            if sample.has_mark(edge) {
+                *hits += 1;
+            }
+        }
+    }
+    for (edge, hits) in result.edges.iter_mut().zip(support) {
+        edge.confidence = hits as f64 / resamples as f64;
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// Partially directed graphs
+// ============================================================================
+
+/// Adjacency plus arrow marks; an adjacent pair without a mark either way
+/// is undirected.
+#[derive(Debug, Clone)]
+struct Pdag {
+    adjacent: Vec<Vec<bool>>,
+    arrow: Vec<Vec<bool>>,
+}
+
+impl Pdag {
+    fn empty(n: usize) -> Self {
+        Self {
+            adjacent: vec![vec![false; n]; n],
+            arrow: vec![vec![false; n]; n],
+        }
+    }
+
+    fn complete(n: usize) -> Self {
+        let adjacent = (0..n).map(|i| (0..n).map(|j| i != j).collect()).collect();
+        Self {
+            adjacent,
+            arrow: vec![vec![false; n]; n],
+        }
+    }
+
+    /// Pattern of a DAG given as parent lists: skeleton, v-structures, then Meek
+    fn from_dag(parents: &[Vec<usize>]) -> Self {
+        let mut pdag = Self::empty(parents.len());
+        for (child, pa) in parents.iter().enumerate() {
+            for &p in pa {
+                pdag.adjacent[p][child] = true;
+                pdag.adjacent[child][p] = true;
+            }
+        }
+        for (child, pa) in parents.iter().enumerate() {
+            for (k, &a) in pa.iter().enumerate() {
+                for &b in &pa[k + 1..] {
+                    if !pdag.adjacent[a][b] {
+                        pdag.orient(a, child);
+                        pdag.orient(b, child);
+                    }
+                }
+            }
+        }
+        pdag.apply_meek();
+        pdag
+    }
+
+    fn len(&self) -> usize {
+        self.adjacent.len()
+    }
+
+    fn remove(&mut self, a: usize, b: usize) {
+        self.adjacent[a][b] = false;
+        self.adjacent[b][a] = false;
+        self.arrow[a][b] = false;
+        self.arrow[b][a] = false;
+    }
+
+    fn parents(&self, node: usize) -> Vec<usize> {
+        (0..self.len()).filter(|&p| self.arrow[p][node]).collect()
+    }
+
+    /// Nodes joined to `node` by an undirected edge
+    fn neighbours(&self, node: usize) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&k| self.undirected(node, k))
+            .collect()
+    }
+
+    fn is_clique(&self, nodes: &[usize]) -> bool {
+        nodes
+            .iter()
+            .enumerate()
+            .all(|(i, &a)| nodes[i + 1..].iter().all(|&b| self.adjacent[a][b]))
+    }
+
+    /// Whether every semi-directed path from `from` to `to` passes through
+    /// `blocked`
+    fn blocks(&self, from: usize, to: usize, blocked: &[usize]) -> bool {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![from];
+        seen[from] = true;
+        while let Some(node) = stack.pop() {
+            let steps: Vec<usize> = (0..self.len())
+                .filter(|&next| {
+                    !seen[next]
+                        && !blocked.contains(&next)
+                        && self.adjacent[node][next]
+                        && !self.arrow[next][node]
+                })
+                .collect();
+            for next in steps {
+                if next == to {
+                    return false;
+                }
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+        true
+    }
+
+    /// A DAG (as parent lists) in the class this PDAG represents, or `None`
+    /// if it has no consistent extension (Dor & Tarsi 1992)
+    fn extension(&self) -> Option<Vec<Vec<usize>>> {
+        let n = self.len();
+        let mut parents: Vec<Vec<usize>> = (0..n).map(|v| self.parents(v)).collect();
+        let mut alive = vec![true; n];
+        for _ in 0..n {
+            let sink = (0..n).find(|&x| {
+                alive[x]
+                    && !(0..n).any(|c| alive[c] && self.arrow[x][c])
+                    && (0..n)
+                        .filter(|&y| alive[y] && self.undirected(x, y))
+                        .all(|y| {
+                            (0..n).all(|z| {
+                                z == y
+                                    || z == x
+                                    || !alive[z]
+                                    || !self.adjacent[x][z]
+                                    || self.adjacent[y][z]
+                            })
+                        })
+            })?;
+            for y in (0..n).filter(|&y| alive[y] && self.undirected(sink, y)) {
+                parents[sink].push(y);
+            }
+            alive[sink] = false;
+        }
+        Some(parents)
+    }
+
+    fn undirected(&self, a: usize, b: usize) -> bool {
+        self.adjacent[a][b] && !self.arrow[a][b] && !self.arrow[b][a]
+    }
+
+    fn orient(&mut self, from: usize, to: usize) {
+        self.arrow[from][to] = true;
+        self.arrow[to][from] = false;
+    }
+
+    /// Orient x → z ← y for every unshielded x – z – y with z outside the
+    /// separating set of x and y; arrows already pointing out of z are kept
+    fn orient_colliders(&mut self, sepsets: &HashMap<(usize, usize), Vec<usize>>) {
+        let n = self.len();
+        for z in 0..n {
+            for x in 0..n {
+                for y in x + 1..n {
+                    if !self.adjacent[x][z] || !self.adjacent[y][z] || self.adjacent[x][y] {
+                        continue;
+                    }
+                    if sepsets.get(&(x, y)).is_some_and(|s| s.contains(&z)) {
+                        continue;
+                    }
+                    if !self.arrow[z][x] && !self.arrow[z][y] {
+                        self.orient(x, z);
+                        self.orient(y, z);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Meek rules R1–R3 until no undirected edge can be oriented
+    fn apply_meek(&mut self) {
+        let n = self.len();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for a in 0..n {
+                for b in 0..n {
+                    if !self.undirected(a, b) {
+                        continue;
+                    }
+                    // R1: c → a – b with c, b non-adjacent ⇒ a → b
+                    let r1 = (0..n).any(|c| c != b && self.arrow[c][a] && !self.adjacent[c][b]);
+                    // R2: a → c → b with a – b ⇒ a → b
+                    let r2 = (0..n).any(|c| self.arrow[a][c] && self.arrow[c][b]);
+                    // R3: a – c → b and a – d → b with c, d non-adjacent ⇒ a → b
+                    let r3 = (0..n).any(|c| {
+                        self.undirected(a, c)
+                            && self.arrow[c][b]
+                            && (c + 1..n).any(|d| {
+                                self.undirected(a, d) && self.arrow[d][b] && !self.adjacent[c][d]
+                            })
+                    });
+                    if r1 || r2 || r3 {
+                        self.orient(a, b);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_cpdag(&self, names: &[String], confidence: &[Vec<f64>]) -> Cpdag {
+        let n = self.len();
+        let mut edges = Vec::new();
+        for (a, row) in self.adjacent.iter().enumerate() {
+            for b in (a + 1..n).filter(|&b| row[b]) {
+                let (from, to) = if self.arrow[b][a] { (b, a) } else { (a, b) };
+                edges.push(DiscoveredEdge {
+                    from: names[from].clone(),
+                    to: names[to].clone(),
+                    directed: !self.undirected(a, b),
+                    confidence: confidence[a][b].clamp(0.0, 1.0),
+                });
+            }
+        }
+        Cpdag {
+            variables: names.to_vec(),
+            edges,
+        }
+    }
+}
+
+// ============================================================================
+// PC
+// ============================================================================
+
+enum Tester {
+    FisherZ { correlation: DMatrix<f64>, n: f64 },
+    GSquare(DiscreteData),
+}
+
+impl Tester {
+    fn new(data: &Dataset, test: CiTest) -> Result<Self, String> {
+        let fisher = match test {
+            CiTest::FisherZ if !data.all_continuous() => {
+                return Err(
+                    "Fisher-z needs continuous variables; use CiTest::GSquare or CiTest::Auto"
+                        .to_string(),
+                )
+            }
+            CiTest::FisherZ => true,
+            CiTest::GSquare => false,
+            CiTest::Auto => data.all_continuous(),
+        };
+        if !fisher {
+            return Ok(Tester::GSquare(data.discrete()));
+        }
+        let cov = data.covariance();
+        let d = cov.nrows();
+        let correlation = DMatrix::from_fn(d, d, |i, j| {
+            let scale = (cov[(i, i)] * cov[(j, j)]).sqrt();
+            if i == j {
+                1.0
+            } else if scale > 0.0 {
+                cov[(i, j)] / scale
+            } else {
+                0.0
+            }
+        });
+        Ok(Tester::FisherZ {
+            correlation,
+            n: data.total_weight(),
+        })
+    }
+
+    /// p-value of the hypothesis x ⊥ y | s
+    fn p_value(&self, x: usize, y: usize, s: &[usize]) -> f64 {
+        match self {
+            Tester::FisherZ { correlation, n } => fisher_z(correlation, *n, x, y, s),
+            Tester::GSquare(data) => g_square(data, x, y, s),
+        }
+    }
+}
+
+fn fisher_z(correlation: &DMatrix<f64>, n: f64, x: usize, y: usize, s: &[usize]) -> f64 {
+    let dof = n - s.len() as f64 - 3.0;
+    if dof <= 0.0 {
+        return 1.0;
+    }
+    let vars: Vec<usize> = [x, y].iter().chain(s).copied().collect();
+    let k = vars.len();
+    let sub = DMatrix::from_fn(k, k, |i, j| {
+        correlation[(vars[i], vars[j])] + if i == j { RIDGE } else { 0.0 }
+    });
+    let Some(precision) = sub.try_inverse() else {
+        return 1.0;
+    };
+    let r = (-precision[(0, 1)] / (precision[(0, 0)] * precision[(1, 1)]).sqrt())
+        .clamp(-0.999_999, 0.999_999);
+    let z = 0.5 * ((1.0 + r) / (1.0 - r)).ln() * dof.sqrt();
+    // Two-sided normal tail: erfc(|z|/√2) = Q(1/2, z²/2)
+    regularized_gamma_q(0.5, 0.5 * z * z)
+}
+
+fn g_square(data: &DiscreteData, x: usize, y: usize, s: &[usize]) -> f64 {
+    let mut strata: HashMap<Vec<usize>, HashMap<(usize, usize), f64>> = HashMap::new();
+    for (row, &w) in data.weights.iter().enumerate() {
+        if w > 0.0 {
+            *strata
+                .entry(data.config(s, row))
+                .or_default()
+                .entry((data.codes[x][row], data.codes[y][row]))
+                .or_default() += w;
+        }
+    }
+    let mut statistic = 0.0;
+    let mut dof = 0.0;
+    for table in strata.values() {
+        let mut x_totals: HashMap<usize, f64> = HashMap::new();
+        let mut y_totals: HashMap<usize, f64> = HashMap::new();
+        for (&(a, b), &count) in table {
+            *x_totals.entry(a).or_default() += count;
+            *y_totals.entry(b).or_default() += count;
+        }
+        let total: f64 = x_totals.values().sum();
+        for (&(a, b), &count) in table {
+            statistic += 2.0 * count * (count * total / (x_totals[&a] * y_totals[&b])).ln();
+        }
+        dof += ((x_totals.len() - 1) * (y_totals.len() - 1)) as f64;
+    }
+    if dof == 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(0.5 * dof, 0.5 * statistic.max(0.0))
+}
+
+/// PC-stable: adjacency sets are frozen at the start of each conditioning
+/// level so the skeleton does not depend on variable order
+fn pc(
+    data: &Dataset,
+    alpha: f64,
+    max_condition_size: usize,
+    test: CiTest,
+) -> Result<(Pdag, Vec<Vec<f64>>), String> {
+    let tester = Tester::new(data, test)?;
+    let n = data.names.len();
+    let mut pdag = Pdag::complete(n);
+    let mut sepsets: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    let mut max_p = vec![vec![0.0f64; n]; n];
+
+    for level in 0..=max_condition_size {
+        let frozen = pdag.adjacent.clone();
+        let mut testable = false;
+        for x in 0..n {
+            for y in (0..n).filter(|&y| y != x) {
+                if !pdag.adjacent[x][y] {
+                    continue;
+                }
+                let neighbours: Vec<usize> = (0..n).filter(|&k| k != y && frozen[x][k]).collect();
+                if neighbours.len() < level {
+                    continue;
+                }
+                testable = true;
+                for s in combinations(&neighbours, level) {
+                    let p = tester.p_value(x, y, &s);
+                    max_p[x][y] = max_p[x][y].max(p);
+                    max_p[y][x] = max_p[x][y];
+                    if p > alpha {
+                        pdag.remove(x, y);
+                        sepsets.insert((x.min(y), x.max(y)), s);
+                        break;
+                    }
+                }
+            }
+        }
+        if !testable {
+            break;
+        }
+    }
+
+    pdag.orient_colliders(&sepsets);
+    pdag.apply_meek();
+    let confidence = max_p
+        .iter()
+        .map(|row| row.iter().map(|p| 1.0 - p).collect())
+        .collect();
+    Ok((pdag, confidence))
+}
+
+/// All `k`-element subsets of `items`, in lexicographic order
+fn combinations(items: &[usize], k: usize) -> Vec<Vec<usize>> {
+    let mut out = Vec::new();
+    if k > items.len() {
+        return out;
+    }
+    let mut idx: Vec<usize> = (0..k).collect();
+    loop {
+        out.push(idx.iter().map(|&i| items[i]).collect());
+        let Some(i) = (0..k).rev().find(|&i| idx[i] != i + items.len() - k) else {
+            return out;
+        };
+        idx[i] += 1;
+        for j in i + 1..k {
+            idx[j] = idx[j - 1] + 1;
+        }
+    }
+}
+
+// ============================================================================
+// Greedy Equivalence Search
+// ============================================================================
+
+enum ScoreData {
+    Gaussian(DMatrix<f64>),
+    Multinomial(DiscreteData),
+}
+
+/// Decomposable BIC: linear-Gaussian when every variable is continuous,
+/// multinomial (over binned values) otherwise
+struct BicScore {
+    data: ScoreData,
+    n: f64,
+    penalty: f64,
+    cache: HashMap<(usize, Vec<usize>), f64>,
+}
+
+impl BicScore {
+    fn new(data: &Dataset, penalty_discount: f64) -> Self {
+        let n = data.total_weight();
+        let data = if data.all_continuous() {
+            ScoreData::Gaussian(data.covariance())
+        } else {
+            ScoreData::Multinomial(data.discrete())
+        };
+        Self {
+            data,
+            n,
+            penalty: penalty_discount * 0.5 * n.max(1.0).ln(),
+            cache: HashMap::new(),
+        }
+    }
+
+    fn local(&mut self, node: usize, parents: &[usize]) -> f64 {
+        let mut key = parents.to_vec();
+        key.sort_unstable();
+        if let Some(&score) = self.cache.get(&(node, key.clone())) {
+            return score;
+        }
+        let score = match &self.data {
+            ScoreData::Gaussian(cov) => {
+                let k = key.len();
+                let mut variance = cov[(node, node)];
+                if k > 0 {
+                    let c = DMatrix::from_fn(k, k, |i, j| {
+                        cov[(key[i], key[j])] + if i == j { RIDGE } else { 0.0 }
+                    });
+                    let b = DMatrix::from_fn(k, 1, |i, _| cov[(key[i], node)]);
+                    if let Some(inv) = c.try_inverse() {
+                        variance -= (b.transpose() * inv * &b)[(0, 0)];
+                    }
+                }
+                -0.5 * self.n * variance.max(1e-12).ln() - self.penalty * (k + 1) as f64
+            }
+            ScoreData::Multinomial(data) => {
+                let mut counts: HashMap<(Vec<usize>, usize), f64> = HashMap::new();
+                let mut parent_counts: HashMap<Vec<usize>, f64> = HashMap::new();
+                for (row, &w) in data.weights.iter().enumerate() {
+                    if w > 0.0 {
+                        let config = data.config(&key, row);
+                        *counts
+                            .entry((config.clone(), data.codes[node][row]))
+                            .or_default() += w;
+                        *parent_counts.entry(config).or_default() += w;
+                    }
+                }
+                let log_likelihood: f64 = counts
+                    .iter()
+                    .map(|((config, _), &count)| count * (count / parent_counts[config]).ln())
+                    .sum();
+                let configs: f64 = key.iter().map(|&p| data.levels[p] as f64).product();
+                let params = (data.levels[node].saturating_sub(1)) as f64 * configs;
+                log_likelihood - self.penalty * params
+            }
+        };
+        self.cache.insert((node, key), score);
+        score
+    }
+}
+
+/// A GES operator: insert or delete the edge x – y, orienting the `set`
+/// neighbours of y as the operator requires
+struct Operator {
+    gain: f64,
+    x: usize,
+    y: usize,
+    set: Vec<usize>,
+}
+
+/// Best valid Insert(x, y, T): x, y non-adjacent, T ⊆ undirected neighbours
+/// of y not adjacent to x, NA ∪ T a clique, and every semi-directed path
+/// from y to x blocked by NA ∪ T (Chickering 2002)
+fn best_insert(pdag: &Pdag, score: &mut BicScore) -> Option<Operator> {
+    let n = pdag.len();
+    let mut best: Option<Operator> = None;
+    for y in 0..n {
+        let parents = pdag.parents(y);
+        let neighbours = pdag.neighbours(y);
+        for x in (0..n).filter(|&x| x != y && !pdag.adjacent[x][y]) {
+            let (na, candidates): (Vec<usize>, Vec<usize>) =
+                neighbours.iter().partition(|&&t| pdag.adjacent[t][x]);
+            for size in 0..=candidates.len().min(MAX_OPERATOR_SET) {
+                for t in combinations(&candidates, size) {
+                    let conditioning: Vec<usize> = na.iter().chain(&t).copied().collect();
+                    if !pdag.is_clique(&conditioning) || !pdag.blocks(y, x, &conditioning) {
+                        continue;
+                    }
+                    let base: Vec<usize> = conditioning.iter().chain(&parents).copied().collect();
+                    let mut with = base.clone();
+                    with.push(x);
+                    let gain = score.local(y, &with) - score.local(y, &base);
+                    if gain > SCORE_EPSILON && best.as_ref().is_none_or(|b| gain > b.gain) {
+                        best = Some(Operator { gain, x, y, set: t });
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Best valid Delete(x, y, H): x → y or x – y, H ⊆ NA (undirected
+/// neighbours of y adjacent to x) with NA \ H a clique
+fn best_delete(pdag: &Pdag, score: &mut BicScore) -> Option<Operator> {
+    let n = pdag.len();
+    let mut best: Option<Operator> = None;
+    for y in 0..n {
+        let parents = pdag.parents(y);
+        let neighbours = pdag.neighbours(y);
+        for x in (0..n).filter(|&x| pdag.adjacent[x][y] && !pdag.arrow[y][x]) {
+            let na: Vec<usize> = neighbours
+                .iter()
+                .copied()
+                .filter(|&h| h != x && pdag.adjacent[h][x])
+                .collect();
+            for size in 0..=na.len().min(MAX_OPERATOR_SET) {
+                for h in combinations(&na, size) {
+                    let rest: Vec<usize> = na.iter().copied().filter(|v| !h.contains(v)).collect();
+                    if !pdag.is_clique(&rest) {
+                        continue;
+                    }
+                    let base: Vec<usize> = rest
+                        .iter()
+                        .chain(&parents)
+                        .copied()
+                        .filter(|&v| v != x)
+                        .collect();
+                    let mut with = base.clone();
+                    with.push(x);
+                    let gain = score.local(y, &base) - score.local(y, &with);
+                    if gain > SCORE_EPSILON && best.as_ref().is_none_or(|b| gain > b.gain) {
+                        best = Some(Operator { gain, x, y, set: h });
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Greedy Equivalence Search: insert edges while BIC improves, then delete
+/// them while it improves, re-completing the CPDAG after every operator
+fn ges(data: &Dataset, penalty_discount: f64) -> (Pdag, Vec<Vec<f64>>) {
+    let n = data.names.len();
+    let mut score = BicScore::new(data, penalty_discount);
+    let mut pdag = Pdag::empty(n);
+
+    for _ in 0..MAX_SEARCH_STEPS {
+        let Some(op) = best_insert(&pdag, &mut score) else {
+            break;
+        };
+        let mut next = pdag.clone();
+        next.adjacent[op.x][op.y] = true;
+        next.adjacent[op.y][op.x] = true;
+        next.orient(op.x, op.y);
+        for &t in &op.set {
+            next.orient(t, op.y);
+        }
+        match next.extension() {
+            Some(dag) => pdag = Pdag::from_dag(&dag),
+            None => break,
+        }
+    }
+
+    for _ in 0..MAX_SEARCH_STEPS {
+        let Some(op) = best_delete(&pdag, &mut score) else {
+            break;
+        };
+        let mut next = pdag.clone();
+        next.remove(op.x, op.y);
+        for &h in &op.set {
+            next.orient(op.y, h);
+            if next.undirected(op.x, h) {
+                next.orient(op.x, h);
+            }
+        }
+        match next.extension() {
+            Some(dag) => pdag = Pdag::from_dag(&dag),
+            None => break,
+        }
+    }
+
+    // Edge confidence: logistic of the BIC lost by dropping the edge from
+    // one DAG of the class
+    let dag = pdag.extension().unwrap_or_else(|| vec![Vec::new(); n]);
+    let mut confidence = vec![vec![0.0; n]; n];
+    for (j, parents) in dag.iter().enumerate() {
+        for &i in parents {
+            let without: Vec<usize> = parents.iter().copied().filter(|&p| p != i).collect();
+            let gain = score.local(j, parents) - score.local(j, &without);
+            let c = 1.0 / (1.0 + (-gain).exp());
+            confidence[i][j] = c;
+            confidence[j][i] = c;
+        }
+    }
+    (pdag, confidence)
+}
+
+// ============================================================================
+// Distribution tails
+// ============================================================================
+
+/// ln Γ(x) for x > 0 (Lanczos, g = 7)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFS[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Upper regularized incomplete gamma Q(a, x) = Γ(a, x) / Γ(a); the χ²
+/// survival function with k degrees of freedom is Q(k/2, x/2)
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series for P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..500 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Lentz continued fraction for Q(a, x)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..500 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        (prefix * h).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_record::MemoryType;
+    use rand::Rng;
+
+    fn gaussian(rng: &mut StdRng) -> f64 {
+        let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+        let u2: f64 = rng.gen();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    fn continuous(names: &[&str], columns: Vec<Vec<f64>>) -> Dataset {
+        Dataset::new(
+            names.iter().map(|n| n.to_string()).collect(),
+            columns.into_iter().map(Column::Continuous).collect(),
+        )
+        .unwrap()
+    }
+
+    /// x → z ← y
+    fn collider(rows: usize, seed: u64) -> Dataset {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (mut x, mut y, mut z) = (Vec::new(), Vec::new(), Vec::new());
+        for _ in 0..rows {
+            let a = gaussian(&mut rng);
+            let b = gaussian(&mut rng);
+            x.push(a);
+            y.push(b);
+            z.push(a + b + 0.5 * gaussian(&mut rng));
+        }
+        continuous(&["x", "y", "z"], vec![x, y, z])
+    }
+
+    fn directed(cpdag: &Cpdag, from: &str, to: &str) -> bool {
+        cpdag
+            .edge(from, to)
+            .is_some_and(|e| e.directed && e.from == from && e.to == to)
+    }
+
+    #[test]
+    fn test_distribution_tails() {
+        // χ²₁ critical value at 5%, two-sided normal at 5%
+        assert!((regularized_gamma_q(0.5, 3.841_459 / 2.0) - 0.05).abs() < 1e-4);
+        assert!((regularized_gamma_q(2.0, 9.487_729 / 2.0) - 0.05).abs() < 1e-4);
+        assert!((ln_gamma(5.0) - 24.0f64.ln()).abs() < 1e-10);
+        assert_eq!(
+            combinations(&[1, 2, 3], 2),
+            vec![vec![1, 2], vec![1, 3], vec![2, 3]]
+        );
+        assert_eq!(combinations(&[1, 2], 0), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn test_pc_orients_collider() {
+        let cpdag = discover(&collider(500, 7), &DiscoveryMethod::pc()).unwrap();
+        assert_eq!(cpdag.edges.len(), 2);
+        assert!(cpdag.edge("x", "y").is_none());
+        assert!(directed(&cpdag, "x", "z"));
+        assert!(directed(&cpdag, "y", "z"));
+        assert!(cpdag.edges.iter().all(|e| e.confidence > 0.99));
+    }
+
+    #[test]
+    fn test_pc_leaves_chain_undirected() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let (mut x, mut y, mut z) = (Vec::new(), Vec::new(), Vec::new());
+        for _ in 0..500 {
+            let a = gaussian(&mut rng);
+            let b = a + gaussian(&mut rng);
+            x.push(a);
+            y.push(b);
+            z.push(b + gaussian(&mut rng));
+        }
+        let cpdag = discover(
+            &continuous(&["x", "y", "z"], vec![x, y, z]),
+            &DiscoveryMethod::pc(),
+        )
+        .unwrap();
+        assert!(cpdag.edge("x", "z").is_none());
+        assert_eq!(cpdag.undirected_edges().count(), 2);
+        assert_eq!(cpdag.directed_edges().count(), 0);
+    }
+
+    #[test]
+    fn test_ges_matches_pc_on_collider() {
+        let cpdag = discover(&collider(500, 3), &DiscoveryMethod::ges()).unwrap();
+        assert_eq!(cpdag.edges.len(), 2);
+        assert!(directed(&cpdag, "x", "z"));
+        assert!(directed(&cpdag, "y", "z"));
+        assert!(cpdag.edges.iter().all(|e| e.confidence > 0.99));
+    }
+
+    #[test]
+    fn test_discovery_from_record_metadata() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut records = Vec::new();
+        for _ in 0..1500 {
+            let rain = rng.gen_bool(0.3);
+            let sprinkler = rng.gen_bool(0.4);
+            let wet = (rain || sprinkler) != rng.gen_bool(0.05);
+            records.push(MemoryRecord::new(
+                MemoryType::Temporal,
+                "garden".into(),
+                "observe".into(),
+                "lawn".into(),
+                serde_json::json!({
+                    "weather": if rain { "rain" } else { "dry" },
+                    "sprinkler": sprinkler,
+                    "lawn": { "wet": wet },
+                }),
+            ));
+        }
+        // Records missing a field are skipped
+        records.push(MemoryRecord::new(
+            MemoryType::Temporal,
+            "garden".into(),
+            "observe".into(),
+            "lawn".into(),
+            serde_json::json!({ "weather": "rain" }),
+        ));
+        let data = Dataset::from_records(&records, &["weather", "sprinkler", "/lawn/wet"]).unwrap();
+        assert_eq!(data.rows(), 1500);
+        for method in [DiscoveryMethod::pc(), DiscoveryMethod::ges()] {
+            let cpdag = discover(&data, &method).unwrap();
+            assert!(cpdag.edge("weather", "sprinkler").is_none());
+            assert!(directed(&cpdag, "weather", "/lawn/wet"));
+            assert!(directed(&cpdag, "sprinkler", "/lawn/wet"));
+        }
+        assert!(discover(
+            &data,
+            &DiscoveryMethod::Pc {
+                alpha: 0.05,
+                max_condition_size: 1,
+                test: CiTest::FisherZ,
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_discovery_from_transition_counts() {
+        let mut model = TransitionModel::new();
+        for (state, action, hot, cold) in [
+            ("warm", "heat", 45, 5),
+            ("warm", "cool", 25, 25),
+            ("chilly", "heat", 25, 25),
+            ("chilly", "cool", 5, 45),
+        ] {
+            for (next, times) in [("hot", hot), ("cold", cold)] {
+                for _ in 0..times {
+                    model
+                        .record_transition(super::super::transition::StateTransition {
+                            from_state: state.into(),
+                            action: action.into(),
+                            to_state: next.into(),
+                        })
+                        .unwrap();
+                }
+            }
+        }
+        let data = Dataset::from_transitions(&model).unwrap();
+        assert_eq!(data.rows(), 8);
+        assert_eq!(data.total_weight(), 200.0);
+        let cpdag = discover(&data, &DiscoveryMethod::pc()).unwrap();
+        assert!(cpdag.edge("state", "action").is_none());
+        assert!(directed(&cpdag, "state", "next_state"));
+        assert!(directed(&cpdag, "action", "next_state"));
+    }
+
+    #[test]
+    fn test_bootstrap_confidence() {
+        let data = collider(300, 21);
+        let cpdag = discover_bootstrap(&data, &DiscoveryMethod::pc(), 20, 1).unwrap();
+        assert_eq!(cpdag.edges.len(), 2);
+        for edge in &cpdag.edges {
+            assert!(edge.confidence > 0.7, "{:?}", edge);
+            assert!(edge.confidence <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_accept_respects_acyclicity() {
+        let cpdag = discover(&collider(500, 7), &DiscoveryMethod::pc()).unwrap();
+        let mut graph = CausalGraph::new();
+        graph.add_edge("z".into(), "x".into()).unwrap();
+
+        let report = cpdag.accept_into(&mut graph, 0.9);
+        assert_eq!(report.added, vec![("y".to_string(), "z".to_string())]);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(
+            (report.rejected[0].0.as_str(), report.rejected[0].1.as_str()),
+            ("x", "z")
+        );
+        assert!(graph.is_acyclic());
+        let strength = graph
+            .all_edges()
+            .into_iter()
+            .find(|e| e.from == "y" && e.to == "z")
+            .unwrap()
+            .strength;
+        assert!(strength > 0.9);
+
+        // Directed edges keep their direction; undirected ones can be chosen
+        assert!(cpdag.accept_edge(&mut graph, "z", "y").is_err());
+        assert!(cpdag.accept_edge(&mut graph, "x", "y").is_err());
+        let chain = Cpdag {
+            variables: vec!["a".into(), "b".into()],
+            edges: vec![DiscoveredEdge {
+                from: "a".into(),
+                to: "b".into(),
+                directed: false,
+                confidence: 0.8,
+            }],
+        };
+        let mut graph = CausalGraph::new();
+        let report = chain.accept_into(&mut graph, 0.5);
+        assert!(report.added.is_empty());
+        assert_eq!(report.undirected.len(), 1);
+        chain.accept_edge(&mut graph, "b", "a").unwrap();
+        assert!(graph.has_path("b", "a").unwrap());
+    }
+}
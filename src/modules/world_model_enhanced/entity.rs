@@ -1,12 +1,20 @@
 // Entity Tracking with Kalman Filtering
 //
-// Implements optimal linear state estimation with predict/update cycle.
-// Detects anomalies using Mahalanobis distance and maintains entity permanence.
-
+// Implements Kalman-family state estimation with a predict/update cycle.
+// Motion and measurement models are pluggable (kinematic, matrix or
+// user-supplied functions); nonlinear models are handled by an extended
+// (linearised) or unscented (sigma-point) filter, and an interacting
+// multiple model (IMM) blends several motion hypotheses for maneuvering
+// entities. Detects anomalies using Mahalanobis distance and maintains
+// entity permanence.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Entity state representation (mean and covariance)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityState {
     /// State vector (position, velocity, etc.)
     pub properties: Vec<f64>,
@@ -36,25 +44,179 @@ pub struct Anomaly {
     pub description: String,
 }
 
+/// How well a candidate observation fits a tracker's predicted state; the
+/// basis for gating and data association.
+#[derive(Debug, Clone, Copy)]
+pub struct Innovation {
+    /// Squared Mahalanobis distance d² = y^T × S^-1 × y (smallest over IMM modes)
+    pub mahalanobis_sq: f64,
+
+    /// Gaussian log-likelihood ln p(z | track), mixed over IMM modes
+    pub log_likelihood: f64,
+}
+
+/// Caller-supplied state or measurement function `x ↦ y`.
+pub type StateFn = Arc<dyn Fn(&[f64]) -> Vec<f64> + Send + Sync>;
+
+/// A [`StateFn`] together with the name it is persisted under.
+///
+/// Closures cannot be serialized, so a tracker restored from JSON holds only
+/// the name; re-attach the function with [`EntityTracker::bind`] before
+/// filtering, otherwise predict/update return `Err`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NamedFn {
+    pub name: String,
+    #[serde(skip)]
+    func: Option<StateFn>,
+}
+
+impl NamedFn {
+    pub fn new(
+        name: impl Into<String>,
+        func: impl Fn(&[f64]) -> Vec<f64> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            func: Some(Arc::new(func)),
+        }
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.func.is_some()
+    }
+
+    fn call(&self, x: &[f64]) -> Result<Vec<f64>, String> {
+        let func = self
+            .func
+            .as_ref()
+            .ok_or_else(|| format!("Function '{}' is not bound", self.name))?;
+        Ok(func(x))
+    }
+
+    fn bind(&mut self, name: &str, func: &StateFn) -> bool {
+        if self.name != name {
+            return false;
+        }
+        self.func = Some(func.clone());
+        true
+    }
+}
+
+impl fmt::Debug for NamedFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedFn")
+            .field("name", &self.name)
+            .field("bound", &self.is_bound())
+            .finish()
+    }
+}
+
+impl PartialEq for NamedFn {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+/// State dynamics x' = f(x) applied once per filter step.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MotionModel {
+    /// x' = x; uncertainty still grows by the process noise.
+    #[default]
+    Static,
+    /// State laid out as `[positions.., velocities..]` (two equal halves).
+    ConstantVelocity { dt: f64 },
+    /// State laid out as `[positions.., velocities.., accelerations..]`.
+    ConstantAcceleration { dt: f64 },
+    /// x' = F×x for an arbitrary dim×dim matrix.
+    Linear { f: Vec<Vec<f64>> },
+    /// x' = f(x); linearised numerically by the extended filter.
+    Function { f: NamedFn },
+}
+
+/// Maps a state to the quantities an observation measures: z = h(x).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MeasurementModel {
+    /// Every state component is observed directly.
+    #[default]
+    Identity,
+    /// z = H×x for an m×dim matrix.
+    Linear { h: Vec<Vec<f64>> },
+    /// z = h(x); linearised numerically by the extended filter.
+    Function { h: NamedFn },
+}
+
+/// How predict/update propagate uncertainty through the models.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FilterKind {
+    /// Kalman filter; function models are linearised at the current
+    /// estimate (EKF). Exact for matrix models.
+    #[default]
+    Extended,
+    /// Unscented filter: 2n+1 sigma points are pushed through the models
+    /// instead of linearising them.
+    Unscented { alpha: f64, beta: f64, kappa: f64 },
+}
+
+impl FilterKind {
+    /// Unscented filter with α = 1, β = 2 (optimal for Gaussian priors), κ = 0.
+    pub fn unscented() -> Self {
+        FilterKind::Unscented {
+            alpha: 1.0,
+            beta: 2.0,
+            kappa: 0.0,
+        }
+    }
+}
+
+/// One motion hypothesis of an IMM tracker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MotionMode {
+    pub motion: MotionModel,
+    /// Process noise covariance Q for this hypothesis (dim×dim).
+    pub process_noise: Vec<Vec<f64>>,
+}
+
+/// A motion hypothesis with its own estimate and posterior probability.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModeFilter {
+    motion: MotionModel,
+    process_noise: Vec<Vec<f64>>,
+    state: EntityState,
+    probability: f64,
+}
+
 /// Kalman filter for entity tracking
 ///
 /// Implements the standard Kalman filter equations:
 /// Prediction:  x' = F×x, P' = F×P×F^T + Q
 /// Update: K = P×H^T×(H×P×H^T + R)^-1, x = x + K×(z - H×x), P = (I - K×H)×P
+///
+/// With function models F and H are the Jacobians at the current estimate
+/// (EKF), or the moments are taken over sigma points (UKF). An IMM tracker
+/// runs one filter per [`MotionMode`], mixes their estimates before every
+/// step and reports the probability-weighted combination.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct EntityTracker {
-    /// Current state estimate
+    /// Current state estimate (the mode mixture under IMM)
     state: EntityState,
 
-    /// State transition matrix F (identity for now)
-    transition_matrix: Vec<Vec<f64>>,
+    /// Motion hypotheses; a single one for a plain filter
+    modes: Vec<ModeFilter>,
 
-    /// Process noise covariance Q
-    process_noise: Vec<Vec<f64>>,
+    /// Mode switching probabilities: switching[i][j] = P(mode j next | mode i now)
+    switching: Vec<Vec<f64>>,
+
+    /// Observation model h(x)
+    measurement: MeasurementModel,
 
-    /// Observation matrix H (identity - direct observation)
-    observation_matrix: Vec<Vec<f64>>,
+    /// Extended or unscented propagation
+    filter: FilterKind,
 
     /// Last update timestamp
+    #[serde(skip, default = "Instant::now")]
     last_update: Instant,
 
     /// Anomaly threshold (Mahalanobis distance)
@@ -64,27 +226,144 @@ pub struct EntityTracker {
     permanence_timeout: Duration,
 
     /// Detected anomalies
+    #[serde(skip)]
     anomalies: Vec<Anomaly>,
 }
 
-/// Configuration for EntityTracker allowing custom Kalman F matrix.
+/// Configuration for EntityTracker: motion and measurement models, process
+/// noise and filter variant. The default is the original identity-dynamics
+/// linear Kalman filter.
 #[derive(Debug, Clone, Default)]
 pub struct EntityConfig {
     /// Optional custom state-transition matrix F (must be dim×dim).
-    /// If None, identity matrix is used.
+    /// Shorthand for `MotionModel::Linear`; takes precedence over `motion`.
     pub f_matrix: Option<Vec<Vec<f64>>>,
+
+    /// State dynamics.
+    pub motion: MotionModel,
+
+    /// Process noise covariance Q; 0.01×I when None.
+    pub process_noise: Option<Vec<Vec<f64>>>,
+
+    /// Observation model.
+    pub measurement: MeasurementModel,
+
+    /// Extended or unscented filter.
+    pub filter: FilterKind,
+}
+
+/// Result of one measurement update, before it is committed.
+struct Correction {
+    state: EntityState,
+    innovation_cov: Vec<Vec<f64>>,
+    mahalanobis_sq: f64,
+}
+
+impl Correction {
+    /// Gaussian log-likelihood of the innovation: the IMM mode weight.
+    fn log_likelihood(&self) -> Result<f64, String> {
+        let root = cholesky(&self.innovation_cov)?;
+        let log_det: f64 = (0..root.len()).map(|i| 2.0 * root[i][i].ln()).sum();
+        let m = root.len() as f64;
+        Ok(-0.5 * (self.mahalanobis_sq + log_det + m * (2.0 * std::f64::consts::PI).ln()))
+    }
 }
 
 impl EntityTracker {
     /// Create new tracker with initial state
     pub fn new(initial_state: EntityState) -> Self {
+        Self::with_config(initial_state, EntityConfig::default())
+    }
+
+    /// Create tracker with custom config (e.g. custom F matrix).
+    pub fn with_config(initial_state: EntityState, config: EntityConfig) -> Self {
+        let dim = initial_state.properties.len();
+        let motion = match config.f_matrix {
+            Some(f) => MotionModel::Linear { f },
+            None => config.motion,
+        };
+        let mode = MotionMode {
+            motion,
+            process_noise: config
+                .process_noise
+                .unwrap_or_else(|| diagonal_matrix(dim, 0.01)), // Small process noise
+        };
+        Self::from_modes(
+            initial_state,
+            vec![mode],
+            identity_matrix(1),
+            config.measurement,
+            config.filter,
+        )
+    }
+
+    /// Create an interacting-multiple-model tracker over `modes`, switching
+    /// between them with the Markov matrix `switching` (rows sum to 1). Only
+    /// `measurement` and `filter` are taken from `config`.
+    pub fn imm(
+        initial_state: EntityState,
+        modes: Vec<MotionMode>,
+        switching: Vec<Vec<f64>>,
+        config: EntityConfig,
+    ) -> Result<Self, String> {
         let dim = initial_state.properties.len();
+        let k = modes.len();
+        if k == 0 {
+            return Err("IMM needs at least one motion mode".to_string());
+        }
+        if switching.len() != k || switching.iter().any(|row| row.len() != k) {
+            return Err(format!("Mode switching matrix must be {}×{}", k, k));
+        }
+        for (i, row) in switching.iter().enumerate() {
+            let total: f64 = row.iter().sum();
+            if row.iter().any(|p| *p < 0.0) || (total - 1.0).abs() > 1e-6 {
+                return Err(format!(
+                    "Mode switching row {} is not a probability distribution",
+                    i
+                ));
+            }
+        }
+        if let Some(i) = modes.iter().position(|m| {
+            m.process_noise.len() != dim || m.process_noise.iter().any(|row| row.len() != dim)
+        }) {
+            return Err(format!(
+                "Process noise of mode {} must be {}×{}",
+                i, dim, dim
+            ));
+        }
+        Ok(Self::from_modes(
+            initial_state,
+            modes,
+            switching,
+            config.measurement,
+            config.filter,
+        ))
+    }
+
+    fn from_modes(
+        initial_state: EntityState,
+        modes: Vec<MotionMode>,
+        switching: Vec<Vec<f64>>,
+        measurement: MeasurementModel,
+        filter: FilterKind,
+    ) -> Self {
+        let probability = 1.0 / modes.len() as f64;
+        let modes = modes
+            .into_iter()
+            .map(|mode| ModeFilter {
+                motion: mode.motion,
+                process_noise: mode.process_noise,
+                state: initial_state.clone(),
+                probability,
+            })
+            .collect();
 
         Self {
             state: initial_state,
-            transition_matrix: identity_matrix(dim),
-            process_noise: diagonal_matrix(dim, 0.01), // Small process noise
-            observation_matrix: identity_matrix(dim),
+            modes,
+            switching,
+            measurement,
+            filter,
             last_update: Instant::now(),
             anomaly_threshold: 3.0, // 3σ threshold
             permanence_timeout: Duration::from_secs(60),
@@ -92,60 +371,39 @@ impl EntityTracker {
         }
     }
 
-    /// Create tracker with custom config (e.g. custom F matrix).
-    pub fn with_config(initial_state: EntityState, config: EntityConfig) -> Self {
-        let mut tracker = Self::new(initial_state);
-        if let Some(f) = config.f_matrix {
-            tracker.transition_matrix = f;
-        }
-        tracker
-    }
-
     /// Predict state N steps into future
     pub fn predict(&self, steps: usize) -> Result<EntityState, String> {
-        let mut state = self.state.clone();
+        let mut modes = self.modes.clone();
 
         for _ in 0..steps {
-            // x' = F × x
-            state.properties = matrix_vector_multiply(&self.transition_matrix, &state.properties)?;
-
-            // P' = F × P × F^T + Q
-            let fp = matrix_multiply(&self.transition_matrix, &state.covariance)?;
-            let fpft = matrix_multiply(&fp, &matrix_transpose(&self.transition_matrix))?;
-            state.covariance = matrix_add(&fpft, &self.process_noise)?;
+            self.time_step(&mut modes)?;
         }
 
-        Ok(state)
+        combine(&modes)
     }
 
-    /// Update state with new observation (Kalman update)
+    /// Update state with new observation.
+    ///
+    /// Each observation advances the motion model by one step before the
+    /// measurement update; a tracker with a single `Static` mode skips that
+    /// step, as the identity-dynamics filter always has.
     pub fn update(&mut self, observation: EntityObservation) -> Result<(), String> {
-        // Check entity permanence
-        let time_since_update = observation.timestamp.duration_since(self.last_update);
-        if time_since_update > self.permanence_timeout {
-            // Entity lost - rely on prediction only
-            let steps = (time_since_update.as_secs() / self.permanence_timeout.as_secs()) as usize;
-            self.state = self.predict(steps)?;
-        }
+        let mut modes = self.advance(observation.timestamp)?;
 
-        // Innovation: y = z - H×x
-        let hx = matrix_vector_multiply(&self.observation_matrix, &self.state.properties)?;
-        let innovation: Vec<f64> = observation
-            .measured_properties
+        let corrections = modes
             .iter()
-            .zip(hx.iter())
-            .map(|(z, hx_i)| z - hx_i)
-            .collect();
-
-        // Innovation covariance: S = H×P×H^T + R
-        let hp = matrix_multiply(&self.observation_matrix, &self.state.covariance)?;
-        let hpht = matrix_multiply(&hp, &matrix_transpose(&self.observation_matrix))?;
-        let innovation_cov = matrix_add(&hpht, &observation.measurement_noise)?;
-
-        // Anomaly detection: Mahalanobis distance d² = y^T × S^-1 × y
-        let inv_s = matrix_inverse(&innovation_cov)?;
-        let mahalanobis_sq = quadratic_form(&innovation, &inv_s)?;
-        let mahalanobis = mahalanobis_sq.sqrt();
+            .map(|mode| {
+                measurement_update(self.filter, &self.measurement, &mode.state, &observation)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Anomaly detection: Mahalanobis distance d² = y^T × S^-1 × y. Under
+        // IMM an observation is anomalous only if no mode explains it.
+        let mahalanobis = corrections
+            .iter()
+            .map(|c| c.mahalanobis_sq)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt();
 
         if mahalanobis > self.anomaly_threshold {
             self.anomalies.push(Anomaly {
@@ -158,61 +416,221 @@ impl EntityTracker {
             });
         }
 
-        // Kalman gain: K = P×H^T × S^-1
-        let pht = matrix_multiply(
-            &self.state.covariance,
-            &matrix_transpose(&self.observation_matrix),
-        )?;
-        let kalman_gain = matrix_multiply(&pht, &inv_s)?;
+        // Mode probabilities: μ_j ∝ c_j × L_j
+        if modes.len() > 1 {
+            let log_likelihoods = corrections
+                .iter()
+                .map(|c| c.log_likelihood())
+                .collect::<Result<Vec<f64>, String>>()?;
+            reweight_modes(&mut modes, &log_likelihoods);
+        }
 
-        // State update: x = x + K×y
-        let ky = matrix_vector_multiply(&kalman_gain, &innovation)?;
-        self.state.properties = self
-            .state
-            .properties
-            .iter()
-            .zip(ky.iter())
-            .map(|(x, ky_i)| x + ky_i)
-            .collect();
+        for (mode, correction) in modes.iter_mut().zip(corrections) {
+            mode.state = correction.state;
+        }
+
+        self.state = combine(&modes)?;
+        self.modes = modes;
+        self.last_update = observation.timestamp;
+
+        Ok(())
+    }
+
+    /// Gating statistics of `observation` against the state the next
+    /// `update` would correct, without changing the tracker.
+    pub fn innovation(&self, observation: &EntityObservation) -> Result<Innovation, String> {
+        let modes = self.advance(observation.timestamp)?;
+        let mut mahalanobis_sq = f64::INFINITY;
+        let mut log_terms = Vec::with_capacity(modes.len());
+        for mode in &modes {
+            let correction =
+                measurement_update(self.filter, &self.measurement, &mode.state, observation)?;
+            mahalanobis_sq = mahalanobis_sq.min(correction.mahalanobis_sq);
+            log_terms.push(mode.probability.ln() + correction.log_likelihood()?);
+        }
+        Ok(Innovation {
+            mahalanobis_sq,
+            log_likelihood: log_sum_exp(&log_terms),
+        })
+    }
+
+    /// Probabilistic data association update: `weights[j]` is the
+    /// probability that `observations[j]` originated from this entity and
+    /// the remainder the probability that none did. The result is the
+    /// moment-matched mixture of the predicted state (weighted by that
+    /// remainder) and one corrected state per observation. No anomalies are
+    /// recorded; the association step has already gated the observations.
+    pub fn update_weighted(
+        &mut self,
+        observations: &[EntityObservation],
+        weights: &[f64],
+    ) -> Result<(), String> {
+        if observations.len() != weights.len() {
+            return Err("One association weight per observation is required".to_string());
+        }
+        let total: f64 = weights.iter().sum();
+        if weights.iter().any(|w| *w < 0.0) || total > 1.0 + 1e-9 {
+            return Err(
+                "Association weights must be non-negative and sum to at most 1".to_string(),
+            );
+        }
+        let Some(timestamp) = observations.iter().map(|o| o.timestamp).max() else {
+            return self.coast();
+        };
+
+        let mut modes = self.advance(timestamp)?;
+        let miss = (1.0 - total).max(0.0);
+        let mut log_likelihoods = Vec::with_capacity(modes.len());
+        for mode in modes.iter_mut() {
+            let corrections = observations
+                .iter()
+                .map(|o| measurement_update(self.filter, &self.measurement, &mode.state, o))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if self.modes.len() > 1 {
+                let terms = corrections
+                    .iter()
+                    .zip(weights)
+                    .map(|(c, w)| Ok(w.ln() + c.log_likelihood()?))
+                    .collect::<Result<Vec<f64>, String>>()?;
+                log_likelihoods.push(log_sum_exp(&terms));
+            }
+
+            let mut states = vec![&mode.state];
+            states.extend(corrections.iter().map(|c| &c.state));
+            let mut mixture_weights = vec![miss];
+            mixture_weights.extend_from_slice(weights);
+            mode.state = mixture(&states, &mixture_weights)?;
+        }
+
+        if modes.len() > 1 && total > 0.0 {
+            reweight_modes(&mut modes, &log_likelihoods);
+        }
+
+        self.state = combine(&modes)?;
+        self.modes = modes;
+        self.last_update = timestamp;
 
-        // Covariance update (Joseph form): P = (I - K×H)×P×(I - K×H)^T + K×R×K^T
-        let kh = matrix_multiply(&kalman_gain, &self.observation_matrix)?;
-        let i_minus_kh = matrix_subtract(&identity_matrix(self.state.properties.len()), &kh)?;
-
-        // Term 1: (I - KH) * P * (I - KH)^T
-        let term1_part1 = matrix_multiply(&i_minus_kh, &self.state.covariance)?;
-        let i_minus_kh_t = matrix_transpose(&i_minus_kh);
-        let term1 = matrix_multiply(&term1_part1, &i_minus_kh_t)?;
-
-        // Term 2: K * R * K^T
-        let kr = matrix_multiply(&kalman_gain, &observation.measurement_noise)?;
-        let k_t = matrix_transpose(&kalman_gain);
-        let term2 = matrix_multiply(&kr, &k_t)?;
-
-        // P = term1 + term2
-        let mut p_new = matrix_add(&term1, &term2)?;
-
-        // Symmetrization: P = (P + P^T) / 2 to prevent float drift
-        let n = p_new.len();
-        for i in 0..n {
-            for j in 0..i {
-                let avg = (p_new[i][j] + p_new[j][i]) * 0.5;
-                p_new[i][j] = avg;
-                p_new[j][i] = avg;
+        Ok(())
+    }
+
+    /// Advance one motion step without a measurement (a missed detection).
+    /// Uncertainty grows; the last-update time is left alone, so a coasting
+    /// entity still goes stale.
+    pub fn coast(&mut self) -> Result<(), String> {
+        let mut modes = self.modes.clone();
+        self.time_step(&mut modes)?;
+        self.state = combine(&modes)?;
+        self.modes = modes;
+        Ok(())
+    }
+
+    /// Mode estimates predicted to `timestamp`: extra motion steps when the
+    /// entity was unobserved longer than the permanence timeout, then the
+    /// per-observation step.
+    fn advance(&self, timestamp: Instant) -> Result<Vec<ModeFilter>, String> {
+        let mut modes = self.modes.clone();
+
+        // Check entity permanence
+        let time_since_update = timestamp.duration_since(self.last_update);
+        if time_since_update > self.permanence_timeout {
+            // Entity lost - rely on prediction only
+            let steps = (time_since_update.as_secs() / self.permanence_timeout.as_secs()) as usize;
+            for _ in 0..steps {
+                self.time_step(&mut modes)?;
             }
         }
-        self.state.covariance = p_new;
 
-        self.last_update = observation.timestamp;
+        if modes.len() > 1 || modes[0].motion != MotionModel::Static {
+            self.time_step(&mut modes)?;
+        }
+        Ok(modes)
+    }
+
+    /// One IMM cycle without a measurement: mix the mode estimates, then
+    /// advance each through its motion model.
+    fn time_step(&self, modes: &mut [ModeFilter]) -> Result<(), String> {
+        if modes.len() > 1 {
+            self.mix(modes)?;
+        }
+        for mode in modes.iter_mut() {
+            mode.state = time_update(self.filter, &mode.motion, &mode.process_noise, &mode.state)?;
+        }
+        Ok(())
+    }
+
+    /// IMM interaction step: each mode restarts from the mixture of all mode
+    /// estimates weighted by P(previous mode i | next mode j), and its
+    /// probability becomes the predicted c_j = Σ_i p_ij μ_i.
+    fn mix(&self, modes: &mut [ModeFilter]) -> Result<(), String> {
+        let k = modes.len();
+        let predicted: Vec<f64> = (0..k)
+            .map(|j| {
+                (0..k)
+                    .map(|i| self.switching[i][j] * modes[i].probability)
+                    .sum()
+            })
+            .collect();
 
+        let mut mixed = Vec::with_capacity(k);
+        for (j, c_j) in predicted.iter().enumerate() {
+            if *c_j <= 0.0 {
+                mixed.push(modes[j].state.clone());
+                continue;
+            }
+            let weights: Vec<f64> = (0..k)
+                .map(|i| self.switching[i][j] * modes[i].probability / c_j)
+                .collect();
+            let states: Vec<&EntityState> = modes.iter().map(|m| &m.state).collect();
+            mixed.push(mixture(&states, &weights)?);
+        }
+
+        for ((mode, state), c_j) in modes.iter_mut().zip(mixed).zip(predicted) {
+            mode.state = state;
+            mode.probability = c_j;
+        }
         Ok(())
     }
 
+    /// Attach `func` to every motion or measurement function named `name`,
+    /// e.g. after the tracker was restored from JSON. Returns how many
+    /// models were bound.
+    pub fn bind(&mut self, name: &str, func: StateFn) -> usize {
+        let mut bound = 0;
+        for mode in self.modes.iter_mut() {
+            if let MotionModel::Function { f } = &mut mode.motion {
+                bound += f.bind(name, &func) as usize;
+            }
+        }
+        if let MeasurementModel::Function { h } = &mut self.measurement {
+            bound += h.bind(name, &func) as usize;
+        }
+        bound
+    }
+
     /// Get current state estimate
     pub fn get_state(&self) -> EntityState {
         self.state.clone()
     }
 
+    /// Posterior probability of each motion mode (`[1.0]` for a plain filter).
+    pub fn mode_probabilities(&self) -> Vec<f64> {
+        self.modes.iter().map(|m| m.probability).collect()
+    }
+
+    /// State-transition matrix F of the most probable mode; the Jacobian at
+    /// the current estimate for function models.
+    pub fn transition_matrix(&self) -> Result<Vec<Vec<f64>>, String> {
+        let mode = self
+            .modes
+            .iter()
+            .max_by(|a, b| a.probability.total_cmp(&b.probability))
+            .ok_or_else(|| "Tracker has no motion modes".to_string())?;
+        mode.motion
+            .linearize(&self.state.properties)
+            .map(|(_, f)| f)
+    }
+
     /// Get detected anomalies
     pub fn get_anomalies(&self) -> Vec<Anomaly> {
         self.anomalies.clone()
@@ -238,21 +656,463 @@ impl EntityTracker {
             .collect()
     }
 
-    /// Return diagonal of process noise matrix (noise_floor per dimension).
+    /// Return diagonal of process noise matrix (noise_floor per dimension),
+    /// weighted by mode probability under IMM.
     pub fn process_noise_diagonal(&self) -> Vec<f32> {
-        self.process_noise
-            .iter()
-            .enumerate()
-            .map(|(i, row)| row[i] as f32)
+        let dim = self.state.properties.len();
+        (0..dim)
+            .map(|i| {
+                self.modes
+                    .iter()
+                    .map(|m| m.probability * m.process_noise[i][i])
+                    .sum::<f64>() as f32
+            })
             .collect()
     }
 }
 
+// ============================================================================
+// Motion / Measurement Models
+// ============================================================================
+
+/// Shared evaluation of matrix and function models.
+trait StateModel {
+    /// The caller-supplied function, if this is a function model.
+    fn function(&self) -> Option<&NamedFn>;
+
+    /// Matrix form for a `dim`-dimensional state (matrix models only).
+    fn matrix(&self, dim: usize) -> Result<Vec<Vec<f64>>, String>;
+
+    fn apply(&self, x: &[f64]) -> Result<Vec<f64>, String> {
+        match self.function() {
+            Some(f) => f.call(x),
+            None => matrix_vector_multiply(&self.matrix(x.len())?, x),
+        }
+    }
+
+    /// Model output at `x` and its Jacobian there (central differences for
+    /// function models).
+    fn linearize(&self, x: &[f64]) -> Result<(Vec<f64>, Vec<Vec<f64>>), String> {
+        match self.function() {
+            Some(f) => Ok((f.call(x)?, numerical_jacobian(|p| f.call(p), x)?)),
+            None => {
+                let m = self.matrix(x.len())?;
+                Ok((matrix_vector_multiply(&m, x)?, m))
+            }
+        }
+    }
+}
+
+impl StateModel for MotionModel {
+    fn function(&self) -> Option<&NamedFn> {
+        match self {
+            MotionModel::Function { f } => Some(f),
+            _ => None,
+        }
+    }
+
+    fn matrix(&self, dim: usize) -> Result<Vec<Vec<f64>>, String> {
+        match self {
+            MotionModel::Static | MotionModel::Function { .. } => Ok(identity_matrix(dim)),
+            MotionModel::ConstantVelocity { dt } => kinematic_matrix(dim, 2, *dt),
+            MotionModel::ConstantAcceleration { dt } => kinematic_matrix(dim, 3, *dt),
+            MotionModel::Linear { f } => Ok(f.clone()),
+        }
+    }
+}
+
+impl StateModel for MeasurementModel {
+    fn function(&self) -> Option<&NamedFn> {
+        match self {
+            MeasurementModel::Function { h } => Some(h),
+            _ => None,
+        }
+    }
+
+    fn matrix(&self, dim: usize) -> Result<Vec<Vec<f64>>, String> {
+        match self {
+            MeasurementModel::Identity | MeasurementModel::Function { .. } => {
+                Ok(identity_matrix(dim))
+            }
+            MeasurementModel::Linear { h } => Ok(h.clone()),
+        }
+    }
+}
+
+/// Block transition matrix of a kinematic model with `order` blocks
+/// (2 = position/velocity, 3 = adds acceleration): block (r, c) is
+/// dt^(c-r)/(c-r)! × I for c ≥ r.
+fn kinematic_matrix(dim: usize, order: usize, dt: f64) -> Result<Vec<Vec<f64>>, String> {
+    if dim == 0 || !dim.is_multiple_of(order) {
+        return Err(format!(
+            "Kinematic model needs a state length divisible by {} (got {})",
+            order, dim
+        ));
+    }
+    let axes = dim / order;
+    let mut mat = vec![vec![0.0; dim]; dim];
+    for r in 0..order {
+        let mut coeff = 1.0;
+        for c in r..order {
+            if c > r {
+                coeff *= dt / (c - r) as f64;
+            }
+            for axis in 0..axes {
+                mat[r * axes + axis][c * axes + axis] = coeff;
+            }
+        }
+    }
+    Ok(mat)
+}
+
+/// Jacobian of `f` at `x` by central differences.
+fn numerical_jacobian<F>(f: F, x: &[f64]) -> Result<Vec<Vec<f64>>, String>
+where
+    F: Fn(&[f64]) -> Result<Vec<f64>, String>,
+{
+    let mut columns = Vec::with_capacity(x.len());
+    let mut probe = x.to_vec();
+    for i in 0..x.len() {
+        let h = 1e-6 * x[i].abs().max(1.0);
+        probe[i] = x[i] + h;
+        let up = f(&probe)?;
+        probe[i] = x[i] - h;
+        let down = f(&probe)?;
+        probe[i] = x[i];
+        columns.push(
+            up.iter()
+                .zip(down.iter())
+                .map(|(u, d)| (u - d) / (2.0 * h))
+                .collect::<Vec<f64>>(),
+        );
+    }
+    if columns.is_empty() || columns[0].is_empty() {
+        return Err("Jacobian of an empty function".to_string());
+    }
+    Ok(matrix_transpose(&columns))
+}
+
+// ============================================================================
+// Filter Steps
+// ============================================================================
+
+/// Prediction: propagate `state` through `motion` and add process noise.
+fn time_update(
+    filter: FilterKind,
+    motion: &MotionModel,
+    process_noise: &[Vec<f64>],
+    state: &EntityState,
+) -> Result<EntityState, String> {
+    match filter {
+        FilterKind::Extended => {
+            // x' = f(x), P' = F × P × F^T + Q
+            let (properties, f) = motion.linearize(&state.properties)?;
+            let fp = matrix_multiply(&f, &state.covariance)?;
+            let fpft = matrix_multiply(&fp, &matrix_transpose(&f))?;
+            Ok(EntityState {
+                properties,
+                covariance: matrix_add(&fpft, process_noise)?,
+            })
+        }
+        FilterKind::Unscented { alpha, beta, kappa } => {
+            let sigma = SigmaPoints::new(state, alpha, beta, kappa)?;
+            let propagated = sigma
+                .points
+                .iter()
+                .map(|p| motion.apply(p))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mean = sigma.mean(&propagated);
+            let spread = sigma.covariance(&propagated, &mean, &propagated, &mean);
+            let mut covariance = matrix_add(&spread, process_noise)?;
+            symmetrize(&mut covariance);
+            Ok(EntityState {
+                properties: mean,
+                covariance,
+            })
+        }
+    }
+}
+
+/// Measurement update of `state` with `observation` under `measurement`.
+fn measurement_update(
+    filter: FilterKind,
+    measurement: &MeasurementModel,
+    state: &EntityState,
+    observation: &EntityObservation,
+) -> Result<Correction, String> {
+    let z = &observation.measured_properties;
+    match filter {
+        FilterKind::Extended => {
+            let (hx, h) = measurement.linearize(&state.properties)?;
+            check_observation(z, &hx)?;
+
+            // Innovation: y = z - h(x)
+            let innovation: Vec<f64> = z.iter().zip(hx.iter()).map(|(z, hx_i)| z - hx_i).collect();
+
+            // Innovation covariance: S = H×P×H^T + R
+            let hp = matrix_multiply(&h, &state.covariance)?;
+            let hpht = matrix_multiply(&hp, &matrix_transpose(&h))?;
+            let innovation_cov = matrix_add(&hpht, &observation.measurement_noise)?;
+            let inv_s = matrix_inverse(&innovation_cov)?;
+            let mahalanobis_sq = quadratic_form(&innovation, &inv_s)?;
+
+            // Kalman gain: K = P×H^T × S^-1
+            let pht = matrix_multiply(&state.covariance, &matrix_transpose(&h))?;
+            let kalman_gain = matrix_multiply(&pht, &inv_s)?;
+
+            // State update: x = x + K×y
+            let ky = matrix_vector_multiply(&kalman_gain, &innovation)?;
+            let properties = state
+                .properties
+                .iter()
+                .zip(ky.iter())
+                .map(|(x, ky_i)| x + ky_i)
+                .collect();
+
+            // Covariance update (Joseph form): P = (I - K×H)×P×(I - K×H)^T + K×R×K^T
+            let kh = matrix_multiply(&kalman_gain, &h)?;
+            let i_minus_kh = matrix_subtract(&identity_matrix(state.properties.len()), &kh)?;
+
+            // Term 1: (I - KH) * P * (I - KH)^T
+            let term1_part1 = matrix_multiply(&i_minus_kh, &state.covariance)?;
+            let i_minus_kh_t = matrix_transpose(&i_minus_kh);
+            let term1 = matrix_multiply(&term1_part1, &i_minus_kh_t)?;
+
+            // Term 2: K * R * K^T
+            let kr = matrix_multiply(&kalman_gain, &observation.measurement_noise)?;
+            let k_t = matrix_transpose(&kalman_gain);
+            let term2 = matrix_multiply(&kr, &k_t)?;
+
+            // P = term1 + term2, symmetrized to prevent float drift
+            let mut covariance = matrix_add(&term1, &term2)?;
+            symmetrize(&mut covariance);
+
+            Ok(Correction {
+                state: EntityState {
+                    properties,
+                    covariance,
+                },
+                innovation_cov,
+                mahalanobis_sq,
+            })
+        }
+        FilterKind::Unscented { alpha, beta, kappa } => {
+            let sigma = SigmaPoints::new(state, alpha, beta, kappa)?;
+            let projected = sigma
+                .points
+                .iter()
+                .map(|p| measurement.apply(p))
+                .collect::<Result<Vec<_>, _>>()?;
+            let z_mean = sigma.mean(&projected);
+            check_observation(z, &z_mean)?;
+            let innovation: Vec<f64> = z.iter().zip(z_mean.iter()).map(|(z, m)| z - m).collect();
+
+            // S = Σ Wc (Z_i - z̄)(Z_i - z̄)^T + R, Pxz = Σ Wc (X_i - x̄)(Z_i - z̄)^T
+            let spread = sigma.covariance(&projected, &z_mean, &projected, &z_mean);
+            let innovation_cov = matrix_add(&spread, &observation.measurement_noise)?;
+            let cross = sigma.covariance(&sigma.points, &state.properties, &projected, &z_mean);
+            let inv_s = matrix_inverse(&innovation_cov)?;
+            let mahalanobis_sq = quadratic_form(&innovation, &inv_s)?;
+
+            // K = Pxz × S^-1, x = x + K×y, P = P - K×S×K^T
+            let kalman_gain = matrix_multiply(&cross, &inv_s)?;
+            let ky = matrix_vector_multiply(&kalman_gain, &innovation)?;
+            let properties = state
+                .properties
+                .iter()
+                .zip(ky.iter())
+                .map(|(x, ky_i)| x + ky_i)
+                .collect();
+            let ks = matrix_multiply(&kalman_gain, &innovation_cov)?;
+            let ksk_t = matrix_multiply(&ks, &matrix_transpose(&kalman_gain))?;
+            let mut covariance = matrix_subtract(&state.covariance, &ksk_t)?;
+            symmetrize(&mut covariance);
+
+            Ok(Correction {
+                state: EntityState {
+                    properties,
+                    covariance,
+                },
+                innovation_cov,
+                mahalanobis_sq,
+            })
+        }
+    }
+}
+
+fn check_observation(z: &[f64], expected: &[f64]) -> Result<(), String> {
+    if z.len() != expected.len() {
+        return Err(format!(
+            "Observation has {} values but the measurement model predicts {}",
+            z.len(),
+            expected.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Moment-matched mixture Σ w_i N(x_i, P_i): mean Σ w_i x_i and covariance
+/// Σ w_i (P_i + (x_i - x̄)(x_i - x̄)^T).
+fn mixture(states: &[&EntityState], weights: &[f64]) -> Result<EntityState, String> {
+    let dim = states[0].properties.len();
+    let mut mean = vec![0.0; dim];
+    for (state, w) in states.iter().zip(weights) {
+        if state.properties.len() != dim {
+            return Err("Motion modes disagree on state dimension".to_string());
+        }
+        for (m, x) in mean.iter_mut().zip(state.properties.iter()) {
+            *m += w * x;
+        }
+    }
+
+    let mut covariance = vec![vec![0.0; dim]; dim];
+    for (state, w) in states.iter().zip(weights) {
+        let d: Vec<f64> = state
+            .properties
+            .iter()
+            .zip(mean.iter())
+            .map(|(x, m)| x - m)
+            .collect();
+        for i in 0..dim {
+            for j in 0..dim {
+                covariance[i][j] += w * (state.covariance[i][j] + d[i] * d[j]);
+            }
+        }
+    }
+
+    Ok(EntityState {
+        properties: mean,
+        covariance,
+    })
+}
+
+/// Posterior mode probabilities μ_j ∝ c_j × L_j, computed in log space.
+/// Priors are kept when no mode explains the data at all.
+fn reweight_modes(modes: &mut [ModeFilter], log_likelihoods: &[f64]) {
+    let log_weights: Vec<f64> = modes
+        .iter()
+        .zip(log_likelihoods)
+        .map(|(mode, ll)| mode.probability.ln() + ll)
+        .collect();
+    let max = log_weights
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        return;
+    }
+    let weights: Vec<f64> = log_weights.iter().map(|w| (w - max).exp()).collect();
+    let total: f64 = weights.iter().sum();
+    for (mode, w) in modes.iter_mut().zip(weights) {
+        mode.probability = w / total;
+    }
+}
+
+/// ln Σ exp(x_i) without overflow; -∞ for an empty or all -∞ input.
+fn log_sum_exp(xs: &[f64]) -> f64 {
+    let max = xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        return max;
+    }
+    max + xs.iter().map(|x| (x - max).exp()).sum::<f64>().ln()
+}
+
+/// Probability-weighted estimate over all modes.
+fn combine(modes: &[ModeFilter]) -> Result<EntityState, String> {
+    let states: Vec<&EntityState> = modes.iter().map(|m| &m.state).collect();
+    let weights: Vec<f64> = modes.iter().map(|m| m.probability).collect();
+    mixture(&states, &weights)
+}
+
+/// Scaled unscented transform: 2n+1 sigma points x̄ and x̄ ± columns of
+/// √((n+λ)P), with λ = α²(n+κ) - n.
+struct SigmaPoints {
+    points: Vec<Vec<f64>>,
+    mean_weights: Vec<f64>,
+    cov_weights: Vec<f64>,
+}
+
+impl SigmaPoints {
+    fn new(state: &EntityState, alpha: f64, beta: f64, kappa: f64) -> Result<Self, String> {
+        let dim = state.properties.len();
+        let n = dim as f64;
+        let lambda = alpha * alpha * (n + kappa) - n;
+        let spread = n + lambda;
+        if spread <= 0.0 {
+            return Err("Unscented parameters need α²(n+κ) > 0".to_string());
+        }
+
+        let scaled: Vec<Vec<f64>> = state
+            .covariance
+            .iter()
+            .map(|row| row.iter().map(|v| v * spread).collect())
+            .collect();
+        let columns = matrix_transpose(&cholesky(&scaled)?);
+
+        let mut points = Vec::with_capacity(2 * dim + 1);
+        points.push(state.properties.clone());
+        for sign in [1.0, -1.0] {
+            for column in &columns {
+                points.push(
+                    state
+                        .properties
+                        .iter()
+                        .zip(column.iter())
+                        .map(|(x, c)| x + sign * c)
+                        .collect(),
+                );
+            }
+        }
+
+        let w = 1.0 / (2.0 * spread);
+        let mut mean_weights = vec![w; 2 * dim + 1];
+        let mut cov_weights = vec![w; 2 * dim + 1];
+        mean_weights[0] = lambda / spread;
+        cov_weights[0] = lambda / spread + 1.0 - alpha * alpha + beta;
+
+        Ok(Self {
+            points,
+            mean_weights,
+            cov_weights,
+        })
+    }
+
+    fn mean(&self, ys: &[Vec<f64>]) -> Vec<f64> {
+        let mut mean = vec![0.0; ys[0].len()];
+        for (y, w) in ys.iter().zip(self.mean_weights.iter()) {
+            for (m, v) in mean.iter_mut().zip(y.iter()) {
+                *m += w * v;
+            }
+        }
+        mean
+    }
+
+    /// Σ Wc (a_i - ā)(b_i - b̄)^T
+    fn covariance(
+        &self,
+        a: &[Vec<f64>],
+        a_mean: &[f64],
+        b: &[Vec<f64>],
+        b_mean: &[f64],
+    ) -> Vec<Vec<f64>> {
+        let mut cov = vec![vec![0.0; b_mean.len()]; a_mean.len()];
+        for ((a_i, b_i), w) in a.iter().zip(b.iter()).zip(self.cov_weights.iter()) {
+            for (r, row) in cov.iter_mut().enumerate() {
+                let da = a_i[r] - a_mean[r];
+                for (c, cell) in row.iter_mut().enumerate() {
+                    *cell += w * da * (b_i[c] - b_mean[c]);
+                }
+            }
+        }
+        cov
+    }
+}
+
 // ============================================================================
 // Matrix Operations (Basic Linear Algebra)
 // ============================================================================
 
-fn identity_matrix(dim: usize) -> Vec<Vec<f64>> {
+pub(super) fn identity_matrix(dim: usize) -> Vec<Vec<f64>> {
     let mut mat = vec![vec![0.0; dim]; dim];
     for i in 0..dim {
         mat[i][i] = 1.0;
@@ -260,7 +1120,7 @@ fn identity_matrix(dim: usize) -> Vec<Vec<f64>> {
     mat
 }
 
-fn diagonal_matrix(dim: usize, value: f64) -> Vec<Vec<f64>> {
+pub(super) fn diagonal_matrix(dim: usize, value: f64) -> Vec<Vec<f64>> {
     let mut mat = vec![vec![0.0; dim]; dim];
     for i in 0..dim {
         mat[i][i] = value;
@@ -268,7 +1128,7 @@ fn diagonal_matrix(dim: usize, value: f64) -> Vec<Vec<f64>> {
     mat
 }
 
-fn matrix_transpose(mat: &[Vec<f64>]) -> Vec<Vec<f64>> {
+pub(super) fn matrix_transpose(mat: &[Vec<f64>]) -> Vec<Vec<f64>> {
     let rows = mat.len();
     let cols = mat[0].len();
     let mut transposed = vec![vec![0.0; rows]; cols];
@@ -282,7 +1142,7 @@ fn matrix_transpose(mat: &[Vec<f64>]) -> Vec<Vec<f64>> {
     transposed
 }
 
-fn matrix_vector_multiply(mat: &[Vec<f64>], vec: &[f64]) -> Result<Vec<f64>, String> {
+pub(super) fn matrix_vector_multiply(mat: &[Vec<f64>], vec: &[f64]) -> Result<Vec<f64>, String> {
     if mat[0].len() != vec.len() {
         return Err("Matrix-vector dimension mismatch".to_string());
     }
@@ -293,7 +1153,7 @@ fn matrix_vector_multiply(mat: &[Vec<f64>], vec: &[f64]) -> Result<Vec<f64>, Str
         .collect())
 }
 
-fn matrix_multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+pub(super) fn matrix_multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
     if a[0].len() != b.len() {
         return Err("Matrix dimension mismatch".to_string());
     }
@@ -315,7 +1175,7 @@ fn matrix_multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, Stri
     Ok(result)
 }
 
-fn matrix_add(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+pub(super) fn matrix_add(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
     if a.len() != b.len() || a[0].len() != b[0].len() {
         return Err("Matrix dimension mismatch".to_string());
     }
@@ -337,7 +1197,7 @@ fn matrix_subtract(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, Stri
         .collect())
 }
 
-fn matrix_inverse(mat: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+pub(super) fn matrix_inverse(mat: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
     let n = mat.len();
     if n != mat[0].len() {
         return Err("Matrix must be square".to_string());
@@ -404,6 +1264,53 @@ fn quadratic_form(vec: &[f64], mat: &[Vec<f64>]) -> Result<f64, String> {
     Ok(vec.iter().zip(mv.iter()).map(|(v, mv)| v * mv).sum())
 }
 
+/// P = (P + P^T) / 2 to prevent float drift
+fn symmetrize(mat: &mut [Vec<f64>]) {
+    for i in 1..mat.len() {
+        let (upper, lower) = mat.split_at_mut(i);
+        for (j, row) in upper.iter_mut().enumerate() {
+            let avg = (lower[0][j] + row[i]) * 0.5;
+            lower[0][j] = avg;
+            row[i] = avg;
+        }
+    }
+}
+
+/// Lower-triangular L with L×L^T = A. A diagonal jitter is added when A is
+/// only semi-definite (e.g. a dimension with zero variance).
+fn cholesky(mat: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+    let scale = (0..mat.len())
+        .map(|i| mat[i][i].abs())
+        .fold(0.0, f64::max)
+        .max(1e-12);
+    for jitter in [0.0, 1e-12, 1e-9, 1e-6] {
+        if let Some(root) = try_cholesky(mat, jitter * scale) {
+            return Ok(root);
+        }
+    }
+    Err("Matrix is not positive definite".to_string())
+}
+
+fn try_cholesky(mat: &[Vec<f64>], jitter: f64) -> Option<Vec<Vec<f64>>> {
+    let n = mat.len();
+    let mut root = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| root[i][k] * root[j][k]).sum();
+            if i == j {
+                let d = mat[i][i] + jitter - sum;
+                if d <= 0.0 || !d.is_finite() {
+                    return None;
+                }
+                root[i][j] = d.sqrt();
+            } else {
+                root[i][j] = (mat[i][j] - sum) / root[j][j];
+            }
+        }
+    }
+    Some(root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -609,9 +1516,10 @@ mod tests {
         let f = vec![vec![1.0, 1.0], vec![0.0, 1.0]];
         let config = EntityConfig {
             f_matrix: Some(f.clone()),
+            ..Default::default()
         };
         let tracker = EntityTracker::with_config(initial_state, config);
-        assert_eq!(tracker.transition_matrix, f);
+        assert_eq!(tracker.transition_matrix().unwrap(), f);
         // One-step prediction: [1,0] -> F*[1,0] = [1,0]
         let pred = tracker.predict(1).unwrap();
         assert!((pred.properties[0] - 1.0).abs() < 1e-9);
@@ -626,6 +1534,295 @@ mod tests {
         };
         let tracker = EntityTracker::with_config(initial_state, EntityConfig::default());
         let identity = identity_matrix(2);
-        assert_eq!(tracker.transition_matrix, identity);
+        assert_eq!(tracker.transition_matrix().unwrap(), identity);
+    }
+
+    fn obs(z: Vec<f64>, noise: f64) -> EntityObservation {
+        let m = z.len();
+        EntityObservation {
+            measured_properties: z,
+            measurement_noise: diagonal_matrix(m, noise),
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// Range and bearing of a 2-D position from the origin.
+    fn polar() -> NamedFn {
+        NamedFn::new("polar", |x: &[f64]| {
+            vec![(x[0] * x[0] + x[1] * x[1]).sqrt(), x[1].atan2(x[0])]
+        })
+    }
+
+    #[test]
+    fn test_constant_velocity_prediction() {
+        let initial_state = EntityState {
+            properties: vec![0.0, 0.0, 1.0, 2.0], // [x, y, vx, vy]
+            covariance: identity_matrix(4),
+        };
+        let config = EntityConfig {
+            motion: MotionModel::ConstantVelocity { dt: 0.5 },
+            ..Default::default()
+        };
+        let tracker = EntityTracker::with_config(initial_state, config);
+        let pred = tracker.predict(4).unwrap();
+        assert!((pred.properties[0] - 2.0).abs() < 1e-9);
+        assert!((pred.properties[1] - 4.0).abs() < 1e-9);
+        assert!((pred.properties[2] - 1.0).abs() < 1e-9);
+        // Velocity uncertainty leaks into position
+        assert!(pred.covariance[0][0] > pred.covariance[2][2]);
+        assert!(pred.covariance[0][2] > 0.0);
+    }
+
+    #[test]
+    fn test_constant_acceleration_matrix() {
+        let f = kinematic_matrix(3, 3, 2.0).unwrap();
+        assert_eq!(f[0], vec![1.0, 2.0, 2.0]);
+        assert_eq!(f[1], vec![0.0, 1.0, 2.0]);
+        assert_eq!(f[2], vec![0.0, 0.0, 1.0]);
+        assert!(kinematic_matrix(4, 3, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_velocity_learned_from_position_measurements() {
+        let initial_state = EntityState {
+            properties: vec![0.0, 0.0],
+            covariance: diagonal_matrix(2, 10.0),
+        };
+        let config = EntityConfig {
+            motion: MotionModel::ConstantVelocity { dt: 1.0 },
+            process_noise: Some(diagonal_matrix(2, 1e-4)),
+            measurement: MeasurementModel::Linear {
+                h: vec![vec![1.0, 0.0]],
+            },
+            ..Default::default()
+        };
+        let mut tracker = EntityTracker::with_config(initial_state, config);
+        for t in 1..=20 {
+            tracker.update(obs(vec![3.0 * t as f64], 0.01)).unwrap();
+        }
+        let state = tracker.get_state();
+        assert!((state.properties[0] - 60.0).abs() < 0.1);
+        assert!((state.properties[1] - 3.0).abs() < 0.05);
+        assert!(tracker.get_anomalies().is_empty());
+    }
+
+    #[test]
+    fn test_unscented_matches_kalman_on_linear_models() {
+        let initial_state = EntityState {
+            properties: vec![0.0, 0.0, 1.0, 0.5],
+            covariance: diagonal_matrix(4, 2.0),
+        };
+        let measurement = MeasurementModel::Linear {
+            h: vec![vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]],
+        };
+        let make = |filter| {
+            EntityTracker::with_config(
+                initial_state.clone(),
+                EntityConfig {
+                    motion: MotionModel::ConstantVelocity { dt: 1.0 },
+                    measurement: measurement.clone(),
+                    filter,
+                    ..Default::default()
+                },
+            )
+        };
+        let mut kf = make(FilterKind::Extended);
+        let mut ukf = make(FilterKind::unscented());
+        for t in 1..=5 {
+            let z = vec![1.1 * t as f64, 0.4 * t as f64];
+            kf.update(obs(z.clone(), 0.2)).unwrap();
+            ukf.update(obs(z, 0.2)).unwrap();
+        }
+        let (a, b) = (kf.get_state(), ukf.get_state());
+        for i in 0..4 {
+            assert!((a.properties[i] - b.properties[i]).abs() < 1e-6);
+            for j in 0..4 {
+                assert!((a.covariance[i][j] - b.covariance[i][j]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nonlinear_measurement_converges() {
+        let truth = [3.0, 4.0];
+        let z = polar().call(&truth).unwrap();
+        for filter in [FilterKind::Extended, FilterKind::unscented()] {
+            let config = EntityConfig {
+                process_noise: Some(diagonal_matrix(2, 1e-6)),
+                measurement: MeasurementModel::Function { h: polar() },
+                filter,
+                ..Default::default()
+            };
+            let mut tracker = EntityTracker::with_config(
+                EntityState {
+                    properties: vec![2.6, 4.4],
+                    covariance: identity_matrix(2),
+                },
+                config,
+            );
+            for _ in 0..10 {
+                tracker.update(obs(z.clone(), 1e-4)).unwrap();
+            }
+            let state = tracker.get_state();
+            assert!(
+                (state.properties[0] - truth[0]).abs() < 0.05,
+                "{:?}",
+                filter
+            );
+            assert!(
+                (state.properties[1] - truth[1]).abs() < 0.05,
+                "{:?}",
+                filter
+            );
+        }
+    }
+
+    #[test]
+    fn test_imm_shifts_to_maneuver_mode() {
+        let cv = MotionModel::ConstantVelocity { dt: 1.0 };
+        let modes = vec![
+            MotionMode {
+                motion: cv.clone(),
+                process_noise: diagonal_matrix(2, 1e-4),
+            },
+            MotionMode {
+                motion: cv,
+                process_noise: diagonal_matrix(2, 1.0),
+            },
+        ];
+        let switching = vec![vec![0.95, 0.05], vec![0.05, 0.95]];
+        let config = EntityConfig {
+            measurement: MeasurementModel::Linear {
+                h: vec![vec![1.0, 0.0]],
+            },
+            ..Default::default()
+        };
+        let mut tracker = EntityTracker::imm(
+            EntityState {
+                properties: vec![0.0, 1.0],
+                covariance: diagonal_matrix(2, 0.1),
+            },
+            modes,
+            switching,
+            config,
+        )
+        .unwrap();
+
+        let mut x = 0.0;
+        for _ in 0..15 {
+            x += 1.0;
+            tracker.update(obs(vec![x], 0.01)).unwrap();
+        }
+        let cruising = tracker.mode_probabilities();
+        assert!(cruising[0] > cruising[1], "{:?}", cruising);
+
+        // Sharp turn: velocity jumps from 1 to 6
+        for _ in 0..3 {
+            x += 6.0;
+            tracker.update(obs(vec![x], 0.01)).unwrap();
+        }
+        let maneuvering = tracker.mode_probabilities();
+        assert!(maneuvering[1] > maneuvering[0], "{:?}", maneuvering);
+        assert!((maneuvering.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((tracker.get_state().properties[0] - x).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_single_mode_imm_matches_plain_tracker() {
+        let initial_state = EntityState {
+            properties: vec![0.0, 0.0],
+            covariance: identity_matrix(2),
+        };
+        let motion = MotionModel::ConstantVelocity { dt: 1.0 };
+        let mut plain = EntityTracker::with_config(
+            initial_state.clone(),
+            EntityConfig {
+                motion: motion.clone(),
+                ..Default::default()
+            },
+        );
+        let mut imm = EntityTracker::imm(
+            initial_state,
+            vec![MotionMode {
+                motion,
+                process_noise: diagonal_matrix(2, 0.01),
+            }],
+            vec![vec![1.0]],
+            EntityConfig::default(),
+        )
+        .unwrap();
+        for z in [[1.0, 0.9], [2.1, 1.0], [2.9, 1.1]] {
+            plain.update(obs(z.to_vec(), 0.1)).unwrap();
+            imm.update(obs(z.to_vec(), 0.1)).unwrap();
+        }
+        assert_eq!(plain.get_state().properties, imm.get_state().properties);
+        assert_eq!(imm.mode_probabilities(), vec![1.0]);
+    }
+
+    #[test]
+    fn test_imm_rejects_invalid_switching() {
+        let initial_state = EntityState {
+            properties: vec![0.0],
+            covariance: identity_matrix(1),
+        };
+        let mode = MotionMode {
+            motion: MotionModel::Static,
+            process_noise: identity_matrix(1),
+        };
+        let modes = vec![mode.clone(), mode];
+        let config = EntityConfig::default();
+        let bad_rows = vec![vec![0.5, 0.4], vec![0.5, 0.5]];
+        assert!(EntityTracker::imm(
+            initial_state.clone(),
+            modes.clone(),
+            bad_rows,
+            config.clone()
+        )
+        .is_err());
+        assert!(EntityTracker::imm(
+            initial_state.clone(),
+            modes.clone(),
+            identity_matrix(1),
+            config.clone()
+        )
+        .is_err());
+        assert!(EntityTracker::imm(initial_state, Vec::new(), Vec::new(), config).is_err());
+    }
+
+    #[test]
+    fn test_serde_roundtrip_requires_rebinding() {
+        let config = EntityConfig {
+            motion: MotionModel::Function {
+                f: NamedFn::new("drift", |x: &[f64]| x.iter().map(|v| v + 1.0).collect()),
+            },
+            measurement: MeasurementModel::Function { h: polar() },
+            filter: FilterKind::unscented(),
+            ..Default::default()
+        };
+        let tracker = EntityTracker::with_config(
+            EntityState {
+                properties: vec![3.0, 4.0],
+                covariance: identity_matrix(2),
+            },
+            config,
+        );
+        let expected = tracker.predict(2).unwrap();
+
+        let json = serde_json::to_value(&tracker).unwrap();
+        let mut restored: EntityTracker = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.filter, FilterKind::unscented());
+        let err = restored.predict(1).unwrap_err();
+        assert!(err.contains("drift"), "{}", err);
+
+        let drift: StateFn = Arc::new(|x: &[f64]| x.iter().map(|v| v + 1.0).collect());
+        assert_eq!(restored.bind("drift", drift), 1);
+        let predicted = restored.predict(2).unwrap();
+        assert!((predicted.properties[0] - expected.properties[0]).abs() < 1e-12);
+        assert!(restored.update(obs(vec![5.0, 0.9], 0.1)).is_err());
+
+        let polar_fn: StateFn =
+            Arc::new(|x: &[f64]| vec![(x[0] * x[0] + x[1] * x[1]).sqrt(), x[1].atan2(x[0])]);
+        assert_eq!(restored.bind("polar", polar_fn), 1);
+        restored.update(obs(vec![5.0, 0.9], 0.1)).unwrap();
     }
 }
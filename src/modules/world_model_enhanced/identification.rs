@@ -0,0 +1,916 @@
+// Causal Effect Identification
+//
+// Decides whether P(Y | do(X)) can be computed from observational data given
+// the causal graph and its latent nodes:
+// - d-separation queries (Bayes-ball reachability)
+// - Backdoor adjustment-set search (parents of X first, then minimal sets)
+// - Frontdoor mediator-set search
+// - ID algorithm (Shpitser & Pearl 2006) over the latent projection of the
+//   graph, returning the identifying formula or the hedge that blocks it
+
+use super::causal::CausalGraph;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+type Set = BTreeSet<usize>;
+
+/// Largest adjustment or mediator set enumerated exhaustively; beyond it only
+/// the maximal candidate set is tried.
+const MAX_SEARCH_SIZE: usize = 4;
+
+/// How an identified effect is computed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdjustmentStrategy {
+    /// Σ_z P(y | x, z) P(z)
+    Backdoor { adjustment_set: Vec<String> },
+    /// Σ_m P(m | x) Σ_x' P(y | x', m) P(x')
+    Frontdoor { mediators: Vec<String> },
+    /// General expression from the ID algorithm
+    IdAlgorithm,
+}
+
+/// An identifiable effect and the formula that computes it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Identification {
+    pub treatment: Vec<String>,
+    pub outcome: Vec<String>,
+    pub strategy: AdjustmentStrategy,
+    /// e.g. `P(Y | do(X)) = Σ_{Z} P(Y | X, Z) P(Z)`
+    pub formula: String,
+}
+
+/// Witness of non-identifiability: two confounded components `forest` ⊋
+/// `sub_forest` sharing their root set, where the intervention meets
+/// `forest` but not `sub_forest`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hedge {
+    pub treatment: Vec<String>,
+    pub outcome: Vec<String>,
+    pub forest: Vec<String>,
+    pub sub_forest: Vec<String>,
+    /// Observed pairs in `forest` sharing a latent cause: (a, b, latent)
+    pub confounded: Vec<(String, String, String)>,
+    pub explanation: String,
+}
+
+/// Outcome of `CausalGraph::identify`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Identifiability {
+    Identified(Identification),
+    NotIdentified(Hedge),
+}
+
+impl CausalGraph {
+    /// Whether every path between `x` and `y` is blocked by `given`
+    pub fn d_separated(&self, x: &[&str], y: &[&str], given: &[&str]) -> Result<bool, String> {
+        let dag = Dag::new(self);
+        let x = dag.resolve(x, false)?;
+        let y = dag.resolve(y, false)?;
+        let z = dag.resolve(given, true)?;
+        if !x.is_disjoint(&y) || !x.is_disjoint(&z) || !y.is_disjoint(&z) {
+            return Err("d-separation sets must be disjoint".to_string());
+        }
+        Ok(dag.d_separated(&x, &y, &z, &Set::new()))
+    }
+
+    /// Whether `z` satisfies the backdoor criterion for `x` → `y`: observed,
+    /// free of descendants of `x`, and blocking every path into `x`
+    pub fn is_backdoor_set(&self, x: &[&str], y: &[&str], z: &[&str]) -> Result<bool, String> {
+        let dag = Dag::new(self);
+        let (x, y) = dag.query(x, y)?;
+        Ok(dag.is_backdoor(&x, &y, &dag.resolve(z, true)?))
+    }
+
+    /// A backdoor adjustment set for `x` → `y`: the parents of `x` when they
+    /// qualify, otherwise a smallest qualifying set. `None` if there is none.
+    pub fn find_backdoor_set(&self, x: &[&str], y: &[&str]) -> Result<Option<Vec<String>>, String> {
+        let dag = Dag::new(self);
+        let (x, y) = dag.query(x, y)?;
+        Ok(dag.find_backdoor(&x, &y).map(|z| dag.names(&z)))
+    }
+
+    /// Whether `m` satisfies the frontdoor criterion for `x` → `y`
+    pub fn is_frontdoor_set(&self, x: &[&str], y: &[&str], m: &[&str]) -> Result<bool, String> {
+        let dag = Dag::new(self);
+        let (x, y) = dag.query(x, y)?;
+        Ok(dag.is_frontdoor(&x, &y, &dag.resolve(m, true)?))
+    }
+
+    /// A smallest frontdoor mediator set for `x` → `y`, if any
+    pub fn find_frontdoor_set(
+        &self,
+        x: &[&str],
+        y: &[&str],
+    ) -> Result<Option<Vec<String>>, String> {
+        let dag = Dag::new(self);
+        let (x, y) = dag.query(x, y)?;
+        Ok(dag.find_frontdoor(&x, &y).map(|m| dag.names(&m)))
+    }
+
+    /// Decide whether P(`y` | do(`x`)) is identifiable, trying backdoor
+    /// adjustment, then the frontdoor criterion, then the ID algorithm
+    pub fn identify(&self, x: &[&str], y: &[&str]) -> Result<Identifiability, String> {
+        let dag = Dag::new(self);
+        let (xs, ys) = dag.query(x, y)?;
+        let treatment = dag.names(&xs);
+        let outcome = dag.names(&ys);
+        let effect = format!("P({} | do({}))", outcome.join(", "), treatment.join(", "));
+
+        let (strategy, formula) = if let Some(z) = dag.find_backdoor(&xs, &ys) {
+            let z = dag.names(&z);
+            let formula = backdoor_formula(&treatment, &outcome, &z);
+            (AdjustmentStrategy::Backdoor { adjustment_set: z }, formula)
+        } else if let Some(m) = dag.find_frontdoor(&xs, &ys) {
+            let m = dag.names(&m);
+            let formula = frontdoor_formula(&treatment, &outcome, &m);
+            (AdjustmentStrategy::Frontdoor { mediators: m }, formula)
+        } else {
+            let admg = Admg::project(&dag);
+            let observed: Set = (0..dag.len()).filter(|&v| !dag.latent[v]).collect();
+            let joint = Expr::Prob {
+                vars: observed.clone(),
+                given: Set::new(),
+            };
+            match admg.id(&ys, &xs, &joint, &observed) {
+                Ok(expr) => (AdjustmentStrategy::IdAlgorithm, dag.render(&expr)),
+                Err((forest, sub_forest)) => {
+                    return Ok(Identifiability::NotIdentified(admg.hedge(
+                        &dag,
+                        &effect,
+                        treatment,
+                        outcome,
+                        &forest,
+                        &sub_forest,
+                    )))
+                }
+            }
+        };
+        Ok(Identifiability::Identified(Identification {
+            treatment,
+            outcome,
+            strategy,
+            formula: format!("{} = {}", effect, formula),
+        }))
+    }
+
+    /// Adjustment set used by the empirical intervention estimators, which
+    /// can only evaluate backdoor formulas
+    pub(super) fn backdoor_adjustment(&self, x: &str, y: &str) -> Result<Vec<String>, String> {
+        match self.identify(&[x], &[y])? {
+            Identifiability::Identified(Identification {
+                strategy: AdjustmentStrategy::Backdoor { adjustment_set },
+                ..
+            }) => Ok(adjustment_set),
+            Identifiability::Identified(id) => Err(format!(
+                "{}; stored distributions only support backdoor adjustment",
+                id.formula
+            )),
+            Identifiability::NotIdentified(hedge) => Err(hedge.explanation),
+        }
+    }
+}
+
+fn backdoor_formula(x: &[String], y: &[String], z: &[String]) -> String {
+    let y = y.join(", ");
+    let x = x.join(", ");
+    if z.is_empty() {
+        return format!("P({} | {})", y, x);
+    }
+    let z = z.join(", ");
+    format!("Σ_{{{z}}} P({y} | {x}, {z}) P({z})")
+}
+
+fn frontdoor_formula(x: &[String], y: &[String], m: &[String]) -> String {
+    let primed: Vec<String> = x.iter().map(|v| format!("{}'", v)).collect();
+    let (x, y, m, xp) = (x.join(", "), y.join(", "), m.join(", "), primed.join(", "));
+    format!("Σ_{{{m}}} P({m} | {x}) Σ_{{{xp}}} P({y} | {xp}, {m}) P({xp})")
+}
+
+// ============================================================================
+// Directed graph over all nodes, latent included
+// ============================================================================
+
+/// Index view of a `CausalGraph`; indices follow a topological order (ties
+/// broken by name), so a node's predecessors are exactly the smaller indices.
+struct Dag {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    parents: Vec<Vec<usize>>,
+    children: Vec<Vec<usize>>,
+    latent: Vec<bool>,
+}
+
+impl Dag {
+    fn new(graph: &CausalGraph) -> Self {
+        let ids = graph.node_ids();
+        let position: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i))
+            .collect();
+        let parents_by_id: Vec<Vec<usize>> = ids
+            .iter()
+            .map(|id| {
+                let mut p: Vec<usize> = graph
+                    .get_parents(id)
+                    .iter()
+                    .filter_map(|p| position.get(p.as_str()).copied())
+                    .collect();
+                p.sort_unstable();
+                p
+            })
+            .collect();
+
+        // Kahn's algorithm, always taking the smallest ready id
+        let mut indegree: Vec<usize> = parents_by_id.iter().map(Vec::len).collect();
+        let mut ready: BTreeSet<usize> = (0..ids.len()).filter(|&v| indegree[v] == 0).collect();
+        let mut order = Vec::with_capacity(ids.len());
+        while let Some(v) = ready.pop_first() {
+            order.push(v);
+            for (c, parents) in parents_by_id.iter().enumerate() {
+                if parents.contains(&v) {
+                    indegree[c] -= 1;
+                    if indegree[c] == 0 {
+                        ready.insert(c);
+                    }
+                }
+            }
+        }
+        let mut rank = vec![0; ids.len()];
+        for (r, &v) in order.iter().enumerate() {
+            rank[v] = r;
+        }
+
+        let names: Vec<String> = order.iter().map(|&v| ids[v].clone()).collect();
+        let parents: Vec<Vec<usize>> = order
+            .iter()
+            .map(|&v| {
+                let mut p: Vec<usize> = parents_by_id[v].iter().map(|&q| rank[q]).collect();
+                p.sort_unstable();
+                p
+            })
+            .collect();
+        let mut children = vec![Vec::new(); names.len()];
+        for (c, ps) in parents.iter().enumerate() {
+            for &p in ps {
+                children[p].push(c);
+            }
+        }
+        Self {
+            index: names
+                .iter()
+                .enumerate()
+                .map(|(i, n)| (n.clone(), i))
+                .collect(),
+            latent: names.iter().map(|n| graph.is_latent(n)).collect(),
+            names,
+            parents,
+            children,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    fn names(&self, set: &Set) -> Vec<String> {
+        set.iter().map(|&v| self.names[v].clone()).collect()
+    }
+
+    /// Node indices for `names`; latent nodes are rejected unless `observed_only`
+    /// is false (d-separation may ask about them)
+    fn resolve(&self, names: &[&str], observed_only: bool) -> Result<Set, String> {
+        names
+            .iter()
+            .map(|name| {
+                let v = *self
+                    .index
+                    .get(*name)
+                    .ok_or_else(|| format!("Variable '{}' not found", name))?;
+                if observed_only && self.latent[v] {
+                    return Err(format!("Variable '{}' is latent (unobserved)", name));
+                }
+                Ok(v)
+            })
+            .collect()
+    }
+
+    /// Treatment and outcome sets of an effect query
+    fn query(&self, x: &[&str], y: &[&str]) -> Result<(Set, Set), String> {
+        let xs = self.resolve(x, true)?;
+        let ys = self.resolve(y, true)?;
+        if xs.is_empty() || ys.is_empty() {
+            return Err("Treatment and outcome must be non-empty".to_string());
+        }
+        if !xs.is_disjoint(&ys) {
+            return Err("Treatment and outcome must be disjoint".to_string());
+        }
+        Ok((xs, ys))
+    }
+
+    fn descendants(&self, of: &Set) -> Set {
+        let mut seen = of.clone();
+        let mut stack: Vec<usize> = of.iter().copied().collect();
+        while let Some(v) = stack.pop() {
+            for &c in &self.children[v] {
+                if seen.insert(c) {
+                    stack.push(c);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Ancestors of `of` (inclusive), ignoring edges out of `cut_out`
+    fn ancestors(&self, of: &Set, cut_out: &Set) -> Set {
+        let mut seen = of.clone();
+        let mut stack: Vec<usize> = of.iter().copied().collect();
+        while let Some(v) = stack.pop() {
+            for &p in &self.parents[v] {
+                if !cut_out.contains(&p) && seen.insert(p) {
+                    stack.push(p);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Bayes-ball: whether `x` and `y` are d-separated by `z` once the edges
+    /// leaving `cut_out` are removed
+    fn d_separated(&self, x: &Set, y: &Set, z: &Set, cut_out: &Set) -> bool {
+        let z_ancestors = self.ancestors(z, cut_out);
+        // (node, arrived from a child)
+        let mut stack: Vec<(usize, bool)> = x.iter().map(|&v| (v, true)).collect();
+        let mut visited: BTreeSet<(usize, bool)> = BTreeSet::new();
+        while let Some((v, up)) = stack.pop() {
+            if !visited.insert((v, up)) {
+                continue;
+            }
+            let observed = z.contains(&v);
+            if !observed && y.contains(&v) {
+                return false;
+            }
+            let parents = self.parents[v].iter().filter(|p| !cut_out.contains(p));
+            let children: &[usize] = if cut_out.contains(&v) {
+                &[]
+            } else {
+                &self.children[v]
+            };
+            if up && !observed {
+                stack.extend(parents.map(|&p| (p, true)));
+                stack.extend(children.iter().map(|&c| (c, false)));
+            } else if !up {
+                if !observed {
+                    stack.extend(children.iter().map(|&c| (c, false)));
+                }
+                if z_ancestors.contains(&v) {
+                    stack.extend(parents.map(|&p| (p, true)));
+                }
+            }
+        }
+        true
+    }
+
+    /// Whether a directed path leads from `from` into `to` without entering `avoid`
+    fn directed_path(&self, from: &Set, to: &Set, avoid: &Set) -> bool {
+        let mut seen = from.clone();
+        let mut stack: Vec<usize> = from.iter().copied().collect();
+        while let Some(v) = stack.pop() {
+            for &c in &self.children[v] {
+                if to.contains(&c) {
+                    return true;
+                }
+                if !avoid.contains(&c) && seen.insert(c) {
+                    stack.push(c);
+                }
+            }
+        }
+        false
+    }
+
+    fn is_backdoor(&self, x: &Set, y: &Set, z: &Set) -> bool {
+        z.is_disjoint(x)
+            && z.is_disjoint(y)
+            && z.iter().all(|&v| !self.latent[v])
+            && z.is_disjoint(&self.descendants(x))
+            && self.d_separated(x, y, z, x)
+    }
+
+    fn is_frontdoor(&self, x: &Set, y: &Set, m: &Set) -> bool {
+        !m.is_empty()
+            && m.is_disjoint(x)
+            && m.is_disjoint(y)
+            && m.iter().all(|&v| !self.latent[v])
+            && !self.directed_path(x, y, m)
+            && self.d_separated(x, m, &Set::new(), x)
+            && self.d_separated(m, y, x, m)
+    }
+
+    /// Smallest subset of `candidates` accepted by `valid`, trying sizes
+    /// `min_size..=MAX_SEARCH_SIZE` and then the whole candidate set
+    fn smallest(
+        &self,
+        candidates: &[usize],
+        min_size: usize,
+        valid: impl Fn(&Set) -> bool,
+    ) -> Option<Set> {
+        for size in min_size..=candidates.len().min(MAX_SEARCH_SIZE) {
+            if let Some(set) = subsets(candidates, size).into_iter().find(|s| valid(s)) {
+                return Some(set);
+            }
+        }
+        let all: Set = candidates.iter().copied().collect();
+        (candidates.len() > MAX_SEARCH_SIZE && valid(&all)).then_some(all)
+    }
+
+    fn find_backdoor(&self, x: &Set, y: &Set) -> Option<Set> {
+        let parents: Set = x
+            .iter()
+            .flat_map(|&v| self.parents[v].iter().copied())
+            .filter(|p| !x.contains(p))
+            .collect();
+        if self.is_backdoor(x, y, &parents) {
+            return Some(parents);
+        }
+        let descendants = self.descendants(x);
+        let relevant = self.ancestors(&x.union(y).copied().collect(), &Set::new());
+        let candidates: Vec<usize> = (0..self.len())
+            .filter(|v| {
+                !self.latent[*v]
+                    && !y.contains(v)
+                    && !descendants.contains(v)
+                    && relevant.contains(v)
+            })
+            .collect();
+        self.smallest(&candidates, 0, |z| self.is_backdoor(x, y, z))
+    }
+
+    fn find_frontdoor(&self, x: &Set, y: &Set) -> Option<Set> {
+        let descendants = self.descendants(x);
+        let ancestors = self.ancestors(y, &Set::new());
+        let candidates: Vec<usize> = (0..self.len())
+            .filter(|v| {
+                !self.latent[*v]
+                    && !x.contains(v)
+                    && !y.contains(v)
+                    && descendants.contains(v)
+                    && ancestors.contains(v)
+            })
+            .collect();
+        self.smallest(&candidates, 1, |m| self.is_frontdoor(x, y, m))
+    }
+
+    fn render(&self, expr: &Expr) -> String {
+        let list = |set: &Set| self.names(set).join(", ");
+        let wrap = |e: &Expr| match e {
+            Expr::Prob { .. } => self.render(e),
+            _ => format!("[{}]", self.render(e)),
+        };
+        match expr {
+            Expr::Prob { vars, given } if given.is_empty() => format!("P({})", list(vars)),
+            Expr::Prob { vars, given } => format!("P({} | {})", list(vars), list(given)),
+            Expr::Sum { over, body } => format!("Σ_{{{}}} {}", list(over), self.render(body)),
+            Expr::Product(factors) if factors.is_empty() => "1".to_string(),
+            Expr::Product(factors) if factors.len() == 1 => self.render(&factors[0]),
+            Expr::Product(factors) => factors.iter().map(wrap).collect::<Vec<_>>().join(" "),
+            Expr::Ratio(num, den) => format!("{} / {}", wrap(num), wrap(den)),
+        }
+    }
+}
+
+/// All `k`-element subsets of `items`
+fn subsets(items: &[usize], k: usize) -> Vec<Set> {
+    if k == 0 {
+        return vec![Set::new()];
+    }
+    let mut out = Vec::new();
+    for (i, &first) in items.iter().enumerate() {
+        for mut rest in subsets(&items[i + 1..], k - 1) {
+            rest.insert(first);
+            out.push(rest);
+        }
+    }
+    out
+}
+
+// ============================================================================
+// ID algorithm over the latent projection
+// ============================================================================
+
+/// Probability expression built by the ID algorithm.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Prob { vars: Set, given: Set },
+    Sum { over: Set, body: Box<Expr> },
+    Product(Vec<Expr>),
+    Ratio(Box<Expr>, Box<Expr>),
+}
+
+/// Σ over `domain \ keep` of `p`, a distribution over `domain`
+fn marginal(p: &Expr, keep: &Set, domain: &Set) -> Expr {
+    let over: Set = domain.difference(keep).copied().collect();
+    if over.is_empty() {
+        return p.clone();
+    }
+    match p {
+        Expr::Prob { vars, given } if vars == domain => Expr::Prob {
+            vars: keep.clone(),
+            given: given.clone(),
+        },
+        Expr::Sum { over: inner, body } => Expr::Sum {
+            over: inner.union(&over).copied().collect(),
+            body: body.clone(),
+        },
+        _ => Expr::Sum {
+            over,
+            body: Box::new(p.clone()),
+        },
+    }
+}
+
+/// P(`v` | `given`) derived from `p`, a distribution over `domain`
+fn conditional(p: &Expr, v: usize, given: &Set, domain: &Set) -> Expr {
+    if let Expr::Prob {
+        vars,
+        given: context,
+    } = p
+    {
+        if vars == domain {
+            return Expr::Prob {
+                vars: Set::from([v]),
+                given: given.union(context).copied().collect(),
+            };
+        }
+    }
+    let mut joint = given.clone();
+    joint.insert(v);
+    if given.is_empty() {
+        return marginal(p, &joint, domain);
+    }
+    Expr::Ratio(
+        Box::new(marginal(p, &joint, domain)),
+        Box::new(marginal(p, given, domain)),
+    )
+}
+
+fn sum(over: Set, body: Expr) -> Expr {
+    if over.is_empty() {
+        body
+    } else {
+        Expr::Sum {
+            over,
+            body: Box::new(body),
+        }
+    }
+}
+
+/// Acyclic directed mixed graph over the observed nodes: directed edges for
+/// directed paths through latent nodes only, bidirected edges (with the
+/// latent responsible) for observed pairs sharing a latent ancestor reached
+/// through latent nodes only.
+struct Admg {
+    directed: Vec<Vec<bool>>,
+    bidirected: Vec<Vec<Option<usize>>>,
+}
+
+impl Admg {
+    fn project(dag: &Dag) -> Self {
+        let n = dag.len();
+        // Observed nodes reached from `v` through latent intermediates only
+        let reach = |v: usize| -> Set {
+            let mut found = Set::new();
+            let mut seen = Set::from([v]);
+            let mut stack = vec![v];
+            while let Some(u) = stack.pop() {
+                for &c in &dag.children[u] {
+                    if !seen.insert(c) {
+                        continue;
+                    }
+                    if dag.latent[c] {
+                        stack.push(c);
+                    } else {
+                        found.insert(c);
+                    }
+                }
+            }
+            found
+        };
+        let mut directed = vec![vec![false; n]; n];
+        let mut bidirected = vec![vec![None; n]; n];
+        for (v, row) in directed.iter_mut().enumerate() {
+            let reached = reach(v);
+            if !dag.latent[v] {
+                for &c in &reached {
+                    row[c] = true;
+                }
+                continue;
+            }
+            for &a in &reached {
+                for &b in &reached {
+                    if a != b && bidirected[a][b].is_none() {
+                        bidirected[a][b] = Some(v);
+                    }
+                }
+            }
+        }
+        Self {
+            directed,
+            bidirected,
+        }
+    }
+
+    /// Ancestors of `of` within `domain`, ignoring edges into `cut_in`
+    fn ancestors(&self, of: &Set, domain: &Set, cut_in: &Set) -> Set {
+        let mut seen = of.clone();
+        let mut stack: Vec<usize> = of.iter().copied().collect();
+        while let Some(v) = stack.pop() {
+            if cut_in.contains(&v) {
+                continue;
+            }
+            for &p in domain {
+                if self.directed[p][v] && seen.insert(p) {
+                    stack.push(p);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Components of `domain` connected by bidirected edges
+    fn c_components(&self, domain: &Set) -> Vec<Set> {
+        let mut left = domain.clone();
+        let mut components = Vec::new();
+        while let Some(start) = left.pop_first() {
+            let mut component = Set::from([start]);
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                let linked: Vec<usize> = left
+                    .iter()
+                    .copied()
+                    .filter(|&u| self.bidirected[v][u].is_some())
+                    .collect();
+                for u in linked {
+                    left.remove(&u);
+                    component.insert(u);
+                    stack.push(u);
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// ID(y, x, P, G) with G the subgraph on `domain` and `p` a distribution
+    /// over `domain`; fails with the hedge (F, F')
+    fn id(&self, y: &Set, x: &Set, p: &Expr, domain: &Set) -> Result<Expr, (Set, Set)> {
+        // Line 1: no intervention
+        if x.is_empty() {
+            return Ok(marginal(p, y, domain));
+        }
+        // Line 2: drop non-ancestors of Y
+        let ancestors = self.ancestors(y, domain, &Set::new());
+        if ancestors != *domain {
+            let x = x.intersection(&ancestors).copied().collect();
+            return self.id(y, &x, &marginal(p, &ancestors, domain), &ancestors);
+        }
+        // Line 3: intervene on nodes that cannot affect Y once X is fixed
+        let reaching = self.ancestors(y, domain, x);
+        let w: Set = domain
+            .iter()
+            .copied()
+            .filter(|v| !x.contains(v) && !reaching.contains(v))
+            .collect();
+        if !w.is_empty() {
+            return self.id(y, &x.union(&w).copied().collect(), p, domain);
+        }
+        // Line 4: factorise over the C-components of G \ X
+        let rest: Set = domain.difference(x).copied().collect();
+        let components = self.c_components(&rest);
+        if components.len() > 1 {
+            let factors = components
+                .iter()
+                .map(|s| self.id(s, &domain.difference(s).copied().collect(), p, domain))
+                .collect::<Result<Vec<_>, _>>()?;
+            let outer: Set = domain
+                .iter()
+                .copied()
+                .filter(|v| !y.contains(v) && !x.contains(v))
+                .collect();
+            return Ok(sum(outer, Expr::Product(factors)));
+        }
+        let s = &components[0];
+        let graph_components = self.c_components(domain);
+        // Line 5: G is a single C-component — hedge
+        if graph_components.len() == 1 {
+            return Err((domain.clone(), s.clone()));
+        }
+        let factor = |v: usize| conditional(p, v, &domain.range(..v).copied().collect(), domain);
+        // Line 6: S is a C-component of G
+        if graph_components.contains(s) {
+            let factors = s.iter().map(|&v| factor(v)).collect();
+            return Ok(sum(
+                s.difference(y).copied().collect(),
+                Expr::Product(factors),
+            ));
+        }
+        // Line 7: S sits inside a larger C-component S'
+        let wider = graph_components
+            .iter()
+            .find(|c| s.is_subset(c))
+            .expect("C-components of G \\ X lie inside C-components of G");
+        let factors = wider.iter().map(|&v| factor(v)).collect();
+        self.id(
+            y,
+            &x.intersection(wider).copied().collect(),
+            &Expr::Product(factors),
+            wider,
+        )
+    }
+
+    fn hedge(
+        &self,
+        dag: &Dag,
+        effect: &str,
+        treatment: Vec<String>,
+        outcome: Vec<String>,
+        forest: &Set,
+        sub_forest: &Set,
+    ) -> Hedge {
+        let mut confounded = Vec::new();
+        for &a in forest {
+            for &b in forest.range(a + 1..) {
+                if let Some(latent) = self.bidirected[a][b] {
+                    confounded.push((
+                        dag.names[a].clone(),
+                        dag.names[b].clone(),
+                        dag.names[latent].clone(),
+                    ));
+                }
+            }
+        }
+        let forest_names = dag.names(forest);
+        let sub_names = dag.names(sub_forest);
+        let cut: Set = forest.difference(sub_forest).copied().collect();
+        let links: Vec<String> = confounded
+            .iter()
+            .map(|(a, b, u)| format!("{} ↔ {} via {}", a, b, u))
+            .collect();
+        let explanation = format!(
+            "{} is not identifiable: {{{}}} and {{{}}} form a hedge. Both are single confounded \
+             components ({}) with the same root set, and the intervention fixes {{{}}} in the \
+             larger one only, so the latent confounding cannot be adjusted away",
+            effect,
+            forest_names.join(", "),
+            sub_names.join(", "),
+            links.join("; "),
+            dag.names(&cut).join(", "),
+        );
+        Hedge {
+            treatment,
+            outcome,
+            forest: forest_names,
+            sub_forest: sub_names,
+            confounded,
+            explanation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world_model_enhanced::InterventionQuery;
+
+    fn graph(edges: &[(&str, &str)]) -> CausalGraph {
+        let mut g = CausalGraph::new();
+        for (a, b) in edges {
+            g.add_edge(a.to_string(), b.to_string()).unwrap();
+        }
+        g
+    }
+
+    fn formula(result: Identifiability) -> (AdjustmentStrategy, String) {
+        match result {
+            Identifiability::Identified(id) => (id.strategy, id.formula),
+            Identifiability::NotIdentified(h) => panic!("not identified: {}", h.explanation),
+        }
+    }
+
+    #[test]
+    fn test_d_separation() {
+        let g = graph(&[("A", "B"), ("B", "C"), ("D", "B"), ("C", "E")]);
+        // Chain A → B → C is blocked by B
+        assert!(!g.d_separated(&["A"], &["C"], &[]).unwrap());
+        assert!(g.d_separated(&["A"], &["C"], &["B"]).unwrap());
+        // Collider A → B ← D opens when B or a descendant is observed
+        assert!(g.d_separated(&["A"], &["D"], &[]).unwrap());
+        assert!(!g.d_separated(&["A"], &["D"], &["B"]).unwrap());
+        assert!(!g.d_separated(&["A"], &["D"], &["E"]).unwrap());
+        assert!(g.d_separated(&["A"], &["Q"], &[]).is_err());
+        assert!(g.d_separated(&["A"], &["A"], &[]).is_err());
+    }
+
+    #[test]
+    fn test_backdoor_search() {
+        let mut g = graph(&[("Z", "X"), ("Z", "Y"), ("X", "Y"), ("X", "M")]);
+        assert_eq!(
+            g.find_backdoor_set(&["X"], &["Y"]).unwrap(),
+            Some(vec!["Z".to_string()])
+        );
+        assert!(!g.is_backdoor_set(&["X"], &["Y"], &[]).unwrap());
+        assert!(!g.is_backdoor_set(&["X"], &["Y"], &["Z", "M"]).unwrap());
+        let (strategy, formula) = formula(g.identify(&["X"], &["Y"]).unwrap());
+        assert_eq!(
+            strategy,
+            AdjustmentStrategy::Backdoor {
+                adjustment_set: vec!["Z".into()]
+            }
+        );
+        assert_eq!(formula, "P(Y | do(X)) = Σ_{Z} P(Y | X, Z) P(Z)");
+
+        // A latent parent of X is bypassed through an observed proxy
+        g.add_edge("U".into(), "W".into()).unwrap();
+        g.add_edge("U".into(), "X".into()).unwrap();
+        g.add_edge("W".into(), "Y".into()).unwrap();
+        g.set_latent("U", true).unwrap();
+        assert_eq!(
+            g.find_backdoor_set(&["X"], &["Y"]).unwrap(),
+            Some(vec!["W".to_string(), "Z".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_frontdoor_identification() {
+        let mut g = graph(&[("X", "M"), ("M", "Y")]);
+        let latent = g.add_latent_confounder("X", "Y").unwrap();
+        assert!(g.is_latent(&latent));
+        assert_eq!(g.find_backdoor_set(&["X"], &["Y"]).unwrap(), None);
+        assert_eq!(
+            g.find_frontdoor_set(&["X"], &["Y"]).unwrap(),
+            Some(vec!["M".to_string()])
+        );
+        let (strategy, formula) = formula(g.identify(&["X"], &["Y"]).unwrap());
+        assert_eq!(
+            strategy,
+            AdjustmentStrategy::Frontdoor {
+                mediators: vec!["M".into()]
+            }
+        );
+        assert_eq!(
+            formula,
+            "P(Y | do(X)) = Σ_{M} P(M | X) Σ_{X'} P(Y | X', M) P(X')"
+        );
+        assert!(g.identify(&[latent.as_str()], &["Y"]).is_err());
+    }
+
+    #[test]
+    fn test_id_algorithm_napkin() {
+        // Neither backdoor nor frontdoor applies, but the effect is identifiable
+        let mut g = graph(&[("W", "R"), ("R", "X"), ("X", "Y")]);
+        g.add_latent_confounder("W", "X").unwrap();
+        g.add_latent_confounder("W", "Y").unwrap();
+        let (strategy, formula) = formula(g.identify(&["X"], &["Y"]).unwrap());
+        assert_eq!(strategy, AdjustmentStrategy::IdAlgorithm);
+        assert_eq!(
+            formula,
+            "P(Y | do(X)) = [Σ_{W} P(W) P(X | W, R) P(Y | W, R, X)] / \
+             [Σ_{W, Y} P(W) P(X | W, R) P(Y | W, R, X)]"
+        );
+    }
+
+    #[test]
+    fn test_hedge_explains_non_identifiability() {
+        let mut g = graph(&[("X", "Y")]);
+        g.add_latent_confounder("X", "Y").unwrap();
+        match g.identify(&["X"], &["Y"]).unwrap() {
+            Identifiability::NotIdentified(hedge) => {
+                assert_eq!(hedge.forest, vec!["X".to_string(), "Y".to_string()]);
+                assert_eq!(hedge.sub_forest, vec!["Y".to_string()]);
+                assert_eq!(hedge.confounded.len(), 1);
+                assert!(hedge.explanation.contains("X ↔ Y via U(X,Y)"));
+            }
+            other => panic!("expected hedge, got {:?}", other),
+        }
+
+        // The estimators refuse rather than return a biased number
+        let query = InterventionQuery {
+            outcome: "Y".into(),
+            intervention_var: "X".into(),
+            intervention_value: 1.0,
+            conditioned_on: HashMap::new(),
+            intervention_label: None,
+        };
+        let err = g.compute_intervention(&query).unwrap_err();
+        assert!(err.contains("not identifiable"), "{}", err);
+    }
+
+    #[test]
+    fn test_no_causal_path_identifies_marginal() {
+        let g = graph(&[("Y", "X")]);
+        let (strategy, formula) = formula(g.identify(&["X"], &["Y"]).unwrap());
+        assert_eq!(strategy, AdjustmentStrategy::IdAlgorithm);
+        assert_eq!(formula, "P(Y | do(X)) = P(Y)");
+    }
+}
@@ -8,9 +8,12 @@
 //!
 //! | Component | Role | Algorithm |
 //! |-----------|------|-----------|
-//! | [`TransitionModel`] | Learn P(next_state | state, action) from observations | Dirichlet-Multinomial |
-//! | [`EntityTracker`] | Estimate entity state with uncertainty via Kalman filter | Linear Kalman |
+//! | [`TransitionModel`] | Learn P(next_state | state, action) from observations, forgetting stale evidence and flagging regime shifts | Dirichlet-Multinomial, exponential / sliding-window forgetting, Bayesian online change-point detection |
+//! | [`EntityTracker`] | Estimate entity state with uncertainty via Kalman filter | Kalman / EKF / UKF, IMM |
+//! | [`TrackManager`] | Associate unlabeled observations with entities; spawn, confirm and delete tracks | Mahalanobis gating + Hungarian / JPDA |
 //! | [`CausalGraph`] | Build and query directed acyclic causal graphs, with do-calculus interventions | DAG + cycle prevention |
+//! | [`CausalGraph::identify`] | Check whether P(Y \| do(X)) is identifiable and give the formula, or the hedge that prevents it | d-separation, backdoor / frontdoor search, ID algorithm |
+//! | [`discover`] | Learn a CPDAG from memory-record metadata or transition counts, then accept edges into the graph | PC (Fisher-z / G²), greedy BIC search |
 //! | [`UncertaintyEstimator`] | Decompose uncertainty into epistemic vs aleatoric, calibration tracking | ECE ≤ 0.1 target |
 //!
 //! ## Quick Start
@@ -61,24 +64,39 @@
 
 mod causal;
 pub mod constraint;
+mod discovery;
 pub mod entity;
+mod identification;
 pub mod metalaw;
 pub mod policy;
 mod predictor;
 pub mod simulator;
+mod tracking;
 pub mod transition;
 mod uncertainty;
 
 pub use causal::{CausalEdge, CausalGraph, CausalNode, InterventionQuery};
 pub use constraint::{Constraint, ConstraintEngine, ConstraintSeverity};
-pub use entity::{Anomaly, EntityObservation, EntityState, EntityTracker};
+pub use discovery::{
+    discover, discover_bootstrap, AcceptReport, CiTest, Column, Cpdag, Dataset, DiscoveredEdge,
+    DiscoveryMethod,
+};
+pub use entity::{
+    Anomaly, EntityConfig, EntityObservation, EntityState, EntityTracker, FilterKind, Innovation,
+    MeasurementModel, MotionMode, MotionModel, NamedFn, StateFn,
+};
 pub use metalaw::{InvariantType, MetaLaw, MetaLawEngine};
 pub use policy::Policy;
 pub use predictor::{LearnedTransitionPredictor, PredictionResult, PredictiveModel};
 pub use simulator::{
     MctsSimulator, SimulationHarness, SimulationStep, SimulationTrajectory, SimulatorNode,
 };
-pub use transition::{StateTransition, TransitionModel, TransitionPrediction};
+pub use identification::{AdjustmentStrategy, Hedge, Identifiability, Identification};
+pub use tracking::{AssociationMethod, ScanReport, TrackManager, TrackStatus, TrackingConfig};
+pub use transition::{
+    AdaptationConfig, ChangePoint, ChangePointConfig, Forgetting, SnapshotRow, StateTransition,
+    TransitionModel, TransitionPrediction, TransitionSnapshot,
+};
 pub use uncertainty::{CalibrationMetrics, ConfidenceInterval, UncertaintyEstimator};
 
 use crate::topological_memory::{CausalTopoGraph, EdgeType};
@@ -102,6 +120,9 @@ pub struct WorldModelEnhanced {
     /// Entity trackers (Kalman filters)
     entities: Arc<RwLock<HashMap<String, EntityTracker>>>,
 
+    /// Data association and track lifecycle for unlabeled observations
+    tracking: Arc<RwLock<TrackManager>>,
+
     /// Causal graph for causal reasoning
     causal_graph: Arc<RwLock<CausalGraph>>,
 
@@ -122,6 +143,7 @@ impl WorldModelEnhanced {
         Self {
             transitions: Arc::new(RwLock::new(TransitionModel::new())),
             entities: Arc::new(RwLock::new(HashMap::new())),
+            tracking: Arc::new(RwLock::new(TrackManager::new(TrackingConfig::default()))),
             causal_graph: Arc::new(RwLock::new(CausalGraph::new())),
             predictors: Arc::new(RwLock::new(Vec::new())),
             uncertainty: Arc::new(RwLock::new(UncertaintyEstimator::new())),
@@ -211,13 +233,63 @@ impl WorldModelEnhanced {
     }
 
     /// Get transition uncertainty (Shannon entropy)
+    ///
+    /// Counts are taken after forgetting and, with a staleness half-life
+    /// configured, discounted by how long ago the pair was last observed.
     pub fn get_transition_uncertainty(&self, state: &str, action: &str) -> Result<f64, String> {
         let transitions = self
             .transitions
             .read()
             .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?;
 
-        transitions.compute_entropy(state, action)
+        transitions.transition_uncertainty(state, action)
+    }
+
+    /// Set forgetting, change-point detection and snapshot retention for
+    /// transition learning
+    pub fn set_transition_adaptation(&self, adaptation: AdaptationConfig) -> Result<(), String> {
+        self.transitions
+            .write()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?
+            .set_adaptation(adaptation);
+        Ok(())
+    }
+
+    /// Regime shifts flagged by transition change-point detection
+    pub fn transition_change_points(&self) -> Result<Vec<ChangePoint>, String> {
+        let transitions = self
+            .transitions
+            .read()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?;
+
+        Ok(transitions.change_points().to_vec())
+    }
+
+    /// Snapshot the transition counts; returns the snapshot version
+    pub fn snapshot_transitions(&self, label: &str) -> Result<u64, String> {
+        Ok(self
+            .transitions
+            .write()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?
+            .snapshot(label))
+    }
+
+    /// Retained transition snapshots, oldest first
+    pub fn transition_snapshots(&self) -> Result<Vec<TransitionSnapshot>, String> {
+        let transitions = self
+            .transitions
+            .read()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?;
+
+        Ok(transitions.snapshots().to_vec())
+    }
+
+    /// Roll transition counts back to snapshot `version`
+    pub fn restore_transition_snapshot(&self, version: u64) -> Result<(), String> {
+        self.transitions
+            .write()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?
+            .restore_snapshot(version)
     }
 
     // ========================================================================
@@ -245,6 +317,42 @@ impl WorldModelEnhanced {
         Ok(())
     }
 
+    /// Register a new entity with a configured tracker (motion/measurement
+    /// models, EKF/UKF, IMM).
+    pub fn register_entity_tracker(
+        &self,
+        entity_id: String,
+        tracker: EntityTracker,
+    ) -> Result<(), String> {
+        let mut entities = self
+            .entities
+            .write()
+            .map_err(|e| format!("Failed to acquire entities lock: {}", e))?;
+
+        if entities.contains_key(&entity_id) {
+            return Err(format!("Entity '{}' already exists", entity_id));
+        }
+
+        entities.insert(entity_id, tracker);
+
+        Ok(())
+    }
+
+    /// Attach `func` to every entity motion or measurement function named
+    /// `name`. Needed after `load`, which restores function names only.
+    /// Returns the number of models bound.
+    pub fn bind_entity_fn(&self, name: &str, func: StateFn) -> Result<usize, String> {
+        let mut entities = self
+            .entities
+            .write()
+            .map_err(|e| format!("Failed to acquire entities lock: {}", e))?;
+
+        Ok(entities
+            .values_mut()
+            .map(|tracker| tracker.bind(name, func.clone()))
+            .sum())
+    }
+
     /// Update entity with new observation (Kalman update)
     pub fn update_entity(
         &self,
@@ -263,6 +371,56 @@ impl WorldModelEnhanced {
         tracker.update(observation)
     }
 
+    /// Set the association and track lifecycle policy used by
+    /// `update_unlabeled`; existing tracks are kept.
+    pub fn configure_tracking(&self, config: TrackingConfig) -> Result<(), String> {
+        self.tracking
+            .write()
+            .map_err(|e| format!("Failed to acquire tracking lock: {}", e))?
+            .set_config(config);
+        Ok(())
+    }
+
+    /// Process one scan of observations whose entity is unknown: gate and
+    /// assign them to tracked entities, spawn tentative tracks for the rest,
+    /// confirm tracks into the entity set and delete lost ones. Every
+    /// tracked entity takes part, including ones registered by hand.
+    pub fn update_unlabeled(
+        &self,
+        observations: Vec<EntityObservation>,
+    ) -> Result<ScanReport, String> {
+        let mut entities = self
+            .entities
+            .write()
+            .map_err(|e| format!("Failed to acquire entities lock: {}", e))?;
+        let mut tracking = self
+            .tracking
+            .write()
+            .map_err(|e| format!("Failed to acquire tracking lock: {}", e))?;
+
+        tracking.scan(&mut entities, &observations)
+    }
+
+    /// Tracks spawned by `update_unlabeled` that are not confirmed yet
+    pub fn tentative_tracks(&self) -> Result<Vec<String>, String> {
+        let tracking = self
+            .tracking
+            .read()
+            .map_err(|e| format!("Failed to acquire tracking lock: {}", e))?;
+
+        Ok(tracking.tentative_ids())
+    }
+
+    /// Anomalies raised for confirmed tracks deleted after missed scans
+    pub fn lost_track_anomalies(&self) -> Result<Vec<(String, Anomaly)>, String> {
+        let tracking = self
+            .tracking
+            .read()
+            .map_err(|e| format!("Failed to acquire tracking lock: {}", e))?;
+
+        Ok(tracking.lost_anomalies().to_vec())
+    }
+
     /// Predict entity state N steps into future
     pub fn predict_entity(&self, entity_id: &str, steps: usize) -> Result<EntityState, String> {
         let entities = self
@@ -350,8 +508,34 @@ impl WorldModelEnhanced {
         graph.has_path(from, to)
     }
 
+    /// Mark a causal node as latent (unobserved), e.g. a hidden confounder
+    pub fn set_causal_latent(&self, node: &str, latent: bool) -> Result<(), String> {
+        let mut graph = self
+            .causal_graph
+            .write()
+            .map_err(|e| format!("Failed to acquire causal graph lock: {}", e))?;
+
+        graph.set_latent(node, latent)
+    }
+
+    /// Check whether P(outcome | do(treatment)) is identifiable from
+    /// observational data, with the adjustment formula or the hedge against it
+    pub fn identify_causal_effect(
+        &self,
+        treatment: &str,
+        outcome: &str,
+    ) -> Result<Identifiability, String> {
+        let graph = self
+            .causal_graph
+            .read()
+            .map_err(|e| format!("Failed to acquire causal graph lock: {}", e))?;
+
+        graph.identify(&[treatment], &[outcome])
+    }
+
     /// Perform causal intervention P(Y|do(X=x)).
     /// Prefers exact empirical backdoor adjustment when distributions exist; else heuristic.
+    /// Fails when the effect is not identifiable by backdoor adjustment.
     pub fn causal_intervention(
         &self,
         query: InterventionQuery,
@@ -707,6 +891,41 @@ impl WorldModelEnhanced {
         self.topo_graph.read().map(|g| g.node_count()).unwrap_or(0)
     }
 
+    /// Learn causal structure over `state`, `action` and `next_state` from
+    /// the recorded transition counts. Nothing is added to the causal graph;
+    /// see `accept_causal_edges`.
+    pub fn discover_causal_structure(&self, method: &DiscoveryMethod) -> Result<Cpdag, String> {
+        let transitions = self
+            .transitions
+            .read()
+            .map_err(|e| format!("Failed to acquire transitions lock: {}", e))?;
+        discover(&Dataset::from_transitions(&transitions)?, method)
+    }
+
+    /// Add the discovered directed edges with confidence ≥ `min_confidence`
+    /// to the causal graph; edges that would close a cycle are reported, not added
+    pub fn accept_causal_edges(
+        &self,
+        cpdag: &Cpdag,
+        min_confidence: f64,
+    ) -> Result<AcceptReport, String> {
+        let mut graph = self
+            .causal_graph
+            .write()
+            .map_err(|e| format!("Failed to acquire causal graph lock: {}", e))?;
+        Ok(cpdag.accept_into(&mut graph, min_confidence))
+    }
+
+    /// Accept one discovered edge as `from → to`, e.g. to orient an edge the
+    /// data left undirected
+    pub fn accept_causal_edge(&self, cpdag: &Cpdag, from: &str, to: &str) -> Result<(), String> {
+        let mut graph = self
+            .causal_graph
+            .write()
+            .map_err(|e| format!("Failed to acquire causal graph lock: {}", e))?;
+        cpdag.accept_edge(&mut graph, from, to)
+    }
+
     /// Return all causal edges for serialization.
     pub fn get_causal_edges(&self) -> Vec<CausalEdge> {
         self.causal_graph
@@ -769,11 +988,11 @@ impl WorldModelEnhanced {
         result
     }
 
-    /// Save world model state to a JSON file.
-    /// Persists: transition counts + totals, causal edges.
-    /// Entity Kalman states are NOT persisted (recoverable from live memory).
-    /// Uses atomic write: writes to .tmp file then renames.
-    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> anyhow::Result<()> {
+    /// Learned state as JSON: transition counts + totals, forgetting state,
+    /// snapshots and change points, causal edges and
+    /// latent nodes, and entity trackers. This is the `save` file format and the world-model
+    /// section of a state bundle.
+    pub fn to_json(&self) -> anyhow::Result<serde_json::Value> {
         let transitions = self
             .transitions
             .read()
@@ -810,25 +1029,38 @@ impl WorldModelEnhanced {
             .iter()
             .map(|(id, tracker)| {
                 let state = tracker.get_state();
-                (
+                Ok((
                     id.clone(),
                     serde_json::json!({
                         "properties": state.properties,
                         "covariance": state.covariance,
+                        "tracker": serde_json::to_value(tracker)?,
                     }),
-                )
+                ))
             })
-            .collect();
+            .collect::<anyhow::Result<_>>()?;
 
-        let data = serde_json::json!({
+        Ok(serde_json::json!({
             "version": 1,
             "transition_counts": counts_encoded,
             "transition_totals": totals_encoded,
             "smoothing": transitions.smoothing(),
+            "transition_recency": transitions.recency_json(),
             "causal_edges": causal_edges,
+            "causal_latent": causal.latent_nodes(),
             "entities": entities_encoded,
-        });
+        }))
+    }
 
+    /// Save world model state to a JSON file.
+    /// Persists: transition counts + totals, forgetting state, snapshots and
+    /// change points, causal edges and latent nodes,
+    /// and each entity's full filter state (mode estimates and
+    /// probabilities, models, noise).
+    /// Function models keep only their name; rebind them with `bind_entity_fn`.
+    /// Uses atomic write: writes to .tmp file then renames.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> anyhow::Result<()> {
+        let data = self.to_json()?;
         // Atomic write: write to .tmp then rename
         let tmp_path = path.as_ref().with_extension("json.tmp");
         std::fs::write(&tmp_path, serde_json::to_string_pretty(&data)?)?;
@@ -841,29 +1073,62 @@ impl WorldModelEnhanced {
     pub fn load<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path.as_ref())?;
         let data: serde_json::Value = serde_json::from_str(&content)?;
-
         let wm = Self::new();
+        wm.replace_from_json(&data)?;
+        Ok(wm)
+    }
 
-        // Restore transition counts
+    /// Discard learned state and load `data` (the `to_json` format) in place,
+    /// so clones of the inner `Arc`s see the new state too.
+    pub fn replace_from_json(&self, data: &serde_json::Value) -> anyhow::Result<()> {
         {
-            let mut transitions = wm
+            let mut transitions = self
                 .transitions
                 .write()
                 .map_err(|e| anyhow::anyhow!("lock: {}", e))?;
             let smoothing = data["smoothing"].as_f64().unwrap_or(1.0);
             *transitions = TransitionModel::with_smoothing(smoothing);
+            if let Ok(adaptation) =
+                serde_json::from_value(data["transition_recency"]["adaptation"].clone())
+            {
+                transitions.set_adaptation(adaptation);
+            }
+        }
+        *self
+            .causal_graph
+            .write()
+            .map_err(|e| anyhow::anyhow!("lock: {}", e))? = CausalGraph::new();
+        self.entities
+            .write()
+            .map_err(|e| anyhow::anyhow!("lock: {}", e))?
+            .clear();
+        self.tracking
+            .write()
+            .map_err(|e| anyhow::anyhow!("lock: {}", e))?
+            .clear();
+        self.merge_json(data)
+    }
 
+    /// Add `data` (the `to_json` format) to the current state: transition
+    /// counts are summed, forgetting state is taken only for pairs not
+    /// observed here, causal edges that would close a cycle are dropped,
+    /// and entities already tracked keep their current state.
+    pub fn merge_json(&self, data: &serde_json::Value) -> anyhow::Result<()> {
+        // Restore transition counts
+        {
+            let mut transitions = self
+                .transitions
+                .write()
+                .map_err(|e| anyhow::anyhow!("lock: {}", e))?;
             if let Some(obj) = data["transition_counts"].as_object() {
                 for (k, v) in obj {
                     let parts: Vec<&str> = k.splitn(3, '\x1F').collect();
                     if parts.len() == 3 {
                         if let Some(count) = v.as_u64() {
-                            transitions.counts.insert(
-                                (
-                                    parts[0].to_string(),
-                                    parts[1].to_string(),
-                                    parts[2].to_string(),
-                                ),
+                            transitions.merge_count(
+                                parts[0].to_string(),
+                                parts[1].to_string(),
+                                parts[2].to_string(),
                                 count as usize,
                             );
                         }
@@ -875,19 +1140,22 @@ impl WorldModelEnhanced {
                     let parts: Vec<&str> = k.splitn(2, '\x1F').collect();
                     if parts.len() == 2 {
                         if let Some(total) = v.as_u64() {
-                            transitions.totals.insert(
-                                (parts[0].to_string(), parts[1].to_string()),
-                                total as usize,
-                            );
+                            *transitions
+                                .totals
+                                .entry((parts[0].to_string(), parts[1].to_string()))
+                                .or_default() += total as usize;
                         }
                     }
                 }
             }
+            transitions
+                .merge_recency_json(&data["transition_recency"])
+                .map_err(|e| anyhow::anyhow!(e))?;
         }
 
         // Restore causal edges
         {
-            let mut causal = wm
+            let mut causal = self
                 .causal_graph
                 .write()
                 .map_err(|e| anyhow::anyhow!("lock: {}", e))?;
@@ -896,20 +1164,37 @@ impl WorldModelEnhanced {
                     let from = e["from"].as_str().unwrap_or("").to_string();
                     let to = e["to"].as_str().unwrap_or("").to_string();
                     if !from.is_empty() && !to.is_empty() {
-                        let _ = causal.add_edge(from, to); // ignore cycle prevention errors on load
+                        let strength = e["strength"].as_f64().unwrap_or(1.0);
+                        // ignore cycle prevention errors on load
+                        let _ = causal.add_edge_with_strength(from, to, strength);
                     }
                 }
             }
+            if let Some(arr) = data["causal_latent"].as_array() {
+                for node in arr.iter().filter_map(|n| n.as_str()) {
+                    let _ = causal.set_latent(node, true);
+                }
+            }
         }
 
         // Restore entities
         {
-            let mut entities = wm
+            let mut entities = self
                 .entities
                 .write()
                 .map_err(|e| anyhow::anyhow!("lock: {}", e))?;
             if let Some(obj) = data["entities"].as_object() {
                 for (id, val) in obj {
+                    if entities.contains_key(id) {
+                        continue;
+                    }
+                    if let Ok(tracker) =
+                        serde_json::from_value::<EntityTracker>(val["tracker"].clone())
+                    {
+                        entities.insert(id.clone(), tracker);
+                        continue;
+                    }
+                    // Files written before trackers were persisted: mean + covariance only
                     if let (Some(props_val), Some(cov_val)) =
                         (val["properties"].as_array(), val["covariance"].as_array())
                     {
@@ -935,7 +1220,7 @@ impl WorldModelEnhanced {
             }
         }
 
-        Ok(wm)
+        Ok(())
     }
 
     pub fn causal_node_count(&self) -> usize {
@@ -1232,6 +1517,238 @@ mod tests {
         assert_eq!(state.covariance[0][0], 0.5);
     }
 
+    #[test]
+    fn test_entity_filter_state_persistence() {
+        let file_path = std::env::temp_dir().join("wm_entity_filter_test.json");
+
+        let wm = WorldModelEnhanced::new();
+        let cv = MotionModel::ConstantVelocity { dt: 1.0 };
+        let modes = vec![
+            MotionMode {
+                motion: cv.clone(),
+                process_noise: vec![vec![1e-4, 0.0], vec![0.0, 1e-4]],
+            },
+            MotionMode {
+                motion: cv,
+                process_noise: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            },
+        ];
+        let config = EntityConfig {
+            measurement: MeasurementModel::Function {
+                h: NamedFn::new("position", |x: &[f64]| vec![x[0]]),
+            },
+            filter: FilterKind::unscented(),
+            ..Default::default()
+        };
+        let tracker = EntityTracker::imm(
+            EntityState {
+                properties: vec![0.0, 1.0],
+                covariance: vec![vec![0.1, 0.0], vec![0.0, 0.1]],
+            },
+            modes,
+            vec![vec![0.9, 0.1], vec![0.1, 0.9]],
+            config,
+        )
+        .unwrap();
+        wm.register_entity_tracker("mover".to_string(), tracker)
+            .unwrap();
+        for x in [1.0, 2.0, 3.0, 7.0] {
+            wm.update_entity(
+                "mover",
+                EntityObservation {
+                    measured_properties: vec![x],
+                    measurement_noise: vec![vec![0.01]],
+                    timestamp: std::time::Instant::now(),
+                },
+            )
+            .unwrap();
+        }
+        let expected = wm.predict_entity("mover", 2).unwrap();
+
+        wm.save(&file_path).unwrap();
+        let loaded = WorldModelEnhanced::load(&file_path).unwrap();
+        let _ = std::fs::remove_file(&file_path);
+
+        // Motion is restored: the prediction keeps moving, not static
+        let predicted = loaded.predict_entity("mover", 2).unwrap();
+        for (a, b) in predicted.properties.iter().zip(expected.properties.iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        {
+            let guard = loaded.entities.read().unwrap();
+            let original = wm.entities.read().unwrap();
+            assert_eq!(
+                guard["mover"].mode_probabilities(),
+                original["mover"].mode_probabilities()
+            );
+        }
+
+        // The measurement function needs rebinding before the next update
+        let observation = EntityObservation {
+            measured_properties: vec![11.0],
+            measurement_noise: vec![vec![0.01]],
+            timestamp: std::time::Instant::now(),
+        };
+        assert!(loaded.update_entity("mover", observation.clone()).is_err());
+        let position: StateFn = Arc::new(|x: &[f64]| vec![x[0]]);
+        assert_eq!(loaded.bind_entity_fn("position", position).unwrap(), 1);
+        loaded.update_entity("mover", observation).unwrap();
+    }
+
+    #[test]
+    fn test_update_unlabeled_tracks_crossing_targets() {
+        let wm = WorldModelEnhanced::new();
+        wm.configure_tracking(TrackingConfig {
+            method: AssociationMethod::Jpda {
+                detection_probability: 0.9,
+                clutter_density: 1e-4,
+            },
+            confirm_hits: 2,
+            track: EntityConfig {
+                motion: MotionModel::ConstantVelocity { dt: 1.0 },
+                process_noise: Some(vec![
+                    vec![1e-3, 0.0, 0.0, 0.0],
+                    vec![0.0, 1e-3, 0.0, 0.0],
+                    vec![0.0, 0.0, 1e-2, 0.0],
+                    vec![0.0, 0.0, 0.0, 1e-2],
+                ]),
+                measurement: MeasurementModel::Linear {
+                    h: vec![vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]],
+                },
+                ..Default::default()
+            },
+            initial_variance: 4.0,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let scan = |t: f64| {
+            // Target A moves right along y = 0, target B moves up along x = 20
+            [[t, 0.0], [20.0, t]]
+                .iter()
+                .map(|z| EntityObservation {
+                    measured_properties: z.to_vec(),
+                    measurement_noise: vec![vec![0.01, 0.0], vec![0.0, 0.01]],
+                    timestamp: std::time::Instant::now(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = wm.update_unlabeled(scan(0.0)).unwrap();
+        assert_eq!(first.spawned.len(), 2);
+        let second = wm.update_unlabeled(scan(1.0)).unwrap();
+        assert_eq!(second.confirmed.len(), 2);
+        assert!(wm.tentative_tracks().unwrap().is_empty());
+        for t in 2..8 {
+            let report = wm.update_unlabeled(scan(t as f64)).unwrap();
+            assert!(report.spawned.is_empty(), "scan {}: {:?}", t, report);
+            assert_eq!(report.assignments.len(), 2);
+        }
+
+        // Each confirmed track followed one target and learned its velocity
+        let mut velocities: Vec<(f64, f64)> = wm
+            .list_entities()
+            .unwrap()
+            .iter()
+            .map(|id| {
+                let s = wm.predict_entity(id, 0).unwrap();
+                (s.properties[2], s.properties[3])
+            })
+            .collect();
+        velocities.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert!(velocities[0].0.abs() < 0.1 && (velocities[0].1 - 1.0).abs() < 0.1);
+        assert!((velocities[1].0 - 1.0).abs() < 0.1 && velocities[1].1.abs() < 0.1);
+
+        // Both targets vanish: tracks are lost and reported
+        for _ in 0..5 {
+            wm.update_unlabeled(Vec::new()).unwrap();
+        }
+        assert!(wm.list_entities().unwrap().is_empty());
+        assert_eq!(wm.lost_track_anomalies().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_merge_json_reads_legacy_entities() {
+        let data = serde_json::json!({
+            "entities": {
+                "legacy": {
+                    "properties": [1.0, 2.0],
+                    "covariance": [[1.0, 0.0], [0.0, 1.0]],
+                }
+            }
+        });
+        let wm = WorldModelEnhanced::new();
+        wm.merge_json(&data).unwrap();
+        let state = wm.predict_entity("legacy", 0).unwrap();
+        assert_eq!(state.properties, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_discover_and_accept_causal_edges() {
+        let wm = WorldModelEnhanced::new();
+        for (state, action, hot, cold) in [
+            ("warm", "heat", 45, 5),
+            ("warm", "cool", 25, 25),
+            ("chilly", "heat", 25, 25),
+            ("chilly", "cool", 5, 45),
+        ] {
+            for (next, times) in [("hot", hot), ("cold", cold)] {
+                for _ in 0..times {
+                    wm.observe_transition(state.into(), action.into(), next.into())
+                        .unwrap();
+                }
+            }
+        }
+        let cpdag = wm.discover_causal_structure(&DiscoveryMethod::pc()).unwrap();
+        let report = wm.accept_causal_edges(&cpdag, 0.9).unwrap();
+        assert_eq!(report.added.len(), 2);
+        assert!(wm.has_causal_path("action", "next_state").unwrap());
+        assert!(wm.accept_causal_edge(&cpdag, "next_state", "state").is_err());
+
+        // Edge strengths (discovery confidences) survive a save/load round trip
+        let restored = WorldModelEnhanced::new();
+        restored.replace_from_json(&wm.to_json().unwrap()).unwrap();
+        let strength = |edges: Vec<CausalEdge>| {
+            edges
+                .into_iter()
+                .find(|e| e.from == "state" && e.to == "next_state")
+                .map(|e| e.strength)
+        };
+        assert_eq!(strength(restored.get_causal_edges()), strength(wm.get_causal_edges()));
+        assert!(strength(restored.get_causal_edges()).unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_transition_regime_shift_survives_round_trip() {
+        let wm = WorldModelEnhanced::new();
+        wm.set_transition_adaptation(AdaptationConfig::non_stationary())
+            .unwrap();
+        for next in ["open"; 60].into_iter().chain(["jammed"; 30]) {
+            wm.observe_transition("door".into(), "push".into(), next.into())
+                .unwrap();
+        }
+        let changes = wm.transition_change_points().unwrap();
+        assert_eq!(changes.len(), 1);
+        let before = wm.get_transition_uncertainty("door", "push").unwrap();
+
+        let restored = WorldModelEnhanced::new();
+        restored.replace_from_json(&wm.to_json().unwrap()).unwrap();
+        assert_eq!(restored.transition_change_points().unwrap(), changes);
+        assert_eq!(
+            restored.transition_snapshots().unwrap(),
+            wm.transition_snapshots().unwrap()
+        );
+        let after = restored.get_transition_uncertainty("door", "push").unwrap();
+        assert!((after - before).abs() < 1e-9);
+
+        // Rolling back to the pre-change snapshot brings the old regime back
+        restored
+            .restore_transition_snapshot(changes[0].snapshot.unwrap())
+            .unwrap();
+        let pred = restored.predict_next_state("door", "push").unwrap();
+        assert!(pred.probabilities["open"] > 0.7);
+    }
+
     #[test]
     fn test_wm_causal_counts_empty() {
         let wm = WorldModelEnhanced::new();
@@ -0,0 +1,789 @@
+// Multi-Target Tracking with Data Association
+//
+// Assigns unlabeled observations to entity trackers once per scan:
+// - Mahalanobis gating against each track's predicted measurement
+// - Global nearest neighbour assignment (Hungarian algorithm) or joint
+//   probabilistic data association (JPDA) over clusters of shared gates
+// - Track lifecycle: tentative tracks are spawned from unmatched
+//   observations, confirmed after enough hits and deleted after consecutive
+//   misses; losing a confirmed track raises an Anomaly
+
+use super::entity::{
+    diagonal_matrix, identity_matrix, matrix_inverse, matrix_multiply, matrix_transpose,
+    matrix_vector_multiply, Anomaly, EntityConfig, EntityObservation, EntityState, EntityTracker,
+    Innovation, MeasurementModel, MotionMode, StateFn,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Upper bound on joint association events enumerated for one JPDA cluster;
+/// larger clusters fall back to per-track PDA weights.
+const MAX_JPDA_EVENTS: usize = 50_000;
+
+/// Cost used for impossible pairings in the assignment matrix.
+const INFEASIBLE: f64 = 1e12;
+
+/// How gated observations are shared out between tracks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssociationMethod {
+    /// Global nearest neighbour: each track takes at most one observation,
+    /// minimising total squared Mahalanobis distance.
+    Hungarian,
+    /// Joint probabilistic data association: every track is updated with
+    /// all gated observations, weighted by their marginal association
+    /// probability over feasible joint events.
+    Jpda {
+        /// Probability that a present target is detected in a scan (P_D)
+        detection_probability: f64,
+        /// Expected false observations per unit measurement volume (λ)
+        clutter_density: f64,
+    },
+}
+
+/// Lifecycle stage of a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackStatus {
+    /// Spawned from an unmatched observation, not yet reported as an entity
+    Tentative,
+    /// A tracked entity
+    Confirmed,
+}
+
+/// Configuration for [`TrackManager`].
+#[derive(Clone)]
+pub struct TrackingConfig {
+    /// Gate radius as a Mahalanobis distance (σ); observations farther
+    /// from a track's prediction are never associated with it.
+    pub gate: f64,
+
+    /// Assignment algorithm
+    pub method: AssociationMethod,
+
+    /// Hits needed to confirm a tentative track
+    pub confirm_hits: u32,
+
+    /// Consecutive misses after which a tentative track is dropped
+    pub tentative_max_misses: u32,
+
+    /// Consecutive misses after which a confirmed track is deleted
+    pub max_misses: u32,
+
+    /// Models and filter for spawned tracks
+    pub track: EntityConfig,
+
+    /// When non-empty, spawned tracks are IMM trackers over these modes,
+    /// switching with `mode_switching`.
+    pub imm_modes: Vec<MotionMode>,
+
+    /// IMM mode switching matrix for spawned tracks
+    pub mode_switching: Vec<Vec<f64>>,
+
+    /// Variance of state components an observation does not determine
+    pub initial_variance: f64,
+
+    /// Maps an observation to the initial state of a spawned track.
+    /// Required for function measurement models; otherwise the state is
+    /// the least-norm solution of z = H×x.
+    pub spawn: Option<StateFn>,
+}
+
+impl Default for TrackingConfig {
+    fn default() -> Self {
+        Self {
+            gate: 4.0,
+            method: AssociationMethod::Hungarian,
+            confirm_hits: 3,
+            tentative_max_misses: 2,
+            max_misses: 5,
+            track: EntityConfig::default(),
+            imm_modes: Vec::new(),
+            mode_switching: Vec::new(),
+            initial_variance: 10.0,
+            spawn: None,
+        }
+    }
+}
+
+/// Outcome of one [`TrackManager::scan`].
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    /// (observation index, track id) for each observation a track took;
+    /// under JPDA, pairs with association probability ≥ 0.5
+    pub assignments: Vec<(usize, String)>,
+
+    /// Tentative tracks spawned from unmatched observations
+    pub spawned: Vec<String>,
+
+    /// Tentative tracks confirmed this scan (now entities)
+    pub confirmed: Vec<String>,
+
+    /// Tracks deleted this scan, tentative or confirmed
+    pub deleted: Vec<String>,
+
+    /// Anomalies raised for confirmed tracks lost this scan
+    pub lost: Vec<(String, Anomaly)>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TrackRecord {
+    hits: u32,
+    misses: u32,
+}
+
+/// Associates unlabeled observations with tracked entities and manages
+/// the track lifecycle. Confirmed tracks live in the caller's entity map,
+/// so entities registered by hand take part in association (and are
+/// deleted when they go unobserved) like any other confirmed track.
+pub struct TrackManager {
+    config: TrackingConfig,
+    tentative: BTreeMap<String, EntityTracker>,
+    records: HashMap<String, TrackRecord>,
+    lost: Vec<(String, Anomaly)>,
+    next_id: u64,
+}
+
+impl TrackManager {
+    pub fn new(config: TrackingConfig) -> Self {
+        Self {
+            config,
+            tentative: BTreeMap::new(),
+            records: HashMap::new(),
+            lost: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn config(&self) -> &TrackingConfig {
+        &self.config
+    }
+
+    /// Replace the configuration; existing tracks and counts are kept.
+    pub fn set_config(&mut self, config: TrackingConfig) {
+        self.config = config;
+    }
+
+    /// Ids of tracks not yet confirmed
+    pub fn tentative_ids(&self) -> Vec<String> {
+        self.tentative.keys().cloned().collect()
+    }
+
+    /// Estimate of a tentative track
+    pub fn tentative_state(&self, id: &str) -> Option<EntityState> {
+        self.tentative.get(id).map(|t| t.get_state())
+    }
+
+    /// Every anomaly raised for a lost track, oldest first
+    pub fn lost_anomalies(&self) -> &[(String, Anomaly)] {
+        &self.lost
+    }
+
+    /// Drop tentative tracks and hit/miss counts (e.g. after the entity
+    /// map was replaced).
+    pub fn clear(&mut self) {
+        self.tentative.clear();
+        self.records.clear();
+    }
+
+    /// Process one scan of unlabeled observations against the confirmed
+    /// tracks in `entities` and the tentative tracks held here.
+    pub fn scan(
+        &mut self,
+        entities: &mut HashMap<String, EntityTracker>,
+        observations: &[EntityObservation],
+    ) -> Result<ScanReport, String> {
+        let mut report = ScanReport::default();
+
+        // Confirmed tracks first, each group in id order, so results do not
+        // depend on HashMap iteration order
+        let mut ids: Vec<(String, TrackStatus)> = {
+            let mut confirmed: Vec<String> = entities.keys().cloned().collect();
+            confirmed.sort();
+            confirmed
+                .into_iter()
+                .map(|id| (id, TrackStatus::Confirmed))
+                .collect()
+        };
+        ids.extend(
+            self.tentative
+                .keys()
+                .map(|id| (id.clone(), TrackStatus::Tentative)),
+        );
+
+        // Gating. A track whose measurement model cannot produce an
+        // observation's dimension simply never gates it.
+        let gate_sq = self.config.gate * self.config.gate;
+        let gated: Vec<Vec<Option<Innovation>>> = ids
+            .iter()
+            .map(|(id, status)| {
+                let tracker = match status {
+                    TrackStatus::Confirmed => &entities[id],
+                    TrackStatus::Tentative => &self.tentative[id],
+                };
+                observations
+                    .iter()
+                    .map(|o| {
+                        tracker
+                            .innovation(o)
+                            .ok()
+                            .filter(|inn| inn.mahalanobis_sq <= gate_sq)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let weights = match self.config.method {
+            AssociationMethod::Hungarian => gnn_weights(&gated, gate_sq),
+            AssociationMethod::Jpda {
+                detection_probability,
+                clutter_density,
+            } => jpda_weights(&gated, detection_probability, clutter_density),
+        };
+
+        // Updates and hit/miss bookkeeping
+        let mut claimed = vec![0.0; observations.len()];
+        for ((id, status), row) in ids.iter().zip(&weights) {
+            let tracker = match status {
+                TrackStatus::Confirmed => entities.get_mut(id),
+                TrackStatus::Tentative => self.tentative.get_mut(id),
+            }
+            .ok_or_else(|| format!("Track '{}' disappeared during scan", id))?;
+
+            let mut picked = Vec::new();
+            let mut picked_weights = Vec::new();
+            for (j, w) in row.iter().enumerate() {
+                if *w > 0.0 {
+                    picked.push(observations[j].clone());
+                    picked_weights.push(*w);
+                    claimed[j] += w;
+                    if *w >= 0.5 {
+                        report.assignments.push((j, id.clone()));
+                    }
+                }
+            }
+            if picked.is_empty() {
+                tracker.coast()?;
+            } else {
+                tracker.update_weighted(&picked, &picked_weights)?;
+            }
+
+            let record = self.records.entry(id.clone()).or_default();
+            if picked_weights.iter().sum::<f64>() >= 0.5 {
+                record.hits += 1;
+                record.misses = 0;
+            } else {
+                record.misses += 1;
+            }
+        }
+
+        // Spawn tentative tracks from observations no track took
+        for (j, observation) in observations.iter().enumerate() {
+            if claimed[j] >= 0.5 {
+                continue;
+            }
+            let tracker = self.spawn_tracker(observation)?;
+            let id = self.fresh_id(entities);
+            self.records
+                .insert(id.clone(), TrackRecord { hits: 1, misses: 0 });
+            self.tentative.insert(id.clone(), tracker);
+            report.spawned.push(id);
+        }
+
+        // Lifecycle
+        let tentative_ids: Vec<String> = self.tentative.keys().cloned().collect();
+        for id in tentative_ids {
+            let record = self.records.get(&id).copied().unwrap_or_default();
+            if record.hits >= self.config.confirm_hits {
+                if let Some(tracker) = self.tentative.remove(&id) {
+                    entities.insert(id.clone(), tracker);
+                    report.confirmed.push(id);
+                }
+            } else if record.misses >= self.config.tentative_max_misses {
+                self.tentative.remove(&id);
+                self.records.remove(&id);
+                report.deleted.push(id);
+            }
+        }
+        for (id, status) in &ids {
+            if *status != TrackStatus::Confirmed {
+                continue;
+            }
+            let misses = self.records.get(id).map(|r| r.misses).unwrap_or(0);
+            if misses >= self.config.max_misses {
+                entities.remove(id);
+                self.records.remove(id);
+                let anomaly = Anomaly {
+                    severity: misses as f64,
+                    threshold: self.config.max_misses as f64,
+                    description: format!(
+                        "Track '{}' lost after {} consecutive missed scans",
+                        id, misses
+                    ),
+                };
+                self.lost.push((id.clone(), anomaly.clone()));
+                report.lost.push((id.clone(), anomaly));
+                report.deleted.push(id.clone());
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn fresh_id(&mut self, entities: &HashMap<String, EntityTracker>) -> String {
+        loop {
+            let id = format!("track-{}", self.next_id);
+            self.next_id += 1;
+            if !entities.contains_key(&id) && !self.tentative.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn spawn_tracker(&self, observation: &EntityObservation) -> Result<EntityTracker, String> {
+        let state = self.spawn_state(observation)?;
+        if self.config.imm_modes.is_empty() {
+            Ok(EntityTracker::with_config(state, self.config.track.clone()))
+        } else {
+            EntityTracker::imm(
+                state,
+                self.config.imm_modes.clone(),
+                self.config.mode_switching.clone(),
+                self.config.track.clone(),
+            )
+        }
+    }
+
+    /// Initial estimate for a track spawned from `observation`.
+    fn spawn_state(&self, observation: &EntityObservation) -> Result<EntityState, String> {
+        let z = &observation.measured_properties;
+        if let Some(spawn) = &self.config.spawn {
+            let properties = spawn(z);
+            let dim = properties.len();
+            return Ok(EntityState {
+                properties,
+                covariance: diagonal_matrix(dim, self.config.initial_variance),
+            });
+        }
+        match &self.config.track.measurement {
+            MeasurementModel::Identity => Ok(EntityState {
+                properties: z.clone(),
+                covariance: observation.measurement_noise.clone(),
+            }),
+            MeasurementModel::Linear { h } => {
+                // x = H⁺×z with H⁺ = H^T×(H×H^T)^-1. Observed directions carry
+                // H⁺×R×H⁺^T; the null space of H gets the initial variance.
+                let ht = matrix_transpose(h);
+                let pinv = matrix_multiply(&ht, &matrix_inverse(&matrix_multiply(h, &ht)?)?)?;
+                let properties = matrix_vector_multiply(&pinv, z)?;
+                let observed = matrix_multiply(
+                    &matrix_multiply(&pinv, &observation.measurement_noise)?,
+                    &matrix_transpose(&pinv),
+                )?;
+                let projector = matrix_multiply(&pinv, h)?;
+                let identity = identity_matrix(properties.len());
+                let covariance = observed
+                    .iter()
+                    .zip(projector.iter().zip(identity.iter()))
+                    .map(|(o_row, (p_row, i_row))| {
+                        o_row
+                            .iter()
+                            .zip(p_row.iter().zip(i_row.iter()))
+                            .map(|(o, (p, i))| o + self.config.initial_variance * (i - p))
+                            .collect()
+                    })
+                    .collect();
+                Ok(EntityState {
+                    properties,
+                    covariance,
+                })
+            }
+            MeasurementModel::Function { h } => Err(format!(
+                "Spawning tracks under measurement function '{}' needs TrackingConfig::spawn",
+                h.name
+            )),
+        }
+    }
+}
+
+/// Global-nearest-neighbour weights: 1.0 for each assigned pair. Each
+/// track may stay unassigned and each observation unclaimed at cost gate²,
+/// so any gated pairing (d² ≤ gate²) beats leaving both open.
+fn gnn_weights(gated: &[Vec<Option<Innovation>>], gate_sq: f64) -> Vec<Vec<f64>> {
+    let tracks = gated.len();
+    let observations = gated.first().map_or(0, |row| row.len());
+    let mut weights = vec![vec![0.0; observations]; tracks];
+    if tracks == 0 || observations == 0 {
+        return weights;
+    }
+
+    // [ d²     | miss  ]
+    // [ unused | 0     ]
+    let n = tracks + observations;
+    let mut cost = vec![vec![INFEASIBLE; n]; n];
+    for t in 0..tracks {
+        for (o, inn) in gated[t].iter().enumerate() {
+            if let Some(inn) = inn {
+                cost[t][o] = inn.mahalanobis_sq;
+            }
+        }
+        cost[t][observations + t] = gate_sq;
+    }
+    for o in 0..observations {
+        cost[tracks + o][o] = gate_sq;
+        for t in 0..tracks {
+            cost[tracks + o][observations + t] = 0.0;
+        }
+    }
+
+    for (t, col) in hungarian(&cost).into_iter().take(tracks).enumerate() {
+        if col < observations && gated[t][col].is_some() {
+            weights[t][col] = 1.0;
+        }
+    }
+    weights
+}
+
+/// Minimum-cost perfect assignment for a square cost matrix (Kuhn-Munkres
+/// with potentials, O(n³)). Returns the column assigned to each row.
+fn hungarian(cost: &[Vec<f64>]) -> Vec<usize> {
+    let n = cost.len();
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    // owner[j]: row matched to column j (1-based, 0 = free)
+    let mut owner = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        owner[0] = row;
+        let mut j0 = 0;
+        let mut min_slack = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = owner[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = j0;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if owner[j0] == 0 {
+                break;
+            }
+        }
+        // Augment along the alternating path
+        while j0 != 0 {
+            let j1 = way[j0];
+            owner[j0] = owner[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for (j, row) in owner.iter().enumerate().skip(1) {
+        if *row != 0 {
+            assignment[row - 1] = j - 1;
+        }
+    }
+    assignment
+}
+
+/// JPDA marginal association probabilities β[t][o].
+///
+/// Tracks are grouped into clusters that share gated observations; within
+/// a cluster every feasible joint event (each track takes at most one
+/// gated observation, each observation goes to at most one track) is
+/// weighted by Π P_D×L/λ over assigned tracks and Π (1 - P_D) over the
+/// rest, and β is the normalised sum over the events containing (t, o).
+fn jpda_weights(
+    gated: &[Vec<Option<Innovation>>],
+    detection_probability: f64,
+    clutter_density: f64,
+) -> Vec<Vec<f64>> {
+    let tracks = gated.len();
+    let observations = gated.first().map_or(0, |row| row.len());
+    let mut weights = vec![vec![0.0; observations]; tracks];
+    let pd = detection_probability.clamp(1e-9, 1.0);
+    let clutter = clutter_density.max(1e-300);
+    let miss = 1.0 - pd;
+
+    // Per-track candidate list: (observation, P_D×L/λ)
+    let candidates: Vec<Vec<(usize, f64)>> = gated
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .filter_map(|(o, inn)| inn.map(|inn| (o, pd * inn.log_likelihood.exp() / clutter)))
+                .collect()
+        })
+        .collect();
+
+    for cluster in clusters(&candidates, observations) {
+        let events: usize = cluster.iter().fold(1usize, |acc, t| {
+            acc.saturating_mul(candidates[*t].len() + 1)
+        });
+        if events > MAX_JPDA_EVENTS {
+            // Too many joint events: treat each track independently (PDA)
+            for &t in &cluster {
+                let total: f64 = miss + candidates[t].iter().map(|(_, g)| g).sum::<f64>();
+                for &(o, g) in &candidates[t] {
+                    weights[t][o] = g / total;
+                }
+            }
+            continue;
+        }
+
+        let mut marginals = vec![vec![0.0; observations]; cluster.len()];
+        let mut total = 0.0;
+        let mut taken = vec![false; observations];
+        let mut chosen = vec![None; cluster.len()];
+        enumerate_events(
+            &cluster,
+            &candidates,
+            miss,
+            0,
+            1.0,
+            &mut taken,
+            &mut chosen,
+            &mut marginals,
+            &mut total,
+        );
+        if total > 0.0 {
+            for (k, &t) in cluster.iter().enumerate() {
+                for o in 0..observations {
+                    weights[t][o] = marginals[k][o] / total;
+                }
+            }
+        }
+    }
+    weights
+}
+
+/// Depth-first walk over joint association events for the tracks of one
+/// cluster, accumulating event weights into `marginals` and `total`.
+#[allow(clippy::too_many_arguments)]
+fn enumerate_events(
+    cluster: &[usize],
+    candidates: &[Vec<(usize, f64)>],
+    miss: f64,
+    depth: usize,
+    weight: f64,
+    taken: &mut [bool],
+    chosen: &mut [Option<usize>],
+    marginals: &mut [Vec<f64>],
+    total: &mut f64,
+) {
+    if depth == cluster.len() {
+        *total += weight;
+        for (k, choice) in chosen.iter().enumerate() {
+            if let Some(o) = choice {
+                marginals[k][*o] += weight;
+            }
+        }
+        return;
+    }
+
+    chosen[depth] = None;
+    enumerate_events(
+        cluster,
+        candidates,
+        miss,
+        depth + 1,
+        weight * miss,
+        taken,
+        chosen,
+        marginals,
+        total,
+    );
+    for &(o, g) in &candidates[cluster[depth]] {
+        if taken[o] {
+            continue;
+        }
+        taken[o] = true;
+        chosen[depth] = Some(o);
+        enumerate_events(
+            cluster,
+            candidates,
+            miss,
+            depth + 1,
+            weight * g,
+            taken,
+            chosen,
+            marginals,
+            total,
+        );
+        taken[o] = false;
+    }
+    chosen[depth] = None;
+}
+
+/// Connected components of tracks linked by a shared gated observation.
+fn clusters(candidates: &[Vec<(usize, f64)>], observations: usize) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    let mut first_track: Vec<Option<usize>> = vec![None; observations];
+    for (t, row) in candidates.iter().enumerate() {
+        for &(o, _) in row {
+            match first_track[o] {
+                None => first_track[o] = Some(t),
+                Some(other) => {
+                    let (a, b) = (find(&mut parent, t), find(&mut parent, other));
+                    parent[a] = b;
+                }
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (t, row) in candidates.iter().enumerate() {
+        if !row.is_empty() {
+            let root = find(&mut parent, t);
+            groups.entry(root).or_default().push(t);
+        }
+    }
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn obs(z: Vec<f64>) -> EntityObservation {
+        let m = z.len();
+        EntityObservation {
+            measured_properties: z,
+            measurement_noise: diagonal_matrix(m, 0.01),
+            timestamp: Instant::now(),
+        }
+    }
+
+    fn inn(d_sq: f64) -> Option<Innovation> {
+        Some(Innovation {
+            mahalanobis_sq: d_sq,
+            log_likelihood: -0.5 * d_sq,
+        })
+    }
+
+    #[test]
+    fn test_hungarian_finds_global_optimum() {
+        // Greedy row-by-row would give row 0 → col 0 (total 1 + 10)
+        let cost = vec![vec![1.0, 2.0], vec![2.0, 10.0]];
+        assert_eq!(hungarian(&cost), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_gnn_leaves_ungated_pairs_open() {
+        let gated = vec![vec![inn(1.0), None], vec![inn(0.5), None]];
+        let weights = gnn_weights(&gated, 16.0);
+        // Both tracks want observation 0; the closer one gets it
+        assert_eq!(weights[1][0], 1.0);
+        assert_eq!(weights[0], vec![0.0, 0.0]);
+        assert_eq!(weights[1][1], 0.0);
+    }
+
+    #[test]
+    fn test_jpda_marginals_respect_exclusivity() {
+        let gated = vec![vec![inn(0.1), inn(0.2)], vec![inn(0.2), inn(0.1)]];
+        let weights = jpda_weights(&gated, 0.9, 1e-3);
+        for row in &weights {
+            assert!(row.iter().sum::<f64>() <= 1.0 + 1e-12);
+        }
+        for o in 0..2 {
+            assert!(weights[0][o] + weights[1][o] <= 1.0 + 1e-12);
+        }
+        assert!(weights[0][0] > weights[0][1]);
+        assert!(weights[1][1] > weights[1][0]);
+    }
+
+    #[test]
+    fn test_clusters_split_on_disjoint_gates() {
+        let candidates = vec![
+            vec![(0, 1.0)],
+            vec![(0, 1.0), (1, 1.0)],
+            vec![(2, 1.0)],
+            vec![],
+        ];
+        assert_eq!(clusters(&candidates, 3), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn test_spawn_state_uses_pseudo_inverse() {
+        let config = TrackingConfig {
+            track: EntityConfig {
+                measurement: MeasurementModel::Linear {
+                    h: vec![vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]],
+                },
+                ..Default::default()
+            },
+            initial_variance: 25.0,
+            ..Default::default()
+        };
+        let manager = TrackManager::new(config);
+        let state = manager.spawn_state(&obs(vec![3.0, -2.0])).unwrap();
+        assert_eq!(state.properties, vec![3.0, -2.0, 0.0, 0.0]);
+        assert!((state.covariance[0][0] - 0.01).abs() < 1e-12);
+        assert!((state.covariance[3][3] - 25.0).abs() < 1e-12);
+        assert!(state.covariance[0][3].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_track_lifecycle() {
+        let config = TrackingConfig {
+            confirm_hits: 2,
+            tentative_max_misses: 1,
+            max_misses: 2,
+            ..Default::default()
+        };
+        let mut manager = TrackManager::new(config);
+        let mut entities = HashMap::new();
+
+        let first = manager.scan(&mut entities, &[obs(vec![0.0, 0.0])]).unwrap();
+        assert_eq!(first.spawned.len(), 1);
+        let id = first.spawned[0].clone();
+        assert_eq!(manager.tentative_ids(), vec![id.clone()]);
+
+        let second = manager
+            .scan(&mut entities, &[obs(vec![0.05, 0.0])])
+            .unwrap();
+        assert_eq!(second.assignments, vec![(0, id.clone())]);
+        assert_eq!(second.confirmed, vec![id.clone()]);
+        assert!(entities.contains_key(&id));
+
+        // Clutter far away spawns a tentative track that dies after one miss
+        let third = manager
+            .scan(&mut entities, &[obs(vec![50.0, 50.0])])
+            .unwrap();
+        assert_eq!(third.spawned.len(), 1);
+        let clutter = third.spawned[0].clone();
+        let fourth = manager.scan(&mut entities, &[]).unwrap();
+        assert!(fourth.deleted.contains(&clutter));
+        assert_eq!(fourth.lost[0].0, id);
+        assert!(!entities.contains_key(&id));
+        assert!(manager.lost_anomalies()[0].1.description.contains(&id));
+    }
+}
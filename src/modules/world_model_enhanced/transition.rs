@@ -2,8 +2,16 @@
 //
 // Implements P(s'|s,a) learning using conjugate Dirichlet prior with Laplace smoothing.
 // Tracks state transitions and provides uncertainty-aware predictions.
+// For non-stationary environments each (state, action) pair can forget old
+// evidence (exponential decay or a sliding window), flag regime shifts with
+// Bayesian online change-point detection, and the counts can be snapshotted
+// and restored by version.
 
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Change points kept; the oldest are dropped first
+const MAX_CHANGE_POINTS: usize = 1024;
 
 /// A single state transition observation (s, a, s')
 #[derive(Debug, Clone, PartialEq)]
@@ -23,12 +31,137 @@ pub struct TransitionPrediction {
     pub observation_count: usize,
 }
 
+/// How older observations of a (state, action) pair are weighed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Forgetting {
+    /// Every observation counts forever
+    None,
+    /// Each new observation of a pair multiplies its counts by `decay` ∈ (0, 1],
+    /// bounding the effective sample size by 1 / (1 − decay)
+    Exponential { decay: f64 },
+    /// Only the pair's last `size` observations are counted
+    SlidingWindow { size: usize },
+}
+
+/// Bayesian online change-point detection (Adams & MacKay, 2007), run per
+/// (state, action) pair over its next-state outcomes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChangePointConfig {
+    /// Prior probability that a regime ends at any observation (1 / expected run length)
+    pub hazard: f64,
+    /// Run lengths tracked; longer runs are merged into the longest one
+    pub max_run_length: usize,
+    /// Posterior mass on runs newer than the current regime needed to flag a change
+    pub threshold: f64,
+    /// Minimum observations between two change points of the same pair
+    pub min_segment: usize,
+    /// Replace the pair's forgetting counts with the new regime's counts
+    pub reset_on_change: bool,
+    /// Snapshot the model before a detected change is applied
+    pub snapshot_on_change: bool,
+}
+
+impl Default for ChangePointConfig {
+    fn default() -> Self {
+        Self {
+            hazard: 0.01,
+            max_run_length: 250,
+            threshold: 0.5,
+            min_segment: 5,
+            reset_on_change: true,
+            snapshot_on_change: true,
+        }
+    }
+}
+
+/// Non-stationarity settings of a [`TransitionModel`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdaptationConfig {
+    pub forgetting: Forgetting,
+    pub change_detection: Option<ChangePointConfig>,
+    /// Half-life, in model observations, applied to a pair's evidence since it
+    /// was last observed when reporting `transition_uncertainty`
+    pub staleness_half_life: Option<f64>,
+    /// Snapshots retained; the oldest are dropped first
+    pub max_snapshots: usize,
+}
+
+impl AdaptationConfig {
+    /// Stationary model: no forgetting, no change detection (the default)
+    pub fn stationary() -> Self {
+        Self {
+            forgetting: Forgetting::None,
+            change_detection: None,
+            staleness_half_life: None,
+            max_snapshots: 16,
+        }
+    }
+
+    /// Exponential forgetting (decay 0.95), change detection with defaults and
+    /// a staleness half-life of 100 observations
+    pub fn non_stationary() -> Self {
+        Self {
+            forgetting: Forgetting::Exponential { decay: 0.95 },
+            change_detection: Some(ChangePointConfig::default()),
+            staleness_half_life: Some(100.0),
+            max_snapshots: 16,
+        }
+    }
+}
+
+impl Default for AdaptationConfig {
+    fn default() -> Self {
+        Self::stationary()
+    }
+}
+
+/// A regime shift flagged for one (state, action) pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangePoint {
+    pub state: String,
+    pub action: String,
+    /// Index of the pair's first observation in the new regime
+    pub start: u64,
+    /// Model clock (observations recorded) when the change was flagged
+    pub detected_at: u64,
+    /// Posterior mass on runs that began after the previous regime
+    pub probability: f64,
+    /// Version of the snapshot taken before the change was applied
+    pub snapshot: Option<u64>,
+}
+
+/// One (state, action, next_state) entry of a snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotRow {
+    pub state: String,
+    pub action: String,
+    pub next_state: String,
+    /// Lifetime count
+    pub count: usize,
+    /// Count after forgetting
+    pub weight: f64,
+}
+
+/// Versioned copy of the learned transition counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionSnapshot {
+    pub version: u64,
+    pub label: String,
+    /// Model clock when the snapshot was taken
+    pub clock: u64,
+    pub rows: Vec<SnapshotRow>,
+}
+
 /// Dirichlet-Multinomial model for state transitions
 ///
 /// Uses conjugate prior approach:
 /// - Prior: Dir(α) with α_k = 1 (Laplace smoothing)
 /// - Posterior: Dir(α + counts)
 /// - Prediction: P(s'|s,a) = (α_{sas'} + count_{sas'}) / Σ_k (α_{sak} + count_{sak})
+///
+/// With forgetting enabled, `count` is the pair's decayed or windowed count;
+/// `counts` and `totals` always hold lifetime counts.
 #[derive(Debug, Clone)]
 pub struct TransitionModel {
     /// Transition counts: (state, action, next_state) → count
@@ -39,6 +172,185 @@ pub struct TransitionModel {
 
     /// Laplace smoothing parameter (α)
     smoothing: f64,
+
+    adaptation: AdaptationConfig,
+
+    /// Observations recorded, used to age pairs
+    clock: u64,
+
+    /// Forgetting counts and change-point posterior per (state, action)
+    recency: HashMap<(String, String), PairRecency>,
+
+    snapshots: Vec<TransitionSnapshot>,
+    next_version: u64,
+    change_points: Vec<ChangePoint>,
+}
+
+/// Recency state of one (state, action) pair
+#[derive(Debug, Clone, Default)]
+struct PairRecency {
+    /// next_state → count after forgetting (unused with `Forgetting::None`)
+    weights: HashMap<String, f64>,
+    /// Outcomes in arrival order (sliding window only)
+    window: VecDeque<String>,
+    last_seen: u64,
+    observations: u64,
+    detector: RunLengthPosterior,
+}
+
+impl PairRecency {
+    /// Record `weight` observations of `next` as the newest evidence
+    fn add(&mut self, next: &str, weight: usize, forgetting: Forgetting) {
+        match forgetting {
+            Forgetting::None => {}
+            Forgetting::Exponential { decay } => {
+                let decay = decay.clamp(0.0, 1.0);
+                self.weights.retain(|_, w| {
+                    *w *= decay;
+                    *w > 1e-9
+                });
+                *self.weights.entry(next.to_string()).or_insert(0.0) += weight as f64;
+            }
+            Forgetting::SlidingWindow { .. } => self.absorb(next, weight as f64, forgetting),
+        }
+    }
+
+    /// Add `weight` to `next` without aging existing evidence
+    fn absorb(&mut self, next: &str, weight: f64, forgetting: Forgetting) {
+        match forgetting {
+            Forgetting::None => {}
+            Forgetting::Exponential { .. } => {
+                *self.weights.entry(next.to_string()).or_insert(0.0) += weight;
+            }
+            Forgetting::SlidingWindow { size } => {
+                for _ in 0..weight.round() as usize {
+                    self.window.push_back(next.to_string());
+                }
+                while self.window.len() > size.max(1) {
+                    self.window.pop_front();
+                }
+                self.reweigh_window();
+            }
+        }
+    }
+
+    /// Keep only the evidence of the last `run` observations
+    fn reset_to_run(&mut self, run: usize, counts: &HashMap<String, f64>, forgetting: Forgetting) {
+        match forgetting {
+            Forgetting::None => {}
+            Forgetting::Exponential { .. } => self.weights = counts.clone(),
+            Forgetting::SlidingWindow { .. } => {
+                let drop = self.window.len().saturating_sub(run);
+                self.window.drain(..drop);
+                self.reweigh_window();
+            }
+        }
+    }
+
+    fn reweigh_window(&mut self) {
+        self.weights.clear();
+        for next in &self.window {
+            *self.weights.entry(next.clone()).or_insert(0.0) += 1.0;
+        }
+    }
+}
+
+/// Run-length posterior with a Dirichlet-categorical model per run
+#[derive(Debug, Clone, Default)]
+struct RunLengthPosterior {
+    /// Index = run length
+    runs: Vec<Run>,
+    outcomes: HashSet<String>,
+    map_run: usize,
+    last_start: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Run {
+    prob: f64,
+    counts: HashMap<String, f64>,
+    n: f64,
+}
+
+impl RunLengthPosterior {
+    /// Add one outcome. Returns the new MAP run length and the mass on runs
+    /// newer than the previous regime when the MAP run length drops.
+    fn update(
+        &mut self,
+        outcome: &str,
+        alpha: f64,
+        config: &ChangePointConfig,
+    ) -> Option<(usize, f64)> {
+        if self.runs.is_empty() {
+            self.runs.push(Run {
+                prob: 1.0,
+                ..Run::default()
+            });
+        }
+        self.outcomes.insert(outcome.to_string());
+        let k = (self.outcomes.len() + 1) as f64;
+        let hazard = config.hazard.clamp(1e-9, 1.0);
+
+        let mut change = 0.0;
+        for run in &mut self.runs {
+            let c = run.counts.get(outcome).copied().unwrap_or(0.0);
+            let joint = run.prob * (c + alpha) / (run.n + alpha * k);
+            change += joint * hazard;
+            run.prob = joint * (1.0 - hazard);
+            *run.counts.entry(outcome.to_string()).or_insert(0.0) += 1.0;
+            run.n += 1.0;
+        }
+        self.runs.insert(
+            0,
+            Run {
+                prob: change,
+                ..Run::default()
+            },
+        );
+        let len = config.max_run_length.max(1) + 1;
+        if self.runs.len() > len {
+            let tail: f64 = self.runs.drain(len..).map(|r| r.prob).sum();
+            self.runs[len - 1].prob += tail;
+        }
+
+        let evidence: f64 = self.runs.iter().map(|r| r.prob).sum();
+        if !evidence.is_normal() {
+            self.runs.clear();
+            self.map_run = 0;
+            return None;
+        }
+        for run in &mut self.runs {
+            run.prob /= evidence;
+        }
+
+        let previous = self.map_run;
+        self.map_run = self
+            .runs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.prob.total_cmp(&b.1.prob))
+            .map(|(r, _)| r)
+            .unwrap_or(0);
+        if self.map_run >= previous {
+            return None;
+        }
+        let newer: f64 = self.runs[..=previous.min(self.runs.len() - 1)]
+            .iter()
+            .map(|r| r.prob)
+            .sum();
+        (newer >= config.threshold).then_some((self.map_run, newer))
+    }
+}
+
+/// Persisted form of a pair's recency state
+#[derive(Serialize, Deserialize)]
+struct PairRecord {
+    state: String,
+    action: String,
+    last_seen: u64,
+    observations: u64,
+    weights: HashMap<String, f64>,
+    window: Vec<String>,
 }
 
 impl TransitionModel {
@@ -53,26 +365,66 @@ impl TransitionModel {
             counts: HashMap::new(),
             totals: HashMap::new(),
             smoothing,
+            adaptation: AdaptationConfig::default(),
+            clock: 0,
+            recency: HashMap::new(),
+            snapshots: Vec::new(),
+            next_version: 1,
+            change_points: Vec::new(),
         }
     }
 
+    /// Create model with Laplace smoothing and the given non-stationarity settings
+    pub fn with_adaptation(adaptation: AdaptationConfig) -> Self {
+        let mut model = Self::new();
+        model.set_adaptation(adaptation);
+        model
+    }
+
     /// Return the smoothing parameter (α)
     pub fn smoothing(&self) -> f64 {
         self.smoothing
     }
 
+    /// Return the non-stationarity settings
+    pub fn adaptation(&self) -> &AdaptationConfig {
+        &self.adaptation
+    }
+
+    /// Change the non-stationarity settings. Switching the forgetting mode
+    /// restarts every pair's forgetting counts from its lifetime counts;
+    /// change-point posteriors restart in any case.
+    pub fn set_adaptation(&mut self, adaptation: AdaptationConfig) {
+        let reseed = adaptation.forgetting != self.adaptation.forgetting;
+        self.adaptation = adaptation;
+        if reseed {
+            let pairs: Vec<(String, String)> = self.totals.keys().cloned().collect();
+            for sa in pairs {
+                let mut pair = self.seeded_recency(&sa);
+                if let Some(old) = self.recency.get(&sa) {
+                    pair.last_seen = old.last_seen;
+                    pair.observations = old.observations;
+                }
+                self.recency.insert(sa, pair);
+            }
+        }
+        for pair in self.recency.values_mut() {
+            pair.detector = RunLengthPosterior::default();
+        }
+        let max = self.adaptation.max_snapshots.max(1);
+        if self.snapshots.len() > max {
+            self.snapshots.drain(..self.snapshots.len() - max);
+        }
+    }
+
     /// Record a state transition observation
     pub fn record_transition(&mut self, transition: StateTransition) -> Result<(), String> {
-        let key = (
-            transition.from_state.clone(),
-            transition.action.clone(),
-            transition.to_state.clone(),
+        self.observe(
+            transition.from_state,
+            transition.action,
+            transition.to_state,
+            1,
         );
-        let sa_key = (transition.from_state, transition.action);
-
-        *self.counts.entry(key).or_insert(0) += 1;
-        *self.totals.entry(sa_key).or_insert(0) += 1;
-
         Ok(())
     }
 
@@ -84,52 +436,190 @@ impl TransitionModel {
         actual_outcome: &str,
         booster_gamma: f64,
     ) {
-        let key = (
-            state.to_string(),
-            action.to_string(),
-            actual_outcome.to_string(),
-        );
-        let sa_key = (state.to_string(), action.to_string());
         let boost = if booster_gamma < 1.0 {
             1
         } else {
             booster_gamma as usize
         };
-        *self.counts.entry(key).or_insert(0) += boost;
-        *self.totals.entry(sa_key).or_insert(0) += boost;
+        self.observe(
+            state.to_string(),
+            action.to_string(),
+            actual_outcome.to_string(),
+            boost,
+        );
+    }
+
+    /// Count `weight` observations of (s, a, s'), age the pair's evidence and
+    /// run change-point detection on the outcome
+    fn observe(&mut self, from_state: String, action: String, to_state: String, weight: usize) {
+        let sa_key = (from_state, action);
+        if !self.recency.contains_key(&sa_key) {
+            let pair = self.seeded_recency(&sa_key);
+            self.recency.insert(sa_key.clone(), pair);
+        }
+        *self
+            .counts
+            .entry((sa_key.0.clone(), sa_key.1.clone(), to_state.clone()))
+            .or_insert(0) += weight;
+        *self.totals.entry(sa_key.clone()).or_insert(0) += weight;
+        self.clock += 1;
+
+        let forgetting = self.adaptation.forgetting;
+        let alpha = self.smoothing.max(1e-3);
+        let Some(pair) = self.recency.get_mut(&sa_key) else {
+            return;
+        };
+        pair.add(&to_state, weight, forgetting);
+        pair.last_seen = self.clock;
+        pair.observations += 1;
+
+        let Some(config) = self.adaptation.change_detection else {
+            return;
+        };
+        let Some((run, probability)) = pair.detector.update(&to_state, alpha, &config) else {
+            return;
+        };
+        let start = pair.observations - run as u64;
+        if pair
+            .detector
+            .last_start
+            .is_some_and(|last| start < last + config.min_segment as u64)
+        {
+            return;
+        }
+        pair.detector.last_start = Some(start);
+        let regime = pair.detector.runs[run].counts.clone();
+
+        let snapshot = config
+            .snapshot_on_change
+            .then(|| self.snapshot(&format!("before change in ({}, {})", sa_key.0, sa_key.1)));
+        if config.reset_on_change {
+            if let Some(pair) = self.recency.get_mut(&sa_key) {
+                pair.reset_to_run(run, &regime, forgetting);
+            }
+        }
+        self.change_points.push(ChangePoint {
+            state: sa_key.0,
+            action: sa_key.1,
+            start,
+            detected_at: self.clock,
+            probability,
+            snapshot,
+        });
+        if self.change_points.len() > MAX_CHANGE_POINTS {
+            self.change_points.remove(0);
+        }
+    }
+
+    /// Recency state for a pair first seen now, with forgetting counts
+    /// started from its lifetime counts
+    fn seeded_recency(&self, sa_key: &(String, String)) -> PairRecency {
+        let mut pair = PairRecency::default();
+        if self.adaptation.forgetting == Forgetting::None {
+            return pair;
+        }
+        let mut rows: Vec<(&String, usize)> = self
+            .counts
+            .iter()
+            .filter(|((s, a, _), _)| *s == sa_key.0 && *a == sa_key.1)
+            .map(|((_, _, ns), &c)| (ns, c))
+            .collect();
+        rows.sort();
+        for (next, count) in rows {
+            pair.absorb(next, count as f64, self.adaptation.forgetting);
+        }
+        pair
+    }
+
+    /// Add lifetime counts from another model (e.g. a saved state) as
+    /// evidence of the current regime
+    pub(crate) fn merge_count(
+        &mut self,
+        state: String,
+        action: String,
+        next: String,
+        count: usize,
+    ) {
+        let forgetting = self.adaptation.forgetting;
+        if let Some(pair) = self.recency.get_mut(&(state.clone(), action.clone())) {
+            pair.absorb(&next, count as f64, forgetting);
+        }
+        *self.counts.entry((state, action, next)).or_default() += count;
+    }
+
+    /// Count of (state, action, next) after forgetting
+    fn weight(&self, state: &str, action: &str, next: &str) -> f64 {
+        let key = (state.to_string(), action.to_string());
+        match (self.adaptation.forgetting, self.recency.get(&key)) {
+            (Forgetting::None, _) | (_, None) => self
+                .counts
+                .get(&(key.0, key.1, next.to_string()))
+                .map_or(0.0, |&c| c as f64),
+            (_, Some(pair)) => pair.weights.get(next).copied().unwrap_or(0.0),
+        }
+    }
+
+    /// Observations of (state, action) still counted after forgetting
+    pub fn effective_count(&self, state: &str, action: &str) -> f64 {
+        let key = (state.to_string(), action.to_string());
+        match (self.adaptation.forgetting, self.recency.get(&key)) {
+            (Forgetting::None, _) | (_, None) => self.totals.get(&key).map_or(0.0, |&t| t as f64),
+            (_, Some(pair)) => pair.weights.values().sum(),
+        }
+    }
+
+    /// Observations recorded since (state, action) was last observed, or
+    /// `None` if it has no recency information (never seen or loaded only)
+    pub fn staleness(&self, state: &str, action: &str) -> Option<u64> {
+        self.recency
+            .get(&(state.to_string(), action.to_string()))
+            .filter(|pair| pair.observations > 0 || pair.last_seen > 0)
+            .map(|pair| self.clock - pair.last_seen)
+    }
+
+    /// Total observations recorded by this model (its clock)
+    pub fn clock(&self) -> u64 {
+        self.clock
     }
 
     /// Predict next state distribution given current state and action
     pub fn predict(&self, state: &str, action: &str) -> Result<TransitionPrediction, String> {
+        self.predict_discounted(state, action, 1.0)
+    }
+
+    /// Posterior predictive with the pair's counts scaled by `discount`
+    fn predict_discounted(
+        &self,
+        state: &str,
+        action: &str,
+        discount: f64,
+    ) -> Result<TransitionPrediction, String> {
         let sa_key = (state.to_string(), action.to_string());
 
-        // Get all observed next states for this (state, action) pair
-        let next_states: Vec<String> = self
+        // The pair must have been observed at least once
+        if !self
             .counts
             .keys()
-            .filter(|(s, a, _)| s == state && a == action)
-            .map(|(_, _, ns)| ns.clone())
-            .collect();
-
-        if next_states.is_empty() {
+            .any(|(s, a, _)| s == state && a == action)
+        {
             return Err(format!(
                 "No transitions observed for state '{}' with action '{}'",
                 state, action
             ));
         }
 
-        let total = self.totals.get(&sa_key).unwrap_or(&0);
+        let total = self.totals.get(&sa_key).copied().unwrap_or(0);
         let global_states = self.get_states();
         let vocab_size = global_states.len();
 
         // Compute posterior probabilities with Laplace smoothing
         let mut probabilities = HashMap::new();
-        let denominator = (*total as f64) + (self.smoothing * vocab_size as f64);
+        let effective = self.effective_count(state, action) * discount;
+        let denominator = effective + (self.smoothing * vocab_size as f64);
 
         for next_state in &global_states {
-            let key = (state.to_string(), action.to_string(), next_state.clone());
-            let count = self.counts.get(&key).cloned().unwrap_or(0);
-            let prob = (count as f64 + self.smoothing) / denominator;
+            let count = self.weight(state, action, next_state) * discount;
+            let prob = (count + self.smoothing) / denominator;
             probabilities.insert(next_state.clone(), prob);
         }
 
@@ -141,7 +631,7 @@ impl TransitionModel {
             action: action.to_string(),
             probabilities,
             entropy,
-            observation_count: *total,
+            observation_count: total,
         })
     }
 
@@ -151,6 +641,21 @@ impl TransitionModel {
         Ok(prediction.entropy)
     }
 
+    /// Entropy of the next-state distribution after forgetting, with the
+    /// pair's evidence further halved every `staleness_half_life`
+    /// observations since it was last seen. Equals `compute_entropy` for a
+    /// stationary model.
+    pub fn transition_uncertainty(&self, state: &str, action: &str) -> Result<f64, String> {
+        let discount = match (
+            self.adaptation.staleness_half_life,
+            self.staleness(state, action),
+        ) {
+            (Some(half_life), Some(age)) if half_life > 0.0 => 0.5f64.powf(age as f64 / half_life),
+            _ => 1.0,
+        };
+        Ok(self.predict_discounted(state, action, discount)?.entropy)
+    }
+
     /// Helper: compute entropy from probability distribution
     fn compute_entropy_from_probs(&self, probs: &HashMap<String, f64>) -> f64 {
         probs
@@ -190,6 +695,181 @@ impl TransitionModel {
         actions.dedup();
         actions
     }
+
+    // ========================================================================
+    // Change points and snapshots
+    // ========================================================================
+
+    /// Regime shifts flagged so far, oldest first
+    pub fn change_points(&self) -> &[ChangePoint] {
+        &self.change_points
+    }
+
+    /// Store a copy of the current counts and return its version
+    pub fn snapshot(&mut self, label: &str) -> u64 {
+        let version = self.next_version;
+        self.next_version += 1;
+        let mut rows: Vec<SnapshotRow> = self
+            .counts
+            .iter()
+            .map(|((s, a, ns), &count)| SnapshotRow {
+                state: s.clone(),
+                action: a.clone(),
+                next_state: ns.clone(),
+                count,
+                weight: self.weight(s, a, ns),
+            })
+            .collect();
+        rows.sort_by(|x, y| {
+            (&x.state, &x.action, &x.next_state).cmp(&(&y.state, &y.action, &y.next_state))
+        });
+        self.snapshots.push(TransitionSnapshot {
+            version,
+            label: label.to_string(),
+            clock: self.clock,
+            rows,
+        });
+        let max = self.adaptation.max_snapshots.max(1);
+        if self.snapshots.len() > max {
+            self.snapshots.drain(..self.snapshots.len() - max);
+        }
+        version
+    }
+
+    /// Retained snapshots, oldest first
+    pub fn snapshots(&self) -> &[TransitionSnapshot] {
+        &self.snapshots
+    }
+
+    /// Replace the counts with those of snapshot `version`. Restored pairs
+    /// count as last seen when the snapshot was taken; a sliding window is
+    /// refilled in next-state order.
+    pub fn restore_snapshot(&mut self, version: u64) -> Result<(), String> {
+        let snapshot = self
+            .snapshots
+            .iter()
+            .find(|s| s.version == version)
+            .cloned()
+            .ok_or_else(|| format!("No transition snapshot with version {}", version))?;
+        let forgetting = self.adaptation.forgetting;
+        self.counts.clear();
+        self.totals.clear();
+        self.recency.clear();
+        for row in snapshot.rows {
+            let sa_key = (row.state, row.action);
+            let pair = self.recency.entry(sa_key.clone()).or_default();
+            pair.absorb(&row.next_state, row.weight, forgetting);
+            pair.last_seen = snapshot.clock;
+            *self.totals.entry(sa_key.clone()).or_insert(0) += row.count;
+            self.counts
+                .insert((sa_key.0, sa_key.1, row.next_state), row.count);
+        }
+        Ok(())
+    }
+
+    /// Settings, forgetting counts, snapshots and change points as JSON
+    /// (the `transition_recency` section of a saved world model).
+    /// Change-point posteriors are not saved and restart on load.
+    pub(crate) fn recency_json(&self) -> serde_json::Value {
+        let mut pairs: Vec<PairRecord> = self
+            .recency
+            .iter()
+            .map(|((s, a), pair)| PairRecord {
+                state: s.clone(),
+                action: a.clone(),
+                last_seen: pair.last_seen,
+                observations: pair.observations,
+                weights: pair.weights.clone(),
+                window: pair.window.iter().cloned().collect(),
+            })
+            .collect();
+        pairs.sort_by(|x, y| (&x.state, &x.action).cmp(&(&y.state, &y.action)));
+        serde_json::json!({
+            "adaptation": self.adaptation,
+            "clock": self.clock,
+            "pairs": pairs,
+            "snapshots": self.snapshots,
+            "next_version": self.next_version,
+            "change_points": self.change_points,
+        })
+    }
+
+    /// Add `recency_json` output: pairs without local recency state take the
+    /// saved one, unknown snapshots and change points are appended. A saved
+    /// snapshot whose version is taken by a different local one is appended
+    /// under a fresh version. Settings are left alone; use `set_adaptation` first.
+    pub(crate) fn merge_recency_json(&mut self, data: &serde_json::Value) -> Result<(), String> {
+        if data.is_null() {
+            return Ok(());
+        }
+        let clock = data["clock"].as_u64().unwrap_or(0);
+        let pairs: Vec<PairRecord> = serde_json::from_value(data["pairs"].clone())
+            .map_err(|e| format!("Invalid transition recency pairs: {}", e))?;
+        for record in pairs {
+            let sa_key = (record.state, record.action);
+            if self.recency.get(&sa_key).is_some_and(|p| p.last_seen > 0) {
+                continue;
+            }
+            self.recency.insert(
+                sa_key,
+                PairRecency {
+                    weights: record.weights,
+                    window: record.window.into(),
+                    last_seen: record.last_seen,
+                    observations: record.observations,
+                    detector: RunLengthPosterior::default(),
+                },
+            );
+        }
+        self.clock = self.clock.max(clock);
+
+        let snapshots: Vec<TransitionSnapshot> = serde_json::from_value(data["snapshots"].clone())
+            .map_err(|e| format!("Invalid transition snapshots: {}", e))?;
+        let newest = self
+            .snapshots
+            .iter()
+            .chain(&snapshots)
+            .map(|s| s.version)
+            .max()
+            .unwrap_or(0);
+        let mut next_version = self
+            .next_version
+            .max(data["next_version"].as_u64().unwrap_or(1))
+            .max(newest + 1);
+        for mut snapshot in snapshots {
+            let same_contents = |s: &TransitionSnapshot| {
+                s.label == snapshot.label && s.clock == snapshot.clock && s.rows == snapshot.rows
+            };
+            if self.snapshots.iter().any(same_contents) {
+                continue;
+            }
+            if self.snapshots.iter().any(|s| s.version == snapshot.version) {
+                snapshot.version = next_version;
+                next_version += 1;
+            }
+            self.snapshots.push(snapshot);
+        }
+        self.snapshots.sort_by_key(|s| s.version);
+        let max = self.adaptation.max_snapshots.max(1);
+        if self.snapshots.len() > max {
+            self.snapshots.drain(..self.snapshots.len() - max);
+        }
+        self.next_version = next_version;
+
+        let change_points: Vec<ChangePoint> = serde_json::from_value(data["change_points"].clone())
+            .map_err(|e| format!("Invalid transition change points: {}", e))?;
+        for cp in change_points {
+            if !self.change_points.contains(&cp) {
+                self.change_points.push(cp);
+            }
+        }
+        self.change_points.sort_by_key(|cp| cp.detected_at);
+        if self.change_points.len() > MAX_CHANGE_POINTS {
+            self.change_points
+                .drain(..self.change_points.len() - MAX_CHANGE_POINTS);
+        }
+        Ok(())
+    }
 }
 
 impl Default for TransitionModel {
@@ -535,4 +1215,173 @@ mod tests {
 
         assert_eq!(model.observation_count(), 15);
     }
+
+    fn observe_n(model: &mut TransitionModel, state: &str, action: &str, next: &str, n: usize) {
+        for _ in 0..n {
+            model
+                .record_transition(StateTransition {
+                    from_state: state.to_string(),
+                    action: action.to_string(),
+                    to_state: next.to_string(),
+                })
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_stationary_default_unchanged() {
+        let mut model = TransitionModel::new();
+        observe_n(&mut model, "S1", "A1", "S2", 40);
+        observe_n(&mut model, "S1", "A1", "S3", 40);
+
+        assert_eq!(*model.adaptation(), AdaptationConfig::stationary());
+        assert_eq!(model.effective_count("S1", "A1"), 80.0);
+        let entropy = model.compute_entropy("S1", "A1").unwrap();
+        assert!((model.transition_uncertainty("S1", "A1").unwrap() - entropy).abs() < 1e-12);
+        assert!(model.change_points().is_empty());
+    }
+
+    #[test]
+    fn test_exponential_forgetting_tracks_new_regime() {
+        let mut stationary = TransitionModel::new();
+        let mut forgetting = TransitionModel::with_adaptation(AdaptationConfig {
+            forgetting: Forgetting::Exponential { decay: 0.9 },
+            ..AdaptationConfig::stationary()
+        });
+        for model in [&mut stationary, &mut forgetting] {
+            observe_n(model, "S1", "A1", "S2", 50);
+            observe_n(model, "S1", "A1", "S3", 20);
+        }
+
+        assert!(stationary.predict("S1", "A1").unwrap().probabilities["S3"] < 0.5);
+        let pred = forgetting.predict("S1", "A1").unwrap();
+        assert!(pred.probabilities["S3"] > 0.7);
+        // Lifetime counts are kept
+        assert_eq!(pred.observation_count, 70);
+        // Effective sample size is bounded by 1 / (1 - decay)
+        assert!(forgetting.effective_count("S1", "A1") < 10.0);
+    }
+
+    #[test]
+    fn test_sliding_window_counts_last_observations() {
+        let mut model = TransitionModel::with_adaptation(AdaptationConfig {
+            forgetting: Forgetting::SlidingWindow { size: 10 },
+            ..AdaptationConfig::stationary()
+        });
+        observe_n(&mut model, "S1", "A1", "S2", 30);
+        observe_n(&mut model, "S1", "A1", "S3", 4);
+
+        assert_eq!(model.effective_count("S1", "A1"), 10.0);
+        // (6 + 1) / (10 + 3) and (4 + 1) / (10 + 3)
+        let pred = model.predict("S1", "A1").unwrap();
+        assert!((pred.probabilities["S2"] - 7.0 / 13.0).abs() < 1e-9);
+        assert!((pred.probabilities["S3"] - 5.0 / 13.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_change_point_detection_flags_regime_shift() {
+        let mut model = TransitionModel::with_adaptation(AdaptationConfig::non_stationary());
+        // Stable but mixed regime: no change expected
+        for _ in 0..30 {
+            observe_n(&mut model, "S1", "A1", "S2", 2);
+            observe_n(&mut model, "S1", "A1", "S3", 1);
+        }
+        assert!(model.change_points().is_empty());
+
+        observe_n(&mut model, "S1", "A1", "S4", 30);
+        let changes = model.change_points();
+        assert_eq!(changes.len(), 1, "{:?}", changes);
+        let change = &changes[0];
+        assert_eq!(
+            (change.state.as_str(), change.action.as_str()),
+            ("S1", "A1")
+        );
+        assert!((90..=95).contains(&change.start), "{:?}", change);
+        assert!(change.probability >= 0.5);
+
+        // The pre-change counts were snapshotted and the new regime dominates
+        let version = change.snapshot.expect("snapshot before reset");
+        let snapshot = model
+            .snapshots()
+            .iter()
+            .find(|s| s.version == version)
+            .unwrap();
+        assert!(snapshot
+            .rows
+            .iter()
+            .any(|r| r.next_state == "S2" && r.count == 60));
+        let pred = model.predict("S1", "A1").unwrap();
+        assert!(pred.probabilities["S4"] > 0.8);
+        // Old-regime outcomes keep only the smoothing mass after the reset
+        assert_eq!(pred.probabilities["S2"], pred.probabilities["S1"]);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut model = TransitionModel::with_adaptation(AdaptationConfig {
+            forgetting: Forgetting::SlidingWindow { size: 5 },
+            max_snapshots: 2,
+            ..AdaptationConfig::stationary()
+        });
+        observe_n(&mut model, "S1", "A1", "S2", 8);
+        let v1 = model.snapshot("first");
+        observe_n(&mut model, "S1", "A1", "S3", 8);
+        let before = model.predict("S1", "A1").unwrap().probabilities;
+
+        model.restore_snapshot(v1).unwrap();
+        assert_eq!(model.observation_count(), 8);
+        assert_eq!(model.effective_count("S1", "A1"), 5.0);
+        assert!(model.predict("S1", "A1").unwrap().probabilities["S2"] > 0.6);
+        assert!(before["S3"] > 0.6);
+
+        model.snapshot("second");
+        model.snapshot("third");
+        let versions: Vec<u64> = model.snapshots().iter().map(|s| s.version).collect();
+        assert_eq!(versions, vec![2, 3]);
+        assert!(model.restore_snapshot(v1).is_err());
+    }
+
+    #[test]
+    fn test_merge_renumbers_colliding_snapshots() {
+        let mut local = TransitionModel::new();
+        observe_n(&mut local, "S1", "A1", "S2", 3);
+        assert_eq!(local.snapshot("local"), 1);
+
+        let mut remote = TransitionModel::new();
+        observe_n(&mut remote, "S1", "A1", "S3", 4);
+        assert_eq!(remote.snapshot("remote"), 1);
+        let saved = remote.recency_json();
+
+        local.merge_recency_json(&saved).unwrap();
+        let labels: Vec<(u64, &str)> = local
+            .snapshots()
+            .iter()
+            .map(|s| (s.version, s.label.as_str()))
+            .collect();
+        assert_eq!(labels, vec![(1, "local"), (2, "remote")]);
+        assert_eq!(local.snapshots()[1].rows, remote.snapshots()[0].rows);
+
+        // Merging the same data again adds nothing; new versions keep counting
+        local.merge_recency_json(&saved).unwrap();
+        assert_eq!(local.snapshots().len(), 2);
+        assert_eq!(local.snapshot("next"), 3);
+    }
+
+    #[test]
+    fn test_uncertainty_grows_with_staleness() {
+        let mut model = TransitionModel::with_adaptation(AdaptationConfig {
+            staleness_half_life: Some(20.0),
+            ..AdaptationConfig::stationary()
+        });
+        observe_n(&mut model, "S1", "A1", "S2", 20);
+        let fresh = model.transition_uncertainty("S1", "A1").unwrap();
+        assert!((fresh - model.compute_entropy("S1", "A1").unwrap()).abs() < 1e-12);
+
+        observe_n(&mut model, "S5", "A2", "S6", 60);
+        assert_eq!(model.staleness("S1", "A1"), Some(60));
+        let stale = model.transition_uncertainty("S1", "A1").unwrap();
+        assert!(stale > fresh + 0.5, "fresh {} stale {}", fresh, stale);
+        // Predictions themselves do not age without forgetting
+        assert!(stale > model.compute_entropy("S1", "A1").unwrap() + 0.5);
+    }
 }
@@ -62,22 +62,70 @@ impl MonitoringService {
             bursty: self.request_poisson.is_bursty(),
         }
     }
+
+    /// Render this service's metrics plus the global `telemetry::METRICS`
+    /// registry in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let snap = self.snapshot();
+        let mut out = String::new();
+        out.push_str("# HELP hipcortex_request_rate Mean request rate (Poisson estimate)\n");
+        out.push_str("# TYPE hipcortex_request_rate gauge\n");
+        out.push_str(&format!("hipcortex_request_rate {}\n", snap.request_rate));
+        out.push_str("# HELP hipcortex_request_bursty 1 when request arrivals are bursty\n");
+        out.push_str("# TYPE hipcortex_request_bursty gauge\n");
+        out.push_str(&format!("hipcortex_request_bursty {}\n", snap.bursty as u8));
+        out.push_str("# HELP hipcortex_cache_hits_total Semantic cache hits\n");
+        out.push_str("# TYPE hipcortex_cache_hits_total counter\n");
+        out.push_str(&format!("hipcortex_cache_hits_total {}\n", snap.cache_hits));
+        out.push_str("# HELP hipcortex_fsm_transitions_total FSM transitions by edge\n");
+        out.push_str("# TYPE hipcortex_fsm_transitions_total counter\n");
+        let mut edges: Vec<(&String, &String, &usize)> = snap
+            .fsm_transitions
+            .iter()
+            .flat_map(|(from, inner)| inner.iter().map(move |(to, c)| (from, to, c)))
+            .collect();
+        edges.sort();
+        for (from, to, count) in edges {
+            out.push_str(&format!(
+                "hipcortex_fsm_transitions_total{{from=\"{}\",to=\"{}\"}} {}\n",
+                from, to, count
+            ));
+        }
+        out.push_str(&crate::telemetry::METRICS.render_prometheus());
+        out
+    }
 }
 
 #[cfg(feature = "web-server")]
 use axum::{routing::get, Json, Router};
 
 #[cfg(feature = "web-server")]
-/// Build a router exposing a `/metrics` endpoint.
+/// Build a router exposing a JSON `/metrics` endpoint and a Prometheus
+/// text-format `/metrics/prometheus` endpoint.
 pub fn routes(service: Arc<Mutex<MonitoringService>>) -> Router {
-    Router::new().route(
-        "/metrics",
-        get(move || {
-            let service = service.clone();
-            async move {
-                let metrics = service.lock().unwrap().snapshot();
-                Json(metrics)
-            }
-        }),
-    )
+    let prom = service.clone();
+    Router::new()
+        .route(
+            "/metrics",
+            get(move || {
+                let service = service.clone();
+                async move {
+                    let metrics = service.lock().unwrap().snapshot();
+                    Json(metrics)
+                }
+            }),
+        )
+        .route(
+            "/metrics/prometheus",
+            get(move || {
+                let service = prom.clone();
+                async move {
+                    let body = service.lock().unwrap().render_prometheus();
+                    (
+                        [("Content-Type", "text/plain; version=0.0.4; charset=utf-8")],
+                        body,
+                    )
+                }
+            }),
+        )
 }
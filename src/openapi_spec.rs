@@ -341,7 +341,7 @@ pub const OPENAPI_SPEC: &str = r##"{
         "operationId": "causalIntervention",
         "summary": "do-calculus intervention query",
         "security": [],
-        "responses": { "200": { "description": "Intervention result" } }
+        "responses": { "200": { "description": "Intervention result with the adjustment formula used, or why the effect is not identifiable" } }
       }
     },
     "/worldmodel/causal/counterfactual": {
@@ -0,0 +1,228 @@
+//! PageTier — sled-backed overflow for a `MemoryStore` with a hot-record cap.
+//!
+//! Chain-of-thought: a store opened `with_paging` keeps at most a fixed number
+//! of decoded records in its `RecordSet`; the oldest resident records beyond
+//! that are written here and dropped from memory and from the backend. Unlike
+//! the cold tier's immutable zstd segments, pages are read and deleted one
+//! record at a time, so lookups and forgets stay cheap. Records sit in a sled
+//! tree keyed by a sequence number that preserves page-out order; RAM holds
+//! only the id → sequence map and actor/action/target postings of
+//! `(seq, id)`, rebuilt with one streaming pass on open. All mutation still
+//! goes through `MemoryStore`, which takes a record back out of its page
+//! before changing it.
+
+use crate::memory_record::MemoryRecord;
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Posting-list entry: ordered by sequence, so lookups come out oldest first.
+type Key = (u64, Uuid);
+type Index = HashMap<String, BTreeSet<Key>>;
+
+pub struct PageTier {
+    db: sled::Db,
+    pages: sled::Tree,
+    next_seq: u64,
+    seq_of: HashMap<Uuid, u64>,
+    by_actor: Index,
+    by_action: Index,
+    by_target: Index,
+}
+
+impl PageTier {
+    /// Open (or create) a page tier in the sled directory `dir`, rebuilding
+    /// its indices from the stored records.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let db = sled::open(dir)?;
+        let pages = db.open_tree("pages")?;
+        let mut tier = Self {
+            db,
+            pages,
+            next_seq: 0,
+            seq_of: HashMap::new(),
+            by_actor: HashMap::new(),
+            by_action: HashMap::new(),
+            by_target: HashMap::new(),
+        };
+        for item in tier.pages.iter() {
+            let (key, value) = item?;
+            let seq = decode_seq(&key)?;
+            let rec: MemoryRecord = serde_json::from_slice(&value)?;
+            tier.index(seq, &rec);
+            tier.next_seq = seq + 1;
+        }
+        Ok(tier)
+    }
+
+    pub fn len(&self) -> usize {
+        self.seq_of.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq_of.is_empty()
+    }
+
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.seq_of.contains_key(&id)
+    }
+
+    /// Read a paged record without removing it.
+    pub fn get(&self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        match self.seq_of.get(&id) {
+            Some(seq) => self.read(*seq).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Ids of the paged records with this actor, in page-out order.
+    pub fn by_actor(&self, actor: &str) -> Vec<Uuid> {
+        posting(&self.by_actor, actor)
+    }
+
+    pub fn by_action(&self, action: &str) -> Vec<Uuid> {
+        posting(&self.by_action, action)
+    }
+
+    pub fn by_target(&self, target: &str) -> Vec<Uuid> {
+        posting(&self.by_target, target)
+    }
+
+    /// Stream the paged records in page-out order. The iterator does not
+    /// borrow the tier, so callers can release its lock while reading.
+    pub fn iter(&self) -> impl Iterator<Item = Result<MemoryRecord>> {
+        self.pages
+            .iter()
+            .values()
+            .map(|value| Ok(serde_json::from_slice(&value?)?))
+    }
+
+    /// Every paged record, in page-out order.
+    pub fn scan(&self) -> Result<Vec<MemoryRecord>> {
+        self.iter().collect()
+    }
+
+    /// Write `records` out and flush. A record already paged is replaced.
+    /// Returns the number written.
+    pub fn page_out(&mut self, records: &[MemoryRecord]) -> Result<usize> {
+        for rec in records {
+            self.remove_one(rec.id)?;
+            let seq = self.next_seq;
+            self.next_seq += 1;
+            self.pages
+                .insert(seq.to_be_bytes(), serde_json::to_vec(rec)?)?;
+            self.index(seq, rec);
+        }
+        self.db.flush()?;
+        Ok(records.len())
+    }
+
+    /// Remove a record from its page and return it, for faulting back in.
+    pub fn take(&mut self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        let rec = self.remove_one(id)?;
+        if rec.is_some() {
+            self.db.flush()?;
+        }
+        Ok(rec)
+    }
+
+    /// Delete the given records; ids that are not paged are ignored.
+    /// Returns the number deleted.
+    pub fn remove(&mut self, ids: &[Uuid]) -> Result<usize> {
+        let mut n = 0;
+        for id in ids {
+            if self.remove_one(*id)?.is_some() {
+                n += 1;
+            }
+        }
+        if n > 0 {
+            self.db.flush()?;
+        }
+        Ok(n)
+    }
+
+    /// Delete every paged record `keep` rejects; returns their ids.
+    pub fn retain(&mut self, mut keep: impl FnMut(&MemoryRecord) -> bool) -> Result<Vec<Uuid>> {
+        let mut gone = Vec::new();
+        for rec in self.iter() {
+            let rec = rec?;
+            if !keep(&rec) {
+                gone.push(rec.id);
+            }
+        }
+        self.remove(&gone)?;
+        Ok(gone)
+    }
+
+    pub fn clear(&mut self) -> Result<()> {
+        self.pages.clear()?;
+        self.db.flush()?;
+        self.seq_of.clear();
+        self.by_actor.clear();
+        self.by_action.clear();
+        self.by_target.clear();
+        Ok(())
+    }
+
+    fn read(&self, seq: u64) -> Result<MemoryRecord> {
+        let value = self
+            .pages
+            .get(seq.to_be_bytes())?
+            .ok_or_else(|| anyhow!("page {seq} is indexed but missing"))?;
+        Ok(serde_json::from_slice(&value)?)
+    }
+
+    fn remove_one(&mut self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        let Some(seq) = self.seq_of.get(&id).copied() else {
+            return Ok(None);
+        };
+        let rec = self.read(seq)?;
+        self.pages.remove(seq.to_be_bytes())?;
+        self.unindex(seq, &rec);
+        Ok(Some(rec))
+    }
+
+    fn index(&mut self, seq: u64, r: &MemoryRecord) {
+        let key = (seq, r.id);
+        self.seq_of.insert(r.id, seq);
+        for (index, field) in [
+            (&mut self.by_actor, &r.actor),
+            (&mut self.by_action, &r.action),
+            (&mut self.by_target, &r.target),
+        ] {
+            index.entry(field.clone()).or_default().insert(key);
+        }
+    }
+
+    fn unindex(&mut self, seq: u64, r: &MemoryRecord) {
+        let key = (seq, r.id);
+        self.seq_of.remove(&r.id);
+        for (index, field) in [
+            (&mut self.by_actor, &r.actor),
+            (&mut self.by_action, &r.action),
+            (&mut self.by_target, &r.target),
+        ] {
+            if let Some(keys) = index.get_mut(field) {
+                keys.remove(&key);
+                if keys.is_empty() {
+                    index.remove(field);
+                }
+            }
+        }
+    }
+}
+
+fn posting(index: &Index, term: &str) -> Vec<Uuid> {
+    index
+        .get(term)
+        .map(|keys| keys.iter().map(|(_, id)| *id).collect())
+        .unwrap_or_default()
+}
+
+fn decode_seq(key: &[u8]) -> Result<u64> {
+    let bytes: [u8; 8] = key
+        .try_into()
+        .map_err(|_| anyhow!("bad page key of {} bytes", key.len()))?;
+    Ok(u64::from_be_bytes(bytes))
+}
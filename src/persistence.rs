@@ -1,4 +1,5 @@
-use crate::memory_record::MemoryRecord;
+use crate::memory_record::{MemoryRecord, MemoryType, RecordStatus};
+use crate::store_format::FormatHeader;
 use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm,
@@ -7,6 +8,7 @@ use anyhow::Result;
 #[cfg(feature = "async-store")]
 use async_trait::async_trait;
 use base64::Engine as _;
+use chrono::{DateTime, Utc};
 use rand::RngCore;
 use std::io::{BufRead, BufReader, Write};
 #[cfg(feature = "async-store")]
@@ -21,6 +23,99 @@ pub trait MemoryBackend {
     fn append(&mut self, record: &MemoryRecord) -> Result<()>;
     fn flush(&mut self) -> Result<()>;
     fn clear(&mut self) -> Result<()>;
+
+    /// Overwrite the stored copy of `record` (matched by id). `Ok(false)`
+    /// means the backend cannot update in place and the store should rewrite
+    /// it with `clear` + `append`.
+    fn update(&mut self, _record: &MemoryRecord) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Remove the records with these ids; `Ok(false)` as for `update`.
+    fn delete(&mut self, _ids: &[uuid::Uuid]) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Answer `filter` from the backend's own indexes. `Ok(None)` when the
+    /// backend cannot, in which case the store scans its records.
+    fn query(&mut self, _filter: &RecordFilter) -> Result<Option<Vec<MemoryRecord>>> {
+        Ok(None)
+    }
+}
+
+/// Field filters for `MemoryStore::query`, pushed down to backends that can
+/// evaluate them. All set fields must match; results keep insertion order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+    pub record_type: Option<MemoryType>,
+    pub status: Option<RecordStatus>,
+    /// Inclusive lower bound on `timestamp`.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `timestamp`.
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+impl RecordFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn record_type(mut self, record_type: MemoryType) -> Self {
+        self.record_type = Some(record_type);
+        self
+    }
+
+    pub fn status(mut self, status: RecordStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn since(mut self, ts: DateTime<Utc>) -> Self {
+        self.since = Some(ts);
+        self
+    }
+
+    pub fn until(mut self, ts: DateTime<Utc>) -> Self {
+        self.until = Some(ts);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, r: &MemoryRecord) -> bool {
+        self.actor.as_ref().is_none_or(|a| &r.actor == a)
+            && self.action.as_ref().is_none_or(|a| &r.action == a)
+            && self.target.as_ref().is_none_or(|t| &r.target == t)
+            && self
+                .record_type
+                .as_ref()
+                .is_none_or(|t| &r.record_type == t)
+            && self.status.is_none_or(|s| r.status == s)
+            && self.since.is_none_or(|ts| r.timestamp >= ts)
+            && self.until.is_none_or(|ts| r.timestamp < ts)
+    }
 }
 
 #[cfg(feature = "async-store")]
@@ -30,6 +125,16 @@ pub trait AsyncMemoryBackend {
     async fn append(&mut self, record: &MemoryRecord) -> Result<()>;
     async fn flush(&mut self) -> Result<()>;
     async fn clear(&mut self) -> Result<()>;
+
+    /// Async counterpart of `MemoryBackend::update`.
+    async fn update(&mut self, _record: &MemoryRecord) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Async counterpart of `MemoryBackend::delete`.
+    async fn delete(&mut self, _ids: &[uuid::Uuid]) -> Result<bool> {
+        Ok(false)
+    }
 }
 
 pub struct FileBackend {
@@ -157,6 +262,18 @@ impl MemoryBackend for FileBackend {
             let content = std::fs::read_to_string(&self.path)?;
             let mut lines: Vec<&str> = content.lines().collect();
 
+            // Format header: refuse files from a newer build; keep the line
+            // aside so crash recovery below never mistakes it for a record.
+            let first = lines.iter().position(|l| !l.trim().is_empty());
+            let header = first.and_then(|i| FormatHeader::parse(lines[i]).map(|h| (i, h)));
+            let header_line = match header {
+                Some((i, h)) => {
+                    h.ensure_readable()?;
+                    Some(lines.drain(..=i).next_back().unwrap_or_default())
+                }
+                None => None,
+            };
+
             // ── Crash recovery: truncate incomplete trailing line ──────
             // If the server crashed mid-write, the last line may be a
             // partial JSON record. Scan backward and drop broken lines.
@@ -195,7 +312,7 @@ impl MemoryBackend for FileBackend {
             }
 
             if dropped > 0 {
-                let fixed = lines.join("\n") + "\n";
+                let fixed = header_line.into_iter().chain(lines.iter().copied()).collect::<Vec<_>>().join("\n") + "\n";
                 std::fs::write(&self.path, &fixed)?;
                 eprintln!(
                     "[MemoryStore] Crash recovery: dropped {} incomplete line(s) from {}",
@@ -254,12 +371,17 @@ impl MemoryBackend for FileBackend {
 
     fn append(&mut self, record: &MemoryRecord) -> Result<()> {
         if self.writer.is_none() {
-            self.writer = Some(std::io::BufWriter::new(
+            let fresh = !std::fs::metadata(&self.path).is_ok_and(|m| m.len() > 0);
+            let mut writer = std::io::BufWriter::new(
                 std::fs::OpenOptions::new()
                     .create(true)
                     .append(true)
                     .open(&self.path)?,
-            ));
+            );
+            if fresh {
+                writeln!(writer, "{}", FormatHeader::current().to_line())?;
+            }
+            self.writer = Some(writer);
         }
         let mut writer = self.writer.as_mut().unwrap();
         let data = serde_json::to_vec(record)?;
@@ -444,7 +566,9 @@ impl AsyncMemoryBackend for AsyncFileBackend {
             let mut line = String::new();
             while reader.read_line(&mut line).await? > 0 {
                 let trimmed = line.trim();
-                if !trimmed.is_empty() {
+                if let Some(header) = FormatHeader::parse(trimmed) {
+                    header.ensure_readable()?;
+                } else if !trimmed.is_empty() {
                     if let Some(cipher) = &self.cipher {
                         #[derive(serde::Deserialize)]
                         struct EncLine {
@@ -493,8 +617,20 @@ impl AsyncMemoryBackend for AsyncFileBackend {
 
     async fn append(&mut self, record: &MemoryRecord) -> Result<()> {
         if self.writer.is_none() {
-            let file = AsyncFile::create(&self.path).await?;
-            self.writer = Some(AsyncBufWriter::new(file));
+            let fresh = !tokio::fs::metadata(&self.path)
+                .await
+                .is_ok_and(|m| m.len() > 0);
+            let file = AsyncFile::options()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            let mut writer = AsyncBufWriter::new(file);
+            if fresh {
+                let header = FormatHeader::current().to_line() + "\n";
+                writer.write_all(header.as_bytes()).await?;
+            }
+            self.writer = Some(writer);
         }
         let writer = self.writer.as_mut().unwrap();
         let data = serde_json::to_vec(record)?;
@@ -0,0 +1,305 @@
+//! Point-in-time recovery — rebuild the memory store as of any tx or timestamp.
+//!
+//! Chain-of-thought: `MemoryStore::snapshot`/`rollback` and `SnapshotManager` only
+//! restore whole files taken at arbitrary moments, and `TxLog` entries used to
+//! carry ids but no contents, so there was nothing to replay. With the store's
+//! change journal enabled every tx now carries the post-images of the records it
+//! touched (`RecordChange`). Recovery is then: load the newest checkpoint at or
+//! before the target tx, and replay the log forward to the target. Checkpoints
+//! live next to the log in `<log>.checkpoints/ckpt-<tx>.jsonl`. `start_journal`
+//! writes one the first time the journal is switched on, since anything written
+//! before that is not in the log. Without a checkpoint at or before the target
+//! the replay starts from an empty store at tx 0, which is only complete if the
+//! journal has been on since the log was created, so such targets are refused
+//! once the log holds memory txs that carry no changes.
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use uuid::Uuid;
+
+use crate::memory_record::MemoryRecord;
+use crate::memory_store::MemoryStore;
+use crate::persistence::{InMemoryBackend, MemoryBackend};
+use crate::tx_log::{RecordChange, TxEntry, TxKind, TxLog};
+
+/// A point on the tx timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOf {
+    /// State right after this tx was applied.
+    Tx(u64),
+    /// State after the last tx logged at or before this unix time (ms).
+    TimestampMs(u64),
+}
+
+impl AsOf {
+    /// Parse a bare tx id (`"42"`) or an RFC 3339 timestamp.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Ok(tx) = s.parse::<u64>() {
+            return Ok(AsOf::Tx(tx));
+        }
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|t| AsOf::TimestampMs(t.timestamp_millis().max(0) as u64))
+            .map_err(|_| format!("as_of must be a tx id or RFC 3339 timestamp, got {s:?}"))
+    }
+}
+
+/// Directory holding checkpoints for `log`.
+pub fn checkpoint_dir(log: &TxLog) -> PathBuf {
+    let mut name = log.path().as_os_str().to_os_string();
+    name.push(".checkpoints");
+    PathBuf::from(name)
+}
+
+/// tx ids of every checkpoint on disk, ascending.
+pub fn list_checkpoints(log: &TxLog) -> Vec<u64> {
+    let mut txs: Vec<u64> = std::fs::read_dir(checkpoint_dir(log))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            name.strip_prefix("ckpt-")?.strip_suffix(".jsonl")?.parse().ok()
+        })
+        .collect();
+    txs.sort_unstable();
+    txs
+}
+
+/// Log the store's pending journal as one tx. The record ids are taken from the
+/// changes themselves. Returns `None` when there was nothing to log.
+pub fn log_pending<B: MemoryBackend>(
+    store: &mut MemoryStore<B>,
+    log: &TxLog,
+    kind: TxKind,
+    actor: &str,
+) -> Option<u64> {
+    let changes = store.take_changes();
+    if changes.is_empty() {
+        return None;
+    }
+    let mut ids: Vec<Uuid> = changes
+        .iter()
+        .filter_map(|c| match c {
+            RecordChange::Upsert { record } => Some(record.id),
+            RecordChange::Delete { id } => Some(*id),
+            RecordChange::Clear => None,
+        })
+        .collect();
+    ids.dedup();
+    Some(log.append_with_changes(kind, ids, actor, changes))
+}
+
+/// Write a full copy of the store (hot and cold tiers) as a checkpoint and log
+/// a `Checkpoint` tx. Pending journal entries ride on that tx so the checkpoint
+/// and the log agree. Enables the journal if it was off. Returns the tx id.
+pub fn checkpoint<B: MemoryBackend>(store: &mut MemoryStore<B>, log: &TxLog) -> Result<u64, String> {
+    store.enable_change_journal();
+    let changes = store.take_changes();
+    let tx = log.append_with_changes(TxKind::Checkpoint, vec![], "system", changes);
+
+    let mut records: Vec<MemoryRecord> = store.all().to_vec();
+    if let Some(cold) = store.cold_tier() {
+        records.extend(cold.scan().map_err(|e| format!("cold tier scan: {e}"))?);
+    }
+
+    let dir = checkpoint_dir(log);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("checkpoint dir: {e}"))?;
+    let path = dir.join(format!("ckpt-{tx:012}.jsonl"));
+    let tmp = path.with_extension("jsonl.tmp");
+    let mut file = std::fs::File::create(&tmp).map_err(|e| format!("checkpoint: {e}"))?;
+    for rec in &records {
+        let line = serde_json::to_string(rec).map_err(|e| format!("checkpoint: {e}"))?;
+        writeln!(file, "{line}").map_err(|e| format!("checkpoint: {e}"))?;
+    }
+    file.sync_all().map_err(|e| format!("checkpoint: {e}"))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("checkpoint: {e}"))?;
+    Ok(tx)
+}
+
+/// Turn on the store's change journal for `log`. The first time, a checkpoint
+/// is written so replays have a base covering whatever was written while the
+/// journal was off — unless the log and the store are both empty, where a
+/// replay from tx 0 is already complete. Returns the checkpoint tx, if any.
+pub fn start_journal<B: MemoryBackend>(
+    store: &mut MemoryStore<B>,
+    log: &TxLog,
+) -> Result<Option<u64>, String> {
+    if store.change_journal_enabled() {
+        return Ok(None);
+    }
+    let empty = store.all().is_empty() && store.cold_tier().is_none_or(|c| c.is_empty());
+    if empty && log.current_tx() == 0 {
+        store.enable_change_journal();
+        return Ok(None);
+    }
+    checkpoint(store, log).map(Some)
+}
+
+/// Delete all but the newest `keep` checkpoints. Returns how many were removed.
+pub fn prune_checkpoints(log: &TxLog, keep: usize) -> Result<usize, String> {
+    let txs = list_checkpoints(log);
+    let excess = txs.len().saturating_sub(keep);
+    for tx in &txs[..excess] {
+        std::fs::remove_file(checkpoint_dir(log).join(format!("ckpt-{tx:012}.jsonl")))
+            .map_err(|e| format!("prune checkpoint {tx}: {e}"))?;
+    }
+    Ok(excess)
+}
+
+fn read_entries(path: &Path) -> Result<Vec<TxEntry>, String> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let file = std::fs::File::open(path).map_err(|e| format!("read tx log: {e}"))?;
+    let mut entries: Vec<TxEntry> = std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|l| serde_json::from_str(&l).ok())
+        .collect();
+    entries.sort_by_key(|e| e.tx_id);
+    Ok(entries)
+}
+
+fn read_checkpoint(log: &TxLog, tx: u64) -> Result<Vec<MemoryRecord>, String> {
+    let path = checkpoint_dir(log).join(format!("ckpt-{tx:012}.jsonl"));
+    let file = std::fs::File::open(&path).map_err(|e| format!("open checkpoint {tx}: {e}"))?;
+    std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(&l).map_err(|e| format!("checkpoint {tx}: {e}")))
+        .collect()
+}
+
+/// A memory tx logged without its record changes (journal off), which a
+/// replay cannot reproduce. Tiering demotions change no record and carry none.
+fn unjournaled(entry: &TxEntry) -> bool {
+    entry.changes.is_empty()
+        && matches!(
+            entry.kind,
+            TxKind::MemoryAdd
+                | TxKind::MemoryUpdate
+                | TxKind::MemoryDelete
+                | TxKind::Consolidate
+                | TxKind::ForgetActor
+                | TxKind::ArchiveRecord
+        )
+}
+
+/// Resolve `as_of` to a concrete tx id (0 = before the first tx).
+pub fn resolve_tx(log: &TxLog, as_of: AsOf) -> Result<u64, String> {
+    match as_of {
+        AsOf::Tx(tx) => {
+            let current = log.current_tx();
+            if tx > current {
+                return Err(format!("tx {tx} is ahead of the log (current tx {current})"));
+            }
+            Ok(tx)
+        }
+        AsOf::TimestampMs(ms) => Ok(read_entries(log.path())?
+            .iter()
+            .filter(|e| e.timestamp_ms <= ms)
+            .map(|e| e.tx_id)
+            .max()
+            .unwrap_or(0)),
+    }
+}
+
+/// Reconstruct the record set as of `as_of`: newest checkpoint at or before the
+/// target, then every logged change up to and including the target tx. With no
+/// such checkpoint, a target past any unjournaled memory tx is an error.
+pub fn state_as_of(log: &TxLog, as_of: AsOf) -> Result<Vec<MemoryRecord>, String> {
+    let target = resolve_tx(log, as_of)?;
+    let checkpoints = list_checkpoints(log);
+    let base = checkpoints.iter().copied().filter(|&t| t <= target).max();
+    let entries = read_entries(log.path())?;
+
+    let mut state: IndexMap<Uuid, MemoryRecord> = IndexMap::new();
+    let from = match base {
+        Some(tx) => {
+            state.extend(read_checkpoint(log, tx)?.into_iter().map(|r| (r.id, r)));
+            tx
+        }
+        None => {
+            if let Some(e) = entries.iter().find(|e| e.tx_id <= target && unjournaled(e)) {
+                return Err(match checkpoints.first() {
+                    Some(oldest) => format!(
+                        "tx {target} predates the oldest checkpoint (tx {oldest}) and tx {} \
+                         was logged without record changes, so it cannot be replayed",
+                        e.tx_id
+                    ),
+                    None => format!(
+                        "no checkpoint covers tx {target} and tx {} was logged without \
+                         record changes, so it cannot be replayed",
+                        e.tx_id
+                    ),
+                });
+            }
+            0
+        }
+    };
+    for entry in entries
+        .into_iter()
+        .filter(|e| e.tx_id > from && e.tx_id <= target)
+    {
+        for change in entry.changes {
+            match change {
+                RecordChange::Upsert { record } => {
+                    state.insert(record.id, *record);
+                }
+                RecordChange::Delete { id } => {
+                    state.shift_remove(&id);
+                }
+                RecordChange::Clear => state.clear(),
+            }
+        }
+    }
+    Ok(state.into_values().collect())
+}
+
+/// A throwaway in-memory store holding the state as of `as_of`, for read-only
+/// time-travel queries. Nothing it does is logged, and the records are loaded
+/// as they were, without going through the add path or its metrics.
+pub fn store_as_of(log: &TxLog, as_of: AsOf) -> Result<MemoryStore<InMemoryBackend>, String> {
+    Ok(MemoryStore::from_records(state_as_of(log, as_of)?))
+}
+
+/// Outcome of `restore`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RestoreReport {
+    /// tx the store was rewound to.
+    pub target_tx: u64,
+    /// `Restore` tx logged for the rewind itself.
+    pub restore_tx: u64,
+    pub records_before: usize,
+    pub records_after: usize,
+}
+
+/// Rewind `store` to its state as of `as_of` and log a `Restore` tx carrying
+/// the full restored record set, so later replays pass through the rewind.
+pub fn restore<B: MemoryBackend>(
+    store: &mut MemoryStore<B>,
+    log: &TxLog,
+    as_of: AsOf,
+) -> Result<RestoreReport, String> {
+    let target_tx = resolve_tx(log, as_of)?;
+    let records = state_as_of(log, AsOf::Tx(target_tx))?;
+    let records_before =
+        store.all().len() + store.cold_tier().map(|c| c.len()).unwrap_or(0);
+    let records_after = records.len();
+
+    store.enable_change_journal();
+    store.replace_all(records).map_err(|e| e.to_string())?;
+    // The journal now starts with Clear, so anything pending before it is moot.
+    let changes = store.take_changes();
+    let restore_tx = log.append_with_changes(TxKind::Restore, vec![], "system", changes);
+    Ok(RestoreReport {
+        target_tx,
+        restore_tx,
+        records_before,
+        records_after,
+    })
+}
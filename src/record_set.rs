@@ -0,0 +1,269 @@
+//! RecordSet — persistent, id-keyed record storage behind `MemoryStore`.
+//!
+//! Chain-of-thought: `MemoryStore` used to hold a `Vec<MemoryRecord>` with
+//! actor/action/target indices of Vec positions. Every delete shifted the
+//! positions, so each one rebuilt all three indices, and every published
+//! snapshot (`ConcurrentMemoryStore`) deep-copied the whole Vec. Here records
+//! sit behind `Arc`s in an RRB vector (`imbl::Vector`) in insertion order,
+//! each tagged with a sequence number that never changes, and the indices map
+//! a key to the `(seq, id)` pairs of its records. Cloning the set is O(1) and
+//! shares structure with the original; a write afterwards copies O(log n)
+//! nodes plus the record it touches. Ids are unique: pushing a record whose
+//! id is already present replaces it in place.
+
+use crate::memory_record::MemoryRecord;
+use std::sync::Arc;
+use uuid::Uuid;
+
+type Postings = imbl::OrdSet<(u64, Uuid)>;
+type Index = imbl::HashMap<String, Postings>;
+
+#[derive(Clone)]
+struct Slot {
+    seq: u64,
+    record: Arc<MemoryRecord>,
+}
+
+/// Insertion-ordered records with actor/action/target indices keyed by
+/// record id. See the module docs for the cost model.
+#[derive(Clone, Default)]
+pub struct RecordSet {
+    slots: imbl::Vector<Slot>,
+    seq_of: imbl::HashMap<Uuid, u64>,
+    next_seq: u64,
+    by_actor: Index,
+    by_action: Index,
+    by_target: Index,
+}
+
+impl RecordSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.slots.iter())
+    }
+
+    /// The record at insertion position `i`.
+    pub fn get(&self, i: usize) -> Option<&MemoryRecord> {
+        self.slots.get(i).map(|s| &*s.record)
+    }
+
+    pub fn first(&self) -> Option<&MemoryRecord> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&MemoryRecord> {
+        self.slots.last().map(|s| &*s.record)
+    }
+
+    pub fn to_vec(&self) -> Vec<MemoryRecord> {
+        self.iter().cloned().collect()
+    }
+
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.seq_of.contains_key(&id)
+    }
+
+    pub fn by_id(&self, id: Uuid) -> Option<&MemoryRecord> {
+        self.position(id).and_then(|i| self.get(i))
+    }
+
+    pub fn by_actor(&self, actor: &str) -> Vec<&MemoryRecord> {
+        self.lookup(&self.by_actor, actor)
+    }
+
+    pub fn by_action(&self, action: &str) -> Vec<&MemoryRecord> {
+        self.lookup(&self.by_action, action)
+    }
+
+    pub fn by_target(&self, target: &str) -> Vec<&MemoryRecord> {
+        self.lookup(&self.by_target, target)
+    }
+
+    /// Append `record`, or replace the record with the same id where it stands.
+    pub fn push(&mut self, record: MemoryRecord) {
+        if let Some(i) = self.position(record.id) {
+            self.modify_at(i, |r| *r = record);
+            return;
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.index(seq, &record);
+        self.seq_of.insert(record.id, seq);
+        self.slots.push_back(Slot {
+            seq,
+            record: Arc::new(record),
+        });
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> Option<MemoryRecord> {
+        let i = self.position(id)?;
+        let slot = self.slots.remove(i);
+        self.seq_of.remove(&id);
+        self.unindex(slot.seq, &slot.record);
+        Some(Arc::unwrap_or_clone(slot.record))
+    }
+
+    /// Drop every record `keep` rejects; returns their ids in insertion order.
+    pub fn retain(&mut self, mut keep: impl FnMut(&MemoryRecord) -> bool) -> Vec<Uuid> {
+        let gone: Vec<Uuid> = self.iter().filter(|r| !keep(r)).map(|r| r.id).collect();
+        for id in &gone {
+            self.remove(*id);
+        }
+        gone
+    }
+
+    /// Apply `f` to the record with `id`, reindexing it if its actor, action
+    /// or target changed. Returns the updated record.
+    pub fn update(&mut self, id: Uuid, f: impl FnOnce(&mut MemoryRecord)) -> Option<&MemoryRecord> {
+        let i = self.position(id)?;
+        self.modify_at(i, f);
+        self.get(i)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self {
+            next_seq: self.next_seq,
+            ..Self::default()
+        };
+    }
+
+    fn position(&self, id: Uuid) -> Option<usize> {
+        let seq = *self.seq_of.get(&id)?;
+        self.slots.binary_search_by_key(&seq, |s| s.seq).ok()
+    }
+
+    fn modify_at(&mut self, i: usize, f: impl FnOnce(&mut MemoryRecord)) {
+        let Some(slot) = self.slots.get_mut(i) else {
+            return;
+        };
+        let seq = slot.seq;
+        let before = Arc::clone(&slot.record);
+        f(Arc::make_mut(&mut slot.record));
+        let after = Arc::clone(&slot.record);
+        if before.id != after.id {
+            self.seq_of.remove(&before.id);
+            self.seq_of.insert(after.id, seq);
+        }
+        if before.actor != after.actor
+            || before.action != after.action
+            || before.target != after.target
+            || before.id != after.id
+        {
+            self.unindex(seq, &before);
+            self.index(seq, &after);
+        }
+    }
+
+    fn lookup(&self, index: &Index, key: &str) -> Vec<&MemoryRecord> {
+        let Some(postings) = index.get(key) else {
+            return Vec::new();
+        };
+        postings
+            .iter()
+            .filter_map(|(seq, _)| {
+                let i = self.slots.binary_search_by_key(seq, |s| s.seq).ok()?;
+                self.get(i)
+            })
+            .collect()
+    }
+
+    fn index(&mut self, seq: u64, r: &MemoryRecord) {
+        let key = (seq, r.id);
+        self.by_actor
+            .entry(r.actor.clone())
+            .or_default()
+            .insert(key);
+        self.by_action
+            .entry(r.action.clone())
+            .or_default()
+            .insert(key);
+        self.by_target
+            .entry(r.target.clone())
+            .or_default()
+            .insert(key);
+    }
+
+    fn unindex(&mut self, seq: u64, r: &MemoryRecord) {
+        let key = (seq, r.id);
+        for (index, field) in [
+            (&mut self.by_actor, &r.actor),
+            (&mut self.by_action, &r.action),
+            (&mut self.by_target, &r.target),
+        ] {
+            if let Some(postings) = index.get_mut(field) {
+                postings.remove(&key);
+                if postings.is_empty() {
+                    index.remove(field);
+                }
+            }
+        }
+    }
+}
+
+impl FromIterator<MemoryRecord> for RecordSet {
+    fn from_iter<I: IntoIterator<Item = MemoryRecord>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for record in iter {
+            set.push(record);
+        }
+        set
+    }
+}
+
+impl std::ops::Index<usize> for RecordSet {
+    type Output = MemoryRecord;
+
+    fn index(&self, i: usize) -> &MemoryRecord {
+        &self.slots[i].record
+    }
+}
+
+impl<'a> IntoIterator for &'a RecordSet {
+    type Item = &'a MemoryRecord;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for RecordSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Records of a `RecordSet` in insertion order.
+#[derive(Clone)]
+pub struct Iter<'a>(imbl::vector::Iter<'a, Slot, imbl::shared_ptr::DefaultSharedPtr>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a MemoryRecord;
+
+    fn next(&mut self) -> Option<&'a MemoryRecord> {
+        self.0.next().map(|s| &*s.record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|s| &*s.record)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
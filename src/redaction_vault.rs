@@ -0,0 +1,226 @@
+//! Redaction vault — reversible tokenization of detected PII spans.
+//!
+//! Chain-of-thought: blocking every record that mentions an email address
+//! loses the rest of the record, while storing it leaks the address into
+//! every snapshot, export and index. Redaction swaps each detected span for a
+//! token like `[[EMAIL:kfcbnhodpaejmlgi]]` and keeps the original, AES-GCM
+//! encrypted, in the vault. Tokens are an HMAC-SHA256 of (kind, value) under
+//! a subkey derived from the vault key, so the same value always maps to the
+//! same token: exact-match lookups and dedup keep working on redacted records,
+//! and nobody without the key can confirm a guessed value. The token alphabet is `a`–`p` (one letter per
+//! hex nibble) so tokens never trip the digit-based PII patterns themselves.
+//! Vault entries are appended to a JSONL file next to the store when one is
+//! configured; `rehydrate` turns tokens back into the original text.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::Aes256Gcm;
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use rand::RngCore;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::safety_classifier::DetectedSpan;
+
+lazy_static::lazy_static! {
+    static ref TOKEN_RE: Regex = Regex::new(r"\[\[([A-Z_]+):([a-p]{16})\]\]").unwrap();
+}
+
+/// One encrypted original, as stored in the vault file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultEntry {
+    token: String,
+    kind: String,
+    /// base64 nonce (12 bytes).
+    nonce: String,
+    /// base64 AES-256-GCM ciphertext of the original value.
+    data: String,
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub struct RedactionVault {
+    /// Token MAC key, derived from the vault key so the AES key is never
+    /// used for anything but encryption.
+    token_key: [u8; 32],
+    cipher: Aes256Gcm,
+    entries: Mutex<HashMap<String, VaultEntry>>,
+    path: Option<PathBuf>,
+}
+
+impl RedactionVault {
+    /// Vault that lives only as long as the process.
+    pub fn new_in_memory(key: [u8; 32]) -> Self {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(&key).expect("HMAC takes any key length");
+        mac.update(b"hipcortex-redaction-token");
+        Self {
+            token_key: mac.finalize().into_bytes().into(),
+            cipher: Aes256Gcm::new(&key.into()),
+            entries: Mutex::new(HashMap::new()),
+            path: None,
+        }
+    }
+
+    /// Open (or create) the vault file at `path`. Entries that do not decrypt
+    /// with `key` are an error: the file belongs to a different key.
+    pub fn open<P: AsRef<Path>>(path: P, key: [u8; 32]) -> Result<Self> {
+        let mut vault = Self::new_in_memory(key);
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading vault {}", path.display()))?;
+            let mut entries = HashMap::new();
+            for (idx, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry: VaultEntry = serde_json::from_str(line)
+                    .with_context(|| format!("vault {} line {}", path.display(), idx + 1))?;
+                entries.insert(entry.token.clone(), entry);
+            }
+            if let Some(entry) = entries.values().next() {
+                vault
+                    .decrypt(entry)
+                    .context("vault was written with a different key")?;
+            }
+            vault.entries = Mutex::new(entries);
+        }
+        vault.path = Some(path);
+        Ok(vault)
+    }
+
+    /// Parse a 32-byte key from 64 hex characters.
+    pub fn key_from_hex(hex_key: &str) -> Result<[u8; 32]> {
+        let bytes = hex::decode(hex_key.trim()).context("vault key is not hex")?;
+        bytes
+            .try_into()
+            .map_err(|_| anyhow!("vault key must be 32 bytes (64 hex characters)"))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Deterministic token for `value`; the original is stored on first use.
+    pub fn tokenize(&self, kind: &str, value: &str) -> Result<String> {
+        let kind: String = kind
+            .to_ascii_uppercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphabetic() { c } else { '_' })
+            .collect();
+        let mut mac =
+            <HmacSha256 as Mac>::new_from_slice(&self.token_key).expect("HMAC takes any key length");
+        mac.update(kind.as_bytes());
+        mac.update(&[0u8]);
+        mac.update(value.as_bytes());
+        let id: String = mac.finalize().into_bytes()[..8]
+            .iter()
+            .flat_map(|b| [b >> 4, b & 0x0f])
+            .map(|n| (b'a' + n) as char)
+            .collect();
+        let token = format!("[[{kind}:{id}]]");
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.contains_key(&token) {
+            return Ok(token);
+        }
+        let mut nonce = [0u8; 12];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let data = self
+            .cipher
+            .encrypt(aes_gcm::Nonce::from_slice(&nonce), value.as_bytes())
+            .map_err(|e| anyhow!("vault encryption failed: {e}"))?;
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let entry = VaultEntry {
+            token: token.clone(),
+            kind,
+            nonce: b64.encode(nonce),
+            data: b64.encode(data),
+        };
+        if let Some(path) = &self.path {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("opening vault {}", path.display()))?;
+            writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        }
+        entries.insert(token.clone(), entry);
+        Ok(token)
+    }
+
+    /// Original value behind `token`, if this vault issued it.
+    pub fn reveal(&self, token: &str) -> Result<Option<String>> {
+        let entry = self.entries.lock().unwrap().get(token).cloned();
+        entry.map(|e| self.decrypt(&e)).transpose()
+    }
+
+    /// Replace `spans` (byte ranges into `text`) with tokens. Overlapping
+    /// spans collapse into the earliest, longest one. Returns the new text
+    /// and how many spans were replaced.
+    pub fn redact(&self, text: &str, spans: &[DetectedSpan]) -> Result<(String, usize)> {
+        let mut spans: Vec<&DetectedSpan> = spans
+            .iter()
+            .filter(|s| s.start < s.end && s.end <= text.len())
+            .filter(|s| text.is_char_boundary(s.start) && text.is_char_boundary(s.end))
+            .collect();
+        spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        let mut replaced = 0;
+        for span in spans {
+            if span.start < cursor {
+                continue;
+            }
+            out.push_str(&text[cursor..span.start]);
+            out.push_str(&self.tokenize(&span.kind, &text[span.start..span.end])?);
+            cursor = span.end;
+            replaced += 1;
+        }
+        out.push_str(&text[cursor..]);
+        Ok((out, replaced))
+    }
+
+    /// Put the originals back in place of every token this vault knows.
+    /// Unknown tokens are left as they are.
+    pub fn rehydrate(&self, text: &str) -> Result<String> {
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for m in TOKEN_RE.find_iter(text) {
+            if let Some(original) = self.reveal(m.as_str())? {
+                out.push_str(&text[cursor..m.start()]);
+                out.push_str(&original);
+                cursor = m.end();
+            }
+        }
+        out.push_str(&text[cursor..]);
+        Ok(out)
+    }
+
+    fn decrypt(&self, entry: &VaultEntry) -> Result<String> {
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let nonce = b64.decode(&entry.nonce)?;
+        if nonce.len() != 12 {
+            return Err(anyhow!("vault entry {} has a bad nonce", entry.token));
+        }
+        let plain = self
+            .cipher
+            .decrypt(
+                aes_gcm::Nonce::from_slice(&nonce),
+                b64.decode(&entry.data)?.as_ref(),
+            )
+            .map_err(|_| anyhow!("cannot decrypt vault entry {}", entry.token))?;
+        Ok(String::from_utf8(plain)?)
+    }
+}
@@ -1,12 +1,17 @@
 // Chain-of-Thought: classify content into safety categories using regex + keyword
 // patterns. Produces a risk score and recommended action (Allow/Flag/Block/Quarantine).
 // Designed to replace the string-match stub in SafetyGuardrail with semantic checks.
+// The regex set is one `SafetyClassifierBackend`; an ONNX model or a local LLM
+// can be plugged in behind the same trait. Redactable matches (PII, secrets,
+// MRNs) are also reported as byte spans so the guardrail can tokenize them.
 
 use regex::Regex;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
+
+use crate::llm_clients::LLMClient;
 
 /// What kind of unsafe content was detected.
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ContentCategory {
     Safe,
     PII,
@@ -23,6 +28,18 @@ pub enum Action {
     Flag,
     Block,
     Quarantine,
+    /// Detected spans were replaced with vault tokens before storing.
+    Redact,
+}
+
+/// A match that can be cut out of the text, as byte offsets.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DetectedSpan {
+    pub category: ContentCategory,
+    /// Upper-case label used in the redaction token (e.g. `EMAIL`).
+    pub kind: String,
+    pub start: usize,
+    pub end: usize,
 }
 
 /// Result of a content safety classification.
@@ -32,14 +49,17 @@ pub struct ClassificationResult {
     pub risk_score: f64,
     pub patterns_matched: Vec<String>,
     pub recommended_action: Action,
+    #[serde(default)]
+    pub spans: Vec<DetectedSpan>,
 }
 
 /// Thread-safe compiled pattern sets (compiled once at first use).
+/// Labelled patterns double as redaction kinds.
 struct Patterns {
-    pii: Vec<Regex>,
-    phi: Vec<Regex>,
+    pii: Vec<(&'static str, Regex)>,
+    phi: Vec<(&'static str, Regex)>,
     injection: Vec<Regex>,
-    sensitive: Vec<Regex>,
+    sensitive: Vec<(&'static str, Regex)>,
     unsafe_content: Vec<Regex>,
 }
 
@@ -48,23 +68,23 @@ impl Patterns {
         Self {
             pii: vec![
                 // Email addresses
-                Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}").unwrap(),
+                ("EMAIL", Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}").unwrap()),
                 // US phone numbers
-                Regex::new(r"\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}").unwrap(),
+                ("PHONE", Regex::new(r"\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}").unwrap()),
                 // SSN
-                Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap(),
+                ("SSN", Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap()),
                 // Credit card numbers (simplified)
-                Regex::new(r"\b\d{4}[- ]?\d{4}[- ]?\d{4}[- ]?\d{4}\b").unwrap(),
+                ("CARD", Regex::new(r"\b\d{4}[- ]?\d{4}[- ]?\d{4}[- ]?\d{4}\b").unwrap()),
                 // Common credit card prefixes
-                Regex::new(r"\b(?:4[0-9]{12}(?:[0-9]{3})?|5[1-5][0-9]{14}|3[47][0-9]{13}|6(?:011|5[0-9]{2})[0-9]{12})\b").unwrap(),
+                ("CARD", Regex::new(r"\b(?:4[0-9]{12}(?:[0-9]{3})?|5[1-5][0-9]{14}|3[47][0-9]{13}|6(?:011|5[0-9]{2})[0-9]{12})\b").unwrap()),
             ],
             phi: vec![
                 // Medical record numbers
-                Regex::new(r"(?i)\bMRN[#:\s]*\d+\b").unwrap(),
+                ("MRN", Regex::new(r"(?i)\bMRN[#:\s]*\d+\b").unwrap()),
                 // ICD codes
-                Regex::new(r"\b[A-Z]\d{2}(?:\.\d{1,3})?\b").unwrap(),
+                ("ICD", Regex::new(r"\b[A-Z]\d{2}(?:\.\d{1,3})?\b").unwrap()),
                 // Common PHI indicators
-                Regex::new(r"(?i)\b(?:diagnosis|prognosis|prescri(?:bed|ption)|dosage|patient\s+id|health\s+record)\b").unwrap(),
+                ("PHI_TERM", Regex::new(r"(?i)\b(?:diagnosis|prognosis|prescri(?:bed|ption)|dosage|patient\s+id|health\s+record)\b").unwrap()),
             ],
             injection: vec![
                 // Direct override commands
@@ -73,14 +93,17 @@ impl Patterns {
                 Regex::new(r"(?i)\b(?:new\s+system\s+(?:prompt|message|instruction)|reset\s+(?:your|the)\s+(?:instructions?|prompt)|forget\s+(?:everything|all)\s+(?:above|before))\b").unwrap(),
                 // Role subversion
                 Regex::new(r"(?i)\b(?:pretend\s+(?:you\s+are|to\s+be)|act\s+as\s+if\s+you|you\s+must\s+(?:not\s+)?refuse)\b").unwrap(),
+                // Indirect injection in retrieved text: disregard phrasing and
+                // chat-template control markers
+                Regex::new(r"(?i)\bdisregard\s+(?:all\s+|any\s+)?(?:previous|prior|above|earlier)\s+(?:instructions?|prompts?|rules)\b|<\|im_start\|>|<\|system\|>|\[/?INST\]|<</?SYS>>").unwrap(),
             ],
             sensitive: vec![
                 // API keys
-                Regex::new(r#"(?i)\b(?:sk-[a-zA-Z0-9]{20,}|api[_-]?key[=:]\s*['"]?[a-zA-Z0-9_-]{20,}['"]?|token[=:]\s*['"]?[a-zA-Z0-9_-]{20,}['"]?)\b"#).unwrap(),
+                ("API_KEY", Regex::new(r#"(?i)\b(?:sk-[a-zA-Z0-9]{20,}|api[_-]?key[=:]\s*['"]?[a-zA-Z0-9_-]{20,}['"]?|token[=:]\s*['"]?[a-zA-Z0-9_-]{20,}['"]?)\b"#).unwrap()),
                 // AWS keys
-                Regex::new(r"\bAKIA[0-9A-Z]{16}\b").unwrap(),
+                ("AWS_KEY", Regex::new(r"\bAKIA[0-9A-Z]{16}\b").unwrap()),
                 // Generic secrets
-                Regex::new(r#"(?i)\b(?:secret[=:]\s*['"]?[a-zA-Z0-9_-]{8,}['"]?|password[=:]\s*['"]?[^\s'"]+['"]?|private[_-]?key)\b"#).unwrap(),
+                ("SECRET", Regex::new(r#"(?i)\b(?:secret[=:]\s*['"]?[a-zA-Z0-9_-]{8,}['"]?|password[=:]\s*['"]?[^\s'"]+['"]?|private[_-]?key)\b"#).unwrap()),
             ],
             unsafe_content: vec![
                 Regex::new(r"(?i)\b(?:self[- ]?harm|suicide|kill\s+(?:yourself|myself))\b").unwrap(),
@@ -131,7 +154,7 @@ impl SafetyClassifier {
         let mut worst_category = ContentCategory::Safe;
 
         // Check each pattern category
-        for re in &PATTERNS.pii {
+        for (_, re) in &PATTERNS.pii {
             if let Some(m) = re.find(content) {
                 patterns_matched.push(format!("PII:{}", m.as_str()));
                 max_risk = max_risk.max(0.90);
@@ -139,7 +162,7 @@ impl SafetyClassifier {
             }
         }
 
-        for re in &PATTERNS.phi {
+        for (_, re) in &PATTERNS.phi {
             if let Some(m) = re.find(content) {
                 patterns_matched.push(format!("PHI:{}", m.as_str()));
                 max_risk = max_risk.max(0.85);
@@ -155,7 +178,7 @@ impl SafetyClassifier {
             }
         }
 
-        for re in &PATTERNS.sensitive {
+        for (_, re) in &PATTERNS.sensitive {
             if let Some(m) = re.find(content) {
                 patterns_matched.push(format!("Sensitive:{}", m.as_str()));
                 // Only flag as Sensitive if not already flagged as higher risk.
@@ -196,6 +219,7 @@ impl SafetyClassifier {
             risk_score: risk,
             patterns_matched,
             recommended_action: action,
+            spans: detect_spans(content),
         }
     }
 
@@ -219,6 +243,173 @@ impl SafetyClassifier {
     }
 }
 
+/// Byte spans of every redactable match: all PII and secrets, plus medical
+/// record numbers (ICD codes and PHI keywords are signals, not identifiers).
+pub fn detect_spans(content: &str) -> Vec<DetectedSpan> {
+    let labelled = PATTERNS
+        .pii
+        .iter()
+        .map(|p| (ContentCategory::PII, p))
+        .chain(PATTERNS.phi.iter().take(1).map(|p| (ContentCategory::PHI, p)))
+        .chain(PATTERNS.sensitive.iter().map(|p| (ContentCategory::Sensitive, p)));
+    let mut spans = Vec::new();
+    for (category, (kind, re)) in labelled {
+        for m in re.find_iter(content) {
+            spans.push(DetectedSpan {
+                category: category.clone(),
+                kind: kind.to_string(),
+                start: m.start(),
+                end: m.end(),
+            });
+        }
+    }
+    spans.sort_by_key(|s| (s.start, std::cmp::Reverse(s.end)));
+    spans
+}
+
+// ─── Pluggable backends ──────────────────────────────────────────────────────
+
+/// Anything that can score content for the guardrail. Backends report risk,
+/// category and redactable spans; the guardrail's per-namespace policy turns
+/// that into an action, so `recommended_action` is only advisory here.
+pub trait SafetyClassifierBackend: Send + Sync {
+    fn name(&self) -> &str;
+    fn classify(&self, content: &str) -> ClassificationResult;
+
+    /// Classify several texts at once, in order. Backends with per-call
+    /// overhead can override this; the default classifies one by one.
+    fn classify_batch(&self, contents: &[&str]) -> Vec<ClassificationResult> {
+        contents.iter().map(|c| self.classify(c)).collect()
+    }
+}
+
+impl SafetyClassifierBackend for SafetyClassifier {
+    fn name(&self) -> &str {
+        "regex"
+    }
+
+    fn classify(&self, content: &str) -> ClassificationResult {
+        SafetyClassifier::classify(self, content)
+    }
+}
+
+/// Asks an LLM for a JSON verdict:
+/// `{"category": "PII", "risk": 0.9, "spans": [{"text": "...", "kind": "EMAIL"}]}`.
+/// Span texts are located in the content; unparseable replies fall back to
+/// the regex classifier so an unreachable model never disables screening.
+pub struct LlmClassifierBackend {
+    client: Arc<dyn LLMClient>,
+    fallback: SafetyClassifier,
+}
+
+impl LlmClassifierBackend {
+    pub fn new(client: Arc<dyn LLMClient>) -> Self {
+        Self {
+            client,
+            fallback: SafetyClassifier::new(),
+        }
+    }
+
+    fn prompt(content: &str) -> String {
+        format!(
+            "Classify the text between <text> tags for safety. Reply with JSON only: \
+             {{\"category\": one of Safe|PII|PHI|PromptInjection|UnsafeContent|Sensitive, \
+             \"risk\": 0.0-1.0, \"spans\": [{{\"text\": exact personal data or secret, \
+             \"kind\": EMAIL|PHONE|NAME|ADDRESS|SSN|CARD|MRN|SECRET}}]}}\n<text>{content}</text>"
+        )
+    }
+
+    fn parse(&self, content: &str, reply: &str) -> Option<ClassificationResult> {
+        let json = &reply[reply.find('{')?..=reply.rfind('}')?];
+        let v: serde_json::Value = serde_json::from_str(json).ok()?;
+        let category: ContentCategory =
+            serde_json::from_value(v.get("category")?.clone()).ok()?;
+        let risk = v.get("risk")?.as_f64()?.clamp(0.0, 1.0);
+        let mut spans = Vec::new();
+        let mut patterns_matched = Vec::new();
+        for span in v.get("spans").and_then(|s| s.as_array()).into_iter().flatten() {
+            let Some(text) = span.get("text").and_then(|t| t.as_str()) else {
+                continue;
+            };
+            let kind = span.get("kind").and_then(|k| k.as_str()).unwrap_or("PII");
+            for (start, m) in content.match_indices(text).filter(|_| !text.is_empty()) {
+                spans.push(DetectedSpan {
+                    category: ContentCategory::PII,
+                    kind: kind.to_ascii_uppercase(),
+                    start,
+                    end: start + m.len(),
+                });
+            }
+            patterns_matched.push(format!("llm:{kind}"));
+        }
+        let recommended_action = if category == ContentCategory::Safe {
+            Action::Allow
+        } else if risk >= DEFAULT_BLOCK_THRESHOLD {
+            Action::Block
+        } else if risk >= DEFAULT_FLAG_THRESHOLD {
+            Action::Flag
+        } else {
+            Action::Allow
+        };
+        Some(ClassificationResult {
+            risk_score: if category == ContentCategory::Safe { 0.0 } else { risk },
+            category,
+            patterns_matched,
+            recommended_action,
+            spans,
+        })
+    }
+}
+
+impl SafetyClassifierBackend for LlmClassifierBackend {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    fn classify(&self, content: &str) -> ClassificationResult {
+        let reply = self.client.generate_response(&Self::prompt(content));
+        self.parse(content, &reply)
+            .unwrap_or_else(|| self.fallback.classify(content))
+    }
+}
+
+/// Runs several backends and keeps the riskiest verdict, with the union of
+/// their spans — e.g. regex for exact identifiers plus a model for names.
+pub struct EnsembleClassifier {
+    backends: Vec<Box<dyn SafetyClassifierBackend>>,
+}
+
+impl EnsembleClassifier {
+    pub fn new(backends: Vec<Box<dyn SafetyClassifierBackend>>) -> Self {
+        Self { backends }
+    }
+}
+
+impl SafetyClassifierBackend for EnsembleClassifier {
+    fn name(&self) -> &str {
+        "ensemble"
+    }
+
+    fn classify(&self, content: &str) -> ClassificationResult {
+        let mut results = self.backends.iter().map(|b| b.classify(content));
+        let Some(mut best) = results.next() else {
+            return SafetyClassifier::new().classify(content);
+        };
+        for result in results {
+            best.patterns_matched.extend(result.patterns_matched);
+            best.spans.extend(result.spans);
+            if result.risk_score > best.risk_score {
+                best.category = result.category;
+                best.risk_score = result.risk_score;
+                best.recommended_action = result.recommended_action;
+            }
+        }
+        best.spans.sort_by_key(|s| (s.start, std::cmp::Reverse(s.end)));
+        best.spans.dedup_by(|a, b| a.start == b.start && a.end == b.end);
+        best
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,14 +1,147 @@
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-use crate::safety_classifier::{ClassificationResult, SafetyClassifier};
+use crate::memory_record::{MemoryRecord, RecordStatus};
+use crate::redaction_vault::RedactionVault;
+use crate::safety_classifier::{
+    Action, ClassificationResult, ContentCategory, SafetyClassifier, SafetyClassifierBackend,
+    DEFAULT_BLOCK_THRESHOLD, DEFAULT_FLAG_THRESHOLD,
+};
+
+/// How classified content is handled in one namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetyPolicy {
+    pub block_threshold: f64,
+    pub flag_threshold: f64,
+    /// Categories whose spans are tokenized into the vault instead of
+    /// blocking the whole record.
+    pub redact: Vec<ContentCategory>,
+    /// Categories that are stored with status `quarantine` instead of blocked.
+    pub quarantine: Vec<ContentCategory>,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            block_threshold: DEFAULT_BLOCK_THRESHOLD,
+            flag_threshold: DEFAULT_FLAG_THRESHOLD,
+            redact: Vec::new(),
+            quarantine: Vec::new(),
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// `Redact` when the result has spans in a redacted category, otherwise
+    /// the threshold decision.
+    pub fn decide(&self, result: &ClassificationResult) -> Action {
+        if result
+            .spans
+            .iter()
+            .any(|s| self.redact.contains(&s.category))
+        {
+            return Action::Redact;
+        }
+        self.threshold_action(result)
+    }
+
+    /// Allow / Flag / Block, with Block softened to Quarantine for the
+    /// policy's quarantine categories.
+    pub fn threshold_action(&self, result: &ClassificationResult) -> Action {
+        if result.category == ContentCategory::Safe || result.risk_score < self.flag_threshold {
+            Action::Allow
+        } else if result.risk_score < self.block_threshold {
+            Action::Flag
+        } else if self.quarantine.contains(&result.category) {
+            Action::Quarantine
+        } else {
+            Action::Block
+        }
+    }
+}
+
+/// The default policy plus per-namespace overrides (namespaces are the
+/// `ns:<name>` record tags).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetyPolicies {
+    pub default: SafetyPolicy,
+    pub namespaces: HashMap<String, SafetyPolicy>,
+}
+
+impl SafetyPolicies {
+    pub fn for_namespace(&self, namespace: Option<&str>) -> &SafetyPolicy {
+        namespace
+            .and_then(|ns| self.namespaces.get(ns))
+            .unwrap_or(&self.default)
+    }
+
+    /// Parse inline JSON, or read it from the file `spec` names.
+    pub fn from_spec(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let json = if spec.starts_with('{') {
+            spec.to_string()
+        } else {
+            std::fs::read_to_string(spec).map_err(|e| format!("reading {spec}: {e}"))?
+        };
+        serde_json::from_str(&json).map_err(|e| format!("invalid safety policy: {e}"))
+    }
+}
+
+/// A write refused by the safety policy; `MemoryStore::add` returns it inside
+/// `anyhow::Error` so callers can tell a refusal from a storage failure.
+#[derive(Debug, Clone)]
+pub struct WriteRefused(pub String);
+
+impl std::fmt::Display for WriteRefused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for WriteRefused {}
+
+/// The text a record is judged on: actor, action, target and every string in
+/// its metadata.
+fn screen_context(rec: &MemoryRecord) -> String {
+    fn strings<'a>(value: &'a serde_json::Value, out: &mut Vec<&'a str>) {
+        match value {
+            serde_json::Value::String(s) => out.push(s),
+            serde_json::Value::Array(items) => items.iter().for_each(|v| strings(v, out)),
+            serde_json::Value::Object(map) => map.values().for_each(|v| strings(v, out)),
+            _ => {}
+        }
+    }
+    let mut parts = vec![rec.actor.as_str(), rec.action.as_str(), rec.target.as_str()];
+    strings(&rec.metadata, &mut parts);
+    parts.join(" ")
+}
+
+fn record_namespace(rec: &MemoryRecord) -> Option<&str> {
+    rec.tags.iter().find_map(|t| t.strip_prefix("ns:"))
+}
+
+/// What `screen_record` did to a record that was let through.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenOutcome {
+    pub action: Action,
+    pub category: ContentCategory,
+    pub risk_score: f64,
+    /// Spans replaced with vault tokens across all fields.
+    pub redacted: usize,
+}
 
 pub struct SafetyGuardrail {
     violation_log: HashMap<String, Vec<String>>, // op -> reasons
     snapshots: Vec<serde_json::Value>,
-    classifier: SafetyClassifier,
+    backend: Arc<dyn SafetyClassifierBackend>,
+    policies: SafetyPolicies,
+    vault: Option<Arc<RedactionVault>>,
 }
 
 impl SafetyGuardrail {
@@ -16,16 +149,105 @@ impl SafetyGuardrail {
         Self {
             violation_log: HashMap::new(),
             snapshots: Vec::new(),
-            classifier: SafetyClassifier::new(),
+            backend: Arc::new(SafetyClassifier::new()),
+            policies: SafetyPolicies::default(),
+            vault: None,
+        }
+    }
+
+    /// Swap the classifier (regex by default) for another backend.
+    pub fn set_backend(&mut self, backend: Box<dyn SafetyClassifierBackend>) {
+        self.backend = Arc::from(backend);
+    }
+
+    /// Handle on the classifier backend, so slow classification (e.g. an
+    /// LLM backend) can run after the guardrail lock is released.
+    pub fn classifier(&self) -> Arc<dyn SafetyClassifierBackend> {
+        Arc::clone(&self.backend)
+    }
+
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    pub fn policies(&self) -> &SafetyPolicies {
+        &self.policies
+    }
+
+    pub fn set_policies(&mut self, policies: SafetyPolicies) {
+        self.policies = policies;
+    }
+
+    /// Set the policy for one namespace, or the default for `None`.
+    pub fn set_policy(&mut self, namespace: Option<&str>, policy: SafetyPolicy) {
+        match namespace {
+            Some(ns) => {
+                self.policies.namespaces.insert(ns.to_string(), policy);
+            }
+            None => self.policies.default = policy,
+        }
+    }
+
+    pub fn vault(&self) -> Option<Arc<RedactionVault>> {
+        self.vault.clone()
+    }
+
+    pub fn set_vault(&mut self, vault: Option<Arc<RedactionVault>>) {
+        self.vault = vault;
+    }
+
+    /// Apply `HIPCORTEX_SAFETY_POLICY` (inline JSON or a file path) and, when
+    /// `HIPCORTEX_VAULT_KEY` (64 hex chars) is set, open the redaction vault at
+    /// `HIPCORTEX_VAULT_PATH`, else `default_vault_path`, else in memory.
+    /// Unset variables leave the current configuration alone.
+    pub fn configure_from_env(&mut self, default_vault_path: Option<&Path>) -> Result<(), String> {
+        if let Ok(spec) = std::env::var("HIPCORTEX_SAFETY_POLICY") {
+            self.policies = SafetyPolicies::from_spec(&spec)?;
+        }
+        if let Ok(hex_key) = std::env::var("HIPCORTEX_VAULT_KEY") {
+            let key = RedactionVault::key_from_hex(&hex_key).map_err(|e| e.to_string())?;
+            let path = std::env::var("HIPCORTEX_VAULT_PATH")
+                .ok()
+                .map(std::path::PathBuf::from)
+                .or_else(|| default_vault_path.map(Path::to_path_buf));
+            let vault = match path {
+                Some(path) => RedactionVault::open(path, key).map_err(|e| format!("{e:#}"))?,
+                None => RedactionVault::new_in_memory(key),
+            };
+            self.vault = Some(Arc::new(vault));
         }
+        Ok(())
+    }
+
+    /// What screening `rec` needs: the classifier handle plus its namespace
+    /// policy and the vault. Cheap to take, so callers can drop the guardrail
+    /// lock before the (possibly slow) classification runs.
+    pub fn record_screen(&self, rec: &MemoryRecord) -> RecordScreen {
+        RecordScreen {
+            backend: self.classifier(),
+            policy: self.policies.for_namespace(record_namespace(rec)).clone(),
+            vault: self.vault.clone(),
+        }
+    }
+
+    /// Screen a record before it is persisted; see `RecordScreen::screen`.
+    pub fn screen_record(&mut self, rec: &mut MemoryRecord) -> Result<ScreenOutcome, String> {
+        self.record_screen(rec)
+            .screen(rec, |ctx, reason| self.log_violation(ctx, reason))
+    }
+
+    /// Tokenize `text` as `rec`'s namespace policy would, e.g. the text a
+    /// screened record is embedded from. Same values give the same tokens.
+    pub fn redact_for(&self, rec: &MemoryRecord, text: &mut String) -> Result<usize, String> {
+        self.record_screen(rec).redact(text)
     }
 
     /// Check a precondition using semantic content classification.
     /// Returns Ok(()) if content is safe, Err(reason) if it should be blocked.
     pub fn check_precondition(&mut self, op_context: &str) -> Result<(), String> {
-        let result = self.classifier.classify(op_context);
-        match result.recommended_action {
-            crate::safety_classifier::Action::Block => {
+        let result = self.backend.classify(op_context);
+        match self.policies.default.threshold_action(&result) {
+            Action::Block => {
                 let reason = format!(
                     "precondition blocked: {:?} risk={:.2} patterns={:?}",
                     result.category, result.risk_score, result.patterns_matched
@@ -35,10 +257,7 @@ impl SafetyGuardrail {
             }
             _ => {
                 // Flag but don't block — log for audit
-                if !matches!(
-                    result.category,
-                    crate::safety_classifier::ContentCategory::Safe
-                ) {
+                if result.category != ContentCategory::Safe {
                     self.log_violation(
                         op_context,
                         &format!(
@@ -59,7 +278,7 @@ impl SafetyGuardrail {
         op_context: &str,
         block_threshold: f64,
     ) -> Result<ClassificationResult, String> {
-        let result = self.classifier.classify(op_context);
+        let result = self.backend.classify(op_context);
         if result.risk_score >= block_threshold {
             let reason = format!(
                 "precondition blocked (threshold={}): {:?} risk={:.2}",
@@ -73,9 +292,9 @@ impl SafetyGuardrail {
     }
 
     pub fn check_postcondition(&mut self, op_context: &str) -> Result<(), String> {
-        let result = self.classifier.classify(op_context);
-        match result.recommended_action {
-            crate::safety_classifier::Action::Block => {
+        let result = self.backend.classify(op_context);
+        match self.policies.default.threshold_action(&result) {
+            Action::Block => {
                 let reason = format!(
                     "postcondition blocked: {:?} risk={:.2}",
                     result.category, result.risk_score
@@ -90,7 +309,7 @@ impl SafetyGuardrail {
     /// Classify content without blocking. Returns the classification result for
     /// callers that want to make their own decision.
     pub fn classify(&self, op_context: &str) -> ClassificationResult {
-        self.classifier.classify(op_context)
+        self.backend.classify(op_context)
     }
 
     pub fn log_violation(&mut self, op_context: &str, reason: &str) {
@@ -128,6 +347,164 @@ impl SafetyGuardrail {
     }
 }
 
+/// One record's screening inputs, detached from the guardrail; see
+/// `SafetyGuardrail::record_screen`.
+pub struct RecordScreen {
+    backend: Arc<dyn SafetyClassifierBackend>,
+    policy: SafetyPolicy,
+    vault: Option<Arc<RedactionVault>>,
+}
+
+impl RecordScreen {
+    /// Screen a record under its namespace policy. The decision is made on
+    /// actor, action, target and metadata strings; when the policy redacts,
+    /// spans in all of them are tokenized, the record is resealed and the
+    /// remainder re-checked. Quarantine sets the record's status. Blocked
+    /// records return `Err`, except that a prompt injection the injection
+    /// defense already quarantined stays stored quarantined. Violations are
+    /// reported to `log` as (context, reason).
+    pub fn screen(
+        &self,
+        rec: &mut MemoryRecord,
+        mut log: impl FnMut(&str, &str),
+    ) -> Result<ScreenOutcome, String> {
+        let ctx = screen_context(rec);
+        let mut result = self.backend.classify(&ctx);
+        let mut action = self.policy.decide(&result);
+        let mut redacted = 0;
+
+        if action == Action::Redact {
+            let Some(vault) = &self.vault else {
+                let reason = "record blocked: policy redacts but no redaction vault is configured";
+                log(&ctx, reason);
+                return Err(reason.to_string());
+            };
+            for field in [&mut rec.actor, &mut rec.action, &mut rec.target] {
+                redacted += self.redact_text(field, vault)?;
+            }
+            redacted += self.redact_value(&mut rec.metadata, vault)?;
+            let hash = rec.compute_hash();
+            rec.integrity = Some(hash.clone());
+            rec.content_hash = Some(hash);
+
+            let residual = screen_context(rec);
+            result = self.backend.classify(&residual);
+            action = match self.policy.threshold_action(&result) {
+                Action::Allow | Action::Flag => Action::Redact,
+                other => other,
+            };
+        }
+
+        if action == Action::Block
+            && rec.status == RecordStatus::Quarantine
+            && result.category == ContentCategory::PromptInjection
+        {
+            action = Action::Quarantine;
+        }
+
+        let outcome = ScreenOutcome {
+            action: action.clone(),
+            category: result.category.clone(),
+            risk_score: result.risk_score,
+            redacted,
+        };
+        match action {
+            Action::Block => {
+                let reason = format!(
+                    "record blocked: {:?} risk={:.2} patterns={:?}",
+                    result.category, result.risk_score, result.patterns_matched
+                );
+                log(&ctx, &reason);
+                Err(reason)
+            }
+            Action::Quarantine => {
+                rec.status = RecordStatus::Quarantine;
+                log(
+                    &ctx,
+                    &format!(
+                        "record quarantined: {:?} risk={:.2}",
+                        result.category, result.risk_score
+                    ),
+                );
+                Ok(outcome)
+            }
+            Action::Flag => {
+                log(
+                    &ctx,
+                    &format!(
+                        "record flagged: {:?} risk={:.2}",
+                        result.category, result.risk_score
+                    ),
+                );
+                Ok(outcome)
+            }
+            Action::Allow | Action::Redact => Ok(outcome),
+        }
+    }
+
+    /// Tokenize the spans of `text` the policy redacts; 0 without a vault.
+    pub fn redact(&self, text: &mut String) -> Result<usize, String> {
+        match &self.vault {
+            Some(vault) if !self.policy.redact.is_empty() => self.redact_text(text, vault),
+            _ => Ok(0),
+        }
+    }
+
+    fn redact_text(&self, text: &mut String, vault: &RedactionVault) -> Result<usize, String> {
+        let spans: Vec<_> = self
+            .backend
+            .classify(text)
+            .spans
+            .into_iter()
+            .filter(|s| self.policy.redact.contains(&s.category))
+            .collect();
+        if spans.is_empty() {
+            return Ok(0);
+        }
+        let (out, n) = vault.redact(text, &spans).map_err(|e| e.to_string())?;
+        *text = out;
+        Ok(n)
+    }
+
+    fn redact_value(
+        &self,
+        value: &mut serde_json::Value,
+        vault: &RedactionVault,
+    ) -> Result<usize, String> {
+        match value {
+            serde_json::Value::String(s) => self.redact_text(s, vault),
+            serde_json::Value::Array(items) => {
+                items.iter_mut().map(|v| self.redact_value(v, vault)).sum()
+            }
+            serde_json::Value::Object(map) => {
+                map.values_mut().map(|v| self.redact_value(v, vault)).sum()
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
 lazy_static::lazy_static! {
     pub static ref SAFETY_GUARDRAIL: Mutex<SafetyGuardrail> = Mutex::new(SafetyGuardrail::new());
 }
+
+fn global_guardrail() -> std::sync::MutexGuard<'static, SafetyGuardrail> {
+    SAFETY_GUARDRAIL
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// `SafetyGuardrail::screen_record` against `SAFETY_GUARDRAIL`, holding its
+/// lock only to take the record's screen and to log the outcome, so a slow
+/// classifier backend doesn't stall every other writer in the process.
+pub fn screen_record(rec: &mut MemoryRecord) -> Result<ScreenOutcome, String> {
+    let screen = global_guardrail().record_screen(rec);
+    screen.screen(rec, |ctx, reason| global_guardrail().log_violation(ctx, reason))
+}
+
+/// `SafetyGuardrail::redact_for` against `SAFETY_GUARDRAIL`, classifying
+/// without its lock held.
+pub fn redact_for(rec: &MemoryRecord, text: &mut String) -> Result<usize, String> {
+    let screen = global_guardrail().record_screen(rec);
+    screen.redact(text)
+}
@@ -1,8 +1,13 @@
 // @generated automatically by Diesel CLI.
+//
+// `Uuid` columns are a Postgres type in diesel; only `memory_records` is
+// portable to SQLite.
+#[cfg(feature = "postgres_backend")]
+use diesel::allow_tables_to_appear_in_same_query;
 #[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
-use diesel::{allow_tables_to_appear_in_same_query, table};
+use diesel::table;
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     symbolic_nodes (id) {
         id -> Uuid,
@@ -12,7 +17,7 @@ table! {
     }
 }
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     symbolic_edges (id) {
         id -> Integer,
@@ -23,7 +28,7 @@ table! {
     }
 }
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     temporal_events (id) {
         id -> Uuid,
@@ -35,7 +40,7 @@ table! {
     }
 }
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     procedural_policies (id) {
         id -> Uuid,
@@ -48,7 +53,7 @@ table! {
     }
 }
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     perception_inputs (id) {
         id -> Uuid,
@@ -60,7 +65,7 @@ table! {
     }
 }
 
-#[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+#[cfg(feature = "postgres_backend")]
 table! {
     reflexion_snapshots (id) {
         id -> Uuid,
@@ -72,6 +77,21 @@ table! {
 }
 
 #[cfg(any(feature = "postgres_backend", feature = "sqlite_backend"))]
+table! {
+    memory_records (seq) {
+        seq -> BigInt,
+        id -> Text,
+        record_type -> Text,
+        actor -> Text,
+        action -> Text,
+        target -> Text,
+        timestamp -> BigInt,
+        status -> Text,
+        body -> Text,
+    }
+}
+
+#[cfg(feature = "postgres_backend")]
 allow_tables_to_appear_in_same_query!(
     symbolic_nodes,
     symbolic_edges,
@@ -4,7 +4,7 @@ use crate::cognitive_state::{
     BeliefDistribution, BeliefSummary, CognitiveDelta, CognitiveError, CognitiveSnapshot,
     GoalSnapshot, ProvenanceSummary, SelfStateView, SkillSnapshot, TemporalView, WorldStateView,
 };
-use crate::memory_record::MemoryType;
+use crate::memory_record::{MemoryType, RecordStatus};
 use crate::memory_store::MemoryStore;
 use crate::payloads::{BeliefPayload, GoalPayload, SkillPayload};
 use crate::persistence::{InMemoryBackend, MemoryBackend};
@@ -97,10 +97,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> SimulationFork<B> {
         handle: &crate::cognitive_state::CognitiveHandle<B>,
         base_tx: u64,
     ) -> Result<Self, CognitiveError> {
-        let records = {
-            let ms = handle.memory.lock().map_err(|_| CognitiveError::LockError)?;
-            ms.all().to_vec()
-        };
+        let records = handle.memory.snapshot().all().to_vec();
         let mut fork_store = MemoryStore::<InMemoryBackend>::new_in_memory();
         for r in records {
             fork_store
@@ -194,7 +191,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> SimulationFork<B> {
         let goal_factors: Vec<(f32, bool)> = {
             let all = self.store.all();
             all.iter()
-                .find(|r| r.record_type == MemoryType::Goal && r.status == "active")
+                .find(|r| r.record_type == MemoryType::Goal && r.status == RecordStatus::Active)
                 .and_then(|r| serde_json::from_value::<GoalPayload>(r.metadata.clone()).ok())
                 .map(|p| {
                     p.success_factors
@@ -301,7 +298,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> SimulationFork<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Temporal
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .collect();
@@ -328,7 +325,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> SimulationFork<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Goal
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .filter_map(|r| {
@@ -357,7 +354,7 @@ impl<B: MemoryBackend + Send + Sync + 'static> SimulationFork<B> {
             .iter()
             .filter(|r| {
                 r.record_type == MemoryType::Belief
-                    && r.status == "active"
+                    && r.status == RecordStatus::Active
                     && (actor.is_empty() || r.actor == actor)
             })
             .filter_map(|r| {
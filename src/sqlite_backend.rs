@@ -0,0 +1,170 @@
+use crate::memory_record::MemoryRecord;
+use crate::persistence::{MemoryBackend, RecordFilter};
+use crate::schema::memory_records;
+use anyhow::Result;
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use std::path::{Path, PathBuf};
+
+const MIGRATION: &str = include_str!("../migrations/00000000000001_create_memory_records/up.sql");
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = memory_records)]
+struct Row {
+    id: String,
+    record_type: String,
+    actor: String,
+    action: String,
+    target: String,
+    timestamp: i64,
+    status: String,
+    body: String,
+}
+
+impl Row {
+    fn from_record(r: &MemoryRecord) -> Result<Self> {
+        Ok(Self {
+            id: r.id.to_string(),
+            record_type: format!("{:?}", r.record_type),
+            actor: r.actor.clone(),
+            action: r.action.clone(),
+            target: r.target.clone(),
+            timestamp: r.timestamp.timestamp_micros(),
+            status: r.status.as_str().to_string(),
+            body: serde_json::to_string(r)?,
+        })
+    }
+}
+
+fn decode(bodies: Vec<String>) -> Result<Vec<MemoryRecord>> {
+    bodies
+        .iter()
+        .map(|b| serde_json::from_str(b).map_err(Into::into))
+        .collect()
+}
+
+/// `MemoryBackend` over a SQLite file (`memory_records` table, see
+/// `migrations/`). Records are upserted by id, so `update` and `delete` work
+/// in place, and `RecordFilter`s become indexed `WHERE` clauses.
+///
+/// `clear` opens a transaction that the next `flush` commits, making the
+/// store's clear-and-rewrite paths atomic: a crash mid-rewrite (or dropping
+/// the backend unflushed) leaves the previous contents.
+pub struct SqliteBackend {
+    path: PathBuf,
+    conn: SqliteConnection,
+    in_tx: bool,
+}
+
+impl SqliteBackend {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut conn = SqliteConnection::establish(&path.to_string_lossy())?;
+        conn.batch_execute("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+        conn.batch_execute(MIGRATION)?;
+        Ok(Self {
+            path,
+            conn,
+            in_tx: false,
+        })
+    }
+
+    /// Private database, gone when the backend is dropped.
+    pub fn in_memory() -> Result<Self> {
+        Self::new(":memory:")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn upsert(&mut self, record: &MemoryRecord) -> Result<()> {
+        let row = Row::from_record(record)?;
+        diesel::insert_into(memory_records::table)
+            .values(&row)
+            .on_conflict(memory_records::id)
+            .do_update()
+            .set(&row)
+            .execute(&mut self.conn)?;
+        Ok(())
+    }
+}
+
+impl MemoryBackend for SqliteBackend {
+    fn load(&mut self) -> Result<Vec<MemoryRecord>> {
+        let bodies = memory_records::table
+            .select(memory_records::body)
+            .order(memory_records::seq.asc())
+            .load::<String>(&mut self.conn)?;
+        decode(bodies)
+    }
+
+    fn append(&mut self, record: &MemoryRecord) -> Result<()> {
+        self.upsert(record)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.in_tx {
+            self.conn.batch_execute("COMMIT")?;
+            self.in_tx = false;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        if !self.in_tx {
+            self.conn.batch_execute("BEGIN IMMEDIATE")?;
+            self.in_tx = true;
+        }
+        diesel::delete(memory_records::table).execute(&mut self.conn)?;
+        Ok(())
+    }
+
+    fn update(&mut self, record: &MemoryRecord) -> Result<bool> {
+        self.upsert(record)?;
+        Ok(true)
+    }
+
+    fn delete(&mut self, ids: &[uuid::Uuid]) -> Result<bool> {
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        for chunk in ids.chunks(500) {
+            diesel::delete(memory_records::table.filter(memory_records::id.eq_any(chunk)))
+                .execute(&mut self.conn)?;
+        }
+        Ok(true)
+    }
+
+    fn query(&mut self, filter: &RecordFilter) -> Result<Option<Vec<MemoryRecord>>> {
+        use memory_records::dsl;
+        let mut q = dsl::memory_records
+            .select(dsl::body)
+            .order(dsl::seq.asc())
+            .into_boxed();
+        if let Some(actor) = &filter.actor {
+            q = q.filter(dsl::actor.eq(actor));
+        }
+        if let Some(action) = &filter.action {
+            q = q.filter(dsl::action.eq(action));
+        }
+        if let Some(target) = &filter.target {
+            q = q.filter(dsl::target.eq(target));
+        }
+        if let Some(t) = &filter.record_type {
+            q = q.filter(dsl::record_type.eq(format!("{:?}", t)));
+        }
+        if let Some(status) = filter.status {
+            q = q.filter(dsl::status.eq(status.as_str()));
+        }
+        if let Some(since) = filter.since {
+            q = q.filter(dsl::timestamp.ge(since.timestamp_micros()));
+        }
+        if let Some(until) = filter.until {
+            q = q.filter(dsl::timestamp.lt(until.timestamp_micros()));
+        }
+        if let Some(limit) = filter.limit {
+            q = q.limit(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+        Ok(Some(decode(q.load::<String>(&mut self.conn)?)?))
+    }
+}
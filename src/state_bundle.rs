@@ -0,0 +1,692 @@
+//! State bundle — one portable, versioned document for the whole cognitive state.
+//!
+//! Chain-of-thought: `/v1/state/export` returns a summary view, `/memory/export`
+//! a flat record list, `SnapshotManager` a tarball of the store file, and none
+//! of them carry the symbolic graph, topology, world model or self-model. A
+//! bundle is a single JSON document: a manifest (format, version, producer,
+//! tx cursor, namespaces, per-section SHA-256 and count) plus one section per
+//! component. Sections a source does not have are simply absent. On import the
+//! document is first migrated up to `BUNDLE_VERSION` (older shapes, including
+//! the `/memory/export` array, are rewritten step by step), then every section
+//! is checked against its checksum before anything touches the live state.
+//! Namespace remapping rewrites `ns:<name>` tags, so a tenant exported from one
+//! instance can land under a different name in another.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
+use crate::memory_record::MemoryRecord;
+use crate::persistence::MemoryBackend;
+use crate::self_model::{CapabilityDescriptor, SelfModel};
+use crate::store_format::FormatHeader;
+use crate::symbolic_store::{InMemoryGraph, SymbolicEdge, SymbolicNode, SymbolicStore};
+use crate::topological_memory::graph::SerializableTopoGraph;
+use crate::topological_memory::CausalTopoGraph;
+use crate::tx_log::{TxKind, TxLog};
+use crate::world_model_enhanced::WorldModelEnhanced;
+
+/// `manifest.format` of every bundle.
+pub const BUNDLE_FORMAT: &str = "hipcortex-state-bundle";
+/// Version written by this build; older bundles are migrated on read.
+pub const BUNDLE_VERSION: u32 = 1;
+
+pub const SECTION_RECORDS: &str = "records";
+pub const SECTION_SYMBOLIC: &str = "symbolic";
+pub const SECTION_TOPOLOGY: &str = "topology";
+pub const SECTION_WORLD_MODEL: &str = "world_model";
+pub const SECTION_CAPABILITIES: &str = "capabilities";
+
+// ─── Document ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionInfo {
+    /// Hex SHA-256 of the section's compact JSON.
+    pub sha256: String,
+    /// Items in the section (records, nodes + edges, …).
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: String,
+    pub version: u32,
+    /// e.g. "hipcortex 0.9.0".
+    pub producer: String,
+    pub created_at: DateTime<Utc>,
+    /// Source tx log position at export time, if it had one.
+    pub tx_cursor: Option<u64>,
+    /// Namespaces (`ns:` tags) present in `records`.
+    pub namespaces: Vec<String>,
+    pub sections: BTreeMap<String, SectionInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolicSection {
+    pub nodes: Vec<SymbolicNode>,
+    pub edges: Vec<SymbolicEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateBundle {
+    pub manifest: BundleManifest,
+    /// Hot and cold records.
+    #[serde(default)]
+    pub records: Vec<MemoryRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbolic: Option<SymbolicSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topology: Option<SerializableTopoGraph>,
+    /// `WorldModelEnhanced::to_json` output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_model: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Vec<CapabilityDescriptor>>,
+}
+
+impl StateBundle {
+    /// Serialize with fresh checksums in the manifest.
+    pub fn to_json(&self) -> Result<Value> {
+        let mut doc = serde_json::to_value(self)?;
+        seal(&mut doc)?;
+        Ok(doc)
+    }
+
+    /// Migrate `doc` to `BUNDLE_VERSION`, verify every section checksum and
+    /// decode. Returns the bundle and the migrations that were applied.
+    pub fn from_json(doc: Value) -> Result<(Self, Vec<String>)> {
+        let (doc, applied) = migrate(doc)?;
+        verify(&doc)?;
+        let bundle = serde_json::from_value(doc).context("decoding bundle")?;
+        Ok((bundle, applied))
+    }
+}
+
+fn section_info(section: &Value) -> SectionInfo {
+    use sha2::{Digest, Sha256};
+    let count = match section {
+        Value::Array(items) => items.len(),
+        Value::Object(map) if map.contains_key("nodes") => {
+            let len = |k: &str| map.get(k).and_then(Value::as_array).map_or(0, Vec::len);
+            len("nodes") + len("edges")
+        }
+        Value::Object(map) => map.len(),
+        _ => 0,
+    };
+    SectionInfo {
+        sha256: hex::encode(Sha256::digest(section.to_string().as_bytes())),
+        count,
+    }
+}
+
+const SECTIONS: [&str; 5] = [
+    SECTION_RECORDS,
+    SECTION_SYMBOLIC,
+    SECTION_TOPOLOGY,
+    SECTION_WORLD_MODEL,
+    SECTION_CAPABILITIES,
+];
+
+/// Recompute `manifest.sections` for every section present in `doc`.
+fn seal(doc: &mut Value) -> Result<()> {
+    let sections: serde_json::Map<String, Value> = SECTIONS
+        .iter()
+        .filter_map(|name| {
+            let info = section_info(doc.get(*name)?);
+            Some((name.to_string(), serde_json::to_value(info).ok()?))
+        })
+        .collect();
+    let manifest = doc
+        .get_mut("manifest")
+        .and_then(Value::as_object_mut)
+        .context("bundle has no manifest")?;
+    manifest.insert("sections".into(), Value::Object(sections));
+    Ok(())
+}
+
+fn verify(doc: &Value) -> Result<()> {
+    let manifest = doc.get("manifest").context("bundle has no manifest")?;
+    let format = manifest.get("format").and_then(Value::as_str).unwrap_or("");
+    if format != BUNDLE_FORMAT {
+        bail!("not a state bundle (format {format:?})");
+    }
+    let listed: BTreeMap<String, SectionInfo> =
+        serde_json::from_value(manifest.get("sections").cloned().unwrap_or_default())
+            .context("manifest.sections")?;
+    for name in SECTIONS {
+        match (doc.get(name), listed.get(name)) {
+            (Some(section), Some(expected)) => {
+                let actual = section_info(section);
+                if actual.sha256 != expected.sha256 {
+                    bail!("section {name} checksum mismatch: bundle is corrupt or was edited");
+                }
+            }
+            (Some(_), None) => bail!("section {name} is not listed in the manifest"),
+            (None, Some(_)) => bail!("section {name} is listed in the manifest but missing"),
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
+
+// ─── Migrations ──────────────────────────────────────────────────────────────
+
+type MigrationStep = fn(&mut Value) -> Result<()>;
+
+/// `(from_version, description, step)`; each step rewrites a document at
+/// `from_version` in place into `from_version + 1`.
+const MIGRATIONS: &[(u32, &str, MigrationStep)] = &[(
+    0,
+    "wrap a legacy record export in a v1 bundle",
+    migrate_v0_to_v1,
+)];
+
+/// Version of `doc`. Anything without a manifest is a version-0 record dump:
+/// a JSON array of records or the `{"records": [...]}` of `/memory/export`.
+pub fn bundle_version(doc: &Value) -> u32 {
+    doc.pointer("/manifest/version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32)
+}
+
+/// Apply migrations until `doc` is at `BUNDLE_VERSION`.
+pub fn migrate(mut doc: Value) -> Result<(Value, Vec<String>)> {
+    let mut applied = Vec::new();
+    loop {
+        let version = bundle_version(&doc);
+        if version == BUNDLE_VERSION {
+            return Ok((doc, applied));
+        }
+        if version > BUNDLE_VERSION {
+            bail!("bundle version {version} is newer than this build supports ({BUNDLE_VERSION})");
+        }
+        let (_, description, step) = MIGRATIONS
+            .iter()
+            .find(|(from, _, _)| *from == version)
+            .with_context(|| format!("no migration from bundle version {version}"))?;
+        step(&mut doc).with_context(|| format!("migrating from version {version}"))?;
+        applied.push(format!("v{version}→v{}: {description}", version + 1));
+    }
+}
+
+fn migrate_v0_to_v1(doc: &mut Value) -> Result<()> {
+    let (records, exported_at) = match doc.take() {
+        Value::Array(items) => (items, None),
+        Value::Object(mut map) => {
+            let records = match map.remove("records") {
+                Some(Value::Array(items)) => items,
+                _ => bail!("expected a record array or an object with \"records\""),
+            };
+            (records, map.remove("exported_at"))
+        }
+        _ => bail!("expected a record array or an object with \"records\""),
+    };
+    let records = records
+        .into_iter()
+        .map(|mut r| {
+            // Same aliases `/memory/add` accepts; hand-written dumps use them.
+            if let Some(t) = r.get("record_type").and_then(Value::as_str) {
+                let canonical = match t {
+                    "Episodic" | "ShortTerm" | "Working" => "Temporal",
+                    "Semantic" | "LongTerm" => "Symbolic",
+                    "Reflexive" => "Reflexion",
+                    "Perceptual" => "Perception",
+                    other => other,
+                };
+                r["record_type"] = Value::from(canonical);
+            }
+            serde_json::from_value::<MemoryRecord>(r)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .context("legacy record")?;
+
+    let created_at = exported_at
+        .and_then(|v| serde_json::from_value::<DateTime<Utc>>(v).ok())
+        .unwrap_or_else(Utc::now);
+    let bundle = StateBundle {
+        manifest: BundleManifest {
+            format: BUNDLE_FORMAT.into(),
+            version: 1,
+            producer: "legacy record export".into(),
+            created_at,
+            tx_cursor: None,
+            namespaces: namespaces_of(&records),
+            sections: BTreeMap::new(),
+        },
+        records,
+        symbolic: None,
+        topology: None,
+        world_model: None,
+        capabilities: None,
+    };
+    *doc = bundle.to_json()?;
+    Ok(())
+}
+
+// ─── Export ──────────────────────────────────────────────────────────────────
+
+/// The live components a bundle is exported from or imported into. Only the
+/// memory store is required; `None` components are left out of the bundle on
+/// export and reported as ignored on import.
+pub struct BundleTargets<B: MemoryBackend> {
+    pub memory: Arc<ConcurrentMemoryStore<B>>,
+    pub symbolic: Option<Arc<Mutex<SymbolicStore<InMemoryGraph>>>>,
+    pub topology: Option<Arc<Mutex<CausalTopoGraph>>>,
+    pub world: Option<Arc<RwLock<WorldModelEnhanced>>>,
+    pub self_model: Option<Arc<SelfModel>>,
+    pub tx_log: Option<Arc<TxLog>>,
+}
+
+impl<B: MemoryBackend> BundleTargets<B> {
+    pub fn new(memory: Arc<ConcurrentMemoryStore<B>>) -> Self {
+        Self {
+            memory,
+            symbolic: None,
+            topology: None,
+            world: None,
+            self_model: None,
+            tx_log: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleExportOptions {
+    /// Export only records tagged `ns:<namespace>`.
+    pub namespace: Option<String>,
+}
+
+fn lock_err<T>(_: T) -> anyhow::Error {
+    anyhow::anyhow!("state lock poisoned")
+}
+
+fn namespaces_of(records: &[MemoryRecord]) -> Vec<String> {
+    let set: BTreeSet<&str> = records
+        .iter()
+        .flat_map(|r| r.tags.iter().filter_map(|t| t.strip_prefix("ns:")))
+        .collect();
+    set.into_iter().map(str::to_string).collect()
+}
+
+/// Collect every available component into a bundle.
+pub fn export<B: MemoryBackend>(
+    targets: &BundleTargets<B>,
+    options: &BundleExportOptions,
+) -> Result<StateBundle> {
+    let mut records = {
+        let store = targets.memory.lock().map_err(lock_err)?;
+        let mut records = store.all().to_vec();
+        if let Some(cold) = store.cold_tier() {
+            records.extend(cold.scan()?);
+        }
+        records
+    };
+    if let Some(ns) = &options.namespace {
+        let tag = format!("ns:{ns}");
+        records.retain(|r| r.tags.contains(&tag));
+    }
+
+    let symbolic = match &targets.symbolic {
+        Some(s) => {
+            let (nodes, edges) = s.lock().map_err(lock_err)?.export_graph();
+            Some(SymbolicSection { nodes, edges })
+        }
+        None => None,
+    };
+    let topology = match &targets.topology {
+        Some(t) => Some(t.lock().map_err(lock_err)?.to_serializable()),
+        None => None,
+    };
+    let world_model = match &targets.world {
+        Some(w) => Some(w.read().map_err(lock_err)?.to_json()?),
+        None => None,
+    };
+    let capabilities = match &targets.self_model {
+        Some(sm) => Some(sm.capabilities().map_err(anyhow::Error::msg)?),
+        None => None,
+    };
+
+    Ok(StateBundle {
+        manifest: BundleManifest {
+            format: BUNDLE_FORMAT.into(),
+            version: BUNDLE_VERSION,
+            producer: format!("hipcortex {}", env!("CARGO_PKG_VERSION")),
+            created_at: Utc::now(),
+            tx_cursor: targets.tx_log.as_ref().map(|l| l.current_tx()),
+            namespaces: namespaces_of(&records),
+            sections: BTreeMap::new(),
+        },
+        records,
+        symbolic,
+        topology,
+        world_model,
+        capabilities,
+    })
+}
+
+// ─── Import ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleMode {
+    /// Add what is missing; records whose id already exists are kept as they are.
+    #[default]
+    Merge,
+    /// Make records, graphs and the world model match the bundle exactly.
+    /// Capabilities are upserted either way.
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleImportOptions {
+    #[serde(default)]
+    pub mode: BundleMode,
+    /// Source namespace → target namespace. The key `""` moves records that
+    /// carry no namespace into the target one.
+    #[serde(default)]
+    pub namespace_map: BTreeMap<String, String>,
+    /// Actor recorded on the import tx.
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleImportReport {
+    /// Version the bundle was written with, before migration.
+    pub source_version: u32,
+    pub migrations: Vec<String>,
+    pub source_tx_cursor: Option<u64>,
+    pub records_added: usize,
+    /// Merge mode: records whose id was already present.
+    pub records_existing: usize,
+    /// Replace mode: records removed because the bundle does not have them.
+    pub records_removed: usize,
+    pub records_remapped: usize,
+    pub symbolic_nodes: usize,
+    pub symbolic_edges: usize,
+    pub topology_nodes: usize,
+    pub topology_edges: usize,
+    /// Topology edges rejected by the target graph (e.g. would close a cycle).
+    pub topology_edges_rejected: usize,
+    pub world_model: bool,
+    pub capabilities: usize,
+    /// Sections in the bundle with no matching target.
+    pub sections_ignored: Vec<String>,
+    /// Tx that logged the record changes, when the target has a tx log.
+    pub tx: Option<u64>,
+}
+
+/// Rewrite `ns:` tags per `map`. Returns whether anything changed.
+fn remap_namespace(rec: &mut MemoryRecord, map: &BTreeMap<String, String>) -> bool {
+    if map.is_empty() {
+        return false;
+    }
+    let mut changed = false;
+    let has_ns = rec.tags.iter().any(|t| t.starts_with("ns:"));
+    for tag in rec.tags.iter_mut() {
+        if let Some(to) = tag.strip_prefix("ns:").and_then(|ns| map.get(ns)) {
+            *tag = format!("ns:{to}");
+            changed = true;
+        }
+    }
+    if !has_ns {
+        if let Some(to) = map.get("") {
+            rec.tags.push(format!("ns:{to}"));
+            changed = true;
+        }
+    }
+    if changed {
+        let mut seen = std::collections::HashSet::new();
+        rec.tags.retain(|t| seen.insert(t.clone()));
+        let hash = rec.compute_hash();
+        rec.integrity = Some(hash.clone());
+        rec.content_hash = Some(hash);
+    }
+    changed
+}
+
+/// Verify, migrate and apply a bundle document to `targets`.
+pub fn import<B: MemoryBackend>(
+    targets: &BundleTargets<B>,
+    doc: Value,
+    options: &BundleImportOptions,
+) -> Result<BundleImportReport> {
+    let source_version = bundle_version(&doc);
+    let (bundle, migrations) = StateBundle::from_json(doc)?;
+    let mut report = BundleImportReport {
+        source_version,
+        migrations,
+        source_tx_cursor: bundle.manifest.tx_cursor,
+        ..Default::default()
+    };
+    let replace = options.mode == BundleMode::Replace;
+
+    // Records
+    let mut records = bundle.records;
+    for rec in records.iter_mut() {
+        if remap_namespace(rec, &options.namespace_map) {
+            report.records_remapped += 1;
+        }
+    }
+    // Bundles pass the store's write screen like any other write, screened
+    // before the writer lock is taken.
+    let screening = targets.memory.snapshot();
+    for rec in records.iter_mut() {
+        screening.screen(rec)?;
+    }
+    {
+        let mut store = targets.memory.lock().map_err(lock_err)?;
+        if replace {
+            let incoming: HashSet<Uuid> = records.iter().map(|r| r.id).collect();
+            let mut before: HashSet<Uuid> = store.all().iter().map(|r| r.id).collect();
+            if let Some(cold) = store.cold_tier() {
+                before.extend(cold.ids());
+            }
+            report.records_removed = before.difference(&incoming).count();
+            report.records_added = incoming.difference(&before).count();
+            report.records_existing = incoming.len() - report.records_added;
+            store.replace_all(records)?;
+        } else {
+            let mut present: HashSet<Uuid> = store.all().iter().map(|r| r.id).collect();
+            if let Some(cold) = store.cold_tier() {
+                present.extend(cold.ids());
+            }
+            for rec in records {
+                if present.insert(rec.id) {
+                    store.add_prescreened(rec)?;
+                    report.records_added += 1;
+                } else {
+                    report.records_existing += 1;
+                }
+            }
+            store.flush()?;
+        }
+        if let Some(log) = &targets.tx_log {
+            let kind = if replace {
+                TxKind::Restore
+            } else {
+                TxKind::MemoryAdd
+            };
+            let actor = options.actor.as_deref().unwrap_or("bundle_import");
+            report.tx = crate::pitr::log_pending(&mut store, log, kind, actor);
+        }
+    }
+
+    // Symbolic graph: node ids are assigned by the target graph, so edges are
+    // remapped. Merge reuses an existing node with the same label and properties.
+    match (bundle.symbolic, &targets.symbolic) {
+        (Some(section), Some(target)) => {
+            let mut graph = target.lock().map_err(lock_err)?;
+            let (existing, existing_edges) = graph.export_graph();
+            if replace {
+                for node in &existing {
+                    graph.remove_node(node.id);
+                }
+            }
+            let mut ids: HashMap<Uuid, Uuid> = HashMap::new();
+            for node in section.nodes {
+                let reuse = if replace {
+                    None
+                } else {
+                    existing
+                        .iter()
+                        .find(|n| n.label == node.label && n.properties == node.properties)
+                        .map(|n| n.id)
+                };
+                let id = match reuse {
+                    Some(id) => id,
+                    None => {
+                        report.symbolic_nodes += 1;
+                        graph.add_node(&node.label, node.properties)
+                    }
+                };
+                ids.insert(node.id, id);
+            }
+            let edges: HashSet<(Uuid, Uuid, String)> = if replace {
+                HashSet::new()
+            } else {
+                existing_edges
+                    .into_iter()
+                    .map(|e| (e.from, e.to, e.relation))
+                    .collect()
+            };
+            for edge in section.edges {
+                let (Some(&from), Some(&to)) = (ids.get(&edge.from), ids.get(&edge.to)) else {
+                    continue;
+                };
+                if !edges.contains(&(from, to, edge.relation.clone())) {
+                    graph.add_edge(from, to, &edge.relation);
+                    report.symbolic_edges += 1;
+                }
+            }
+        }
+        (Some(_), None) => report.sections_ignored.push(SECTION_SYMBOLIC.into()),
+        (None, _) => {}
+    }
+
+    // Topology
+    match (bundle.topology, &targets.topology) {
+        (Some(section), Some(target)) => {
+            let mut topo = target.lock().map_err(lock_err)?;
+            if replace {
+                *topo = CausalTopoGraph::new();
+            }
+            for node in section.nodes {
+                if topo
+                    .add_node(node.symbolic_id, node.micro_embedding, node.properties)
+                    .is_ok()
+                {
+                    report.topology_nodes += 1;
+                }
+            }
+            for edge in section.edges {
+                if topo.get_neighbors(&edge.from).contains(&edge.to) {
+                    continue;
+                }
+                match topo.add_edge(
+                    edge.from,
+                    edge.to,
+                    edge.edge_type,
+                    edge.strength,
+                    edge.confidence,
+                ) {
+                    Ok(()) => report.topology_edges += 1,
+                    Err(_) => report.topology_edges_rejected += 1,
+                }
+            }
+        }
+        (Some(_), None) => report.sections_ignored.push(SECTION_TOPOLOGY.into()),
+        (None, _) => {}
+    }
+
+    // World model
+    match (bundle.world_model, &targets.world) {
+        (Some(section), Some(target)) => {
+            let wm = target.read().map_err(lock_err)?;
+            if replace {
+                wm.replace_from_json(&section)?;
+            } else {
+                wm.merge_json(&section)?;
+            }
+            report.world_model = true;
+        }
+        (Some(_), None) => report.sections_ignored.push(SECTION_WORLD_MODEL.into()),
+        (None, _) => {}
+    }
+
+    // Self-model capabilities
+    match (bundle.capabilities, &targets.self_model) {
+        (Some(section), Some(target)) => {
+            for cap in section {
+                target.upsert_capability(cap).map_err(anyhow::Error::msg)?;
+                report.capabilities += 1;
+            }
+        }
+        (Some(_), None) => report.sections_ignored.push(SECTION_CAPABILITIES.into()),
+        (None, _) => {}
+    }
+
+    Ok(report)
+}
+
+// ─── Files ───────────────────────────────────────────────────────────────────
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("gz")
+}
+
+/// Write `bundle` as JSON, gzip-compressed when `path` ends in `.gz`.
+pub fn write_bundle(path: &Path, bundle: &StateBundle) -> Result<()> {
+    let doc = bundle.to_json()?;
+    let bytes = serde_json::to_vec(&doc)?;
+    let tmp = path.with_extension("tmp");
+    {
+        let file =
+            std::fs::File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+        if is_gzip(path) {
+            let mut enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            enc.write_all(&bytes)?;
+            enc.finish()?.sync_all()?;
+        } else {
+            let mut file = file;
+            file.write_all(&bytes)?;
+            file.sync_all()?;
+        }
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Read a bundle document (plain or `.gz`). A JSON Lines record dump, such as
+/// a plaintext store file, is read as a version-0 record array.
+pub fn read_bundle(path: &Path) -> Result<Value> {
+    let file = std::fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut text = String::new();
+    if is_gzip(path) {
+        flate2::read::GzDecoder::new(file).read_to_string(&mut text)?;
+    } else {
+        std::io::BufReader::new(file).read_to_string(&mut text)?;
+    }
+    if let Ok(doc) = serde_json::from_str(&text) {
+        return Ok(doc);
+    }
+    let lines = text
+        .lines()
+        .filter(|l| !l.trim().is_empty() && FormatHeader::parse(l).is_none())
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<Value>, _>>()
+        .with_context(|| {
+            format!(
+                "{} is neither a bundle nor a JSONL record dump",
+                path.display()
+            )
+        })?;
+    Ok(Value::Array(lines))
+}
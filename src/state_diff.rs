@@ -166,6 +166,7 @@ pub fn compute_tx_diff<B: MemoryBackend>(
             TxKind::WorkspaceOp => {
                 health_delta.workspace_ops += 1;
             }
+            TxKind::Checkpoint | TxKind::Restore => {}
         }
     }
 
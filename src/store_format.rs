@@ -0,0 +1,278 @@
+//! Store format — version header and record migrations for JSONL memory stores.
+//!
+//! Chain-of-thought: `MemoryRecord` only ever grew by adding `#[serde(default)]`
+//! fields, so old files kept loading and nobody could tell an old file from a
+//! new one. A store file now starts with one header line,
+//! `{"hipcortex_format":2,"written_by":"hipcortex 0.9.0"}`, written in plain
+//! JSON even when the records below it are compressed or encrypted. Files
+//! without a header are format 1. Readers accept anything up to
+//! `FORMAT_VERSION` (typed fields parse old spellings leniently) and refuse
+//! newer files instead of silently dropping fields they do not know.
+//! `migrate_file` rewrites a plaintext store at the current format in place,
+//! one record-level step per version, keeping a backup of the original.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::memory_record::{MemoryRecord, Priority, RecordStatus};
+
+/// Format written by this build.
+pub const FORMAT_VERSION: u32 = 2;
+/// Format of files that have no header line.
+pub const LEGACY_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatHeader {
+    #[serde(rename = "hipcortex_format")]
+    pub version: u32,
+    pub written_by: String,
+    /// Format the file had before `migrate_file` last rewrote it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migrated_from: Option<u32>,
+}
+
+impl FormatHeader {
+    pub fn current() -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            written_by: format!("hipcortex {}", env!("CARGO_PKG_VERSION")),
+            migrated_from: None,
+        }
+    }
+
+    /// Parse `line` as a header; `None` for record lines.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if !line.starts_with('{') || !line.contains("\"hipcortex_format\"") {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    /// The header as one JSONL line, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).expect("header serializes")
+    }
+
+    /// Error out if this build cannot read the file.
+    pub fn ensure_readable(&self) -> Result<()> {
+        if self.version > FORMAT_VERSION {
+            bail!(
+                "store format {} (written by {}) is newer than this build supports ({}); upgrade hipcortex",
+                self.version,
+                self.written_by,
+                FORMAT_VERSION
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Format version of the store at `path`: the header's, or
+/// `LEGACY_FORMAT_VERSION` for a file without one.
+pub fn detect_version(path: &Path) -> Result<u32> {
+    use std::io::BufRead;
+    let file = std::fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        return Ok(FormatHeader::parse(&line).map_or(LEGACY_FORMAT_VERSION, |h| h.version));
+    }
+    Ok(FORMAT_VERSION)
+}
+
+// ─── Record migrations ───────────────────────────────────────────────────────
+
+/// Rewrites one record (as JSON) from version `n` to `n + 1`; returns whether
+/// anything changed.
+type RecordStep = fn(&mut Value) -> bool;
+
+/// `(from_version, description, step)`.
+const MIGRATIONS: &[(u32, &str, RecordStep)] = &[(
+    1,
+    "canonical priority/status values and explicit defaults",
+    v1_to_v2,
+)];
+
+/// Format 1 left `priority`/`status` as free-form strings and omitted fields
+/// added later. Spell them out so the file no longer depends on defaults.
+fn v1_to_v2(rec: &mut Value) -> bool {
+    let Some(obj) = rec.as_object_mut() else {
+        return false;
+    };
+    let mut changed = false;
+    let str_of = |obj: &serde_json::Map<String, Value>, key: &str| {
+        obj.get(key)
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string()
+    };
+    let priority = Priority::parse_lenient(&str_of(obj, "priority")).as_str();
+    let status = RecordStatus::parse_lenient(&str_of(obj, "status")).as_str();
+    for (key, value) in [("priority", priority), ("status", status)] {
+        if obj.get(key).and_then(Value::as_str) != Some(value) {
+            obj.insert(key.to_string(), Value::from(value));
+            changed = true;
+        }
+    }
+    for (key, default) in [
+        ("confidence", Value::from(1.0)),
+        ("version", Value::from(0)),
+        ("tags", Value::Array(Vec::new())),
+    ] {
+        if obj.get(key).is_none_or(Value::is_null) {
+            obj.insert(key.to_string(), default);
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrateOptions {
+    /// Report only; leave the file untouched.
+    pub dry_run: bool,
+    /// Skip the `<file>.v<N>.bak` copy of the original.
+    pub no_backup: bool,
+    /// Write the migrated store here instead of replacing the input.
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Descriptions of the steps applied, in order.
+    pub steps: Vec<String>,
+    pub records: usize,
+    pub changed: usize,
+    /// Changed records whose integrity hash was recomputed.
+    pub resealed: usize,
+    /// 1-based line numbers that were not valid records and were dropped.
+    pub dropped_lines: Vec<usize>,
+    pub backup: Option<PathBuf>,
+    pub written: Option<PathBuf>,
+}
+
+/// Upgrade the plaintext JSONL store at `path` to `FORMAT_VERSION`.
+///
+/// Compressed and encrypted stores are refused (their records cannot be
+/// rewritten without the key); they still load, since typed fields parse the
+/// old spellings. Changed records get a fresh integrity hash.
+pub fn migrate_file(path: &Path, options: &MigrateOptions) -> Result<MigrationReport> {
+    let wal = path.with_extension("wal");
+    if wal.exists() {
+        bail!(
+            "{} has an unflushed write-ahead log; open the store once so it is recovered, then migrate",
+            path.display()
+        );
+    }
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .peekable();
+    let from_version = match lines.peek().and_then(|(_, l)| FormatHeader::parse(l)) {
+        Some(header) => {
+            header.ensure_readable()?;
+            lines.next();
+            header.version
+        }
+        None => LEGACY_FORMAT_VERSION,
+    };
+    let steps: Vec<&(u32, &str, RecordStep)> = MIGRATIONS
+        .iter()
+        .filter(|(from, _, _)| *from >= from_version && *from < FORMAT_VERSION)
+        .collect();
+
+    let mut report = MigrationReport {
+        from_version,
+        to_version: FORMAT_VERSION,
+        steps: steps
+            .iter()
+            .map(|(from, d, _)| format!("v{from}→v{}: {d}", from + 1))
+            .collect(),
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    for (idx, line) in lines {
+        let mut value: Value = match serde_json::from_str(line) {
+            Ok(v @ Value::Object(_)) => v,
+            Ok(_) | Err(_) if !line.trim_start().starts_with('{') => {
+                bail!(
+                    "line {} is not JSON; compressed stores cannot be migrated",
+                    idx + 1
+                )
+            }
+            _ => {
+                report.dropped_lines.push(idx + 1);
+                continue;
+            }
+        };
+        if value.get("nonce").is_some() && value.get("data").is_some() {
+            bail!(
+                "{} is encrypted; encrypted stores cannot be migrated",
+                path.display()
+            );
+        }
+        let mut changed = false;
+        for (_, _, step) in &steps {
+            changed |= step(&mut value);
+        }
+        let mut rec: MemoryRecord = match serde_json::from_value(value) {
+            Ok(rec) => rec,
+            Err(_) => {
+                report.dropped_lines.push(idx + 1);
+                continue;
+            }
+        };
+        if changed {
+            report.changed += 1;
+            if rec.integrity.is_some() {
+                let hash = rec.compute_hash();
+                rec.integrity = Some(hash.clone());
+                rec.content_hash = Some(hash);
+                report.resealed += 1;
+            }
+        }
+        report.records += 1;
+        out.push(rec);
+    }
+
+    let nothing_to_do = steps.is_empty() && report.dropped_lines.is_empty();
+    if options.dry_run || (nothing_to_do && options.out.is_none()) {
+        return Ok(report);
+    }
+
+    let target = options.out.clone().unwrap_or_else(|| path.to_path_buf());
+    if target == path && !options.no_backup {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".v{from_version}.bak"));
+        let backup = PathBuf::from(name);
+        std::fs::copy(path, &backup)
+            .with_context(|| format!("backing up to {}", backup.display()))?;
+        report.backup = Some(backup);
+    }
+    let header = FormatHeader {
+        migrated_from: Some(from_version).filter(|v| *v != FORMAT_VERSION),
+        ..FormatHeader::current()
+    };
+    let mut body = header.to_line();
+    body.push('\n');
+    for rec in &out {
+        body.push_str(&serde_json::to_string(rec)?);
+        body.push('\n');
+    }
+    let tmp = target.with_extension("migrate.tmp");
+    std::fs::write(&tmp, body).with_context(|| format!("writing {}", tmp.display()))?;
+    std::fs::rename(&tmp, &target)?;
+    report.written = Some(target);
+    Ok(report)
+}
@@ -0,0 +1,255 @@
+//! Summarizer — abstractive (LLM) or extractive summaries for consolidation.
+//!
+//! Chain-of-thought: consolidation used to write fixed strings ("summary:<tags>",
+//! "causal pattern: a → b") and `CognitiveDelta::Consolidate` made the caller
+//! bring its own summary. A `Summarizer` turns a cluster of source records into
+//! text. With an `LLMClient` it asks for an abstractive summary and scores its
+//! faithfulness against the sources; an empty reply or a score below
+//! `min_faithfulness` falls back to the extractive path, which is also the only
+//! path when no model is configured. Every summary keeps `evidence` = source ids.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::llm_clients::LLMClient;
+use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::memory_store::MemoryStore;
+use crate::persistence::MemoryBackend;
+use crate::symbolic_store::{GraphDatabase, SymbolicStore};
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "are", "was", "were", "has",
+    "have", "had", "its", "their", "they", "them", "then", "than", "which", "who", "whom",
+    "about", "over", "after", "before", "also", "been", "being", "but", "not", "all", "any",
+    "each", "such", "these", "those", "there", "here", "records", "record", "summary",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryMethod {
+    Abstractive,
+    Extractive,
+}
+
+/// A summary of a record cluster plus its provenance and quality score.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Summary {
+    pub text: String,
+    pub method: SummaryMethod,
+    /// Fraction [0, 1] of the summary's content words supported by the sources.
+    pub faithfulness: f32,
+    /// Source record ids — becomes `MemoryRecord::evidence` on the summary record.
+    pub evidence: Vec<Uuid>,
+    /// Why the abstractive path was abandoned, if it was attempted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_reason: Option<String>,
+}
+
+pub struct Summarizer {
+    client: Option<Arc<dyn LLMClient>>,
+    /// Sources beyond this count are dropped from the prompt (oldest first kept).
+    pub max_sources: usize,
+    /// Abstractive summaries scoring below this fall back to extractive.
+    pub min_faithfulness: f32,
+    /// Number of source lines the extractive path keeps.
+    pub extractive_lines: usize,
+}
+
+impl Default for Summarizer {
+    fn default() -> Self {
+        Self::extractive()
+    }
+}
+
+impl Summarizer {
+    /// Summarizer with no model: always extractive.
+    pub fn extractive() -> Self {
+        Self {
+            client: None,
+            max_sources: 50,
+            min_faithfulness: 0.5,
+            extractive_lines: 3,
+        }
+    }
+
+    /// Summarizer backed by any `LLMClient`.
+    pub fn with_llm(client: Arc<dyn LLMClient>) -> Self {
+        Self {
+            client: Some(client),
+            ..Self::extractive()
+        }
+    }
+
+    pub fn has_model(&self) -> bool {
+        self.client.is_some()
+    }
+
+    /// Summarize `sources`. Never fails: falls back to extractive on any LLM problem.
+    pub fn summarize(&self, sources: &[&MemoryRecord]) -> Summary {
+        let evidence: Vec<Uuid> = sources.iter().map(|r| r.id).collect();
+        let texts: Vec<String> = sources.iter().map(|r| record_text(r)).collect();
+
+        let mut fallback_reason = None;
+        if let Some(client) = &self.client {
+            let prompt = build_prompt(&texts[..texts.len().min(self.max_sources)]);
+            let reply = client.generate_response(&prompt).trim().to_string();
+            if reply.is_empty() {
+                fallback_reason = Some("model returned empty summary".to_string());
+            } else {
+                let faithfulness = score_faithfulness(&reply, &texts);
+                if faithfulness >= self.min_faithfulness {
+                    return Summary {
+                        text: reply,
+                        method: SummaryMethod::Abstractive,
+                        faithfulness,
+                        evidence,
+                        fallback_reason: None,
+                    };
+                }
+                fallback_reason = Some(format!(
+                    "faithfulness {:.2} below threshold {:.2}",
+                    faithfulness, self.min_faithfulness
+                ));
+            }
+        }
+
+        let text = extractive_summary(&texts, self.extractive_lines);
+        Summary {
+            faithfulness: score_faithfulness(&text, &texts),
+            text,
+            method: SummaryMethod::Extractive,
+            evidence,
+            fallback_reason,
+        }
+    }
+
+    /// Summarize `sources` into an Abstract-tier record (`action = "consolidated"`,
+    /// `target = "summary: <text>"`) with `evidence` pointing at every source.
+    pub fn summary_record(&self, actor: &str, sources: &[&MemoryRecord]) -> MemoryRecord {
+        let summary = self.summarize(sources);
+        let mut rec = MemoryRecord::new(
+            MemoryType::Temporal,
+            actor.to_string(),
+            "consolidated".into(),
+            format!("summary: {}", summary.text),
+            serde_json::json!({
+                "source_count": sources.len(),
+                "summary_method": summary.method,
+                "faithfulness": summary.faithfulness,
+                "fallback_reason": summary.fallback_reason,
+            }),
+        );
+        rec.evidence = summary.evidence;
+        let hash = rec.compute_hash();
+        rec.integrity = Some(hash.clone());
+        rec.content_hash = Some(hash);
+        rec
+    }
+}
+
+/// Canonical text for a record: `actor action target`.
+pub fn record_text(r: &MemoryRecord) -> String {
+    format!("{} {} {}", r.actor, r.action, r.target)
+}
+
+fn build_prompt(texts: &[String]) -> String {
+    let mut prompt = String::from(
+        "Summarize the following memory records in one to three sentences. \
+         Only state facts present in the records; do not speculate.\n",
+    );
+    for t in texts {
+        prompt.push_str("- ");
+        prompt.push_str(t);
+        prompt.push('\n');
+    }
+    prompt
+}
+
+fn content_tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|t| t.to_lowercase())
+        .filter(|t| t.chars().count() >= 3 && !STOPWORDS.contains(&t.as_str()))
+        .collect()
+}
+
+/// Lexical faithfulness: fraction of the summary's content tokens that occur in
+/// at least one source. 0.0 for a summary with no content tokens.
+pub fn score_faithfulness(summary: &str, sources: &[String]) -> f32 {
+    let support: HashSet<String> = sources.iter().flat_map(|s| content_tokens(s)).collect();
+    let tokens = content_tokens(summary);
+    if tokens.is_empty() {
+        return 0.0;
+    }
+    let supported = tokens.iter().filter(|t| support.contains(*t)).count();
+    supported as f32 / tokens.len() as f32
+}
+
+/// Pick the `n` most central source lines (highest content-token overlap with the
+/// rest of the cluster), kept in original order and joined with "; ".
+pub fn extractive_summary(texts: &[String], n: usize) -> String {
+    if texts.is_empty() {
+        return String::new();
+    }
+    let token_sets: Vec<HashSet<String>> = texts
+        .iter()
+        .map(|t| content_tokens(t).into_iter().collect())
+        .collect();
+    let mut scored: Vec<(usize, usize)> = token_sets
+        .iter()
+        .enumerate()
+        .map(|(i, set)| {
+            let overlap = token_sets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| set.intersection(other).count())
+                .sum();
+            (i, overlap)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut keep: Vec<usize> = scored.into_iter().take(n.max(1)).map(|(i, _)| i).collect();
+    keep.sort_unstable();
+    let mut seen = HashSet::new();
+    keep.into_iter()
+        .map(|i| texts[i].clone())
+        .filter(|t| seen.insert(t.clone()))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Detect communities among `node_ids` in `graph` and summarize each one.
+///
+/// A node maps to a memory record through its `record_id` property or a
+/// `mem-<uuid>` label; those records become the summary's sources and evidence.
+/// Communities with no resolvable records are skipped.
+pub fn summarize_communities<B: MemoryBackend, G: GraphDatabase>(
+    store: &MemoryStore<B>,
+    graph: &SymbolicStore<G>,
+    node_ids: &[Uuid],
+    summarizer: &Summarizer,
+    actor: &str,
+) -> Vec<(Vec<Uuid>, MemoryRecord)> {
+    crate::consolidation::detect_communities(graph, node_ids)
+        .into_iter()
+        .filter_map(|community| {
+            let sources: Vec<&MemoryRecord> = community
+                .iter()
+                .filter_map(|nid| graph.get_node(*nid))
+                .filter_map(|node| {
+                    node.properties
+                        .get("record_id")
+                        .map(|s| s.as_str())
+                        .or_else(|| node.label.strip_prefix("mem-"))
+                        .and_then(|s| Uuid::parse_str(s).ok())
+                })
+                .filter_map(|rid| store.find_by_id(rid))
+                .collect();
+            if sources.is_empty() {
+                return None;
+            }
+            Some((community, summarizer.summary_record(actor, &sources)))
+        })
+        .collect()
+}
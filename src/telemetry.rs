@@ -0,0 +1,462 @@
+//! Telemetry — Prometheus metrics registry and OTLP span export.
+//!
+//! Chain-of-thought: MonitoringService only knows request rate, cache hits and
+//! FSM transitions, and each subsystem timed itself (or not at all). This module
+//! is the single sink: subsystems call `METRICS.observe(..)` / `METRICS.inc(..)`
+//! and the web server renders everything in Prometheus text format on `/metrics`.
+//!
+//! Tracing: `CognitiveHandle::transact` and friends open `tracing` spans. The
+//! `OtlpLayer` collects finished spans into a shared buffer and `OtlpExporter`
+//! ships them as OTLP/HTTP JSON to `<endpoint>/v1/traces`. No collector = no-op.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+// ─── Metric names ────────────────────────────────────────────────────────────
+
+pub const MEMORY_ADD_SECONDS: &str = "hipcortex_memory_add_duration_seconds";
+pub const MEMORY_SEARCH_SECONDS: &str = "hipcortex_memory_search_duration_seconds";
+pub const TRANSACT_SECONDS: &str = "hipcortex_transact_duration_seconds";
+pub const COHERENCE_GATE_TOTAL: &str = "hipcortex_coherence_gate_total";
+pub const CONSOLIDATION_RUNS_TOTAL: &str = "hipcortex_consolidation_runs_total";
+pub const FORKS_TOTAL: &str = "hipcortex_forks_total";
+pub const LLM_CALL_SECONDS: &str = "hipcortex_llm_call_duration_seconds";
+pub const LLM_TOKENS_TOTAL: &str = "hipcortex_llm_tokens_total";
+pub const MAINTENANCE_RUNS_TOTAL: &str = "hipcortex_maintenance_runs_total";
+pub const MAINTENANCE_JOB_SECONDS: &str = "hipcortex_maintenance_job_duration_seconds";
+
+/// (name, help) pairs rendered as `# HELP` lines.
+const HELP: &[(&str, &str)] = &[
+    (MEMORY_ADD_SECONDS, "MemoryStore::add latency"),
+    (MEMORY_SEARCH_SECONDS, "MemoryStore::search_semantic latency"),
+    (TRANSACT_SECONDS, "CognitiveHandle::transact latency by delta"),
+    (COHERENCE_GATE_TOTAL, "Coherence gate decisions by outcome"),
+    (CONSOLIDATION_RUNS_TOTAL, "Consolidation runs by kind"),
+    (FORKS_TOTAL, "Simulation forks created"),
+    (LLM_CALL_SECONDS, "LLM call latency by provider"),
+    (LLM_TOKENS_TOTAL, "LLM tokens by provider and kind (prompt|completion)"),
+    (MAINTENANCE_RUNS_TOTAL, "Maintenance job runs by job and outcome"),
+    (MAINTENANCE_JOB_SECONDS, "Maintenance job latency by job"),
+];
+
+/// Latency buckets in seconds (upper bounds, `+Inf` implied).
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+// ─── Histogram ───────────────────────────────────────────────────────────────
+
+/// Cumulative-bucket histogram in the Prometheus sense.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        for (i, b) in self.bounds.iter().enumerate() {
+            if value <= *b {
+                self.counts[i] += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+}
+
+// ─── Registry ────────────────────────────────────────────────────────────────
+
+type Labels = Vec<(String, String)>;
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+    labels
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn render_labels(labels: &Labels, extra: Option<(&str, String)>) -> String {
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, v.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+    if let Some((k, v)) = extra {
+        parts.push(format!("{}=\"{}\"", k, v));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+/// Thread-safe counter + histogram registry.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    counters: Mutex<BTreeMap<String, BTreeMap<Labels, f64>>>,
+    histograms: Mutex<BTreeMap<String, BTreeMap<Labels, Histogram>>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `by` to the counter `name{labels}`.
+    pub fn inc(&self, name: &str, labels: &[(&str, &str)], by: f64) {
+        if let Ok(mut c) = self.counters.lock() {
+            *c.entry(name.to_string())
+                .or_default()
+                .entry(to_labels(labels))
+                .or_insert(0.0) += by;
+        }
+    }
+
+    /// Record one observation into the histogram `name{labels}`.
+    pub fn observe(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        if let Ok(mut h) = self.histograms.lock() {
+            h.entry(name.to_string())
+                .or_default()
+                .entry(to_labels(labels))
+                .or_insert_with(|| Histogram::new(DEFAULT_BUCKETS))
+                .observe(value);
+        }
+    }
+
+    /// Record an elapsed duration (seconds) into the histogram `name{labels}`.
+    pub fn observe_duration(&self, name: &str, labels: &[(&str, &str)], elapsed: Duration) {
+        self.observe(name, labels, elapsed.as_secs_f64());
+    }
+
+    /// Current counter value (0.0 if never incremented).
+    pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> f64 {
+        self.counters
+            .lock()
+            .ok()
+            .and_then(|c| c.get(name).and_then(|m| m.get(&to_labels(labels)).copied()))
+            .unwrap_or(0.0)
+    }
+
+    /// Snapshot of a histogram, if it has any observations.
+    pub fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Option<Histogram> {
+        self.histograms
+            .lock()
+            .ok()
+            .and_then(|h| h.get(name).and_then(|m| m.get(&to_labels(labels)).cloned()))
+    }
+
+    /// Render all metrics in Prometheus text exposition format (0.0.4).
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let help = |name: &str| HELP.iter().find(|(n, _)| *n == name).map(|(_, h)| *h);
+
+        if let Ok(counters) = self.counters.lock() {
+            for (name, series) in counters.iter() {
+                if let Some(h) = help(name) {
+                    out.push_str(&format!("# HELP {} {}\n", name, h));
+                }
+                out.push_str(&format!("# TYPE {} counter\n", name));
+                for (labels, v) in series {
+                    out.push_str(&format!("{}{} {}\n", name, render_labels(labels, None), v));
+                }
+            }
+        }
+        if let Ok(hists) = self.histograms.lock() {
+            for (name, series) in hists.iter() {
+                if let Some(h) = help(name) {
+                    out.push_str(&format!("# HELP {} {}\n", name, h));
+                }
+                out.push_str(&format!("# TYPE {} histogram\n", name));
+                for (labels, hist) in series {
+                    for (b, c) in hist.bounds.iter().zip(&hist.counts) {
+                        out.push_str(&format!(
+                            "{}_bucket{} {}\n",
+                            name,
+                            render_labels(labels, Some(("le", b.to_string()))),
+                            c
+                        ));
+                    }
+                    out.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        name,
+                        render_labels(labels, Some(("le", "+Inf".to_string()))),
+                        hist.count
+                    ));
+                    out.push_str(&format!("{}_sum{} {}\n", name, render_labels(labels, None), hist.sum));
+                    out.push_str(&format!("{}_count{} {}\n", name, render_labels(labels, None), hist.count));
+                }
+            }
+        }
+        out
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide registry. Subsystems record here; `/metrics` renders it.
+    pub static ref METRICS: MetricsRegistry = MetricsRegistry::new();
+}
+
+/// Record one LLM call: latency plus prompt/completion token counts.
+pub fn record_llm_call(provider: &str, elapsed: Duration, prompt_tokens: u64, completion_tokens: u64) {
+    METRICS.observe_duration(LLM_CALL_SECONDS, &[("provider", provider)], elapsed);
+    METRICS.inc(
+        LLM_TOKENS_TOTAL,
+        &[("provider", provider), ("kind", "prompt")],
+        prompt_tokens as f64,
+    );
+    METRICS.inc(
+        LLM_TOKENS_TOTAL,
+        &[("provider", provider), ("kind", "completion")],
+        completion_tokens as f64,
+    );
+}
+
+/// Rough token estimate used when a provider does not report usage: 1 token ≈ 4 chars.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+// ─── OTLP span export ────────────────────────────────────────────────────────
+
+/// A closed span ready for export.
+#[derive(Debug, Clone)]
+pub struct FinishedSpan {
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub start_unix_nanos: u128,
+    pub end_unix_nanos: u128,
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Per-span state stored in the registry extensions while the span is open.
+struct SpanState {
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+    start_unix_nanos: u128,
+    attributes: Vec<(String, String)>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+fn now_unix_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+fn random_hex(bytes: usize) -> String {
+    use rand::RngCore;
+    let mut buf = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buf);
+    hex::encode(buf)
+}
+
+/// `tracing_subscriber` layer that buffers closed spans for OTLP export.
+pub struct OtlpLayer {
+    buffer: Arc<Mutex<Vec<FinishedSpan>>>,
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let parent = span.parent().and_then(|p| {
+            p.extensions()
+                .get::<SpanState>()
+                .map(|s| (s.trace_id.clone(), s.span_id.clone()))
+        });
+        let mut attributes = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut attributes));
+        let (trace_id, parent_span_id) = match parent {
+            Some((trace, parent_id)) => (trace, Some(parent_id)),
+            None => (random_hex(16), None),
+        };
+        span.extensions_mut().insert(SpanState {
+            trace_id,
+            span_id: random_hex(8),
+            parent_span_id,
+            start_unix_nanos: now_unix_nanos(),
+            attributes,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(state) = span.extensions_mut().get_mut::<SpanState>() {
+                values.record(&mut FieldVisitor(&mut state.attributes));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(state) = span.extensions_mut().remove::<SpanState>() else { return };
+        let finished = FinishedSpan {
+            trace_id: state.trace_id,
+            span_id: state.span_id,
+            parent_span_id: state.parent_span_id,
+            name: span.name().to_string(),
+            start_unix_nanos: state.start_unix_nanos,
+            end_unix_nanos: now_unix_nanos(),
+            attributes: state.attributes,
+        };
+        if let Ok(mut buf) = self.buffer.lock() {
+            buf.push(finished);
+        }
+    }
+}
+
+/// Ships buffered spans to an OTLP/HTTP collector as JSON.
+#[derive(Clone)]
+pub struct OtlpExporter {
+    endpoint: String,
+    service_name: String,
+    buffer: Arc<Mutex<Vec<FinishedSpan>>>,
+}
+
+impl OtlpExporter {
+    /// Create an exporter for `endpoint` (e.g. `http://localhost:4318`) and the
+    /// layer that feeds it. Install the layer on a subscriber of your choosing.
+    pub fn new(endpoint: impl Into<String>, service_name: impl Into<String>) -> (Self, OtlpLayer) {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        (
+            Self {
+                endpoint: endpoint.into().trim_end_matches('/').to_string(),
+                service_name: service_name.into(),
+                buffer: buffer.clone(),
+            },
+            OtlpLayer { buffer },
+        )
+    }
+
+    /// Number of spans waiting to be exported.
+    pub fn pending(&self) -> usize {
+        self.buffer.lock().map(|b| b.len()).unwrap_or(0)
+    }
+
+    /// Build the OTLP `ExportTraceServiceRequest` JSON body for `spans`.
+    pub fn encode(&self, spans: &[FinishedSpan]) -> serde_json::Value {
+        let spans: Vec<serde_json::Value> = spans
+            .iter()
+            .map(|s| {
+                let attrs: Vec<serde_json::Value> = s
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| serde_json::json!({ "key": k, "value": { "stringValue": v } }))
+                    .collect();
+                let mut span = serde_json::json!({
+                    "traceId": s.trace_id,
+                    "spanId": s.span_id,
+                    "name": s.name,
+                    "kind": 1,
+                    "startTimeUnixNano": s.start_unix_nanos.to_string(),
+                    "endTimeUnixNano": s.end_unix_nanos.to_string(),
+                    "attributes": attrs,
+                });
+                if let Some(p) = &s.parent_span_id {
+                    span["parentSpanId"] = serde_json::json!(p);
+                }
+                span
+            })
+            .collect();
+        serde_json::json!({
+            "resourceSpans": [{
+                "resource": { "attributes": [
+                    { "key": "service.name", "value": { "stringValue": self.service_name } }
+                ]},
+                "scopeSpans": [{
+                    "scope": { "name": "hipcortex", "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }]
+            }]
+        })
+    }
+
+    /// Drain the buffer and POST it to `<endpoint>/v1/traces`.
+    /// Returns the number of spans exported. On failure the spans are dropped.
+    pub fn flush(&self) -> anyhow::Result<usize> {
+        let spans: Vec<FinishedSpan> = match self.buffer.lock() {
+            Ok(mut b) => std::mem::take(&mut *b),
+            Err(_) => return Err(anyhow::anyhow!("span buffer poisoned")),
+        };
+        if spans.is_empty() {
+            return Ok(0);
+        }
+        let body = self.encode(&spans);
+        let resp = reqwest::blocking::Client::new()
+            .post(format!("{}/v1/traces", self.endpoint))
+            .json(&body)
+            .send()?;
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!("collector returned {}", resp.status()));
+        }
+        Ok(spans.len())
+    }
+
+    /// Flush on a background thread every `interval`. Errors go to stderr.
+    pub fn spawn_flush_loop(&self, interval: Duration) -> std::thread::JoinHandle<()> {
+        let exporter = self.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            if let Err(e) = exporter.flush() {
+                eprintln!("[telemetry] OTLP export error: {e}");
+            }
+        })
+    }
+}
+
+/// Install a global subscriber exporting spans to `OTEL_EXPORTER_OTLP_ENDPOINT`.
+/// Returns `None` (and installs nothing) when the variable is unset or a global
+/// subscriber already exists.
+pub fn init_from_env(service_name: &str) -> Option<OtlpExporter> {
+    use tracing_subscriber::layer::SubscriberExt;
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+    let (exporter, layer) = OtlpExporter::new(endpoint, service_name);
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::set_global_default(subscriber).ok()?;
+    exporter.spawn_flush_loop(Duration::from_secs(5));
+    Some(exporter)
+}
@@ -10,6 +10,8 @@ use std::{
 };
 use uuid::Uuid;
 
+use crate::memory_record::MemoryRecord;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TxKind {
     MemoryAdd,
@@ -26,6 +28,22 @@ pub enum TxKind {
     ForgetActor,
     ArchiveRecord,
     WorkspaceOp,
+    /// Point-in-time recovery: a full store snapshot was written at this tx.
+    Checkpoint,
+    /// Point-in-time recovery: the store was rewound to an earlier state.
+    Restore,
+}
+
+/// Post-image of one memory mutation, carried by a `TxEntry` so the log can be
+/// replayed on top of a checkpoint (see `pitr`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecordChange {
+    /// Record inserted or replaced by id.
+    Upsert { record: Box<MemoryRecord> },
+    Delete { id: Uuid },
+    /// Every record dropped.
+    Clear,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +53,9 @@ pub struct TxEntry {
     pub kind: TxKind,
     pub record_ids: Vec<Uuid>,
     pub actor: String,
+    /// Record post-images; empty for entries written without a change journal.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<RecordChange>,
 }
 
 pub struct TxLog {
@@ -68,6 +89,17 @@ impl TxLog {
 
     /// Append one TxEntry. Returns assigned tx_id. Infallible from caller — write errors go to stderr.
     pub fn append(&self, kind: TxKind, record_ids: Vec<Uuid>, actor: &str) -> u64 {
+        self.append_with_changes(kind, record_ids, actor, Vec::new())
+    }
+
+    /// `append` plus the record changes this tx made, making the entry replayable.
+    pub fn append_with_changes(
+        &self,
+        kind: TxKind,
+        record_ids: Vec<Uuid>,
+        actor: &str,
+        changes: Vec<RecordChange>,
+    ) -> u64 {
         let tx_id = self.counter.fetch_add(1, Ordering::SeqCst);
         let entry = TxEntry {
             tx_id,
@@ -78,6 +110,7 @@ impl TxLog {
             kind,
             record_ids,
             actor: actor.to_string(),
+            changes,
         };
         match serde_json::to_string(&entry) {
             Ok(line) => {
@@ -114,6 +147,11 @@ impl TxLog {
         Ok(result)
     }
 
+    /// Path of the JSONL log file.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
     /// Last assigned tx_id (0 if nothing appended yet).
     pub fn current_tx(&self) -> u64 {
         self.counter.load(Ordering::SeqCst).saturating_sub(1)
@@ -3,6 +3,7 @@ use image::{DynamicImage, GenericImageView};
 use nalgebra::DMatrix;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 pub struct VisionEncoder;
@@ -166,6 +167,149 @@ impl VisionEncoder {
     }
 }
 
+/// Resize, crop and normalization matching how an image model was trained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImagePreprocess {
+    /// Side of the square model input.
+    pub size: u32,
+    pub mean: [f32; 3],
+    pub std: [f32; 3],
+}
+
+impl ImagePreprocess {
+    /// OpenAI CLIP statistics at 224x224.
+    pub fn clip() -> Self {
+        Self {
+            size: 224,
+            mean: [0.481_454_7, 0.457_827_5, 0.408_210_7],
+            std: [0.268_629_5, 0.261_302_6, 0.275_777_1],
+        }
+    }
+
+    /// ImageNet statistics at 224x224 (ResNet and most torchvision models).
+    pub fn imagenet() -> Self {
+        Self {
+            size: 224,
+            mean: [0.485, 0.456, 0.406],
+            std: [0.229, 0.224, 0.225],
+        }
+    }
+
+    /// One image as CHW planes: shortest side resized to `size`, center
+    /// crop, then per-channel `(x / 255 - mean) / std`.
+    pub fn planes(&self, image: &DynamicImage) -> Vec<f32> {
+        let size = self.size.max(1);
+        let (w, h) = image.dimensions();
+        let scale = size as f32 / w.min(h).max(1) as f32;
+        let (rw, rh) = (
+            ((w as f32 * scale).round() as u32).max(size),
+            ((h as f32 * scale).round() as u32).max(size),
+        );
+        let resized = image.resize_exact(rw, rh, image::imageops::FilterType::CatmullRom);
+        let cropped = resized
+            .crop_imm((rw - size) / 2, (rh - size) / 2, size, size)
+            .to_rgb8();
+        let area = (size * size) as usize;
+        let mut out = vec![0.0f32; 3 * area];
+        for (i, p) in cropped.pixels().enumerate() {
+            for c in 0..3 {
+                out[c * area + i] = (p[c] as f32 / 255.0 - self.mean[c]) / self.std[c];
+            }
+        }
+        out
+    }
+
+    /// A batch as one NCHW buffer of `images.len() * 3 * size * size` floats.
+    pub fn batch(&self, images: &[DynamicImage]) -> Vec<f32> {
+        #[cfg(feature = "parallel")]
+        let planes: Vec<Vec<f32>> = images.par_iter().map(|img| self.planes(img)).collect();
+        #[cfg(not(feature = "parallel"))]
+        let planes: Vec<Vec<f32>> = images.iter().map(|img| self.planes(img)).collect();
+        planes.concat()
+    }
+}
+
+/// Learned image embeddings from an ONNX model (a CLIP image tower, a
+/// ResNet with its classifier removed, ...) run on the CPU with tract.
+///
+/// The model's first input must take NCHW float pixels
+/// (`[N, 3, size, size]`, the batch axis may be symbolic) and its first
+/// output the `[N, D]` embedding; extra trailing unit axes are flattened.
+#[cfg(feature = "onnx")]
+pub struct OnnxImageEncoder {
+    plan: tract_onnx::prelude::TypedRunnableModel<tract_onnx::prelude::TypedModel>,
+    preprocess: ImagePreprocess,
+    space: String,
+    max_batch: usize,
+}
+
+#[cfg(feature = "onnx")]
+impl OnnxImageEncoder {
+    /// Load the model at `path`. `space` names the embedding space; give the
+    /// matching text tower the same name to search images by text.
+    pub fn load<P: AsRef<Path>>(path: P, preprocess: ImagePreprocess, space: &str) -> Result<Self> {
+        use tract_onnx::prelude::*;
+        let mut model = tract_onnx::onnx().model_for_path(path)?;
+        let batch = model.symbol_table.sym("N");
+        let side = preprocess.size as usize;
+        model = model.with_input_fact(
+            0,
+            InferenceFact::dt_shape(
+                f32::datum_type(),
+                tvec!(batch.to_dim(), 3.to_dim(), side.to_dim(), side.to_dim()),
+            ),
+        )?;
+        Ok(Self {
+            plan: model.into_optimized()?.into_runnable()?,
+            preprocess,
+            space: space.to_string(),
+            max_batch: 16,
+        })
+    }
+
+    /// Largest batch sent to the model at once (default 16).
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch.max(1);
+        self
+    }
+
+    pub fn preprocess(&self) -> &ImagePreprocess {
+        &self.preprocess
+    }
+
+    /// L2-normalized embeddings for `images`, in order.
+    pub fn encode_images(&self, images: &[DynamicImage]) -> Result<Vec<Vec<f32>>> {
+        use tract_onnx::prelude::*;
+        let side = self.preprocess.size as usize;
+        let mut out = Vec::with_capacity(images.len());
+        for chunk in images.chunks(self.max_batch) {
+            let input =
+                Tensor::from_shape(&[chunk.len(), 3, side, side], &self.preprocess.batch(chunk))?;
+            let result = self.plan.run(tvec!(input.into()))?;
+            let data = result[0].as_slice::<f32>()?;
+            let dim = data.len() / chunk.len();
+            out.extend(data.chunks(dim).map(VisionEncoder::l2_normalize));
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl crate::perception_adapter::ImageEncoder for OnnxImageEncoder {
+    fn name(&self) -> &str {
+        "onnx-image"
+    }
+
+    fn space(&self) -> &str {
+        &self.space
+    }
+
+    fn encode(&self, bytes: &[u8]) -> Result<Vec<f32>> {
+        let img = image::load_from_memory(bytes)?;
+        Ok(self.encode_images(&[img])?.remove(0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
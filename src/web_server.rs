@@ -1,13 +1,19 @@
 #[cfg(feature = "web-server")]
 use crate::archive_store::ArchiveStore;
 #[cfg(feature = "web-server")]
+use crate::async_memory_store::{AsyncMemoryStore, RecordPatch};
+#[cfg(feature = "web-server")]
 use crate::aureus_bridge::AureusBridge;
 #[cfg(feature = "web-server")]
 use crate::coherence::CoherenceChecker;
 #[cfg(feature = "web-server")]
+use crate::concurrent_memory_store::ConcurrentMemoryStore;
+#[cfg(feature = "web-server")]
 use crate::consolidation::{compute_pressure, consolidate, ConsolidationConfig};
 #[cfg(feature = "web-server")]
-use crate::memory_record::{MemoryRecord, MemoryType};
+use crate::embedding_provider::{AsyncEmbeddingProvider, RemoteEmbeddingProvider};
+#[cfg(feature = "web-server")]
+use crate::memory_record::{MemoryRecord, MemoryType, Priority, RecordStatus};
 #[cfg(feature = "web-server")]
 use crate::memory_store::MemoryStore;
 #[cfg(feature = "web-server")]
@@ -100,11 +106,6 @@ impl ApiTier {
 }
 
 /// Parsed from HIPCORTEX_API_KEYS env var: "key1:free,key2:pro,key3:team"
-#[cfg(feature = "web-server")]
-fn default_priority_str() -> String {
-    "normal".to_string()
-}
-
 #[cfg(feature = "web-server")]
 fn load_api_keys() -> HashMap<String, ApiTier> {
     std::env::var("HIPCORTEX_API_KEYS")
@@ -123,6 +124,63 @@ fn load_api_keys() -> HashMap<String, ApiTier> {
         .collect()
 }
 
+/// True when the request carries the admin credential: `X-Admin-Key` equal to
+/// HIPCORTEX_ADMIN_KEY. Unset means no caller is an admin — admin routes stay
+/// closed even in open / self-hosted mode, since tier keys do not cover them.
+#[cfg(feature = "web-server")]
+fn is_admin(headers: &HeaderMap) -> bool {
+    let expected = std::env::var("HIPCORTEX_ADMIN_KEY").unwrap_or_default();
+    let provided = headers
+        .get("X-Admin-Key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    !expected.is_empty()
+        && expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+#[cfg(feature = "web-server")]
+fn admin_forbidden() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(serde_json::json!({
+            "error": "admin credential required (X-Admin-Key matching HIPCORTEX_ADMIN_KEY)"
+        })),
+    )
+}
+
+/// Status for a failed store write: 403 when the safety screen refused the
+/// record (`WriteRefused`), `otherwise` for anything else.
+#[cfg(feature = "web-server")]
+fn write_status(e: &anyhow::Error, otherwise: StatusCode) -> StatusCode {
+    if e.is::<crate::safety_guardrail::WriteRefused>() {
+        StatusCode::FORBIDDEN
+    } else {
+        otherwise
+    }
+}
+
+/// Reject a cognitive delta whose record carries an unknown `status`. Record
+/// deserialization maps unknown statuses to `Active` for legacy files, so a
+/// typo in API input would otherwise make the record live.
+#[cfg(feature = "web-server")]
+fn check_delta_status(delta: &serde_json::Value) -> Result<(), String> {
+    let record = match delta.get("type").and_then(|t| t.as_str()) {
+        Some("AddMemory") => Some(delta),
+        Some("Consolidate") => delta.get("summary"),
+        _ => None,
+    };
+    match record.and_then(|r| r.get("status")) {
+        Some(serde_json::Value::String(s)) => s.parse::<RecordStatus>().map(|_| ()),
+        Some(serde_json::Value::Null) | None => Ok(()),
+        Some(other) => Err(format!("record status must be a string, got {other}")),
+    }
+}
+
 #[cfg(feature = "web-server")]
 #[derive(Serialize, Deserialize)]
 pub struct TierResponse {
@@ -143,7 +201,7 @@ pub struct TierLimits {
 /// Each handler closure Arc-clones only the fields it needs.
 #[cfg(feature = "web-server")]
 pub struct AppState<B: MemoryBackend + Send + Sync + 'static> {
-    pub memory_store: Arc<Mutex<MemoryStore<B>>>,
+    pub memory_store: Arc<ConcurrentMemoryStore<B>>,
     pub symbolic_store: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
     /// Dirichlet-Multinomial transitions + Kalman entity tracking + causal DAG
     pub world_model: Arc<RwLock<WorldModelEnhanced>>,
@@ -205,6 +263,10 @@ pub struct SearchMemoryRequest {
     /// If true, include quarantined records in results. Default false.
     #[serde(default)]
     pub include_quarantined: Option<bool>,
+    /// Search the store as it was at this tx id or RFC 3339 timestamp
+    /// (replayed from the tx log). Default: the live store.
+    #[serde(default)]
+    pub as_of: Option<String>,
 }
 
 #[cfg(feature = "web-server")]
@@ -227,6 +289,25 @@ pub struct SearchFlatResponse {
 pub struct SearchMemoryResponse {
     results: Vec<SearchResult>,
     total: usize,
+    /// High-risk injection hits left out of `results`. Searching does not
+    /// quarantine them; `POST /memory/injections/quarantine` or the
+    /// `injection_sweep` maintenance job does.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    withheld: Vec<crate::injection_defense::InjectionAssessment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[cfg(feature = "web-server")]
+impl SearchMemoryResponse {
+    fn failed(error: impl Into<String>) -> Self {
+        Self {
+            results: vec![],
+            total: 0,
+            withheld: vec![],
+            error: Some(error.into()),
+        }
+    }
 }
 
 #[cfg(feature = "web-server")]
@@ -234,6 +315,9 @@ pub struct SearchMemoryResponse {
 pub struct SearchResult {
     score: f64,
     record: MemoryRecordResponse,
+    /// Set when the record scores as a possible prompt injection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    injection: Option<crate::injection_defense::InjectionAssessment>,
 }
 
 /// POST /memory/bulk — add multiple records in one HTTP request
@@ -391,6 +475,9 @@ pub struct IngestResponse {
     pub target: String,
     pub working_memory: bool,
     pub warning: Option<serde_json::Value>,
+    /// Injection assessment, present when the text looked like a prompt injection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub injection: Option<serde_json::Value>,
 }
 
 /// POST /memory/embed — auto-generate embedding then store memory.
@@ -429,8 +516,8 @@ pub struct AddMemoryRequest {
     #[serde(default)]
     tags: Vec<String>,
     /// Priority: "pinned"|"high"|"normal"|"low". Pinned bypass decay in search. Default "normal".
-    #[serde(default = "default_priority_str")]
-    priority: String,
+    #[serde(default)]
+    priority: Priority,
 }
 
 #[cfg(feature = "web-server")]
@@ -440,6 +527,9 @@ pub struct AddMemoryResponse {
     record_id: Option<String>,
     error: Option<String>,
     warning: Option<serde_json::Value>, // possible contradiction detected
+    /// Guardrail outcome when the record was redacted, flagged or quarantined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    safety: Option<serde_json::Value>,
 }
 
 #[cfg(feature = "web-server")]
@@ -454,7 +544,10 @@ pub struct QueryMemoryParams {
     limit: Option<usize>,
     tags: Option<String>, // comma-separated tags filter e.g. "bug,architecture"
     priority: Option<String>, // filter by priority
-    as_of: Option<String>, // ISO 8601 timestamp — return records with timestamp <= as_of
+    /// Tx id or ISO 8601 timestamp. A tx id (or, with a tx log, a timestamp)
+    /// replays the store to that point; a timestamp also keeps only records
+    /// with timestamp <= as_of.
+    as_of: Option<String>,
     /// If "true", include quarantined records. Default: exclude quarantine.
     include_quarantined: Option<String>,
     /// If "true", include records whose `expires_at` is in the past.
@@ -468,6 +561,19 @@ pub struct QueryMemoryParams {
 pub struct QueryMemoryResponse {
     records: Vec<MemoryRecordResponse>,
     total: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[cfg(feature = "web-server")]
+impl QueryMemoryResponse {
+    fn failed(error: impl Into<String>) -> Self {
+        Self {
+            records: vec![],
+            total: 0,
+            error: Some(error.into()),
+        }
+    }
 }
 
 #[cfg(feature = "web-server")]
@@ -486,17 +592,55 @@ pub struct MemoryRecordResponse {
     /// Who or what wrote this memory (e.g. "user-input", "claude-3-7").
     pub source: Option<String>,
     /// "pinned" | "high" | "normal" | "low". Pinned bypasses decay.
-    pub priority: String,
+    pub priority: Priority,
     /// Domain tags for RAG filtering (e.g. ["database", "auth"]).
     pub tags: Vec<String>,
     /// Update counter. 0 = original. Increments on PATCH /memory/update/:id.
     pub version: u32,
     /// "active" | "quarantine" | "archived".
-    pub status: String,
+    pub status: RecordStatus,
     /// Unix timestamp when record expires. None = never expires.
     pub expires_at: Option<i64>,
 }
 
+#[cfg(feature = "web-server")]
+impl From<&MemoryRecord> for MemoryRecordResponse {
+    fn from(r: &MemoryRecord) -> Self {
+        Self {
+            id: r.id.to_string(),
+            record_type: format!("{:?}", r.record_type),
+            timestamp: r.timestamp.to_rfc3339(),
+            actor: r.actor.clone(),
+            action: r.action.clone(),
+            target: r.target.clone(),
+            metadata: r.metadata.clone(),
+            integrity: r.integrity.clone(),
+            confidence: r.confidence,
+            source: r.source.clone(),
+            priority: r.priority,
+            tags: r.tags.clone(),
+            version: r.version,
+            status: r.status,
+            expires_at: r.expires_at,
+        }
+    }
+}
+
+/// Query params for point-in-time reads (`GET /memory/:id?as_of=`).
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+pub struct AsOfParams {
+    as_of: Option<String>,
+}
+
+/// Body of `POST /v1/state/restore`: exactly one of `tx` or `at` (RFC 3339).
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+pub struct RestoreRequest {
+    tx: Option<u64>,
+    at: Option<String>,
+}
+
 #[cfg(feature = "web-server")]
 pub async fn run(addr: SocketAddr) {
     let store = Arc::new(Mutex::new(SymbolicStore::new()));
@@ -506,7 +650,7 @@ pub async fn run(addr: SocketAddr) {
 #[cfg(feature = "web-server")]
 pub async fn run_with_memory<B: MemoryBackend + Send + Sync + 'static>(
     addr: SocketAddr,
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
 ) {
     let coherence = Arc::new(CoherenceChecker::new());
     let calibration = Arc::new(CalibrationTracker::new());
@@ -549,6 +693,21 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     addr: SocketAddr,
     state: AppState<B>,
 ) {
+    // Safety policies and the redaction vault come from the environment
+    // (HIPCORTEX_SAFETY_POLICY, HIPCORTEX_VAULT_KEY, HIPCORTEX_VAULT_PATH).
+    // The vault defaults to `memory-vault.jsonl` beside the store in the data
+    // dir, the file the CLI picks for `memory.jsonl`, so originals survive a
+    // restart.
+    let data_dir = std::env::var("DATA_DIR")
+        .or_else(|_| std::env::var("HIPCORTEX_STORAGE"))
+        .unwrap_or_else(|_| ".".to_string());
+    let vault_path = std::path::Path::new(&data_dir).join("memory-vault.jsonl");
+    if let Ok(mut guard) = crate::safety_guardrail::SAFETY_GUARDRAIL.lock() {
+        if let Err(e) = guard.configure_from_env(Some(&vault_path)) {
+            eprintln!("[safety] invalid safety configuration ({e}); keeping defaults");
+        }
+    }
+
     // ── Unpack state into locals so closures can capture by value ─────────
     let symbolic_store = state.symbolic_store.clone();
     let memory_store = state.memory_store.clone();
@@ -564,6 +723,24 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     let forks = state.forks.clone();
     let twins = state.twins.clone();
 
+    // Every write path (add, ingest, embed, import, conversations, bundles)
+    // goes through the store's safety screen.
+    if let Ok(mut ms) = memory_store.lock() {
+        ms.set_write_screening(true);
+    }
+
+    // With a tx log, every memory mutation is journaled onto a tx so the store
+    // can be replayed to any point (see `pitr`). If the journal is not on yet,
+    // starting it checkpoints the store so writes made before it are covered.
+    if let Some(log) = &tx_log_arc {
+        if let Ok(mut ms) = memory_store.lock() {
+            if let Err(e) = crate::pitr::start_journal(&mut ms, log) {
+                eprintln!("[pitr] journal start checkpoint failed ({e}); journaling without one");
+                ms.enable_change_journal();
+            }
+        }
+    }
+
     // ── Symbolic store routes ─────────────────────────────────────────────
     let graph_route = {
         let store = symbolic_store.clone();
@@ -588,7 +765,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
 
     // ── Memory store routes ───────────────────────────────────────────────
     let add_memory_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         let wm = world_model.clone();
         let arc = archive_store.clone();
         let sym = symbolic_store.clone();
@@ -600,7 +777,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let bulk_add_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Json(req): Json<BulkAddRequest>| async move {
             handle_bulk_add(store, Json(req)).await
         })
@@ -608,22 +785,24 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
 
     let query_memory_route = {
         let store = memory_store.clone();
+        let txl = tx_log_arc.clone();
         get(move |Query(params): Query<QueryMemoryParams>| async move {
-            handle_query_memory(store, params).await
+            handle_query_memory(store, txl, params).await
         })
     };
 
     // Semantic / keyword search: POST /memory/search
     let search_route = {
         let store = memory_store.clone();
+        let txl = tx_log_arc.clone();
         post(move |Json(req): Json<SearchMemoryRequest>| async move {
-            handle_search_memory(store, Json(req)).await
+            handle_search_memory(store, txl, Json(req)).await
         })
     };
 
     // GDPR forget: DELETE /memory/forget/:actor
     let forget_route = {
-        let ms = memory_store.clone();
+        let ms = AsyncMemoryStore::from(memory_store.clone());
         let ss = symbolic_store.clone();
         delete(
             move |Path(actor): Path<String>| async move { handle_forget_actor(ms, ss, actor).await },
@@ -632,7 +811,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
 
     // Embed and add: POST /memory/embed
     let embed_add_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Json(req): Json<EmbedAndAddRequest>| async move {
             handle_embed_and_add(store, Json(req)).await
         })
@@ -671,7 +850,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
 
     // PATCH /memory/update/:id — versioned in-place update
     let update_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         patch(
             move |Path(id): Path<String>, Json(req): Json<UpdateMemoryRequest>| async move {
                 handle_update_memory(store, id, Json(req)).await
@@ -688,14 +867,14 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let audit_verify_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         get(move || {
             let s = store.clone();
             async move { handle_audit_verify(s).await }
         })
     };
     let audit_export_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         get(move || {
             let s = store.clone();
             async move { handle_audit_export(s).await }
@@ -725,7 +904,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
         })
     };
     let consolidate_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Query(params): Query<ConsolidateParams>| async move {
             handle_consolidate(store, Query(params)).await
         })
@@ -755,7 +934,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let ingest_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         let wm = world_model.clone();
         post(move |Json(req): Json<IngestRequest>| async move {
             handle_ingest(store, wm, Json(req)).await
@@ -763,28 +942,28 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let quarantine_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_quarantine_memory(s, id).await }
         })
     };
     let restore_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_restore_memory(s, id).await }
         })
     };
     let corroborate_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_corroborate(s, id).await }
         })
     };
     let contradict_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_contradict(s, id).await }
@@ -812,9 +991,16 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
 
     let delete_memory_route = {
         let ms = memory_store.clone();
-        delete(
-            move |Path(id): Path<String>| async move { handle_delete_memory(ms, Path(id)).await },
-        )
+        let txl = tx_log_arc.clone();
+        get(move |Path(id): Path<String>, Query(p): Query<AsOfParams>| {
+            let ms = ms.clone();
+            let txl = txl.clone();
+            async move { handle_get_memory(ms, txl, id, p).await }
+        })
+        .delete({
+            let ms = AsyncMemoryStore::from(memory_store.clone());
+            move |Path(id): Path<String>| async move { handle_delete_memory(ms, Path(id)).await }
+        })
     };
 
     let metrics_route = {
@@ -895,7 +1081,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
         })
     };
     let memory_reflect_route = {
-        let ms = memory_store.clone();
+        let ms = AsyncMemoryStore::from(memory_store.clone());
         let au = aureus.clone();
         post(move |Json(req): Json<serde_json::Value>| async move {
             handle_memory_reflect(ms, au, Json(req)).await
@@ -917,6 +1103,47 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
             async move { handle_self_health(s, c).await }
         })
     };
+    // ── Maintenance scheduler ─────────────────────────────────────────────
+    // Replaces the old fixed-interval TTL-eviction and coherence loops: expiry
+    // purge, decay, GC, source-trust decay, coherence check, workspace and
+    // lease expiry and pressure-gated consolidation on per-job schedules.
+    let maintenance = {
+        let mut targets = crate::maintenance::MaintenanceTargets::new(memory_store.clone());
+        targets.archive = Some(archive_store.clone());
+        targets.symbolic = Some(symbolic_store.clone());
+        targets.tx_log = tx_log_arc.clone();
+        targets.coherence = Some(coherence_arc.clone());
+        targets.workspaces = Some(cognitive.workspace_registry.clone());
+        targets.leases = Some(cognitive.lease_manager.clone());
+        let config = crate::maintenance::MaintenanceConfig::from_env().unwrap_or_else(|e| {
+            eprintln!("[maintenance] invalid HIPCORTEX_MAINTENANCE ({e}); using defaults");
+            crate::maintenance::MaintenanceConfig::default()
+        });
+        let scheduler = crate::maintenance::standard_scheduler(targets, &config)
+            .expect("default maintenance schedules are valid");
+        let scheduler = Arc::new(Mutex::new(scheduler));
+        crate::maintenance::spawn_daemon(scheduler.clone(), std::time::Duration::from_secs(1));
+        scheduler
+    };
+    // ── Conversation threads ──────────────────────────────────────────────
+    // Rolling summaries use the same summarizer as consolidation when one is set.
+    let conversations = {
+        let threads = crate::conversation_memory::ConversationThreads::default();
+        let threads = match cognitive.summarizer.clone() {
+            Some(s) => threads.with_summarizer(s),
+            None => threads,
+        };
+        Arc::new(threads)
+    };
+    let bundle_targets = Arc::new(crate::state_bundle::BundleTargets {
+        memory: memory_store.clone(),
+        symbolic: Some(symbolic_store.clone()),
+        topology: Some(topo_arc.clone()),
+        world: Some(world_model.clone()),
+        self_model: Some(self_model_arc.clone()),
+        tx_log: tx_log_arc.clone(),
+    });
+
     let app = Router::new()
         .route("/", get(|| async { axum::response::Redirect::permanent("/pricing") }))
         .route("/health", get(|| async {
@@ -934,6 +1161,15 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
         .route("/memory/query", query_memory_route)
         .route("/memory/search", search_route)
         .route("/memory/export", export_route)
+        .route("/memory/import", {
+            let m = AsyncMemoryStore::from(memory_store.clone());
+            post(move |Json(req): Json<ImportMemoryRequest>| {
+                let m = m.clone();
+                async move { handle_import_memory(m, req).await }
+            })
+            // Chat exports and span dumps are routinely larger than the 2 MB default.
+            .layer(axum::extract::DefaultBodyLimit::max(64 * 1024 * 1024))
+        })
         .route("/memory/forget/:actor", forget_route)
         .route("/memory/search-flat", search_flat_route)
         .route("/memory/update/:id", update_route)
@@ -989,9 +1225,68 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
         .route("/memory/corroborate/:id", corroborate_route)
         .route("/memory/contradict/:id", contradict_route)
         .route("/memory/context", context_route)
+        .route("/memory/injections", {
+            let store = memory_store.clone();
+            get(move |Query(params): Query<InjectionReportParams>| async move {
+                handle_injection_report(store, params).await
+            })
+        })
+        .route("/memory/injections/quarantine", {
+            let store = memory_store.clone();
+            post(move |Query(params): Query<InjectionReportParams>| async move {
+                handle_injection_quarantine(store, params).await
+            })
+        })
         .route("/memory/live_beliefs", live_beliefs_route)
         .route("/memory/:id",             delete_memory_route)
         .route("/metrics", metrics_route)
+        .route("/maintenance/jobs", {
+            let m = maintenance.clone();
+            get(move || {
+                let m = m.clone();
+                async move { handle_maintenance_jobs(m).await }
+            })
+        })
+        .route("/maintenance/history", {
+            let m = maintenance.clone();
+            get(move |Query(p): Query<MaintenanceHistoryParams>| {
+                let m = m.clone();
+                async move { handle_maintenance_history(m, p).await }
+            })
+        })
+        .route("/maintenance/jobs/:name/:action", {
+            let m = maintenance.clone();
+            post(move |Path((name, action)): Path<(String, String)>| {
+                let m = m.clone();
+                async move { handle_maintenance_action(m, name, action).await }
+            })
+        })
+        .route("/conversations", {
+            let (c, m) = (conversations.clone(), memory_store.clone());
+            get(move || {
+                let (c, m) = (c.clone(), m.clone());
+                async move { handle_list_conversations(c, m).await }
+            })
+        })
+        .route("/conversations/:session", {
+            let (c, m) = (conversations.clone(), memory_store.clone());
+            let (c2, m2) = (c.clone(), m.clone());
+            get(move |Path(session): Path<String>, Query(p): Query<ConversationPageParams>| {
+                let (c, m) = (c.clone(), m.clone());
+                async move { handle_conversation_page(c, m, session, p).await }
+            })
+            .post(move |Path(session): Path<String>, Json(req): Json<ConversationAppendRequest>| {
+                let (c, m) = (c2.clone(), m2.clone());
+                async move { handle_conversation_append(c, AsyncMemoryStore::from(m), session, req).await }
+            })
+        })
+        .route("/conversations/:session/summarize", {
+            let (c, m) = (conversations.clone(), memory_store.clone());
+            post(move |Path(session): Path<String>, Query(p): Query<ConversationSummarizeParams>| {
+                let (c, m) = (c.clone(), m.clone());
+                async move { handle_conversation_summarize(c, AsyncMemoryStore::from(m), session, p).await }
+            })
+        })
         .route("/stats", stats_route)
         .route("/tier", get(handle_tier))
         .route("/pricing", get(handle_pricing))
@@ -1107,7 +1402,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                 match &txl {
                     None => Json(serde_json::json!({"from_tx": from_tx, "to_tx": to_tx, "error": "tx_log not configured"})),
                     Some(log) => {
-                        let ms = store.lock().unwrap();
+                        let ms = store.snapshot();
                         match crate::state_diff::compute_tx_diff(log, from_tx, to_tx, &*ms) {
                             Ok(diff) => Json(
                                 serde_json::to_value(diff)
@@ -1120,7 +1415,7 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
             })
         })
         .route("/v1/memory/consolidate", {
-            let store = memory_store.clone();
+            let store = AsyncMemoryStore::from(memory_store.clone());
             let arc = archive_store.clone();
             let sym = symbolic_store.clone();
             let txl = tx_log_arc.clone();
@@ -1133,21 +1428,30 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                     min_group_size: min_group,
                     ..Default::default()
                 };
-                let dummy_log;
-                let log_ref: &crate::tx_log::TxLog = match &txl {
-                    Some(l) => l,
-                    None => {
-                        let dir = std::env::temp_dir();
-                        dummy_log = crate::tx_log::TxLog::open(dir.join("hc-consolidate-tmp.jsonl")).unwrap();
-                        &dummy_log
-                    }
-                };
-                let mut ms = store.lock().unwrap();
-                let mut arc = arc.lock().unwrap();
-                let mut sym = sym.lock().unwrap();
-                match crate::consolidation::consolidate(&mut ms, &mut arc, &mut sym, log_ref, &config) {
-                    Ok(r) => Json(serde_json::to_value(r).unwrap_or(serde_json::json!({"error": "serialization failed"}))),
-                    Err(e) => Json(serde_json::json!({"error": e})),
+                let consolidated = store
+                    .transact(move |ms| {
+                        let dummy_log;
+                        let log_ref: &crate::tx_log::TxLog = match &txl {
+                            Some(l) => l,
+                            None => {
+                                let dir = std::env::temp_dir();
+                                dummy_log = crate::tx_log::TxLog::open(dir.join("hc-consolidate-tmp.jsonl"))
+                                    .map_err(anyhow::Error::msg)?;
+                                &dummy_log
+                            }
+                        };
+                        let mut arc = arc.lock().unwrap();
+                        let mut sym = sym.lock().unwrap();
+                        Ok(crate::consolidation::consolidate(ms, &mut arc, &mut sym, log_ref, &config))
+                    })
+                    .await;
+                match consolidated {
+                    Ok(Ok(r)) => Json(
+                        serde_json::to_value(r)
+                            .unwrap_or(serde_json::json!({"error": "serialization failed"})),
+                    ),
+                    Ok(Err(e)) => Json(serde_json::json!({"error": e})),
+                    Err(e) => Json(serde_json::json!({"error": e.to_string()})),
                 }
             })
         })
@@ -1160,6 +1464,24 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                 }
             })
         })
+        .route("/v1/state/checkpoint", {
+            let store = AsyncMemoryStore::from(memory_store.clone());
+            let txl = tx_log_arc.clone();
+            post(move || {
+                let store = store.clone();
+                let txl = txl.clone();
+                async move { handle_state_checkpoint(store, txl).await }
+            })
+        })
+        .route("/v1/state/restore", {
+            let store = AsyncMemoryStore::from(memory_store.clone());
+            let txl = tx_log_arc.clone();
+            post(move |headers: HeaderMap, Json(req): Json<RestoreRequest>| {
+                let store = store.clone();
+                let txl = txl.clone();
+                async move { handle_state_restore(store, txl, headers, req).await }
+            })
+        })
         .route("/v1/state/export", {
             let cog = cognitive.clone();
             get(move || async move {
@@ -1188,6 +1510,29 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                 }
             })
         })
+        .route(
+            "/v1/state/bundle",
+            {
+                let targets = bundle_targets.clone();
+                get(move |Query(params): Query<BundleExportParams>| {
+                    let targets = targets.clone();
+                    async move { handle_bundle_export(targets, params).await }
+                })
+            }
+            .post({
+                let targets = bundle_targets.clone();
+                move |Json(req): Json<BundleImportRequest>| {
+                    let targets = targets.clone();
+                    async move { handle_bundle_import(targets, req).await }
+                }
+            })
+            .layer(axum::extract::DefaultBodyLimit::max(512 * 1024 * 1024)),
+        )
+        .route(
+            "/v1/safety/policy",
+            get(handle_safety_policy_get).put(handle_safety_policy_put),
+        )
+        .route("/v1/safety/rehydrate", post(handle_safety_rehydrate))
         .route("/v1/beliefs", {
             let store = memory_store.clone();
             get(
@@ -1215,20 +1560,28 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                     Some(v) => v.clone(),
                     None => return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({"ok": false, "error": "missing delta"}))),
                 };
-                match serde_json::from_value::<crate::cognitive_state::CognitiveDelta>(delta_val) {
-                    Err(e) => (axum::http::StatusCode::UNPROCESSABLE_ENTITY, axum::Json(serde_json::json!({"ok": false, "error": e.to_string()}))),
-                    Ok(delta) => match cog.transact_ex(delta, &actor) {
-                        Ok(r) => {
-                            let mut body = serde_json::json!({"ok": true, "tx_cursor": r.tx_cursor});
-                            if let Some(n) = r.records_deleted {
-                                body["records_deleted"] = serde_json::json!(n);
-                            }
-                            (axum::http::StatusCode::OK, axum::Json(body))
+                if let Err(e) = check_delta_status(&delta_val) {
+                    return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({"ok": false, "error": e})));
+                }
+                let delta = match serde_json::from_value::<crate::cognitive_state::CognitiveDelta>(delta_val) {
+                    Ok(delta) => delta,
+                    Err(e) => return (axum::http::StatusCode::UNPROCESSABLE_ENTITY, axum::Json(serde_json::json!({"ok": false, "error": e.to_string()}))),
+                };
+                // The transaction writes and flushes the store; keep it off the runtime workers.
+                let result = tokio::task::spawn_blocking(move || cog.transact_ex(delta, &actor))
+                    .await
+                    .unwrap_or_else(|e| Err(crate::cognitive_state::CognitiveError::StoreError(e.to_string())));
+                match result {
+                    Ok(r) => {
+                        let mut body = serde_json::json!({"ok": true, "tx_cursor": r.tx_cursor});
+                        if let Some(n) = r.records_deleted {
+                            body["records_deleted"] = serde_json::json!(n);
                         }
-                        Err(crate::cognitive_state::CognitiveError::CoherenceRejection(r)) => (axum::http::StatusCode::CONFLICT, axum::Json(serde_json::json!({"ok": false, "error": r, "code": "CoherenceRejection"}))),
-                        Err(crate::cognitive_state::CognitiveError::NotImplemented(op)) => (axum::http::StatusCode::NOT_IMPLEMENTED, axum::Json(serde_json::json!({"ok": false, "error": format!("{op} not implemented"), "code": "NotImplemented"}))),
-                        Err(e) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(serde_json::json!({"ok": false, "error": e.to_string()}))),
-                    },
+                        (axum::http::StatusCode::OK, axum::Json(body))
+                    }
+                    Err(crate::cognitive_state::CognitiveError::CoherenceRejection(r)) => (axum::http::StatusCode::CONFLICT, axum::Json(serde_json::json!({"ok": false, "error": r, "code": "CoherenceRejection"}))),
+                    Err(crate::cognitive_state::CognitiveError::NotImplemented(op)) => (axum::http::StatusCode::NOT_IMPLEMENTED, axum::Json(serde_json::json!({"ok": false, "error": format!("{op} not implemented"), "code": "NotImplemented"}))),
+                    Err(e) => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(serde_json::json!({"ok": false, "error": e.to_string()}))),
                 }
             })
         })
@@ -1315,6 +1668,9 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                     Some(v) => v.clone(),
                     None => return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({"ok": false, "error": "missing delta"}))),
                 };
+                if let Err(e) = check_delta_status(&delta_val) {
+                    return (axum::http::StatusCode::BAD_REQUEST, axum::Json(serde_json::json!({"ok": false, "error": e})));
+                }
                 let delta = match serde_json::from_value::<crate::cognitive_state::CognitiveDelta>(delta_val) {
                     Err(e) => return (axum::http::StatusCode::UNPROCESSABLE_ENTITY, axum::Json(serde_json::json!({"ok": false, "error": e.to_string()}))),
                     Ok(d) => d,
@@ -1537,48 +1893,16 @@ pub async fn run_with_state<B: MemoryBackend + Send + Sync + 'static>(
                 })))
             })
         })
-        .layer(middleware::from_fn(api_key_middleware));
-
-    // G11: Background TTL eviction — purges expired records every 5 minutes.
-    // This is separate from the read-time filter in query handlers, which hides
-    // expired records but does not reclaim storage. This thread actually removes them.
-    {
-        let eviction_store = memory_store.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300));
-            loop {
-                interval.tick().await;
-                if let Ok(mut ms) = eviction_store.lock() {
-                    let removed = ms.purge_expired();
-                    if removed > 0 {
-                        eprintln!("[EvictionThread] purged {} expired records", removed);
-                    }
-                }
-            }
-        });
-    }
-
-    // G10: Background CoherenceChecker — runs check_consistency every 60s
-    {
-        let coherence_bg = coherence_arc.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
-            loop {
-                interval.tick().await;
-                match coherence_bg.check_consistency() {
-                    Ok(reports) => {
-                        if !reports.is_empty() {
-                            eprintln!(
-                                "[CoherenceChecker] {} inconsistencies detected",
-                                reports.len()
-                            );
-                        }
-                    }
-                    Err(e) => eprintln!("[CoherenceChecker] check_consistency error: {}", e),
-                }
+        .layer(middleware::from_fn({
+            let ms = memory_store.clone();
+            let txl = tx_log_arc.clone();
+            move |req: Request<axum::body::Body>, next: Next<axum::body::Body>| {
+                let ms = ms.clone();
+                let txl = txl.clone();
+                async move { tx_journal_middleware(ms, txl, req, next).await }
             }
-        });
-    }
+        }))
+        .layer(middleware::from_fn(api_key_middleware));
 
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
@@ -1781,6 +2105,34 @@ fn check_meter_limit(key: &str, tier: &ApiTier) -> bool {
     false
 }
 
+/// Axum middleware: after a mutating request, log any memory changes the handler
+/// left in the store's journal (update, delete, quarantine, …) as one tx, so
+/// point-in-time recovery sees mutations from handlers that do not log their own.
+#[cfg(feature = "web-server")]
+async fn tx_journal_middleware<B: MemoryBackend + Send + Sync + 'static, Body>(
+    store: Arc<ConcurrentMemoryStore<B>>,
+    tx_log: Option<Arc<TxLog>>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let kind = match *req.method() {
+        Method::GET | Method::HEAD | Method::OPTIONS => return next.run(req).await,
+        Method::DELETE => crate::tx_log::TxKind::MemoryDelete,
+        _ => crate::tx_log::TxKind::MemoryUpdate,
+    };
+    let resp = next.run(req).await;
+    if let Some(log) = tx_log {
+        // Appending to the tx log writes to disk; do it on the blocking pool.
+        let _ = tokio::task::spawn_blocking(move || {
+            if let Ok(mut ms) = store.lock() {
+                crate::pitr::log_pending(&mut ms, &log, kind, "api");
+            }
+        })
+        .await;
+    }
+    resp
+}
+
 /// Axum middleware: validates X-Api-Key header (when HIPCORTEX_API_KEYS is set),
 /// enforces per-tier write quotas on POST /memory/add and POST /memory/search,
 /// and stamps X-HipCortex-Tier on every response for observability.
@@ -1929,10 +2281,14 @@ async fn handle_tier(headers: HeaderMap) -> Json<TierResponse> {
 /// query embedding is supplied.
 #[cfg(feature = "web-server")]
 async fn handle_search_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
+    tx_log: Option<Arc<TxLog>>,
     Json(req): Json<SearchMemoryRequest>,
 ) -> Result<Json<SearchMemoryResponse>, (StatusCode, Json<SearchMemoryResponse>)> {
     let limit = req.limit.unwrap_or(10).min(100);
+    let snapshot = time_travel_store(tx_log, req.as_of.as_deref())
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(SearchMemoryResponse::failed(e))))?;
 
     // Resolve query embedding:
     // Priority: explicit embedding > auto-generate from embedding_model > keyword-only
@@ -1942,13 +2298,10 @@ async fn handle_search_memory<B: MemoryBackend + Send + Sync + 'static>(
         match generate_embedding(model_str, &req.query).await {
             Ok(v) if !v.is_empty() => Some(v),
             Ok(_) => None, // empty = fall back to keyword search
-            Err(_e) => {
+            Err(e) => {
                 return Err((
                     StatusCode::BAD_GATEWAY,
-                    Json(SearchMemoryResponse {
-                        results: vec![],
-                        total: 0,
-                    }),
+                    Json(SearchMemoryResponse::failed(format!("embedding: {e}"))),
                 ))
             }
         }
@@ -1956,66 +2309,103 @@ async fn handle_search_memory<B: MemoryBackend + Send + Sync + 'static>(
         None
     };
 
+    let include_quarantined = req.include_quarantined.unwrap_or(false);
+
+    // The search reads the published snapshot: no writer lock. Cold-tier
+    // records are scored in place and rank alongside hot ones, which can read
+    // segments from disk, so the scoring runs on the blocking pool.
+    let live = store.snapshot();
     let now_ts = chrono::Utc::now().timestamp();
-    match store.lock() {
-        Ok(ms) => {
-            let results = ms.search_semantic(
+    let results = {
+        let live = live.clone();
+        let query = req.query.clone();
+        tokio::task::spawn_blocking(move || match snapshot {
+            Some(mut past) => {
+                past.source_trust = live.source_trust.clone();
+                past.search_tiered(resolved_embedding.as_deref(), &query, limit, include_quarantined)
+            }
+            None => live.search_tiered(
                 resolved_embedding.as_deref(),
-                &req.query,
+                &query,
                 limit,
-                req.include_quarantined.unwrap_or(false),
-            );
-            let response_results = results
-                .into_iter()
-                .filter(|(r, _)| r.expires_at.map_or(true, |exp| exp > now_ts))
-                .map(|(r, score)| SearchResult {
-                    score,
-                    record: MemoryRecordResponse {
-                        id: r.id.to_string(),
-                        record_type: format!("{:?}", r.record_type),
-                        timestamp: r.timestamp.to_rfc3339(),
-                        actor: r.actor.clone(),
-                        action: r.action.clone(),
-                        target: r.target.clone(),
-                        metadata: r.metadata.clone(),
-                        integrity: r.integrity.clone(),
-                        confidence: r.confidence,
-                        source: r.source.clone(),
-                        priority: r.priority.clone(),
-                        tags: r.tags.clone(),
-                        version: r.version,
-                        status: r.status.clone(),
-                        expires_at: r.expires_at,
-                    },
-                })
-                .collect::<Vec<_>>();
-            let response_results = if let Some(max_tok) = req.max_tokens {
-                let max_chars = max_tok * 4;
-                let mut total_chars = 0usize;
-                response_results
-                    .into_iter()
-                    .take_while(|r| {
-                        total_chars += r.record.target.len();
-                        total_chars <= max_chars
-                    })
-                    .collect::<Vec<_>>()
-            } else {
-                response_results
-            };
-            let total = response_results.len();
-            Ok(Json(SearchMemoryResponse {
-                results: response_results,
-                total,
-            }))
-        }
-        Err(_e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(SearchMemoryResponse {
-                results: vec![],
-                total: 0,
-            }),
-        )),
-    }
+                include_quarantined,
+            ),
+        })
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(SearchMemoryResponse::failed(e.to_string())),
+            )
+        })?
+    };
+    // Injection defense: all hits are classified in one batch, outside any
+    // lock. High-risk hits are withheld and reported, suspicious ones are
+    // annotated; quarantining is left to the sweep.
+    let injection_cfg = crate::injection_defense::InjectionConfig::default();
+    let results: Vec<(MemoryRecord, f64)> = results
+        .into_iter()
+        .filter(|(r, _)| r.expires_at.is_none_or(|exp| exp > now_ts))
+        .collect();
+    let hits: Vec<(&MemoryRecord, Option<f64>)> = results
+        .iter()
+        .map(|(r, _)| (r, crate::injection_defense::source_trust_of(&live.source_trust, r)))
+        .collect();
+    let assessments = crate::injection_defense::assess_batch(&hits, &injection_cfg);
+    let mut withheld = Vec::new();
+    let response_results = results
+        .into_iter()
+        .zip(assessments)
+        .filter_map(|((r, score), assessment)| match assessment.level {
+            crate::injection_defense::InjectionLevel::HighRisk if !include_quarantined => {
+                withheld.push(assessment);
+                None
+            }
+            crate::injection_defense::InjectionLevel::Clean => Some((r, score, None)),
+            _ => Some((r, score, Some(assessment))),
+        })
+        .map(|(r, score, injection)| SearchResult {
+            score,
+            injection,
+            record: MemoryRecordResponse {
+                id: r.id.to_string(),
+                record_type: format!("{:?}", r.record_type),
+                timestamp: r.timestamp.to_rfc3339(),
+                actor: r.actor.clone(),
+                action: r.action.clone(),
+                target: r.target.clone(),
+                metadata: r.metadata.clone(),
+                integrity: r.integrity.clone(),
+                confidence: r.confidence,
+                source: r.source.clone(),
+                priority: r.priority,
+                tags: r.tags.clone(),
+                version: r.version,
+                status: r.status,
+                expires_at: r.expires_at,
+            },
+        })
+        .collect::<Vec<_>>();
+    let response_results = if let Some(max_tok) = req.max_tokens {
+        let max_chars = max_tok * 4;
+        let mut total_chars = 0usize;
+        response_results
+            .into_iter()
+            .take_while(|r| {
+                total_chars += r.record.target.len();
+                total_chars <= max_chars
+            })
+            .collect::<Vec<_>>()
+    } else {
+        response_results
+    };
+    let total = response_results.len();
+    Ok(Json(SearchMemoryResponse {
+        results: response_results,
+        total,
+        withheld,
+        error: None,
+    }))
 }
 
 /// GET /stats — returns live server statistics (record count, breakdown, metering)
@@ -2029,6 +2419,8 @@ struct StatsResponse {
     unique_actors: usize,
     metering_enabled: bool,
     tier_counts: HashMap<String, u64>,
+    /// Hot experience tiers (raw / episode / abstract) plus cold tier size.
+    storage_tiers: crate::experience_store::TierSizes,
 }
 
 #[cfg(feature = "web-server")]
@@ -2043,78 +2435,70 @@ pub struct AuditVerifyResponse {
 /// Data portability: migrate between instances, backup, or import into other systems.
 #[cfg(feature = "web-server")]
 async fn handle_export_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     Query(params): Query<QueryMemoryParams>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match store.lock() {
-        Ok(ms) => {
-            let records = ms.all();
-            let now_ts = chrono::Utc::now().timestamp();
-            let include_expired = params.include_expired.as_deref() == Some("true");
-            let filtered: Vec<_> = records
-                .iter()
-                .filter(|r| {
-                    params.actor.as_ref().map_or(true, |a| &r.actor == a)
-                        && (include_expired || r.expires_at.map_or(true, |exp| exp > now_ts))
-                })
-                .collect();
-            let json_records: Vec<serde_json::Value> = filtered
-                .iter()
-                .map(|r| {
-                    serde_json::json!({
-                        "id":          r.id.to_string(),
-                        "record_type": format!("{:?}", r.record_type),
-                        "timestamp":   r.timestamp.to_rfc3339(),
-                        "actor":       r.actor,
-                        "action":      r.action,
-                        "target":      r.target,
-                        "metadata":    r.metadata,
-                        "integrity":   r.integrity,
-                        "confidence":  r.confidence,
-                        "source":      r.source.clone(),
-                        "priority":    r.priority.clone(),
-                        "tags":        r.tags.clone(),
-                        "version":     r.version,
-                        "status":      r.status.clone(),
-                        "expires_at":  r.expires_at,
-                    })
-                })
-                .collect();
-            Ok(Json(serde_json::json!({
-                "records": json_records,
-                "total": json_records.len(),
-                "exported_at": chrono::Utc::now().to_rfc3339(),
-            })))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Lock error: {}", e)})),
-        )),
-    }
+    let ms = store.snapshot();
+    let records = ms.all();
+    let now_ts = chrono::Utc::now().timestamp();
+    let include_expired = params.include_expired.as_deref() == Some("true");
+    let filtered: Vec<_> = records
+        .iter()
+        .filter(|r| {
+            params.actor.as_ref().is_none_or(|a| &r.actor == a)
+                && (include_expired || r.expires_at.is_none_or(|exp| exp > now_ts))
+        })
+        .collect();
+    let json_records: Vec<serde_json::Value> = filtered
+        .iter()
+        .map(|r| {
+            serde_json::json!({
+                "id":          r.id.to_string(),
+                "record_type": format!("{:?}", r.record_type),
+                "timestamp":   r.timestamp.to_rfc3339(),
+                "actor":       r.actor,
+                "action":      r.action,
+                "target":      r.target,
+                "metadata":    r.metadata,
+                "integrity":   r.integrity,
+                "confidence":  r.confidence,
+                "source":      r.source.clone(),
+                "priority":    r.priority,
+                "tags":        r.tags.clone(),
+                "version":     r.version,
+                "status":      r.status,
+                "expires_at":  r.expires_at,
+            })
+        })
+        .collect();
+    Ok(Json(serde_json::json!({
+        "records": json_records,
+        "total": json_records.len(),
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+    })))
 }
 
 #[cfg(feature = "web-server")]
 async fn handle_stats<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
 ) -> Json<StatsResponse> {
-    let (total_records, active_records, by_type, unique_actors) = match store.lock() {
-        Ok(ms) => {
-            let records = ms.all();
-            let total = records.len();
-            let now_ts = chrono::Utc::now().timestamp();
-            let active = records
-                .iter()
-                .filter(|r| r.expires_at.map_or(true, |exp| exp > now_ts))
-                .count();
-            let mut by_type: HashMap<String, usize> = HashMap::new();
-            let mut actors: std::collections::HashSet<&str> = std::collections::HashSet::new();
-            for r in records {
-                *by_type.entry(format!("{:?}", r.record_type)).or_insert(0) += 1;
-                actors.insert(&r.actor);
-            }
-            (total, active, by_type, actors.len())
+    let (total_records, active_records, by_type, unique_actors, storage_tiers) = {
+        let ms = store.snapshot();
+        let records = ms.all();
+        let total = records.len();
+        let now_ts = chrono::Utc::now().timestamp();
+        let active = records
+            .iter()
+            .filter(|r| r.expires_at.is_none_or(|exp| exp > now_ts))
+            .count();
+        let mut by_type: HashMap<String, usize> = HashMap::new();
+        let mut actors: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        for r in records {
+            *by_type.entry(format!("{:?}", r.record_type)).or_insert(0) += 1;
+            actors.insert(&r.actor);
         }
-        Err(_) => (0, 0, HashMap::new(), 0),
+        let tiers = crate::experience_store::tier_sizes(&*ms);
+        (total, active, by_type, actors.len(), tiers)
     };
 
     let metering_enabled = !load_api_keys().is_empty();
@@ -2127,6 +2511,7 @@ async fn handle_stats<B: MemoryBackend + Send + Sync + 'static>(
         unique_actors,
         metering_enabled,
         tier_counts,
+        storage_tiers,
     })
 }
 
@@ -2250,53 +2635,79 @@ const PRICING_HTML: &str = r#"<!DOCTYPE html>
 
 #[cfg(feature = "web-server")]
 async fn handle_bulk_add<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     Json(req): Json<BulkAddRequest>,
 ) -> Json<BulkAddResponse> {
-    let mut record_ids: Vec<String> = Vec::new();
-    let mut errors: Vec<crate::memory_store::BulkAddError> = Vec::new();
-
-    match store.lock() {
+    let submitted = req.records.len();
+    // Screen on the blocking pool against a snapshot, then add under the lock.
+    let snapshot = store.snapshot();
+    let screened = tokio::task::spawn_blocking(move || {
+        let mut screened = Vec::new();
+        let mut errors: Vec<crate::memory_store::BulkAddError> = Vec::new();
+        for (idx, r) in req.records.into_iter().enumerate() {
+            let actor_name = r.actor.clone();
+            let record_type = parse_record_type_alias(r.record_type.as_deref());
+            let mut record = MemoryRecord::new(
+                record_type,
+                r.actor,
+                r.action,
+                r.target,
+                r.metadata.unwrap_or_else(|| serde_json::json!({})),
+            );
+            match snapshot.screen(&mut record) {
+                Ok(_) => screened.push((idx, actor_name, record)),
+                Err(e) => errors.push(crate::memory_store::BulkAddError {
+                    index: idx,
+                    actor: actor_name,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        (screened, errors)
+    })
+    .await;
+    let outcome = match screened {
+        Ok((screened, mut errors)) => {
+            store
+                .transact(move |ms| {
+                    let mut record_ids: Vec<String> = Vec::new();
+                    for (idx, actor_name, record) in screened {
+                        let id = record.id.to_string();
+                        match ms.add_prescreened(record) {
+                            Ok(_) => record_ids.push(id),
+                            Err(e) => errors.push(crate::memory_store::BulkAddError {
+                                index: idx,
+                                actor: actor_name,
+                                reason: e.to_string(),
+                            }),
+                        }
+                    }
+                    errors.sort_by_key(|e| e.index);
+                    Ok((record_ids, errors))
+                })
+                .await
+        }
+        Err(e) => Err(e.into()),
+    };
+    match outcome {
+        Ok((record_ids, errors)) => Json(BulkAddResponse {
+            success: errors.is_empty(),
+            inserted: record_ids.len(),
+            failed: errors.len(),
+            record_ids,
+            errors,
+        }),
         Err(e) => Json(BulkAddResponse {
             success: false,
             inserted: 0,
-            failed: req.records.len(),
+            failed: submitted,
             record_ids: vec![],
             errors: vec![crate::memory_store::BulkAddError {
                 index: 0,
                 actor: String::new(),
-                reason: format!("Lock error: {}", e),
+                reason: e.to_string(),
             }],
         }),
-        Ok(mut ms) => {
-            for (idx, r) in req.records.into_iter().enumerate() {
-                let actor_name = r.actor.clone();
-                let record_type = parse_record_type_alias(r.record_type.as_deref());
-                let record = MemoryRecord::new(
-                    record_type,
-                    r.actor,
-                    r.action,
-                    r.target,
-                    r.metadata.unwrap_or_else(|| serde_json::json!({})),
-                );
-                let id = record.id.to_string();
-                match ms.add(record) {
-                    Ok(_) => record_ids.push(id),
-                    Err(e) => errors.push(crate::memory_store::BulkAddError {
-                        index: idx,
-                        actor: actor_name,
-                        reason: e.to_string(),
-                    }),
-                }
-            }
-            Json(BulkAddResponse {
-                success: errors.is_empty(),
-                inserted: record_ids.len(),
-                failed: errors.len(),
-                record_ids,
-                errors,
-            })
-        }
     }
 }
 
@@ -2305,74 +2716,28 @@ async fn handle_bulk_add<B: MemoryBackend + Send + Sync + 'static>(
 /// Returns empty Vec on empty response (caller should fall back to keyword search).
 #[cfg(feature = "web-server")]
 async fn generate_embedding(model_str: &str, text: &str) -> Result<Vec<f64>, String> {
-    if model_str.starts_with("ollama/") {
-        let model = &model_str["ollama/".len()..];
-        let ollama_url =
-            std::env::var("OLLAMA_URL").unwrap_or_else(|_| "http://localhost:11434".to_string());
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .map_err(|e| e.to_string())?;
-        let body = serde_json::json!({ "model": model, "prompt": text });
-        let resp = client
-            .post(format!("{}/api/embeddings", ollama_url))
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("Ollama request failed: {}", e))?;
-        let data: serde_json::Value = resp.json().await.unwrap_or_default();
-        Ok(data["embedding"]
-            .as_array()
-            .map(|arr| arr.iter().filter_map(|v| v.as_f64()).collect())
-            .unwrap_or_default())
-    } else if model_str.starts_with("openai/") {
-        let model = &model_str["openai/".len()..];
-        let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_default();
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .map_err(|e| e.to_string())?;
-        let body = serde_json::json!({ "model": model, "input": text });
-        let resp = client
-            .post("https://api.openai.com/v1/embeddings")
-            .bearer_auth(&api_key)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("OpenAI request failed: {}", e))?;
-        let data: serde_json::Value = resp.json().await.unwrap_or_default();
-        Ok(data["data"][0]["embedding"]
-            .as_array()
-            .map(|arr| arr.iter().filter_map(|v| v.as_f64()).collect())
-            .unwrap_or_default())
-    } else {
-        Err(format!(
-            "embedding_model must start with 'ollama/' or 'openai/', got: {}",
-            model_str
-        ))
-    }
+    let provider = RemoteEmbeddingProvider::from_model(model_str).map_err(|e| e.to_string())?;
+    let embedding = provider.embed(text).await.map_err(|e| e.to_string())?;
+    Ok(embedding.into_iter().map(f64::from).collect())
 }
 
 #[cfg(feature = "web-server")]
 async fn handle_embed_and_add<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     Json(req): Json<EmbedAndAddRequest>,
 ) -> Result<Json<AddMemoryResponse>, (StatusCode, Json<AddMemoryResponse>)> {
-    let embedding = match generate_embedding(&req.embedding_model, &req.target).await {
-        Ok(v) => v,
-        Err(e) => {
-            return Err((
-                StatusCode::BAD_GATEWAY,
-                Json(AddMemoryResponse {
-                    success: false,
-                    record_id: None,
-                    error: Some(e),
-                    warning: None,
-                }),
-            ))
-        }
+    let failed = |status: StatusCode, error: String| {
+        (
+            status,
+            Json(AddMemoryResponse {
+                success: false,
+                record_id: None,
+                error: Some(error),
+                warning: None,
+                safety: None,
+            }),
+        )
     };
-
     let record_type = match req.record_type.as_deref() {
         Some("Symbolic") => MemoryType::Symbolic,
         Some("Procedural") => MemoryType::Procedural,
@@ -2380,37 +2745,39 @@ async fn handle_embed_and_add<B: MemoryBackend + Send + Sync + 'static>(
         Some("Perception") => MemoryType::Perception,
         _ => MemoryType::Temporal,
     };
-    let mut metadata = req.metadata.unwrap_or_else(|| serde_json::json!({}));
-    if let serde_json::Value::Object(ref mut map) = metadata {
-        map.insert("embedding".to_string(), serde_json::json!(embedding));
-    }
+    let metadata = req.metadata.unwrap_or_else(|| serde_json::json!({}));
     let record = MemoryRecord::new(record_type, req.actor, req.action, req.target, metadata);
 
-    match store.lock() {
-        Ok(mut ms) => match ms.add(record.clone()) {
-            Ok(_) => Ok(Json(AddMemoryResponse {
-                success: true,
-                record_id: Some(record.id.to_string()),
-                error: None,
-                warning: None,
-            })),
-            Err(e) => Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(AddMemoryResponse {
-                    success: false,
-                    record_id: None,
-                    error: Some(e.to_string()),
-                    warning: None,
-                }),
-            )),
-        },
+    // Screen before embedding so the embedding service never sees redacted values.
+    let (mut record, outcome) = store
+        .screen(record)
+        .await
+        .map_err(|e| failed(write_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()))?;
+    let embedding = generate_embedding(&req.embedding_model, &record.target)
+        .await
+        .map_err(|e| failed(StatusCode::BAD_GATEWAY, e))?;
+    record.set_metadata_field("embedding", embedding).ok();
+    let safety = outcome
+        .filter(|o| o.action != crate::safety_classifier::Action::Allow)
+        .map(|o| serde_json::json!(o));
+
+    let id = record.id;
+    match store.transact(move |ms| ms.add_prescreened(record)).await {
+        Ok(()) => Ok(Json(AddMemoryResponse {
+            success: true,
+            record_id: Some(id.to_string()),
+            error: None,
+            warning: None,
+            safety,
+        })),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(AddMemoryResponse {
                 success: false,
                 record_id: None,
-                error: Some(format!("Lock error: {}", e)),
+                error: Some(e.to_string()),
                 warning: None,
+                safety: None,
             }),
         )),
     }
@@ -2420,37 +2787,30 @@ async fn handle_embed_and_add<B: MemoryBackend + Send + Sync + 'static>(
 /// Returns plain string array — for no-code tools (Flowise, Dify, n8n, Make.com)
 #[cfg(feature = "web-server")]
 async fn handle_search_flat<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     Query(params): Query<SearchFlatParams>,
 ) -> Json<SearchFlatResponse> {
     let query = params.query.unwrap_or_default();
     let limit = params.limit.unwrap_or(10).min(50);
     let now_ts = chrono::Utc::now().timestamp();
 
-    match store.lock() {
-        Ok(ms) => {
-            let results = ms.search_semantic(None, &query, limit, false);
-            let memories: Vec<String> = results
-                .into_iter()
-                .filter(|(r, _)| {
-                    r.expires_at.map_or(true, |exp| exp > now_ts)
-                        && params.actor.as_ref().map_or(true, |a| &r.actor == a)
-                })
-                .map(|(r, _)| format!("[{}] {}", r.action, r.target))
-                .collect();
-            let total = memories.len();
-            Json(SearchFlatResponse { memories, total })
-        }
-        Err(_) => Json(SearchFlatResponse {
-            memories: vec![],
-            total: 0,
-        }),
-    }
+    let ms = store.snapshot();
+    let results = ms.search_semantic(None, &query, limit, false);
+    let memories: Vec<String> = results
+        .into_iter()
+        .filter(|(r, _)| {
+            r.expires_at.is_none_or(|exp| exp > now_ts)
+                && params.actor.as_ref().is_none_or(|a| &r.actor == a)
+        })
+        .map(|(r, _)| format!("[{}] {}", r.action, r.target))
+        .collect();
+    let total = memories.len();
+    Json(SearchFlatResponse { memories, total })
 }
 
 #[cfg(feature = "web-server")]
 async fn handle_update_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     id_str: String,
     Json(req): Json<UpdateMemoryRequest>,
 ) -> Result<Json<UpdateMemoryResponse>, (StatusCode, Json<UpdateMemoryResponse>)> {
@@ -2469,42 +2829,38 @@ async fn handle_update_memory<B: MemoryBackend + Send + Sync + 'static>(
         }
     };
 
-    match store.lock() {
-        Ok(mut ms) => match ms.update_record(
-            id,
-            req.target.as_deref(),
-            req.action.as_deref(),
-            req.confidence,
-            req.source.as_deref(),
-            req.metadata,
-        ) {
-            Ok(_) => {
-                let version = ms.find_by_id(id).map(|r| r.version).unwrap_or(0);
-                Ok(Json(UpdateMemoryResponse {
-                    success: true,
-                    record_id: id.to_string(),
-                    version,
-                    error: None,
-                }))
-            }
-            Err(e) => Err((
-                StatusCode::NOT_FOUND,
-                Json(UpdateMemoryResponse {
-                    success: false,
-                    record_id: id.to_string(),
-                    version: 0,
-                    error: Some(e.to_string()),
-                }),
-            )),
-        },
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+    let patch = RecordPatch {
+        target: req.target,
+        action: req.action,
+        confidence: req.confidence,
+        source: req.source,
+        metadata: req.metadata,
+    };
+    let failed = |status: StatusCode, error: String| {
+        (
+            status,
             Json(UpdateMemoryResponse {
                 success: false,
-                record_id: id_str,
+                record_id: id.to_string(),
                 version: 0,
-                error: Some(format!("Lock error: {}", e)),
+                error: Some(error),
             }),
+        )
+    };
+    match store.update(id, patch).await {
+        Ok(Some(updated)) => Ok(Json(UpdateMemoryResponse {
+            success: true,
+            record_id: id.to_string(),
+            version: updated.version,
+            error: None,
+        })),
+        Ok(None) => Err(failed(
+            StatusCode::NOT_FOUND,
+            format!("record {id} not found"),
+        )),
+        Err(e) => Err(failed(
+            write_status(&e, StatusCode::INTERNAL_SERVER_ERROR),
+            e.to_string(),
         )),
     }
 }
@@ -2514,75 +2870,59 @@ async fn handle_update_memory<B: MemoryBackend + Send + Sync + 'static>(
 /// Solves: "what is the current value of X?" query pattern.
 #[cfg(feature = "web-server")]
 async fn handle_latest_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     Query(params): Query<LatestMemoryParams>,
 ) -> Result<Json<QueryMemoryResponse>, (StatusCode, Json<QueryMemoryResponse>)> {
     let limit = params.limit.unwrap_or(20).min(100);
-    match store.lock() {
-        Ok(ms) => {
-            let records = ms.find_latest(params.actor.as_deref(), params.action.as_deref(), limit);
-            let response_records = records
-                .into_iter()
-                .map(|r| MemoryRecordResponse {
-                    id: r.id.to_string(),
-                    record_type: format!("{:?}", r.record_type),
-                    timestamp: r.timestamp.to_rfc3339(),
-                    actor: r.actor.clone(),
-                    action: r.action.clone(),
-                    target: r.target.clone(),
-                    metadata: r.metadata.clone(),
-                    integrity: r.integrity.clone(),
-                    confidence: r.confidence,
-                    source: r.source.clone(),
-                    priority: r.priority.clone(),
-                    tags: r.tags.clone(),
-                    version: r.version,
-                    status: r.status.clone(),
-                    expires_at: r.expires_at,
-                })
-                .collect::<Vec<_>>();
-            let total = response_records.len();
-            Ok(Json(QueryMemoryResponse {
-                records: response_records,
-                total,
-            }))
-        }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(QueryMemoryResponse {
-                records: vec![],
-                total: 0,
-            }),
-        )),
-    }
+    let ms = store.snapshot();
+    let records = ms.find_latest(params.actor.as_deref(), params.action.as_deref(), limit);
+    let response_records = records
+        .into_iter()
+        .map(|r| MemoryRecordResponse {
+            id: r.id.to_string(),
+            record_type: format!("{:?}", r.record_type),
+            timestamp: r.timestamp.to_rfc3339(),
+            actor: r.actor.clone(),
+            action: r.action.clone(),
+            target: r.target.clone(),
+            metadata: r.metadata.clone(),
+            integrity: r.integrity.clone(),
+            confidence: r.confidence,
+            source: r.source.clone(),
+            priority: r.priority,
+            tags: r.tags.clone(),
+            version: r.version,
+            status: r.status,
+            expires_at: r.expires_at,
+        })
+        .collect::<Vec<_>>();
+    let total = response_records.len();
+    Ok(Json(QueryMemoryResponse {
+        records: response_records,
+        total,
+        error: None,
+    }))
 }
 
 /// GET /audit/verify — check Merkle chain integrity
 #[cfg(feature = "web-server")]
 async fn handle_audit_verify<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
 ) -> Json<AuditVerifyResponse> {
-    match store.lock() {
-        Ok(ms) => match ms.audit_verify() {
-            Ok((intact, count)) => Json(AuditVerifyResponse {
-                intact,
-                entry_count: count,
-                message: if intact {
-                    format!("Audit log intact — {} entries verified", count)
-                } else {
-                    "TAMPER DETECTED — Merkle chain broken".to_string()
-                },
-            }),
-            Err(e) => Json(AuditVerifyResponse {
-                intact: false,
-                entry_count: 0,
-                message: format!("Verification error: {}", e),
-            }),
-        },
+    match store.transact(|ms| ms.audit_verify()).await {
+        Ok((intact, count)) => Json(AuditVerifyResponse {
+            intact,
+            entry_count: count,
+            message: if intact {
+                format!("Audit log intact — {} entries verified", count)
+            } else {
+                "TAMPER DETECTED — Merkle chain broken".to_string()
+            },
+        }),
         Err(e) => Json(AuditVerifyResponse {
             intact: false,
             entry_count: 0,
-            message: format!("Lock error: {}", e),
+            message: format!("Verification error: {}", e),
         }),
     }
 }
@@ -2590,26 +2930,20 @@ async fn handle_audit_verify<B: MemoryBackend + Send + Sync + 'static>(
 /// GET /audit/export — download full audit log as JSON array
 #[cfg(feature = "web-server")]
 async fn handle_audit_export<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    match store.lock() {
-        Ok(ms) => match ms.audit_export() {
-            Ok(entries) => {
-                let total = entries.len();
-                Ok(Json(serde_json::json!({
-                    "entries": entries,
-                    "total": total,
-                    "exported_at": chrono::Utc::now().to_rfc3339(),
-                })))
-            }
-            Err(e) => Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": e.to_string()})),
-            )),
-        },
+    match store.transact(|ms| ms.audit_export()).await {
+        Ok(entries) => {
+            let total = entries.len();
+            Ok(Json(serde_json::json!({
+                "entries": entries,
+                "total": total,
+                "exported_at": chrono::Utc::now().to_rfc3339(),
+            })))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Lock error: {}", e)})),
+            Json(serde_json::json!({"error": e.to_string()})),
         )),
     }
 }
@@ -2721,7 +3055,7 @@ async fn handle_delete_node(
 /// Returns 404 if either record UUID does not exist in the memory store.
 #[cfg(feature = "web-server")]
 async fn handle_memory_link<B: MemoryBackend + Send + Sync + 'static>(
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
     topo: Arc<Mutex<crate::topological_memory::CausalTopoGraph>>,
     req: MemoryLinkRequest,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
@@ -2752,12 +3086,7 @@ async fn handle_memory_link<B: MemoryBackend + Send + Sync + 'static>(
 
     // Validate both records exist
     {
-        let ms = memory_store.lock().map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"success": false, "error": format!("lock: {}", e)})),
-            )
-        })?;
+        let ms = memory_store.snapshot();
         if ms.find_by_id(from_uuid).is_none() {
             return Err((
                 StatusCode::NOT_FOUND,
@@ -2814,7 +3143,7 @@ async fn handle_memory_link<B: MemoryBackend + Send + Sync + 'static>(
 #[cfg(feature = "web-server")]
 async fn handle_memory_neighbors<B: MemoryBackend + Send + Sync + 'static>(
     topo: Arc<Mutex<crate::topological_memory::CausalTopoGraph>>,
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
     id: String,
 ) -> Json<MemoryNeighborsResponse> {
     let sym_id = format!("mem-{}", id);
@@ -2836,12 +3165,11 @@ async fn handle_memory_neighbors<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let mut records = Vec::new();
-    if let Ok(ms) = memory_store.lock() {
-        for n_id in neighbors.iter().chain(incoming.iter()) {
-            if let Ok(u) = uuid::Uuid::parse_str(n_id) {
-                if let Some(rec) = ms.find_by_id(u) {
-                    records.push(rec.clone());
-                }
+    let ms = memory_store.snapshot();
+    for n_id in neighbors.iter().chain(incoming.iter()) {
+        if let Ok(u) = uuid::Uuid::parse_str(n_id) {
+            if let Some(rec) = ms.find_by_id(u) {
+                records.push(rec.clone());
             }
         }
     }
@@ -2879,7 +3207,7 @@ async fn handle_memory_neighbors<B: MemoryBackend + Send + Sync + 'static>(
 #[cfg(feature = "web-server")]
 async fn handle_memory_search_related<B: MemoryBackend + Send + Sync + 'static>(
     topo: Arc<Mutex<crate::topological_memory::CausalTopoGraph>>,
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
     Query(params): Query<MemoryRelatedParams>,
 ) -> Json<serde_json::Value> {
     let limit = params.limit.unwrap_or(10).min(50);
@@ -2923,35 +3251,30 @@ async fn handle_memory_search_related<B: MemoryBackend + Send + Sync + 'static>(
                 .collect();
 
             // Build enriched results vec — look up full MemoryRecord for each result.
-            // LOCK ORDER: topo (already held above) → memory_store. All write paths
-            // (handle_memory_link) acquire memory_store first, then topo — but never hold
-            // both simultaneously. This read-only path nests them; preserve non-overlapping
-            // order in future write paths to avoid circular wait.
-            let results: Vec<serde_json::Value> = match memory_store.lock() {
-                Ok(ms) => raw
-                    .iter()
-                    .map(|(sym_id, score)| {
-                        let id_str = sym_id.trim_start_matches("mem-");
-                        let score_rounded = (score * 1000.0).round() / 1000.0;
-                        let record = id_str
-                            .parse::<uuid::Uuid>()
-                            .ok()
-                            .and_then(|uid| ms.find_by_id(uid))
-                            .map(|rec| {
-                                serde_json::json!({
-                                    "id":         rec.id.to_string(),
-                                    "actor":      rec.actor,
-                                    "action":     rec.action,
-                                    "target":     rec.target,
-                                    "confidence": rec.confidence,
-                                })
+            // Reads a snapshot, so nothing is nested under the topo lock.
+            let ms = memory_store.snapshot();
+            let results: Vec<serde_json::Value> = raw
+                .iter()
+                .map(|(sym_id, score)| {
+                    let id_str = sym_id.trim_start_matches("mem-");
+                    let score_rounded = (score * 1000.0).round() / 1000.0;
+                    let record = id_str
+                        .parse::<uuid::Uuid>()
+                        .ok()
+                        .and_then(|uid| ms.find_by_id(uid))
+                        .map(|rec| {
+                            serde_json::json!({
+                                "id":         rec.id.to_string(),
+                                "actor":      rec.actor,
+                                "action":     rec.action,
+                                "target":     rec.target,
+                                "confidence": rec.confidence,
                             })
-                            .unwrap_or_else(|| serde_json::json!({"id": id_str}));
-                        serde_json::json!({ "score": score_rounded, "record": record })
-                    })
-                    .collect(),
-                Err(_) => vec![],
-            };
+                        })
+                        .unwrap_or_else(|| serde_json::json!({"id": id_str}));
+                    serde_json::json!({ "score": score_rounded, "record": record })
+                })
+                .collect();
 
             Json(serde_json::json!({
                 "seed_id":   params.seed_id,
@@ -2969,102 +3292,100 @@ async fn handle_memory_search_related<B: MemoryBackend + Send + Sync + 'static>(
 /// Keyword-similarity based dedup. Executes deletes when dry_run=false (default).
 #[cfg(feature = "web-server")]
 async fn handle_consolidate<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     Query(params): Query<ConsolidateParams>,
 ) -> Json<serde_json::Value> {
     let threshold = params.threshold.unwrap_or(0.80).clamp(0.0, 1.0);
     let dry_run = params.dry_run.unwrap_or(false);
 
-    match store.lock() {
-        Err(e) => Json(serde_json::json!({"error": format!("Lock error: {}", e)})),
-        Ok(mut ms) => {
-            let records = ms.all().to_vec(); // clone to release borrow before mutations
-            let now_ts_consolidate = chrono::Utc::now().timestamp();
-            let candidates: Vec<_> = records
-                .iter()
-                .filter(|r| params.actor.as_ref().map_or(true, |a| &r.actor == a))
-                .filter(|r| r.expires_at.map_or(true, |exp| exp > now_ts_consolidate))
-                .collect();
+    let ms = store.snapshot();
+    let records = ms.all();
+    let now_ts_consolidate = chrono::Utc::now().timestamp();
+    let candidates: Vec<_> = records
+        .iter()
+        .filter(|r| params.actor.as_ref().is_none_or(|a| &r.actor == a))
+        .filter(|r| r.expires_at.is_none_or(|exp| exp > now_ts_consolidate))
+        .collect();
 
-            // Find near-duplicate pairs by Jaccard token similarity on `.target`
-            let mut pairs: Vec<(String, String, u32)> = Vec::new(); // (keep_id, drop_id, pct)
-            let mut drop_set: std::collections::HashSet<String> = std::collections::HashSet::new();
-            for i in 0..candidates.len() {
-                for j in (i + 1)..candidates.len() {
-                    // Skip records already marked for dropping
-                    if drop_set.contains(&candidates[j].id.to_string()) {
-                        continue;
-                    }
-                    let words_i: std::collections::HashSet<&str> =
-                        candidates[i].target.split_whitespace().collect();
-                    let words_j: std::collections::HashSet<&str> =
-                        candidates[j].target.split_whitespace().collect();
-                    if words_i.is_empty() || words_j.is_empty() {
-                        continue;
-                    }
-                    let intersection = words_i.intersection(&words_j).count();
-                    let sim = intersection as f64 / words_i.len().max(words_j.len()) as f64;
-                    if sim >= threshold {
-                        // Keep newer; drop older
-                        let (keep, drop) = if candidates[i].timestamp >= candidates[j].timestamp {
-                            (candidates[i].id.to_string(), candidates[j].id.to_string())
-                        } else {
-                            (candidates[j].id.to_string(), candidates[i].id.to_string())
-                        };
-                        drop_set.insert(drop.clone());
-                        pairs.push((keep, drop, (sim * 100.0) as u32));
-                    }
-                }
+    // Find near-duplicate pairs by Jaccard token similarity on `.target`
+    let mut pairs: Vec<(String, String, u32)> = Vec::new(); // (keep_id, drop_id, pct)
+    let mut drop_set: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            // Skip records already marked for dropping
+            if drop_set.contains(&candidates[j].id.to_string()) {
+                continue;
             }
-
-            let found = pairs.len();
-            let mut deleted = 0usize;
-
-            if !dry_run && !pairs.is_empty() {
-                for (_, drop_id, _) in &pairs {
-                    if let Ok(uuid) = uuid::Uuid::parse_str(drop_id) {
-                        if ms.delete_by_id(uuid) {
-                            deleted += 1;
-                        }
-                    }
-                }
+            let words_i: std::collections::HashSet<&str> =
+                candidates[i].target.split_whitespace().collect();
+            let words_j: std::collections::HashSet<&str> =
+                candidates[j].target.split_whitespace().collect();
+            if words_i.is_empty() || words_j.is_empty() {
+                continue;
             }
-
-            Json(serde_json::json!({
-                "found_duplicates": found,
-                "dry_run": dry_run,
-                "deleted": deleted,
-                "pairs": pairs.iter().map(|(k, d, s)| serde_json::json!({
-                    "keep": k, "drop": d, "similarity_pct": s
-                })).collect::<Vec<_>>(),
-                "note": if dry_run {
-                    "Dry run — no changes made. Re-run without ?dry_run=true to execute."
+            let intersection = words_i.intersection(&words_j).count();
+            let sim = intersection as f64 / words_i.len().max(words_j.len()) as f64;
+            if sim >= threshold {
+                // Keep newer; drop older
+                let (keep, drop) = if candidates[i].timestamp >= candidates[j].timestamp {
+                    (candidates[i].id.to_string(), candidates[j].id.to_string())
                 } else {
-                    "Duplicates deleted. Re-run with ?dry_run=true to preview without changes."
-                }
-            }))
+                    (candidates[j].id.to_string(), candidates[i].id.to_string())
+                };
+                drop_set.insert(drop.clone());
+                pairs.push((keep, drop, (sim * 100.0) as u32));
+            }
         }
     }
+
+    let found = pairs.len();
+    let mut deleted = 0usize;
+
+    if !dry_run && !pairs.is_empty() {
+        let drop_ids: Vec<uuid::Uuid> = pairs
+            .iter()
+            .filter_map(|(_, drop_id, _)| uuid::Uuid::parse_str(drop_id).ok())
+            .collect();
+        deleted = match store
+            .transact(move |ms| Ok(drop_ids.into_iter().filter(|id| ms.delete_by_id(*id)).count()))
+            .await
+        {
+            Ok(n) => n,
+            Err(e) => return Json(serde_json::json!({"error": e.to_string()})),
+        };
+    }
+
+    Json(serde_json::json!({
+        "found_duplicates": found,
+        "dry_run": dry_run,
+        "deleted": deleted,
+        "pairs": pairs.iter().map(|(k, d, s)| serde_json::json!({
+            "keep": k, "drop": d, "similarity_pct": s
+        })).collect::<Vec<_>>(),
+        "note": if dry_run {
+            "Dry run — no changes made. Re-run without ?dry_run=true to execute."
+        } else {
+            "Duplicates deleted. Re-run with ?dry_run=true to preview without changes."
+        }
+    }))
 }
 
 #[cfg(feature = "web-server")]
 async fn handle_prometheus_metrics<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
 ) -> axum::response::Response<String> {
-    let (total, by_type, actors, metered) = match store.lock() {
-        Ok(ms) => {
-            let records = ms.all();
-            let total = records.len();
-            let mut by_type: std::collections::HashMap<String, usize> =
-                std::collections::HashMap::new();
-            let mut actors: std::collections::HashSet<&str> = std::collections::HashSet::new();
-            for r in records {
-                *by_type.entry(format!("{:?}", r.record_type)).or_insert(0) += 1;
-                actors.insert(&r.actor);
-            }
-            (total, by_type, actors.len(), !load_api_keys().is_empty())
+    let (total, by_type, actors, metered) = {
+        let ms = store.snapshot();
+        let records = ms.all();
+        let total = records.len();
+        let mut by_type: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        let mut actors: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        for r in records {
+            *by_type.entry(format!("{:?}", r.record_type)).or_insert(0) += 1;
+            actors.insert(&r.actor);
         }
-        Err(_) => (0, std::collections::HashMap::new(), 0, false),
+        (total, by_type, actors.len(), !load_api_keys().is_empty())
     };
 
     let mut lines = vec![
@@ -3078,13 +3399,15 @@ async fn handle_prometheus_metrics<B: MemoryBackend + Send + Sync + 'static>(
         "# TYPE hipcortex_metering_enabled gauge".to_string(),
         format!("hipcortex_metering_enabled {}", if metered { 1 } else { 0 }),
     ];
+    lines.push("# TYPE hipcortex_records_by_type gauge".to_string());
     for (t, count) in &by_type {
         lines.push(format!(
             "hipcortex_records_by_type{{type=\"{}\"}} {}",
             t, count
         ));
     }
-    lines.push(String::new());
+    // Subsystem latency histograms and counters (add/search/transact, gate, LLM, …)
+    lines.push(crate::telemetry::METRICS.render_prometheus());
 
     axum::response::Response::builder()
         .header("Content-Type", "text/plain; version=0.0.4; charset=utf-8")
@@ -3203,7 +3526,7 @@ async fn handle_graph_search(
 pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     addr: SocketAddr,
     symbolic_store: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
 ) {
     // Backward-compat: no AppState available here, use a no-op world model
     let world_model: Arc<RwLock<WorldModelEnhanced>> =
@@ -3238,7 +3561,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
 
     // Memory store routes
     let add_memory_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         let wm = world_model.clone();
         let arc = archive_store.clone();
         let sym = symbolic_store.clone();
@@ -3250,7 +3573,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let bulk_add_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Json(req): Json<BulkAddRequest>| async move {
             handle_bulk_add(store, Json(req)).await
         })
@@ -3259,7 +3582,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     let query_memory_route = {
         let store = memory_store.clone();
         get(move |Query(params): Query<QueryMemoryParams>| async move {
-            handle_query_memory(store, params).await
+            handle_query_memory(store, None, params).await
         })
     };
 
@@ -3267,13 +3590,13 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     let search_route = {
         let store = memory_store.clone();
         post(move |Json(req): Json<SearchMemoryRequest>| async move {
-            handle_search_memory(store, Json(req)).await
+            handle_search_memory(store, None, Json(req)).await
         })
     };
 
     // GDPR forget: DELETE /memory/forget/:actor
     let forget_route = {
-        let ms = memory_store.clone();
+        let ms = AsyncMemoryStore::from(memory_store.clone());
         let ss = symbolic_store.clone();
         delete(
             move |Path(actor): Path<String>| async move { handle_forget_actor(ms, ss, actor).await },
@@ -3282,7 +3605,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
 
     // Embed and add: POST /memory/embed
     let embed_add_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Json(req): Json<EmbedAndAddRequest>| async move {
             handle_embed_and_add(store, Json(req)).await
         })
@@ -3321,7 +3644,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
 
     // PATCH /memory/update/:id — versioned in-place update
     let update_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         patch(
             move |Path(id): Path<String>, Json(req): Json<UpdateMemoryRequest>| async move {
                 handle_update_memory(store, id, Json(req)).await
@@ -3338,14 +3661,14 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let audit_verify_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         get(move || {
             let s = store.clone();
             async move { handle_audit_verify(s).await }
         })
     };
     let audit_export_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         get(move || {
             let s = store.clone();
             async move { handle_audit_export(s).await }
@@ -3375,14 +3698,14 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
         })
     };
     let consolidate_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Query(params): Query<ConsolidateParams>| async move {
             handle_consolidate(store, Query(params)).await
         })
     };
 
     let ingest_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         let wm = world_model.clone();
         post(move |Json(req): Json<IngestRequest>| async move {
             handle_ingest(store, wm, Json(req)).await
@@ -3390,28 +3713,28 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let quarantine_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_quarantine_memory(s, id).await }
         })
     };
     let restore_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_restore_memory(s, id).await }
         })
     };
     let corroborate_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_corroborate(s, id).await }
         })
     };
     let contradict_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| {
             let s = store.clone();
             async move { handle_contradict(s, id).await }
@@ -3433,22 +3756,24 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
     };
 
     let goal_react_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         post(move |Path(id): Path<String>| async move {
             let goal_id = match uuid::Uuid::parse_str(&id) {
                 Ok(u) => u,
                 Err(_) => return Json(serde_json::json!({"error": "invalid uuid"})),
             };
-            let mut engine = crate::loop_engine::ReactEngine::new();
-            let result = {
-                let mut s = store.lock().unwrap();
-                engine.run(&mut s, goal_id, 0)
-            };
+            let result = store
+                .transact(move |s| {
+                    let mut engine = crate::loop_engine::ReactEngine::new();
+                    Ok(engine.run(s, goal_id, 0))
+                })
+                .await;
             match result {
-                Ok(status) => Json(
+                Ok(Ok(status)) => Json(
                     serde_json::json!({"goal_id": goal_id.to_string(), "status": format!("{:?}", status)}),
                 ),
-                Err(e) => Json(serde_json::json!({"error": e})),
+                Ok(Err(e)) => Json(serde_json::json!({"error": e})),
+                Err(e) => Json(serde_json::json!({"error": e.to_string()})),
             }
         })
     };
@@ -3461,7 +3786,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
                 Err(_) => return Json(serde_json::json!({"error": "invalid uuid"})),
             };
             let records: Vec<_> = {
-                let s = store.lock().unwrap();
+                let s = store.snapshot();
                 s.all()
                     .iter()
                     .filter(|r| r.derived_from == Some(goal_id))
@@ -3491,7 +3816,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
                 _ => return Json(serde_json::json!({"error": "from_id and to_id required"})),
             };
             let (from, to) = {
-                let s = store.lock().unwrap();
+                let s = store.snapshot();
                 let from = match s.find_by_id(from_id) {
                     Some(r) => r.clone(),
                     None => return Json(serde_json::json!({"error": "from_id not found"})),
@@ -3520,7 +3845,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
             match &txl {
                 None => Json(serde_json::json!({"error": "tx_log not configured"})),
                 Some(log) => {
-                    let ms = store.lock().unwrap();
+                    let ms = store.snapshot();
                     match crate::state_diff::compute_tx_diff(log, from_tx, to_tx, &*ms) {
                         Ok(diff) => Json(
                             serde_json::to_value(diff)
@@ -3535,7 +3860,7 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
 
     // POST /v1/memory/consolidate — manual consolidation trigger
     let v1_consolidate_route = {
-        let store = memory_store.clone();
+        let store = AsyncMemoryStore::from(memory_store.clone());
         let arc = archive_store.clone();
         let sym = symbolic_store.clone();
         let txl = tx_log_arc.clone();
@@ -3548,25 +3873,30 @@ pub async fn run_with_both_stores<B: MemoryBackend + Send + Sync + 'static>(
                 min_group_size: min_group,
                 ..Default::default()
             };
-            let dummy_log;
-            let log_ref: &crate::tx_log::TxLog = match &txl {
-                Some(l) => l,
-                None => {
-                    let dir = std::env::temp_dir();
-                    dummy_log =
-                        crate::tx_log::TxLog::open(dir.join("hc-consolidate-tmp.jsonl")).unwrap();
-                    &dummy_log
-                }
-            };
-            let mut ms = store.lock().unwrap();
-            let mut arc = arc.lock().unwrap();
-            let mut sym = sym.lock().unwrap();
-            match crate::consolidation::consolidate(&mut ms, &mut arc, &mut sym, log_ref, &config) {
-                Ok(r) => Json(
+            let consolidated = store
+                .transact(move |ms| {
+                    let dummy_log;
+                    let log_ref: &crate::tx_log::TxLog = match &txl {
+                        Some(l) => l,
+                        None => {
+                            let dir = std::env::temp_dir();
+                            dummy_log = crate::tx_log::TxLog::open(dir.join("hc-consolidate-tmp.jsonl"))
+                                .map_err(anyhow::Error::msg)?;
+                            &dummy_log
+                        }
+                    };
+                    let mut arc = arc.lock().unwrap();
+                    let mut sym = sym.lock().unwrap();
+                    Ok(crate::consolidation::consolidate(ms, &mut arc, &mut sym, log_ref, &config))
+                })
+                .await;
+            match consolidated {
+                Ok(Ok(r)) => Json(
                     serde_json::to_value(r)
                         .unwrap_or(serde_json::json!({"error": "serialization failed"})),
                 ),
-                Err(e) => Json(serde_json::json!({"error": e})),
+                Ok(Err(e)) => Json(serde_json::json!({"error": e})),
+                Err(e) => Json(serde_json::json!({"error": e.to_string()})),
             }
         })
     };
@@ -4074,7 +4404,7 @@ fn extract_tags(lower: &str, context: Option<&str>) -> Vec<String> {
 /// Target UX: client.remember("text") with no memory architecture required.
 #[cfg(feature = "web-server")]
 async fn handle_ingest<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     world_model: Arc<RwLock<WorldModelEnhanced>>,
     Json(req): Json<IngestRequest>,
 ) -> Result<Json<IngestResponse>, (StatusCode, Json<IngestResponse>)> {
@@ -4104,14 +4434,15 @@ async fn handle_ingest<B: MemoryBackend + Send + Sync + 'static>(
     );
     record.confidence = confidence;
     record.source = Some("auto-ingest".to_string());
-    record.priority = priority.clone();
+    record.priority = Priority::parse_lenient(&priority);
     record.tags = tags.clone();
     if let Some(ttl_secs) = ttl {
         record.expires_at = Some((chrono::Utc::now().timestamp()) + ttl_secs as i64);
     }
 
     // Check contradiction
-    let warning = store.try_lock().ok().and_then(|ms| {
+    let warning = {
+        let ms = store.snapshot();
         let existing = ms.find_by_actor(&actor);
         let new_words: std::collections::HashSet<&str> = req.text.split_whitespace().collect();
         let conflicts: Vec<serde_json::Value> = existing.iter().take(30)
@@ -4127,59 +4458,73 @@ async fn handle_ingest<B: MemoryBackend + Send + Sync + 'static>(
             .take(2)
             .collect();
         if conflicts.is_empty() { None } else { Some(serde_json::json!(conflicts)) }
-    });
+    };
 
     let working_memory = ttl.is_some() && ttl.unwrap_or(0) <= 86400;
     let record_id = record.id.to_string();
     let rtype = format!("{:?}", record.record_type);
 
-    match store.lock() {
-        Ok(mut ms) => match ms.add(record.clone()) {
-            Ok(_) => {
-                // Auto-feed WorldModelEnhanced — non-blocking, best-effort
-                if let Ok(mut wm) = world_model.try_write() {
-                    let _ = wm.observe_transition(
-                        record.actor.clone(),
-                        record.action.clone(),
-                        record.target.clone(),
-                    );
-                    if record.priority == "pinned" && record.record_type == MemoryType::Symbolic {
-                        let _ = wm.add_causal_edge(record.actor.clone(), record.target.clone());
-                    }
+    // Injection scoring and the write screen both classify: run them on the
+    // blocking pool against a snapshot, not under the writer lock. Screen
+    // before the clone so the world model and the response see the redacted
+    // text.
+    let snapshot = store.snapshot();
+    let screened = tokio::task::spawn_blocking(move || {
+        let trust = snapshot.source_trust.get_trust("auto-ingest");
+        let assessment = crate::injection_defense::mark_on_ingest(
+            &mut record,
+            Some(trust),
+            &crate::injection_defense::InjectionConfig::default(),
+        );
+        let injection = (assessment.level != crate::injection_defense::InjectionLevel::Clean)
+            .then(|| serde_json::json!(assessment));
+        snapshot.screen(&mut record)?;
+        Ok::<_, anyhow::Error>((record, injection))
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|screened| screened);
+    let added = match screened {
+        Ok((record, injection)) => {
+            store
+                .transact(move |ms| {
+                    ms.add_prescreened(record.clone())?;
+                    Ok((record, injection))
+                })
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    match added {
+        Ok((record, injection)) => {
+            // Auto-feed WorldModelEnhanced — non-blocking, best-effort
+            if let Ok(wm) = world_model.try_write() {
+                let _ = wm.observe_transition(
+                    record.actor.clone(),
+                    record.action.clone(),
+                    record.target.clone(),
+                );
+                if record.priority == Priority::Pinned && record.record_type == MemoryType::Symbolic {
+                    let _ = wm.add_causal_edge(record.actor.clone(), record.target.clone());
                 }
-                Ok(Json(IngestResponse {
-                    record_id,
-                    record_type: rtype,
-                    priority,
-                    tags,
-                    ttl_seconds: ttl,
-                    confidence,
-                    actor,
-                    action,
-                    target: req.text,
-                    working_memory,
-                    warning,
-                }))
             }
-            Err(e) => Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(IngestResponse {
-                    record_id: String::new(),
-                    record_type: String::new(),
-                    priority: String::new(),
-                    tags: vec![],
-                    ttl_seconds: None,
-                    confidence: 0.0,
-                    actor: String::new(),
-                    action: String::new(),
-                    target: req.text,
-                    working_memory: false,
-                    warning: Some(serde_json::json!({"error": e.to_string()})),
-                }),
-            )),
-        },
+            Ok(Json(IngestResponse {
+                record_id,
+                record_type: rtype,
+                priority,
+                tags: record.tags.clone(),
+                ttl_seconds: ttl,
+                confidence,
+                actor,
+                action,
+                target: record.target.clone(),
+                working_memory,
+                warning,
+                injection,
+            }))
+        }
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            write_status(&e, StatusCode::INTERNAL_SERVER_ERROR),
             Json(IngestResponse {
                 record_id: String::new(),
                 record_type: String::new(),
@@ -4191,7 +4536,8 @@ async fn handle_ingest<B: MemoryBackend + Send + Sync + 'static>(
                 action: String::new(),
                 target: req.text,
                 working_memory: false,
-                warning: Some(serde_json::json!({"error": format!("Lock error: {}", e)})),
+                warning: Some(serde_json::json!({"error": e.to_string()})),
+                injection: None,
             }),
         )),
     }
@@ -4214,7 +4560,7 @@ fn parse_record_type_alias(s: Option<&str>) -> crate::memory_record::MemoryType
 
 #[cfg(feature = "web-server")]
 async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     world_model: Arc<RwLock<WorldModelEnhanced>>,
     archive_store: Arc<Mutex<ArchiveStore>>,
     symbolic_store: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
@@ -4222,27 +4568,8 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
     calibration: Arc<CalibrationTracker>,
     req: AddMemoryRequest,
 ) -> Result<Json<AddMemoryResponse>, (StatusCode, Json<AddMemoryResponse>)> {
-    // Safety: classify free-text target/action before mutation
-    {
-        let ctx = format!("{} {} {}", req.actor, req.action, req.target);
-        if let Ok(mut guard) = crate::safety_guardrail::SAFETY_GUARDRAIL.lock() {
-            if let Err(reason) = guard.check_precondition(&ctx) {
-                return Err((
-                    StatusCode::FORBIDDEN,
-                    Json(AddMemoryResponse {
-                        success: false,
-                        record_id: None,
-                        error: Some(reason),
-                        warning: None,
-                    }),
-                ));
-            }
-        }
-    }
-
     let record_type = parse_record_type_alias(req.record_type.as_deref());
 
-    let actor_name = req.actor.clone();
     let mut record = MemoryRecord::new(
         record_type,
         req.actor,
@@ -4263,7 +4590,7 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
     if !req.tags.is_empty() {
         record.tags = req.tags;
     }
-    if req.priority != "normal" {
+    if req.priority != Priority::Normal {
         record.priority = req.priority;
     }
 
@@ -4282,15 +4609,69 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
         }
     }
 
-    match store.lock() {
-        Ok(mut ms) => {
+    // Safety: the store's write screen, run up front so the contradiction
+    // check, webhook and response only ever see the screened record.
+    // Depending on policy, PII is tokenized into the vault, the record is
+    // quarantined, or the write is refused. Then injection defense weighs the
+    // record's injection score by its source's trust; suspicious records are
+    // tagged, high-risk ones are stored quarantined. Both classify, so they
+    // run on the blocking pool against a snapshot, not under the writer lock.
+    let snapshot = store.snapshot();
+    let screened = tokio::task::spawn_blocking(move || {
+        let outcome = snapshot.screen(&mut record)?;
+        let trust = record
+            .source
+            .as_deref()
+            .map(|s| snapshot.source_trust.get_trust(s));
+        let injection = crate::injection_defense::mark_on_ingest(
+            &mut record,
+            trust,
+            &crate::injection_defense::InjectionConfig::default(),
+        );
+        Ok::<_, anyhow::Error>((record, outcome, injection))
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|screened| screened);
+    let (record, outcome, injection) = match screened {
+        Ok(screened) => screened,
+        Err(e) => {
+            return Err((
+                write_status(&e, StatusCode::INTERNAL_SERVER_ERROR),
+                Json(AddMemoryResponse {
+                    success: false,
+                    record_id: None,
+                    error: Some(e.to_string()),
+                    warning: None,
+                    safety: None,
+                }),
+            ));
+        }
+    };
+    let mut safety = outcome
+        .filter(|o| o.action != crate::safety_classifier::Action::Allow)
+        .map(|o| serde_json::json!(o));
+    if injection.level != crate::injection_defense::InjectionLevel::Clean {
+        let mut report = match safety.take() {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        report.insert("injection".to_string(), serde_json::json!(injection));
+        safety = Some(serde_json::Value::Object(report));
+    }
+
+    let shared = store.shared().clone();
+    let added = store
+        .transact(move |ms| {
+            let actor_name = record.actor.clone();
+
             // Actor memory quota check (env var HIPCORTEX_ACTOR_MAX_RECORDS, default: unlimited)
             // Done inside the same lock to avoid TOCTOU and double-lock issues.
             if let Ok(max_str) = std::env::var("HIPCORTEX_ACTOR_MAX_RECORDS") {
                 if let Ok(max) = max_str.parse::<usize>() {
                     let actor_count = ms.find_by_actor(&actor_name).len();
                     if actor_count >= max {
-                        return Err((
+                        return Ok(Err((
                             StatusCode::from_u16(429).unwrap(),
                             Json(AddMemoryResponse {
                                 success: false,
@@ -4300,8 +4681,9 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                                     actor_name, max
                                 )),
                                 warning: None,
+                                safety: None,
                             }),
-                        ));
+                        )));
                     }
                 }
             }
@@ -4344,20 +4726,21 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                 }
             };
 
-            match ms.add(record.clone()) {
+            Ok(match ms.add_prescreened(record.clone()) {
                 Ok(_) => {
                     // TxLog append + auto-consolidation trigger (non-blocking, best-effort)
                     if let Some(ref log) = tx_log {
-                        log.append(
+                        log.append_with_changes(
                             crate::tx_log::TxKind::MemoryAdd,
                             vec![record.id],
                             &record.actor,
+                            ms.take_changes(),
                         );
                         let config = ConsolidationConfig::default();
                         let should_consolidate =
-                            compute_pressure(&ms, &config) > config.pressure_threshold;
+                            compute_pressure(ms, &config) > config.pressure_threshold;
                         if should_consolidate {
-                            let store2 = store.clone();
+                            let store2 = shared.clone();
                             let arc2 = archive_store.clone();
                             let sym2 = symbolic_store.clone();
                             let log2 = log.clone();
@@ -4373,7 +4756,7 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                     }
                     // Auto-feed WorldModelEnhanced — non-blocking (try_write), best-effort
                     // A failed lock acquisition simply skips the feed without blocking the request
-                    if let Ok(mut wm) = world_model.try_write() {
+                    if let Ok(wm) = world_model.try_write() {
                         let _ = wm.observe_transition(
                             record.actor.clone(),
                             record.action.clone(),
@@ -4382,7 +4765,7 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                         // Register causal edge for any Symbolic record OR any pinned record.
                         // Symbolic = intentional decision (feeds causal reasoning regardless of priority).
                         // Pinned = explicitly important (feeds causal reasoning regardless of type).
-                        if record.record_type == MemoryType::Symbolic || record.priority == "pinned"
+                        if record.record_type == MemoryType::Symbolic || record.priority == Priority::Pinned
                         {
                             let _ = wm.add_causal_edge(record.actor.clone(), record.target.clone());
                         }
@@ -4398,14 +4781,15 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                         }),
                     );
                     {
-                        let pressure = compute_pressure(&ms, &ConsolidationConfig::default());
-                        calibration.update_from_store(&ms, pressure, 0);
+                        let pressure = compute_pressure(ms, &ConsolidationConfig::default());
+                        calibration.update_from_store(ms, pressure, 0);
                     }
                     Ok(Json(AddMemoryResponse {
                         success: true,
                         record_id: Some(record.id.to_string()),
                         error: None,
                         warning: contradiction_warning,
+                        safety,
                     }))
                 }
                 Err(e) => Err((
@@ -4415,17 +4799,22 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
                         record_id: None,
                         error: Some(e.to_string()),
                         warning: None,
+                        safety: None,
                     }),
                 )),
-            }
-        }
+            })
+        })
+        .await;
+    match added {
+        Ok(result) => result,
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(AddMemoryResponse {
                 success: false,
                 record_id: None,
-                error: Some(format!("Lock error: {}", e)),
+                error: Some(e.to_string()),
                 warning: None,
+                safety: None,
             }),
         )),
     }
@@ -4433,121 +4822,119 @@ async fn handle_add_memory<B: MemoryBackend + Send + Sync + 'static>(
 
 #[cfg(feature = "web-server")]
 async fn handle_query_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
+    tx_log: Option<Arc<TxLog>>,
     params: QueryMemoryParams,
 ) -> Result<Json<QueryMemoryResponse>, (StatusCode, Json<QueryMemoryResponse>)> {
-    match store.lock() {
-        Ok(store) => {
-            let all_records = store.all();
-            let mut filtered_records = all_records.iter().collect::<Vec<_>>();
-
-            // Apply filters
-            // Multi-actor filter takes precedence over single actor
-            if let Some(actors_str) = &params.actors {
-                let actor_list: Vec<&str> = actors_str.split(',').map(|a| a.trim()).collect();
-                filtered_records.retain(|r| actor_list.contains(&r.actor.as_str()));
-            } else if let Some(actor) = &params.actor {
-                filtered_records.retain(|r| r.actor == *actor);
-            }
-            // Exclude quarantined unless explicitly requested
-            let include_quarantined = params.include_quarantined.as_deref() == Some("true");
-            if !include_quarantined {
-                filtered_records.retain(|r| r.status != "quarantine");
-            }
-            if let Some(action) = &params.action {
-                filtered_records.retain(|r| r.action == *action);
-            }
-            if let Some(record_type) = &params.record_type {
-                let target_type = match record_type.as_str() {
-                    "Temporal" => MemoryType::Temporal,
-                    "Symbolic" => MemoryType::Symbolic,
-                    "Procedural" => MemoryType::Procedural,
-                    "Reflexion" => MemoryType::Reflexion,
-                    "Perception" => MemoryType::Perception,
-                    _ => {
-                        return Err((
-                            StatusCode::BAD_REQUEST,
-                            Json(QueryMemoryResponse {
-                                records: vec![],
-                                total: 0,
-                            }),
-                        ))
-                    }
-                };
-                filtered_records.retain(|r| r.record_type == target_type);
-            }
-
-            // Exclude records past their TTL (unless ?include_expired=true for debugging)
-            let now_ts = chrono::Utc::now().timestamp();
-            let include_expired = params.include_expired.as_deref() == Some("true");
-            if !include_expired {
-                filtered_records.retain(|r| r.expires_at.map_or(true, |exp| exp > now_ts));
-            }
-
-            // Filter by tags (any match)
-            if let Some(tags_str) = &params.tags {
-                let tag_list: Vec<&str> = tags_str.split(',').map(|t| t.trim()).collect();
-                filtered_records
-                    .retain(|r| tag_list.iter().any(|t| r.tags.contains(&t.to_string())));
-            }
-            // Filter by priority
-            if let Some(priority) = &params.priority {
-                filtered_records.retain(|r| &r.priority == priority);
-            }
-            // Filter by as_of timestamp (time-travel query)
-            if let Some(as_of_str) = &params.as_of {
-                if let Ok(as_of_ts) = chrono::DateTime::parse_from_rfc3339(as_of_str) {
-                    let as_of_utc = as_of_ts.with_timezone(&chrono::Utc);
-                    filtered_records.retain(|r| r.timestamp <= as_of_utc);
-                }
+    let snapshot = time_travel_store(tx_log, params.as_of.as_deref())
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(QueryMemoryResponse::failed(e))))?;
+    let store = store.snapshot();
+    let all_records = match &snapshot {
+        Some(snap) => snap.all(),
+        None => store.all(),
+    };
+    let mut filtered_records = all_records.iter().collect::<Vec<_>>();
+
+    // Apply filters
+    // Multi-actor filter takes precedence over single actor
+    if let Some(actors_str) = &params.actors {
+        let actor_list: Vec<&str> = actors_str.split(',').map(|a| a.trim()).collect();
+        filtered_records.retain(|r| actor_list.contains(&r.actor.as_str()));
+    } else if let Some(actor) = &params.actor {
+        filtered_records.retain(|r| r.actor == *actor);
+    }
+    // Exclude quarantined unless explicitly requested
+    let include_quarantined = params.include_quarantined.as_deref() == Some("true");
+    if !include_quarantined {
+        filtered_records.retain(|r| r.status != RecordStatus::Quarantine);
+    }
+    if let Some(action) = &params.action {
+        filtered_records.retain(|r| r.action == *action);
+    }
+    if let Some(record_type) = &params.record_type {
+        let target_type = match record_type.as_str() {
+            "Temporal" => MemoryType::Temporal,
+            "Symbolic" => MemoryType::Symbolic,
+            "Procedural" => MemoryType::Procedural,
+            "Reflexion" => MemoryType::Reflexion,
+            "Perception" => MemoryType::Perception,
+            other => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(QueryMemoryResponse::failed(format!(
+                        "unknown record_type {other:?}"
+                    ))),
+                ))
             }
+        };
+        filtered_records.retain(|r| r.record_type == target_type);
+    }
 
-            // Sort by timestamp descending (newest first) before applying limit
-            filtered_records.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    // Exclude records past their TTL (unless ?include_expired=true for debugging)
+    let now_ts = chrono::Utc::now().timestamp();
+    let include_expired = params.include_expired.as_deref() == Some("true");
+    if !include_expired {
+        filtered_records.retain(|r| r.expires_at.is_none_or(|exp| exp > now_ts));
+    }
 
-            // Apply limit
-            let limit = params.limit.unwrap_or(100);
-            filtered_records.truncate(limit);
-
-            let response_records = filtered_records
-                .into_iter()
-                .map(|r| MemoryRecordResponse {
-                    id: r.id.to_string(),
-                    record_type: format!("{:?}", r.record_type),
-                    timestamp: r.timestamp.to_rfc3339(),
-                    actor: r.actor.clone(),
-                    action: r.action.clone(),
-                    target: r.target.clone(),
-                    metadata: r.metadata.clone(),
-                    integrity: r.integrity.clone(),
-                    confidence: r.confidence,
-                    source: r.source.clone(),
-                    priority: r.priority.clone(),
-                    tags: r.tags.clone(),
-                    version: r.version,
-                    status: r.status.clone(),
-                    expires_at: r.expires_at,
-                })
-                .collect::<Vec<_>>();
-
-            Ok(Json(QueryMemoryResponse {
-                total: response_records.len(),
-                records: response_records,
-            }))
+    // Filter by tags (any match)
+    if let Some(tags_str) = &params.tags {
+        let tag_list: Vec<&str> = tags_str.split(',').map(|t| t.trim()).collect();
+        filtered_records
+            .retain(|r| tag_list.iter().any(|t| r.tags.contains(&t.to_string())));
+    }
+    // Filter by priority
+    if let Some(priority) = &params.priority {
+        let priority = Priority::parse_lenient(priority);
+        filtered_records.retain(|r| r.priority == priority);
+    }
+    // Filter by as_of timestamp (time-travel query)
+    if let Some(as_of_str) = &params.as_of {
+        if let Ok(as_of_ts) = chrono::DateTime::parse_from_rfc3339(as_of_str) {
+            let as_of_utc = as_of_ts.with_timezone(&chrono::Utc);
+            filtered_records.retain(|r| r.timestamp <= as_of_utc);
         }
-        Err(_e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(QueryMemoryResponse {
-                records: vec![],
-                total: 0,
-            }),
-        )),
     }
+
+    // Sort by timestamp descending (newest first) before applying limit
+    filtered_records.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+
+    // Apply limit
+    let limit = params.limit.unwrap_or(100);
+    filtered_records.truncate(limit);
+
+    let response_records = filtered_records
+        .into_iter()
+        .map(|r| MemoryRecordResponse {
+            id: r.id.to_string(),
+            record_type: format!("{:?}", r.record_type),
+            timestamp: r.timestamp.to_rfc3339(),
+            actor: r.actor.clone(),
+            action: r.action.clone(),
+            target: r.target.clone(),
+            metadata: r.metadata.clone(),
+            integrity: r.integrity.clone(),
+            confidence: r.confidence,
+            source: r.source.clone(),
+            priority: r.priority,
+            tags: r.tags.clone(),
+            version: r.version,
+            status: r.status,
+            expires_at: r.expires_at,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(QueryMemoryResponse {
+        total: response_records.len(),
+        records: response_records,
+        error: None,
+    }))
 }
 
 #[cfg(feature = "web-server")]
 async fn handle_forget_actor<B: MemoryBackend + Send + Sync + 'static>(
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: AsyncMemoryStore<B>,
     symbolic_store: Arc<
         Mutex<crate::symbolic_store::SymbolicStore<crate::symbolic_store::InMemoryGraph>>,
     >,
@@ -4570,22 +4957,12 @@ async fn handle_forget_actor<B: MemoryBackend + Send + Sync + 'static>(
     }
 
     // Delete from temporal/procedural/reflexion memory store
-    let records_deleted = match memory_store.lock() {
-        Ok(mut ms) => match ms.delete_by_actor(&actor) {
-            Ok(ids) => ids.len(),
-            Err(e) => {
-                return Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ForgetActorResponse {
-                        success: false,
-                        actor,
-                        records_deleted: 0,
-                        symbolic_nodes_deleted: 0,
-                        error: Some(e.to_string()),
-                    }),
-                ))
-            }
-        },
+    let forgotten = actor.clone();
+    let records_deleted = match memory_store
+        .transact(move |ms| ms.delete_by_actor(&forgotten))
+        .await
+    {
+        Ok(ids) => ids.len(),
         Err(e) => {
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -4594,7 +4971,7 @@ async fn handle_forget_actor<B: MemoryBackend + Send + Sync + 'static>(
                     actor,
                     records_deleted: 0,
                     symbolic_nodes_deleted: 0,
-                    error: Some(format!("Lock error: {}", e)),
+                    error: Some(e.to_string()),
                 }),
             ))
         }
@@ -4672,7 +5049,7 @@ async fn handle_delete_webhook(Path(id): Path<String>) -> StatusCode {
 /// DELETE /memory/:id — delete a single memory record by UUID
 #[cfg(feature = "web-server")]
 async fn handle_delete_memory<B: MemoryBackend + Send + Sync + 'static>(
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: AsyncMemoryStore<B>,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let uuid = uuid::Uuid::parse_str(&id).map_err(|_| {
@@ -4682,14 +5059,14 @@ async fn handle_delete_memory<B: MemoryBackend + Send + Sync + 'static>(
         )
     })?;
 
-    let mut ms = memory_store.lock().map_err(|e| {
+    let deleted = memory_store.delete(uuid).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"success": false, "error": format!("lock: {}", e)})),
+            Json(serde_json::json!({"success": false, "error": e.to_string()})),
         )
     })?;
 
-    if ms.delete_by_id(uuid) {
+    if deleted {
         Ok(Json(serde_json::json!({"success": true})))
     } else {
         Err((
@@ -4699,6 +5076,159 @@ async fn handle_delete_memory<B: MemoryBackend + Send + Sync + 'static>(
     }
 }
 
+// ── Point-in-time recovery ────────────────────────────────────────────────────
+
+/// Resolve an `as_of` param to a replayed copy of the store. `Ok(None)` means
+/// read the live store: no `as_of`, or a timestamp with no tx log to replay
+/// (the caller then only filters by record timestamp). A tx id needs a log;
+/// an unparseable `as_of` is an error, never a silent live read. The replay
+/// reads the log and a checkpoint from disk, so it runs on the blocking pool.
+#[cfg(feature = "web-server")]
+async fn time_travel_store(
+    tx_log: Option<Arc<TxLog>>,
+    as_of: Option<&str>,
+) -> Result<Option<MemoryStore<crate::persistence::InMemoryBackend>>, String> {
+    let Some(raw) = as_of else { return Ok(None) };
+    let point = crate::pitr::AsOf::parse(raw)?;
+    match (tx_log, point) {
+        (Some(log), _) => {
+            tokio::task::spawn_blocking(move || crate::pitr::store_as_of(&log, point).map(Some))
+                .await
+                .map_err(|e| format!("replay task failed: {e}"))?
+        }
+        (None, crate::pitr::AsOf::Tx(_)) => Err("as_of=<tx> requires a tx log".into()),
+        (None, crate::pitr::AsOf::TimestampMs(_)) => Ok(None),
+    }
+}
+
+/// GET /memory/:id?as_of= — fetch one record, optionally as of a past tx or time.
+#[cfg(feature = "web-server")]
+async fn handle_get_memory<B: MemoryBackend + Send + Sync + 'static>(
+    store: Arc<ConcurrentMemoryStore<B>>,
+    tx_log: Option<Arc<TxLog>>,
+    id: String,
+    params: AsOfParams,
+) -> Result<Json<MemoryRecordResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = uuid::Uuid::parse_str(&id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "invalid UUID"})),
+        )
+    })?;
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not found", "id": id})),
+        )
+    };
+    let past = match params.as_of.as_deref() {
+        Some(raw) => {
+            let point = crate::pitr::AsOf::parse(raw)
+                .map_err(|e| (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": e}))))?;
+            let log = tx_log.ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "as_of requires a tx log"})),
+                )
+            })?;
+            Some((log, point))
+        }
+        None => None,
+    };
+    // A replay reads the log from disk and a live cold record is read in
+    // place (not faulted) from its segment; both run on the blocking pool.
+    let found = tokio::task::spawn_blocking(move || match past {
+        Some((log, point)) => crate::pitr::state_as_of(&log, point)
+            .map(|records| records.into_iter().find(|r| r.id == uuid)),
+        None => Ok(store.snapshot().find_by_id(uuid).cloned()),
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })?
+    .map_err(|e| (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": e}))))?;
+    found
+        .map(|r| Json(MemoryRecordResponse::from(&r)))
+        .ok_or_else(not_found)
+}
+
+/// POST /v1/state/checkpoint — write a recovery checkpoint at the current tx.
+#[cfg(feature = "web-server")]
+async fn handle_state_checkpoint<B: MemoryBackend + Send + Sync + 'static>(
+    store: AsyncMemoryStore<B>,
+    tx_log: Option<Arc<TxLog>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Some(log) = tx_log else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "tx_log not configured"})),
+        );
+    };
+    let result = store
+        .transact(move |ms| Ok(crate::pitr::checkpoint(ms, &log)))
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()));
+    match result {
+        Ok(tx) => (StatusCode::OK, Json(serde_json::json!({"checkpoint_tx": tx}))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e})),
+        ),
+    }
+}
+
+/// POST /v1/state/restore — rewind the memory store to `{"tx": N}` or `{"at": "<rfc3339>"}`.
+/// Admin only: a rewind discards every later write.
+#[cfg(feature = "web-server")]
+async fn handle_state_restore<B: MemoryBackend + Send + Sync + 'static>(
+    store: AsyncMemoryStore<B>,
+    tx_log: Option<Arc<TxLog>>,
+    headers: HeaderMap,
+    req: RestoreRequest,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if !is_admin(&headers) {
+        return admin_forbidden();
+    }
+    let Some(log) = tx_log else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "tx_log not configured"})),
+        );
+    };
+    let point = match (req.tx, req.at.as_deref()) {
+        (Some(tx), None) => crate::pitr::AsOf::Tx(tx),
+        (None, Some(at)) => match crate::pitr::AsOf::parse(at) {
+            Ok(p @ crate::pitr::AsOf::TimestampMs(_)) => p,
+            _ => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": "at must be an RFC 3339 timestamp"})),
+                )
+            }
+        },
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "provide exactly one of tx or at"})),
+            )
+        }
+    };
+    let result = store
+        .transact(move |ms| Ok(crate::pitr::restore(ms, &log, point)))
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()));
+    match result {
+        Ok(report) => (
+            StatusCode::OK,
+            Json(serde_json::to_value(report).unwrap_or_default()),
+        ),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": e}))),
+    }
+}
+
 /// GET /webhooks — list all registered webhooks
 #[cfg(feature = "web-server")]
 async fn handle_list_webhooks() -> Json<serde_json::Value> {
@@ -4713,7 +5243,7 @@ async fn handle_list_webhooks() -> Json<serde_json::Value> {
 /// Quarantined records are excluded from search/query by default.
 #[cfg(feature = "web-server")]
 async fn handle_quarantine_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     id_str: String,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let id = match uuid::Uuid::parse_str(&id_str) {
@@ -4725,19 +5255,25 @@ async fn handle_quarantine_memory<B: MemoryBackend + Send + Sync + 'static>(
             ))
         }
     };
-    match store.lock() {
-        Ok(mut ms) => match ms.set_status(id, "quarantine") {
-            Ok(_) => Ok(Json(
-                serde_json::json!({"success": true, "id": id_str, "status": "quarantine"}),
-            )),
-            Err(e) => Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"success": false, "error": e.to_string()})),
-            )),
-        },
+    let found = store
+        .transact(move |ms| {
+            if ms.find_by_id(id).is_none() {
+                return Ok(None);
+            }
+            ms.set_status(id, RecordStatus::Quarantine).map(Some)
+        })
+        .await;
+    match found {
+        Ok(Some(_)) => Ok(Json(
+            serde_json::json!({"success": true, "id": id_str, "status": "quarantine"}),
+        )),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"success": false, "error": format!("record {id} not found")})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"success": false, "error": format!("Lock error: {}", e)})),
+            Json(serde_json::json!({"success": false, "error": e.to_string()})),
         )),
     }
 }
@@ -4745,7 +5281,7 @@ async fn handle_quarantine_memory<B: MemoryBackend + Send + Sync + 'static>(
 /// POST /memory/restore/:id — restore a quarantined record to active status.
 #[cfg(feature = "web-server")]
 async fn handle_restore_memory<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     id_str: String,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let id = match uuid::Uuid::parse_str(&id_str) {
@@ -4757,19 +5293,25 @@ async fn handle_restore_memory<B: MemoryBackend + Send + Sync + 'static>(
             ))
         }
     };
-    match store.lock() {
-        Ok(mut ms) => match ms.set_status(id, "active") {
-            Ok(_) => Ok(Json(
-                serde_json::json!({"success": true, "id": id_str, "status": "active"}),
-            )),
-            Err(e) => Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"success": false, "error": e.to_string()})),
-            )),
-        },
+    let found = store
+        .transact(move |ms| {
+            if ms.find_by_id(id).is_none() {
+                return Ok(None);
+            }
+            ms.set_status(id, RecordStatus::Active).map(Some)
+        })
+        .await;
+    match found {
+        Ok(Some(_)) => Ok(Json(
+            serde_json::json!({"success": true, "id": id_str, "status": "active"}),
+        )),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"success": false, "error": format!("record {id} not found")})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"success": false, "error": format!("Lock error: {}", e)})),
+            Json(serde_json::json!({"success": false, "error": e.to_string()})),
         )),
     }
 }
@@ -4779,7 +5321,7 @@ async fn handle_restore_memory<B: MemoryBackend + Send + Sync + 'static>(
 /// POST /memory/corroborate/:id — increase confidence by 0.10 (max 1.0).
 #[cfg(feature = "web-server")]
 async fn handle_corroborate<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     id_str: String,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let id = match uuid::Uuid::parse_str(&id_str) {
@@ -4791,20 +5333,26 @@ async fn handle_corroborate<B: MemoryBackend + Send + Sync + 'static>(
             ))
         }
     };
-    match store.lock() {
-        Ok(mut ms) => match ms.corroborate(id) {
-            Ok((before, after)) => Ok(Json(serde_json::json!({
-                "success": true, "id": id_str,
-                "confidence_before": before, "confidence_after": after
-            }))),
-            Err(e) => Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"success": false, "error": e.to_string()})),
-            )),
-        },
+    let found = store
+        .transact(move |ms| {
+            if ms.find_by_id(id).is_none() {
+                return Ok(None);
+            }
+            ms.corroborate(id).map(Some)
+        })
+        .await;
+    match found {
+        Ok(Some((before, after))) => Ok(Json(serde_json::json!({
+            "success": true, "id": id_str,
+            "confidence_before": before, "confidence_after": after
+        }))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"success": false, "error": format!("record {id} not found")})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"success": false, "error": format!("Lock error: {}", e)})),
+            Json(serde_json::json!({"success": false, "error": e.to_string()})),
         )),
     }
 }
@@ -4812,7 +5360,7 @@ async fn handle_corroborate<B: MemoryBackend + Send + Sync + 'static>(
 /// POST /memory/contradict/:id — decrease confidence by 0.15. Auto-quarantines if < 0.30.
 #[cfg(feature = "web-server")]
 async fn handle_contradict<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: AsyncMemoryStore<B>,
     id_str: String,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let id = match uuid::Uuid::parse_str(&id_str) {
@@ -4824,21 +5372,27 @@ async fn handle_contradict<B: MemoryBackend + Send + Sync + 'static>(
             ))
         }
     };
-    match store.lock() {
-        Ok(mut ms) => match ms.contradict(id) {
-            Ok((before, after, quarantined)) => Ok(Json(serde_json::json!({
-                "success": true, "id": id_str,
-                "confidence_before": before, "confidence_after": after,
-                "quarantined": quarantined
-            }))),
-            Err(e) => Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"success": false, "error": e.to_string()})),
-            )),
-        },
+    let found = store
+        .transact(move |ms| {
+            if ms.find_by_id(id).is_none() {
+                return Ok(None);
+            }
+            ms.contradict(id).map(Some)
+        })
+        .await;
+    match found {
+        Ok(Some((before, after, quarantined))) => Ok(Json(serde_json::json!({
+            "success": true, "id": id_str,
+            "confidence_before": before, "confidence_after": after,
+            "quarantined": quarantined
+        }))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"success": false, "error": format!("record {id} not found")})),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"success": false, "error": format!("Lock error: {}", e)})),
+            Json(serde_json::json!({"success": false, "error": e.to_string()})),
         )),
     }
 }
@@ -4853,6 +5407,8 @@ struct ContextRequest {
     limit: Option<usize>,
     max_tokens: Option<usize>,
     format: Option<String>, // "markdown" (default) | "plain" | "xml"
+    /// Fence off text from untrusted or suspicious records (default true).
+    spotlight: Option<bool>,
 }
 
 /// GET /memory/live_beliefs?actor=&limit= — unified beliefs surface per unified-beliefs-surface spec.
@@ -4871,74 +5427,110 @@ struct ContextResponse {
     context: String,
     record_count: usize,
     estimated_tokens: usize,
+    /// High-risk injection hits withheld from the context. Reading does not
+    /// quarantine them; the injection sweep does.
+    withheld: Vec<crate::injection_defense::InjectionAssessment>,
+    /// Records rendered inside spotlight delimiters.
+    spotlighted: usize,
 }
 
 /// POST /memory/context — search memory and return a formatted context block
 /// ready to inject into an LLM prompt. Zero LLM calls — pure formatting.
 #[cfg(feature = "web-server")]
 async fn handle_memory_context<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     Json(req): Json<ContextRequest>,
 ) -> Result<Json<ContextResponse>, (StatusCode, Json<ContextResponse>)> {
     let limit = req.limit.unwrap_or(10).min(50);
     let now_ts = chrono::Utc::now().timestamp();
-    match store.lock() {
-        Ok(ms) => {
-            let mut results = ms.search_semantic(None, &req.query, limit, false);
-            // Apply actor filter if provided
-            if let Some(actor) = &req.actor {
-                results.retain(|(r, _)| &r.actor == actor);
-            }
-            // Exclude expired
-            results.retain(|(r, _)| r.expires_at.map_or(true, |exp| exp > now_ts));
-            let record_count = results.len();
-            let fmt = req.format.as_deref().unwrap_or("markdown");
-            let lines: Vec<String> = results.iter().map(|(r, score)| {
-                match fmt {
-                    "xml" => format!("  <memory score=\"{:.2}\" confidence=\"{:.2}\" source=\"{}\">[{}] {}</memory>",
-                        score, r.confidence, r.source.as_deref().unwrap_or("unknown"), r.action, r.target),
-                    "plain" => format!("- [{}] {} (confidence: {:.0}%)",
-                        r.action, r.target, r.confidence * 100.0),
-                    _ => format!("- **[{}]** {} *(confidence: {:.0}%, source: {})*",
-                        r.action, r.target, r.confidence * 100.0,
-                        r.source.as_deref().unwrap_or("unknown")),
-                }
-            }).collect();
-            let context = if lines.is_empty() {
-                "No relevant memories found.".to_string()
-            } else {
-                match fmt {
-                    "xml" => format!("<memories>\n{}\n</memories>", lines.join("\n")),
-                    _ => format!("Relevant memories:\n{}", lines.join("\n")),
-                }
-            };
-            // Truncate by max_tokens if requested (1 token ≈ 4 chars)
-            let context = if let Some(max_tok) = req.max_tokens {
-                let max_chars = max_tok * 4;
-                if context.len() > max_chars {
-                    context[..max_chars].to_string()
-                } else {
-                    context
-                }
-            } else {
-                context
-            };
-            let estimated_tokens = context.len() / 4;
-            Ok(Json(ContextResponse {
-                context,
-                record_count,
-                estimated_tokens,
-            }))
+    let ms = store.snapshot();
+    let mut results: Vec<(MemoryRecord, f64)> = ms
+        .search_semantic(None, &req.query, limit, false)
+        .into_iter()
+        .map(|(r, score)| (r.clone(), score))
+        .collect();
+    // Apply actor filter if provided
+    if let Some(actor) = &req.actor {
+        results.retain(|(r, _)| &r.actor == actor);
+    }
+    // Exclude expired
+    results.retain(|(r, _)| r.expires_at.is_none_or(|exp| exp > now_ts));
+    // Injection defense: classify the hits in one batch, withhold
+    // high-risk ones and remember which of the rest need spotlighting.
+    let injection_cfg = crate::injection_defense::InjectionConfig::default();
+    let hits: Vec<(&MemoryRecord, Option<f64>)> = results
+        .iter()
+        .map(|(r, _)| (r, crate::injection_defense::source_trust_of(&ms.source_trust, r)))
+        .collect();
+    let assessments = crate::injection_defense::assess_batch(&hits, &injection_cfg);
+    let mut withheld = Vec::new();
+    let mut untrusted = std::collections::HashSet::new();
+    for assessment in assessments {
+        if assessment.level == crate::injection_defense::InjectionLevel::HighRisk {
+            withheld.push(assessment);
+        } else if assessment.untrusted(&injection_cfg) {
+            untrusted.insert(assessment.id);
         }
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ContextResponse {
-                context: format!("Error: {}", e),
-                record_count: 0,
-                estimated_tokens: 0,
-            }),
-        )),
     }
+    results.retain(|(r, _)| !withheld.iter().any(|a| a.id == r.id));
+    let spotlight = (req.spotlight.unwrap_or(true) && !untrusted.is_empty())
+        .then(crate::injection_defense::Spotlight::new);
+    let record_count = results.len();
+    let fmt = req.format.as_deref().unwrap_or("markdown");
+    let body = |r: &MemoryRecord| match &spotlight {
+        Some(spot) if untrusted.contains(&r.id) => {
+            spot.wrap(&format!("[{}] {}", r.action, r.target))
+        }
+        _ if matches!(fmt, "xml" | "plain") => format!("[{}] {}", r.action, r.target),
+        _ => format!("**[{}]** {}", r.action, r.target),
+    };
+    let lines: Vec<String> = results.iter().map(|(r, score)| {
+        match fmt {
+            "xml" => format!("  <memory score=\"{:.2}\" confidence=\"{:.2}\" source=\"{}\">{}</memory>",
+                score, r.confidence, r.source.as_deref().unwrap_or("unknown"), body(r)),
+            "plain" => format!("- {} (confidence: {:.0}%)",
+                body(r), r.confidence * 100.0),
+            _ => format!("- {} *(confidence: {:.0}%, source: {})*",
+                body(r), r.confidence * 100.0,
+                r.source.as_deref().unwrap_or("unknown")),
+        }
+    }).collect();
+    let notice = spotlight
+        .as_ref()
+        .map(|spot| format!("{}\n", spot.notice()))
+        .unwrap_or_default();
+    let context = if lines.is_empty() {
+        "No relevant memories found.".to_string()
+    } else {
+        match fmt {
+            "xml" => format!("{}<memories>\n{}\n</memories>", notice, lines.join("\n")),
+            _ => format!("{}Relevant memories:\n{}", notice, lines.join("\n")),
+        }
+    };
+    // Truncate by max_tokens if requested (1 token ≈ 4 chars)
+    let context = if let Some(max_tok) = req.max_tokens {
+        let max_chars = max_tok * 4;
+        if context.len() > max_chars {
+            // Spotlight delimiters are multi-byte; cut on a char boundary.
+            let mut end = max_chars;
+            while !context.is_char_boundary(end) {
+                end -= 1;
+            }
+            context[..end].to_string()
+        } else {
+            context
+        }
+    } else {
+        context
+    };
+    let estimated_tokens = context.len() / 4;
+    Ok(Json(ContextResponse {
+        context,
+        record_count,
+        estimated_tokens,
+        withheld,
+        spotlighted: if spotlight.is_some() { untrusted.len() } else { 0 },
+    }))
 }
 
 /// GET /memory/live_beliefs — unified live beliefs surface (surgical, simple queries on existing stores).
@@ -4947,7 +5539,7 @@ async fn handle_memory_context<B: MemoryBackend + Send + Sync + 'static>(
 /// Actor scoped, limited. Used as default first call in proactive harness (see benchmarks + spec).
 #[cfg(feature = "web-server")]
 async fn handle_memory_live_beliefs<B: MemoryBackend + Send + Sync + 'static>(
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: Arc<ConcurrentMemoryStore<B>>,
     symbolic_store: Arc<Mutex<SymbolicStore<InMemoryGraph>>>,
     world_model: Arc<RwLock<WorldModelEnhanced>>,
     aureus: Arc<Mutex<AureusBridge>>,
@@ -5043,30 +5635,28 @@ async fn handle_memory_live_beliefs<B: MemoryBackend + Send + Sync + 'static>(
         Ok(reps) => serde_json::json!({ "inconsistencies": reps.len() }),
         Err(e) => serde_json::json!({ "inconsistencies": 0, "error": e }),
     };
-    let pinned = match memory_store.lock() {
-        Ok(ms) => {
-            let all = ms.all();
-            let mut filtered: Vec<_> = all
-                .into_iter()
-                .filter(|r| {
-                    r.priority == "pinned" && actor.as_ref().map_or(true, |a| &r.actor == a)
-                })
-                .collect();
-            filtered.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-            filtered.truncate(limit);
-            filtered
-                .into_iter()
-                .map(|r| {
-                    serde_json::json!({
-                        "id": r.id.to_string(),
-                        "action": r.action,
-                        "target": r.target,
-                        "confidence": r.confidence,
-                    })
+    let pinned = {
+        let ms = memory_store.snapshot();
+        let all = ms.all();
+        let mut filtered: Vec<_> = all
+            .iter()
+            .filter(|r| {
+                r.priority == Priority::Pinned && actor.as_ref().is_none_or(|a| &r.actor == a)
+            })
+            .collect();
+        filtered.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+        filtered.truncate(limit);
+        filtered
+            .into_iter()
+            .map(|r| {
+                serde_json::json!({
+                    "id": r.id.to_string(),
+                    "action": r.action,
+                    "target": r.target,
+                    "confidence": r.confidence,
                 })
-                .collect()
-        }
-        Err(_) => vec![],
+            })
+            .collect::<Vec<_>>()
     };
 
     let summary = format!(
@@ -5102,35 +5692,31 @@ async fn handle_memory_live_beliefs<B: MemoryBackend + Send + Sync + 'static>(
 /// GET /v1/beliefs?min_conf= — Belief records from MemoryStore filtered by confidence threshold.
 #[cfg(feature = "web-server")]
 async fn handle_v1_beliefs<B: MemoryBackend + Send + Sync + 'static>(
-    store: Arc<Mutex<MemoryStore<B>>>,
+    store: Arc<ConcurrentMemoryStore<B>>,
     min_conf: f32,
 ) -> Json<serde_json::Value> {
-    match store.lock() {
-        Ok(ms) => {
-            let beliefs: Vec<serde_json::Value> = ms
-                .all()
-                .iter()
-                .filter(|r| r.record_type == MemoryType::Belief && r.confidence >= min_conf)
-                .map(|r| {
-                    serde_json::json!({
-                        "id": r.id,
-                        "actor": r.actor,
-                        "action": r.action,
-                        "target": r.target,
-                        "confidence": r.confidence,
-                        "metadata": r.metadata,
-                    })
-                })
-                .collect();
-            let count = beliefs.len();
-            Json(serde_json::json!({
-                "beliefs": beliefs,
-                "count": count,
-                "min_conf": min_conf,
-            }))
-        }
-        Err(e) => Json(serde_json::json!({"error": e.to_string(), "beliefs": []})),
-    }
+    let ms = store.snapshot();
+    let beliefs: Vec<serde_json::Value> = ms
+        .all()
+        .iter()
+        .filter(|r| r.record_type == MemoryType::Belief && r.confidence >= min_conf)
+        .map(|r| {
+            serde_json::json!({
+                "id": r.id,
+                "actor": r.actor,
+                "action": r.action,
+                "target": r.target,
+                "confidence": r.confidence,
+                "metadata": r.metadata,
+            })
+        })
+        .collect();
+    let count = beliefs.len();
+    Json(serde_json::json!({
+        "beliefs": beliefs,
+        "count": count,
+        "min_conf": min_conf,
+    }))
 }
 
 // ── G5/G6: WorldModel state introspection REST ────────────────────────────────
@@ -5241,7 +5827,7 @@ async fn handle_wm_observe(
         }));
     }
     match world_model.write() {
-        Ok(mut wm) => match wm.observe_transition(from, action, to) {
+        Ok(wm) => match wm.observe_transition(from, action, to) {
             Ok(_) => Json(
                 serde_json::json!({"success": true, "total_transitions": wm.transition_count()}),
             ),
@@ -5422,6 +6008,499 @@ async fn handle_wm_rollout(
     }
 }
 
+// ── Maintenance scheduler HTTP handlers ─────────────────────────────────────
+
+#[cfg(feature = "web-server")]
+type SharedScheduler = Arc<Mutex<crate::maintenance::MaintenanceScheduler>>;
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct MaintenanceHistoryParams {
+    job: Option<String>,
+    limit: Option<usize>,
+    /// Only failed runs.
+    failed: Option<bool>,
+}
+
+/// GET /maintenance/jobs — schedule, pause state and last outcome per job.
+#[cfg(feature = "web-server")]
+async fn handle_maintenance_jobs(scheduler: SharedScheduler) -> Json<serde_json::Value> {
+    let s = scheduler.lock().unwrap();
+    let jobs = s.jobs();
+    let failing: Vec<&str> = jobs
+        .iter()
+        .filter(|j| j.consecutive_failures > 0)
+        .map(|j| j.name.as_str())
+        .collect();
+    Json(serde_json::json!({
+        "jobs": jobs,
+        "failing": failing,
+        "next_wakeup": s.next_wakeup(),
+    }))
+}
+
+/// GET /maintenance/history?job=&limit=&failed= — most recent runs first.
+#[cfg(feature = "web-server")]
+async fn handle_maintenance_history(
+    scheduler: SharedScheduler,
+    params: MaintenanceHistoryParams,
+) -> Json<serde_json::Value> {
+    let s = scheduler.lock().unwrap();
+    let limit = params.limit.unwrap_or(50).min(crate::maintenance::DEFAULT_HISTORY_CAP);
+    let runs: Vec<_> = if params.failed.unwrap_or(false) {
+        s.failures(crate::maintenance::DEFAULT_HISTORY_CAP)
+            .into_iter()
+            .filter(|r| params.job.as_deref().is_none_or(|j| r.job == j))
+            .take(limit)
+            .collect()
+    } else {
+        s.history(params.job.as_deref(), limit)
+    };
+    Json(serde_json::json!({ "count": runs.len(), "runs": runs }))
+}
+
+/// POST /maintenance/jobs/:name/{run|pause|resume}
+#[cfg(feature = "web-server")]
+async fn handle_maintenance_action(
+    scheduler: SharedScheduler,
+    name: String,
+    action: String,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let error = |code: StatusCode, e: String| (code, Json(serde_json::json!({ "error": e })));
+    match action.as_str() {
+        "run" => {
+            let claimed = scheduler.lock().unwrap().claim(&name);
+            let claimed = match claimed {
+                Ok(c) => c,
+                Err(e) if e.contains("already running") => return error(StatusCode::CONFLICT, e),
+                Err(e) => return error(StatusCode::NOT_FOUND, e),
+            };
+            let run = tokio::task::spawn_blocking(move || claimed.execute())
+                .await
+                .expect("maintenance job task panicked");
+            scheduler.lock().unwrap().complete(run.clone());
+            let status = if run.ok { StatusCode::OK } else { StatusCode::INTERNAL_SERVER_ERROR };
+            (status, Json(serde_json::json!(run)))
+        }
+        "pause" | "resume" => {
+            let mut s = scheduler.lock().unwrap();
+            let result = if action == "pause" { s.pause(&name) } else { s.resume(&name) };
+            match result {
+                Ok(()) => (StatusCode::OK, Json(serde_json::json!(s.job(&name)))),
+                Err(e) => error(StatusCode::NOT_FOUND, e),
+            }
+        }
+        other => error(
+            StatusCode::BAD_REQUEST,
+            format!("unknown action {other:?}; expected run|pause|resume"),
+        ),
+    }
+}
+
+// ── Import HTTP handler ─────────────────────────────────────────────────────
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct ImportMemoryRequest {
+    /// Format name or alias (`openai`, `claude`, `langgraph`, `otlp`, `csv`, …);
+    /// detected from `content` when omitted.
+    format: Option<String>,
+    /// The document: a string (CSV, Markdown, JSON Lines) or inline JSON.
+    content: serde_json::Value,
+    /// Name used in error locations and the default `source`.
+    origin: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+    session: Option<String>,
+    source: Option<String>,
+}
+
+/// POST /memory/import — run one document through the importers; returns the `ImportReport`.
+#[cfg(feature = "web-server")]
+async fn handle_import_memory<B: MemoryBackend + Send + 'static>(
+    store: AsyncMemoryStore<B>,
+    req: ImportMemoryRequest,
+) -> (StatusCode, Json<serde_json::Value>) {
+    use crate::importers::{ImportFormat, ImportOptions, Importer};
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e })));
+    let format = match req.format.as_deref().map(ImportFormat::parse).transpose() {
+        Ok(f) => f,
+        Err(e) => return bad_request(e.to_string()),
+    };
+    let content = match req.content {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    };
+    let origin = req.origin.unwrap_or_else(|| "request".into());
+    let mut importer = Importer::new(ImportOptions {
+        format,
+        dry_run: req.dry_run,
+        source: req.source,
+        session: req.session,
+    });
+    let result = match store
+        .transact(move |ms| Ok(importer.import_str(ms, &content, &origin)))
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("import task failed: {e}") })),
+            )
+        }
+    };
+    match result {
+        Ok(report) => (StatusCode::OK, Json(serde_json::json!(report))),
+        Err(e) => bad_request(format!("{e:#}")),
+    }
+}
+
+// ── State bundle HTTP handlers ──────────────────────────────────────────────
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct BundleExportParams {
+    namespace: Option<String>,
+}
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct BundleImportRequest {
+    /// A bundle as returned by GET /v1/state/bundle, or a legacy record export.
+    bundle: serde_json::Value,
+    #[serde(flatten)]
+    options: crate::state_bundle::BundleImportOptions,
+}
+
+/// GET /v1/state/bundle — the whole state as a sealed bundle document.
+#[cfg(feature = "web-server")]
+async fn handle_bundle_export<B: MemoryBackend + Send + 'static>(
+    targets: Arc<crate::state_bundle::BundleTargets<B>>,
+    params: BundleExportParams,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let options = crate::state_bundle::BundleExportOptions {
+        namespace: params.namespace,
+    };
+    let result = tokio::task::spawn_blocking(move || {
+        crate::state_bundle::export(&targets, &options)?.to_json()
+    })
+    .await
+    .unwrap_or_else(|e| Err(anyhow::anyhow!("bundle export task failed: {e}")));
+    match result {
+        Ok(doc) => (StatusCode::OK, Json(doc)),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("{e:#}") })),
+        ),
+    }
+}
+
+/// POST /v1/state/bundle — verify, migrate and apply a bundle; returns the import report.
+#[cfg(feature = "web-server")]
+async fn handle_bundle_import<B: MemoryBackend + Send + 'static>(
+    targets: Arc<crate::state_bundle::BundleTargets<B>>,
+    req: BundleImportRequest,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let result = match tokio::task::spawn_blocking(move || {
+        crate::state_bundle::import(&targets, req.bundle, &req.options)
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": format!("bundle import task failed: {e}") })),
+            )
+        }
+    };
+    match result {
+        Ok(report) => (StatusCode::OK, Json(serde_json::json!(report))),
+        Err(e) => (
+            write_status(&e, StatusCode::BAD_REQUEST),
+            Json(serde_json::json!({ "error": format!("{e:#}") })),
+        ),
+    }
+}
+
+// ── Safety policy / redaction vault HTTP handlers ───────────────────────────
+
+/// GET /v1/safety/policy — default and per-namespace guardrail policies.
+#[cfg(feature = "web-server")]
+async fn handle_safety_policy_get() -> (StatusCode, Json<serde_json::Value>) {
+    let guard = crate::safety_guardrail::SAFETY_GUARDRAIL.lock().unwrap();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "backend": guard.backend_name(),
+            "vault": guard.vault().is_some(),
+            "policies": guard.policies(),
+        })),
+    )
+}
+
+/// PUT /v1/safety/policy — replace the policies (`{"default": .., "namespaces": {..}}`).
+/// Admin only: loosening a policy would let PII through unredacted.
+#[cfg(feature = "web-server")]
+async fn handle_safety_policy_put(
+    headers: HeaderMap,
+    Json(policies): Json<crate::safety_guardrail::SafetyPolicies>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if !is_admin(&headers) {
+        return admin_forbidden();
+    }
+    let mut guard = crate::safety_guardrail::SAFETY_GUARDRAIL.lock().unwrap();
+    let needs_vault = std::iter::once(&policies.default)
+        .chain(policies.namespaces.values())
+        .any(|p| !p.redact.is_empty());
+    if needs_vault && guard.vault().is_none() {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "redaction needs a vault; set HIPCORTEX_VAULT_KEY"
+            })),
+        );
+    }
+    guard.set_policies(policies);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "policies": guard.policies() })),
+    )
+}
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct RehydrateRequest {
+    text: String,
+}
+
+/// POST /v1/safety/rehydrate — put vault originals back in place of tokens.
+/// Admin only: this is the one route that reveals redacted values.
+#[cfg(feature = "web-server")]
+async fn handle_safety_rehydrate(
+    headers: HeaderMap,
+    Json(req): Json<RehydrateRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if !is_admin(&headers) {
+        return admin_forbidden();
+    }
+    let vault = crate::safety_guardrail::SAFETY_GUARDRAIL
+        .lock()
+        .unwrap()
+        .vault();
+    let Some(vault) = vault else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no redaction vault configured" })),
+        );
+    };
+    match vault.rehydrate(&req.text) {
+        Ok(text) => (StatusCode::OK, Json(serde_json::json!({ "text": text }))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("{e:#}") })),
+        ),
+    }
+}
+
+// ── Prompt-injection report HTTP handlers ───────────────────────────────────
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct InjectionReportParams {
+    /// Overrides the suspicious threshold (report) or the quarantine
+    /// threshold (sweep).
+    threshold: Option<f64>,
+    include_quarantined: Option<bool>,
+    limit: Option<usize>,
+}
+
+/// GET /memory/injections — records that score as possible prompt
+/// injections, riskiest first.
+#[cfg(feature = "web-server")]
+async fn handle_injection_report<B: MemoryBackend + Send + Sync + 'static>(
+    store: Arc<ConcurrentMemoryStore<B>>,
+    params: InjectionReportParams,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let result = tokio::task::spawn_blocking(move || {
+        let mut cfg = crate::injection_defense::InjectionConfig::default();
+        if let Some(t) = params.threshold {
+            cfg.suspicious_threshold = t.clamp(0.0, 1.0);
+        }
+        let ms = store.snapshot();
+        let found = crate::injection_defense::scan(
+            &ms,
+            &cfg,
+            params.include_quarantined.unwrap_or(false),
+        );
+        let total = found.len();
+        let records: Vec<serde_json::Value> = found
+            .into_iter()
+            .take(params.limit.unwrap_or(100).min(1000))
+            .map(|a| {
+                let rec = ms.find_by_id(a.id);
+                serde_json::json!({
+                    "assessment": a,
+                    "actor": rec.map(|r| r.actor.clone()),
+                    "target": rec.map(|r| r.target.clone()),
+                    "status": rec.map(|r| r.status),
+                })
+            })
+            .collect();
+        Ok::<_, String>(serde_json::json!({ "total": total, "records": records }))
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    match result {
+        Ok(report) => (StatusCode::OK, Json(report)),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e })),
+        ),
+    }
+}
+
+/// POST /memory/injections/quarantine — quarantine every active high-risk record.
+#[cfg(feature = "web-server")]
+async fn handle_injection_quarantine<B: MemoryBackend + Send + Sync + 'static>(
+    store: Arc<ConcurrentMemoryStore<B>>,
+    params: InjectionReportParams,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let result = tokio::task::spawn_blocking(move || {
+        let mut cfg = crate::injection_defense::InjectionConfig::default();
+        if let Some(t) = params.threshold {
+            cfg.quarantine_threshold = t.clamp(0.0, 1.0);
+        }
+        crate::injection_defense::quarantine_high_risk(&store, &cfg).map_err(|e| format!("{e:#}"))
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    match result {
+        Ok(ids) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "quarantined": ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e })),
+        ),
+    }
+}
+
+// ── Conversation thread HTTP handlers ───────────────────────────────────────
+
+#[cfg(feature = "web-server")]
+type SharedThreads = Arc<crate::conversation_memory::ConversationThreads>;
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct ConversationPageParams {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct ConversationAppendRequest {
+    role: String,
+    text: String,
+    /// Turn being answered; defaults to the previous turn in the session.
+    reply_to: Option<uuid::Uuid>,
+}
+
+#[cfg(feature = "web-server")]
+#[derive(Deserialize)]
+struct ConversationSummarizeParams {
+    /// Also fold the newest `keep_recent` turns (default true).
+    all: Option<bool>,
+}
+
+/// GET /conversations — ids of sessions with stored turns.
+#[cfg(feature = "web-server")]
+async fn handle_list_conversations<B: MemoryBackend>(
+    threads: SharedThreads,
+    store: Arc<ConcurrentMemoryStore<B>>,
+) -> Json<serde_json::Value> {
+    let sessions = threads.sessions(&store.snapshot());
+    Json(serde_json::json!({ "count": sessions.len(), "sessions": sessions }))
+}
+
+/// GET /conversations/:session?offset=&limit= — one page of turns, oldest first.
+#[cfg(feature = "web-server")]
+async fn handle_conversation_page<B: MemoryBackend>(
+    threads: SharedThreads,
+    store: Arc<ConcurrentMemoryStore<B>>,
+    session: String,
+    params: ConversationPageParams,
+) -> Json<serde_json::Value> {
+    let page = threads.page(
+        &store.snapshot(),
+        &session,
+        params.offset.unwrap_or(0),
+        params.limit.unwrap_or(50),
+    );
+    Json(serde_json::json!(page))
+}
+
+/// POST /conversations/:session — append a turn; may extract beliefs and roll a summary.
+#[cfg(feature = "web-server")]
+async fn handle_conversation_append<B: MemoryBackend + Send + Sync + 'static>(
+    threads: SharedThreads,
+    store: AsyncMemoryStore<B>,
+    session: String,
+    req: ConversationAppendRequest,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if req.text.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "text must not be empty" })),
+        );
+    }
+    let appended = store
+        .transact(move |ms| threads.append(ms, &session, &req.role, &req.text, req.reply_to))
+        .await;
+    match appended {
+        Ok(outcome) => (StatusCode::OK, Json(serde_json::json!(outcome))),
+        Err(e) => (
+            write_status(&e, StatusCode::BAD_REQUEST),
+            Json(serde_json::json!({ "error": e.to_string() })),
+        ),
+    }
+}
+
+/// POST /conversations/:session/summarize?all= — fold unsummarized turns now.
+#[cfg(feature = "web-server")]
+async fn handle_conversation_summarize<B: MemoryBackend + Send + Sync + 'static>(
+    threads: SharedThreads,
+    store: AsyncMemoryStore<B>,
+    session: String,
+    params: ConversationSummarizeParams,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let summarized = store
+        .transact(move |ms| {
+            let id = threads.summarize(ms, &session, params.all.unwrap_or(true))?;
+            let summary = threads.latest_summary(ms, &session).map(|r| r.target.clone());
+            Ok((id, summary))
+        })
+        .await;
+    match summarized {
+        Ok((id, summary)) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "summary_id": id,
+                "summary": summary,
+            })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        ),
+    }
+}
+
 // ── Topological substrate HTTP handlers ─────────────────────────────────────
 
 #[cfg(feature = "web-server")]
@@ -5630,7 +6709,7 @@ async fn handle_wm_register_entity(
         covariance,
     };
     match world_model.write() {
-        Ok(mut wm) => match wm.register_entity(id.clone(), initial) {
+        Ok(wm) => match wm.register_entity(id.clone(), initial) {
             Ok(_) => Json(serde_json::json!({"success": true, "id": id})),
             Err(e) => Json(serde_json::json!({"success": false, "error": e})),
         },
@@ -5690,7 +6769,7 @@ async fn handle_wm_causal_add_edge(
         return Json(serde_json::json!({"success": false, "error": "from and to required"}));
     }
     match world_model.write() {
-        Ok(mut wm) => match wm.add_causal_edge(from.clone(), to.clone()) {
+        Ok(wm) => match wm.add_causal_edge(from.clone(), to.clone()) {
             Ok(_) => Json(serde_json::json!({"success": true, "from": from, "to": to})),
             Err(e) => Json(serde_json::json!({"success": false, "error": e})),
         },
@@ -5700,6 +6779,8 @@ async fn handle_wm_causal_add_edge(
 
 /// POST /worldmodel/causal/intervention — P(Y|do(X=x)) do-calculus query
 /// Body: {"outcome": "Y", "intervention_var": "X", "intervention_value": 1.0, "conditioned_on": {}}
+/// The response carries the identification result: the adjustment formula
+/// used, or the hedge explaining why the effect is not identifiable.
 #[cfg(feature = "web-server")]
 async fn handle_wm_causal_intervention(
     world_model: Arc<RwLock<WorldModelEnhanced>>,
@@ -5722,7 +6803,7 @@ async fn handle_wm_causal_intervention(
         })
         .unwrap_or_default();
 
-    use crate::world_model_enhanced::InterventionQuery;
+    use crate::world_model_enhanced::{AdjustmentStrategy, Identifiability, InterventionQuery};
     let query = InterventionQuery {
         outcome,
         intervention_var,
@@ -5732,12 +6813,35 @@ async fn handle_wm_causal_intervention(
     };
 
     match world_model.read() {
-        Ok(wm) => match wm.causal_intervention(query) {
-            Ok(result) => {
-                Json(serde_json::json!({"success": true, "outcome_probabilities": result}))
+        Ok(wm) => {
+            let identification = wm
+                .identify_causal_effect(&query.intervention_var, &query.outcome)
+                .ok();
+            // The estimate is computed by backdoor adjustment only; frontdoor
+            // and ID-algorithm formulas are reported under `identification`.
+            let formula = match &identification {
+                Some(Identifiability::Identified(id))
+                    if matches!(id.strategy, AdjustmentStrategy::Backdoor { .. }) =>
+                {
+                    Some(id.formula.clone())
+                }
+                _ => None,
+            };
+            match wm.causal_intervention(query) {
+                Ok(result) => Json(serde_json::json!({
+                    "success": true,
+                    "outcome_probabilities": result,
+                    "adjustment_formula": formula,
+                    "identification": identification,
+                })),
+                Err(e) => Json(serde_json::json!({
+                    "success": false,
+                    "error": e,
+                    "adjustment_formula": null,
+                    "identification": identification,
+                })),
             }
-            Err(e) => Json(serde_json::json!({"success": false, "error": e})),
-        },
+        }
         Err(e) => Json(serde_json::json!({"success": false, "error": format!("lock: {}", e)})),
     }
 }
@@ -5779,7 +6883,7 @@ async fn handle_wm_causal_counterfactual(
 /// POST /memory/reflect — run AureusBridge reflexion over memory context
 #[cfg(feature = "web-server")]
 async fn handle_memory_reflect<B: MemoryBackend + Send + Sync + 'static>(
-    memory_store: Arc<Mutex<MemoryStore<B>>>,
+    memory_store: AsyncMemoryStore<B>,
     aureus: Arc<Mutex<AureusBridge>>,
     Json(req): Json<serde_json::Value>,
 ) -> Json<serde_json::Value> {
@@ -5787,27 +6891,31 @@ async fn handle_memory_reflect<B: MemoryBackend + Send + Sync + 'static>(
         .as_str()
         .unwrap_or("recent decisions")
         .to_string();
-    // Lock store first, then bridge (consistent lock ordering to avoid deadlock)
-    let mut store = match memory_store.lock() {
-        Ok(s) => s,
-        Err(e) => return Json(serde_json::json!({"error": format!("store lock: {}", e)})),
-    };
-    let mut bridge = match aureus.lock() {
-        Ok(b) => b,
-        Err(e) => return Json(serde_json::json!({"error": format!("bridge lock: {}", e)})),
-    };
-    let llm_available = bridge.llm_configured();
-    let loops_before = bridge.loops_run();
-    let hyp = bridge.reflect_on_memory(&query, &mut *store);
-    let is_fallback = bridge.loops_run() == loops_before; // no new loop ran → fallback
-    Json(serde_json::json!({
-        "hypothesis":    hyp.text,
-        "confidence":    hyp.confidence,
-        "evidence":      hyp.evidence,
-        "loops_run":     bridge.loops_run(),
-        "llm_available": llm_available,
-        "is_fallback":   is_fallback,
-    }))
+    // The reflection may call an LLM, so it runs on the blocking pool. Lock
+    // store first, then bridge (consistent lock ordering to avoid deadlock).
+    let reflected = memory_store
+        .transact(move |store| {
+            let mut bridge = aureus
+                .lock()
+                .map_err(|e| anyhow::anyhow!("bridge lock: {}", e))?;
+            let llm_available = bridge.llm_configured();
+            let loops_before = bridge.loops_run();
+            let hyp = bridge.reflect_on_memory(&query, store);
+            let is_fallback = bridge.loops_run() == loops_before; // no new loop ran → fallback
+            Ok(serde_json::json!({
+                "hypothesis":    hyp.text,
+                "confidence":    hyp.confidence,
+                "evidence":      hyp.evidence,
+                "loops_run":     bridge.loops_run(),
+                "llm_available": llm_available,
+                "is_fallback":   is_fallback,
+            }))
+        })
+        .await;
+    match reflected {
+        Ok(body) => Json(body),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
 }
 
 /// GET /memory/hypotheses — AureusBridge reflexion metadata
@@ -5842,18 +6950,20 @@ async fn handle_memory_hypotheses(aureus: Arc<Mutex<AureusBridge>>) -> Json<serd
 async fn handle_worldmodel_status(
     world_model: Arc<RwLock<WorldModelEnhanced>>,
 ) -> Json<serde_json::Value> {
-    let (total_transitions, entity_count) = match world_model.read() {
+    let (total_transitions, entity_count, change_points) = match world_model.read() {
         Ok(wm) => (
             wm.transition_count(),
             wm.list_entities().unwrap_or_default().len(),
+            wm.transition_change_points().unwrap_or_default().len(),
         ),
-        Err(_) => (0, 0),
+        Err(_) => (0, 0, 0),
     };
     Json(serde_json::json!({
         "status": "available",
         "mode": "full",
         "total_transitions_observed": total_transitions,
         "tracked_entities": entity_count,
+        "transition_change_points": change_points,
         "endpoints": {
             "observe": "POST /worldmodel/observe",
             "predict": "POST /worldmodel/predict {state,action} (also GET ?state=&action=)",
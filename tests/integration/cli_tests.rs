@@ -162,3 +162,236 @@ fn cli_prompt_stores_reflexion() {
     assert!(data.contains("\"action\":\"prompt\""));
     std::fs::remove_file(path).unwrap();
 }
+
+#[test]
+fn cli_maintain_runs_selected_jobs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let path = path.to_str().unwrap();
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "maintain", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("purge_expired").and(predicate::str::contains("consolidation")));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "maintain", "--job", "purge_expired", "--job", "decay"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("purged 0 expired records"))
+        .stdout(predicate::str::contains("cognitive_gc").not());
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "maintain", "--job", "nope"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn cli_checkpoint_and_recover() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let path = path.to_str().unwrap();
+    let add = |target: &str| {
+        Command::cargo_bin("cli")
+            .unwrap()
+            .args(["--store", path, "add", "--actor", "a", "--action", "saw", "--target", target])
+            .assert()
+            .success();
+    };
+    add("before");
+    let out = Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "checkpoint"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let tx = out.split_whitespace().nth(3).unwrap().to_string();
+    add("after");
+
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "recover", "--tx", &tx, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 records").and(predicate::str::contains("after").not()));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "recover", "--tx", &tx])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 -> 1 records"));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "query"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("before").and(predicate::str::contains("after").not()));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "recover"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn cli_import_dry_run_then_import_dedups() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let path = path.to_str().unwrap();
+    let input = dir.path().join("notes.csv");
+    std::fs::write(&input, "actor,action,target\nalice,said,hello\nbob,said,\n").unwrap();
+    let input = input.to_str().unwrap();
+
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "import", input, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would import 1 of 1").and(predicate::str::contains("line 3")));
+    let report = dir.path().join("report.json");
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "import", input, "--report", report.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("imported 1 of 1"));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report).unwrap()).unwrap();
+    assert_eq!(report["format"], "csv");
+    assert_eq!(report["errors"].as_array().unwrap().len(), 1);
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", path, "import", input])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("imported 0 of 1 records, 1 duplicates"));
+}
+
+#[test]
+fn cli_bundle_export_then_import_into_fresh_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source.jsonl");
+    let source = source.to_str().unwrap();
+    let target = dir.path().join("target.jsonl");
+    let target = target.to_str().unwrap();
+    let bundle = dir.path().join("state.json.gz");
+    let bundle = bundle.to_str().unwrap();
+
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", source, "add", "--actor", "alice", "--action", "said", "--target", "hi"])
+        .assert()
+        .success();
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", source, "export-bundle", bundle])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("exported 1 records"));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", target, "import-bundle", bundle, "--ns", "=team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 records added, 0 already present, 0 removed, 1 remapped"));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", target, "import-bundle", bundle])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 records added, 1 already present"));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", target, "import-bundle", bundle, "--ns", "bad"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn cli_migrate_upgrades_legacy_store_with_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("legacy.jsonl");
+    let line = serde_json::json!({
+        "id": "6f1c2a4e-8d1b-4c9e-9f5a-2b7d3e4f5a6b",
+        "record_type": "Symbolic",
+        "timestamp": "2025-01-01T00:00:00Z",
+        "actor": "alice", "action": "said", "target": "hi",
+        "metadata": {}, "priority": "URGENT", "status": "archive",
+    });
+    std::fs::write(&store, format!("{line}\n")).unwrap();
+    let store = store.to_str().unwrap();
+
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", store, "migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("format 1 -> 2: would update 1 of 1 records"));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", store, "migrate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("updated 1 of 1 records"))
+        .stdout(predicate::str::contains("legacy.jsonl.v1.bak"));
+    let content = std::fs::read_to_string(store).unwrap();
+    assert!(content.starts_with("{\"hipcortex_format\":2"));
+    assert!(content.contains("\"priority\":\"high\"") && content.contains("\"status\":\"archived\""));
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", store, "migrate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already at format 2"));
+}
+
+#[test]
+fn cli_add_redacts_pii_into_vault_and_rehydrates() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("memory.jsonl");
+    let store = store.to_str().unwrap();
+    let key = "0a".repeat(32);
+    let cli = || {
+        let mut cmd = Command::cargo_bin("cli").unwrap();
+        cmd.env("HIPCORTEX_VAULT_KEY", &key).env(
+            "HIPCORTEX_SAFETY_POLICY",
+            r#"{"default": {"redact": ["PII"]}}"#,
+        );
+        cmd
+    };
+
+    let out = cli()
+        .args(["--store", store, "add", "--actor", "alice", "--action", "emailed"])
+        .args(["--target", "sam@example.com"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("redacted 1 span(s): [[EMAIL:"))
+        .get_output()
+        .stdout
+        .clone();
+    let token = String::from_utf8(out).unwrap();
+    let token = token.trim().rsplit(' ').next().unwrap().to_string();
+
+    let content = std::fs::read_to_string(store).unwrap();
+    assert!(!content.contains("sam@example.com") && content.contains(&token));
+    let vault = std::fs::read_to_string(dir.path().join("memory-vault.jsonl")).unwrap();
+    assert!(!vault.contains("sam@example.com"));
+
+    cli()
+        .args(["--store", store, "rehydrate", &format!("write to {token}")])
+        .assert()
+        .success()
+        .stdout("write to sam@example.com\n");
+    // Without a policy the default still refuses PII.
+    Command::cargo_bin("cli")
+        .unwrap()
+        .args(["--store", store, "add", "--actor", "alice", "--action", "emailed"])
+        .args(["--target", "sam@example.com"])
+        .assert()
+        .failure();
+}
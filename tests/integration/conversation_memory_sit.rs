@@ -23,3 +23,25 @@ fn openmanus_round_trip_into_store() {
     assert_eq!(store.all().len(), 2);
     std::fs::remove_file(path).unwrap();
 }
+
+#[test]
+fn threads_survive_store_reopen() {
+    use hipcortex::conversation_memory::ConversationThreads;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("conv.jsonl");
+    let threads = ConversationThreads::default();
+    {
+        let mut store = MemoryStore::new(path.to_str().unwrap()).unwrap();
+        threads.append(&mut store, "s1", "user", "I live in Oslo", None).unwrap();
+        threads.append(&mut store, "s1", "assistant", "Noted.", None).unwrap();
+    }
+    let mut store = MemoryStore::new(path.to_str().unwrap()).unwrap();
+    let next = threads.append(&mut store, "s1", "user", "thanks", None).unwrap();
+    assert_eq!(next.message.turn, 2);
+    let page = threads.page(&store, "s1", 0, 10);
+    assert_eq!(page.total, 3);
+    assert!(store
+        .all()
+        .iter()
+        .any(|r| r.record_type == MemoryType::Belief && r.target == "user lives in Oslo"));
+}
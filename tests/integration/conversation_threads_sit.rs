@@ -0,0 +1,133 @@
+//! SIT: /conversations/:session — append, page and summarize over HTTP.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::tx_log::TxLog;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state(tx_log: Option<Arc<TxLog>>) -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        tx_log.clone(),
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-conv-archive.jsonl"),
+        ))),
+        tx_log,
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn append_page_and_summarize_over_http() {
+    let addr: std::net::SocketAddr = "127.0.0.1:3073".parse().unwrap();
+    let state = make_state(None);
+    let _srv = tokio::spawn(async move {
+        hipcortex::web_server::run_with_state(addr, state).await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+    let base = "http://127.0.0.1:3073";
+
+    let first: serde_json::Value = client
+        .post(format!("{base}/conversations/chat-1"))
+        .json(&serde_json::json!({"role": "user", "text": "My name is Ada. I prefer short answers."}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(first["message"]["turn"], 0);
+    assert_eq!(first["beliefs"].as_array().unwrap().len(), 2);
+    let first_id = first["message"]["id"].as_str().unwrap().to_string();
+
+    let reply: serde_json::Value = client
+        .post(format!("{base}/conversations/chat-1"))
+        .json(&serde_json::json!({"role": "assistant", "text": "Hi Ada."}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reply["message"]["reply_to"], first_id.as_str());
+
+    // reply_to from another session is rejected.
+    let resp = client
+        .post(format!("{base}/conversations/chat-2"))
+        .json(&serde_json::json!({"role": "user", "text": "hello", "reply_to": first_id}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let page: serde_json::Value = client
+        .get(format!("{base}/conversations/chat-1?offset=1&limit=5"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["turns"][0]["text"], "Hi Ada.");
+
+    let summary: serde_json::Value = client
+        .post(format!("{base}/conversations/chat-1/summarize"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(summary["summary_id"].is_string());
+    let page: serde_json::Value = client
+        .get(format!("{base}/conversations/chat-1"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(page["summary"].is_string());
+
+    let sessions: serde_json::Value = client
+        .get(format!("{base}/conversations"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(sessions["sessions"], serde_json::json!(["chat-1"]));
+}
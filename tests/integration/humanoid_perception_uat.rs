@@ -17,6 +17,7 @@ fn user_flow_humanoid_robotics_trace() {
         text: Some("robot perception".into()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["humanoid".into()],
     };
     let out = PerceptionAdapter::adapt(input.clone()).unwrap();
@@ -1,5 +1,6 @@
 //! SIT: ContinuousDynamics, HybridRollout, DigitalTwin, ExperienceStore integration.
 
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::cognitive_state::CognitiveHandle;
 use hipcortex::coherence::CoherenceChecker;
 use hipcortex::continuous_dynamics::ContinuousDynamics;
@@ -14,7 +15,7 @@ use hipcortex::world_model_enhanced::WorldModelEnhanced;
 use std::sync::{Arc, Mutex, RwLock};
 
 fn make_handle() -> Arc<CognitiveHandle<InMemoryBackend>> {
-    let ms = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let ms = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let wm = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let sm = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());
@@ -64,7 +65,7 @@ fn digital_twin_rollout_produces_hybrid_result() {
 
 #[test]
 fn experience_store_classifies_raw_correctly() {
-    let ms = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let ms = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let mut store_guard = ms.lock().unwrap();
     for i in 0..5 {
         let rec = MemoryRecord::new(
@@ -86,7 +87,7 @@ fn experience_store_classifies_raw_correctly() {
 
 #[test]
 fn experience_store_compression_ratio_is_one_for_all_raw() {
-    let ms = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let ms = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     {
         let mut g = ms.lock().unwrap();
         let rec = MemoryRecord::new(MemoryType::Temporal, "a".into(), "b".into(), "c".into(), serde_json::json!({}));
@@ -102,7 +103,7 @@ fn experience_store_compression_ratio_is_one_for_all_raw() {
 #[test]
 fn experience_store_raw_pressure_at_capacity() {
     // pressure = raw_count / RAW_CAP; at 1 record pressure << 1
-    let ms = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let ms = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     {
         let mut g = ms.lock().unwrap();
         let rec = MemoryRecord::new(MemoryType::Temporal, "a".into(), "b".into(), "c".into(), serde_json::json!({}));
@@ -0,0 +1,126 @@
+//! SIT: /memory/import — format detection, dry run and dedup over HTTP.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::tx_log::TxLog;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state(tx_log: Option<Arc<TxLog>>) -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        tx_log.clone(),
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-import-archive.jsonl"),
+        ))),
+        tx_log,
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn import_over_http() {
+    let addr: std::net::SocketAddr = "127.0.0.1:3074".parse().unwrap();
+    let state = make_state(None);
+    let store = state.memory_store.clone();
+    let _srv = tokio::spawn(async move {
+        hipcortex::web_server::run_with_state(addr, state).await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+    let url = "http://127.0.0.1:3074/memory/import";
+    let chat = serde_json::json!({"id": "s-1", "messages": [
+        {"role": "user", "content": "hi"},
+        {"role": "assistant", "content": "hello"}
+    ]});
+
+    let dry: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({"content": chat, "dry_run": true}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dry["format"], "openai");
+    assert_eq!(dry["imported"], 2);
+    assert!(store.lock().unwrap().all().is_empty());
+
+    let done: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({"content": chat, "format": "chatgpt"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(done["record_ids"].as_array().unwrap().len(), 2);
+    let again: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({"content": chat}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(again["duplicates"], 2);
+
+    // CSV as a string body needs a format or an origin with an extension.
+    let csv: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({"content": "actor,target\nops,deploy done\n", "origin": "ops.csv"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(csv["imported"], 1);
+    let resp = client
+        .post(url)
+        .json(&serde_json::json!({"content": "plain words"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let resp = client
+        .post(url)
+        .json(&serde_json::json!({"content": "x", "format": "xml"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    assert_eq!(store.lock().unwrap().all().len(), 3);
+}
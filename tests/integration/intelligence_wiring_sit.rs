@@ -1,8 +1,9 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::archive_store::ArchiveStore;
 use hipcortex::aureus_bridge::AureusBridge;
 use hipcortex::coherence::CoherenceChecker;
 /// SIT tests for intelligence layer wiring (requires web-server feature — gated in mod.rs)
-use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority, RecordStatus};
 use hipcortex::memory_store::MemoryStore;
 use hipcortex::persistence::InMemoryBackend;
 use hipcortex::self_model::calibration::CalibrationTracker;
@@ -14,7 +15,7 @@ use hipcortex::CausalTopoGraph;
 use std::sync::{Arc, Mutex, RwLock};
 
 pub fn make_app_state() -> AppState<InMemoryBackend> {
-    let memory_store = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let self_model = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());
@@ -531,15 +532,15 @@ fn test_memory_record_has_confidence_field() {
 #[test]
 fn test_memory_record_has_all_new_fields() {
     let mut r = make_record("alice", "decided", "use_postgres");
-    r.priority = "high".to_string();
+    r.priority = Priority::High;
     r.tags = vec!["database".to_string()];
     r.version = 2;
-    r.status = "active".to_string();
+    r.status = RecordStatus::Active;
     r.source = Some("user-input".to_string());
-    assert_eq!(r.priority, "high");
+    assert_eq!(r.priority, Priority::High);
     assert_eq!(r.tags.len(), 1);
     assert_eq!(r.version, 2);
-    assert_eq!(r.status, "active");
+    assert_eq!(r.status, RecordStatus::Active);
     assert!(r.source.is_some());
 }
 
@@ -552,7 +553,7 @@ fn test_query_memory_response_includes_confidence() {
         let mut ms = state.memory_store.lock().unwrap();
         let mut r = make_record("alice", "decided", "use_postgres");
         r.confidence = 0.8;
-        r.priority = "high".to_string();
+        r.priority = Priority::High;
         r.tags = vec!["database".to_string()];
         ms.add(r).unwrap();
     }
@@ -562,6 +563,6 @@ fn test_query_memory_response_includes_confidence() {
     assert!(!records.is_empty());
     let r = records[0];
     assert_eq!(r.confidence, 0.8_f32);
-    assert_eq!(r.priority, "high");
+    assert_eq!(r.priority, Priority::High);
     assert_eq!(r.tags[0], "database");
 }
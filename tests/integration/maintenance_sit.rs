@@ -0,0 +1,100 @@
+//! SIT: /maintenance endpoints on the web server.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state() -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        None,
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-maintenance-archive.jsonl"),
+        ))),
+        tx_log: None,
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn maintenance_jobs_can_be_listed_triggered_and_paused() {
+    let addr: std::net::SocketAddr = "127.0.0.1:3071".parse().unwrap();
+    let state = make_state();
+    let srv = tokio::spawn(async move {
+        hipcortex::web_server::run_with_state(addr, state).await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+    let base = "http://127.0.0.1:3071";
+
+    let jobs: serde_json::Value = client.get(format!("{base}/maintenance/jobs")).send().await.unwrap().json().await.unwrap();
+    let names: Vec<&str> = jobs["jobs"].as_array().unwrap().iter().map(|j| j["name"].as_str().unwrap()).collect();
+    assert!(names.contains(&"purge_expired"));
+    assert!(names.contains(&"workspace_evict"));
+    assert!(names.contains(&"lease_cleanup"));
+    // No tx log in this state → consolidation job is not registered.
+    assert!(!names.contains(&"consolidation"));
+
+    let resp = client.post(format!("{base}/maintenance/jobs/decay/run")).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let run: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(run["ok"], true);
+    assert_eq!(run["trigger"], "manual");
+
+    let paused: serde_json::Value = client
+        .post(format!("{base}/maintenance/jobs/decay/pause"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(paused["paused"], true);
+
+    let history: serde_json::Value = client
+        .get(format!("{base}/maintenance/history?job=decay"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(history["count"], 1);
+
+    let missing = client.post(format!("{base}/maintenance/jobs/nope/run")).send().await.unwrap();
+    assert_eq!(missing.status().as_u16(), 404);
+    let bad = client.post(format!("{base}/maintenance/jobs/decay/explode")).send().await.unwrap();
+    assert_eq!(bad.status().as_u16(), 400);
+
+    srv.abort();
+}
@@ -2,6 +2,8 @@ mod agent_bridge_sit;
 mod cli_tests;
 mod conversation_memory_sit;
 mod conversation_memory_uat;
+#[cfg(feature = "web-server")]
+mod conversation_threads_sit;
 mod edge_workflow_sit;
 mod edge_workflow_uat;
 mod effort_confidence_sit;
@@ -10,6 +12,10 @@ mod graph_backend_sit;
 #[cfg(feature = "grpc-server")]
 mod grpc_tests;
 mod humanoid_perception_uat;
+#[cfg(feature = "web-server")]
+mod importers_sit;
+#[cfg(feature = "web-server")]
+mod injection_defense_sit;
 mod integration_tests;
 mod intelligence_hooks_sit;
 #[cfg(feature = "web-server")]
@@ -18,11 +24,15 @@ mod intelligence_uat;
 #[cfg(feature = "web-server")]
 mod intelligence_wiring_sit;
 mod llm_integration_tests;
+#[cfg(feature = "web-server")]
+mod maintenance_sit;
 #[cfg(all(feature = "web-server", feature = "grpc-server"))]
 mod mcp_server_sit;
 #[cfg(all(feature = "web-server", feature = "grpc-server"))]
 mod mcp_server_uat;
 mod openmanus_integration_sit;
+#[cfg(feature = "web-server")]
+mod pitr_sit;
 mod plugin_host_sit;
 mod plugin_host_uat;
 mod rag_export_sit;
@@ -32,6 +42,8 @@ mod reasoning_trace_sit_tests;
 mod retrieval_pipeline_sit;
 mod retrieval_pipeline_uat;
 mod safety_guardrail_sit;
+#[cfg(feature = "web-server")]
+mod safety_redaction_sit;
 mod semantic_cache_sit;
 mod semantic_cache_uat;
 mod semantic_compression_sit;
@@ -39,6 +51,8 @@ mod semantic_compression_uat;
 #[cfg(feature = "web-server")]
 mod sit_tests;
 mod smart_glasses_sit;
+#[cfg(feature = "web-server")]
+mod state_bundle_sit;
 mod system_integration_tests;
 mod test_end_to_end;
 #[cfg(feature = "web-server")]
@@ -14,6 +14,7 @@ fn openmanus_message_through_adapter_and_layer() {
         text: Some(msg.text.clone()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["sit".into()],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
@@ -0,0 +1,204 @@
+//! SIT: point-in-time recovery — `as_of` reads and /v1/state/restore over HTTP.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::tx_log::TxLog;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state(tx_log: Arc<TxLog>) -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        Some(tx_log.clone()),
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-pitr-archive.jsonl"),
+        ))),
+        tx_log: Some(tx_log),
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn as_of_reads_and_restore_over_http() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = Arc::new(TxLog::open(dir.path().join("tx.jsonl")).unwrap());
+    let addr: std::net::SocketAddr = "127.0.0.1:3072".parse().unwrap();
+    let state = make_state(log);
+    let _srv = tokio::spawn(async move {
+        hipcortex::web_server::run_with_state(addr, state).await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+    let base = "http://127.0.0.1:3072";
+    // Process-wide; the same key safety_redaction_sit sets, since they share a binary.
+    std::env::set_var("HIPCORTEX_ADMIN_KEY", "safety-sit-admin");
+
+    let add = |target: &'static str| {
+        let client = client.clone();
+        async move {
+            let body: serde_json::Value = client
+                .post(format!("{base}/memory/add"))
+                .json(&serde_json::json!({"actor": "agent", "action": "believes", "target": target}))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            body["record_id"].as_str().unwrap().to_string()
+        }
+    };
+    let current_tx = || {
+        let client = client.clone();
+        async move {
+            let v: serde_json::Value = client
+                .get(format!("{base}/v1/state/tx"))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            v["current_tx"].as_u64().unwrap()
+        }
+    };
+
+    let id_a = add("door is open").await;
+    let tx1 = current_tx().await;
+    add("door is locked").await;
+    let resp = client
+        .patch(format!("{base}/memory/update/{id_a}"))
+        .json(&serde_json::json!({"target": "door is closed"}))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    // Query as of tx1: only the first record, in its original form.
+    let past: serde_json::Value = client
+        .get(format!("{base}/memory/query?as_of={tx1}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(past["total"], 1);
+    assert_eq!(past["records"][0]["target"], "door is open");
+
+    let then: serde_json::Value = client
+        .get(format!("{base}/memory/{id_a}?as_of={tx1}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(then["target"], "door is open");
+    let now: serde_json::Value = client
+        .get(format!("{base}/memory/{id_a}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(now["target"], "door is closed");
+
+    let hits: serde_json::Value = client
+        .post(format!("{base}/memory/search"))
+        .json(&serde_json::json!({"query": "door", "as_of": tx1.to_string()}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(hits["total"], 1);
+
+    // A tx beyond the log is a bad request, not an empty result.
+    let resp = client
+        .get(format!("{base}/memory/query?as_of=999999"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // An unparseable as_of is rejected with the parse error, not read live.
+    let resp = client
+        .post(format!("{base}/memory/search"))
+        .json(&serde_json::json!({"query": "door", "as_of": "yesterday-ish"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body["error"].as_str().is_some_and(|e| !e.is_empty()), "{body}");
+    let resp = client
+        .get(format!("{base}/memory/query?as_of=yesterday-ish"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    // Restore is an admin route.
+    let resp = client
+        .post(format!("{base}/v1/state/restore"))
+        .json(&serde_json::json!({"tx": tx1}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    let report: serde_json::Value = client
+        .post(format!("{base}/v1/state/restore"))
+        .header("X-Admin-Key", "safety-sit-admin")
+        .json(&serde_json::json!({"tx": tx1}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["target_tx"], tx1);
+    assert_eq!(report["records_after"], 1);
+    let live: serde_json::Value = client
+        .get(format!("{base}/memory/query"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(live["total"], 1);
+    assert_eq!(live["records"][0]["target"], "door is open");
+}
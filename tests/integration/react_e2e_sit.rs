@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use hipcortex::loop_engine::ReactEngine;
-    use hipcortex::memory_record::{MemoryRecord, MemoryType};
+    use hipcortex::memory_record::{MemoryRecord, MemoryType, RecordStatus};
     use hipcortex::memory_store::MemoryStore;
     use hipcortex::payloads::{GoalPayload, GoalStatus, SuccessFactor};
 
@@ -140,7 +140,7 @@ mod tests {
 
         let search_results = store.search_semantic(None, "test_target", 100, false);
         assert!(
-            !search_results.iter().any(|(r, _)| r.status == "archived"),
+            !search_results.iter().any(|(r, _)| r.status == RecordStatus::Archived),
             "Archived records must not appear in default search"
         );
     }
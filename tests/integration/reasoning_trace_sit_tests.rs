@@ -12,6 +12,7 @@ fn store_reasoning_trace_via_adapter_and_indexer() {
         text: Some("remember me".to_string()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["sit".into()],
     };
     let out = PerceptionAdapter::adapt(input.clone()).unwrap();
@@ -19,6 +19,7 @@ fn retrieval_round_trip() {
         text: Some("article content".into()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec![],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
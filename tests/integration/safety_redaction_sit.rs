@@ -0,0 +1,206 @@
+//! SIT: namespace safety policy redacts PII on /memory/add; /v1/safety/rehydrate reverses it.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::redaction_vault::RedactionVault;
+use hipcortex::safety_guardrail::SAFETY_GUARDRAIL;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state() -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        None,
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-safety-archive.jsonl"),
+        ))),
+        tx_log: None,
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn namespace_policy_redacts_and_vault_rehydrates() {
+    let addr: std::net::SocketAddr = "127.0.0.1:3077".parse().unwrap();
+    let base = "http://127.0.0.1:3077";
+    let state = make_state();
+    let store = state.memory_store.clone();
+    tokio::spawn(async move { hipcortex::web_server::run_with_state(addr, state).await });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+    std::env::set_var("HIPCORTEX_ADMIN_KEY", "safety-sit-admin");
+
+    SAFETY_GUARDRAIL
+        .lock()
+        .unwrap()
+        .set_vault(Some(Arc::new(RedactionVault::new_in_memory([3u8; 32]))));
+    let policies = json!({"namespaces": {"support-sit": {"redact": ["PII"]}}});
+    // Policy changes and rehydration are admin-only.
+    let resp = client
+        .put(format!("{base}/v1/safety/policy"))
+        .json(&policies)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let resp = client
+        .put(format!("{base}/v1/safety/policy"))
+        .header("X-Admin-Key", "not-the-admin")
+        .json(&policies)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let resp = client
+        .put(format!("{base}/v1/safety/policy"))
+        .header("X-Admin-Key", "safety-sit-admin")
+        .json(&policies)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let current: Value = client
+        .get(format!("{base}/v1/safety/policy"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(current["backend"], "regex");
+    assert_eq!(
+        current["policies"]["namespaces"]["support-sit"]["redact"],
+        json!(["PII"])
+    );
+
+    let body: Value = client
+        .post(format!("{base}/memory/add"))
+        .json(&json!({
+            "actor": "agent",
+            "action": "emailed",
+            "target": "follow up with dana@example.com",
+            "tags": ["ns:support-sit"],
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["success"], true, "{body}");
+    assert_eq!(body["safety"]["action"], "Redact");
+    assert_eq!(body["safety"]["redacted"], 1);
+    let stored = store
+        .lock()
+        .unwrap()
+        .all()
+        .iter()
+        .find(|r| r.tags.contains(&"ns:support-sit".to_string()))
+        .map(|r| r.target.clone())
+        .unwrap();
+    assert!(stored.starts_with("follow up with [[EMAIL:"), "{stored}");
+
+    let resp = client
+        .post(format!("{base}/v1/safety/rehydrate"))
+        .json(&json!({ "text": stored }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let rehydrated: Value = client
+        .post(format!("{base}/v1/safety/rehydrate"))
+        .header("X-Admin-Key", "safety-sit-admin")
+        .json(&json!({ "text": stored }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(rehydrated["text"], "follow up with dana@example.com");
+
+    // Updates are screened like adds.
+    let id = body["record_id"].as_str().unwrap();
+    let resp = client
+        .patch(format!("{base}/memory/update/{id}"))
+        .json(&json!({"target": "now ask erin@example.com"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let updated = store
+        .snapshot()
+        .find_by_id(id.parse().unwrap())
+        .map(|r| r.target.clone())
+        .unwrap();
+    assert!(updated.starts_with("now ask [[EMAIL:"), "{updated}");
+
+    // Other write routes share the store's screen, metadata strings included.
+    let bulk: Value = client
+        .post(format!("{base}/memory/bulk"))
+        .json(&json!({"records": [{
+            "actor": "agent",
+            "action": "noted",
+            "target": "customer follow-up",
+            "metadata": {"contact": "dana@example.com"},
+        }]}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bulk["inserted"], 0, "{bulk}");
+    assert!(bulk["errors"][0]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("record blocked"));
+
+    // Outside the namespace the default policy still refuses the write.
+    let resp = client
+        .post(format!("{base}/memory/add"))
+        .json(&json!({
+            "actor": "agent",
+            "action": "emailed",
+            "target": "follow up with dana@example.com",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+
+    SAFETY_GUARDRAIL
+        .lock()
+        .unwrap()
+        .set_policies(Default::default());
+}
@@ -41,6 +41,7 @@ fn smart_glasses_capture_and_retrieve() {
         text: None,
         embedding: Some(embedding),
         image_data: Some(bytes),
+        audio: None,
         tags: vec!["glasses".into()],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
@@ -0,0 +1,125 @@
+//! SIT: /v1/state/bundle — export from one instance, import into another.
+
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::aureus_bridge::AureusBridge;
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::SelfModel;
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::tx_log::TxLog;
+use hipcortex::web_server::AppState;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::CausalTopoGraph;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn make_state(tx_log: Option<Arc<TxLog>>) -> AppState<InMemoryBackend> {
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
+    let self_model = Arc::new(SelfModel::new());
+    let coherence = Arc::new(CoherenceChecker::new());
+    let calibration = Arc::new(hipcortex::self_model::calibration::CalibrationTracker::new());
+    let cognitive = Arc::new(hipcortex::cognitive_state::CognitiveHandle::new(
+        Arc::clone(&memory_store),
+        Arc::clone(&world_model),
+        Arc::clone(&self_model),
+        tx_log.clone(),
+        Arc::clone(&coherence),
+        Arc::clone(&calibration),
+        Arc::new(hipcortex::cognitive_gc::CognitiveGC::new()),
+    ));
+    AppState {
+        memory_store,
+        symbolic_store: Arc::new(Mutex::new(SymbolicStore::new())),
+        world_model,
+        aureus: Arc::new(Mutex::new(AureusBridge::new())),
+        self_model,
+        coherence,
+        topo_graph: Arc::new(Mutex::new(CausalTopoGraph::new())),
+        archive_store: Arc::new(Mutex::new(hipcortex::archive_store::ArchiveStore::new(
+            std::env::temp_dir().join("hc-test-bundle-archive.jsonl"),
+        ))),
+        tx_log,
+        calibration,
+        cognitive,
+        forks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+        twins: Arc::new(Mutex::new(std::collections::HashMap::new())),
+    }
+}
+
+#[tokio::test]
+async fn bundle_moves_state_between_instances() {
+    let source_addr: std::net::SocketAddr = "127.0.0.1:3075".parse().unwrap();
+    let target_addr: std::net::SocketAddr = "127.0.0.1:3076".parse().unwrap();
+    let source = make_state(None);
+    {
+        let mut store = source.memory_store.lock().unwrap();
+        let mut rec = MemoryRecord::new(
+            MemoryType::Symbolic,
+            "alice".into(),
+            "noted".into(),
+            "quarterly plan".into(),
+            serde_json::json!({}),
+        );
+        rec.tags.push("ns:acme".into());
+        store.add(rec).unwrap();
+    }
+    source
+        .world_model
+        .read()
+        .unwrap()
+        .observe_transition("draft".into(), "review".into(), "approved".into())
+        .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let log = Arc::new(TxLog::open(dir.path().join("tx.jsonl")).unwrap());
+    let target = make_state(Some(log.clone()));
+    let target_store = target.memory_store.clone();
+    let target_world = target.world_model.clone();
+    tokio::spawn(async move { hipcortex::web_server::run_with_state(source_addr, source).await });
+    tokio::spawn(async move { hipcortex::web_server::run_with_state(target_addr, target).await });
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let client = reqwest::Client::new();
+
+    let bundle: serde_json::Value = client
+        .get("http://127.0.0.1:3075/v1/state/bundle")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(bundle["manifest"]["format"], "hipcortex-state-bundle");
+    assert_eq!(
+        bundle["manifest"]["namespaces"],
+        serde_json::json!(["acme"])
+    );
+
+    let url = "http://127.0.0.1:3076/v1/state/bundle";
+    let report: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({"bundle": bundle, "namespace_map": {"acme": "acme-eu"}}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["records_added"], 1);
+    assert_eq!(report["records_remapped"], 1);
+    assert_eq!(report["world_model"], true);
+    assert!(report["tx"].is_u64());
+    assert_eq!(target_store.lock().unwrap().all()[0].tags, ["ns:acme-eu"]);
+    assert_eq!(target_world.read().unwrap().transition_count(), 1);
+
+    let mut tampered = bundle.clone();
+    tampered["records"][0]["target"] = serde_json::json!("rewritten");
+    let resp = client
+        .post(url)
+        .json(&serde_json::json!({"bundle": tampered, "mode": "replace"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    assert_eq!(target_store.lock().unwrap().all().len(), 1);
+}
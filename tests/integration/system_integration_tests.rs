@@ -34,6 +34,7 @@ fn memory_round_trip() {
         text: Some("travel".to_string()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec![],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
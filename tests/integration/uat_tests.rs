@@ -471,6 +471,7 @@ fn user_store_reasoning_trace() {
         text: Some("UAT reasoning".to_string()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["uat".into()],
     };
     let out = PerceptionAdapter::adapt(input.clone()).unwrap();
@@ -3,6 +3,7 @@
 //! G-BELIEFS: GET /memory/live_beliefs loops_run key
 //! G-RELATED: GET /memory/search/related results enrichment
 
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::aureus_bridge::AureusBridge;
 use hipcortex::coherence::CoherenceChecker;
 use hipcortex::memory_store::MemoryStore;
@@ -15,7 +16,7 @@ use hipcortex::CausalTopoGraph;
 use std::sync::{Arc, Mutex, RwLock};
 
 fn make_state() -> AppState<InMemoryBackend> {
-    let memory_store = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let self_model = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());
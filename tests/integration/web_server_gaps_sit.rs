@@ -6,7 +6,7 @@ use chrono::{Duration, Utc};
 /// G5 quarantine/restore/search exclusion
 /// G8 corroborate / contradict confidence
 /// G13 /memory/context LLM prompt endpoint
-use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority, RecordStatus};
 use hipcortex::memory_store::MemoryStore;
 use hipcortex::persistence::InMemoryBackend;
 
@@ -29,7 +29,7 @@ fn make_record(actor: &str, action: &str, target: &str) -> MemoryRecord {
 #[test]
 fn test_memory_record_default_status_is_active() {
     let r = make_record("alice", "decided", "use postgres");
-    assert_eq!(r.status, "active");
+    assert_eq!(r.status, RecordStatus::Active);
 }
 
 #[test]
@@ -43,7 +43,7 @@ fn test_memory_record_status_serializes() {
 fn test_memory_record_status_deserializes_missing_as_active() {
     let json = r#"{"id":"00000000-0000-0000-0000-000000000001","record_type":"Symbolic","timestamp":"2024-01-01T00:00:00Z","actor":"alice","action":"decided","target":"use postgres","metadata":{}}"#;
     let r: MemoryRecord = serde_json::from_str(json).unwrap();
-    assert_eq!(r.status, "active");
+    assert_eq!(r.status, RecordStatus::Active);
 }
 
 // ── G5: MemoryStore.set_status ────────────────────────────────────────────────
@@ -54,9 +54,9 @@ fn test_set_status_quarantine() {
     let r = make_record("alice", "decided", "use postgres");
     let id = r.id;
     store.add(r).unwrap();
-    store.set_status(id, "quarantine").unwrap();
+    store.set_status(id, RecordStatus::Quarantine).unwrap();
     let found = store.find_by_id(id).unwrap();
-    assert_eq!(found.status, "quarantine");
+    assert_eq!(found.status, RecordStatus::Quarantine);
 }
 
 #[test]
@@ -65,17 +65,17 @@ fn test_set_status_restore() {
     let r = make_record("alice", "decided", "use postgres");
     let id = r.id;
     store.add(r).unwrap();
-    store.set_status(id, "quarantine").unwrap();
-    store.set_status(id, "active").unwrap();
+    store.set_status(id, RecordStatus::Quarantine).unwrap();
+    store.set_status(id, RecordStatus::Active).unwrap();
     let found = store.find_by_id(id).unwrap();
-    assert_eq!(found.status, "active");
+    assert_eq!(found.status, RecordStatus::Active);
 }
 
 #[test]
 fn test_set_status_not_found_errors() {
     let mut store = make_store();
     let fake_id = uuid::Uuid::new_v4();
-    assert!(store.set_status(fake_id, "quarantine").is_err());
+    assert!(store.set_status(fake_id, RecordStatus::Quarantine).is_err());
 }
 
 // ── G5: search excludes quarantined by default ────────────────────────────────
@@ -86,7 +86,7 @@ fn test_search_excludes_quarantined() {
     let r = make_record("alice", "decided", "postgres is the database");
     let id = r.id;
     store.add(r).unwrap();
-    store.set_status(id, "quarantine").unwrap();
+    store.set_status(id, RecordStatus::Quarantine).unwrap();
     let results = store.search_semantic(None, "postgres", 10, false);
     assert!(
         results.iter().all(|(rec, _)| rec.id != id),
@@ -100,7 +100,7 @@ fn test_search_includes_quarantined_when_flag_set() {
     let r = make_record("alice", "decided", "postgres is the database");
     let id = r.id;
     store.add(r).unwrap();
-    store.set_status(id, "quarantine").unwrap();
+    store.set_status(id, RecordStatus::Quarantine).unwrap();
     let results = store.search_semantic(None, "postgres", 10, true);
     assert!(
         results.iter().any(|(rec, _)| rec.id == id),
@@ -230,7 +230,7 @@ fn test_contradict_auto_quarantines_at_low_confidence() {
         "should auto-quarantine when confidence drops below 0.3"
     );
     let found = store.find_by_id(id).unwrap();
-    assert_eq!(found.status, "quarantine");
+    assert_eq!(found.status, RecordStatus::Quarantine);
 }
 
 #[test]
@@ -277,7 +277,7 @@ fn test_search_includes_non_expired_record() {
 fn test_search_excludes_expired_pinned_record() {
     let mut store = make_store();
     let mut r = make_record("alice", "decided", "use postgres");
-    r.priority = "pinned".to_string();
+    r.priority = Priority::Pinned;
     r.expires_at = Some(Utc::now().timestamp() - 1); // expired
     store.add(r).unwrap();
     let results = store.search_semantic(None, "postgres", 10, false);
@@ -293,7 +293,7 @@ fn test_pinned_record_with_query_match_scores_2_0_not_decayed() {
 
     // Old pinned record with short half-life — WITHOUT the fix it would get decayed and score < 2.0
     let mut pinned_r = make_record("alice", "decided", "use postgres architecture");
-    pinned_r.priority = "pinned".to_string();
+    pinned_r.priority = Priority::Pinned;
     pinned_r.timestamp = Utc::now() - Duration::seconds(200);
     pinned_r.metadata = serde_json::json!({
         "decay_factor": 1.0,
@@ -336,7 +336,7 @@ fn test_high_priority_ranks_above_normal_for_same_content() {
     let mut store = make_store();
 
     let mut high_r = make_record("alice", "decided", "use postgres as database");
-    high_r.priority = "high".to_string();
+    high_r.priority = Priority::High;
     store.add(high_r.clone()).unwrap();
 
     let normal_r = make_record("bob", "decided", "use postgres as database");
@@ -364,7 +364,7 @@ fn test_low_priority_ranks_below_normal_for_same_content() {
     let mut store = make_store();
 
     let mut low_r = make_record("alice", "decided", "use redis for cache");
-    low_r.priority = "low".to_string();
+    low_r.priority = Priority::Low;
     store.add(low_r.clone()).unwrap();
 
     let normal_r = make_record("bob", "decided", "use redis for cache");
@@ -461,9 +461,9 @@ fn test_causal_edge_condition_fires_for_symbolic_normal_priority() {
     // The new condition: record_type == Symbolic OR priority == "pinned"
     // This test documents that Symbolic + normal priority SHOULD trigger causal edge.
     let record_type = MemoryType::Symbolic;
-    let priority = "normal";
+    let priority = Priority::Normal;
     // OR condition
-    let should_fire = matches!(record_type, MemoryType::Symbolic) || priority == "pinned";
+    let should_fire = matches!(record_type, MemoryType::Symbolic) || priority == Priority::Pinned;
     assert!(
         should_fire,
         "Symbolic record with normal priority must fire causal edge (OR condition)"
@@ -475,9 +475,9 @@ fn test_causal_edge_condition_old_and_semantics_would_fail() {
     use hipcortex::memory_record::MemoryType;
     // Documenting that the OLD AND condition was too restrictive
     let record_type = MemoryType::Symbolic;
-    let priority = "normal";
+    let priority = Priority::Normal;
     // OLD condition: AND — this was the bug
-    let old_condition = matches!(record_type, MemoryType::Symbolic) && priority == "pinned";
+    let old_condition = matches!(record_type, MemoryType::Symbolic) && priority == Priority::Pinned;
     assert!(
         !old_condition,
         "OLD AND condition must NOT fire for Symbolic+normal (this was the bug)"
@@ -495,7 +495,7 @@ fn test_search_semantic_pinned_longitudinal_truncation() {
     for day in 1..=30 {
         let mut r = make_record("alice", "pinned_fact", &format!("fact day {}", day));
         r.timestamp = base_time + Duration::days(day);
-        r.priority = "pinned".to_string();
+        r.priority = Priority::Pinned;
         store.add(r).unwrap();
     }
 
@@ -524,13 +524,13 @@ fn test_longitudinal_pinned_sorting_and_truncation_contract() {
     for day in 1..=50 {
         let mut r = make_record("bot", "learned", &format!("rule {}", day));
         r.timestamp = base_time + Duration::days(day);
-        r.priority = "pinned".to_string();
+        r.priority = Priority::Pinned;
         store.add(r).unwrap();
     }
 
     // Simulate handle_memory_live_beliefs filtering and truncation logic
     let all = store.all();
-    let mut filtered: Vec<_> = all.into_iter().filter(|r| r.priority == "pinned").collect();
+    let mut filtered: Vec<_> = all.into_iter().filter(|r| r.priority == Priority::Pinned).collect();
     filtered.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
     filtered.truncate(10);
 
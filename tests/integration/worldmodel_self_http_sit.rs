@@ -1,6 +1,7 @@
 //! HTTP SIT: worldmodel + self routes on run_with_state (web-server).
 //! cargo test --no-default-features --features "web-server,petgraph_backend" --test integration_suite worldmodel_self
 
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::archive_store::ArchiveStore;
 use hipcortex::aureus_bridge::AureusBridge;
 use hipcortex::coherence::CoherenceChecker;
@@ -23,7 +24,7 @@ fn make_state() -> AppState<InMemoryBackend> {
         required_memory_mb: 50.0,
         limitations: vec![],
     });
-    let memory_store = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let memory_store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let world_model = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let coherence = Arc::new(CoherenceChecker::new());
     let calibration = Arc::new(CalibrationTracker::new());
@@ -222,3 +223,90 @@ async fn worldmodel_observe_predict_rollout_and_self_surface() {
 
     srv.abort();
 }
+
+#[tokio::test]
+async fn worldmodel_causal_intervention_reports_identification() {
+    let state = make_state();
+    {
+        let wm = state.world_model.read().unwrap();
+        wm.add_causal_edge("Z".into(), "X".into()).unwrap();
+        wm.add_causal_edge("Z".into(), "Y".into()).unwrap();
+        wm.add_causal_edge("X".into(), "Y".into()).unwrap();
+        // A → B → C with A and C confounded by latent U: frontdoor only.
+        wm.add_causal_edge("U".into(), "A".into()).unwrap();
+        wm.add_causal_edge("U".into(), "C".into()).unwrap();
+        wm.add_causal_edge("A".into(), "B".into()).unwrap();
+        wm.add_causal_edge("B".into(), "C".into()).unwrap();
+        wm.set_causal_latent("U", true).unwrap();
+    }
+    let world_model = Arc::clone(&state.world_model);
+    let addr: std::net::SocketAddr = "127.0.0.1:3062".parse().unwrap();
+    let srv = tokio::spawn(async move {
+        hipcortex::web_server::run_with_state(addr, state).await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+    let client = reqwest::Client::new();
+    let url = "http://127.0.0.1:3062/worldmodel/causal/intervention";
+    let body = serde_json::json!({
+        "outcome": "Y",
+        "intervention_var": "X",
+        "intervention_value": 1.0
+    });
+
+    let res: serde_json::Value = client
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(res["success"], true, "intervention: {}", res);
+    assert_eq!(res["identification"]["status"], "identified");
+    assert_eq!(res["identification"]["strategy"]["type"], "backdoor");
+    let formula = res["adjustment_formula"].as_str().expect("formula");
+    assert!(formula.contains("P(Y | do(X))"), "formula: {}", formula);
+    assert!(formula.contains('Z'), "formula: {}", formula);
+
+    // Frontdoor is identified but not what the estimate uses, so only
+    // `identification` carries its formula.
+    let res: serde_json::Value = client
+        .post(url)
+        .json(&serde_json::json!({
+            "outcome": "C",
+            "intervention_var": "A",
+            "intervention_value": 1.0
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(res["success"], false, "intervention: {}", res);
+    assert_eq!(res["identification"]["strategy"]["type"], "frontdoor");
+    assert!(res["identification"]["formula"].is_string());
+    assert!(res["adjustment_formula"].is_null());
+
+    // Hiding the confounder leaves no backdoor set; the endpoint explains why.
+    world_model
+        .read()
+        .unwrap()
+        .set_causal_latent("Z", true)
+        .unwrap();
+    let res: serde_json::Value = client
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(res["success"], false, "intervention: {}", res);
+    assert_eq!(res["identification"]["status"], "not_identified");
+    assert!(res["adjustment_formula"].is_null());
+
+    srv.abort();
+}
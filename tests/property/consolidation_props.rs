@@ -9,6 +9,7 @@
 // Note: 90% reduction target (AC-4 full) requires Sub-spec 1 ExperienceStore.
 // Tests here verify the mining layer. See bottom for the full AC-4 TODO.
 
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::consolidation::mine_and_consolidate;
 use hipcortex::memory_record::{MemoryRecord, MemoryType};
 use hipcortex::memory_store::MemoryStore;
@@ -167,7 +168,7 @@ fn experience_store_90_percent_reduction() {
     use hipcortex::world_model_enhanced::WorldModelEnhanced;
     use std::sync::{Arc, Mutex, RwLock};
 
-    let ms = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let ms = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let wm = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let sm = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());
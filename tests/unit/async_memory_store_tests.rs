@@ -1,5 +1,7 @@
 #[cfg(feature = "async-store")]
-use hipcortex::async_memory_store::AsyncMemoryStore;
+use hipcortex::async_memory_store::{AsyncMemoryStore, RecordPatch};
+#[cfg(feature = "async-store")]
+use hipcortex::embedding_provider::AsyncEmbeddingProvider;
 #[cfg(feature = "async-store")]
 use hipcortex::memory_record::{MemoryRecord, MemoryType};
 #[cfg(feature = "async-store")]
@@ -11,7 +13,7 @@ async fn async_store_add() {
     let path = "async_store.jsonl";
     let _ = tokio::fs::remove_file(path).await;
     let backend = AsyncFileBackend::new(path, false).await.unwrap();
-    let mut store = AsyncMemoryStore::new(backend, std::path::Path::new("async_store.audit"), 2)
+    let store = AsyncMemoryStore::new(backend, std::path::Path::new("async_store.audit"), 2)
         .await
         .unwrap();
     let r = MemoryRecord::new(
@@ -23,7 +25,7 @@ async fn async_store_add() {
     );
     store.add(r).await.unwrap();
     store.flush().await.unwrap();
-    assert_eq!(store.all().len(), 1);
+    assert_eq!(store.snapshot().all().len(), 1);
     tokio::fs::remove_file(path).await.unwrap();
 }
 
@@ -34,7 +36,7 @@ async fn async_store_encrypted() {
     let _ = tokio::fs::remove_file(path).await;
     let key = [9u8; 32];
     let backend = AsyncFileBackend::new_encrypted(path, key).await.unwrap();
-    let mut store = AsyncMemoryStore::new(backend, std::path::Path::new("async_enc.audit"), 1)
+    let store = AsyncMemoryStore::new(backend, std::path::Path::new("async_enc.audit"), 1)
         .await
         .unwrap();
     store
@@ -53,7 +55,7 @@ async fn async_store_encrypted() {
     let store = AsyncMemoryStore::new(backend, std::path::Path::new("async_enc.audit"), 1)
         .await
         .unwrap();
-    assert_eq!(store.all().len(), 1);
+    assert_eq!(store.snapshot().all().len(), 1);
     tokio::fs::remove_file(path).await.unwrap();
     tokio::fs::remove_file("async_enc.audit").await.unwrap();
 }
@@ -65,7 +67,7 @@ async fn async_store_wal_recovery() {
     let _ = tokio::fs::remove_file(path).await;
     let _ = tokio::fs::remove_file("async_store_wal.wal").await;
     let backend = AsyncFileBackend::new(path, false).await.unwrap();
-    let mut store = AsyncMemoryStore::new(backend, std::path::Path::new("async_wal.audit"), 8)
+    let store = AsyncMemoryStore::new(backend, std::path::Path::new("async_wal.audit"), 8)
         .await
         .unwrap();
     store
@@ -84,8 +86,141 @@ async fn async_store_wal_recovery() {
     let store = AsyncMemoryStore::new(backend, std::path::Path::new("async_wal.audit"), 8)
         .await
         .unwrap();
-    assert_eq!(store.all().len(), 1);
+    assert_eq!(store.snapshot().all().len(), 1);
     let _ = tokio::fs::remove_file(path).await;
     let _ = tokio::fs::remove_file("async_store_wal.wal").await;
     let _ = tokio::fs::remove_file("async_wal.audit").await;
 }
+
+#[cfg(feature = "async-store")]
+fn rec(actor: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Symbolic,
+        actor.into(),
+        "said".into(),
+        target.into(),
+        serde_json::json!({}),
+    )
+}
+
+#[cfg(feature = "async-store")]
+#[tokio::test]
+async fn async_store_update_and_delete_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mem.jsonl");
+    let audit = dir.path().join("mem.audit");
+    let store = AsyncMemoryStore::new(AsyncFileBackend::new(&path, false).await.unwrap(), &audit, 8)
+        .await
+        .unwrap();
+    let keep = store.add(rec("a", "first draft")).await.unwrap();
+    let gone = store.add(rec("b", "scratch")).await.unwrap();
+    let before = store.get(keep).unwrap().version;
+    let updated = store
+        .update(
+            keep,
+            RecordPatch {
+                target: Some("final copy".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.version, before + 1);
+    assert!(store
+        .update(uuid::Uuid::new_v4(), RecordPatch::default())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(store.delete(gone).await.unwrap());
+    assert!(!store.delete(gone).await.unwrap());
+    assert_eq!(store.get(keep).unwrap().target, "final copy");
+    drop(store);
+
+    let store = AsyncMemoryStore::new(AsyncFileBackend::new(&path, false).await.unwrap(), &audit, 8)
+        .await
+        .unwrap();
+    let snapshot = store.snapshot();
+    assert_eq!(snapshot.all().len(), 1);
+    assert_eq!(snapshot.all()[0].target, "final copy");
+}
+
+#[cfg(feature = "async-store")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn async_store_concurrent_writers_and_readers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mem.jsonl");
+    let store = AsyncMemoryStore::new(
+        AsyncFileBackend::new(&path, false).await.unwrap(),
+        &dir.path().join("mem.audit"),
+        1,
+    )
+    .await
+    .unwrap();
+    let mut tasks = Vec::new();
+    for w in 0..8 {
+        let store = store.clone();
+        tasks.push(tokio::spawn(async move {
+            for i in 0..10 {
+                store.add(rec(&format!("w{w}"), &format!("note {i}"))).await.unwrap();
+                let hits = store.search("note", 5).await.unwrap();
+                assert!(!hits.is_empty());
+            }
+        }));
+    }
+    for t in tasks {
+        t.await.unwrap();
+    }
+    assert_eq!(store.snapshot().all().len(), 80);
+    drop(store);
+
+    let reopened = AsyncMemoryStore::new(
+        AsyncFileBackend::new(&path, false).await.unwrap(),
+        &dir.path().join("mem.audit"),
+        1,
+    )
+    .await
+    .unwrap();
+    assert_eq!(reopened.snapshot().all().len(), 80);
+}
+
+#[cfg(feature = "async-store")]
+struct LengthEmbedder;
+
+#[cfg(feature = "async-store")]
+#[async_trait::async_trait]
+impl AsyncEmbeddingProvider for LengthEmbedder {
+    async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        Ok(vec![text.len() as f32, 1.0])
+    }
+
+    fn name(&self) -> &str {
+        "length"
+    }
+}
+
+#[cfg(feature = "async-store")]
+#[tokio::test]
+async fn async_store_embeds_records_and_queries() {
+    use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+    use hipcortex::memory_store::MemoryStore;
+    use std::sync::Arc;
+
+    let shared = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    let store = AsyncMemoryStore::from(shared.clone()).with_embedder(Arc::new(LengthEmbedder));
+    let id = store.add(rec("a", "abc")).await.unwrap();
+    let stored = shared.snapshot().find_by_id(id).cloned().unwrap();
+    assert_eq!(stored.metadata["embedding"], serde_json::json!([3.0, 1.0]));
+
+    let ids = store
+        .transact(|ms| {
+            let r = rec("b", "xyz");
+            let id = r.id;
+            ms.add(r)?;
+            Ok(id)
+        })
+        .await
+        .unwrap();
+    assert!(shared.snapshot().find_by_id(ids).is_some());
+    assert!(!store.search("abc", 5).await.unwrap().is_empty());
+}
@@ -1,3 +1,4 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::cognitive_state::{CognitiveDelta, CognitiveError, CognitiveSnapshot};
 use hipcortex::memory_record::{MemoryRecord, MemoryType};
 use hipcortex::payloads::{BeliefPayload, EpistemicStatus, GoalStatus, SkillPayload};
@@ -124,7 +125,7 @@ use std::sync::{Arc, Mutex, RwLock};
 
 fn make_handle() -> CognitiveHandle<InMemoryBackend> {
     CognitiveHandle::new(
-        Arc::new(Mutex::new(MemoryStore::new_in_memory())),
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())),
         Arc::new(RwLock::new(WorldModelEnhanced::new())),
         Arc::new(SelfModel::new()),
         None,
@@ -475,7 +476,7 @@ fn test_consolidate_archives_sources_and_inserts_summary() {
     use std::sync::{Arc, Mutex, RwLock};
 
     let handle = CognitiveHandle::<InMemoryBackend>::new(
-        Arc::new(Mutex::new(MemoryStore::new_in_memory())),
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())),
         Arc::new(RwLock::new(WorldModelEnhanced::new())),
         Arc::new(SelfModel::new()),
         None,
@@ -530,7 +531,7 @@ fn test_forget_actor_deletes_all_records_and_returns_count() {
     use std::sync::{Arc, Mutex, RwLock};
 
     let handle = CognitiveHandle::<InMemoryBackend>::new(
-        Arc::new(Mutex::new(MemoryStore::new_in_memory())),
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())),
         Arc::new(RwLock::new(WorldModelEnhanced::new())),
         Arc::new(SelfModel::new()),
         None,
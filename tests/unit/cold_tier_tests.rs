@@ -0,0 +1,114 @@
+use hipcortex::cold_tier::ColdTier;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use serde_json::json;
+
+fn rec(target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        "actor".into(),
+        "did".into(),
+        target.into(),
+        json!({}),
+    )
+}
+
+#[test]
+fn demote_writes_compressed_segment() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cold = ColdTier::open(dir.path()).unwrap();
+    let recs: Vec<MemoryRecord> = (0..50).map(|i| rec(&format!("t{i}"))).collect();
+    assert_eq!(cold.demote(&recs).unwrap(), 50);
+    assert_eq!(cold.len(), 50);
+    assert_eq!(cold.segment_count(), 1);
+    let plain: usize = recs.iter().map(|r| serde_json::to_vec(r).unwrap().len() + 1).sum();
+    assert!((cold.bytes_on_disk() as usize) < plain, "segment should be compressed");
+    assert_eq!(cold.get(recs[7].id).unwrap().unwrap().target, "t7");
+}
+
+#[test]
+fn index_and_tombstones_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = rec("a");
+    let b = rec("b");
+    {
+        let mut cold = ColdTier::open(dir.path()).unwrap();
+        cold.demote(&[a.clone()]).unwrap();
+        cold.demote(&[b.clone()]).unwrap();
+        assert_eq!(cold.fault(a.id).unwrap().unwrap().target, "a");
+    }
+    let cold = ColdTier::open(dir.path()).unwrap();
+    assert_eq!(cold.segment_count(), 2);
+    assert!(!cold.contains(a.id));
+    assert!(cold.contains(b.id));
+    assert_eq!(cold.scan().unwrap().len(), 1);
+}
+
+#[test]
+fn compact_drops_faulted_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cold = ColdTier::open(dir.path()).unwrap();
+    let recs: Vec<MemoryRecord> = (0..4).map(|i| rec(&format!("t{i}"))).collect();
+    cold.demote(&recs[..2]).unwrap();
+    cold.demote(&recs[2..]).unwrap();
+    cold.fault(recs[0].id).unwrap();
+    assert_eq!(cold.compact().unwrap(), 3);
+    assert_eq!(cold.segment_count(), 1);
+    let reopened = ColdTier::open(dir.path()).unwrap();
+    assert_eq!(reopened.len(), 3);
+    assert!(!reopened.contains(recs[0].id));
+}
+
+#[test]
+fn refaulted_record_survives_reopen_after_second_demotion() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = rec("a");
+    {
+        let mut cold = ColdTier::open(dir.path()).unwrap();
+        cold.demote(&[a.clone()]).unwrap();
+        let hot = cold.fault(a.id).unwrap().unwrap();
+        cold.demote(&[hot]).unwrap();
+        assert!(cold.contains(a.id));
+    }
+    let cold = ColdTier::open(dir.path()).unwrap();
+    assert!(cold.contains(a.id));
+    assert_eq!(cold.get(a.id).unwrap().unwrap().target, "a");
+    assert_eq!(cold.scan().unwrap().len(), 1);
+}
+
+#[test]
+fn legacy_tombstone_cleared_by_demotion() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = rec("a");
+    {
+        let mut cold = ColdTier::open(dir.path()).unwrap();
+        cold.demote(&[a.clone()]).unwrap();
+    }
+    // Older tombstone files hold bare ids
+    std::fs::write(dir.path().join("faulted.ids"), format!("{}\n", a.id)).unwrap();
+    {
+        let mut cold = ColdTier::open(dir.path()).unwrap();
+        assert!(!cold.contains(a.id));
+        cold.demote(&[a.clone()]).unwrap();
+    }
+    let cold = ColdTier::open(dir.path()).unwrap();
+    assert!(cold.contains(a.id));
+}
+
+#[test]
+fn search_stubs_keep_only_ranking_metadata_and_track_the_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut a = rec("a");
+    a.metadata = json!({"embedding": [0.1, 0.2], "decay_factor": 0.5, "notes": "long text"});
+    let b = rec("b");
+    {
+        let mut cold = ColdTier::open(dir.path()).unwrap();
+        cold.demote(&[a.clone(), b.clone()]).unwrap();
+        cold.fault(b.id).unwrap();
+    }
+    let cold = ColdTier::open(dir.path()).unwrap();
+    let stubs: Vec<&MemoryRecord> = cold.search_stubs().collect();
+    assert_eq!(stubs.len(), 1, "faulted records have no stub");
+    assert_eq!(stubs[0].id, a.id);
+    assert_eq!(stubs[0].metadata, json!({"embedding": [0.1, 0.2], "decay_factor": 0.5}));
+    assert_eq!(cold.get(a.id).unwrap().unwrap().metadata["notes"], "long text");
+}
@@ -0,0 +1,190 @@
+use hipcortex::concurrent_memory_store::{
+    ConcurrentMemoryStore, MemoryQuery, MemoryStoreConfig, SortCriteria,
+};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority};
+use hipcortex::memory_store::MemoryStore;
+use serde_json::json;
+use std::sync::Arc;
+
+fn rec(actor: &str, action: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        action.into(),
+        target.into(),
+        json!({}),
+    )
+}
+
+#[test]
+fn readers_see_the_last_commit_while_a_writer_holds_the_store() {
+    let store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
+    store.add_memory(rec("alice", "wrote", "spec")).unwrap();
+    let before = store.snapshot();
+
+    let mut writer = store.lock().unwrap();
+    writer.add(rec("bob", "wrote", "tests")).unwrap();
+    // With `Mutex<MemoryStore>` this reader would wait for `writer`.
+    let reader = {
+        let store = Arc::clone(&store);
+        std::thread::spawn(move || store.snapshot().all().len())
+    };
+    assert_eq!(reader.join().unwrap(), 1);
+    drop(writer);
+
+    let after = store.snapshot();
+    assert_eq!(after.all().len(), 2);
+    assert_eq!(after.find_by_actor("bob")[0].target, "tests");
+    assert!(after.version() > before.version());
+    // Old snapshots stay valid and unchanged.
+    assert_eq!(before.all().len(), 1);
+
+    // A guard that only reads publishes nothing.
+    let version = after.version();
+    assert_eq!(store.lock().unwrap().all().len(), 2);
+    assert_eq!(store.snapshot().version(), version);
+}
+
+#[test]
+fn paginated_queries_filter_sort_and_page() {
+    let store = ConcurrentMemoryStore::new(MemoryStore::new_in_memory());
+    let now = chrono::Utc::now();
+    let records: Vec<MemoryRecord> = (0..15)
+        .map(|i| {
+            let mut r = rec("ops", &format!("step_{i}"), "deploy");
+            r.timestamp = now - chrono::Duration::minutes(i);
+            r
+        })
+        .chain(std::iter::once(rec("dev", "step_0", "deploy")))
+        .collect();
+    let batch = store.add_memories_batch(records);
+    assert_eq!(batch.successful.len(), 16);
+    assert!(batch.failed.is_empty());
+
+    let query = MemoryQuery {
+        actor: Some("ops".into()),
+        ..Default::default()
+    };
+    let page1 = store.query_memories_paginated(&query, 0, 10);
+    assert_eq!(
+        (page1.records.len(), page1.total_count, page1.total_pages),
+        (10, 15, 2)
+    );
+    let page2 = store.query_memories_paginated(&query, 1, 10);
+    assert_eq!(page2.records.len(), 5);
+    assert!(store
+        .query_memories_paginated(&query, 9, 10)
+        .records
+        .is_empty());
+
+    // Sorting applies before paging: the oldest record lands on the last page.
+    let newest_first = MemoryQuery {
+        sort_by: Some(SortCriteria::Timestamp),
+        ..query.clone()
+    };
+    let last = store.query_memories_paginated(&newest_first, 1, 10);
+    assert_eq!(last.records.last().unwrap().action, "step_14");
+
+    let window = MemoryQuery {
+        from_timestamp: Some(now - chrono::Duration::minutes(4)),
+        to_timestamp: Some(now - chrono::Duration::minutes(2)),
+        offset: Some(1),
+        limit: Some(1),
+        ..query
+    };
+    let hit = store.query_memories_paginated(&window, 0, 10);
+    assert_eq!(hit.total_count, 1);
+    assert_eq!(hit.records[0].action, "step_3");
+
+    let by_target = MemoryQuery {
+        target: Some("deploy".into()),
+        action: Some("step_0".into()),
+        ..Default::default()
+    };
+    assert_eq!(store.snapshot().matching(&by_target).len(), 2);
+}
+
+#[test]
+fn query_cache_is_scoped_to_a_snapshot_version() {
+    let store = ConcurrentMemoryStore::new(MemoryStore::new_in_memory());
+    store.add_memory(rec("alice", "noted", "one")).unwrap();
+    let query = MemoryQuery {
+        actor: Some("alice".into()),
+        ..Default::default()
+    };
+    assert_eq!(store.query_memories_paginated(&query, 0, 10).total_count, 1);
+    assert_eq!(store.query_memories_paginated(&query, 0, 10).total_count, 1);
+    assert_eq!(store.get_stats().cache_hit_rate, 0.5);
+
+    store.add_memory(rec("alice", "noted", "two")).unwrap();
+    assert_eq!(store.query_memories_paginated(&query, 0, 10).total_count, 2);
+}
+
+#[test]
+fn pruning_counts_queued_reads_and_spares_pinned_records() {
+    let config = MemoryStoreConfig {
+        min_access_threshold: 1,
+        prune_threshold_days: 30,
+        ..Default::default()
+    };
+    let store = ConcurrentMemoryStore::with_config(MemoryStore::new_in_memory(), config);
+    let old = chrono::Utc::now() - chrono::Duration::days(60);
+    let mut records = Vec::new();
+    for (actor, priority) in [
+        ("read", Priority::Normal),
+        ("unread", Priority::Normal),
+        ("pinned", Priority::Pinned),
+    ] {
+        let mut r = rec(actor, "noted", "fact");
+        r.timestamp = old;
+        r.priority = priority;
+        records.push(r);
+    }
+    store.add_memories_batch(records);
+
+    let read = MemoryQuery {
+        actor: Some("read".into()),
+        ..Default::default()
+    };
+    store.query_memories_paginated(&read, 0, 10);
+    store.query_memories_paginated(&read, 0, 10);
+    // The reads are applied by the next writer, not by the reader.
+    assert_eq!(store.snapshot().find_by_actor("read")[0].access_count, 0);
+
+    assert_eq!(store.prune_memories(), 1);
+    let snapshot = store.snapshot();
+    assert!(snapshot.find_by_actor("unread").is_empty());
+    assert_eq!(snapshot.find_by_actor("read")[0].access_count, 2);
+    assert_eq!(snapshot.find_by_actor("pinned").len(), 1);
+
+    let stats = store.get_stats();
+    assert_eq!(stats.total_records, 2);
+    assert_eq!(stats.records_by_type[&MemoryType::Temporal], 2);
+}
+
+#[test]
+fn similarity_and_removal_work_over_a_file_backend() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let probe = rec("alice", "deployed", "api v2");
+    let (near, far) = (
+        rec("alice", "deployed", "api v1"),
+        rec("carol", "baked", "bread"),
+    );
+    let far_id = far.id;
+    {
+        let store = ConcurrentMemoryStore::new(MemoryStore::new(&path).unwrap());
+        store.add_memories_batch(vec![probe.clone(), near.clone(), far]);
+
+        let similar = store.find_similar_memories(&probe, 0.3, 5);
+        assert_eq!(similar[0].0.id, near.id);
+        assert!(similar.iter().all(|(r, _)| r.id != probe.id));
+
+        assert!(store.remove_memory(far_id));
+        assert!(!store.remove_memory(far_id));
+        store.lock().unwrap().compact_backend().unwrap();
+    }
+    let reopened = MemoryStore::new(&path).unwrap();
+    assert_eq!(reopened.all().len(), 2);
+    assert!(reopened.find_by_id(far_id).is_none());
+}
@@ -1,4 +1,9 @@
-use hipcortex::conversation_memory::ConversationMemory;
+use hipcortex::conversation_memory::{
+    extract_statements, ConversationMemory, ConversationThreads, StatementKind, ThreadConfig,
+    SUMMARY_ACTION,
+};
+use hipcortex::memory_record::MemoryType;
+use hipcortex::memory_store::MemoryStore;
 
 #[test]
 fn add_and_len() {
@@ -16,3 +21,129 @@ fn ingest_openmanus_json() {
     assert_eq!(cm.len(), 1);
     assert_eq!(cm.messages()[0].sender, "assistant");
 }
+
+#[test]
+fn add_message_threads_turns() {
+    let mut cm = ConversationMemory::new();
+    let first = cm.add_message("user", "hi");
+    cm.add_message("assistant", "hello");
+    assert_eq!(cm.messages()[1].turn, 1);
+    assert_eq!(cm.messages()[1].reply_to, Some(first));
+}
+
+#[test]
+fn threads_are_stored_per_session_and_survive_a_new_handle() {
+    let mut store = MemoryStore::new_in_memory();
+    let threads = ConversationThreads::default();
+    let a0 = threads.append(&mut store, "a", "user", "hello", None).unwrap();
+    threads.append(&mut store, "b", "user", "other session", None).unwrap();
+    let a1 = threads.append(&mut store, "a", "assistant", "hi there", None).unwrap();
+    assert_eq!(a1.message.turn, 1);
+    assert_eq!(a1.message.reply_to, Some(a0.message.id));
+
+    // A fresh handle reads the same thread back from the store.
+    let turns = ConversationThreads::default().turns(&store, "a");
+    let texts: Vec<&str> = turns.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, ["hello", "hi there"]);
+    assert_eq!(turns[1].sender, "assistant");
+    assert_eq!(threads.sessions(&store), ["a", "b"]);
+}
+
+#[test]
+fn reply_to_must_belong_to_the_session() {
+    let mut store = MemoryStore::new_in_memory();
+    let threads = ConversationThreads::default();
+    let other = threads.append(&mut store, "b", "user", "x", None).unwrap();
+    let a0 = threads.append(&mut store, "a", "user", "q1", None).unwrap();
+    threads.append(&mut store, "a", "user", "q2", None).unwrap();
+    assert!(threads
+        .append(&mut store, "a", "assistant", "?", Some(other.message.id))
+        .is_err());
+    let answer = threads
+        .append(&mut store, "a", "assistant", "answer to q1", Some(a0.message.id))
+        .unwrap();
+    assert_eq!(answer.message.reply_to, Some(a0.message.id));
+}
+
+#[test]
+fn page_returns_a_window_of_turns() {
+    let mut store = MemoryStore::new_in_memory();
+    let threads = ConversationThreads::default();
+    for i in 0..5 {
+        threads.append(&mut store, "s", "user", &format!("turn {i}"), None).unwrap();
+    }
+    let page = threads.page(&store, "s", 1, 2);
+    assert_eq!(page.total, 5);
+    let texts: Vec<&str> = page.turns.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, ["turn 1", "turn 2"]);
+    assert!(page.summary.is_none());
+}
+
+#[test]
+fn rolling_summary_folds_older_turns_when_over_budget() {
+    let mut store = MemoryStore::new_in_memory();
+    let threads = ConversationThreads::new(ThreadConfig {
+        summary_budget_chars: 60,
+        keep_recent: 2,
+        extract_beliefs: false,
+    });
+    let mut summaries = Vec::new();
+    for i in 0..6 {
+        let text = format!("message number {i} about the deployment");
+        let out = threads.append(&mut store, "s", "user", &text, None).unwrap();
+        summaries.extend(out.summary);
+    }
+    assert!(!summaries.is_empty(), "budget exceeded at least once");
+    let all = threads.summaries(&store, "s");
+    assert_eq!(all.len(), summaries.len());
+    // Consecutive summaries cover adjacent, non-overlapping turn ranges.
+    for pair in all.windows(2) {
+        assert_eq!(
+            pair[1].metadata["from_turn"].as_u64().unwrap(),
+            pair[0].metadata["to_turn"].as_u64().unwrap() + 1
+        );
+    }
+    let last = all.last().unwrap();
+    assert_eq!(last.action, SUMMARY_ACTION);
+    assert!(!last.evidence.is_empty());
+    assert!(last.metadata["to_turn"].as_u64().unwrap() <= 3, "keep_recent turns stay out");
+    // Turns are kept after summarization.
+    assert_eq!(threads.turns(&store, "s").len(), 6);
+
+    // An explicit summarize with `all` folds the rest.
+    threads.summarize(&mut store, "s", true).unwrap().unwrap();
+    assert_eq!(threads.latest_summary(&store, "s").unwrap().metadata["to_turn"], 5);
+    assert!(threads.summarize(&mut store, "s", true).unwrap().is_none());
+}
+
+#[test]
+fn user_turns_yield_beliefs_with_evidence() {
+    let mut store = MemoryStore::new_in_memory();
+    let threads = ConversationThreads::default();
+    let out = threads
+        .append(&mut store, "s", "user", "My name is Ada and I prefer tea over coffee.", None)
+        .unwrap();
+    assert_eq!(out.beliefs.len(), 2);
+    for id in &out.beliefs {
+        let b = store.find_by_id(*id).unwrap();
+        assert_eq!(b.record_type, MemoryType::Belief);
+        assert_eq!(b.evidence, vec![out.message.id]);
+        assert_eq!(b.metadata["session_id"], "s");
+    }
+    let assistant = threads
+        .append(&mut store, "s", "assistant", "I prefer to ask first.", None)
+        .unwrap();
+    assert!(assistant.beliefs.is_empty(), "only user turns are mined");
+}
+
+#[test]
+fn extract_statements_splits_clauses() {
+    let got = extract_statements("I live in Paris, and I hate rain! Remember that the demo is Friday");
+    assert_eq!(got.len(), 3);
+    assert_eq!(got[0].kind, StatementKind::Fact);
+    assert_eq!(got[0].proposition, "user lives in Paris");
+    assert_eq!(got[1].kind, StatementKind::Preference);
+    assert_eq!(got[1].proposition, "user dislikes rain");
+    assert_eq!(got[2].proposition, "the demo is Friday");
+    assert!(extract_statements("what's the weather like?").is_empty());
+}
@@ -1,3 +1,4 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::continuous_dynamics::{ContinuousDynamics, KalmanVectorField};
 use hipcortex::digital_twin::{DigitalTwin, SyncPolicy};
 use hipcortex::cognitive_state::CognitiveHandle;
@@ -10,7 +11,7 @@ use hipcortex::persistence::InMemoryBackend;
 use std::sync::{Arc, Mutex, RwLock};
 
 fn make_handle() -> CognitiveHandle<InMemoryBackend> {
-    let store = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let wm = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let sm = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());
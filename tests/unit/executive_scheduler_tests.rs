@@ -0,0 +1,335 @@
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::executive_scheduler::{
+    ExecutiveScheduler, FailureAction, RetryPolicy, SkillExecutor, TickOutcome, ToolExecutor,
+    ToolFn,
+};
+use hipcortex::llm_clients::LLMClient;
+use hipcortex::memory_record::{MemoryType, Priority};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::payloads::{GoalPayload, GoalStatus};
+use hipcortex::procedural_cache::ProceduralCache;
+use hipcortex::self_model::{ModuleHealth, SelfModel};
+use hipcortex::skill_compiler::{SkillTemplate, SkillTemplateStep};
+use hipcortex::task_graph::{Plan, TaskGraph, TaskNode, TaskState};
+use hipcortex::InMemoryBackend;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+fn gates() -> (SelfModel, CoherenceChecker) {
+    (SelfModel::new(), CoherenceChecker::new())
+}
+
+fn chain(steps: &[&str]) -> TaskGraph {
+    TaskGraph::from_steps(steps.iter().map(|s| TaskNode::new(s, 1.0)).collect())
+}
+
+fn states(graph: &TaskGraph) -> Vec<TaskState> {
+    graph
+        .graph
+        .node_weights()
+        .map(|n| n.state.clone())
+        .collect()
+}
+
+/// Tool that fails its first `failures` calls.
+fn flaky(failures: u32, calls: Arc<AtomicU32>) -> ToolFn {
+    Arc::new(move |_, _| {
+        if calls.fetch_add(1, Ordering::SeqCst) < failures {
+            Err("transient".into())
+        } else {
+            Ok(())
+        }
+    })
+}
+
+struct Scripted(&'static str);
+
+impl LLMClient for Scripted {
+    fn generate_response(&self, _prompt: &str) -> String {
+        self.0.to_string()
+    }
+}
+
+#[test]
+fn steps_dispatch_to_skills_and_tools() {
+    let cache = Arc::new(Mutex::new(ProceduralCache::new()));
+    let skills = SkillExecutor::new(cache.clone());
+    skills.register(SkillTemplate {
+        id: Uuid::new_v4(),
+        name: "deploy".into(),
+        steps: ["build", "ship"]
+            .iter()
+            .map(|s| SkillTemplateStep {
+                state_name: s.to_string(),
+                action_pattern: format!("{s}_done"),
+                extracted_args: vec![],
+            })
+            .collect(),
+        success_rate: 1.0,
+        observation_count: 3,
+    });
+    let calls = Arc::new(AtomicU32::new(0));
+    let tools = ToolExecutor::new()
+        .with_tool("notify", flaky(0, calls.clone()))
+        .with_llm_tool("summarise", Arc::new(Scripted("DONE")));
+
+    let mut scheduler = ExecutiveScheduler::new(4);
+    scheduler.register_executor(Arc::new(skills));
+    scheduler.register_executor(Arc::new(tools));
+    let graph = TaskGraph::from_steps(vec![
+        TaskNode::new("deploy service", 1.0).with_effect("deployed", "true"),
+        TaskNode::new("notify team", 1.0),
+        TaskNode::new("summarise release", 1.0),
+    ]);
+    let goal = Uuid::new_v4();
+    scheduler.adopt_plan(graph, goal).unwrap();
+
+    let (sm, cc) = gates();
+    assert!(matches!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::StepCompleted { .. }
+    ));
+    assert_eq!(scheduler.goal_stack[0].local_beliefs["deployed"], "true");
+    scheduler.tick(&sm, &cc).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(goal)
+    );
+    assert_eq!(scheduler.tick(&sm, &cc).unwrap(), TickOutcome::Idle);
+}
+
+#[test]
+fn failed_steps_retry_then_follow_the_policy() {
+    let (sm, cc) = gates();
+
+    // Succeeds on the third and last allowed attempt.
+    let calls = Arc::new(AtomicU32::new(0));
+    let mut scheduler = ExecutiveScheduler::new(4);
+    scheduler.register_executor(Arc::new(
+        ToolExecutor::new().with_tool("fetch", flaky(2, calls.clone())),
+    ));
+    scheduler
+        .adopt_plan(chain(&["fetch page"]), Uuid::new_v4())
+        .unwrap();
+    for attempt in 1..=2 {
+        match scheduler.tick(&sm, &cc).unwrap() {
+            TickOutcome::StepRetrying { attempt: a, .. } => assert_eq!(a, attempt),
+            other => panic!("expected a retry, got {other:?}"),
+        }
+    }
+    assert!(matches!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(_)
+    ));
+
+    // Exhausted retries abort the goal and the steps after it.
+    let mut scheduler = ExecutiveScheduler::new(4).with_retry_policy(RetryPolicy {
+        max_attempts: 2,
+        on_exhausted: FailureAction::AbortGoal,
+    });
+    scheduler.register_executor(Arc::new(
+        ToolExecutor::new()
+            .with_tool("fetch", flaky(u32::MAX, Arc::new(AtomicU32::new(0))))
+            .with_tool("parse", flaky(0, Arc::new(AtomicU32::new(0)))),
+    ));
+    let goal = Uuid::new_v4();
+    scheduler
+        .adopt_plan(chain(&["fetch page", "parse page"]), goal)
+        .unwrap();
+    scheduler.tick(&sm, &cc).unwrap();
+    assert_eq!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalAborted {
+            goal,
+            reason: "transient".into()
+        }
+    );
+    assert!(scheduler.goal_stack.is_empty());
+    assert_eq!(
+        states(scheduler.active_graph.as_ref().unwrap()),
+        [TaskState::Aborted, TaskState::Aborted]
+    );
+
+    // SkipStep moves on; an LLM refusal and an unroutable step are fatal.
+    let mut scheduler = ExecutiveScheduler::new(4).with_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        on_exhausted: FailureAction::SkipStep,
+    });
+    scheduler.register_executor(Arc::new(
+        ToolExecutor::new().with_llm_tool("ask", Arc::new(Scripted("FAILED: no access"))),
+    ));
+    scheduler
+        .adopt_plan(chain(&["ask oracle", "ask again", "dance"]), Uuid::new_v4())
+        .unwrap();
+    for _ in 0..2 {
+        assert!(matches!(
+            scheduler.tick(&sm, &cc).unwrap(),
+            TickOutcome::StepRetrying { .. }
+        ));
+    }
+    match scheduler.tick(&sm, &cc).unwrap() {
+        TickOutcome::StepSkipped { reason, .. } => assert_eq!(reason, "no access"),
+        other => panic!("expected a skip, got {other:?}"),
+    }
+    for _ in 0..3 {
+        scheduler.tick(&sm, &cc).unwrap();
+    }
+    // `dance` has no executor: skipped without retries, ending the goal.
+    assert!(matches!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(_)
+    ));
+    assert!(states(scheduler.active_graph.as_ref().unwrap())
+        .iter()
+        .all(|s| *s == TaskState::Aborted));
+}
+
+#[test]
+fn urgent_goals_preempt_and_hand_back() {
+    let (sm, cc) = gates();
+    let mut scheduler = ExecutiveScheduler::new(4);
+    let (routine, urgent, peer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    assert!(scheduler
+        .submit(chain(&["r1", "r2"]), routine, Priority::Normal)
+        .unwrap());
+    scheduler.tick(&sm, &cc).unwrap();
+
+    assert!(scheduler
+        .submit(chain(&["u1"]), urgent, Priority::High)
+        .unwrap());
+    // Same priority as the running goal: queued underneath it.
+    assert!(!scheduler
+        .submit(chain(&["p1"]), peer, Priority::High)
+        .unwrap());
+    let order: Vec<Uuid> = scheduler.goal_stack.iter().map(|f| f.task_id).collect();
+    assert_eq!(order, [routine, peer, urgent]);
+
+    assert_eq!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(urgent)
+    );
+    assert_eq!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(peer)
+    );
+    // The routine goal resumes at its second step.
+    assert_eq!(scheduler.current_step().unwrap().summary, "r2");
+    assert_eq!(
+        scheduler.tick(&sm, &cc).unwrap(),
+        TickOutcome::GoalCompleted(routine)
+    );
+
+    let mut full = ExecutiveScheduler::new(1);
+    full.submit(chain(&["a"]), Uuid::new_v4(), Priority::Low)
+        .unwrap();
+    assert!(full
+        .submit(chain(&["b"]), Uuid::new_v4(), Priority::Pinned)
+        .is_err());
+}
+
+#[test]
+fn diagnostic_frames_clear_once_healthy() {
+    let mut scheduler = ExecutiveScheduler::new(4);
+    let goal = Uuid::new_v4();
+    scheduler.adopt_plan(chain(&["only"]), goal).unwrap();
+    let cc = CoherenceChecker::new();
+    let sick = SelfModel::new();
+    sick.report_health(
+        "planner".into(),
+        ModuleHealth {
+            latency_ms: 5_000.0,
+            error_rate: 1.0,
+            resource_usage: 1.0,
+        },
+    )
+    .unwrap();
+    assert!(scheduler.tick(&sick, &cc).is_err());
+    assert_eq!(scheduler.goal_stack.len(), 2);
+
+    assert_eq!(
+        scheduler.tick(&SelfModel::new(), &cc).unwrap(),
+        TickOutcome::GoalCompleted(goal)
+    );
+    assert!(scheduler.goal_stack.is_empty());
+}
+
+#[test]
+fn checkpoints_resume_after_restart() {
+    let (sm, cc) = gates();
+    let mut store = MemoryStore::new_in_memory();
+    let plan = Plan {
+        steps: ["pack", "load", "drive"]
+            .iter()
+            .map(|s| TaskNode::new(s, 1.0))
+            .collect(),
+        cost: 3.0,
+        expanded: 0,
+    };
+    let goal = HashMap::from([("delivered".to_string(), "true".to_string())]);
+    let saved = plan.persist(&mut store, "planner", &goal).unwrap();
+    let graph = TaskGraph::load_plan(&store, saved.goal_id).unwrap();
+    let side = Uuid::new_v4();
+
+    let executive_id = {
+        let mut scheduler = ExecutiveScheduler::new(4);
+        scheduler.adopt_plan(graph, saved.goal_id).unwrap();
+        scheduler.register_executor(Arc::new(
+            ToolExecutor::new()
+                .with_tool("pack", flaky(0, Default::default()))
+                .with_tool("load", flaky(1, Default::default())),
+        ));
+        scheduler.run_tick(&sm, &cc, &mut store).unwrap();
+        assert!(matches!(
+            scheduler.run_tick(&sm, &cc, &mut store).unwrap(),
+            TickOutcome::StepRetrying { .. }
+        ));
+        scheduler
+            .submit(chain(&["x"]), side, Priority::Low)
+            .unwrap();
+        scheduler.checkpoint(&mut store).unwrap();
+        scheduler.executive_id
+    };
+
+    let status = |store: &MemoryStore<InMemoryBackend>| {
+        let rec = store.find_by_id(saved.goal_id).unwrap();
+        serde_json::from_value::<GoalPayload>(rec.metadata.clone())
+            .unwrap()
+            .status
+    };
+    assert_eq!(status(&store), GoalStatus::InProgress);
+
+    // "Restart": rebuild from the store and re-register executors.
+    let mut scheduler = ExecutiveScheduler::resume(&store, executive_id, 4).unwrap();
+    let order: Vec<Uuid> = scheduler.goal_stack.iter().map(|f| f.task_id).collect();
+    assert_eq!(order, [side, saved.goal_id]);
+    assert_eq!(scheduler.current_step().unwrap().summary, "load");
+    scheduler.register_executor(Arc::new(
+        ToolExecutor::new()
+            .with_tool("load", flaky(u32::MAX, Default::default()))
+            .with_tool("drive", flaky(0, Default::default()))
+            .with_tool("x", flaky(0, Default::default())),
+    ));
+    // One failure was carried over, so two more exhaust the default three.
+    scheduler.run_tick(&sm, &cc, &mut store).unwrap();
+    assert!(matches!(
+        scheduler.run_tick(&sm, &cc, &mut store).unwrap(),
+        TickOutcome::GoalAborted { .. }
+    ));
+    assert_eq!(status(&store), GoalStatus::Failed);
+    assert_eq!(scheduler.current_step().unwrap().summary, "x");
+
+    // Each tick replaced the previous checkpoint instead of adding one.
+    let checkpoints: Vec<_> = store
+        .all_by_type(MemoryType::Reflexion)
+        .into_iter()
+        .filter(|r| r.action == "checkpoint_continuation")
+        .collect();
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(checkpoints[0].metadata["sequence_number"], 5);
+
+    let fresh = ExecutiveScheduler::resume(&store, Uuid::new_v4(), 4).unwrap();
+    assert!(fresh.goal_stack.is_empty());
+}
@@ -1,5 +1,5 @@
 use hipcortex::experience_store::ExperienceStore;
-use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority};
 use hipcortex::memory_store::MemoryStore;
 use hipcortex::persistence::InMemoryBackend;
 use serde_json::json;
@@ -91,3 +91,152 @@ fn search_compressed_finds_matching_targets() {
     assert_eq!(results.len(), 1);
     assert!(results[0].target.contains("login"));
 }
+
+// ─── Hot/cold tiering ────────────────────────────────────────────────────────
+
+use hipcortex::experience_store::{enforce_tiering, over_raw_cap, tier_sizes, TieringPolicy};
+
+fn tiered_store(dir: &std::path::Path) -> MemoryStore<InMemoryBackend> {
+    MemoryStore::new_in_memory()
+        .with_cold_tier(dir.join("cold"))
+        .unwrap()
+}
+
+fn raw(actor: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.to_string(),
+        "observed".to_string(),
+        target.to_string(),
+        json!({}),
+    )
+}
+
+#[test]
+fn tiering_demotes_oldest_raw_records_to_cold() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    for i in 0..10 {
+        store.add(raw("actor", &format!("event-{i}"))).unwrap();
+    }
+    let policy = TieringPolicy { raw_cap: 8, raw_target: 4, consolidate_min_frequency: 0 };
+    let report = enforce_tiering(&mut store, None, &policy).unwrap();
+    assert_eq!(report.actors_over_pressure, 1);
+    assert_eq!(report.records_demoted, 6);
+    assert_eq!(store.all().len(), 4);
+    assert!(store.all().iter().all(|r| r.target != "event-0"));
+
+    let sizes = tier_sizes(&store);
+    assert_eq!(sizes.raw, 4);
+    assert_eq!(sizes.cold, 6);
+    assert_eq!(sizes.cold_segments, 1);
+    assert!(sizes.cold_bytes > 0);
+}
+
+#[test]
+fn tiering_keeps_episode_and_pinned_records_hot() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    let mut pinned = raw("actor", "pinned-fact");
+    pinned.priority = Priority::Pinned;
+    let pinned_id = pinned.id;
+    store.add(pinned).unwrap();
+    let mut belief = MemoryRecord::new(MemoryType::Belief, "actor".into(), "assert".into(), "b".into(), json!({}));
+    belief.evidence = vec![Uuid::new_v4()];
+    let belief_id = belief.id;
+    store.add(belief).unwrap();
+    for i in 0..5 {
+        store.add(raw("actor", &format!("e{i}"))).unwrap();
+    }
+    let policy = TieringPolicy { raw_cap: 3, raw_target: 0, consolidate_min_frequency: 0 };
+    enforce_tiering(&mut store, None, &policy).unwrap();
+    assert!(store.find_by_id(pinned_id).is_some());
+    assert!(store.find_by_id(belief_id).is_some());
+    assert_eq!(store.cold_tier().unwrap().len(), 5);
+}
+
+#[test]
+fn over_raw_cap_checks_only_the_given_actor() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    for i in 0..3 {
+        store.add(raw("busy", &format!("e{i}"))).unwrap();
+    }
+    store.add(raw("quiet", "once")).unwrap();
+    let policy = TieringPolicy { raw_cap: 3, raw_target: 1, consolidate_min_frequency: 0 };
+    assert!(over_raw_cap(&store, "busy", &policy));
+    assert!(!over_raw_cap(&store, "quiet", &policy));
+    assert!(!over_raw_cap(&MemoryStore::new_in_memory(), "busy", &policy), "no cold tier");
+}
+
+#[test]
+fn tiering_below_cap_is_noop() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    store.add(raw("actor", "only")).unwrap();
+    let report = enforce_tiering(&mut store, None, &TieringPolicy::default()).unwrap();
+    assert_eq!(report.records_demoted, 0);
+    assert_eq!(store.all().len(), 1);
+}
+
+#[test]
+fn cold_records_are_read_in_place_and_fault_in_on_tiered_find() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    let mut a = raw("actor", "quantum tunnelling notes");
+    a.metadata = json!({"source_doc": "lab-book"});
+    let b = raw("actor", "grocery list");
+    let (a_id, b_id) = (a.id, b.id);
+    store.add(a).unwrap();
+    store.add(b).unwrap();
+    assert_eq!(store.demote_to_cold(&[a_id, b_id]).unwrap(), 2);
+    assert!(store.all().is_empty());
+
+    // find_by_id reads the cold record without faulting it
+    assert_eq!(store.find_by_id(a_id).unwrap().target, "quantum tunnelling notes");
+    assert!(store.cold_tier().unwrap().contains(a_id));
+
+    // find_by_id_tiered faults the record back hot
+    assert_eq!(store.find_by_id_tiered(b_id).unwrap().target, "grocery list");
+    assert!(store.all().iter().any(|r| r.id == b_id));
+    assert!(!store.cold_tier().unwrap().contains(b_id));
+
+    // search scores cold records in place and returns them whole
+    let hits = store.search_tiered(None, "quantum", 5, false);
+    assert_eq!(hits[0].0.id, a_id);
+    assert_eq!(hits[0].0.metadata["source_doc"], "lab-book");
+    assert!(store.search_semantic(None, "quantum", 5, false).is_empty());
+    assert!(store.cold_tier().unwrap().contains(a_id));
+    assert_eq!(store.all().len(), 1);
+}
+
+#[test]
+fn snapshots_read_the_cold_tier() {
+    use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+
+    let dir = tempfile::tempdir().unwrap();
+    let shared = ConcurrentMemoryStore::new(tiered_store(dir.path()));
+    let r = raw("actor", "quantum tunnelling notes");
+    let id = r.id;
+    {
+        let mut ms = shared.lock().unwrap();
+        ms.add(r).unwrap();
+        ms.demote_to_cold(&[id]).unwrap();
+    }
+    let snap = shared.snapshot();
+    assert_eq!(snap.find_by_id(id).unwrap().target, "quantum tunnelling notes");
+    assert_eq!(snap.search_tiered(None, "quantum", 5, false)[0].0.id, id);
+    assert!(shared.lock().unwrap().cold_tier().unwrap().contains(id));
+}
+
+#[test]
+fn update_record_faults_cold_record_in() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = tiered_store(dir.path());
+    let r = raw("actor", "old");
+    let id = r.id;
+    store.add(r).unwrap();
+    store.demote_to_cold(&[id]).unwrap();
+    store.update_record(id, Some("new"), None, None, None, None).unwrap();
+    assert_eq!(store.find_by_id(id).unwrap().target, "new");
+}
@@ -0,0 +1,79 @@
+use hipcortex::htn::{HtnDomain, Method};
+use hipcortex::task_graph::{Planner, TaskNode};
+use std::collections::HashMap;
+
+fn beliefs(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn travel_domain() -> HtnDomain {
+    HtnDomain::new(vec![
+        TaskNode::new("call taxi", 1.0).with_effect("taxi", "here"),
+        TaskNode::new("ride taxi", 20.0)
+            .with_precondition("taxi", "here")
+            .with_precondition("cash", "yes")
+            .with_effect("at", "park"),
+        TaskNode::new("walk", 5.0)
+            .with_precondition("weather", "dry")
+            .with_effect("at", "park"),
+        TaskNode::new("withdraw cash", 2.0).with_effect("cash", "yes"),
+    ])
+    // Walking is preferred, but only when dry.
+    .with_method(
+        Method::new("by foot", "go to park", &["walk"]).with_precondition("weather", "dry"),
+    )
+    .with_method(Method::new(
+        "by taxi",
+        "go to park",
+        &["get cash", "call taxi", "ride taxi"],
+    ))
+    .with_method(Method::new("have cash", "get cash", &[]).with_precondition("cash", "yes"))
+    .with_method(Method::new("atm", "get cash", &["withdraw cash"]))
+}
+
+#[test]
+fn decomposes_with_method_order_and_backtracking() {
+    let domain = travel_domain();
+    let plan = domain
+        .decompose(&beliefs(&[("weather", "dry")]), &["go to park"])
+        .unwrap();
+    assert_eq!(plan.steps.len(), 1);
+    assert_eq!(plan.steps[0].summary, "walk");
+
+    let plan = domain
+        .decompose(&beliefs(&[("weather", "rain")]), &["go to park"])
+        .unwrap();
+    let names: Vec<&str> = plan.steps.iter().map(|s| s.summary.as_str()).collect();
+    assert_eq!(names, ["withdraw cash", "call taxi", "ride taxi"]);
+    assert_eq!(plan.cost, 23.0);
+
+    // The decomposition replays cleanly under the A* planner's validator.
+    let validator = Planner::new(vec![]);
+    assert!(validator
+        .validate(
+            &beliefs(&[("weather", "rain")]),
+            &beliefs(&[("at", "park")]),
+            &plan.steps
+        )
+        .is_ok());
+}
+
+#[test]
+fn unknown_tasks_and_runaway_recursion_are_errors() {
+    let domain = travel_domain();
+    assert!(domain
+        .decompose(&HashMap::new(), &["fly"])
+        .unwrap_err()
+        .contains("unknown task `fly`"));
+
+    let mut looping = HtnDomain::new(vec![]);
+    looping.add_method(Method::new("again", "spin", &["spin"]));
+    looping.max_depth = 10;
+    assert!(looping
+        .decompose(&HashMap::new(), &["spin"])
+        .unwrap_err()
+        .contains("exceeds depth 10"));
+}
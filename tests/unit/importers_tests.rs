@@ -0,0 +1,212 @@
+use hipcortex::conversation_memory::{ConversationThreads, TURN_ACTION};
+use hipcortex::importers::{ImportFormat, ImportOptions, Importer};
+use hipcortex::memory_record::MemoryType;
+use hipcortex::memory_store::MemoryStore;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+fn importer(format: Option<ImportFormat>) -> Importer {
+    Importer::new(ImportOptions {
+        format,
+        ..Default::default()
+    })
+}
+
+const OPENAI_CHAT: &str = r#"{"id": "conv-1", "messages": [
+    {"role": "system", "content": "be brief"},
+    {"role": "user", "content": [{"type": "text", "text": "what is 2+2?"}, {"type": "image_url", "image_url": {"url": "x"}}]},
+    {"role": "assistant", "content": "4"}
+]}"#;
+
+#[test]
+fn detects_formats_from_extension_and_shape() {
+    let detect = |name: &str, content: &str| ImportFormat::detect(Some(Path::new(name)), content);
+    assert_eq!(detect("a.csv", ""), Some(ImportFormat::Csv));
+    assert_eq!(detect("a.md", ""), Some(ImportFormat::Markdown));
+    assert_eq!(detect("a.json", OPENAI_CHAT), Some(ImportFormat::Openai));
+    assert_eq!(
+        detect("a.json", r#"[{"uuid": "c", "chat_messages": []}]"#),
+        Some(ImportFormat::Anthropic)
+    );
+    assert_eq!(
+        detect("a.json", r#"{"id": "r", "run_type": "chain"}"#),
+        Some(ImportFormat::Langchain)
+    );
+    assert_eq!(detect("a.json", r#"{"resourceSpans": []}"#), Some(ImportFormat::Otel));
+    assert_eq!(
+        detect("a.jsonl", "{\"actor\": \"a\", \"target\": \"t\"}\n{\"actor\": \"b\", \"target\": \"u\"}\n"),
+        Some(ImportFormat::Trace)
+    );
+    assert_eq!(detect("a.txt", "plain text"), None);
+    assert_eq!(ImportFormat::parse("LangGraph").unwrap(), ImportFormat::Langchain);
+    assert!(ImportFormat::parse("xml").is_err());
+}
+
+#[test]
+fn chat_transcripts_become_conversation_threads() {
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, OPENAI_CHAT, "chat.json").unwrap();
+    assert_eq!(report.format, ImportFormat::Openai);
+    assert_eq!(report.imported, 3);
+
+    let turns = ConversationThreads::default().turns(&store, "conv-1");
+    let texts: Vec<&str> = turns.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, ["be brief", "what is 2+2?", "4"]);
+    assert_eq!(turns[2].reply_to, Some(turns[1].id));
+    let rec = store.find_by_id(turns[0].id).unwrap();
+    assert_eq!(rec.source.as_deref(), Some("import:openai:chat.json"));
+}
+
+#[test]
+fn claude_export_maps_human_to_user() {
+    let export = r#"[{"uuid": "c-9", "name": "Trip", "chat_messages": [
+        {"sender": "human", "text": "Plan a trip", "created_at": "2026-01-02T03:04:05Z"},
+        {"sender": "assistant", "content": [{"type": "text", "text": "Sure"}]}
+    ]}]"#;
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, export, "claude.json").unwrap();
+    assert_eq!((report.format, report.imported), (ImportFormat::Anthropic, 2));
+    let turns = ConversationThreads::default().turns(&store, "c-9");
+    assert_eq!(turns[0].sender, "user");
+    assert_eq!(turns[0].timestamp.to_rfc3339(), "2026-01-02T03:04:05+00:00");
+    assert_eq!(turns[1].text, "Sure");
+}
+
+#[test]
+fn reimport_is_deduplicated() {
+    let mut store = MemoryStore::new_in_memory();
+    importer(None).import_str(&mut store, OPENAI_CHAT, "chat.json").unwrap();
+    let again = importer(None).import_str(&mut store, OPENAI_CHAT, "chat.json").unwrap();
+    assert_eq!((again.imported, again.duplicates), (0, 3));
+    assert_eq!(store.all().len(), 3);
+
+    // The same text in another session is not a duplicate.
+    let other = OPENAI_CHAT.replace("conv-1", "conv-2");
+    let report = importer(None).import_str(&mut store, &other, "chat.json").unwrap();
+    assert_eq!(report.imported, 3);
+}
+
+#[test]
+fn langchain_runs_are_flattened_with_parent_links() {
+    let runs = r#"{"id": "root", "name": "agent", "run_type": "chain",
+        "start_time": "2026-03-01T10:00:00.000001",
+        "inputs": {"input": "weather?"}, "outputs": {"output": "sunny"},
+        "child_runs": [
+            {"id": "c1", "name": "search", "run_type": "tool", "outputs": {"output": "22C"},
+             "extra": {"metadata": {"langgraph_node": "tools"}}},
+            {"id": "c2", "name": "noop", "run_type": "chain", "outputs": {}}
+        ]}"#;
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, runs, "run.json").unwrap();
+    assert_eq!((report.imported, report.skipped), (2, 1));
+    let root = store.all().iter().find(|r| r.action == "chain").unwrap();
+    let tool = store.all().iter().find(|r| r.action == "tool").unwrap();
+    assert_eq!(root.target, "sunny");
+    assert_eq!(tool.actor, "tools", "langgraph node wins over run name");
+    assert_eq!(tool.derived_from, Some(root.id));
+    assert_eq!(tool.metadata["run_id"], "c1");
+}
+
+#[test]
+fn otel_genai_spans_are_imported_and_others_skipped() {
+    let otlp = r#"{"resourceSpans": [{"resource": {"attributes": [{"key": "service.name", "value": {"stringValue": "bot"}}]},
+      "scopeSpans": [{"spans": [
+        {"traceId": "t1", "spanId": "s1", "name": "chat gpt-4o", "startTimeUnixNano": "1700000000000000000",
+         "attributes": [
+            {"key": "gen_ai.operation.name", "value": {"stringValue": "chat"}},
+            {"key": "gen_ai.request.model", "value": {"stringValue": "gpt-4o"}},
+            {"key": "gen_ai.usage.input_tokens", "value": {"intValue": "12"}}],
+         "events": [{"name": "gen_ai.choice", "attributes": [{"key": "message", "value": {"stringValue": "hello there"}}]}]},
+        {"traceId": "t1", "spanId": "s2", "parentSpanId": "s1", "name": "GET /db", "attributes": []}
+      ]}]}]}"#;
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, otlp, "spans.json").unwrap();
+    assert_eq!((report.format, report.imported, report.skipped), (ImportFormat::Otel, 1, 1));
+    let rec = &store.all()[0];
+    assert_eq!((rec.actor.as_str(), rec.action.as_str()), ("bot", "chat"));
+    assert_eq!(rec.target, "hello there");
+    assert_eq!(rec.metadata["model"], "gpt-4o");
+    assert_eq!(rec.metadata["input_tokens"], 12);
+    assert_eq!(rec.timestamp.timestamp(), 1_700_000_000);
+}
+
+#[test]
+fn csv_rows_map_columns_and_report_bad_rows() {
+    let csv = "actor,action,text,type,tags,priority_note\n\
+               alice,said,\"hello, world\",symbolic,a;b,urgent\n\
+               bob,said,,temporal,,\n\
+               carol,said,hi,nonsense,,\n";
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, csv, "notes.csv").unwrap();
+    assert_eq!(report.imported, 1);
+    assert_eq!(report.errors.len(), 2);
+    assert_eq!(report.errors[0].location, "notes.csv:line 3");
+    let rec = &store.all()[0];
+    assert_eq!(rec.target, "hello, world");
+    assert_eq!(rec.record_type, MemoryType::Symbolic);
+    assert_eq!(rec.tags, ["a", "b"]);
+    assert_eq!(rec.metadata["priority_note"], "urgent");
+
+    let no_target = importer(Some(ImportFormat::Csv)).import_str(&mut store, "a,b\n1,2\n", "x.csv");
+    assert!(no_target.is_err());
+}
+
+#[test]
+fn markdown_folder_is_walked_with_front_matter_and_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(
+        dir.path().join("a.md"),
+        "---\ntitle: Groceries\ntags: [home, todo]\n---\nBuy milk #errand\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("sub/b.md"), "# Ideas\n\nWrite more tests.\n").unwrap();
+    std::fs::write(dir.path().join("empty.md"), "   \n").unwrap();
+    std::fs::write(dir.path().join("ignored.txt"), "not a note").unwrap();
+
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_path(&mut store, dir.path()).unwrap();
+    assert_eq!((report.imported, report.skipped), (2, 1));
+    let a = store.all().iter().find(|r| r.metadata["path"] == "a.md").unwrap();
+    assert_eq!(a.metadata["title"], "Groceries");
+    assert_eq!(a.tags, ["note", "home", "todo", "errand"]);
+    let b = store.all().iter().find(|r| r.metadata["path"] == "sub/b.md").unwrap();
+    assert_eq!(b.metadata["title"], "Ideas");
+    assert_eq!(b.record_type, MemoryType::Symbolic);
+}
+
+#[test]
+fn trace_items_without_target_are_errors_not_silent_skips() {
+    let trace = r#"[{"actor": "a", "action": "ran", "target": "tests"}, {"actor": "b"}]"#;
+    let mut store = MemoryStore::new_in_memory();
+    let report = importer(None).import_str(&mut store, trace, "trace.json").unwrap();
+    assert_eq!(report.imported, 1);
+    assert_eq!(report.errors[0].location, "trace.json[1]");
+}
+
+#[test]
+fn dry_run_writes_nothing_and_progress_is_reported() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let mut imp = Importer::new(ImportOptions {
+        dry_run: true,
+        ..Default::default()
+    })
+    .with_progress(move |p| sink.lock().unwrap().push((p.done, p.total)));
+    let mut store = MemoryStore::new_in_memory();
+    let report = imp.import_str(&mut store, OPENAI_CHAT, "chat.json").unwrap();
+    assert_eq!(report.imported, 3);
+    assert!(report.record_ids.is_empty());
+    assert!(store.all().is_empty());
+    assert_eq!(*seen.lock().unwrap(), [(1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn imported_turns_page_like_live_ones() {
+    let mut store = MemoryStore::new_in_memory();
+    importer(None).import_str(&mut store, OPENAI_CHAT, "chat.json").unwrap();
+    let threads = ConversationThreads::default();
+    let next = threads.append(&mut store, "conv-1", "user", "thanks", None).unwrap();
+    assert_eq!(next.message.turn, 3);
+    assert!(store.all().iter().all(|r| r.action == TURN_ACTION));
+}
@@ -1,5 +1,5 @@
 use hipcortex::memory_diff::{compute_diff, StateDiff};
-use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, RecordStatus};
 
 #[test]
 fn test_compute_diff_detects_confidence_change() {
@@ -15,7 +15,7 @@ fn test_compute_diff_detects_confidence_change() {
     let mut r2 = r1.clone();
     r2.confidence = 0.4;
     r2.version = 1;
-    r2.status = "quarantine".to_string();
+    r2.status = RecordStatus::Quarantine;
 
     let diff = compute_diff(&r1, &r2);
     assert_eq!(diff.record_id, r1.id);
@@ -28,8 +28,8 @@ fn test_compute_diff_detects_confidence_change() {
     );
     assert!(diff.status_change.is_some(), "status change not detected");
     let (from_s, to_s) = diff.status_change.unwrap();
-    assert_eq!(from_s, "active");
-    assert_eq!(to_s, "quarantine");
+    assert_eq!(from_s, RecordStatus::Active);
+    assert_eq!(to_s, RecordStatus::Quarantine);
 }
 
 #[test]
@@ -1,4 +1,4 @@
-use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_record::{MemoryRecord, MemoryType, RecordStatus};
 use hipcortex::memory_store::MemoryStore;
 use std::fs;
 
@@ -20,14 +20,14 @@ fn test_archived_records_excluded_from_default_search() {
         "t_old".into(),
         serde_json::json!({}),
     );
-    archived.status = "archived".to_string();
+    archived.status = RecordStatus::Archived;
 
     store.add(active).unwrap();
     store.add(archived).unwrap();
 
     let results = store.search_semantic(None, "t", 10, false);
     assert!(
-        !results.iter().any(|(r, _)| r.status == "archived"),
+        !results.iter().any(|(r, _)| r.status == RecordStatus::Archived),
         "archived records must not appear in default search"
     );
 }
@@ -236,6 +236,34 @@ fn test_rocksdb_backend() {
     std::fs::remove_file("rocks_test.audit.log").unwrap();
 }
 
+#[test]
+fn test_query_scans_when_backend_cannot_filter() {
+    use hipcortex::persistence::RecordFilter;
+    let mut store = MemoryStore::new_in_memory();
+    for (actor, target) in [("a", "x"), ("b", "x"), ("a", "y")] {
+        store
+            .add(MemoryRecord::new(
+                MemoryType::Temporal,
+                actor.into(),
+                "act".into(),
+                target.into(),
+                serde_json::json!({}),
+            ))
+            .unwrap();
+    }
+    let hits = store.query(&RecordFilter::new().actor("a")).unwrap();
+    let targets: Vec<&str> = hits.iter().map(|r| r.target.as_str()).collect();
+    assert_eq!(targets, ["x", "y"]);
+    let first = store
+        .query(&RecordFilter::new().target("x").limit(1))
+        .unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].actor, "a");
+
+    store.namespace = Some("team".into());
+    assert!(store.query(&RecordFilter::new()).unwrap().is_empty());
+}
+
 #[test]
 fn test_provenance_fields_do_not_change_hash_of_legacy_record() {
     let record = MemoryRecord::new(
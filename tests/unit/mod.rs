@@ -10,24 +10,32 @@ mod aureus_bridge_tests;
 mod belief_payload_tests;
 mod calibration_tests;
 mod cognitive_gc_tests;
+mod cold_tier_tests;
 mod jtms_tests;
 // #[cfg(feature = "web-server")]
 // mod api_tests;
 mod coherence_tests;
+mod concurrent_memory_store_tests;
 mod consolidation_tests;
 mod conversation_memory_tests;
 mod edge_workflow_small;
 mod effort_tests;
 mod enhancement_advisor_tests;
 mod execution_gate_tests;
+mod executive_scheduler_tests;
 mod graph_connectivity_tests;
+mod htn_tests;
 mod hypothesis_manager_tests;
+mod importers_tests;
+mod injection_defense_tests;
 mod integration_layer_tests;
 mod knowledge_export_tests;
 mod latent_map_tests;
 mod llama_client_tests;
+mod maintenance_tests;
 #[cfg(all(feature = "web-server", feature = "grpc-server"))]
 mod mcp_server_tests;
+mod mcp_tests;
 mod memory_diff_tests;
 mod memory_expiry_consistency_tests;
 mod memory_graph_tests;
@@ -37,27 +45,39 @@ mod memory_store_eviction_tests;
 mod memory_store_tests;
 mod memory_tests;
 mod multimodal_perception_tests;
+mod paged_store_tests;
+mod pddl_tests;
 mod perception_adapter_tests;
+mod pitr_tests;
 #[cfg(feature = "plugin")]
 mod plugin_host_tests;
 mod procedural_cache_map_tests;
 mod procedural_cache_tests;
 mod puzzle_tests;
 mod rag_adapter_tests;
+mod redaction_vault_tests;
 mod reasoning_trace_store_tests;
+mod record_set_tests;
 mod retrieval_pipeline_tests;
 mod safety_guardrail_tests;
 mod segmented_ring_buffer_tests;
 mod semantic_cache_tests;
 mod sled_graph_tests;
 mod snapshot_manager_tests;
+#[cfg(feature = "sqlite_backend")]
+mod sqlite_backend_tests;
+mod state_bundle_tests;
 mod state_diff_tests;
+mod store_format_tests;
+mod summarizer_tests;
 mod symbolic_store_tests;
 mod temporal_fsm_backend_tests;
 mod temporal_indexer_feature_tests;
 mod simulation_fork_tests;
 mod workspace_tests;
 mod temporal_indexer_tests;
+mod task_graph_tests;
+mod telemetry_tests;
 mod tx_log_tests;
 mod vision_encoder_tests;
 mod world_model_export_tests;
@@ -18,6 +18,7 @@ fn multimodal_text_and_image() {
         text: Some("smart glasses".into()),
         embedding: None,
         image_data: Some(bytes),
+        audio: None,
         tags: vec!["multimodal".into()],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
@@ -32,6 +33,7 @@ fn humanoid_robotics_embedding_trace() {
         text: None,
         embedding: Some(embed.clone()),
         image_data: None,
+        audio: None,
         tags: vec!["robot".into()],
     };
     let out = PerceptionAdapter::adapt(input.clone()).unwrap();
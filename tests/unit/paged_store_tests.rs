@@ -0,0 +1,225 @@
+use hipcortex::cognitive_gc::CognitiveGC;
+use hipcortex::cognitive_state::{CognitiveDelta, CognitiveHandle};
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority, RecordStatus};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::{FileBackend, RecordFilter};
+use hipcortex::self_model::{calibration::CalibrationTracker, SelfModel};
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use serde_json::json;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+fn rec(actor: &str, action: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        action.into(),
+        target.into(),
+        json!({}),
+    )
+}
+
+fn paged(dir: &Path, hot: usize) -> MemoryStore<FileBackend> {
+    MemoryStore::new(dir.join("memory.jsonl"))
+        .unwrap()
+        .with_paging(dir.join("pages"), hot)
+        .unwrap()
+}
+
+fn targets(records: &[MemoryRecord]) -> Vec<&str> {
+    records.iter().map(|r| r.target.as_str()).collect()
+}
+
+#[test]
+fn resident_set_is_bounded_and_lookups_read_pages_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = paged(dir.path(), 4);
+    let mut ids = Vec::new();
+    for i in 0..50 {
+        let r = rec(&format!("actor-{}", i % 5), "noted", &format!("fact {i}"));
+        ids.push(r.id);
+        store.add(r).unwrap();
+    }
+    assert!(store.all().len() <= 4);
+    assert_eq!(store.all().len() + store.page_tier().unwrap().len(), 50);
+    assert_eq!(store.record_count(), 50);
+
+    assert_eq!(store.find_by_id(ids[0]).unwrap().target, "fact 0");
+    assert!(!store.all().contains(ids[0]), "reads do not fault records in");
+    let actor: Vec<&str> = store
+        .find_by_actor("actor-2")
+        .iter()
+        .map(|r| r.target.as_str())
+        .collect();
+    assert_eq!(
+        actor,
+        [
+            "fact 2", "fact 7", "fact 12", "fact 17", "fact 22", "fact 27", "fact 32", "fact 37",
+            "fact 42", "fact 47"
+        ]
+    );
+    assert_eq!(store.search_semantic(None, "fact", 3, false).len(), 3);
+
+    // The backend holds only the resident records.
+    drop(store);
+    let plain = MemoryStore::new(dir.path().join("memory.jsonl")).unwrap();
+    assert!(plain.all().len() <= 4);
+}
+
+#[test]
+fn writes_fault_paged_records_in_and_keep_indices_consistent() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = paged(dir.path(), 1);
+    let mut a = rec("alice", "wrote", "spec");
+    a.tags = vec!["design".into()];
+    let b = rec("bob", "wrote", "tests");
+    let c = rec("alice", "reviewed", "tests");
+    let (a_id, b_id, c_id) = (a.id, b.id, c.id);
+    for r in [a, b, c] {
+        store.add(r).unwrap();
+    }
+    assert!(store.page_tier().unwrap().contains(a_id));
+
+    assert!(store.delete_by_id(b_id));
+    assert!(!store.delete_by_id(b_id));
+    assert_eq!(store.find_by_action("wrote").len(), 1);
+    assert_eq!(store.find_by_target("tests")[0].id, c_id);
+
+    store
+        .update_record(a_id, Some("spec v2"), Some("rewrote"), None, None, None)
+        .unwrap();
+    assert!(store.all().contains(a_id));
+    assert!(store.find_by_target("spec").is_empty());
+    assert!(store.find_by_action("wrote").is_empty());
+    let updated = store.find_by_tags(&["design"]);
+    assert_eq!(updated[0].target, "spec v2");
+    assert_eq!(updated[0].version, 1);
+
+    store.set_status(c_id, RecordStatus::Archived).unwrap();
+    assert_eq!(store.record_count(), 1);
+    assert_eq!(store.all_by_type(MemoryType::Temporal).len(), 1);
+    let (_, after, quarantined) = store.contradict(a_id).unwrap();
+    assert!(after < 1.0 && !quarantined);
+
+    assert_eq!(store.delete_by_actor("alice").unwrap().len(), 2);
+    assert!(store.find_by_actors(&["alice", "bob"]).is_empty());
+    assert!(store.page_tier().unwrap().is_empty());
+}
+
+#[test]
+fn reopen_keeps_paged_records_and_their_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let (keep, gone) = (rec("carol", "noted", "keep"), rec("carol", "noted", "gone"));
+    let gone_id = gone.id;
+    {
+        let mut store = paged(dir.path(), 1);
+        store.add(keep).unwrap();
+        store.add(gone).unwrap();
+        store.delete_durable(gone_id).unwrap();
+        store.add(rec("dave", "noted", "later")).unwrap();
+    }
+    let mut store = paged(dir.path(), 8);
+    assert_eq!(store.all().len(), 1);
+    let carol: Vec<&str> = store
+        .find_by_actor("carol")
+        .iter()
+        .map(|r| r.target.as_str())
+        .collect();
+    assert_eq!(carol, ["keep"]);
+    store.add(rec("erin", "noted", "newest")).unwrap();
+    let all = store.query(&RecordFilter::new()).unwrap();
+    assert_eq!(targets(&all), ["keep", "later", "newest"]);
+}
+
+#[test]
+fn query_search_and_latest_cover_paged_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = paged(dir.path(), 2);
+    let mut old = rec("ops", "deployed", "api v1");
+    old.timestamp -= chrono::Duration::hours(2);
+    let new = rec("ops", "deployed", "api v2");
+    let mut pinned = rec("ops", "rule", "never deploy on friday");
+    pinned.priority = Priority::Pinned;
+    let mut expired = rec("ops", "deployed", "api v0");
+    expired.expires_at = Some(chrono::Utc::now().timestamp() - 10);
+    let mut hidden = rec("ops", "deployed", "api canary");
+    hidden.status = RecordStatus::Quarantine;
+    hidden.timestamp -= chrono::Duration::hours(3);
+    for r in [old, new, pinned, expired, hidden] {
+        store.add(r).unwrap();
+    }
+    assert_eq!(store.page_tier().unwrap().len(), 3);
+
+    let hits = store.search_semantic(None, "api deployed", 10, false);
+    let found: Vec<&str> = hits.iter().map(|(r, _)| r.target.as_str()).collect();
+    assert_eq!(found[0], "never deploy on friday");
+    assert!(found.contains(&"api v2") && found.contains(&"api v1"));
+    assert!(!found.contains(&"api v0") && !found.contains(&"api canary"));
+    assert_eq!(
+        store.search_semantic(None, "api deployed", 10, true).len(),
+        hits.len() + 1
+    );
+
+    let latest = store.find_latest(Some("ops"), Some("deployed"), 5);
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].target, "api v2");
+
+    let hour_ago = chrono::Utc::now() - chrono::Duration::hours(1);
+    let recent = store
+        .query(
+            &RecordFilter::new()
+                .action("deployed")
+                .status(RecordStatus::Active)
+                .since(hour_ago),
+        )
+        .unwrap();
+    assert_eq!(targets(&recent), ["api v2", "api v0"]);
+    assert_eq!(store.query(&RecordFilter::new().limit(2)).unwrap().len(), 2);
+
+    assert_eq!(store.purge_expired(), 1);
+    assert_eq!(store.query(&RecordFilter::new()).unwrap().len(), 4);
+}
+
+#[test]
+fn cognitive_handle_and_snapshots_run_over_a_paged_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let memory = Arc::new(ConcurrentMemoryStore::new(paged(dir.path(), 2)));
+    let handle = CognitiveHandle::new(
+        memory.clone(),
+        Arc::new(RwLock::new(WorldModelEnhanced::new())),
+        Arc::new(SelfModel::new()),
+        None,
+        Arc::new(CoherenceChecker::new()),
+        Arc::new(CalibrationTracker::new()),
+        Arc::new(CognitiveGC::new()),
+    );
+    let first = rec("frank", "logged", "entry 0");
+    let first_id = first.id;
+    handle
+        .transact(CognitiveDelta::AddMemory(first), "test")
+        .unwrap();
+    for i in 1..10 {
+        let r = rec("frank", "logged", &format!("entry {i}"));
+        handle.transact(CognitiveDelta::AddMemory(r), "test").unwrap();
+    }
+
+    let snap = memory.snapshot();
+    assert!(!snap.all().contains(first_id));
+    assert_eq!(snap.find_by_id(first_id).unwrap().target, "entry 0");
+    assert_eq!(snap.find_by_actor("frank").len(), 10);
+    let hits = snap.search_tiered(None, "entry", 20, false);
+    assert_eq!(hits.len(), 10);
+
+    let result = handle
+        .transact_ex(
+            CognitiveDelta::ForgetActor {
+                actor: "frank".into(),
+            },
+            "test",
+        )
+        .unwrap();
+    assert_eq!(result.records_deleted, Some(10));
+    assert!(memory.snapshot().find_by_actor("frank").is_empty());
+}
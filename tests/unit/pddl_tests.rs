@@ -0,0 +1,109 @@
+use hipcortex::pddl::{compile, parse_domain, parse_problem};
+use hipcortex::task_graph::Planner;
+
+const LOGISTICS: &str = r#"
+; One truck, typed locations, per-road costs.
+(define (domain delivery)
+  (:requirements :strips :typing :negative-preconditions :action-costs)
+  (:types truck package - vehicle-or-cargo place)
+  (:predicates (at ?x - vehicle-or-cargo ?p - place) (in ?pk - package ?t - truck)
+               (road ?a ?b - place) (broken ?t - truck))
+  (:functions (distance ?a ?b - place) (total-cost))
+  (:action drive
+    :parameters (?t - truck ?from ?to - place)
+    :precondition (and (at ?t ?from) (road ?from ?to) (not (broken ?t)))
+    :effect (and (not (at ?t ?from)) (at ?t ?to)
+                 (increase (total-cost) (distance ?from ?to))))
+  (:action load
+    :parameters (?pk - package ?t - truck ?p - place)
+    :precondition (and (at ?pk ?p) (at ?t ?p))
+    :effect (and (not (at ?pk ?p)) (in ?pk ?t) (increase (total-cost) 1)))
+  (:action unload
+    :parameters (?pk - package ?t - truck ?p - place)
+    :precondition (and (in ?pk ?t) (at ?t ?p))
+    :effect (and (not (in ?pk ?t)) (at ?pk ?p) (increase (total-cost) 1))))
+"#;
+
+const SHIP: &str = r#"
+(define (problem ship-box)
+  (:domain delivery)
+  (:objects van - truck box - package depot shop mall - place)
+  (:init (at van depot) (at box depot)
+         (road depot shop) (road shop depot) (road depot mall) (road mall shop)
+         (= (distance depot shop) 10) (= (distance shop depot) 10)
+         (= (distance depot mall) 2) (= (distance mall shop) 3)
+         (= (total-cost) 0))
+  (:goal (and (at box shop) (not (in box van))))
+  (:metric minimize (total-cost)))
+"#;
+
+#[test]
+fn parses_typed_domain_and_problem() {
+    let domain = parse_domain(LOGISTICS).unwrap();
+    assert_eq!(domain.name, "delivery");
+    assert_eq!(domain.actions.len(), 3);
+    assert_eq!(domain.types["truck"], "vehicle-or-cargo");
+    assert_eq!(
+        domain.actions[0].parameters[1],
+        ("?from".into(), "place".into())
+    );
+    assert_eq!(domain.actions[0].precondition.len(), 3);
+    assert!(!domain.actions[0].precondition[2].positive);
+
+    let problem = parse_problem(SHIP).unwrap();
+    assert_eq!(problem.objects.len(), 5);
+    assert_eq!(problem.init.len(), 6);
+    assert_eq!(problem.fluents.len(), 5);
+    assert_eq!(problem.goal.len(), 2);
+}
+
+#[test]
+fn grounds_with_static_pruning_and_plans_cheapest_route() {
+    let ground = compile(LOGISTICS, SHIP).unwrap();
+    // `road` is static: only the four existing roads ground a drive.
+    let drives = ground
+        .actions
+        .iter()
+        .filter(|a| a.summary.starts_with("(drive"))
+        .count();
+    assert_eq!(drives, 4);
+    assert!(ground
+        .actions
+        .iter()
+        .all(|a| !a.preconditions.keys().any(|k| k.starts_with("(road"))));
+    assert_eq!(ground.initial["(at van depot)"], "true");
+    assert_eq!(ground.initial["(at box shop)"], "false");
+    assert_eq!(ground.goal["(in box van)"], "false");
+
+    let plan = Planner::new(ground.actions.clone())
+        .search(&ground.initial, &ground.goal)
+        .unwrap();
+    let names: Vec<&str> = plan.steps.iter().map(|s| s.summary.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "(load box van depot)",
+            "(drive van depot mall)",
+            "(drive van mall shop)",
+            "(unload box van shop)"
+        ]
+    );
+    assert_eq!(plan.cost, 7.0);
+}
+
+#[test]
+fn rejects_unsupported_or_mismatched_input() {
+    let err = parse_domain(
+        "(define (domain d) (:action a :parameters () :precondition (or (p) (q)) :effect (r)))",
+    )
+    .unwrap_err();
+    assert!(err.contains("`or`"));
+    assert!(parse_domain("(define (domain d)")
+        .unwrap_err()
+        .contains("unbalanced"));
+
+    let other = SHIP.replace("(:domain delivery)", "(:domain other)");
+    assert!(compile(LOGISTICS, &other)
+        .unwrap_err()
+        .contains("domain `other`"));
+}
@@ -1,4 +1,12 @@
-use hipcortex::perception_adapter::{AdapterError, Modality, PerceptInput, PerceptionAdapter};
+use hipcortex::embedding_provider::EmbeddingProvider;
+use hipcortex::memory_record::MemoryType;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::perception_adapter::{
+    AdapterError, AudioClip, AudioEncoder, ImageEncoder, LogMelEncoder, Modality, PerceptInput,
+    PerceptionAdapter, PerceptionConfig,
+};
+use image::{DynamicImage, ImageOutputFormat, RgbImage};
+use std::sync::Arc;
 
 #[test]
 fn adapt_text_input() {
@@ -7,6 +15,7 @@ fn adapt_text_input() {
         text: Some("test text".to_string()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["tag1".to_string()],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
@@ -20,6 +29,7 @@ fn adapt_embedding_input() {
         text: None,
         embedding: Some(vec![0.1, 0.2, 0.3]),
         image_data: None,
+        audio: None,
         tags: vec![],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
@@ -33,6 +43,7 @@ fn adapt_invalid_input() {
         text: None,
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec![],
     };
     let err = PerceptionAdapter::adapt(input).unwrap_err();
@@ -46,6 +57,7 @@ fn adapt_image_input() {
         text: None,
         embedding: None,
         image_data: Some(vec![1, 2, 3]),
+        audio: None,
         tags: vec!["img".to_string()],
     };
     let err = PerceptionAdapter::adapt(input).unwrap_err();
@@ -59,8 +71,199 @@ fn adapt_symbolic_concept() {
         text: None,
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["concept".to_string()],
     };
     let out = PerceptionAdapter::adapt(input).unwrap();
-    assert!(out.is_empty());
+    assert_eq!(out.len(), 4);
+}
+
+fn percept(modality: Modality) -> PerceptInput {
+    PerceptInput {
+        modality,
+        text: None,
+        embedding: None,
+        image_data: None,
+        audio: None,
+        tags: vec![],
+    }
+}
+
+fn text(t: &str) -> PerceptInput {
+    PerceptInput {
+        text: Some(t.into()),
+        ..percept(Modality::Text)
+    }
+}
+
+fn png(rgb: [u8; 3]) -> PerceptInput {
+    let mut buf = std::io::Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(RgbImage::from_pixel(8, 8, image::Rgb(rgb)))
+        .write_to(&mut buf, ImageOutputFormat::Png)
+        .unwrap();
+    PerceptInput {
+        image_data: Some(buf.into_inner()),
+        ..percept(Modality::Image)
+    }
+}
+
+fn tone(hz: f32) -> AudioClip {
+    let sample_rate = 16_000;
+    AudioClip {
+        samples: (0..sample_rate / 4)
+            .map(|i| (2.0 * std::f32::consts::PI * hz * i as f32 / sample_rate as f32).sin())
+            .collect(),
+        sample_rate: sample_rate as u32,
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[test]
+fn output_dimension_is_configured_per_modality() {
+    let adapter = PerceptionAdapter::new(
+        PerceptionConfig::default()
+            .with_dim(Modality::Text, 32)
+            .with_dim(Modality::Audio, 16),
+    );
+    assert_eq!(
+        adapter.perceive(&text("hello")).unwrap().embedding.len(),
+        32
+    );
+    let audio = PerceptInput {
+        audio: Some(tone(440.0)),
+        ..percept(Modality::Audio)
+    };
+    assert_eq!(adapter.perceive(&audio).unwrap().embedding.len(), 16);
+    // No entry and no default: the encoder's native dimension.
+    let image = adapter.perceive(&png([200, 10, 10])).unwrap();
+    assert_eq!(
+        (image.embedding.len(), image.space.as_str()),
+        (48, "grid-rgb")
+    );
+    let norm: f32 = image.embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    assert!((norm - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn log_mel_features_separate_pitches() {
+    let enc = LogMelEncoder::default();
+    let a = enc.encode(&tone(440.0)).unwrap();
+    assert_eq!(a.len(), 80);
+    assert_eq!(a, enc.encode(&tone(440.0)).unwrap());
+    let norm = |v: Vec<f32>| {
+        let n = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        v.into_iter().map(|x| x / n).collect::<Vec<_>>()
+    };
+    let (a, near, far) = (
+        norm(a),
+        norm(enc.encode(&tone(450.0)).unwrap()),
+        norm(enc.encode(&tone(3000.0)).unwrap()),
+    );
+    assert!(cosine(&a, &near) > cosine(&a, &far));
+    assert!(enc
+        .encode(&AudioClip {
+            samples: vec![],
+            sample_rate: 16_000
+        })
+        .is_err());
+}
+
+#[test]
+fn percepts_are_stored_as_perception_records_and_searchable() {
+    let adapter = PerceptionAdapter::new(PerceptionConfig::uniform(64));
+    let mut store = MemoryStore::new_in_memory();
+    let (id, stored) = adapter
+        .store(&mut store, "camera", &text("red ball on the table"))
+        .unwrap();
+    adapter
+        .store(&mut store, "camera", &text("quarterly budget spreadsheet"))
+        .unwrap();
+    adapter
+        .store(&mut store, "camera", &png([255, 0, 0]))
+        .unwrap();
+
+    let rec = store.find_by_id(id).unwrap();
+    assert_eq!(rec.record_type, MemoryType::Perception);
+    assert_eq!(rec.metadata["modality"], "text");
+    assert_eq!(rec.metadata["embedding"].as_array().unwrap().len(), 64);
+    assert!(rec.tags.contains(&"modality:text".to_string()));
+    assert_eq!(rec.integrity, Some(rec.compute_hash()));
+    assert_eq!(stored.embedding.len(), 64);
+
+    let hits = adapter
+        .search(&store, &text("a red ball"), None, 10)
+        .unwrap();
+    // The image lives in another space, so only the two texts compete.
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].0.id, id);
+}
+
+/// Toy two-tower model: words and average colours map onto the same
+/// (red, green, blue) axes.
+struct ToyClipText;
+
+impl EmbeddingProvider for ToyClipText {
+    fn embed(&self, text: &str) -> Vec<f32> {
+        ["red", "green", "blue"]
+            .iter()
+            .map(|c| text.matches(c).count() as f32)
+            .collect()
+    }
+
+    fn dimension(&self) -> usize {
+        3
+    }
+
+    fn name(&self) -> &str {
+        "toy-clip"
+    }
+}
+
+struct ToyClipImage;
+
+impl ImageEncoder for ToyClipImage {
+    fn name(&self) -> &str {
+        "toy-clip"
+    }
+
+    fn encode(&self, bytes: &[u8]) -> anyhow::Result<Vec<f32>> {
+        let img = image::load_from_memory(bytes)?.to_rgb8();
+        let n = img.pixels().len() as f32;
+        Ok((0..3)
+            .map(|c| img.pixels().map(|p| p[c] as f32).sum::<f32>() / n)
+            .collect())
+    }
+}
+
+#[test]
+fn shared_space_encoders_enable_cross_modal_search() {
+    let adapter = PerceptionAdapter::new(PerceptionConfig::default())
+        .with_text_encoder(Arc::new(ToyClipText))
+        .with_image_encoder(Arc::new(ToyClipImage));
+    let mut store = MemoryStore::new_in_memory();
+    let (red, _) = adapter.store(&mut store, "cam", &png([250, 5, 5])).unwrap();
+    let (blue, _) = adapter.store(&mut store, "cam", &png([5, 5, 250])).unwrap();
+    adapter
+        .store(&mut store, "cam", &text("a blue note"))
+        .unwrap();
+
+    let hits = adapter
+        .search(&store, &text("something red"), Some(&[Modality::Image]), 5)
+        .unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].0.id, red);
+    let hits = adapter
+        .search(&store, &text("blue"), Some(&[Modality::Image]), 1)
+        .unwrap();
+    assert_eq!(hits[0].0.id, blue);
+
+    // With the default encoders text and images live in different spaces.
+    let plain = PerceptionAdapter::new(PerceptionConfig::default());
+    assert!(plain
+        .search(&store, &text("red"), Some(&[Modality::Image]), 5)
+        .unwrap()
+        .is_empty());
 }
@@ -0,0 +1,248 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::cognitive_gc::CognitiveGC;
+use hipcortex::cognitive_state::{CognitiveDelta, CognitiveHandle};
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::pitr::{self, AsOf};
+use hipcortex::self_model::calibration::CalibrationTracker;
+use hipcortex::self_model::SelfModel;
+use hipcortex::tx_log::{RecordChange, TxKind, TxLog};
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn rec(actor: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        "saw".into(),
+        target.into(),
+        serde_json::json!({}),
+    )
+}
+
+fn journaled_store() -> MemoryStore<InMemoryBackend> {
+    let mut store = MemoryStore::new_in_memory();
+    store.enable_change_journal();
+    store
+}
+
+/// Add `r` and log it as one tx, the way `CognitiveHandle` does.
+fn add_logged(store: &mut MemoryStore<InMemoryBackend>, log: &TxLog, r: MemoryRecord) -> u64 {
+    let id = r.id;
+    store.add(r).unwrap();
+    log.append_with_changes(TxKind::MemoryAdd, vec![id], "test", store.take_changes())
+}
+
+fn targets<'a>(records: impl IntoIterator<Item = &'a MemoryRecord>) -> Vec<String> {
+    records.into_iter().map(|r| r.target.clone()).collect()
+}
+
+#[test]
+fn journal_is_off_by_default_and_records_mutations_when_enabled() {
+    let mut store = MemoryStore::new_in_memory();
+    store.add(rec("a", "x")).unwrap();
+    assert!(store.take_changes().is_empty());
+
+    store.enable_change_journal();
+    let r = rec("a", "y");
+    let id = r.id;
+    store.add(r).unwrap();
+    store.update_record(id, Some("y2"), None, None, None, None).unwrap();
+    store.delete_by_id(id);
+    let changes = store.take_changes();
+    assert_eq!(changes.len(), 3);
+    assert!(matches!(&changes[0], RecordChange::Upsert { record } if record.target == "y"));
+    assert!(matches!(&changes[1], RecordChange::Upsert { record } if record.target == "y2"));
+    assert!(matches!(&changes[2], RecordChange::Delete { id: d } if *d == id));
+    assert!(store.take_changes().is_empty(), "take_changes drains");
+}
+
+#[test]
+fn state_as_of_replays_log_without_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = journaled_store();
+
+    let first = rec("a", "one");
+    let first_id = first.id;
+    let t1 = add_logged(&mut store, &log, first);
+    let t2 = add_logged(&mut store, &log, rec("a", "two"));
+    store.update_record(first_id, Some("one-edited"), None, None, None, None).unwrap();
+    let t3 = pitr::log_pending(&mut store, &log, TxKind::MemoryUpdate, "test").unwrap();
+    store.delete_by_id(first_id);
+    let t4 = pitr::log_pending(&mut store, &log, TxKind::MemoryDelete, "test").unwrap();
+
+    assert!(pitr::state_as_of(&log, AsOf::Tx(0)).unwrap().is_empty());
+    assert_eq!(targets(&pitr::state_as_of(&log, AsOf::Tx(t1)).unwrap()), ["one"]);
+    assert_eq!(targets(&pitr::state_as_of(&log, AsOf::Tx(t2)).unwrap()), ["one", "two"]);
+    assert_eq!(
+        targets(&pitr::state_as_of(&log, AsOf::Tx(t3)).unwrap()),
+        ["one-edited", "two"]
+    );
+    assert_eq!(targets(&pitr::state_as_of(&log, AsOf::Tx(t4)).unwrap()), ["two"]);
+    assert!(pitr::state_as_of(&log, AsOf::Tx(t4 + 1)).is_err(), "future tx rejected");
+}
+
+#[test]
+fn checkpoint_is_used_as_replay_base() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    // Records added before the journal was on are only recoverable via a checkpoint.
+    let mut store = MemoryStore::new_in_memory();
+    store.add(rec("a", "pre-journal")).unwrap();
+
+    let ckpt = pitr::checkpoint(&mut store, &log).unwrap();
+    assert_eq!(pitr::list_checkpoints(&log), vec![ckpt]);
+    let after = add_logged(&mut store, &log, rec("a", "post"));
+
+    assert_eq!(
+        targets(&pitr::state_as_of(&log, AsOf::Tx(ckpt)).unwrap()),
+        ["pre-journal"]
+    );
+    assert_eq!(
+        targets(&pitr::state_as_of(&log, AsOf::Tx(after)).unwrap()),
+        ["pre-journal", "post"]
+    );
+}
+
+#[test]
+fn prune_keeps_newest_checkpoints() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = journaled_store();
+    let txs: Vec<u64> = (0..3).map(|_| pitr::checkpoint(&mut store, &log).unwrap()).collect();
+    assert_eq!(pitr::prune_checkpoints(&log, 2).unwrap(), 1);
+    assert_eq!(pitr::list_checkpoints(&log), txs[1..].to_vec());
+}
+
+#[test]
+fn restore_rewinds_store_and_is_itself_replayable() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = journaled_store();
+    let t1 = add_logged(&mut store, &log, rec("a", "keep"));
+    add_logged(&mut store, &log, rec("a", "mistake"));
+    assert_eq!(store.all().len(), 2);
+
+    let report = pitr::restore(&mut store, &log, AsOf::Tx(t1)).unwrap();
+    assert_eq!(report.target_tx, t1);
+    assert_eq!((report.records_before, report.records_after), (2, 1));
+    assert_eq!(targets(store.all()), ["keep"]);
+
+    // Replaying through the Restore tx yields the restored state, not the pre-restore one.
+    let replayed = pitr::state_as_of(&log, AsOf::Tx(report.restore_tx)).unwrap();
+    assert_eq!(targets(&replayed), ["keep"]);
+    let entry = log.query_range(report.restore_tx, report.restore_tx).unwrap();
+    assert!(matches!(entry[0].kind, TxKind::Restore));
+}
+
+#[test]
+fn as_of_parses_tx_ids_and_timestamps() {
+    assert_eq!(AsOf::parse("42").unwrap(), AsOf::Tx(42));
+    assert_eq!(
+        AsOf::parse("1970-01-01T00:00:01Z").unwrap(),
+        AsOf::TimestampMs(1000)
+    );
+    assert!(AsOf::parse("yesterday").is_err());
+
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = journaled_store();
+    let t1 = add_logged(&mut store, &log, rec("a", "x"));
+    assert_eq!(pitr::resolve_tx(&log, AsOf::TimestampMs(0)).unwrap(), 0);
+    assert_eq!(pitr::resolve_tx(&log, AsOf::TimestampMs(u64::MAX)).unwrap(), t1);
+}
+
+#[test]
+fn cognitive_handle_attaches_changes_to_its_txs() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = Arc::new(TxLog::open(dir.path().join("tx.jsonl")).unwrap());
+    let handle = CognitiveHandle::new(
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())),
+        Arc::new(RwLock::new(WorldModelEnhanced::new())),
+        Arc::new(SelfModel::new()),
+        Some(log.clone()),
+        Arc::new(CoherenceChecker::new()),
+        Arc::new(CalibrationTracker::new()),
+        Arc::new(CognitiveGC::new()),
+    );
+    let t1 = handle
+        .transact(CognitiveDelta::AddMemory(rec("agent", "door open")), "agent")
+        .unwrap();
+    handle
+        .transact(CognitiveDelta::AddMemory(rec("agent", "door closed")), "agent")
+        .unwrap();
+
+    let past = pitr::store_as_of(&log, AsOf::Tx(t1)).unwrap();
+    assert_eq!(targets(past.all()), ["door open"]);
+    assert_eq!(handle.memory.lock().unwrap().all().len(), 2, "live store untouched");
+}
+
+#[test]
+fn restore_overwrites_records_resident_in_the_cold_tier() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = MemoryStore::new_in_memory()
+        .with_cold_tier(dir.path().join("cold"))
+        .unwrap();
+    store.enable_change_journal();
+    let r = rec("a", "v1");
+    let id = r.id;
+    let t1 = add_logged(&mut store, &log, r);
+    store.update_record(id, Some("v2"), None, None, None, None).unwrap();
+    pitr::log_pending(&mut store, &log, TxKind::MemoryUpdate, "test").unwrap();
+    store.demote_to_cold(&[id]).unwrap();
+    pitr::log_pending(&mut store, &log, TxKind::ArchiveRecord, "test");
+
+    pitr::restore(&mut store, &log, AsOf::Tx(t1)).unwrap();
+    let cold = store.cold_tier().unwrap();
+    assert!(cold.contains(id), "restored record stays cold");
+    assert_eq!(cold.get(id).unwrap().unwrap().target, "v1");
+    assert_eq!(cold.scan().unwrap().len(), 1, "old cold copy tombstoned");
+    drop(cold);
+    assert_eq!(store.find_by_id(id).unwrap().target, "v1");
+}
+
+#[test]
+fn start_journal_checkpoints_records_written_before_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut fresh = MemoryStore::new_in_memory();
+    assert_eq!(pitr::start_journal(&mut fresh, &log).unwrap(), None, "empty log and store");
+    assert!(fresh.change_journal_enabled());
+
+    let mut store = MemoryStore::new_in_memory();
+    store.add(rec("a", "pre-journal")).unwrap();
+    let ckpt = pitr::start_journal(&mut store, &log).unwrap().expect("checkpoint written");
+    assert!(store.change_journal_enabled());
+    assert_eq!(pitr::start_journal(&mut store, &log).unwrap(), None, "only the first time");
+    let after = add_logged(&mut store, &log, rec("a", "post"));
+    assert_eq!(
+        targets(&pitr::state_as_of(&log, AsOf::Tx(after)).unwrap()),
+        ["pre-journal", "post"]
+    );
+    assert_eq!(pitr::list_checkpoints(&log), vec![ckpt]);
+}
+
+#[test]
+fn targets_before_the_oldest_checkpoint_are_refused_after_unjournaled_txs() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let mut store = MemoryStore::new_in_memory();
+    let r = rec("a", "unlogged");
+    let id = r.id;
+    store.add(r).unwrap();
+    let blind = log.append(TxKind::MemoryAdd, vec![id], "test");
+    let ckpt = pitr::checkpoint(&mut store, &log).unwrap();
+
+    let err = pitr::state_as_of(&log, AsOf::Tx(blind)).unwrap_err();
+    assert!(err.contains("oldest checkpoint"), "{err}");
+    assert!(pitr::restore(&mut store, &log, AsOf::Tx(blind)).is_err());
+    assert_eq!(store.all().len(), 1, "refused restore leaves the store alone");
+    assert_eq!(
+        targets(&pitr::state_as_of(&log, AsOf::Tx(ckpt)).unwrap()),
+        ["unlogged"]
+    );
+}
@@ -12,6 +12,7 @@ fn store_trace_after_perception() {
         text: Some("trace".to_string()),
         embedding: None,
         image_data: None,
+        audio: None,
         tags: vec!["unit".to_string()],
     };
     let out = PerceptionAdapter::adapt(input.clone()).unwrap();
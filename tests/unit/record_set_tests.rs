@@ -0,0 +1,64 @@
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::record_set::RecordSet;
+use serde_json::json;
+
+fn rec(actor: &str, action: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        action.into(),
+        target.into(),
+        json!({}),
+    )
+}
+
+fn targets(records: Vec<&MemoryRecord>) -> Vec<&str> {
+    records.into_iter().map(|r| r.target.as_str()).collect()
+}
+
+#[test]
+fn indices_follow_ids_through_removes_and_updates() {
+    let mut set = RecordSet::new();
+    let a = rec("alice", "saw", "door");
+    let b = rec("bob", "saw", "window");
+    let c = rec("alice", "opened", "door");
+    let (a_id, b_id, c_id) = (a.id, b.id, c.id);
+    set.push(a);
+    set.push(b);
+    set.push(c);
+
+    assert!(set.remove(a_id).is_some());
+    assert_eq!(targets(set.by_actor("alice")), ["door"]);
+    assert_eq!(set.by_actor("alice")[0].id, c_id);
+    assert_eq!(set.by_id(b_id).unwrap().target, "window");
+
+    set.update(b_id, |r| r.actor = "alice".into());
+    assert_eq!(targets(set.by_actor("alice")), ["window", "door"]);
+    assert!(set.by_actor("bob").is_empty());
+    assert_eq!(targets(set.by_action("saw")), ["window"]);
+
+    let mut dup = set.by_id(c_id).unwrap().clone();
+    dup.target = "hatch".into();
+    set.push(dup);
+    assert_eq!(set.len(), 2);
+    assert_eq!(targets(set.by_target("hatch")), ["hatch"]);
+    assert!(set.by_target("door").is_empty());
+}
+
+#[test]
+fn clones_are_unaffected_by_later_writes() {
+    let mut set: RecordSet = (0..100).map(|i| rec("a", "noted", &format!("fact {i}"))).collect();
+    let first = set[0].id;
+    let frozen = set.clone();
+
+    set.update(first, |r| r.target = "changed".into());
+    set.retain(|r| r.target != "fact 50");
+    set.push(rec("b", "noted", "late"));
+
+    assert_eq!(frozen.len(), 100);
+    assert_eq!(frozen[0].target, "fact 0");
+    assert!(frozen.by_actor("b").is_empty());
+    assert_eq!(set.len(), 100);
+    assert_eq!(set[0].target, "changed");
+    assert_eq!(set.last().unwrap().target, "late");
+}
@@ -0,0 +1,90 @@
+use hipcortex::redaction_vault::RedactionVault;
+use hipcortex::safety_classifier::detect_spans;
+
+const KEY: [u8; 32] = [7u8; 32];
+
+#[test]
+fn tokens_are_deterministic_and_reversible() {
+    let vault = RedactionVault::new_in_memory(KEY);
+    let a = vault.tokenize("email", "bob@example.com").unwrap();
+    let b = vault.tokenize("EMAIL", "bob@example.com").unwrap();
+    assert_eq!(a, b);
+    assert!(a.starts_with("[[EMAIL:") && a.ends_with("]]"));
+    assert!(!a.chars().any(|c| c.is_ascii_digit()));
+    assert_ne!(a, vault.tokenize("EMAIL", "eve@example.com").unwrap());
+    assert_eq!(vault.len(), 2);
+    assert_eq!(
+        vault.reveal(&a).unwrap().as_deref(),
+        Some("bob@example.com")
+    );
+    // A different key gives different tokens for the same value.
+    let other = RedactionVault::new_in_memory([8u8; 32]);
+    assert_ne!(a, other.tokenize("EMAIL", "bob@example.com").unwrap());
+}
+
+#[test]
+fn token_is_an_hmac_under_a_derived_key() {
+    use hmac::{Hmac, Mac};
+    type HmacSha256 = Hmac<sha2::Sha256>;
+    let mac = |key: &[u8], parts: &[&[u8]]| {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(key).unwrap();
+        parts.iter().for_each(|p| mac.update(p));
+        mac.finalize().into_bytes()
+    };
+    let token_key = mac(&KEY, &[b"hipcortex-redaction-token"]);
+    let tag = mac(&token_key, &[b"EMAIL", &[0u8], b"bob@example.com"]);
+    let id: String = tag[..8]
+        .iter()
+        .flat_map(|b| [b >> 4, b & 0x0f])
+        .map(|n| (b'a' + n) as char)
+        .collect();
+    let vault = RedactionVault::new_in_memory(KEY);
+    assert_eq!(
+        vault.tokenize("EMAIL", "bob@example.com").unwrap(),
+        format!("[[EMAIL:{id}]]")
+    );
+}
+
+#[test]
+fn redact_then_rehydrate_round_trips() {
+    let vault = RedactionVault::new_in_memory(KEY);
+    let text = "mail bob@example.com or call 555-123-4567, SSN 123-45-6789";
+    let (redacted, n) = vault.redact(text, &detect_spans(text)).unwrap();
+    assert_eq!(n, 3);
+    assert!(!redacted.contains("bob@") && !redacted.contains("4567"));
+    assert!(detect_spans(&redacted).is_empty());
+    assert_eq!(vault.rehydrate(&redacted).unwrap(), text);
+    // Unknown tokens are left alone.
+    let foreign = "[[EMAIL:aaaaaaaaaaaaaaaa]]";
+    assert_eq!(vault.rehydrate(foreign).unwrap(), foreign);
+}
+
+#[test]
+fn vault_file_survives_reopen_and_rejects_wrong_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.jsonl");
+    let token = {
+        let vault = RedactionVault::open(&path, KEY).unwrap();
+        vault.tokenize("PHONE", "555-123-4567").unwrap()
+    };
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("555-123-4567"));
+
+    let vault = RedactionVault::open(&path, KEY).unwrap();
+    assert_eq!(
+        vault.reveal(&token).unwrap().as_deref(),
+        Some("555-123-4567")
+    );
+    // Re-tokenizing a known value does not append a duplicate entry.
+    vault.tokenize("PHONE", "555-123-4567").unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+    assert!(RedactionVault::open(&path, [9u8; 32]).is_err());
+}
+
+#[test]
+fn key_must_be_64_hex_chars() {
+    assert_eq!(RedactionVault::key_from_hex(&"07".repeat(32)).unwrap(), KEY);
+    assert!(RedactionVault::key_from_hex("abcd").is_err());
+    assert!(RedactionVault::key_from_hex(&"zz".repeat(32)).is_err());
+}
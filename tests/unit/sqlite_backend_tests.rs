@@ -0,0 +1,157 @@
+use hipcortex::memory_record::{MemoryRecord, MemoryType, RecordStatus};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::{MemoryBackend, RecordFilter};
+use hipcortex::sqlite_backend::SqliteBackend;
+use serde_json::json;
+
+fn rec(actor: &str, action: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        action.into(),
+        target.into(),
+        json!({"k": target}),
+    )
+}
+
+#[test]
+fn backend_round_trips_records_and_upserts_by_id() {
+    let mut backend = SqliteBackend::in_memory().unwrap();
+    let mut a = rec("alice", "wrote", "spec");
+    a.tags = vec!["design".into()];
+    let b = rec("bob", "read", "spec");
+    backend.append(&a).unwrap();
+    backend.append(&b).unwrap();
+
+    a.confidence = 0.4;
+    assert!(backend.update(&a).unwrap());
+    let loaded = backend.load().unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].id, a.id, "update keeps insertion order");
+    assert_eq!(loaded[0].confidence, 0.4);
+    assert_eq!(loaded[0].tags, vec!["design".to_string()]);
+    assert_eq!(loaded[0].metadata, json!({"k": "spec"}));
+
+    assert!(backend.delete(&[b.id]).unwrap());
+    assert_eq!(backend.load().unwrap().len(), 1);
+}
+
+#[test]
+fn backend_pushes_filters_into_sql() {
+    let mut backend = SqliteBackend::in_memory().unwrap();
+    let mut old = rec("alice", "deploy", "api");
+    old.timestamp -= chrono::Duration::days(2);
+    let mut quarantined = rec("alice", "deploy", "web");
+    quarantined.status = RecordStatus::Quarantine;
+    let mut belief = rec("bob", "believes", "api");
+    belief.record_type = MemoryType::Belief;
+    for r in [&old, &quarantined, &belief] {
+        backend.append(r).unwrap();
+    }
+
+    let ids = |f: RecordFilter, backend: &mut SqliteBackend| -> Vec<uuid::Uuid> {
+        backend
+            .query(&f)
+            .unwrap()
+            .unwrap()
+            .iter()
+            .map(|r| r.id)
+            .collect()
+    };
+    assert_eq!(
+        ids(RecordFilter::new().actor("alice"), &mut backend),
+        [old.id, quarantined.id]
+    );
+    assert_eq!(
+        ids(
+            RecordFilter::new().target("api").action("deploy"),
+            &mut backend
+        ),
+        [old.id]
+    );
+    assert_eq!(
+        ids(
+            RecordFilter::new().record_type(MemoryType::Belief),
+            &mut backend
+        ),
+        [belief.id]
+    );
+    assert_eq!(
+        ids(
+            RecordFilter::new().status(RecordStatus::Quarantine),
+            &mut backend
+        ),
+        [quarantined.id]
+    );
+    let day_ago = chrono::Utc::now() - chrono::Duration::days(1);
+    assert_eq!(
+        ids(RecordFilter::new().since(day_ago).limit(1), &mut backend),
+        [quarantined.id]
+    );
+    assert_eq!(
+        ids(RecordFilter::new().until(day_ago), &mut backend),
+        [old.id]
+    );
+}
+
+#[test]
+fn unflushed_rewrite_rolls_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.db");
+    let kept = rec("alice", "wrote", "spec");
+    {
+        let mut backend = SqliteBackend::new(&path).unwrap();
+        backend.append(&kept).unwrap();
+        backend.clear().unwrap();
+        backend.append(&rec("bob", "half", "written")).unwrap();
+        // Dropped before flush: the clear + append never commits.
+    }
+    let mut backend = SqliteBackend::new(&path).unwrap();
+    let loaded = backend.load().unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].id, kept.id);
+}
+
+#[test]
+fn store_updates_and_deletes_persist_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.db");
+    let (a, b, c) = (
+        rec("alice", "noted", "one"),
+        rec("bob", "noted", "two"),
+        rec("carol", "noted", "three"),
+    );
+    let (a_id, b_id, c_id) = (a.id, b.id, c.id);
+    {
+        let mut store = MemoryStore::new_sqlite(&path, 2).unwrap();
+        for r in [a, b, c] {
+            store.add(r).unwrap();
+        }
+        store
+            .update_record(a_id, Some("one, revised"), None, None, None, None)
+            .unwrap();
+        store.set_status(c_id, RecordStatus::Archived).unwrap();
+        assert_eq!(store.delete_by_actor("bob").unwrap(), vec![b_id]);
+
+        let pushed = store
+            .query(&RecordFilter::new().status(RecordStatus::Active))
+            .unwrap();
+        assert_eq!(pushed.len(), 1);
+        assert_eq!(pushed[0].target, "one, revised");
+    }
+
+    let mut store = MemoryStore::new_sqlite(&path, 1).unwrap();
+    let targets: Vec<&str> = store.all().iter().map(|r| r.target.as_str()).collect();
+    assert_eq!(targets, ["one, revised", "three"]);
+    assert_eq!(store.find_by_id(a_id).unwrap().version, 1);
+    assert_eq!(
+        store.find_by_id(c_id).unwrap().status,
+        RecordStatus::Archived
+    );
+
+    // delete_by_id reaches SQLite without a compaction.
+    assert!(store.delete_by_id(c_id));
+    drop(store);
+    let store = MemoryStore::new_sqlite(&path, 1).unwrap();
+    assert_eq!(store.all().len(), 1);
+}
@@ -0,0 +1,306 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::{CapabilityDescriptor, SelfModel};
+use hipcortex::state_bundle::{
+    export, import, read_bundle, write_bundle, BundleExportOptions, BundleImportOptions,
+    BundleMode, BundleTargets, StateBundle, BUNDLE_VERSION,
+};
+use hipcortex::symbolic_store::SymbolicStore;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use hipcortex::{CausalTopoGraph, EdgeType};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn record(actor: &str, target: &str, ns: Option<&str>) -> MemoryRecord {
+    let mut rec = MemoryRecord::new(
+        MemoryType::Symbolic,
+        actor.into(),
+        "said".into(),
+        target.into(),
+        json!({}),
+    );
+    if let Some(ns) = ns {
+        rec.tags.push(format!("ns:{ns}"));
+    }
+    rec
+}
+
+fn full_targets() -> BundleTargets<InMemoryBackend> {
+    let mut targets = BundleTargets::new(Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())));
+    targets.symbolic = Some(Arc::new(Mutex::new(SymbolicStore::new())));
+    targets.topology = Some(Arc::new(Mutex::new(CausalTopoGraph::new())));
+    targets.world = Some(Arc::new(RwLock::new(WorldModelEnhanced::new())));
+    targets.self_model = Some(Arc::new(SelfModel::new()));
+    targets
+}
+
+fn populated() -> BundleTargets<InMemoryBackend> {
+    let targets = full_targets();
+    {
+        let mut store = targets.memory.lock().unwrap();
+        store.add(record("alice", "hello", Some("acme"))).unwrap();
+        store.add(record("bob", "hi", None)).unwrap();
+    }
+    {
+        let mut graph = targets.symbolic.as_ref().unwrap().lock().unwrap();
+        let a = graph.add_node("Rust", HashMap::new());
+        let b = graph.add_node("Language", HashMap::new());
+        graph.add_edge(a, b, "is_a");
+    }
+    {
+        let mut topo = targets.topology.as_ref().unwrap().lock().unwrap();
+        topo.add_node("rain".into(), [0.0; 128], HashMap::new())
+            .unwrap();
+        topo.add_node("wet".into(), [0.0; 128], HashMap::new())
+            .unwrap();
+        topo.add_edge("rain".into(), "wet".into(), EdgeType::Causal, 0.9, 0.8)
+            .unwrap();
+    }
+    let wm = targets.world.as_ref().unwrap().read().unwrap();
+    wm.observe_transition("idle".into(), "start".into(), "running".into())
+        .unwrap();
+    wm.add_causal_edge("rain".into(), "wet".into()).unwrap();
+    drop(wm);
+    targets
+        .self_model
+        .as_ref()
+        .unwrap()
+        .upsert_capability(CapabilityDescriptor {
+            name: "summarize".into(),
+            description: "LLM summaries".into(),
+            required_cpu_percent: 5.0,
+            required_memory_mb: 64.0,
+            limitations: vec![],
+        })
+        .unwrap();
+    targets
+}
+
+#[test]
+fn round_trip_carries_every_section() {
+    let source = populated();
+    let bundle = export(&source, &BundleExportOptions::default()).unwrap();
+    assert_eq!(bundle.manifest.version, BUNDLE_VERSION);
+    assert_eq!(bundle.manifest.namespaces, ["acme"]);
+    let doc = bundle.to_json().unwrap();
+    assert_eq!(doc["manifest"]["sections"]["records"]["count"], 2);
+    // Every symbolic store carries its System:Self anchor: 3 nodes + 1 edge.
+    assert_eq!(doc["manifest"]["sections"]["symbolic"]["count"], 4);
+
+    let target = full_targets();
+    let report = import(&target, doc, &BundleImportOptions::default()).unwrap();
+    assert!(report.migrations.is_empty());
+    assert_eq!(report.records_added, 2);
+    // The target's own anchor is reused rather than duplicated.
+    assert_eq!((report.symbolic_nodes, report.symbolic_edges), (2, 1));
+    assert_eq!((report.topology_nodes, report.topology_edges), (2, 1));
+    assert!(report.world_model);
+
+    assert_eq!(target.memory.lock().unwrap().all().len(), 2);
+    let (nodes, edges) = target
+        .symbolic
+        .as_ref()
+        .unwrap()
+        .lock()
+        .unwrap()
+        .export_graph();
+    assert_eq!((nodes.len(), edges.len()), (3, 1));
+    let topo = target.topology.as_ref().unwrap().lock().unwrap();
+    assert_eq!(topo.get_neighbors("rain"), ["wet"]);
+    let wm = target.world.as_ref().unwrap().read().unwrap();
+    assert_eq!(wm.transition_count(), 1);
+    assert_eq!(wm.get_causal_edges().len(), 1);
+    let caps = target.self_model.as_ref().unwrap().capabilities().unwrap();
+    assert!(caps
+        .iter()
+        .any(|c| c.name == "summarize" && c.required_memory_mb == 64.0));
+}
+
+#[test]
+fn merging_twice_adds_nothing_new() {
+    let doc = export(&populated(), &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    let target = full_targets();
+    import(&target, doc.clone(), &BundleImportOptions::default()).unwrap();
+    let again = import(&target, doc, &BundleImportOptions::default()).unwrap();
+    assert_eq!((again.records_added, again.records_existing), (0, 2));
+    assert_eq!((again.symbolic_nodes, again.symbolic_edges), (0, 0));
+    assert_eq!((again.topology_nodes, again.topology_edges), (0, 0));
+    assert_eq!(target.memory.lock().unwrap().all().len(), 2);
+    // Counts are summed on merge, so the transition was observed twice.
+    assert_eq!(
+        target
+            .world
+            .as_ref()
+            .unwrap()
+            .read()
+            .unwrap()
+            .transition_count(),
+        2
+    );
+}
+
+#[test]
+fn replace_drops_state_missing_from_the_bundle() {
+    let doc = export(&populated(), &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    let target = populated();
+    target
+        .memory
+        .lock()
+        .unwrap()
+        .add(record("carol", "extra", None))
+        .unwrap();
+    let options = BundleImportOptions {
+        mode: BundleMode::Replace,
+        ..Default::default()
+    };
+    let report = import(&target, doc, &options).unwrap();
+    assert_eq!((report.records_added, report.records_removed), (2, 3));
+    assert_eq!(target.memory.lock().unwrap().all().len(), 2);
+    let (nodes, _) = target
+        .symbolic
+        .as_ref()
+        .unwrap()
+        .lock()
+        .unwrap()
+        .export_graph();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(
+        target
+            .world
+            .as_ref()
+            .unwrap()
+            .read()
+            .unwrap()
+            .transition_count(),
+        1
+    );
+}
+
+#[test]
+fn tampered_section_is_rejected_before_anything_is_written() {
+    let mut doc = export(&populated(), &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    doc["records"][0]["target"] = json!("edited");
+    let target = full_targets();
+    let err = import(&target, doc, &BundleImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"), "{err}");
+    assert!(target.memory.lock().unwrap().all().is_empty());
+}
+
+#[test]
+fn newer_versions_and_foreign_documents_are_refused() {
+    let mut doc = export(&full_targets(), &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    doc["manifest"]["version"] = json!(BUNDLE_VERSION + 1);
+    assert!(StateBundle::from_json(doc)
+        .unwrap_err()
+        .to_string()
+        .contains("newer"));
+    assert!(StateBundle::from_json(json!({"hello": "world"})).is_err());
+}
+
+#[test]
+fn legacy_record_export_is_migrated() {
+    let rec = record("alice", "legacy", None);
+    let legacy = json!({
+        "records": [{
+            "id": rec.id.to_string(),
+            "record_type": "Episodic",
+            "timestamp": rec.timestamp.to_rfc3339(),
+            "actor": "alice", "action": "said", "target": "legacy",
+        }],
+        "total": 1,
+        "exported_at": "2026-01-01T00:00:00+00:00",
+    });
+    let (bundle, migrations) = StateBundle::from_json(legacy).unwrap();
+    assert_eq!(migrations.len(), 1);
+    assert_eq!(bundle.records[0].id, rec.id);
+    assert_eq!(bundle.records[0].record_type, MemoryType::Temporal);
+    assert_eq!(
+        bundle.manifest.created_at.to_rfc3339(),
+        "2026-01-01T00:00:00+00:00"
+    );
+    assert!(bundle.symbolic.is_none());
+}
+
+#[test]
+fn namespaces_are_filtered_on_export_and_remapped_on_import() {
+    let source = populated();
+    let only_acme = BundleExportOptions {
+        namespace: Some("acme".into()),
+    };
+    let bundle = export(&source, &only_acme).unwrap();
+    assert_eq!(bundle.records.len(), 1);
+
+    let doc = export(&source, &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    let mut options = BundleImportOptions::default();
+    options
+        .namespace_map
+        .insert("acme".into(), "acme-staging".into());
+    options.namespace_map.insert("".into(), "shared".into());
+    let target = BundleTargets::new(Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())));
+    let report = import(&target, doc, &options).unwrap();
+    assert_eq!(report.records_remapped, 2);
+    assert_eq!(
+        report.sections_ignored,
+        ["symbolic", "topology", "world_model", "capabilities"]
+    );
+    let store = target.memory.lock().unwrap();
+    let alice = store.all().iter().find(|r| r.actor == "alice").unwrap();
+    let bob = store.all().iter().find(|r| r.actor == "bob").unwrap();
+    assert_eq!(alice.tags, ["ns:acme-staging"]);
+    assert_eq!(bob.tags, ["ns:shared"]);
+    assert_eq!(
+        alice.integrity.as_deref(),
+        Some(alice.compute_hash().as_str())
+    );
+}
+
+#[test]
+fn remapping_two_namespaces_onto_one_keeps_a_single_tag() {
+    let source = full_targets();
+    {
+        let mut rec = record("carol", "merge", Some("a"));
+        rec.tags.push("topic".into());
+        rec.tags.push("ns:b".into());
+        source.memory.lock().unwrap().add(rec).unwrap();
+    }
+    let doc = export(&source, &BundleExportOptions::default())
+        .unwrap()
+        .to_json()
+        .unwrap();
+    let mut options = BundleImportOptions::default();
+    options.namespace_map.insert("a".into(), "merged".into());
+    options.namespace_map.insert("b".into(), "merged".into());
+    let target = BundleTargets::new(Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())));
+    import(&target, doc, &options).unwrap();
+    let store = target.memory.lock().unwrap();
+    assert_eq!(store.all()[0].tags, ["ns:merged", "topic"]);
+}
+
+#[test]
+fn gzip_bundles_round_trip_through_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state.json.gz");
+    let bundle = export(&populated(), &BundleExportOptions::default()).unwrap();
+    write_bundle(&path, &bundle).unwrap();
+    let (read, _) = StateBundle::from_json(read_bundle(&path).unwrap()).unwrap();
+    assert_eq!(read.records.len(), 2);
+    assert_eq!(read.manifest.sections.len(), 5);
+}
@@ -0,0 +1,195 @@
+use hipcortex::memory_record::{MemoryRecord, MemoryType, Priority, RecordStatus};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::store_format::{
+    detect_version, migrate_file, FormatHeader, MigrateOptions, FORMAT_VERSION,
+    LEGACY_FORMAT_VERSION,
+};
+use serde_json::json;
+
+fn legacy_line(priority: serde_json::Value, status: serde_json::Value) -> String {
+    let mut rec = MemoryRecord::new(
+        MemoryType::Temporal,
+        "alice".into(),
+        "said".into(),
+        "hi".into(),
+        json!({}),
+    );
+    rec.integrity = Some("stale".into());
+    let mut v = serde_json::to_value(&rec).unwrap();
+    let obj = v.as_object_mut().unwrap();
+    for key in ["confidence", "version", "tags"] {
+        obj.remove(key);
+    }
+    obj.insert("priority".into(), priority);
+    obj.insert("status".into(), status);
+    v.to_string()
+}
+
+#[test]
+fn priority_and_status_parse_legacy_spellings() {
+    assert_eq!(Priority::parse_lenient(" HIGH "), Priority::High);
+    assert_eq!(Priority::parse_lenient("urgent"), Priority::High);
+    assert_eq!(Priority::parse_lenient(""), Priority::Normal);
+    assert_eq!(Priority::parse_lenient("whatever"), Priority::Normal);
+    assert_eq!(
+        RecordStatus::parse_lenient("Quarantined"),
+        RecordStatus::Quarantine
+    );
+    assert_eq!(
+        RecordStatus::parse_lenient("archvied"),
+        RecordStatus::Active
+    );
+    assert_eq!("Archive".parse(), Ok(RecordStatus::Archived));
+    assert!("archvied".parse::<RecordStatus>().is_err());
+    assert!(Priority::is_canonical("pinned") && !Priority::is_canonical("Pinned"));
+    assert!(Priority::Pinned > Priority::High && Priority::High > Priority::Low);
+
+    let rec: MemoryRecord =
+        serde_json::from_str(&legacy_line(json!("Pinned"), json!(null))).unwrap();
+    assert_eq!(
+        (rec.priority, rec.status),
+        (Priority::Pinned, RecordStatus::Active)
+    );
+    let out = serde_json::to_value(&rec).unwrap();
+    assert_eq!(
+        (&out["priority"], &out["status"]),
+        (&json!("pinned"), &json!("active"))
+    );
+}
+
+#[test]
+fn new_store_files_start_with_a_header_and_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    {
+        let mut store = MemoryStore::new(&path).unwrap();
+        let rec = MemoryRecord::new(
+            MemoryType::Symbolic,
+            "a".into(),
+            "b".into(),
+            "c".into(),
+            json!({}),
+        );
+        store.add(rec).unwrap();
+        store.flush().unwrap();
+    }
+    let content = std::fs::read_to_string(&path).unwrap();
+    let header = FormatHeader::parse(content.lines().next().unwrap()).unwrap();
+    assert_eq!(header.version, FORMAT_VERSION);
+    assert_eq!(detect_version(&path).unwrap(), FORMAT_VERSION);
+    let store = MemoryStore::new(&path).unwrap();
+    assert_eq!(store.all().len(), 1);
+}
+
+#[test]
+fn files_from_a_newer_build_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let header = FormatHeader {
+        version: FORMAT_VERSION + 1,
+        ..FormatHeader::current()
+    };
+    std::fs::write(&path, format!("{}\n", header.to_line())).unwrap();
+    let err = MemoryStore::new(&path)
+        .err()
+        .expect("newer format must not load");
+    assert!(err.to_string().contains("newer than this build"), "{err}");
+    assert!(migrate_file(&path, &MigrateOptions::default()).is_err());
+}
+
+#[test]
+fn legacy_file_is_migrated_in_place_with_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    let original = format!(
+        "{}\n{}\n{{\"id\":\"trunc\n",
+        legacy_line(json!("HIGH"), json!("quarantined")),
+        legacy_line(json!("normal"), json!("active")),
+    );
+    std::fs::write(&path, &original).unwrap();
+    assert_eq!(detect_version(&path).unwrap(), LEGACY_FORMAT_VERSION);
+
+    // Legacy files still load unmigrated (on a copy: crash recovery would
+    // rewrite the truncated trailing line away).
+    let copy = dir.path().join("copy.jsonl");
+    std::fs::copy(&path, &copy).unwrap();
+    assert_eq!(MemoryStore::new(&copy).unwrap().all().len(), 2);
+
+    let dry = migrate_file(
+        &path,
+        &MigrateOptions {
+            dry_run: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!((dry.records, dry.changed), (2, 2));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+    let report = migrate_file(&path, &MigrateOptions::default()).unwrap();
+    assert_eq!(
+        (report.from_version, report.to_version),
+        (1, FORMAT_VERSION)
+    );
+    assert_eq!(report.steps.len(), 1);
+    assert_eq!(
+        (report.resealed, report.dropped_lines.clone()),
+        (2, vec![3])
+    );
+    let backup = report.backup.unwrap();
+    assert_eq!(std::fs::read_to_string(backup).unwrap(), original);
+    assert_eq!(detect_version(&path).unwrap(), FORMAT_VERSION);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let header = FormatHeader::parse(content.lines().next().unwrap()).unwrap();
+    assert_eq!(header.migrated_from, Some(1));
+    let first: serde_json::Value = serde_json::from_str(content.lines().nth(1).unwrap()).unwrap();
+    assert_eq!(
+        (&first["priority"], &first["status"]),
+        (&json!("high"), &json!("quarantine"))
+    );
+    assert_eq!(first["confidence"], 1.0);
+    let rec: MemoryRecord = serde_json::from_value(first).unwrap();
+    assert_eq!(rec.integrity, Some(rec.compute_hash()));
+
+    // Already current: nothing rewritten, no second backup.
+    let again = migrate_file(&path, &MigrateOptions::default()).unwrap();
+    assert!(again.steps.is_empty() && again.backup.is_none() && again.written.is_none());
+}
+
+#[test]
+fn compressed_stores_are_not_migrated() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.jsonl");
+    std::fs::write(&path, "KLUv/QBYaGVsbG8=\n").unwrap();
+    let err = migrate_file(&path, &MigrateOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("compressed"), "{err}");
+}
+
+#[test]
+fn snapshots_carry_the_header_and_roll_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let snap = dir.path().join("snap.jsonl");
+    let mut store = MemoryStore::new_in_memory();
+    let rec = MemoryRecord::new(
+        MemoryType::Symbolic,
+        "a".into(),
+        "b".into(),
+        "c".into(),
+        json!({}),
+    );
+    store.add(rec).unwrap();
+    store.snapshot(&snap).unwrap();
+    assert_eq!(detect_version(&snap).unwrap(), FORMAT_VERSION);
+    store
+        .add(MemoryRecord::new(
+            MemoryType::Symbolic,
+            "x".into(),
+            "y".into(),
+            "z".into(),
+            json!({}),
+        ))
+        .unwrap();
+    store.rollback(&snap).unwrap();
+    assert_eq!(store.all().len(), 1);
+}
@@ -0,0 +1,211 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::cognitive_gc::CognitiveGC;
+use hipcortex::cognitive_state::{CognitiveDelta, CognitiveHandle};
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::consolidation::{consolidate_with_summarizer, mine_and_consolidate_with_summarizer, ConsolidationConfig};
+use hipcortex::archive_store::ArchiveStore;
+use hipcortex::llm_clients::LLMClient;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::payloads::BeliefPayload;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::calibration::CalibrationTracker;
+use hipcortex::self_model::SelfModel;
+use hipcortex::summarizer::{score_faithfulness, summarize_communities, SummaryMethod, Summarizer};
+use hipcortex::symbolic_store::{InMemoryGraph, SymbolicStore};
+use hipcortex::tx_log::TxLog;
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use uuid::Uuid;
+
+/// LLM stub that always answers with a fixed string.
+struct FixedClient(&'static str);
+
+impl LLMClient for FixedClient {
+    fn generate_response(&self, _prompt: &str) -> String {
+        self.0.to_string()
+    }
+}
+
+fn rec(actor: &str, action: &str, target: &str) -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        actor.into(),
+        action.into(),
+        target.into(),
+        serde_json::json!({}),
+    )
+}
+
+fn cluster() -> Vec<MemoryRecord> {
+    vec![
+        rec("robot", "opened", "kitchen door"),
+        rec("robot", "fetched", "coffee mug from kitchen"),
+        rec("robot", "delivered", "coffee mug to desk"),
+    ]
+}
+
+#[test]
+fn extractive_without_model_keeps_evidence() {
+    let records = cluster();
+    let sources: Vec<&MemoryRecord> = records.iter().collect();
+    let summary = Summarizer::extractive().summarize(&sources);
+    assert_eq!(summary.method, SummaryMethod::Extractive);
+    assert!(summary.fallback_reason.is_none());
+    assert!(summary.text.contains("coffee mug"));
+    assert_eq!(summary.faithfulness, 1.0);
+    assert_eq!(summary.evidence, records.iter().map(|r| r.id).collect::<Vec<_>>());
+}
+
+#[test]
+fn abstractive_summary_accepted_when_faithful() {
+    let records = cluster();
+    let sources: Vec<&MemoryRecord> = records.iter().collect();
+    let s = Summarizer::with_llm(Arc::new(FixedClient("Robot fetched coffee mug from kitchen, delivered to desk.")));
+    let summary = s.summarize(&sources);
+    assert_eq!(summary.method, SummaryMethod::Abstractive);
+    assert!(summary.faithfulness >= 0.99);
+}
+
+#[test]
+fn unfaithful_or_empty_model_output_falls_back() {
+    let records = cluster();
+    let sources: Vec<&MemoryRecord> = records.iter().collect();
+
+    let hallucinating = Summarizer::with_llm(Arc::new(FixedClient("Quarterly revenue exceeded forecasts.")));
+    let summary = hallucinating.summarize(&sources);
+    assert_eq!(summary.method, SummaryMethod::Extractive);
+    assert!(summary.fallback_reason.unwrap().contains("below threshold"));
+
+    let silent = Summarizer::with_llm(Arc::new(FixedClient("   ")));
+    let summary = silent.summarize(&sources);
+    assert_eq!(summary.method, SummaryMethod::Extractive);
+    assert!(summary.fallback_reason.unwrap().contains("empty"));
+}
+
+#[test]
+fn faithfulness_scores_unsupported_tokens() {
+    let sources = vec!["robot opened kitchen door".to_string()];
+    assert_eq!(score_faithfulness("robot opened door", &sources), 1.0);
+    assert_eq!(score_faithfulness("robot opened window", &sources), 2.0 / 3.0);
+    assert_eq!(score_faithfulness("", &sources), 0.0);
+}
+
+#[test]
+fn consolidate_with_summarizer_links_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = MemoryStore::new_in_memory();
+    let mut ids = Vec::new();
+    for r in cluster() {
+        ids.push(r.id);
+        store.add(r).unwrap();
+    }
+    let mut archive = ArchiveStore::new(dir.path().join("archive.jsonl"));
+    let mut graph: SymbolicStore<InMemoryGraph> = SymbolicStore::new();
+    let log = TxLog::open(dir.path().join("tx.jsonl")).unwrap();
+    let config = ConsolidationConfig { min_group_size: 3, ..Default::default() };
+
+    let s = Summarizer::extractive();
+    let report = consolidate_with_summarizer(&mut store, &mut archive, &mut graph, &log, &config, Some(&s)).unwrap();
+    assert_eq!(report.summary_records_created, 1);
+
+    let summary = store.all().iter().find(|r| r.action == "consolidated").unwrap();
+    let mut evidence = summary.evidence.clone();
+    evidence.sort();
+    ids.sort();
+    assert_eq!(evidence, ids);
+    assert_eq!(summary.metadata["summary_method"], "extractive");
+    assert!(summary.metadata["summary"].as_str().unwrap().contains("robot"));
+}
+
+#[test]
+fn mined_beliefs_get_summary_justification() {
+    let mut store = MemoryStore::new_in_memory();
+    for _ in 0..3 {
+        let a = rec("agent", "observe", "sensor");
+        let mut b = rec("agent", "act", "motor");
+        b.derived_from = Some(a.id);
+        store.add(a).unwrap();
+        store.add(b).unwrap();
+    }
+    let s = Summarizer::extractive();
+    let report = mine_and_consolidate_with_summarizer(&mut store, None, 3, "agent", Some(&s)).unwrap();
+    assert!(report.beliefs_induced > 0);
+    let belief = store.all().iter().find(|r| r.record_type == MemoryType::Belief).unwrap();
+    let payload: BeliefPayload = serde_json::from_value(belief.metadata.clone()).unwrap();
+    assert!(payload.justification.contains("observe"));
+    assert!(belief.metadata.get("faithfulness").is_some());
+}
+
+#[test]
+fn communities_are_summarized_from_linked_records() {
+    let mut store = MemoryStore::new_in_memory();
+    let mut graph: SymbolicStore<InMemoryGraph> = SymbolicStore::new();
+    let mut nodes = Vec::new();
+    for r in cluster() {
+        let mut props = HashMap::new();
+        props.insert("record_id".to_string(), r.id.to_string());
+        nodes.push(graph.add_node(&r.target, props));
+        store.add(r).unwrap();
+    }
+    graph.add_edge(nodes[0], nodes[1], "next");
+    graph.add_edge(nodes[1], nodes[2], "next");
+    let unlinked = graph.add_node("orphan", HashMap::new());
+    nodes.push(unlinked);
+
+    let out = summarize_communities(&store, &graph, &nodes, &Summarizer::extractive(), "summarizer");
+    assert_eq!(out.len(), 1, "community without records is skipped");
+    let (community, summary) = &out[0];
+    assert_eq!(community.len(), 3);
+    assert_eq!(summary.evidence.len(), 3);
+    assert!(summary.target.starts_with("summary: "));
+}
+
+#[test]
+fn summarize_delta_generates_summary_record() {
+    let handle = CognitiveHandle::new(
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::<InMemoryBackend>::new_in_memory())),
+        Arc::new(RwLock::new(WorldModelEnhanced::new())),
+        Arc::new(SelfModel::new()),
+        None,
+        Arc::new(CoherenceChecker::new()),
+        Arc::new(CalibrationTracker::new()),
+        Arc::new(CognitiveGC::new()),
+    )
+    .with_summarizer(Summarizer::with_llm(Arc::new(FixedClient("robot delivered coffee mug"))));
+
+    let ids: Vec<Uuid> = cluster()
+        .into_iter()
+        .map(|r| {
+            let id = r.id;
+            handle.transact(CognitiveDelta::AddMemory(r), "robot").unwrap();
+            id
+        })
+        .collect();
+    handle
+        .transact(CognitiveDelta::Summarize { source_ids: ids.clone() }, "robot")
+        .unwrap();
+
+    let ms = handle.memory.lock().unwrap();
+    assert!(ids.iter().all(|id| ms.find_by_id(*id).is_none()));
+    let summary = ms.all().iter().find(|r| r.action == "consolidated").unwrap();
+    assert_eq!(summary.target, "summary: robot delivered coffee mug");
+    assert_eq!(summary.evidence, ids);
+    assert_eq!(summary.metadata["summary_method"], "abstractive");
+}
+
+#[test]
+fn summarize_delta_rejects_unknown_sources() {
+    let handle = CognitiveHandle::new(
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::<InMemoryBackend>::new_in_memory())),
+        Arc::new(RwLock::new(WorldModelEnhanced::new())),
+        Arc::new(SelfModel::new()),
+        None,
+        Arc::new(CoherenceChecker::new()),
+        Arc::new(CalibrationTracker::new()),
+        Arc::new(CognitiveGC::new()),
+    );
+    let err = handle.transact(CognitiveDelta::Summarize { source_ids: vec![Uuid::new_v4()] }, "a");
+    assert!(err.is_err());
+}
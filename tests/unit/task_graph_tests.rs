@@ -0,0 +1,159 @@
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::executive_scheduler::ExecutiveScheduler;
+use hipcortex::memory_record::MemoryType;
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::payloads::{GoalPayload, GoalStatus};
+use hipcortex::self_model::SelfModel;
+use hipcortex::task_graph::{Planner, TaskGraph, TaskNode, TaskState};
+use hipcortex::world_model_enhanced::{InvariantType, MetaLaw, MetaLawEngine};
+use std::collections::HashMap;
+
+fn beliefs(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Two routes to `at=c`: a direct hop costing 5 and a two-hop detour
+/// costing 2; greedy "fewest unmet goals" search would take the hop.
+fn routes() -> Vec<TaskNode> {
+    vec![
+        TaskNode::new("hop a->c", 5.0)
+            .with_precondition("at", "a")
+            .with_effect("at", "c"),
+        TaskNode::new("walk a->b", 1.0)
+            .with_precondition("at", "a")
+            .with_effect("at", "b"),
+        TaskNode::new("walk b->c", 1.0)
+            .with_precondition("at", "b")
+            .with_effect("at", "c"),
+    ]
+}
+
+#[test]
+fn astar_returns_the_cheapest_plan() {
+    let plan = Planner::new(routes())
+        .search(&beliefs(&[("at", "a")]), &beliefs(&[("at", "c")]))
+        .unwrap();
+    let names: Vec<&str> = plan.steps.iter().map(|s| s.summary.as_str()).collect();
+    assert_eq!(names, ["walk a->b", "walk b->c"]);
+    assert_eq!(plan.cost, 2.0);
+
+    let graph = TaskGraph::solve_planning_problem(
+        beliefs(&[("at", "a")]),
+        beliefs(&[("at", "c")]),
+        &routes(),
+    )
+    .unwrap();
+    assert_eq!(graph.graph.node_count(), 2);
+    assert_eq!(graph.graph.edge_count(), 1);
+
+    let err = Planner::new(routes())
+        .search(&beliefs(&[("at", "a")]), &beliefs(&[("at", "z")]))
+        .unwrap_err();
+    assert!(err.contains("No planning path"));
+}
+
+#[test]
+fn expansion_budget_is_configurable() {
+    // A counter with 200 reachable values; the goal is at the far end.
+    let actions: Vec<TaskNode> = (0..200)
+        .map(|i| {
+            TaskNode::new(&format!("inc {i}"), 1.0)
+                .with_precondition("n", &i.to_string())
+                .with_effect("n", &(i + 1).to_string())
+        })
+        .collect();
+    let (start, goal) = (beliefs(&[("n", "0")]), beliefs(&[("n", "200")]));
+    let plan = Planner::new(actions.clone()).search(&start, &goal).unwrap();
+    assert_eq!(plan.steps.len(), 200);
+    let err = Planner::new(actions)
+        .with_max_expansions(50)
+        .search(&start, &goal)
+        .unwrap_err();
+    assert!(err.contains("expansion limit"));
+}
+
+#[test]
+fn metalaws_prune_search_and_flag_invalid_plans() {
+    // Spending the reserve is cheaper, but fuel may never go below 1.
+    let actions = vec![
+        TaskNode::new("burn reserve", 1.0)
+            .with_precondition("fuel", "2")
+            .with_effect("fuel", "0")
+            .with_effect("done", "yes"),
+        TaskNode::new("refuel and go", 3.0)
+            .with_precondition("fuel", "2")
+            .with_effect("fuel", "2")
+            .with_effect("done", "yes"),
+    ];
+    let mut laws = MetaLawEngine::new();
+    laws.add_law(MetaLaw {
+        law_id: "fuel_floor".into(),
+        invariant_type: InvariantType::Bounded {
+            metric_index: 0,
+            min: 1.0,
+            max: 10.0,
+        },
+    });
+    let start = beliefs(&[("fuel", "2"), ("done", "no")]);
+    let goal = beliefs(&[("done", "yes")]);
+
+    let unconstrained = Planner::new(actions.clone()).search(&start, &goal).unwrap();
+    assert_eq!(unconstrained.steps[0].summary, "burn reserve");
+
+    let planner = Planner::new(actions).with_laws(laws, vec!["fuel".into()]);
+    let errors = planner
+        .validate(&start, &goal, &unconstrained.steps)
+        .unwrap_err();
+    assert_eq!(errors, ["step 0 `burn reserve`: violates fuel_floor"]);
+
+    let plan = planner.search(&start, &goal).unwrap();
+    assert_eq!(plan.steps[0].summary, "refuel and go");
+    assert!(planner.validate(&start, &goal, &plan.steps).is_ok());
+
+    let errors = planner
+        .validate(&beliefs(&[("fuel", "1")]), &goal, &plan.steps)
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("precondition fuel=2 unmet (is 1)"));
+    assert_eq!(
+        planner.validate(&start, &goal, &[]).unwrap_err(),
+        ["goal done=yes not reached"]
+    );
+}
+
+#[test]
+fn persisted_plans_reload_into_the_scheduler() {
+    let goal = beliefs(&[("at", "c")]);
+    let plan = Planner::new(routes())
+        .search(&beliefs(&[("at", "a")]), &goal)
+        .unwrap();
+    let mut store = MemoryStore::new_in_memory();
+    let saved = plan.persist(&mut store, "planner", &goal).unwrap();
+
+    let goal_rec = store.find_by_id(saved.goal_id).unwrap();
+    assert_eq!(goal_rec.record_type, MemoryType::Goal);
+    let payload: GoalPayload = serde_json::from_value(goal_rec.metadata.clone()).unwrap();
+    assert_eq!(payload.status, GoalStatus::Pending);
+    assert_eq!(payload.acceptance_criteria, ["at=c"]);
+    assert_eq!(goal_rec.evidence, saved.step_ids);
+    assert_eq!(store.all_by_type(MemoryType::Skill).len(), 2);
+
+    let graph = TaskGraph::load_plan(&store, saved.goal_id).unwrap();
+    let mut scheduler = ExecutiveScheduler::new(4);
+    scheduler.adopt_plan(graph, saved.goal_id).unwrap();
+    assert_eq!(scheduler.goal_stack[0].task_id, saved.goal_id);
+
+    let (self_model, coherence) = (SelfModel::new(), CoherenceChecker::new());
+    scheduler.tick(&self_model, &coherence).unwrap();
+    assert_eq!(scheduler.goal_stack.len(), 1);
+    scheduler.tick(&self_model, &coherence).unwrap();
+    assert!(scheduler.goal_stack.is_empty());
+    let graph = scheduler.active_graph.as_ref().unwrap();
+    assert!(graph
+        .graph
+        .node_weights()
+        .all(|t| t.state == TaskState::Completed));
+}
@@ -0,0 +1,138 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
+use hipcortex::cognitive_gc::CognitiveGC;
+use hipcortex::cognitive_state::{CognitiveDelta, CognitiveHandle};
+use hipcortex::coherence::CoherenceChecker;
+use hipcortex::llm_clients::instrumented::InstrumentedLLMClient;
+use hipcortex::llm_clients::mock::MockClient;
+use hipcortex::llm_clients::LLMClient;
+use hipcortex::memory_record::{MemoryRecord, MemoryType};
+use hipcortex::memory_store::MemoryStore;
+use hipcortex::persistence::InMemoryBackend;
+use hipcortex::self_model::calibration::CalibrationTracker;
+use hipcortex::self_model::SelfModel;
+use hipcortex::telemetry::{self, MetricsRegistry, OtlpExporter, METRICS};
+use hipcortex::world_model_enhanced::WorldModelEnhanced;
+use std::sync::{Arc, Mutex, RwLock};
+use tracing_subscriber::layer::SubscriberExt;
+
+fn make_handle() -> CognitiveHandle<InMemoryBackend> {
+    CognitiveHandle::new(
+        Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory())),
+        Arc::new(RwLock::new(WorldModelEnhanced::new())),
+        Arc::new(SelfModel::new()),
+        None,
+        Arc::new(CoherenceChecker::new()),
+        Arc::new(CalibrationTracker::new()),
+        Arc::new(CognitiveGC::new()),
+    )
+}
+
+fn rec() -> MemoryRecord {
+    MemoryRecord::new(
+        MemoryType::Temporal,
+        "agent".into(),
+        "did".into(),
+        "thing".into(),
+        serde_json::json!({}),
+    )
+}
+
+#[test]
+fn registry_renders_prometheus_histogram_and_counter() {
+    let reg = MetricsRegistry::new();
+    reg.observe(telemetry::MEMORY_ADD_SECONDS, &[], 0.003);
+    reg.observe(telemetry::MEMORY_ADD_SECONDS, &[], 20.0);
+    reg.inc(telemetry::COHERENCE_GATE_TOTAL, &[("decision", "accept")], 1.0);
+    let text = reg.render_prometheus();
+    assert!(text.contains("# TYPE hipcortex_memory_add_duration_seconds histogram"));
+    assert!(text.contains("hipcortex_memory_add_duration_seconds_bucket{le=\"0.005\"} 1"));
+    assert!(text.contains("hipcortex_memory_add_duration_seconds_bucket{le=\"+Inf\"} 2"));
+    assert!(text.contains("hipcortex_memory_add_duration_seconds_count 2"));
+    assert!(text.contains("hipcortex_coherence_gate_total{decision=\"accept\"} 1"));
+}
+
+#[test]
+fn memory_store_add_and_search_record_latency() {
+    let before_add = METRICS
+        .histogram(telemetry::MEMORY_ADD_SECONDS, &[])
+        .map(|h| h.count())
+        .unwrap_or(0);
+    let before_search = METRICS
+        .histogram(telemetry::MEMORY_SEARCH_SECONDS, &[])
+        .map(|h| h.count())
+        .unwrap_or(0);
+    let mut store = MemoryStore::new_in_memory();
+    store.add(rec()).unwrap();
+    let _ = store.search_semantic(None, "thing", 5, false);
+    let after_add = METRICS.histogram(telemetry::MEMORY_ADD_SECONDS, &[]).unwrap().count();
+    let after_search = METRICS.histogram(telemetry::MEMORY_SEARCH_SECONDS, &[]).unwrap().count();
+    assert!(after_add > before_add);
+    assert!(after_search > before_search);
+}
+
+#[test]
+fn transact_records_gate_and_latency() {
+    let handle = make_handle();
+    let before = METRICS.counter(telemetry::COHERENCE_GATE_TOTAL, &[("decision", "accept")]);
+    handle.transact(CognitiveDelta::AddMemory(rec()), "agent").unwrap();
+    let after = METRICS.counter(telemetry::COHERENCE_GATE_TOTAL, &[("decision", "accept")]);
+    assert!(after >= before + 1.0);
+    let hist = METRICS
+        .histogram(
+            telemetry::TRANSACT_SECONDS,
+            &[("delta", "AddMemory"), ("outcome", "ok")],
+        )
+        .expect("transact histogram populated");
+    assert!(hist.count() >= 1);
+}
+
+#[test]
+fn fork_increments_counter() {
+    let handle = make_handle();
+    let before = METRICS.counter(telemetry::FORKS_TOTAL, &[]);
+    let _fork = handle.fork().unwrap();
+    assert!(METRICS.counter(telemetry::FORKS_TOTAL, &[]) >= before + 1.0);
+}
+
+#[test]
+fn instrumented_llm_client_records_tokens() {
+    let client = InstrumentedLLMClient::new(MockClient, "mock-telemetry");
+    let out = client.generate_response("abcdefgh");
+    assert!(!out.is_empty());
+    let prompt = METRICS.counter(
+        telemetry::LLM_TOKENS_TOTAL,
+        &[("provider", "mock-telemetry"), ("kind", "prompt")],
+    );
+    assert_eq!(prompt, 2.0);
+    assert!(METRICS
+        .histogram(telemetry::LLM_CALL_SECONDS, &[("provider", "mock-telemetry")])
+        .is_some());
+}
+
+#[test]
+fn transact_span_exported_to_collector_stub() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/v1/traces")
+        .match_body(mockito::Matcher::Regex("cognitive.transact".into()))
+        .with_status(200)
+        .create();
+
+    let (exporter, layer) = OtlpExporter::new(server.url(), "hipcortex-test");
+    let subscriber = tracing_subscriber::registry().with(layer);
+    let handle = make_handle();
+    tracing::subscriber::with_default(subscriber, || {
+        handle.transact(CognitiveDelta::AddMemory(rec()), "agent").unwrap();
+    });
+
+    assert!(exporter.pending() >= 1);
+    let body = exporter.encode(&[]);
+    assert_eq!(
+        body["resourceSpans"][0]["resource"]["attributes"][0]["value"]["stringValue"],
+        "hipcortex-test"
+    );
+    let sent = exporter.flush().unwrap();
+    assert!(sent >= 1);
+    assert_eq!(exporter.pending(), 0);
+    mock.assert();
+}
@@ -1,4 +1,4 @@
-use hipcortex::vision_encoder::VisionEncoder;
+use hipcortex::vision_encoder::{ImagePreprocess, VisionEncoder};
 use image::{DynamicImage, ImageOutputFormat, RgbImage};
 use std::io::Cursor;
 
@@ -79,3 +79,271 @@ fn l2_norm_and_entropy() {
     let ent = VisionEncoder::estimate_entropy(&normed);
     assert!(ent > 0.0);
 }
+
+#[test]
+fn preprocess_center_crops_and_normalizes_into_nchw() {
+    // 4x2: red in the left half, blue in the right; the 2x2 center crop keeps
+    // one column of each.
+    let img = RgbImage::from_fn(4, 2, |x, _| {
+        if x < 2 {
+            image::Rgb([255, 0, 0])
+        } else {
+            image::Rgb([0, 0, 255])
+        }
+    });
+    let pre = ImagePreprocess {
+        size: 2,
+        mean: [0.5, 0.5, 0.5],
+        std: [0.5, 0.5, 0.5],
+    };
+    let planes = pre.planes(&DynamicImage::ImageRgb8(img.clone()));
+    let expected = [
+        1.0, -1.0, 1.0, -1.0, // R
+        -1.0, -1.0, -1.0, -1.0, // G
+        -1.0, 1.0, -1.0, 1.0, // B
+    ];
+    assert_eq!(planes.len(), expected.len());
+    for (got, want) in planes.iter().zip(expected) {
+        assert!((got - want).abs() < 1e-3, "{planes:?}");
+    }
+
+    let images = vec![DynamicImage::ImageRgb8(img); 3];
+    let batch = pre.batch(&images);
+    assert_eq!(batch.len(), 3 * 3 * 2 * 2);
+    assert_eq!(&batch[12..24], planes.as_slice());
+    assert_eq!(ImagePreprocess::clip().size, 224);
+}
+
+#[cfg(feature = "onnx")]
+mod onnx {
+    use super::*;
+    use hipcortex::embedding_provider::{ClipTokenizer, EmbeddingProvider, OnnxTextEncoder};
+    use hipcortex::memory_store::MemoryStore;
+    use hipcortex::perception_adapter::{
+        ImageEncoder, Modality, PerceptInput, PerceptionAdapter, PerceptionConfig,
+    };
+    use hipcortex::vision_encoder::OnnxImageEncoder;
+    use prost::Message;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tract_onnx::pb;
+
+    fn value(name: &str, elem_type: i32) -> pb::ValueInfoProto {
+        pb::ValueInfoProto {
+            name: name.into(),
+            r#type: Some(pb::TypeProto {
+                value: Some(pb::type_proto::Value::TensorType(pb::type_proto::Tensor {
+                    elem_type,
+                    shape: None,
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn node(
+        op: &str,
+        inputs: &[&str],
+        output: &str,
+        attribute: Vec<pb::AttributeProto>,
+    ) -> pb::NodeProto {
+        pb::NodeProto {
+            op_type: op.into(),
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: vec![output.into()],
+            attribute,
+            ..Default::default()
+        }
+    }
+
+    fn weights(name: &str, dims: Vec<i64>, float_data: Vec<f32>) -> pb::TensorProto {
+        pb::TensorProto {
+            name: name.into(),
+            dims,
+            data_type: 1,
+            float_data,
+            ..Default::default()
+        }
+    }
+
+    /// Serialize a single-input, single-output graph to a temp .onnx file.
+    fn write_model(
+        dir: &tempfile::TempDir,
+        file: &str,
+        graph: pb::GraphProto,
+    ) -> std::path::PathBuf {
+        let model = pb::ModelProto {
+            ir_version: 8,
+            opset_import: vec![pb::OperatorSetIdProto {
+                domain: String::new(),
+                version: 13,
+            }],
+            graph: Some(graph),
+            ..Default::default()
+        };
+        let path = dir.path().join(file);
+        std::fs::write(&path, model.encode_to_vec()).unwrap();
+        path
+    }
+
+    /// Image tower: per-channel mean of the normalized pixels (identity
+    /// projection), so red and blue images point along different axes.
+    fn image_model(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let identity = vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        write_model(
+            dir,
+            "image.onnx",
+            pb::GraphProto {
+                node: vec![
+                    node("GlobalAveragePool", &["pixels"], "pooled", vec![]),
+                    node("Flatten", &["pooled"], "flat", vec![]),
+                    node("MatMul", &["flat", "proj"], "embeds", vec![]),
+                ],
+                initializer: vec![weights("proj", vec![3, 3], identity)],
+                input: vec![value("pixels", 1)],
+                output: vec![value("embeds", 1)],
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Text tower: mean of per-token rows; "red" and "blue" get the axes
+    /// the image tower uses for those colors, every other token zero.
+    fn text_model(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let mut table = vec![0.0; 4 * 3];
+        table[2 * 3] = 1.0; // red
+        table[3 * 3 + 2] = 1.0; // blue
+        let axes = pb::AttributeProto {
+            name: "axes".into(),
+            r#type: pb::attribute_proto::AttributeType::Ints as i32,
+            ints: vec![1],
+            ..Default::default()
+        };
+        let keepdims = pb::AttributeProto {
+            name: "keepdims".into(),
+            r#type: pb::attribute_proto::AttributeType::Int as i32,
+            i: 0,
+            ..Default::default()
+        };
+        write_model(
+            dir,
+            "text.onnx",
+            pb::GraphProto {
+                node: vec![
+                    node("Gather", &["table", "ids"], "tokens", vec![]),
+                    node("ReduceMean", &["tokens"], "embeds", vec![axes, keepdims]),
+                ],
+                initializer: vec![weights("table", vec![4, 3], table)],
+                input: vec![value("ids", 7)],
+                output: vec![value("embeds", 1)],
+                ..Default::default()
+            },
+        )
+    }
+
+    fn tokenizer() -> ClipTokenizer {
+        let vocab: HashMap<String, i64> = [
+            ("<|startoftext|>", 0),
+            ("<|endoftext|>", 1),
+            ("red</w>", 2),
+            ("blue</w>", 3),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let merges = [
+            ("r", "e"),
+            ("re", "d</w>"),
+            ("b", "l"),
+            ("bl", "u"),
+            ("blu", "e</w>"),
+        ]
+        .into_iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect();
+        let mut tok = ClipTokenizer::from_parts(vocab, merges).unwrap();
+        tok.context_len = 8;
+        tok
+    }
+
+    fn solid(rgb: [u8; 3]) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(12, 10, image::Rgb(rgb)))
+    }
+
+    fn png(rgb: [u8; 3]) -> PerceptInput {
+        let mut buf = Cursor::new(Vec::new());
+        solid(rgb)
+            .write_to(&mut buf, ImageOutputFormat::Png)
+            .unwrap();
+        PerceptInput {
+            modality: Modality::Image,
+            text: None,
+            embedding: None,
+            image_data: Some(buf.into_inner()),
+            audio: None,
+            tags: vec![],
+        }
+    }
+
+    fn text(t: &str) -> PerceptInput {
+        PerceptInput {
+            modality: Modality::Text,
+            text: Some(t.into()),
+            image_data: None,
+            ..png([0, 0, 0])
+        }
+    }
+
+    #[test]
+    fn onnx_image_encoder_batches_and_normalizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let encoder = OnnxImageEncoder::load(image_model(&dir), ImagePreprocess::imagenet(), "toy")
+            .unwrap()
+            .with_max_batch(2);
+        let images: Vec<DynamicImage> = [[250, 5, 5], [5, 250, 5], [5, 5, 250]]
+            .into_iter()
+            .map(solid)
+            .collect();
+        let embeds = encoder.encode_images(&images).unwrap();
+        assert_eq!(embeds.len(), 3);
+        for (i, e) in embeds.iter().enumerate() {
+            assert_eq!(e.len(), 3);
+            assert!((e.iter().map(|x| x * x).sum::<f32>().sqrt() - 1.0).abs() < 1e-4);
+            let argmax = (0..3).max_by(|&a, &b| e[a].total_cmp(&e[b])).unwrap();
+            assert_eq!(argmax, i);
+        }
+        assert_eq!((encoder.name(), encoder.space()), ("onnx-image", "toy"));
+        let bytes = png([250, 5, 5]).image_data.unwrap();
+        let single = encoder.encode(&bytes).unwrap();
+        assert!(single
+            .iter()
+            .zip(&embeds[0])
+            .all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+
+    #[test]
+    fn onnx_towers_in_one_space_search_images_by_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let image =
+            OnnxImageEncoder::load(image_model(&dir), ImagePreprocess::imagenet(), "toy").unwrap();
+        let text_tower = OnnxTextEncoder::load(text_model(&dir), tokenizer(), "toy").unwrap();
+        assert_eq!((text_tower.dimension(), text_tower.name()), (3, "toy"));
+        assert_eq!(text_tower.embed("red"), vec![1.0, 0.0, 0.0]);
+
+        let adapter = PerceptionAdapter::new(PerceptionConfig::default())
+            .with_text_encoder(Arc::new(text_tower))
+            .with_image_encoder(Arc::new(image));
+        let mut store = MemoryStore::new_in_memory();
+        let (red, _) = adapter.store(&mut store, "cam", &png([250, 5, 5])).unwrap();
+        let (blue, _) = adapter.store(&mut store, "cam", &png([5, 5, 250])).unwrap();
+        let hits = adapter
+            .search(&store, &text("something red"), Some(&[Modality::Image]), 2)
+            .unwrap();
+        assert_eq!(hits[0].0.id, red);
+        let hits = adapter
+            .search(&store, &text("blue"), Some(&[Modality::Image]), 1)
+            .unwrap();
+        assert_eq!(hits[0].0.id, blue);
+    }
+}
@@ -1,3 +1,4 @@
+use hipcortex::concurrent_memory_store::ConcurrentMemoryStore;
 use hipcortex::cognitive_gc::CognitiveGC;
 use hipcortex::cognitive_state::{CognitiveDelta, CognitiveHandle};
 use hipcortex::coherence::CoherenceChecker;
@@ -10,7 +11,7 @@ use hipcortex::world_model_enhanced::WorldModelEnhanced;
 use std::sync::{Arc, Mutex, RwLock};
 
 fn make_handle() -> CognitiveHandle<InMemoryBackend> {
-    let store = Arc::new(Mutex::new(MemoryStore::new_in_memory()));
+    let store = Arc::new(ConcurrentMemoryStore::new(MemoryStore::new_in_memory()));
     let wm = Arc::new(RwLock::new(WorldModelEnhanced::new()));
     let sm = Arc::new(SelfModel::new());
     let coherence = Arc::new(CoherenceChecker::new());